@@ -0,0 +1,379 @@
+//! Compiles the JSON files in `assets/data/` into typed Rust statics (see `src/data.rs`).
+//!
+//! The pages read the data straight from the generated statics, so the WASM bundle does no JSON parsing for them, and
+//! a malformed or misspelled data file fails the build instead of rendering an error at runtime. Project tags are
+//! normalized on the way (see `src/tags.rs`). It also checks the form schemas in `forms/` (see `src/contact_form.rs`),
+//! which are compiled in as they are, and scopes the page stylesheets in `assets/styling/` (see `src/scoped_css.rs`)
+//! into the statics of `src/styles.rs`, and reads the size and a tiny placeholder of each image in `assets/images/`
+//! into `src/images.rs`.
+
+use base64::Engine;
+use image::{DynamicImage, ImageDecoder, ImageReader};
+use serde::Deserialize;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::io::Cursor;
+use std::path::Path;
+
+#[allow(dead_code)]
+#[path = "src/contact_form.rs"]
+mod contact_form;
+
+#[path = "src/scoped_css.rs"]
+mod scoped_css;
+
+#[allow(dead_code)]
+#[path = "src/tags.rs"]
+mod tags;
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct About {
+    name: String,
+    title: String,
+    location: String,
+    bio: String,
+    skills: Vec<String>,
+    experience: Vec<Experience>,
+    interests: Vec<String>,
+    contact: Contact,
+    updated: String,
+    #[serde(default)]
+    photo: Option<Photo>,
+    #[serde(default)]
+    gallery: Vec<Photo>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Photo {
+    src: String,
+    alt: String,
+    #[serde(default)]
+    caption: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Experience {
+    company: String,
+    position: String,
+    duration: String,
+    description: String,
+    #[serde(default)]
+    start: Option<String>,
+    #[serde(default)]
+    end: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Contact {
+    email: String,
+    website: String,
+    github: String,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ProjectsFile {
+    projects: Vec<Project>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Project {
+    name: String,
+    description: String,
+    url: String,
+    tags: Vec<String>,
+}
+
+fn main() {
+    let mut form_ids = Vec::new();
+    for (path, source) in contact_form::FORM_FILES {
+        println!("cargo:rerun-if-changed={}", path);
+        let schema =
+            contact_form::FormSchema::parse(path, source).unwrap_or_else(|e| panic!("{}", e));
+        if form_ids.contains(&schema.id) {
+            panic!("{}: another form already has the id `{}`", path, schema.id);
+        }
+        form_ids.push(schema.id);
+    }
+    if !form_ids.iter().any(|id| id == contact_form::CONTACT_FORM) {
+        panic!(
+            "no form in forms/ has the id `{}`",
+            contact_form::CONTACT_FORM
+        );
+    }
+
+    let images = write_images();
+
+    let about: About = read_json("assets/data/about.json");
+    for photo in about.photo.iter().chain(&about.gallery) {
+        if !images.contains(&photo.src) {
+            panic!(
+                "assets/data/about.json: {} is not an image in assets/images/",
+                photo.src
+            );
+        }
+    }
+    let mut projects: ProjectsFile = read_json("assets/data/projects.json");
+
+    // The aliases come from the compiled-in site.toml
+    println!("cargo:rerun-if-changed=site.toml");
+    let mut normalizer = tags::Normalizer::new(tags::TagsConfig::compiled());
+    for project in &mut projects.projects {
+        let mut normalized: Vec<String> = Vec::new();
+        for tag in &project.tags {
+            let tag = normalizer.normalize(tag);
+            if !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+        }
+        project.tags = normalized;
+    }
+
+    let mut code = String::from("// Generated by build.rs from assets/data/. Do not edit.\n\n");
+    write_about(&mut code, &about);
+    write_projects(&mut code, &projects.projects);
+
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("data.rs");
+    fs::write(out, code).expect("failed to write the generated data module");
+
+    write_styles();
+    write_build_commit();
+}
+
+/// Width and height of the placeholders, at most; they are drawn blurred, so more detail is only more bytes.
+const PLACEHOLDER_SIZE: u32 = 16;
+
+/// Read the size of each PNG and JPEG under `assets/images/` and make its placeholder, as the `IMAGES` static. Returns
+/// the site-relative paths of the images.
+fn write_images() -> Vec<String> {
+    println!("cargo:rerun-if-changed=assets/images");
+    let mut files = Vec::new();
+    collect_images(Path::new("assets/images"), &mut files);
+    files.sort();
+
+    let mut code = String::from("// Generated by build.rs from assets/images/. Do not edit.\n\n");
+    code.push_str("pub static IMAGES: &[ImageInfo] = &[\n");
+    let mut srcs = Vec::new();
+    for path in files {
+        let name = path.display().to_string();
+        let bytes = fs::read(&path).unwrap_or_else(|e| panic!("{}: {}", name, e));
+        let image = decode_upright(&bytes).unwrap_or_else(|e| panic!("{}: {}", name, e));
+
+        let mut placeholder = Vec::new();
+        image
+            .thumbnail(PLACEHOLDER_SIZE, PLACEHOLDER_SIZE)
+            .to_rgb8()
+            .write_to(&mut Cursor::new(&mut placeholder), image::ImageFormat::Png)
+            .unwrap_or_else(|e| panic!("{}: {}", name, e));
+        let placeholder = format!(
+            "data:image/png;base64,{}",
+            base64::engine::general_purpose::STANDARD.encode(placeholder)
+        );
+
+        let src = format!("/{}", name.replace('\\', "/"));
+        let _ = writeln!(
+            code,
+            "    ImageInfo {{ src: {}, width: {}, height: {}, placeholder: {} }},",
+            literal(&src),
+            image.width(),
+            image.height(),
+            literal(&placeholder),
+        );
+        srcs.push(src);
+    }
+    code.push_str("];\n");
+
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("images.rs");
+    fs::write(out, code).expect("failed to write the generated images module");
+    srcs
+}
+
+fn collect_images(dir: &Path, files: &mut Vec<std::path::PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_images(&path, files);
+            continue;
+        }
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+        if matches!(extension.as_deref(), Some("png" | "jpg" | "jpeg")) {
+            files.push(path);
+        }
+    }
+}
+
+/// The image turned by its EXIF orientation, the way browsers draw it, like the generator's responsive copies.
+fn decode_upright(bytes: &[u8]) -> image::ImageResult<DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(bytes))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Pass the commit being built to the crate as `SITE_BUILD_COMMIT`, for the health reports.
+fn write_build_commit() {
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+    let commit = std::process::Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=SITE_BUILD_COMMIT={}", commit);
+}
+
+/// Scope each of the page stylesheets and compile them in as `ScopedStyle` statics.
+fn write_styles() {
+    let mut code = String::from("// Generated by build.rs from assets/styling/. Do not edit.\n\n");
+    let mut statics = Vec::new();
+    for name in scoped_css::STYLESHEETS {
+        let path = format!("assets/styling/{}.css", name);
+        println!("cargo:rerun-if-changed={}", path);
+        let source = fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+        let scope = scoped_css::scope_class(name, &source);
+        let css = scoped_css::scope(&source, &scope).unwrap_or_else(|e| panic!("{}: {}", path, e));
+
+        let constant = name.to_uppercase();
+        let _ = writeln!(
+            code,
+            "pub static {}: ScopedStyle = ScopedStyle {{ name: {}, scope: {}, css: {} }};\n",
+            constant,
+            literal(name),
+            literal(&scope),
+            literal(&css),
+        );
+        statics.push(format!("&{}", constant));
+    }
+    let _ = writeln!(
+        code,
+        "/// Every scoped stylesheet\npub static ALL: &[&ScopedStyle] = &[{}];",
+        statics.join(", ")
+    );
+
+    let out = Path::new(&env::var("OUT_DIR").expect("OUT_DIR is set by cargo")).join("styles.rs");
+    fs::write(out, code).expect("failed to write the generated styles module");
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &str) -> T {
+    println!("cargo:rerun-if-changed={}", path);
+    let source = fs::read_to_string(path).unwrap_or_else(|e| panic!("{}: {}", path, e));
+    serde_json::from_str(&source).unwrap_or_else(|e| panic!("{}: {}", path, e))
+}
+
+/// A `str` as a Rust string literal. `Debug` escapes quotes, backslashes and control characters the way Rust does.
+fn literal(text: &str) -> String {
+    format!("{:?}", text)
+}
+
+fn optional_literal(text: Option<&str>) -> String {
+    match text {
+        Some(text) => format!("Some({})", literal(text)),
+        None => "None".to_string(),
+    }
+}
+
+fn literals(items: &[String]) -> String {
+    let items: Vec<String> = items.iter().map(|item| literal(item)).collect();
+    format!("&[{}]", items.join(", "))
+}
+
+fn write_about(code: &mut String, about: &About) {
+    let experience: String = about
+        .experience
+        .iter()
+        .map(|exp| {
+            format!(
+                "        Experience {{ company: {}, position: {}, duration: {}, description: {}, start: {}, end: {} }},\n",
+                literal(&exp.company),
+                literal(&exp.position),
+                literal(&exp.duration),
+                literal(&exp.description),
+                optional_literal(exp.start.as_deref()),
+                optional_literal(exp.end.as_deref()),
+            )
+        })
+        .collect();
+
+    let _ = write!(
+        code,
+        "pub static ABOUT: About = About {{
+    name: {name},
+    title: {title},
+    location: {location},
+    bio: {bio},
+    skills: {skills},
+    experience: &[
+{experience}    ],
+    interests: {interests},
+    contact: AboutContact {{ email: {email}, website: {website}, github: {github} }},
+    updated: {updated},
+    photo: {photo},
+    gallery: &[
+{gallery}    ],
+}};
+
+",
+        name = literal(&about.name),
+        title = literal(&about.title),
+        location = literal(&about.location),
+        bio = literal(&about.bio),
+        skills = literals(&about.skills),
+        experience = experience,
+        interests = literals(&about.interests),
+        email = literal(&about.contact.email),
+        website = literal(&about.contact.website),
+        github = literal(&about.contact.github),
+        updated = literal(&about.updated),
+        photo = about.photo.as_ref().map_or_else(
+            || "None".to_string(),
+            |photo| format!("Some({})", photo_literal(photo))
+        ),
+        gallery = about
+            .gallery
+            .iter()
+            .map(|photo| format!("        {},\n", photo_literal(photo)))
+            .collect::<String>(),
+    );
+}
+
+fn photo_literal(photo: &Photo) -> String {
+    format!(
+        "Photo {{ src: {}, alt: {}, caption: {} }}",
+        literal(&photo.src),
+        literal(&photo.alt),
+        optional_literal(photo.caption.as_deref()),
+    )
+}
+
+fn write_projects(code: &mut String, projects: &[Project]) {
+    code.push_str("pub static PROJECTS: &[Project] = &[\n");
+    for project in projects {
+        let _ = writeln!(
+            code,
+            "    Project {{ name: {}, description: {}, url: {}, tags: {} }},",
+            literal(&project.name),
+            literal(&project.description),
+            literal(&project.url),
+            literals(&project.tags),
+        );
+    }
+    code.push_str("];\n");
+}
@@ -0,0 +1,104 @@
+//! Error reports from readers' browsers, posted to [`CLIENT_ERRORS_ENDPOINT`] by the island loader on hybrid pages.
+//!
+//! Only a sample of page views report (`[telemetry] sample_rate` in `site.toml`, none until the endpoint is deployed),
+//! and a report never holds anything about the reader: the page is a path without its query string, and messages are
+//! cut short and scrubbed of email addresses and long numbers by [`ClientErrorReport::scrubbed`] before the server
+//! stores them. There is no response body; the endpoint answers `204 No Content` either way, since the loader sends
+//! with `navigator.sendBeacon` and never reads it.
+
+use serde::{Deserialize, Serialize};
+
+/// Where the loader posts reports.
+pub const CLIENT_ERRORS_ENDPOINT: &str = "/api/client-errors";
+
+/// Longest message kept, in characters.
+pub const MAX_MESSAGE_LENGTH: usize = 300;
+
+/// What went wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientErrorKind {
+    /// An island didn't mount: the WASM didn't download, compile or start in time, or its mount function failed
+    Island,
+    /// An uncaught JavaScript exception or promise rejection on the page, including panics in mounted islands
+    Exception,
+    /// A page that doesn't exist was asked for, reported by the 404 page: `page` is the missing path and `message` the
+    /// path of the page on this site that linked to it, or empty
+    NotFound,
+}
+
+/// A single error in a reader's browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientErrorReport {
+    pub kind: ClientErrorKind,
+    /// Site path of the page, without query string or fragment
+    pub page: String,
+    /// For island failures, the loader's category: `network`, `compile` or `init`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// For island failures, the mount function of the island
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub island: Option<String>,
+    pub message: String,
+    /// For exceptions, the script the error was thrown in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+}
+
+impl ClientErrorReport {
+    /// The report with URLs reduced to their path, the message cut to [`MAX_MESSAGE_LENGTH`], and anything in it that
+    /// looks like an email address or a long number (a phone or an order number) replaced. The client already sends
+    /// reports in this shape; the server applies it again rather than trust that.
+    pub fn scrubbed(&self) -> Self {
+        Self {
+            kind: self.kind,
+            page: path_only(&self.page),
+            category: self
+                .category
+                .as_deref()
+                .map(|category| truncate(category, 20)),
+            island: self.island.as_deref().map(|island| truncate(island, 60)),
+            message: truncate(&scrub_text(&self.message), MAX_MESSAGE_LENGTH),
+            source: self.source.as_deref().map(path_only),
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+/// `url` without its origin, query string and fragment.
+fn path_only(url: &str) -> String {
+    let without_origin = match url.split_once("://") {
+        Some((_, rest)) => rest.find('/').map_or("/", |start| &rest[start..]),
+        None => url,
+    };
+    let path = without_origin.split(['?', '#']).next().unwrap_or_default();
+    truncate(path, 200)
+}
+
+fn scrub_text(text: &str) -> String {
+    text.split(' ')
+        .map(|word| {
+            let digits = word.chars().filter(char::is_ascii_digit).count();
+            if word.contains('@') && word.contains('.') {
+                "<email>"
+            } else if digits >= 6 {
+                "<number>"
+            } else {
+                word
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text.to_string(),
+    }
+}
@@ -0,0 +1,403 @@
+//! The contact form submission, posted to [`CONTACT_ENDPOINT`](crate::CONTACT_ENDPOINT).
+
+use crate::contact_form::{self, FieldKind, FormSchema, Validator};
+use crate::contact_reasons;
+use crate::phone::{self, PhoneError};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A submission of one of the site's forms: the contact form or another form in `forms/`. Field names match the
+/// `name` attributes of the form, so the same type deserializes a native form post and a JSON request.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContactRequest {
+    pub name: String,
+    pub email: String,
+    /// Id of the selected reason for contact
+    pub reason: String,
+    pub subject: String,
+    pub message: String,
+    /// ISO code of the country the phone number is dialled from
+    #[serde(default)]
+    pub phone_country: String,
+    /// Phone number as typed; empty when none was given
+    #[serde(default)]
+    pub phone: String,
+    /// Id of the form the submission was sent from (see `forms/`); empty for the contact form
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub form: String,
+    /// Values of the form's fields that aren't one of the above, by field name
+    #[serde(flatten)]
+    pub extra: BTreeMap<String, String>,
+}
+
+/// A file sent with a submission in one of the form's `file` fields. It travels next to the [`ContactRequest`], whose
+/// value for the field is the file name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Attachment {
+    /// Name of the form field it was sent in
+    pub field: String,
+    /// File name given by the sender
+    pub filename: String,
+    pub bytes: Vec<u8>,
+}
+
+/// Why a [`ContactRequest`] was rejected. Fields are named as in the form's schema in `forms/`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "problem", rename_all = "snake_case")]
+pub enum ValidationProblem {
+    Required {
+        field: String,
+    },
+    InvalidEmail {
+        field: String,
+    },
+    TooShort {
+        field: String,
+        min: usize,
+    },
+    TooLong {
+        field: String,
+        max: usize,
+    },
+    InvalidDate {
+        field: String,
+    },
+    DateNotInFuture {
+        field: String,
+    },
+    InvalidNumber {
+        field: String,
+    },
+    TooSmall {
+        field: String,
+        min: i64,
+    },
+    TooLarge {
+        field: String,
+        max: i64,
+    },
+    UnknownOption {
+        field: String,
+    },
+    UnknownReason {
+        reason: String,
+    },
+    InvalidPhone {
+        error: PhoneError,
+    },
+    UnknownForm {
+        form: String,
+    },
+    /// A file sent with the submission was turned away by the server's attachment scanners
+    RejectedAttachment {
+        field: String,
+        filename: String,
+        rejection: AttachmentRejection,
+    },
+}
+
+/// Why the server turned away a file sent with a submission.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum AttachmentRejection {
+    TooLarge {
+        max_bytes: u64,
+    },
+    /// The content isn't one of the `accepted` types, whatever its name says
+    TypeNotAccepted {
+        accepted: Vec<String>,
+    },
+    /// The content is a `detected` file, named without one of its `extensions`
+    ExtensionMismatch {
+        detected: String,
+        extensions: Vec<String>,
+    },
+    /// The virus scanner found `signature` in it
+    Infected {
+        signature: String,
+    },
+}
+
+impl ValidationProblem {
+    /// The field the problem is about.
+    pub fn field(&self) -> &str {
+        match self {
+            ValidationProblem::Required { field }
+            | ValidationProblem::InvalidEmail { field }
+            | ValidationProblem::TooShort { field, .. }
+            | ValidationProblem::TooLong { field, .. }
+            | ValidationProblem::InvalidDate { field }
+            | ValidationProblem::DateNotInFuture { field }
+            | ValidationProblem::InvalidNumber { field }
+            | ValidationProblem::TooSmall { field, .. }
+            | ValidationProblem::TooLarge { field, .. }
+            | ValidationProblem::UnknownOption { field }
+            | ValidationProblem::RejectedAttachment { field, .. } => field,
+            ValidationProblem::UnknownReason { .. } => "reason",
+            ValidationProblem::InvalidPhone { .. } => "phone",
+            ValidationProblem::UnknownForm { .. } => "form",
+        }
+    }
+}
+
+impl fmt::Display for ValidationProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationProblem::Required { field } => {
+                write!(f, "{} is required", contact_form::label(field))
+            }
+            ValidationProblem::InvalidEmail { .. } => {
+                write!(f, "Please enter a valid email address")
+            }
+            ValidationProblem::TooShort { field, min } => write!(
+                f,
+                "{} needs at least {} characters",
+                contact_form::label(field),
+                min
+            ),
+            ValidationProblem::TooLong { field, max } => write!(
+                f,
+                "{} can't be longer than {} characters",
+                contact_form::label(field),
+                max
+            ),
+            ValidationProblem::InvalidDate { field } => {
+                write!(f, "{} isn't a valid date", contact_form::label(field))
+            }
+            ValidationProblem::DateNotInFuture { field } => {
+                write!(f, "{} must be after today", contact_form::label(field))
+            }
+            ValidationProblem::InvalidNumber { field } => {
+                write!(f, "{} must be a whole number", contact_form::label(field))
+            }
+            ValidationProblem::TooSmall { field, min } => {
+                write!(f, "{} must be at least {}", contact_form::label(field), min)
+            }
+            ValidationProblem::TooLarge { field, max } => {
+                write!(
+                    f,
+                    "{} can't be more than {}",
+                    contact_form::label(field),
+                    max
+                )
+            }
+            ValidationProblem::UnknownOption { field } => {
+                write!(
+                    f,
+                    "Please choose a {} from the list",
+                    contact_form::label(field).to_lowercase()
+                )
+            }
+            ValidationProblem::UnknownReason { .. } => {
+                write!(f, "Please choose a reason for contact from the list")
+            }
+            ValidationProblem::InvalidPhone { error } => write!(f, "Phone number: {}", error),
+            ValidationProblem::UnknownForm { .. } => write!(f, "This form isn't accepted any more"),
+            ValidationProblem::RejectedAttachment {
+                filename,
+                rejection,
+                ..
+            } => write!(f, "{}: {}", filename, rejection),
+        }
+    }
+}
+
+impl fmt::Display for AttachmentRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttachmentRejection::TooLarge { max_bytes } => write!(
+                f,
+                "files can't be larger than {} MB",
+                max_bytes.div_ceil(1024 * 1024)
+            ),
+            AttachmentRejection::TypeNotAccepted { accepted } => {
+                write!(f, "only {} files are accepted", accepted.join(", "))
+            }
+            AttachmentRejection::ExtensionMismatch {
+                detected,
+                extensions,
+            } => write!(
+                f,
+                "the file is a {} file; please send it named with {}",
+                detected,
+                extensions.join(", ")
+            ),
+            AttachmentRejection::Infected { .. } => {
+                write!(f, "the virus scanner flagged this file, so it wasn't sent")
+            }
+        }
+    }
+}
+
+impl ContactRequest {
+    /// Value of the field called `name`, empty when it wasn't filled in.
+    pub fn value(&self, name: &str) -> &str {
+        match name {
+            "name" => &self.name,
+            "email" => &self.email,
+            "reason" => &self.reason,
+            "subject" => &self.subject,
+            "message" => &self.message,
+            "phone" => &self.phone,
+            contact_form::PHONE_COUNTRY => &self.phone_country,
+            _ => self.extra.get(name).map_or("", String::as_str),
+        }
+    }
+
+    pub fn set(&mut self, name: &str, value: String) {
+        match name {
+            "name" => self.name = value,
+            "email" => self.email = value,
+            "reason" => self.reason = value,
+            "subject" => self.subject = value,
+            "message" => self.message = value,
+            "phone" => self.phone = value,
+            contact_form::PHONE_COUNTRY => self.phone_country = value,
+            _ => {
+                self.extra.insert(name.to_string(), value);
+            }
+        }
+    }
+
+    /// The schema of the form the submission was sent from, if it's a known one.
+    pub fn schema(&self) -> Option<&'static FormSchema> {
+        FormSchema::all()
+            .iter()
+            .find(|schema| schema.is(&self.form))
+    }
+
+    /// Every problem with the submission against the fields of its form, in form order. Empty when it can be sent.
+    pub fn validate(&self) -> Vec<ValidationProblem> {
+        let Some(schema) = self.schema() else {
+            return vec![ValidationProblem::UnknownForm {
+                form: self.form.clone(),
+            }];
+        };
+        let mut problems = Vec::new();
+
+        for spec in &schema.fields {
+            let field = spec.name.clone();
+            let value = self.value(&spec.name).trim();
+
+            match spec.kind {
+                // Always submits one of its options, so anything else didn't come from the form
+                FieldKind::Reason => {
+                    if contact_reasons::reason(&self.reason).is_none() {
+                        problems.push(ValidationProblem::UnknownReason {
+                            reason: self.reason.clone(),
+                        });
+                    }
+                    continue;
+                }
+                FieldKind::Select => {
+                    if !spec.options.iter().any(|option| option.value == value) {
+                        problems.push(ValidationProblem::UnknownOption { field });
+                    }
+                    continue;
+                }
+                _ if value.is_empty() => {
+                    if spec.required {
+                        problems.push(ValidationProblem::Required { field });
+                    }
+                    continue;
+                }
+                FieldKind::Email if !value.contains('@') => {
+                    problems.push(ValidationProblem::InvalidEmail { field });
+                    continue;
+                }
+                FieldKind::Phone => {
+                    if let Some(Err(error)) = self.parse_phone() {
+                        problems.push(ValidationProblem::InvalidPhone { error });
+                        continue;
+                    }
+                }
+                FieldKind::Date if NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err() => {
+                    problems.push(ValidationProblem::InvalidDate { field });
+                    continue;
+                }
+                FieldKind::Number if value.parse::<i64>().is_err() => {
+                    problems.push(ValidationProblem::InvalidNumber { field });
+                    continue;
+                }
+                _ => {}
+            }
+
+            let length = value.chars().count();
+            for validator in &spec.validators {
+                let problem = match *validator {
+                    Validator::Email if !value.contains('@') => ValidationProblem::InvalidEmail {
+                        field: field.clone(),
+                    },
+                    Validator::MinLength(min) if length < min => ValidationProblem::TooShort {
+                        field: field.clone(),
+                        min,
+                    },
+                    Validator::MaxLength(max) if length > max => ValidationProblem::TooLong {
+                        field: field.clone(),
+                        max,
+                    },
+                    Validator::Min(min) if value.parse::<i64>().is_ok_and(|n| n < min) => {
+                        ValidationProblem::TooSmall {
+                            field: field.clone(),
+                            min,
+                        }
+                    }
+                    Validator::Max(max) if value.parse::<i64>().is_ok_and(|n| n > max) => {
+                        ValidationProblem::TooLarge {
+                            field: field.clone(),
+                            max,
+                        }
+                    }
+                    Validator::Future
+                        if NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                            .is_ok_and(|date| date <= chrono::Utc::now().date_naive()) =>
+                    {
+                        ValidationProblem::DateNotInFuture {
+                            field: field.clone(),
+                        }
+                    }
+                    _ => continue,
+                };
+                if !problems.contains(&problem) {
+                    problems.push(problem);
+                }
+            }
+        }
+
+        problems
+    }
+
+    /// Whether any problem is about the field called `name`.
+    pub fn has_problem(&self, name: &str) -> bool {
+        self.validate()
+            .iter()
+            .any(|problem| problem.field() == name)
+    }
+
+    /// The phone number in E.164 form, if one was given and it's valid.
+    pub fn normalized_phone(&self) -> Option<String> {
+        self.parse_phone()?.ok()
+    }
+
+    fn parse_phone(&self) -> Option<Result<String, PhoneError>> {
+        if self.phone.trim().is_empty() {
+            return None;
+        }
+        Some(phone::normalize(&self.phone_country, &self.phone))
+    }
+}
+
+/// The endpoint's answer to a JSON request. Native form posts get a redirect to
+/// [`CONTACT_THANKS_PATH`](crate::CONTACT_THANKS_PATH) instead.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ContactResponse {
+    /// The message was accepted for delivery; `redirect` is the page to show next
+    Accepted { redirect: String },
+    /// The request failed validation; nothing was sent
+    Invalid { problems: Vec<ValidationProblem> },
+    /// The message couldn't be delivered right now and may be retried after `retry_after_seconds`
+    Unavailable { retry_after_seconds: Option<u32> },
+}
@@ -0,0 +1,89 @@
+//! The read-only content API written by `generate_static --api`: the blog posts and the about page as JSON files next
+//! to the HTML, for clients that want the content without scraping pages. Every build also writes the post metadata
+//! to [`POSTS_JSON_PATH`].
+//!
+//! Files are static, so every document is complete on its own; there are no query parameters or pagination.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Site path of the post listing.
+pub const POSTS_INDEX_PATH: &str = "/api/posts/index.json";
+/// Site path of the about page data, the same document as `assets/data/about.json`.
+pub const ABOUT_PATH: &str = "/api/about.json";
+/// Site path of the post metadata, written by every build for the islands.
+pub const POSTS_JSON_PATH: &str = "/posts.json";
+
+/// Site path of the document for the post with `slug`.
+pub fn post_path(slug: &str) -> String {
+    format!("/api/posts/{}.json", slug)
+}
+
+/// `/api/posts/index.json`: every published post, oldest first.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostIndex {
+    pub posts: Vec<PostSummary>,
+}
+
+/// A post as listed in the index.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostSummary {
+    pub slug: String,
+    pub title: String,
+    pub published: NaiveDate,
+    /// Plain text, as shown on the blog index
+    pub summary: String,
+    /// Absolute URL of the post page
+    pub url: String,
+    /// Site path of the post's own document
+    pub api_path: String,
+}
+
+/// `/api/posts/<slug>.json`: a post with its content.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostDocument {
+    #[serde(flatten)]
+    pub summary: PostSummary,
+    /// Post HTML with absolute link and image URLs, so it renders correctly outside the site
+    pub body_html: String,
+}
+
+/// `/posts.json`: the metadata of every post on the blog index, oldest first, as made by
+/// [`posts::index`](crate::posts::index).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostsJson {
+    pub posts: Vec<PostMeta>,
+}
+
+/// What the site shows about a post outside the post itself.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PostMeta {
+    pub slug: String,
+    pub title: String,
+    /// Site-relative URL of the post page, e.g. `/blog/blog-post-1/`
+    pub path: String,
+    pub published: NaiveDate,
+    /// A teaser for a post that isn't out yet, with no tags, summary or reading time
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub coming_soon: bool,
+    pub tags: Vec<String>,
+    /// Plain text, as shown on the blog index
+    pub summary: String,
+    pub reading_minutes: u32,
+}
+
+impl PostMeta {
+    /// Publish date as shown to readers, e.g. `1 March 2025`.
+    pub fn date_label(&self) -> String {
+        self.published.format("%-d %B %Y").to_string()
+    }
+
+    /// How many years before `today` the post came out, by calendar year, e.g. `2 years ago`, for the
+    /// [`on_this_day`](crate::posts::on_this_day) list.
+    pub fn years_ago_label(&self, today: NaiveDate) -> String {
+        match today.year() - self.published.year() {
+            1 => "1 year ago".to_string(),
+            years => format!("{} years ago", years),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+//! Request and response types shared by the WASM client, the server endpoints and other apps reading the site.
+//!
+//! Both sides validate with the same code, and every error a client may have to show is a variant of an enum here
+//! with its own message, so nothing is passed around as a free-form string.
+
+pub mod client_errors;
+pub mod contact;
+pub mod content;
+pub mod reactions;
+
+pub use client_errors::{ClientErrorKind, ClientErrorReport};
+pub use contact::{
+    Attachment, AttachmentRejection, ContactRequest, ContactResponse, ValidationProblem,
+};
+pub use content::{PostDocument, PostIndex, PostMeta, PostSummary, PostsJson};
+pub use reactions::{Reaction, ReactionCounts, ReactionRequest, ReactionResponse};
@@ -0,0 +1,117 @@
+//! Anonymous reactions to blog posts, posted to [`REACTIONS_ENDPOINT`].
+//!
+//! Nobody signs in: the server counts a reaction once per visitor by hashing their IP address with the post and the
+//! reaction (see [`reactions`](crate::ssg::reactions)). A native form post of a [`ReactionRequest`] gets a redirect
+//! back to the post; a JSON request gets a [`ReactionResponse`]. `GET` with `?post=<slug>` answers with the current
+//! counts, which the island loads over the ones baked into the page at build time.
+//!
+//! The buttons only go on the posts with `[reactions] enabled` set in `site.toml`, as nothing answers at the endpoint
+//! on a static host: every page view would make a failing request, and the buttons would post to an error page.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../../site.toml");
+
+/// Where reactions are posted and counts read.
+pub const REACTIONS_ENDPOINT: &str = "/api/reactions";
+
+/// Whether the compiled-in `site.toml` turns reactions on, for the SPA. The static generator reads the same setting
+/// from the loaded [`ReactionsConfig`](crate::ssg::config::ReactionsConfig).
+pub fn enabled() -> bool {
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct SiteToml {
+        reactions: Reactions,
+    }
+
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct Reactions {
+        enabled: bool,
+    }
+
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(|| {
+        toml::from_str::<SiteToml>(SITE_TOML)
+            .map(|site| site.reactions.enabled)
+            .unwrap_or_default()
+    })
+}
+
+/// A reaction a reader can leave on a post.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reaction {
+    ThumbsUp,
+    Heart,
+    Tada,
+}
+
+impl Reaction {
+    /// Every reaction, in the order the buttons are shown.
+    pub const ALL: [Reaction; 3] = [Reaction::ThumbsUp, Reaction::Heart, Reaction::Tada];
+
+    /// Value submitted by the reaction's button.
+    pub fn id(self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "thumbs_up",
+            Reaction::Heart => "heart",
+            Reaction::Tada => "tada",
+        }
+    }
+
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "👍",
+            Reaction::Heart => "❤️",
+            Reaction::Tada => "🎉",
+        }
+    }
+
+    /// Name read out by screen readers instead of the emoji.
+    pub fn label(self) -> &'static str {
+        match self {
+            Reaction::ThumbsUp => "Like",
+            Reaction::Heart => "Love",
+            Reaction::Tada => "Celebrate",
+        }
+    }
+}
+
+/// A reader's reaction to the post with `post` as its slug. Field names match the form's, so the same type
+/// deserializes a native form post and a JSON request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReactionRequest {
+    pub post: String,
+    pub reaction: Reaction,
+}
+
+/// How many times each reaction was left on a post. Reactions nobody has left yet are missing.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ReactionCounts(pub BTreeMap<Reaction, u64>);
+
+impl ReactionCounts {
+    pub fn get(&self, reaction: Reaction) -> u64 {
+        self.0.get(&reaction).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, reaction: Reaction) {
+        *self.0.entry(reaction).or_default() += 1;
+    }
+}
+
+/// The endpoint's answer to a JSON request.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ReactionResponse {
+    /// The post's counts after the request; `counted` is false when this visitor had already left the reaction
+    Counted {
+        counts: ReactionCounts,
+        counted: bool,
+    },
+    /// No published post has the slug
+    UnknownPost { post: String },
+}
@@ -0,0 +1,77 @@
+//! Callouts: the boxed notes, warnings and tips set apart from the text around them.
+//!
+//! Post bodies write them as shortcodes (see [`shortcodes`](crate::shortcodes)), and hand-written views use the
+//! [`Note`](crate::components::Note), [`Warning`](crate::components::Warning) and [`Tip`](crate::components::Tip)
+//! components. Both produce the markup below, styled by the `.callout` rules in `main.css`:
+//!
+//! ```text
+//! <aside class="callout callout-note" role="note">
+//!     <p class="callout-title"><span aria-hidden="true">ℹ️</span> Note</p>
+//!     <div class="callout-body">…</div>
+//! </aside>
+//! ```
+
+/// The kinds of callout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalloutKind {
+    Note,
+    Warning,
+    Tip,
+}
+
+impl CalloutKind {
+    pub const ALL: [CalloutKind; 3] = [Self::Note, Self::Warning, Self::Tip];
+
+    /// Name in the shortcode, e.g. `{{< warning >}}`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Note => "note",
+            Self::Warning => "warning",
+            Self::Tip => "tip",
+        }
+    }
+
+    /// Title shown when the callout doesn't have its own.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Note => "Note",
+            Self::Warning => "Warning",
+            Self::Tip => "Tip",
+        }
+    }
+
+    /// Icon before the title, hidden from screen readers since the title says the same.
+    pub fn icon(self) -> &'static str {
+        match self {
+            Self::Note => "ℹ️",
+            Self::Warning => "⚠️",
+            Self::Tip => "💡",
+        }
+    }
+
+    pub fn class(self) -> &'static str {
+        match self {
+            Self::Note => "callout callout-note",
+            Self::Warning => "callout callout-warning",
+            Self::Tip => "callout callout-tip",
+        }
+    }
+}
+
+/// A callout around `body`, which is trusted HTML, for the generated pages. `title` is escaped.
+pub fn static_html(kind: CalloutKind, title: Option<&str>, body: &str) -> String {
+    let title = title.unwrap_or(kind.label());
+    format!(
+        r#"<aside class="{class}" role="note">
+    <p class="callout-title"><span aria-hidden="true">{icon}</span> {title}</p>
+    <div class="callout-body">{body}</div>
+</aside>"#,
+        class = kind.class(),
+        icon = kind.icon(),
+        title = title
+            .replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+        body = body.trim(),
+    )
+}
@@ -0,0 +1,157 @@
+//! Accessibility pass: every content image needs alt text.
+//!
+//! Decorative images are allowed, but they have to say so explicitly with an empty alt:
+//! - markdown images (`![](/assets/divider.svg)`) take the override from the file's frontmatter:
+//!   ```yaml
+//!   images:
+//!     /assets/divider.svg:
+//!       alt: ""
+//!   ```
+//! - data files need an `alt` (or `<field>_alt`) key next to any image field
+//! - generated HTML needs an `alt` attribute on every `<img>`, `alt=""` included
+
+use crate::{files_with_extension, Finding};
+use dioxus_site::content::split_frontmatter;
+use dioxus_site::ssg::html;
+use std::fs;
+use std::path::Path;
+
+/// Keys in data files whose values are treated as image paths.
+const IMAGE_KEYS: &[&str] = &["image", "avatar", "photo", "cover", "logo", "thumbnail"];
+
+pub fn check(
+    content_dir: &Path,
+    data_dir: &Path,
+    output_dir: &Path,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let mut findings = Vec::new();
+
+    for path in files_with_extension(content_dir, "md")? {
+        let source = fs::read_to_string(&path)?;
+        check_markdown(&path, &source, &mut findings)?;
+    }
+
+    for path in files_with_extension(data_dir, "json")? {
+        let data: serde_json::Value = serde_json::from_str(&fs::read_to_string(&path)?)?;
+        check_data(&path, &data, "", &mut findings);
+    }
+
+    for path in files_with_extension(output_dir, "html")? {
+        let source = fs::read_to_string(&path)?;
+        check_html(&path, &source, &mut findings);
+    }
+
+    Ok(findings)
+}
+
+fn check_markdown(
+    path: &Path,
+    source: &str,
+    findings: &mut Vec<Finding>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (frontmatter, body) = split_frontmatter(source);
+    let frontmatter: serde_yaml::Value = match frontmatter {
+        Some(raw) => serde_yaml::from_str(raw)?,
+        None => serde_yaml::Value::Null,
+    };
+    let has_override = |src: &str| frontmatter["images"][src]["alt"].is_string();
+
+    let mut in_code_block = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        for (alt, src) in markdown_images(line) {
+            if alt.trim().is_empty() && !has_override(src) {
+                findings.push(Finding::new(
+                    path,
+                    format!(
+                        "image `{src}` has no alt text (describe it, or mark it decorative with `images.\"{src}\".alt: \"\"` in the frontmatter)"
+                    ),
+                ));
+            }
+        }
+
+        check_html(path, line, findings);
+    }
+
+    Ok(())
+}
+
+/// Find `![alt](src "title")` images in a line of markdown, returning `(alt, src)` pairs.
+fn markdown_images(line: &str) -> Vec<(&str, &str)> {
+    let mut images = Vec::new();
+    let mut rest = line;
+
+    while let Some(start) = rest.find("![") {
+        let after = &rest[start + 2..];
+        let Some(alt_end) = after.find("](") else {
+            break;
+        };
+        let target = &after[alt_end + 2..];
+        let Some(target_end) = target.find(')') else {
+            break;
+        };
+
+        let src = target[..target_end]
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .trim_matches(|c| c == '<' || c == '>');
+        images.push((&after[..alt_end], src));
+        rest = &target[target_end + 1..];
+    }
+
+    images
+}
+
+fn check_data(path: &Path, value: &serde_json::Value, pointer: &str, findings: &mut Vec<Finding>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for key in IMAGE_KEYS {
+                if !map.get(*key).is_some_and(|v| v.is_string()) {
+                    continue;
+                }
+                let has_alt = map.get("alt").is_some_and(|v| v.is_string())
+                    || map
+                        .get(&format!("{key}_alt"))
+                        .is_some_and(|v| v.is_string());
+                if !has_alt {
+                    findings.push(Finding::new(
+                        path,
+                        format!(
+                            "`{pointer}/{key}` has no alt text (add \"alt\" or \"{key}_alt\", empty if decorative)"
+                        ),
+                    ));
+                }
+            }
+
+            for (key, child) in map {
+                check_data(path, child, &format!("{pointer}/{key}"), findings);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                check_data(path, child, &format!("{pointer}/{index}"), findings);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn check_html(path: &Path, source: &str, findings: &mut Vec<Finding>) {
+    for tag in html::tags(source, "img") {
+        if html::attr(tag, "alt").is_none() {
+            let src = html::attr(tag, "src").unwrap_or("?");
+            findings.push(Finding::new(
+                path,
+                format!("<img src=\"{src}\"> has no alt attribute (use alt=\"\" if decorative)"),
+            ));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! Asset references pass: everything the site points at has to exist in the generated output.
+//!
+//! - `asset!("/assets/...")` in the Rust sources, since the generated pages serve the same files from the same paths
+//! - `url(...)` in the stylesheets in the output, relative to the stylesheet
+//! - `src` and `href` attributes in the generated HTML, and every candidate in a `srcset`, relative to the page
+//!
+//! Only references within the site are checked; other origins, `data:`, `mailto:` and the like, and links to a
+//! fragment of the same page are skipped. Page links resolve the way the hosts serve them, so `/about` is satisfied by
+//! `about/index.html` or `about.html`.
+
+use crate::{files_with_extension, Finding};
+use dioxus_site::ssg::html;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Elements whose `src` or `href` is checked.
+const REFERENCING_TAGS: &[&str] = &[
+    "a", "link", "script", "img", "source", "iframe", "audio", "video", "track", "embed",
+];
+
+pub fn check(
+    src_dir: &Path,
+    output_dir: &Path,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let mut findings = Vec::new();
+
+    for path in files_with_extension(src_dir, "rs")? {
+        let source = fs::read_to_string(&path)?;
+        for reference in asset_macro_paths(&source) {
+            if resolve(output_dir, Path::new(""), reference).is_none() {
+                findings.push(Finding::new(
+                    &path,
+                    format!(
+                        "asset!(\"{}\") isn't in {} (is it excluded in [assets]?)",
+                        reference,
+                        output_dir.display()
+                    ),
+                ));
+            }
+        }
+    }
+
+    for path in files_with_extension(output_dir, "css")? {
+        let source = fs::read_to_string(&path)?;
+        let base = base_dir(output_dir, &path);
+        for reference in css_urls(&source) {
+            if is_local(reference) && resolve(output_dir, &base, reference).is_none() {
+                findings.push(Finding::new(
+                    &path,
+                    format!("url({}) doesn't match any file in the output", reference),
+                ));
+            }
+        }
+    }
+
+    for path in files_with_extension(output_dir, "html")? {
+        let source = fs::read_to_string(&path)?;
+        let base = base_dir(output_dir, &path);
+        for name in REFERENCING_TAGS {
+            for tag in html::tags(&source, name) {
+                let srcset = html::attr(tag, "srcset").unwrap_or_default();
+                let references = ["src", "href"]
+                    .into_iter()
+                    .filter_map(|attribute| Some((attribute, html::attr(tag, attribute)?)))
+                    .chain(srcset.split(',').filter_map(|candidate| {
+                        Some(("srcset", candidate.split_whitespace().next()?))
+                    }));
+                for (attribute, reference) in references {
+                    if is_local(reference) && resolve(output_dir, &base, reference).is_none() {
+                        findings.push(Finding::new(
+                            &path,
+                            format!(
+                                "<{} {}=\"{}\"> doesn't match any file in the output",
+                                name, attribute, reference
+                            ),
+                        ));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(findings)
+}
+
+/// Paths in every `asset!("...")` call in a Rust source file, leaving out comments.
+fn asset_macro_paths(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .flat_map(|line| {
+            line.match_indices("asset!(\"")
+                .filter_map(move |(start, call)| {
+                    let rest = &line[start + call.len()..];
+                    rest.find('"').map(|end| &rest[..end])
+                })
+        })
+        .collect()
+}
+
+/// The contents of every `url(...)` in a stylesheet, without quotes, leaving out comments.
+fn css_urls(source: &str) -> Vec<&str> {
+    let mut urls = Vec::new();
+    let mut rest = source;
+
+    loop {
+        let comment = rest.find("/*");
+        let url = rest.find("url(");
+        match (comment, url) {
+            (Some(comment), url) if url.is_none_or(|url| comment < url) => {
+                let Some(end) = rest[comment + 2..].find("*/") else {
+                    break;
+                };
+                rest = &rest[comment + 2 + end + 2..];
+            }
+            (_, Some(url)) => {
+                let inner = &rest[url + 4..];
+                let Some(end) = inner.find(')') else {
+                    break;
+                };
+                urls.push(inner[..end].trim().trim_matches(|c| c == '"' || c == '\''));
+                rest = &inner[end + 1..];
+            }
+            _ => break,
+        }
+    }
+
+    urls
+}
+
+/// Whether `reference` points at a file on this site.
+fn is_local(reference: &str) -> bool {
+    let reference = reference.trim();
+    !(reference.is_empty()
+        || reference.starts_with('#')
+        || reference.starts_with("//")
+        || reference.contains("://")
+        || reference
+            .split_once(':')
+            .is_some_and(|(scheme, _)| !scheme.contains('/')))
+}
+
+/// Directory of `file` relative to `output_dir`, which relative references resolve against.
+fn base_dir(output_dir: &Path, file: &Path) -> PathBuf {
+    file.strip_prefix(output_dir)
+        .ok()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+        .unwrap_or_default()
+}
+
+/// The file in `output_dir` a site-relative or relative `reference` (from a file in `base`) is served from, if any.
+fn resolve(output_dir: &Path, base: &Path, reference: &str) -> Option<PathBuf> {
+    let reference = reference
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .trim();
+    let joined = match reference.strip_prefix('/') {
+        Some(absolute) => PathBuf::from(absolute),
+        None => base.join(reference),
+    };
+
+    // `..` never climbs out of the output, like a host's document root
+    let mut relative = PathBuf::new();
+    for component in joined.components() {
+        match component {
+            Component::Normal(part) => relative.push(part),
+            Component::ParentDir => {
+                relative.pop();
+            }
+            _ => {}
+        }
+    }
+
+    let path = output_dir.join(&relative);
+    let candidates = [
+        path.clone(),
+        path.join("index.html"),
+        path.with_extension("html"),
+    ];
+    candidates.into_iter().find(|candidate| candidate.is_file())
+}
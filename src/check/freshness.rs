@@ -0,0 +1,124 @@
+//! Freshness pass: content nobody has touched in `[freshness] max_age_days` days, unless it is marked evergreen.
+//!
+//! - published posts, by their latest `updates` entry or else their publish date
+//! - markdown files in the content directory, by `lastmod` in their frontmatter, or else `date`. Files without
+//!   frontmatter (a README about the directory) aren't content, and proposed posts in `drafts_dir` aren't published,
+//!   so both are left out
+//! - `about.json`, by its `updated` field
+//!
+//! Stale content isn't necessarily wrong, so the pass is opt-in: run it now and then and either revise what it lists
+//! (moving its date on) or mark it evergreen.
+
+use crate::{files_with_extension, Finding};
+use chrono::NaiveDate;
+use dioxus_site::content::split_frontmatter;
+use dioxus_site::posts::{Status, POSTS};
+use dioxus_site::ssg::config::FreshnessConfig;
+use std::fs;
+use std::path::Path;
+
+pub fn check(
+    content_dir: &Path,
+    drafts_dir: &Path,
+    data_dir: &Path,
+    config: &FreshnessConfig,
+    today: NaiveDate,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let max_age = i64::from(config.max_age_days);
+    let stale = |modified: NaiveDate| {
+        let age = (today - modified).num_days();
+        (age > max_age).then_some(age)
+    };
+    let mut findings = Vec::new();
+
+    for post in POSTS
+        .iter()
+        .filter(|post| post.status(today) == Status::Published && !post.evergreen)
+    {
+        if let Some(age) = stale(post.last_modified()) {
+            findings.push(Finding::new(
+                Path::new(post.source),
+                format!(
+                    "`{}` was last modified {} ({} days ago); review it and add an entry to its `updates`, or mark it `evergreen`",
+                    post.slug,
+                    post.last_modified(),
+                    age
+                ),
+            ));
+        }
+    }
+
+    for path in files_with_extension(content_dir, "md")? {
+        if path.starts_with(drafts_dir) {
+            continue;
+        }
+        let source = fs::read_to_string(&path)?;
+        let Some(raw) = split_frontmatter(&source).0 else {
+            continue;
+        };
+        let frontmatter: serde_yaml::Value = serde_yaml::from_str(raw)
+            .map_err(|e| format!("{}: frontmatter: {}", path.display(), e))?;
+        if frontmatter["evergreen"].as_bool() == Some(true) {
+            continue;
+        }
+
+        let Some((key, text)) = ["lastmod", "date"]
+            .into_iter()
+            .find_map(|key| Some((key, frontmatter[key].as_str()?)))
+        else {
+            findings.push(Finding::new(
+                &path,
+                "no `lastmod` or `date` in the frontmatter to tell how fresh it is",
+            ));
+            continue;
+        };
+        match parse_date(text) {
+            Some(modified) => {
+                if let Some(age) = stale(modified) {
+                    findings.push(Finding::new(
+                        &path,
+                        format!(
+                            "last modified {} ({} days ago); review it and move `lastmod` on, or set `evergreen: true`",
+                            modified, age
+                        ),
+                    ));
+                }
+            }
+            None => findings.push(Finding::new(
+                &path,
+                format!("`{}: {}` isn't a YYYY-MM-DD date", key, text),
+            )),
+        }
+    }
+
+    let about_path = data_dir.join("about.json");
+    if about_path.exists() {
+        let about: serde_json::Value = serde_json::from_str(&fs::read_to_string(&about_path)?)?;
+        let updated = about["updated"].as_str().unwrap_or_default();
+        match parse_date(updated) {
+            Some(modified) => {
+                if let Some(age) = stale(modified) {
+                    findings.push(Finding::new(
+                        &about_path,
+                        format!(
+                            "updated {} ({} days ago); check it's still accurate and move `updated` on",
+                            modified, age
+                        ),
+                    ));
+                }
+            }
+            None => findings.push(Finding::new(
+                &about_path,
+                format!("`updated` ({:?}) isn't a YYYY-MM-DD date", updated),
+            )),
+        }
+    }
+
+    Ok(findings)
+}
+
+/// `YYYY-MM-DD`, optionally followed by a time as in `2025-03-01T09:00:00Z`, which is ignored.
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let date = text.trim().get(..10)?;
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
@@ -0,0 +1,433 @@
+//! Just enough of JSON Schema (draft-04) to validate generated documents against the schemas in `schemas/`.
+//!
+//! Supported keywords: `type`, `properties`, `additionalProperties`, `required`, `items`, `enum`, `$ref` to
+//! `#/definitions/...`, `format` (`email` and `uri`) and `pattern`. Patterns support the usual ECMAScript subset that
+//! schemas use: anchors, literals, `.`, character classes with ranges, groups with `|`, and the `?`, `*`, `+` and
+//! `{n,m}` quantifiers. Other keywords are ignored, so a schema that relies on them validates less than it says.
+
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Every way `value` breaks `schema`, as `<JSON pointer>: <problem>`. Empty when it is valid.
+pub fn validate(schema: &Value, value: &Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    validate_at(schema, schema, value, "", &mut problems);
+    problems
+}
+
+fn validate_at(
+    root: &Value,
+    schema: &Value,
+    value: &Value,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    let at = if path.is_empty() { "/" } else { path };
+
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        match resolve(root, reference) {
+            Some(target) => validate_at(root, target, value, path, problems),
+            None => problems.push(format!(
+                "{}: the schema's $ref {} doesn't resolve",
+                at, reference
+            )),
+        }
+        return;
+    }
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(name) => vec![name.as_str()],
+            Value::Array(names) => names.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|name| has_type(value, name)) {
+            problems.push(format!(
+                "{}: expected {}, found {}",
+                at,
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            problems.push(format!("{}: {} isn't one of the allowed values", at, value));
+        }
+    }
+
+    match value {
+        Value::Object(object) => {
+            let properties = schema.get("properties").and_then(Value::as_object);
+            for name in schema
+                .get("required")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+            {
+                if let Some(name) = name.as_str() {
+                    if !object.contains_key(name) {
+                        problems.push(format!("{}: missing required property `{}`", at, name));
+                    }
+                }
+            }
+            for (name, child) in object {
+                let child_path = format!("{}/{}", path, name);
+                match properties.and_then(|properties| properties.get(name)) {
+                    Some(child_schema) => {
+                        validate_at(root, child_schema, child, &child_path, problems)
+                    }
+                    None => match schema.get("additionalProperties") {
+                        Some(Value::Bool(false)) => {
+                            problems.push(format!("{}: property `{}` isn't allowed", at, name))
+                        }
+                        Some(additional @ Value::Object(_)) => {
+                            validate_at(root, additional, child, &child_path, problems)
+                        }
+                        _ => {}
+                    },
+                }
+            }
+        }
+        Value::Array(items) => {
+            if let Some(item_schema) = schema.get("items").filter(|items| items.is_object()) {
+                for (index, item) in items.iter().enumerate() {
+                    validate_at(
+                        root,
+                        item_schema,
+                        item,
+                        &format!("{}/{}", path, index),
+                        problems,
+                    );
+                }
+            }
+        }
+        Value::String(text) => {
+            match schema.get("format").and_then(Value::as_str) {
+                Some("email") if !is_email(text) => {
+                    problems.push(format!("{}: {:?} isn't an email address", at, text))
+                }
+                Some("uri") if !is_uri(text) => {
+                    problems.push(format!("{}: {:?} isn't an absolute URI", at, text))
+                }
+                _ => {}
+            }
+            if let Some(pattern) = schema.get("pattern").and_then(Value::as_str) {
+                match Pattern::parse(pattern) {
+                    Ok(compiled) if !compiled.is_match(text) => {
+                        problems.push(format!("{}: {:?} doesn't match {}", at, text, pattern))
+                    }
+                    Err(e) => {
+                        problems.push(format!("{}: the schema's pattern {} {}", at, pattern, e))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The schema a `#/definitions/name` style pointer refers to.
+fn resolve<'a>(root: &'a Value, reference: &str) -> Option<&'a Value> {
+    root.pointer(reference.strip_prefix('#')?)
+}
+
+fn has_type(value: &Value, name: &str) -> bool {
+    match name {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        _ => type_name(value) == name || (name == "number" && value.is_number()),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn is_email(text: &str) -> bool {
+    text.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty() && domain.contains('.') && !text.contains(char::is_whitespace)
+    })
+}
+
+/// RFC 3986 absolute URIs: a scheme, a colon and something after it.
+fn is_uri(text: &str) -> bool {
+    text.split_once(':').is_some_and(|(scheme, rest)| {
+        scheme
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+            && !rest.is_empty()
+            && !text.contains(char::is_whitespace)
+    })
+}
+
+/// A compiled `pattern`: alternatives of sequences of quantified pieces.
+struct Pattern {
+    alternatives: Vec<Vec<Piece>>,
+}
+
+struct Piece {
+    node: Node,
+    min: usize,
+    max: Option<usize>,
+}
+
+enum Node {
+    Char(char),
+    Any,
+    Class {
+        ranges: Vec<(char, char)>,
+        negated: bool,
+    },
+    Group(Vec<Vec<Piece>>),
+    Start,
+    End,
+}
+
+impl Pattern {
+    fn parse(pattern: &str) -> Result<Self, String> {
+        let chars: Vec<char> = pattern.chars().collect();
+        let mut position = 0;
+        let alternatives = parse_alternatives(&chars, &mut position)?;
+        if position < chars.len() {
+            return Err(format!("has an unmatched `)` at {}", position));
+        }
+        Ok(Self { alternatives })
+    }
+
+    /// Like a JSON Schema `pattern`, the match may start anywhere unless the pattern is anchored.
+    fn is_match(&self, text: &str) -> bool {
+        let chars: Vec<char> = text.chars().collect();
+        (0..=chars.len())
+            .any(|start| !alternative_ends(&self.alternatives, &chars, start).is_empty())
+    }
+}
+
+fn parse_alternatives(chars: &[char], position: &mut usize) -> Result<Vec<Vec<Piece>>, String> {
+    let mut alternatives = vec![Vec::new()];
+    while let Some(&c) = chars.get(*position) {
+        let node = match c {
+            ')' => break,
+            '|' => {
+                *position += 1;
+                alternatives.push(Vec::new());
+                continue;
+            }
+            '(' => {
+                *position += 1;
+                // Non-capturing groups match the same
+                if chars[*position..].starts_with(&['?', ':']) {
+                    *position += 2;
+                }
+                let group = parse_alternatives(chars, position)?;
+                if chars.get(*position) != Some(&')') {
+                    return Err("has an unclosed `(`".to_string());
+                }
+                *position += 1;
+                Node::Group(group)
+            }
+            '[' => parse_class(chars, position)?,
+            '^' => {
+                *position += 1;
+                Node::Start
+            }
+            '$' => {
+                *position += 1;
+                Node::End
+            }
+            '.' => {
+                *position += 1;
+                Node::Any
+            }
+            '\\' => {
+                let escaped = *chars.get(*position + 1).ok_or("ends with a `\\`")?;
+                *position += 2;
+                escape_node(escaped)
+            }
+            _ => {
+                *position += 1;
+                Node::Char(c)
+            }
+        };
+        let (min, max) = parse_quantifier(chars, position)?;
+        if let Some(sequence) = alternatives.last_mut() {
+            sequence.push(Piece { node, min, max });
+        }
+    }
+    Ok(alternatives)
+}
+
+fn escape_node(escaped: char) -> Node {
+    let class = |ranges: &[(char, char)], negated| Node::Class {
+        ranges: ranges.to_vec(),
+        negated,
+    };
+    match escaped {
+        'd' => class(&[('0', '9')], false),
+        'D' => class(&[('0', '9')], true),
+        'w' => class(&[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')], false),
+        'W' => class(&[('a', 'z'), ('A', 'Z'), ('0', '9'), ('_', '_')], true),
+        's' => class(&[(' ', ' '), ('\t', '\r')], false),
+        'S' => class(&[(' ', ' '), ('\t', '\r')], true),
+        other => Node::Char(other),
+    }
+}
+
+fn parse_class(chars: &[char], position: &mut usize) -> Result<Node, String> {
+    *position += 1;
+    let negated = chars.get(*position) == Some(&'^');
+    if negated {
+        *position += 1;
+    }
+
+    let mut ranges = Vec::new();
+    loop {
+        let c = *chars.get(*position).ok_or("has an unclosed `[`")?;
+        if c == ']' && !ranges.is_empty() {
+            *position += 1;
+            break;
+        }
+        let c = if c == '\\' {
+            *position += 1;
+            *chars.get(*position).ok_or("ends with a `\\`")?
+        } else {
+            c
+        };
+        *position += 1;
+        if chars.get(*position) == Some(&'-')
+            && chars.get(*position + 1).is_some_and(|end| *end != ']')
+        {
+            ranges.push((c, chars[*position + 1]));
+            *position += 2;
+        } else {
+            ranges.push((c, c));
+        }
+    }
+    Ok(Node::Class { ranges, negated })
+}
+
+fn parse_quantifier(
+    chars: &[char],
+    position: &mut usize,
+) -> Result<(usize, Option<usize>), String> {
+    let quantifier = match chars.get(*position) {
+        Some('?') => (0, Some(1)),
+        Some('*') => (0, None),
+        Some('+') => (1, None),
+        Some('{') => {
+            let close = chars[*position..]
+                .iter()
+                .position(|c| *c == '}')
+                .ok_or("has an unclosed `{`")?;
+            let inner: String = chars[*position + 1..*position + close].iter().collect();
+            let number = |text: &str| {
+                text.trim()
+                    .parse::<usize>()
+                    .map_err(|_| format!("has a bad quantifier {{{}}}", inner))
+            };
+            let bounds = match inner.split_once(',') {
+                None => {
+                    let count = number(&inner)?;
+                    (count, Some(count))
+                }
+                Some((min, "")) => (number(min)?, None),
+                Some((min, max)) => (number(min)?, Some(number(max)?)),
+            };
+            *position += close;
+            bounds
+        }
+        _ => return Ok((1, Some(1))),
+    };
+    *position += 1;
+    Ok(quantifier)
+}
+
+/// Every position in `text` that one of `alternatives` can end at when it starts at `start`.
+fn alternative_ends(alternatives: &[Vec<Piece>], text: &[char], start: usize) -> BTreeSet<usize> {
+    alternatives
+        .iter()
+        .flat_map(|sequence| sequence_ends(sequence, text, start))
+        .collect()
+}
+
+fn sequence_ends(sequence: &[Piece], text: &[char], start: usize) -> BTreeSet<usize> {
+    let mut positions = BTreeSet::from([start]);
+    for piece in sequence {
+        positions = positions
+            .into_iter()
+            .flat_map(|position| piece_ends(piece, text, position))
+            .collect();
+        if positions.is_empty() {
+            break;
+        }
+    }
+    positions
+}
+
+fn piece_ends(piece: &Piece, text: &[char], start: usize) -> BTreeSet<usize> {
+    let mut ends = BTreeSet::new();
+    if piece.min == 0 {
+        ends.insert(start);
+    }
+
+    // More repetitions than characters can only repeat zero-width matches, so they add nothing
+    let max = piece.max.unwrap_or(text.len() + 1).min(text.len() + 1);
+    let mut level = BTreeSet::from([start]);
+    for count in 1..=max {
+        level = level
+            .into_iter()
+            .flat_map(|position| node_ends(&piece.node, text, position))
+            .collect();
+        if level.is_empty() {
+            break;
+        }
+        if count >= piece.min {
+            ends.extend(level.iter().copied());
+        }
+    }
+    ends
+}
+
+fn node_ends(node: &Node, text: &[char], position: usize) -> BTreeSet<usize> {
+    let next = text.get(position);
+    let matched = match node {
+        Node::Char(c) => next == Some(c),
+        Node::Any => next.is_some_and(|c| *c != '\n'),
+        Node::Class { ranges, negated } => next.is_some_and(|c| {
+            ranges.iter().any(|(low, high)| (low..=high).contains(&c)) != *negated
+        }),
+        Node::Group(alternatives) => return alternative_ends(alternatives, text, position),
+        Node::Start => {
+            return if position == 0 {
+                BTreeSet::from([position])
+            } else {
+                BTreeSet::new()
+            }
+        }
+        Node::End => {
+            return if position == text.len() {
+                BTreeSet::from([position])
+            } else {
+                BTreeSet::new()
+            }
+        }
+    };
+    if matched {
+        BTreeSet::from([position + 1])
+    } else {
+        BTreeSet::new()
+    }
+}
@@ -0,0 +1,31 @@
+//! Legal pass: every feature `site.toml` turns on that handles readers' data (reactions, error reports, analytics, the
+//! contact webhook) has to be mentioned in the privacy policy, so enabling one doesn't leave the policy out of date.
+
+use crate::Finding;
+use chrono::NaiveDate;
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::legal::{self, LEGAL_TEMPLATES_DIR, PRIVACY};
+use std::path::Path;
+
+pub fn check(
+    config: &SiteConfig,
+    today: NaiveDate,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let path = Path::new(LEGAL_TEMPLATES_DIR).join(format!("{}.html", PRIVACY.template));
+    let policy = PRIVACY.render(config, today)?;
+
+    let features = legal::features(config);
+    Ok(legal::unmentioned(&features, &policy)
+        .into_iter()
+        .map(|feature| {
+            Finding::new(
+                &path,
+                format!(
+                    "the site runs {} but the policy doesn't mention it (looked for: {})",
+                    feature.name,
+                    feature.terms.join(", ")
+                ),
+            )
+        })
+        .collect())
+}
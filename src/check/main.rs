@@ -0,0 +1,139 @@
+//! Site checker
+//! Runs build-time quality checks over content, data files and the generated static output.
+//!
+//! Usage: `cargo run --bin check --features ssr -- [--a11y] [--assets] [--seo] [--resume] [--legal] [--prose]
+//! [--freshness] [--unused]`
+//! Without any pass flags every default pass runs; optional passes (`--prose`, `--freshness`, `--unused`) only run when
+//! asked for.
+//! The process exits with an error when a pass reports problems.
+
+mod a11y;
+mod assets;
+mod freshness;
+mod json_schema;
+mod legal;
+mod prose;
+mod resume;
+mod seo;
+mod unused;
+
+use dioxus_site::posts;
+use dioxus_site::ssg::config::SiteConfig;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A problem reported by one of the check passes.
+pub struct Finding {
+    pub file: String,
+    pub message: String,
+}
+
+impl Finding {
+    pub fn new(file: &Path, message: impl Into<String>) -> Self {
+        Self {
+            file: file.display().to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔍 Running site checks...");
+
+    let args: Vec<String> = env::args().skip(1).collect();
+    let run_all = !args.iter().any(|arg| arg.starts_with("--"));
+    let wants = |flag: &str| run_all || args.iter().any(|arg| arg == flag);
+    let asked_for = |flag: &str| args.iter().any(|arg| arg == flag);
+
+    let src_dir = Path::new("src");
+    let content_dir = Path::new("content");
+    let data_dir = Path::new("assets/data");
+    let styles_dir = Path::new("assets/styling");
+    let output_dir = Path::new("static_output");
+    let dictionary_path = Path::new("dictionary.txt");
+
+    let mut findings = Vec::new();
+
+    if wants("--a11y") {
+        println!("♿ Checking accessibility...");
+        findings.extend(a11y::check(content_dir, data_dir, output_dir)?);
+    }
+
+    if wants("--assets") {
+        println!("🔗 Checking asset references...");
+        findings.extend(assets::check(src_dir, output_dir)?);
+    }
+
+    if wants("--seo") {
+        println!("🔎 Checking structured data and social tags...");
+        findings.extend(seo::check(output_dir)?);
+    }
+
+    if wants("--resume") {
+        println!("📄 Checking the JSON Resume export...");
+        findings.extend(resume::check(output_dir)?);
+    }
+
+    if wants("--legal") {
+        println!("⚖️  Checking the privacy policy covers the site's features...");
+        let config = SiteConfig::load(Path::new("site.toml"))?;
+        findings.extend(legal::check(&config, posts::today())?);
+    }
+
+    // Spelling depends on the installed hunspell dictionary, so it is opt-in rather than part of every run
+    if asked_for("--prose") {
+        println!("📝 Checking spelling and prose...");
+        findings.extend(prose::check(content_dir, data_dir, dictionary_path)?);
+    }
+
+    // Ages depend on the day the check runs, so stale content shouldn't fail every build
+    if asked_for("--freshness") {
+        println!("🗓️  Checking content freshness...");
+        let config = SiteConfig::load(Path::new("site.toml"))?;
+        findings.extend(freshness::check(
+            content_dir,
+            Path::new(&config.source.proposals),
+            data_dir,
+            &config.freshness,
+            posts::today(),
+        )?);
+    }
+
+    // Names put together at runtime look unused, so this is a report to go through now and then
+    if asked_for("--unused") {
+        println!("🧹 Checking for unused classes and components...");
+        findings.extend(unused::check(src_dir, styles_dir, output_dir)?);
+    }
+
+    if findings.is_empty() {
+        println!("✅ All checks passed");
+        return Ok(());
+    }
+
+    for finding in &findings {
+        println!("❌ {}: {}", finding.file, finding.message);
+    }
+
+    Err(format!("{} problem(s) found", findings.len()).into())
+}
+
+/// Recursively collect the files under `dir` with the given extension. A missing directory yields no files.
+pub fn files_with_extension(dir: &Path, extension: &str) -> std::io::Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    if !dir.exists() {
+        return Ok(files);
+    }
+
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(files_with_extension(&path, extension)?);
+        } else if path.extension().is_some_and(|ext| ext == extension) {
+            files.push(path);
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,275 @@
+//! Prose pass: spelling and basic style in markdown content and `about.json`.
+//!
+//! Style rules are built in: repeated words ("the the") and double spaces between words. Spelling is checked with
+//! `hunspell` (British English) when it is installed; words listed in `dictionary.txt` at the project root are always
+//! accepted, so technical terms and names only need adding once.
+//!
+//! Code blocks, inline code, URLs, email addresses and HTML tags are skipped.
+
+use crate::{files_with_extension, Finding};
+use dioxus_site::content::split_frontmatter;
+use std::collections::{BTreeSet, HashSet};
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+const HUNSPELL_DICTIONARY: &str = "en_GB";
+
+/// A piece of prose and where it is in its source file: `line 3` for markdown, `/bio` for data files.
+struct Text {
+    location: String,
+    text: String,
+}
+
+pub fn check(
+    content_dir: &Path,
+    data_dir: &Path,
+    dictionary_path: &Path,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let dictionary = load_dictionary(dictionary_path)?;
+    let spelling = hunspell_available();
+    if !spelling {
+        println!("⚠️  hunspell not found, skipping spelling (style checks still run)");
+    }
+
+    let mut findings = Vec::new();
+    let mut sources = Vec::new();
+
+    for path in files_with_extension(content_dir, "md")? {
+        let source = fs::read_to_string(&path)?;
+        sources.push((path, markdown_texts(&source)));
+    }
+
+    let about_path = data_dir.join("about.json");
+    if about_path.exists() {
+        let data: serde_json::Value = serde_json::from_str(&fs::read_to_string(&about_path)?)?;
+        let mut texts = Vec::new();
+        json_texts(&data, "", &mut texts);
+        sources.push((about_path, texts));
+    }
+
+    for (path, texts) in sources {
+        for text in &texts {
+            check_style(&path, text, &mut findings);
+        }
+        if spelling {
+            check_spelling(&path, &texts, &dictionary, &mut findings)?;
+        }
+    }
+
+    Ok(findings)
+}
+
+fn hunspell_available() -> bool {
+    Command::new("hunspell")
+        .arg("-v")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Lowercased words from the project dictionary, one per line. Blank lines and `#` comments are ignored.
+fn load_dictionary(path: &Path) -> Result<HashSet<String>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(HashSet::new());
+    }
+
+    Ok(fs::read_to_string(path)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(str::to_lowercase)
+        .collect())
+}
+
+/// Split a markdown file into checkable lines, leaving out frontmatter, fenced code and inline code.
+fn markdown_texts(source: &str) -> Vec<Text> {
+    let (frontmatter, body) = split_frontmatter(source);
+    // Line numbers should match the file, so count the lines taken up by the frontmatter and its fences
+    let offset = frontmatter.map_or(0, |raw| raw.lines().count() + 2);
+
+    let mut texts = Vec::new();
+    let mut in_code_block = false;
+    for (index, line) in body.lines().enumerate() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block || line.starts_with("    ") {
+            continue;
+        }
+
+        texts.push(Text {
+            location: format!("line {}", offset + index + 1),
+            text: strip_markup(line),
+        });
+    }
+
+    texts
+}
+
+/// Collect the string values of a data file. Keys are identifiers rather than prose and are not checked.
+fn json_texts(value: &serde_json::Value, pointer: &str, texts: &mut Vec<Text>) {
+    match value {
+        serde_json::Value::String(text) => texts.push(Text {
+            location: pointer.to_string(),
+            text: strip_markup(text),
+        }),
+        serde_json::Value::Object(map) => {
+            for (key, child) in map {
+                json_texts(child, &format!("{pointer}/{key}"), texts);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (index, child) in items.iter().enumerate() {
+                json_texts(child, &format!("{pointer}/{index}"), texts);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace inline code, HTML tags and the targets of markdown links and images with `…`, so they are neither
+/// checked nor turned into runs of spaces that would trip the double space rule.
+fn strip_markup(line: &str) -> String {
+    let mut output = String::with_capacity(line.len());
+    let mut chars = line.chars().peekable();
+    let mut skipping: Option<char> = None;
+
+    while let Some(c) = chars.next() {
+        match skipping {
+            Some(end) => {
+                if c == end {
+                    skipping = None;
+                }
+            }
+            None => match c {
+                '`' => {
+                    skipping = Some('`');
+                    output.push('…');
+                }
+                '<' => {
+                    skipping = Some('>');
+                    output.push('…');
+                }
+                ']' if chars.peek() == Some(&'(') => {
+                    skipping = Some(')');
+                    output.push_str("]…");
+                }
+                _ => output.push(c),
+            },
+        }
+    }
+
+    output
+}
+
+/// Words in `prose`, skipping URLs, email addresses and anything containing digits.
+fn words(prose: &str) -> impl Iterator<Item = &str> {
+    prose
+        .split_whitespace()
+        .filter(|token| {
+            !token.contains("://") && !token.contains('@') && !token.starts_with("www.")
+        })
+        .flat_map(|token| token.split(|c: char| !(c.is_alphabetic() || c == '\'')))
+        .map(|word| word.trim_matches('\''))
+        .filter(|word| !word.is_empty())
+}
+
+fn check_style(path: &Path, text: &Text, findings: &mut Vec<Finding>) {
+    let (location, prose) = (&text.location, &text.text);
+
+    let mut previous: Option<&str> = None;
+    for token in prose.split_whitespace() {
+        let word = token.trim_matches(|c: char| !c.is_alphanumeric());
+        if let Some(previous) = previous {
+            // Only flag when nothing separates the two words, so "that, that" is fine
+            if !word.is_empty() && previous.eq_ignore_ascii_case(word) {
+                findings.push(Finding::new(
+                    path,
+                    format!("{location}: repeated word \"{previous} {word}\""),
+                ));
+            }
+        }
+        previous =
+            (!word.is_empty() && token.ends_with(|c: char| c.is_alphanumeric())).then_some(word);
+    }
+
+    // Leading indentation and trailing markdown line breaks are allowed
+    if let Some(gap) = prose.trim().find("  ") {
+        let context: String = prose.trim()[..gap].chars().rev().take(20).collect();
+        let context: String = context.chars().rev().collect();
+        findings.push(Finding::new(
+            path,
+            format!(
+                "{location}: double space after \"{}\"",
+                context.trim_start()
+            ),
+        ));
+    }
+}
+
+fn check_spelling(
+    path: &Path,
+    texts: &[Text],
+    dictionary: &HashSet<String>,
+    findings: &mut Vec<Finding>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    // Only the words are sent, so URLs and other skipped tokens never reach hunspell
+    let mut input = String::new();
+    for text in texts {
+        input.push_str(&words(&text.text).collect::<Vec<_>>().join(" "));
+        input.push('\n');
+    }
+
+    let misspelled: BTreeSet<String> = run_hunspell(&input)?
+        .into_iter()
+        .filter(|word| !dictionary.contains(&word.to_lowercase()))
+        .collect();
+
+    // Report each unknown word once, at its first occurrence
+    for word in misspelled {
+        let Some(text) = texts
+            .iter()
+            .find(|text| words(&text.text).any(|w| w == word))
+        else {
+            continue;
+        };
+        findings.push(Finding::new(
+            path,
+            format!(
+                "{}: unknown word \"{word}\" (fix it or add it to dictionary.txt)",
+                text.location
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Words hunspell doesn't recognise in `input`.
+fn run_hunspell(input: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut child = Command::new("hunspell")
+        .args(["-d", HUNSPELL_DICTIONARY, "-l"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()?;
+
+    child
+        .stdin
+        .take()
+        .ok_or("hunspell stdin unavailable")?
+        .write_all(input.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(format!("hunspell exited with {}", output.status).into());
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
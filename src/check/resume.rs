@@ -0,0 +1,31 @@
+//! Resume pass: the JSON Resume export at [`RESUME_PATH`] has to validate against the JSON Resume schema, so resume
+//! tooling can read it. A missing export is a finding too, since every build writes one.
+
+use crate::json_schema;
+use crate::Finding;
+use dioxus_site::ssg::resume::{RESUME_PATH, SCHEMA_PATH};
+use std::fs;
+use std::path::Path;
+
+pub fn check(output_dir: &Path) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let schema_path = Path::new(SCHEMA_PATH);
+    let schema: serde_json::Value = serde_json::from_str(&fs::read_to_string(schema_path)?)
+        .map_err(|e| format!("{}: {}", schema_path.display(), e))?;
+
+    let path = output_dir.join(RESUME_PATH.trim_start_matches('/'));
+    let Ok(source) = fs::read_to_string(&path) else {
+        return Ok(vec![Finding::new(
+            &path,
+            "missing; generate the site before checking it",
+        )]);
+    };
+    let resume: serde_json::Value = match serde_json::from_str(&source) {
+        Ok(resume) => resume,
+        Err(e) => return Ok(vec![Finding::new(&path, format!("isn't JSON: {}", e))]),
+    };
+
+    Ok(json_schema::validate(&schema, &resume)
+        .into_iter()
+        .map(|problem| Finding::new(&path, problem))
+        .collect())
+}
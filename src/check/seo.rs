@@ -0,0 +1,147 @@
+//! SEO pass: the structured data and social preview tags of every generated page.
+//!
+//! - every `application/ld+json` block has to be JSON with a schema.org `@context`, a `@type` and the properties
+//!   [`structured_data::REQUIRED`] asks of it (a `BlogPosting` needs `headline`, `datePublished` and `author`)
+//! - every page needs one non-empty `og:title` and `og:type`, of `website` or `article`
+//! - an `article` needs a `BlogPosting` or `Article` block to go with it
+//!
+//! Only pages meant to be indexed and shared are checked: redirect stubs, and pages without a canonical link (the error
+//! pages, the GitHub Pages fallback), are left alone.
+
+use crate::{files_with_extension, Finding};
+use dioxus_site::ssg::html;
+use dioxus_site::ssg::structured_data;
+use std::fs;
+use std::path::Path;
+
+/// Open Graph properties every page needs.
+const REQUIRED_OG: &[&str] = &["og:title", "og:type"];
+
+const OG_TYPES: &[&str] = &["website", "article"];
+
+/// JSON-LD types that describe an `article` page.
+const ARTICLE_TYPES: &[&str] = &["BlogPosting", "Article"];
+
+pub fn check(output_dir: &Path) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let pages = files_with_extension(output_dir, "html")?;
+    if pages.is_empty() {
+        return Ok(vec![Finding::new(
+            output_dir,
+            "no pages; generate the site before checking it",
+        )]);
+    }
+
+    let mut findings = Vec::new();
+    for path in pages {
+        let source = fs::read_to_string(&path)?;
+        if is_redirect(&source) || !has_canonical(&source) {
+            continue;
+        }
+        check_page(&path, &source, &mut findings);
+    }
+    Ok(findings)
+}
+
+fn check_page(path: &Path, source: &str, findings: &mut Vec<Finding>) {
+    let mut types = Vec::new();
+    for block in json_ld_blocks(source) {
+        let document: serde_json::Value = match serde_json::from_str(block) {
+            Ok(document) => document,
+            Err(e) => {
+                findings.push(Finding::new(path, format!("JSON-LD isn't JSON: {}", e)));
+                continue;
+            }
+        };
+        types.extend(top_level_types(&document));
+        for problem in structured_data::problems(&document) {
+            findings.push(Finding::new(path, format!("JSON-LD: {}", problem)));
+        }
+    }
+
+    for property in REQUIRED_OG {
+        match og_values(source, property).as_slice() {
+            [] => findings.push(Finding::new(path, format!("no {} meta tag", property))),
+            [value] if value.trim().is_empty() => {
+                findings.push(Finding::new(path, format!("{} is empty", property)))
+            }
+            [_] => {}
+            values => findings.push(Finding::new(
+                path,
+                format!(
+                    "{} {} meta tags; there should be one",
+                    values.len(),
+                    property
+                ),
+            )),
+        }
+    }
+
+    if let [og_type] = og_values(source, "og:type").as_slice() {
+        if !OG_TYPES.contains(og_type) {
+            findings.push(Finding::new(
+                path,
+                format!("og:type `{}` isn't one of {}", og_type, OG_TYPES.join(", ")),
+            ));
+        }
+        if *og_type == "article"
+            && !types
+                .iter()
+                .any(|kind| ARTICLE_TYPES.contains(&kind.as_str()))
+        {
+            findings.push(Finding::new(
+                path,
+                "an article without BlogPosting structured data",
+            ));
+        }
+    }
+}
+
+/// Whether the page only sends visitors elsewhere, like the stubs for old post addresses.
+fn is_redirect(source: &str) -> bool {
+    html::tags(source, "meta").into_iter().any(|tag| {
+        html::attr(tag, "http-equiv").is_some_and(|value| value.eq_ignore_ascii_case("refresh"))
+    })
+}
+
+fn has_canonical(source: &str) -> bool {
+    html::tags(source, "link")
+        .into_iter()
+        .any(|tag| html::attr(tag, "rel").is_some_and(|rel| rel.eq_ignore_ascii_case("canonical")))
+}
+
+/// The text of every `<script type="application/ld+json">` in `source`.
+fn json_ld_blocks(source: &str) -> Vec<&str> {
+    html::tag_spans(source, "script")
+        .into_iter()
+        .filter(|span| {
+            html::attr(&source[span.clone()], "type")
+                .is_some_and(|kind| kind.eq_ignore_ascii_case("application/ld+json"))
+        })
+        .filter_map(|span| {
+            let rest = &source[span.end..];
+            rest.find("</script>").map(|end| &rest[..end])
+        })
+        .collect()
+}
+
+/// The `@type` of a document, or of each item of an array or `@graph`.
+fn top_level_types(document: &serde_json::Value) -> Vec<String> {
+    let nodes = match document.get("@graph").unwrap_or(document) {
+        serde_json::Value::Array(nodes) => nodes.iter().collect(),
+        node => vec![node],
+    };
+    nodes
+        .into_iter()
+        .filter_map(|node| node.get("@type").and_then(|kind| kind.as_str()))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The content of every `<meta property="...">` tag for `property`.
+fn og_values<'a>(source: &'a str, property: &str) -> Vec<&'a str> {
+    html::tags(source, "meta")
+        .into_iter()
+        .filter(|tag| html::attr(tag, "property") == Some(property))
+        .map(|tag| html::attr(tag, "content").unwrap_or_default())
+        .collect()
+}
@@ -0,0 +1,390 @@
+//! Unused code pass: stylesheet classes and components nothing uses any more.
+//!
+//! - a class in a selector in `assets/styling` is used when a generated page has it in a `class` attribute, or a
+//!   string in the Rust sources or a script in the output mentions it, since the SPA and the islands render markup the
+//!   generated pages don't contain. A class put together with `format!`, like `heading-{}`, counts as used for every
+//!   class starting with the text before the placeholder. The Tailwind build isn't checked; it only has the utilities
+//!   in use.
+//! - a `#[component]` is used when it can be reached from a route in the `Route` enum, from `src/main.rs`, where the
+//!   app and the islands are launched, or from a `VirtualDom` created anywhere else (the prerenderers behind the `ssr`
+//!   feature), through the components that render it. Helper functions count as part of every component in their
+//!   file.
+//!
+//! Either can be a false alarm for a name only put together at runtime in some other way, so the pass is opt-in, like
+//! a linter run now and then to keep the crate tidy.
+
+use crate::{files_with_extension, Finding};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// File defining the `Route` enum, whose variants and layouts are the SPA's pages.
+const ROUTES_FILE: &str = "lib.rs";
+
+/// File where the app and the islands are launched.
+const ENTRY_FILE: &str = "main.rs";
+
+pub fn check(
+    src_dir: &Path,
+    styles_dir: &Path,
+    output_dir: &Path,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let sources = files_with_extension(src_dir, "rs")?
+        .into_iter()
+        .map(|path| Ok((fs::read_to_string(&path)?, path)))
+        .collect::<std::io::Result<Vec<(String, PathBuf)>>>()?;
+
+    let mut findings = unused_classes(&sources, styles_dir, output_dir)?;
+    findings.extend(unreachable_components(&sources, src_dir));
+    Ok(findings)
+}
+
+fn unused_classes(
+    sources: &[(String, PathBuf)],
+    styles_dir: &Path,
+    output_dir: &Path,
+) -> Result<Vec<Finding>, Box<dyn std::error::Error>> {
+    let mut used = BTreeSet::new();
+    let mut prefixes = BTreeSet::new();
+    for path in files_with_extension(output_dir, "html")? {
+        let page = fs::read_to_string(&path)?;
+        for (at, attribute) in page.match_indices(" class=\"") {
+            let classes = &page[at + attribute.len()..];
+            let classes = &classes[..classes.find('"').unwrap_or(classes.len())];
+            used.extend(classes.split_whitespace().map(str::to_string));
+        }
+    }
+    for path in files_with_extension(output_dir, "js")? {
+        used.extend(words(&fs::read_to_string(&path)?));
+    }
+    for (source, _) in sources {
+        for literal in string_literals(source) {
+            used.extend(words(literal));
+            prefixes.extend(placeholder_prefixes(literal));
+        }
+    }
+
+    let mut findings = Vec::new();
+    for path in files_with_extension(styles_dir, "css")? {
+        let stylesheet = fs::read_to_string(&path)?;
+        for class in selector_classes(&stylesheet) {
+            let built = prefixes
+                .iter()
+                .any(|prefix| class.len() > prefix.len() && class.starts_with(prefix.as_str()));
+            if !used.contains(&class) && !built {
+                findings.push(Finding::new(
+                    &path,
+                    format!(
+                        ".{} isn't used by any generated page, component or script",
+                        class
+                    ),
+                ));
+            }
+        }
+    }
+    Ok(findings)
+}
+
+fn unreachable_components(sources: &[(String, PathBuf)], src_dir: &Path) -> Vec<Finding> {
+    // Every component with the file it's in and the code it can render from
+    let mut components: BTreeMap<String, (&Path, String)> = BTreeMap::new();
+    let mut roots = String::new();
+
+    for (source, path) in sources {
+        let source = without_comments_and_imports(source);
+        let defined = component_spans(&source);
+        let mut shared = source.clone();
+        for (_, span) in defined.iter().rev() {
+            shared.replace_range(span.clone(), "");
+        }
+        for (name, span) in &defined {
+            let body = format!("{}\n{}", &source[span.clone()], shared);
+            components.insert(name.clone(), (path.as_path(), body));
+        }
+
+        for launched in launched_components(&source) {
+            roots.push_str(launched);
+            roots.push('\n');
+        }
+        let relative = path.strip_prefix(src_dir).unwrap_or(path);
+        if relative == Path::new(ENTRY_FILE) {
+            roots.push_str(&source);
+        } else if relative == Path::new(ROUTES_FILE) {
+            if let Some(routes) = route_enum(&source) {
+                roots.push_str(routes);
+            }
+        }
+    }
+
+    let mut reached = BTreeSet::new();
+    let mut queue = vec![roots];
+    while let Some(code) = queue.pop() {
+        for (name, (_, body)) in &components {
+            if !reached.contains(name) && mentions(&code, name) {
+                reached.insert(name.clone());
+                queue.push(body.clone());
+            }
+        }
+    }
+
+    components
+        .iter()
+        .filter(|(name, _)| !reached.contains(*name))
+        .map(|(name, (path, _))| {
+            Finding::new(
+                path,
+                format!(
+                    "component {} isn't reachable from any route or island",
+                    name
+                ),
+            )
+        })
+        .collect()
+}
+
+/// Class names in the selectors of a stylesheet, in order of first appearance.
+fn selector_classes(stylesheet: &str) -> Vec<String> {
+    let mut classes = Vec::new();
+    let stylesheet = strip_css_comments(stylesheet);
+
+    // A selector is whatever comes before a `{` since the previous rule, declaration or block
+    for prelude in stylesheet
+        .split('{')
+        .map(|part| part.rsplit(['}', ';']).next().unwrap_or_default().trim())
+    {
+        if prelude.starts_with('@') {
+            continue;
+        }
+        let prelude = strip_quoted(prelude);
+        let mut rest = prelude.as_str();
+        while let Some(dot) = rest.find('.') {
+            let after = &rest[dot + 1..];
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+                .unwrap_or(after.len());
+            let name = &after[..end];
+            let starts_like_a_class = name
+                .chars()
+                .next()
+                .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '-');
+            let after_a_number = rest[..dot]
+                .chars()
+                .next_back()
+                .is_some_and(|c| c.is_ascii_digit());
+            if starts_like_a_class && !after_a_number && !classes.iter().any(|c| c == name) {
+                classes.push(name.to_string());
+            }
+            rest = &after[end..];
+        }
+    }
+
+    classes
+}
+
+fn strip_css_comments(stylesheet: &str) -> String {
+    let mut stripped = String::with_capacity(stylesheet.len());
+    let mut rest = stylesheet;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => rest = "",
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// `text` without its quoted strings, such as the values in attribute selectors.
+fn strip_quoted(text: &str) -> String {
+    let mut stripped = String::with_capacity(text.len());
+    let mut quote = None;
+    for c in text.chars() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None => stripped.push(c),
+        }
+    }
+    stripped
+}
+
+/// The words a class name could be in `text`: runs of letters, digits, `-` and `_`.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+}
+
+/// The start of the class names a format string builds: the word right before each placeholder, when it ends in `-`
+/// or `_` (`heading-` in `"heading heading-{}"`). Escaped braces (`{{`) aren't placeholders.
+fn placeholder_prefixes(literal: &str) -> Vec<String> {
+    let bytes = literal.as_bytes();
+    let mut prefixes = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'{' {
+            i += 1;
+            continue;
+        }
+        if bytes.get(i + 1) == Some(&b'{') {
+            i += 2;
+            continue;
+        }
+        let before = &literal[..i];
+        let start = before
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .last()
+            .map_or(i, |(at, _)| at);
+        let word = &before[start..];
+        if word.len() > 1 && word.ends_with(['-', '_']) {
+            prefixes.push(word.to_string());
+        }
+        i += 1;
+    }
+    prefixes
+}
+
+/// The components a `VirtualDom` is created with in `source`, the first argument of `VirtualDom::new` or
+/// `VirtualDom::new_with_props`.
+fn launched_components(source: &str) -> Vec<&str> {
+    source
+        .match_indices("VirtualDom::new")
+        .filter_map(|(at, _)| {
+            let rest = &source[at..];
+            let open = rest.find('(')?;
+            let argument = &rest[open + 1..];
+            let end = argument.find([',', ')'])?;
+            Some(argument[..end].trim())
+        })
+        .collect()
+}
+
+/// The contents of the string literals in Rust source, raw strings included, leaving out comments.
+fn string_literals(source: &str) -> Vec<&str> {
+    let bytes = source.as_bytes();
+    let mut literals = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = source[i..].find('\n').map_or(bytes.len(), |end| i + end);
+            }
+            // A quote as a char literal
+            b'\'' if bytes.get(i + 1) == Some(&b'"') && bytes.get(i + 2) == Some(&b'\'') => i += 3,
+            b'r' if matches!(bytes.get(i + 1), Some(b'#') | Some(b'"'))
+                && (i == 0 || !bytes[i - 1].is_ascii_alphanumeric() && bytes[i - 1] != b'_') =>
+            {
+                let hashes = bytes[i + 1..].iter().take_while(|&&b| b == b'#').count();
+                let open = i + 1 + hashes;
+                if bytes.get(open) != Some(&b'"') {
+                    i += 1;
+                    continue;
+                }
+                let close = format!("\"{}", "#".repeat(hashes));
+                let end = source[open + 1..]
+                    .find(&close)
+                    .map_or(bytes.len(), |end| open + 1 + end);
+                literals.push(&source[open + 1..end]);
+                i = end + close.len();
+            }
+            b'"' => {
+                let mut end = i + 1;
+                while end < bytes.len() && bytes[end] != b'"' {
+                    end += if bytes[end] == b'\\' { 2 } else { 1 };
+                }
+                let end = end.min(bytes.len());
+                literals.push(&source[i + 1..end]);
+                i = end + 1;
+            }
+            _ => i += 1,
+        }
+    }
+
+    literals
+}
+
+/// Source without line comments, doc comments and `use` declarations, which name components without rendering them.
+fn without_comments_and_imports(source: &str) -> String {
+    let mut code = String::with_capacity(source.len());
+    let mut in_use = false;
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("//") {
+            code.push('\n');
+            continue;
+        }
+        if trimmed.starts_with("use ") || trimmed.starts_with("pub use ") {
+            in_use = true;
+        }
+        if in_use {
+            in_use = !line.trim_end().ends_with(';');
+            code.push('\n');
+            continue;
+        }
+        code.push_str(line);
+        code.push('\n');
+    }
+    code
+}
+
+/// Every `#[component]` function in `source`, with the span from its name to the end of its body.
+fn component_spans(source: &str) -> Vec<(String, std::ops::Range<usize>)> {
+    let mut spans = Vec::new();
+    let mut from = 0;
+
+    while let Some(found) = source[from..].find("#[component]") {
+        let start = from + found + "#[component]".len();
+        let Some(fn_at) = source[start..].find("fn ") else {
+            break;
+        };
+        let name_start = start + fn_at + 3;
+        let name: String = source[name_start..]
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        let Some(open) = source[name_start..].find('{').map(|at| name_start + at) else {
+            break;
+        };
+        let mut depth = 0;
+        let mut end = source.len();
+        for (at, c) in source[open..].char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = open + at + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        spans.push((name, name_start..end));
+        from = end;
+    }
+
+    spans
+}
+
+/// The body of the `Route` enum.
+fn route_enum(source: &str) -> Option<&str> {
+    let start = source.find("pub enum Route")?;
+    let open = start + source[start..].find('{')?;
+    let close = open + source[open..].find("\n}")?;
+    Some(&source[open..close])
+}
+
+/// Whether `code` mentions `name` as a whole identifier.
+fn mentions(code: &str, name: &str) -> bool {
+    code.match_indices(name).any(|(at, _)| {
+        let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+        !code[..at].chars().next_back().is_some_and(is_ident)
+            && !code[at + name.len()..].chars().next().is_some_and(is_ident)
+    })
+}
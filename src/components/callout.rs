@@ -0,0 +1,50 @@
+use crate::callouts::CalloutKind;
+use dioxus::prelude::*;
+
+/// A callout of any kind (see [`callouts`](crate::callouts)), with the same markup and styling as the ones in post
+/// bodies. `title` replaces the kind's own, e.g. "Heads up" instead of "Warning".
+#[component]
+pub fn Callout(
+    kind: CalloutKind,
+    #[props(default, into)] title: Option<String>,
+    children: Element,
+) -> Element {
+    let title = title.unwrap_or_else(|| kind.label().to_string());
+
+    rsx! {
+        aside {
+            class: kind.class(),
+            role: "note",
+            p {
+                class: "callout-title",
+                span { aria_hidden: "true", "{kind.icon()}" }
+                " {title}"
+            }
+            div { class: "callout-body", {children} }
+        }
+    }
+}
+
+/// A [`Callout`] for background the reader may want.
+#[component]
+pub fn Note(#[props(default, into)] title: Option<String>, children: Element) -> Element {
+    rsx! {
+        Callout { kind: CalloutKind::Note, title, {children} }
+    }
+}
+
+/// A [`Callout`] for something that can go wrong.
+#[component]
+pub fn Warning(#[props(default, into)] title: Option<String>, children: Element) -> Element {
+    rsx! {
+        Callout { kind: CalloutKind::Warning, title, {children} }
+    }
+}
+
+/// A [`Callout`] for a shortcut or a suggestion.
+#[component]
+pub fn Tip(#[props(default, into)] title: Option<String>, children: Element) -> Element {
+    rsx! {
+        Callout { kind: CalloutKind::Tip, title, {children} }
+    }
+}
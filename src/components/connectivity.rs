@@ -0,0 +1,86 @@
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+
+pub(super) const CONNECTIVITY_CSS: Asset = asset!("/assets/styling/connectivity.css");
+
+/// Banner shown while the browser is offline. Pages then come from the service worker's cache (written by
+/// `src/ssg/service_worker.rs`), so the banner says the content may be out of date, and on pages with a contact form
+/// that messages can't be sent until the connection is back. Renders nothing while online.
+///
+/// Rendered by the SPA's navbar layout, and mounted as an island into `#connectivity-root` on generated pages.
+#[component]
+pub fn ConnectivityStatus() -> Element {
+    let online = use_hook(|| {
+        let online = Signal::new_in_scope(is_online(), ScopeId::ROOT);
+        listen(online);
+        online
+    });
+
+    if online() {
+        return rsx! {};
+    }
+
+    rsx! {
+        Stylesheet { href: CONNECTIVITY_CSS }
+
+        div {
+            class: "connectivity-status",
+            role: "status",
+            strong { "📡 You're offline. " }
+            "This is a saved copy of the page, so it may be out of date."
+            if has_contact_form() {
+                " Messages can't be sent from the form until you're back online."
+            }
+        }
+    }
+}
+
+fn is_online() -> bool {
+    #[cfg(feature = "web")]
+    {
+        if let Some(window) = web_sys::window() {
+            return window.navigator().on_line();
+        }
+    }
+    true
+}
+
+/// Whether the page shows one of the forms that post to the contact endpoint.
+fn has_contact_form() -> bool {
+    #[cfg(feature = "web")]
+    {
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            return document
+                .query_selector("form.contact-form")
+                .ok()
+                .flatten()
+                .is_some();
+        }
+    }
+    false
+}
+
+/// Follow the browser's `online` and `offline` events. The listeners live as long as the page, like the islands.
+#[cfg(feature = "web")]
+fn listen(mut online: Signal<bool>) {
+    use dioxus::core::Runtime;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    // The events arrive outside the Dioxus runtime, so the update is run in this app's root scope
+    let runtime = Runtime::current();
+    for (event, value) in [("online", true), ("offline", false)] {
+        let runtime = runtime.clone();
+        let listener = Closure::<dyn FnMut()>::new(move || {
+            runtime.in_scope(ScopeId::ROOT, || online.set(value));
+        });
+        let _ = window.add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+        listener.forget();
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn listen(_online: Signal<bool>) {}
@@ -0,0 +1,311 @@
+use crate::api::{Attachment, ContactRequest};
+use crate::components::{PhoneInput, ReasonSelect};
+use crate::contact_form::{FieldKind, FieldSpec, FormSchema, CONTACT_FORM, PHONE_COUNTRY};
+use crate::contact_prefill::Prefill;
+use crate::contact_reasons;
+use crate::phone;
+use crate::unsaved_guard::use_unsaved_guard;
+use dioxus::prelude::*;
+
+/// The values of a form built from one of the schemas in `forms/`, from [`use_contact_form`].
+#[derive(Clone, Copy, PartialEq)]
+pub struct ContactForm {
+    schema: &'static FormSchema,
+    request: Signal<ContactRequest>,
+    /// The values as they were last submitted, cleared or prefilled; anything else is an unsaved change
+    saved: Signal<ContactRequest>,
+    /// The files picked in the `file` fields, sent along with the request
+    attachments: Signal<Vec<Attachment>>,
+}
+
+/// State for the form described by `schema`, prefilled from the page's query string (see [`contact_prefill`]).
+/// Leaving the page with changes that weren't submitted asks for confirmation first (see
+/// [`unsaved_guard`](crate::unsaved_guard)).
+pub fn use_contact_form(schema: &'static FormSchema) -> ContactForm {
+    let request = use_signal(|| {
+        let prefill = prefill_from_url();
+        let mut request = ContactRequest {
+            name: prefill.name.unwrap_or_default(),
+            email: prefill.email.unwrap_or_default(),
+            reason: prefill
+                .reason
+                .unwrap_or(contact_reasons::DEFAULT_REASON)
+                .to_string(),
+            subject: prefill.subject.unwrap_or_default(),
+            message: prefill.message.unwrap_or_default(),
+            phone_country: phone::DEFAULT_COUNTRY.to_string(),
+            ..ContactRequest::default()
+        };
+        start(schema, &mut request);
+        request
+    });
+    let saved = use_signal(|| request.peek().clone());
+    let attachments = use_signal(Vec::new);
+    let form = ContactForm {
+        schema,
+        request,
+        saved,
+        attachments,
+    };
+
+    let dirty = use_memo(move || form.is_dirty());
+    use_unsaved_guard(dirty.into());
+    form
+}
+
+/// State for the form described by `schema` holding `values` (field name and value), for the component previews. It
+/// reads nothing from the URL and never asks before leaving the page.
+pub fn use_sample_form(
+    schema: &'static FormSchema,
+    values: &'static [(&'static str, &'static str)],
+) -> ContactForm {
+    let request = use_signal(|| {
+        let mut request = ContactRequest {
+            reason: contact_reasons::DEFAULT_REASON.to_string(),
+            phone_country: phone::DEFAULT_COUNTRY.to_string(),
+            ..ContactRequest::default()
+        };
+        start(schema, &mut request);
+        for (name, value) in values {
+            request.set(name, value.to_string());
+        }
+        request
+    });
+    let saved = use_signal(|| request.peek().clone());
+    let attachments = use_signal(Vec::new);
+    ContactForm {
+        schema,
+        request,
+        saved,
+        attachments,
+    }
+}
+
+/// Fill in what `schema` submits without asking: its id, its defaults and the initial choice of every select.
+fn start(schema: &FormSchema, request: &mut ContactRequest) {
+    if schema.id != CONTACT_FORM {
+        request.form = schema.id.clone();
+    }
+    for (name, value) in &schema.defaults {
+        request.set(name, value.clone());
+    }
+    for field in &schema.fields {
+        if !field.initial_value().is_empty() {
+            request.set(&field.name, field.initial_value().to_string());
+        }
+    }
+}
+
+impl ContactForm {
+    /// The submission as the server will receive it.
+    pub fn request(&self) -> ContactRequest {
+        (self.request)()
+    }
+
+    pub fn set(&mut self, field: &str, value: String) {
+        self.request.write().set(field, value);
+    }
+
+    /// The files to send with the request.
+    pub fn attachments(&self) -> Vec<Attachment> {
+        (self.attachments)()
+    }
+
+    /// Pick `file` for the `file` field called `field`, or remove the one picked with `None`. The field's value is the
+    /// file's name, so a required field counts as filled in.
+    pub fn attach(&mut self, field: &str, file: Option<Attachment>) {
+        let filename = file
+            .as_ref()
+            .map(|file| file.filename.clone())
+            .unwrap_or_default();
+        {
+            let mut attachments = self.attachments.write();
+            attachments.retain(|attachment| attachment.field != field);
+            attachments.extend(file);
+        }
+        self.set(field, filename);
+    }
+
+    pub fn schema(&self) -> &'static FormSchema {
+        self.schema
+    }
+
+    /// Whether a field changed since the form was last submitted, cleared or prefilled.
+    pub fn is_dirty(&self) -> bool {
+        *self.request.read() != *self.saved.read()
+    }
+
+    /// Record `request` as submitted, so leaving the page no longer asks about it.
+    pub fn mark_submitted(&mut self, request: ContactRequest) {
+        self.saved.set(request);
+    }
+
+    /// Empty every field, keeping the chosen reason and phone country.
+    pub fn clear(&mut self) {
+        let mut request = self.request.write();
+        *request = ContactRequest {
+            reason: std::mem::take(&mut request.reason),
+            phone_country: std::mem::take(&mut request.phone_country),
+            ..ContactRequest::default()
+        };
+        start(self.schema, &mut request);
+        self.saved.set(request.clone());
+        self.attachments.write().clear();
+    }
+}
+
+/// The inputs for every field of the form's schema, in order. With `show_errors`, fields that have a problem are
+/// marked with the error style.
+#[component]
+pub fn ContactFields(form: ContactForm, show_errors: bool) -> Element {
+    let request = form.request();
+    let invalid = move |field: &FieldSpec| show_errors && request.has_problem(&field.name);
+
+    rsx! {
+        for row in form.schema.rows() {
+            if row.len() > 1 {
+                div {
+                    class: "form-row",
+                    for field in row {
+                        SchemaField { key: "{field.name}", form, field, invalid: invalid(field) }
+                    }
+                }
+            } else {
+                SchemaField { key: "{row[0].name}", form, field: row[0], invalid: invalid(row[0]) }
+            }
+        }
+    }
+}
+
+/// The input for one field, by its type.
+#[component]
+fn SchemaField(form: ContactForm, field: &'static FieldSpec, invalid: bool) -> Element {
+    let mut form = form;
+    let name = field.name.as_str();
+    let label = field.display_label();
+    let value = form.request().value(name).to_string();
+
+    match field.kind {
+        FieldKind::Reason => rsx! {
+            ReasonSelect {
+                label,
+                reason: value,
+                onchange: move |reason| form.set(name, reason),
+            }
+        },
+        FieldKind::Phone => rsx! {
+            PhoneInput {
+                label,
+                placeholder: field.placeholder.clone(),
+                country: form.request().phone_country,
+                number: value,
+                on_country: move |country| form.set(PHONE_COUNTRY, country),
+                on_number: move |number| form.set(name, number),
+                invalid,
+            }
+        },
+        FieldKind::Textarea => {
+            let lines = field.lines.unwrap_or(6);
+            rsx! {
+                div {
+                    class: "form-group",
+                    label { "for": name, "{label}" }
+                    textarea {
+                        id: name,
+                        name,
+                        class: if invalid { "form-textarea error" } else { "form-textarea" },
+                        placeholder: field.placeholder.as_str(),
+                        rows: "{lines}",
+                        value,
+                        oninput: move |e| form.set(name, e.value()),
+                    }
+                }
+            }
+        }
+        FieldKind::Select => rsx! {
+            div {
+                class: "form-group",
+                label { "for": name, "{label}" }
+                select {
+                    id: name,
+                    name,
+                    class: if invalid { "form-input error" } else { "form-input" },
+                    value: "{value}",
+                    onchange: move |e| form.set(name, e.value()),
+                    for option in &field.options {
+                        option {
+                            key: "{option.value}",
+                            value: "{option.value}",
+                            selected: value == option.value,
+                            "{option.label}"
+                        }
+                    }
+                }
+            }
+        },
+        // The browser keeps the picked file; its bytes are read here so the island can send them
+        FieldKind::File => rsx! {
+            div {
+                class: "form-group",
+                label { "for": name, "{label}" }
+                input {
+                    r#type: field.kind.input_type(),
+                    id: name,
+                    name,
+                    class: if invalid { "form-input error" } else { "form-input" },
+                    onchange: move |e| async move {
+                        let mut file = None;
+                        if let Some(picked) = e.files().into_iter().next() {
+                            match picked.read_bytes().await {
+                                Ok(bytes) => {
+                                    file = Some(Attachment {
+                                        field: name.to_string(),
+                                        filename: picked.name(),
+                                        bytes: bytes.to_vec(),
+                                    });
+                                }
+                                Err(error) => {
+                                    crate::logger::warn(&format!("⚠️ Couldn't read {}: {}", picked.name(), error));
+                                }
+                            }
+                        }
+                        form.attach(name, file);
+                    },
+                }
+            }
+        },
+        FieldKind::Text
+        | FieldKind::Email
+        | FieldKind::Tel
+        | FieldKind::Date
+        | FieldKind::Number => rsx! {
+            div {
+                class: "form-group",
+                label { "for": name, "{label}" }
+                input {
+                    r#type: field.kind.input_type(),
+                    id: name,
+                    name,
+                    class: if invalid { "form-input error" } else { "form-input" },
+                    placeholder: field.placeholder.as_str(),
+                    autocomplete: field.autocomplete.as_deref(),
+                    min: field.min().map(|min| min.to_string()),
+                    max: field.max().map(|max| max.to_string()),
+                    value,
+                    oninput: move |e| form.set(name, e.value()),
+                }
+            }
+        },
+    }
+}
+
+/// Form prefill from the page's query string (see [`contact_prefill`]).
+fn prefill_from_url() -> Prefill {
+    #[cfg(feature = "web")]
+    {
+        if let Some(search) = web_sys::window().and_then(|window| window.location().search().ok()) {
+            return crate::contact_prefill::parse(&search);
+        }
+    }
+    Prefill::default()
+}
@@ -1,6 +1,7 @@
+use crate::styles::Stylesheet;
 use dioxus::prelude::*;
 
-const ECHO_CSS: Asset = asset!("/assets/styling/echo.css");
+pub(super) const ECHO_CSS: Asset = asset!("/assets/styling/echo.css");
 
 /// Echo component that demonstrates client-side state management.
 #[component]
@@ -10,10 +11,10 @@ pub fn Echo() -> Element {
     //
     // use_signal is a hook that creates a state for the component. It takes a closure that returns the initial value of the state.
     // The state is automatically tracked and will rerun any other hooks or components that read it whenever it changes.
-    let mut response = use_signal(|| String::new());
+    let mut response = use_signal(String::new);
 
     rsx! {
-        document::Link { rel: "stylesheet", href: ECHO_CSS }
+        Stylesheet { href: ECHO_CSS }
 
         div {
             id: "echo",
@@ -0,0 +1,38 @@
+use crate::typography;
+use dioxus::prelude::*;
+
+/// Heading on the site's typographic scale (see [`typography`]).
+///
+/// `level` picks both the element and its size, from `h1` to `h4`; deeper levels render as `h4`. An optional `id`
+/// makes the heading a link target and is normalized with [`typography::anchor_id`], so `"About Me"` becomes
+/// `about-me` here and in the generated pages alike. `class` adds page-specific decoration such as a border, but not
+/// a size. `view_transition` marks a heading holding a link as one that moves to the linked page when it's followed
+/// (see [`view_transitions`](crate::view_transitions)).
+#[component]
+pub fn Heading(
+    level: u8,
+    #[props(default, into)] id: Option<String>,
+    #[props(default, into)] class: Option<String>,
+    #[props(default, into)] view_transition: Option<String>,
+    children: Element,
+) -> Element {
+    let level = typography::heading_level(level);
+    let id = id.map(|id| typography::anchor_id(&id));
+    let class = typography::heading_class(level, class.as_deref());
+
+    match level {
+        1 => rsx! { h1 { id, class, "data-view-transition": view_transition, {children} } },
+        2 => rsx! { h2 { id, class, "data-view-transition": view_transition, {children} } },
+        3 => rsx! { h3 { id, class, "data-view-transition": view_transition, {children} } },
+        _ => rsx! { h4 { id, class, "data-view-transition": view_transition, {children} } },
+    }
+}
+
+/// Long-form text such as a post body or a bio. Paragraphs, lists and any headings inside raw HTML follow the same
+/// scale as [`Heading`]. `class` is added after the prose class.
+#[component]
+pub fn Prose(#[props(default, into)] class: Option<String>, children: Element) -> Element {
+    rsx! {
+        div { class: typography::prose_class(class.as_deref()), {children} }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::components::{Img, ImgLayout};
 use dioxus::prelude::*;
 
 const HEADER_SVG: Asset = asset!("/assets/header.svg");
@@ -10,7 +11,16 @@ pub fn Hero() -> Element {
             // Attributes should be defined in the element before any children
             id: "hero",
             // After all attributes are defined, we can define child elements and components
-            img { src: HEADER_SVG, id: "header" }
+            // The hero image is above the fold, so it opts out of lazy loading but still reserves its box
+            Img {
+                src: HEADER_SVG.to_string(),
+                alt: "Dioxus",
+                width: 1007,
+                height: 197,
+                id: "header",
+                layout: ImgLayout::Hero,
+                eager: true,
+            }
             div { id: "links",
                 // The RSX macro also supports text nodes surrounded by quotes
                 a { href: "https://dioxuslabs.com/learn/0.7/", "📚 Learn Dioxus 3" }
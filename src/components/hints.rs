@@ -0,0 +1,51 @@
+use crate::hints;
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+
+pub(super) const HINTS_CSS: Asset = asset!("/assets/styling/hints.css");
+
+/// Banner with the next first-visit hint from `[[hints]]` in `site.toml` (see [`hints`](crate::hints)), until it's
+/// dismissed. Only one hint shows at a time; dismissing it hides the banner, and the next hint in the queue waits for
+/// the next page. Renders nothing once every hint has been dismissed.
+///
+/// It sits in the corner of the window rather than in the page, so it never moves the content. Rendered by the SPA's
+/// navbar layout, and mounted as an island into `#hints-root` on generated pages.
+#[component]
+pub fn Hints() -> Element {
+    // Picked after the first render, since only the browser knows what was dismissed: prerendered pages come without
+    // a hint, and hydrating them doesn't find one that shouldn't be there
+    let mut hint = use_signal(|| None);
+    use_effect(move || hint.set(hints::next(hints::compiled(), &hints::dismissed()).cloned()));
+
+    let Some(shown) = hint() else {
+        return rsx! {};
+    };
+
+    rsx! {
+        Stylesheet { href: HINTS_CSS }
+
+        aside {
+            class: "hint",
+            role: "note",
+            aria_label: "Tip",
+            p {
+                class: "hint-text",
+                "{shown.text}"
+                if let (Some(link), Some(label)) = (&shown.link, &shown.link_label) {
+                    " "
+                    a { href: "{link}", "{label}" }
+                }
+            }
+            button {
+                r#type: "button",
+                class: "hint-dismiss",
+                aria_label: "Dismiss tip",
+                onclick: move |_| {
+                    hints::dismiss(&shown.id);
+                    hint.set(None);
+                },
+                "Got it"
+            }
+        }
+    }
+}
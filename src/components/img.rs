@@ -0,0 +1,80 @@
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+
+pub(super) const IMG_CSS: Asset = asset!("/assets/styling/img.css");
+
+/// Where an [`Img`] sits on the page, which decides its `sizes` hint: how wide it's drawn at each viewport width, so
+/// the browser can pick the smallest file in the `srcset` that still fills it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ImgLayout {
+    /// Across the page, up to the 1200px of the home page header
+    Hero,
+    /// A thumbnail in a grid of cards: the whole width on phones, a column of about 320px otherwise
+    Card,
+    /// In the content column, up to its 960px
+    #[default]
+    Content,
+}
+
+impl ImgLayout {
+    /// The `sizes` attribute for the layout.
+    pub fn sizes(self) -> &'static str {
+        match self {
+            ImgLayout::Hero => "(max-width: 1200px) 100vw, 1200px",
+            ImgLayout::Card => "(max-width: 640px) 100vw, 320px",
+            ImgLayout::Content => "(max-width: 960px) 100vw, 960px",
+        }
+    }
+}
+
+/// Image that reserves its layout box up front and loads lazily.
+///
+/// `width` and `height` are the intrinsic pixel dimensions of the source image. They are emitted as attributes so the
+/// browser knows the aspect ratio before the file arrives, which prevents layout shift. An optional `placeholder` (a tiny
+/// low-quality image, usually a data URI) is shown blurred behind the image until the real one has painted. For the
+/// images in `assets/images/` all three come from [`images`](crate::images), read from the file at build time.
+///
+/// Smaller copies of the image go in `srcset` as `url width` pairs (`/assets/images/photo-640w.jpg 640w, ...`), as
+/// [`responsive_images::srcset`](crate::responsive_images::srcset) lists the ones the generator writes. The `layout`
+/// preset then adds the `sizes` hint, without which the browser assumes the image is as wide as the viewport and fetches
+/// a larger file than a thumbnail needs. Without a `srcset` there is nothing to choose from, so no `sizes` is emitted.
+///
+/// Images above the fold (like the home page hero) should set `eager: true` so they are not deferred.
+#[component]
+pub fn Img(
+    src: String,
+    alt: String,
+    width: u32,
+    height: u32,
+    #[props(default)] placeholder: Option<String>,
+    #[props(default)] srcset: Option<String>,
+    #[props(default)] layout: ImgLayout,
+    #[props(default)] id: Option<String>,
+    #[props(default)] class: Option<String>,
+    #[props(default)] eager: bool,
+) -> Element {
+    let sizes = srcset.as_ref().map(|_| layout.sizes());
+
+    rsx! {
+        Stylesheet { href: IMG_CSS }
+
+        span {
+            class: "img-frame",
+            if let Some(lqip) = placeholder {
+                img { class: "img-lqip", src: lqip, alt: "", aria_hidden: "true" }
+            }
+            img {
+                src,
+                srcset,
+                sizes,
+                alt,
+                id,
+                class,
+                width: "{width}",
+                height: "{height}",
+                loading: if eager { "eager" } else { "lazy" },
+                decoding: "async",
+            }
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use crate::focus::use_focus_trap;
+use dioxus::prelude::*;
+
+/// Id of the drawer, for the toggle's `aria-controls` and the focus trap. There is one navbar per page.
+const DRAWER_ID: &str = "mobile-nav-drawer";
+
+/// Class on `#navbar` telling `navbar.css` that a drawer is there, so the link row can be hidden on small screens.
+pub const HAS_MOBILE_NAV_CLASS: &str = "has-mobile-nav";
+
+/// Hamburger toggle and slide-in drawer holding `children` (the navigation links) on small screens.
+///
+/// `navbar.css` shows the toggle below 640px only. While the drawer is open, focus stays inside it; Escape, the close
+/// button, the backdrop or following a link closes it again and returns focus to the toggle.
+#[component]
+pub fn MobileNav(children: Element) -> Element {
+    let mut open = use_signal(|| false);
+    use_focus_trap(DRAWER_ID, open.into());
+
+    rsx! {
+        div {
+            class: "mobile-nav",
+            button {
+                r#type: "button",
+                class: "mobile-nav-toggle",
+                aria_controls: DRAWER_ID,
+                aria_expanded: "{open}",
+                aria_label: "Open menu",
+                onclick: move |_| open.set(true),
+                "☰"
+            }
+
+            if open() {
+                div { class: "mobile-nav-backdrop", onclick: move |_| open.set(false) }
+            }
+
+            div {
+                id: DRAWER_ID,
+                class: if open() { "mobile-nav-drawer open" } else { "mobile-nav-drawer" },
+                role: "dialog",
+                aria_modal: "true",
+                aria_label: "Site navigation",
+                onkeydown: move |event| {
+                    if event.key() == Key::Escape {
+                        open.set(false);
+                    }
+                },
+                button {
+                    r#type: "button",
+                    class: "mobile-nav-close",
+                    aria_label: "Close menu",
+                    onclick: move |_| open.set(false),
+                    "✕"
+                }
+                // Clicks on the links bubble up here, so following one closes the drawer
+                nav { class: "mobile-nav-links", onclick: move |_| open.set(false), {children} }
+            }
+        }
+    }
+}
+
+/// [`MobileNav`] for a generated page, mounted as an island inside its static `#navbar`. The drawer repeats the links
+/// already in the navbar, so it follows whatever URLs the generator wrote for the output style.
+#[component]
+pub fn StaticMobileNav() -> Element {
+    let links = use_hook(navbar_links);
+
+    use_effect(|| {
+        #[cfg(feature = "web")]
+        if let Some(navbar) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("navbar"))
+        {
+            let _ = navbar.class_list().add_1(HAS_MOBILE_NAV_CLASS);
+        }
+    });
+
+    rsx! {
+        MobileNav {
+            for (label, href) in links {
+                a { key: "{href}", href: "{href}", "{label}" }
+            }
+        }
+    }
+}
+
+/// Label and `href` of every link directly inside `#navbar`.
+fn navbar_links() -> Vec<(String, String)> {
+    #[cfg(feature = "web")]
+    {
+        let Some(links) = web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.query_selector_all("#navbar > a").ok())
+        else {
+            return Vec::new();
+        };
+
+        (0..links.length())
+            .filter_map(|index| links.item(index))
+            .filter_map(|node| {
+                use wasm_bindgen::JsCast;
+                let link = node.dyn_into::<web_sys::Element>().ok()?;
+                Some((
+                    link.text_content()?.trim().to_string(),
+                    link.get_attribute("href")?,
+                ))
+            })
+            .collect()
+    }
+
+    #[cfg(not(feature = "web"))]
+    Vec::new()
+}
@@ -7,3 +7,78 @@ pub use hero::Hero;
 
 mod echo;
 pub use echo::Echo;
+
+mod heading;
+pub use heading::{Heading, Prose};
+
+mod callout;
+pub use callout::{Callout, Note, Tip, Warning};
+
+mod img;
+pub use img::{Img, ImgLayout};
+
+mod save_button;
+pub use save_button::{SaveButton, SaveButtonProps};
+
+mod reading_progress;
+pub use reading_progress::ReadingProgress;
+
+mod reactions;
+pub use reactions::{Reactions, ReactionsProps};
+
+mod site_search;
+pub use site_search::SiteSearch;
+
+mod phone_input;
+pub use phone_input::PhoneInput;
+
+mod reason_select;
+pub use reason_select::ReasonSelect;
+
+mod connectivity;
+pub use connectivity::ConnectivityStatus;
+
+mod contact_fields;
+pub use contact_fields::{use_contact_form, use_sample_form, ContactFields, ContactForm};
+
+mod nav_link;
+pub use nav_link::NavLink;
+
+mod nav_search;
+pub use nav_search::NavSearch;
+
+mod mobile_nav;
+pub use mobile_nav::{MobileNav, StaticMobileNav, HAS_MOBILE_NAV_CLASS};
+
+mod playground;
+pub use playground::{Playground, PlaygroundProps, PostBody, PLAYGROUND_EXECUTE_URL};
+
+mod slideshow;
+pub use slideshow::{Slideshow, NOTES_CLASS, SLIDES_ID, SLIDE_CLASS};
+
+mod hints;
+pub use hints::Hints;
+
+mod not_found;
+pub use not_found::{NotFoundSuggestions, NotFoundSuggestionsProps};
+
+mod narration_player;
+pub use narration_player::{NarrationPlayer, NarrationPlayerProps};
+
+mod on_this_day;
+pub use on_this_day::OnThisDay;
+
+/// Every stylesheet a component loads when it first renders, for [`prefetch`](crate::prefetch) to fetch ahead of it.
+#[cfg(feature = "web")]
+pub(crate) const STYLESHEETS: &[dioxus::prelude::Asset] = &[
+    connectivity::CONNECTIVITY_CSS,
+    echo::ECHO_CSS,
+    hints::HINTS_CSS,
+    img::IMG_CSS,
+    narration_player::NARRATION_CSS,
+    on_this_day::ON_THIS_DAY_CSS,
+    reactions::REACTIONS_CSS,
+    reading_progress::READING_PROGRESS_CSS,
+    site_search::SEARCH_CSS,
+    slideshow::SLIDESHOW_CSS,
+];
@@ -0,0 +1,79 @@
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+
+pub(super) const NARRATION_CSS: Asset = asset!("/assets/styling/narration.css");
+
+/// Id of the `<audio>` element, for the speed control.
+const AUDIO_ID: &str = "narration-audio";
+
+/// Speeds offered by the speed control; 1 is selected to begin with.
+const SPEEDS: [f64; 6] = [0.75, 1.0, 1.25, 1.5, 1.75, 2.0];
+
+/// Player for a post's narration (`audio` on the post), shown at the top of the post: the browser's own audio controls,
+/// which are keyboard accessible and announced by screen readers, and a labelled select for the playback speed.
+///
+/// Nothing is downloaded until the reader presses play. The SPA blog view renders it itself; generated posts have the
+/// same `<audio>` element in `#narration-root`, which it mounts over as an island to add the speed control.
+#[component]
+pub fn NarrationPlayer(src: String, media_type: Option<String>) -> Element {
+    let mut speed = use_signal(|| 1.0);
+
+    use_effect(move || set_playback_rate(speed()));
+
+    rsx! {
+        Stylesheet { href: NARRATION_CSS }
+
+        figure {
+            class: "narration",
+            figcaption { id: "narration-label", "Listen to this post" }
+            audio {
+                id: AUDIO_ID,
+                controls: true,
+                preload: "none",
+                aria_labelledby: "narration-label",
+                source { src: "{src}", r#type: media_type }
+                "Your browser can't play this audio. "
+                a { href: "{src}", download: true, "Download the narration" }
+            }
+            label {
+                class: "narration-speed",
+                "Speed "
+                select {
+                    onchange: move |event| {
+                        if let Ok(value) = event.value().parse() {
+                            speed.set(value);
+                        }
+                    },
+                    for rate in SPEEDS {
+                        option {
+                            key: "{rate}",
+                            value: "{rate}",
+                            selected: rate == speed(),
+                            "{rate}×"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Play the narration at `rate`. The default rate is set too, since the browser resets the rate to it when it starts
+/// loading the audio, which with `preload="none"` is on the first press of play.
+#[cfg(feature = "web")]
+fn set_playback_rate(rate: f64) {
+    use wasm_bindgen::JsCast;
+
+    let Some(audio) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(AUDIO_ID))
+        .and_then(|element| element.dyn_into::<web_sys::HtmlMediaElement>().ok())
+    else {
+        return;
+    };
+    audio.set_default_playback_rate(rate);
+    audio.set_playback_rate(rate);
+}
+
+#[cfg(not(feature = "web"))]
+fn set_playback_rate(_rate: f64) {}
@@ -0,0 +1,19 @@
+use crate::Route;
+use dioxus::prelude::*;
+
+/// Link in a navigation menu: the router's `Link` to `to`, which marks the link to the current page with the `active`
+/// class and `aria-current="page"`.
+#[component]
+pub fn NavLink(to: Route, children: Element) -> Element {
+    let current = use_route::<Route>();
+    let aria_current = (current == to).then_some("page");
+
+    rsx! {
+        Link {
+            to,
+            active_class: "active",
+            aria_current,
+            {children}
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use crate::posts;
+use crate::site_search::{self, Suggestion, QUERY_PARAM, SEARCH_PATH};
+use dioxus::prelude::*;
+use dioxus::router::RouterContext;
+
+/// Results shown under the box; the rest are on the search page.
+const MAX_RESULTS: usize = 5;
+
+/// Id of the result list, for the input's `aria-controls`. There is one navbar per page.
+const LISTBOX_ID: &str = "nav-search-results";
+
+/// Compact search box in the navbar, listing the top results from [`site_search`] as the query is typed.
+///
+/// Arrow keys move through the results, Enter follows the highlighted one (or, with none highlighted, opens the search
+/// page with the query) and Escape closes the list. In the SPA the results are followed with the router; mounted as an
+/// island over the plain search form in a generated page's navbar, they're ordinary links, and the list ends with one
+/// to every result on the search page. `navbar.css` shows it on wide screens only, next to the links that move into
+/// the [`MobileNav`](super::MobileNav) drawer on small ones.
+#[component]
+pub fn NavSearch() -> Element {
+    let mut query = use_signal(String::new);
+    let mut open = use_signal(|| false);
+    let mut active = use_signal(|| None::<usize>);
+    let results = use_memo(move || {
+        site_search::search(&query.read(), posts::today()).suggestions(MAX_RESULTS)
+    });
+    let router = try_consume_context::<RouterContext>();
+    // The search page is only generated for the static site
+    let search_page = router.is_none().then(|| search_url(&query.read()));
+
+    let expanded = open() && !query.read().trim().is_empty();
+    let mut follow = move |href: &str| {
+        open.set(false);
+        active.set(None);
+        go(router, href);
+    };
+
+    rsx! {
+        form {
+            class: "nav-search",
+            role: "search",
+            method: "get",
+            action: SEARCH_PATH,
+            onsubmit: move |event| event.prevent_default(),
+            input {
+                r#type: "search",
+                name: QUERY_PARAM,
+                placeholder: "Search",
+                aria_label: "Search posts and projects",
+                role: "combobox",
+                autocomplete: "off",
+                aria_autocomplete: "list",
+                aria_controls: LISTBOX_ID,
+                aria_expanded: "{expanded}",
+                aria_activedescendant: active().map(option_id),
+                value: "{query}",
+                oninput: move |event| {
+                    query.set(event.value());
+                    active.set(None);
+                    open.set(true);
+                },
+                onfocus: move |_| open.set(true),
+                onblur: move |_| open.set(false),
+                onkeydown: move |event| {
+                    let count = results.read().len();
+                    match event.key() {
+                        Key::ArrowDown if count > 0 => {
+                            event.prevent_default();
+                            open.set(true);
+                            active.set(Some(active().map_or(0, |index| (index + 1) % count)));
+                        }
+                        Key::ArrowUp if count > 0 => {
+                            event.prevent_default();
+                            open.set(true);
+                            active.set(Some(active().map_or(count - 1, |index| (index + count - 1) % count)));
+                        }
+                        Key::Enter => {
+                            event.prevent_default();
+                            let href = match active() {
+                                Some(index) => results.read().get(index).map(|result| result.href.clone()),
+                                None => Some(search_url(&query.read())).filter(|_| router.is_none()),
+                            };
+                            if let Some(href) = href {
+                                follow(&href);
+                            }
+                        }
+                        Key::Escape => {
+                            open.set(false);
+                            active.set(None);
+                        }
+                        _ => {}
+                    }
+                },
+            }
+
+            if expanded {
+                ul {
+                    id: LISTBOX_ID,
+                    class: "nav-search-results",
+                    role: "listbox",
+                    aria_label: "Search results",
+                    if results.read().is_empty() {
+                        li { class: "nav-search-empty", "Nothing found" }
+                    }
+                    for (index, result) in results.read().iter().cloned().enumerate() {
+                        li {
+                            key: "{result.href}",
+                            id: option_id(index),
+                            role: "option",
+                            aria_selected: "{active() == Some(index)}",
+                            // Before the input's blur closes the list, so the click lands
+                            onmousedown: move |event| event.prevent_default(),
+                            {result_link(result, router, follow)}
+                        }
+                    }
+                    if let Some(href) = search_page {
+                        li {
+                            class: "nav-search-all",
+                            onmousedown: move |event| event.prevent_default(),
+                            a { href, "All results" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn result_link(
+    result: Suggestion,
+    router: Option<RouterContext>,
+    mut follow: impl FnMut(&str) + 'static,
+) -> Element {
+    let href = result.href.clone();
+    rsx! {
+        a {
+            href: "{result.href}",
+            tabindex: "-1",
+            onclick: move |event| {
+                if router.is_some() {
+                    event.prevent_default();
+                    follow(&href);
+                }
+            },
+            span { class: "nav-search-title", "{result.title}" }
+            span { class: "nav-search-detail", "{result.detail}" }
+        }
+    }
+}
+
+fn option_id(index: usize) -> String {
+    format!("{}-{}", LISTBOX_ID, index)
+}
+
+/// The search page with `query` filled in.
+fn search_url(query: &str) -> String {
+    let query = query.trim();
+    if query.is_empty() {
+        return SEARCH_PATH.to_string();
+    }
+    #[cfg(feature = "web")]
+    if let Ok(params) = web_sys::UrlSearchParams::new() {
+        params.set(QUERY_PARAM, query);
+        return format!("{}?{}", SEARCH_PATH, String::from(params.to_string()));
+    }
+    format!("{}?{}={}", SEARCH_PATH, QUERY_PARAM, query)
+}
+
+/// Follow `href`: with the router in the SPA, as a page load otherwise.
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn go(router: Option<RouterContext>, href: &str) {
+    match router.filter(|_| href.starts_with('/')) {
+        Some(router) => {
+            router.push(href);
+        }
+        #[cfg(feature = "web")]
+        None => {
+            if let Some(window) = web_sys::window() {
+                let _ = window.location().set_href(href);
+            }
+        }
+        #[cfg(not(feature = "web"))]
+        None => {}
+    }
+}
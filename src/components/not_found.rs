@@ -0,0 +1,100 @@
+use crate::not_found::{self, PageLink};
+use dioxus::prelude::*;
+
+/// "Did you mean…" links on the generated 404 page: the pages closest to the path that wasn't found (see
+/// [`not_found`](crate::not_found)), out of `pages`. Renders nothing when none comes close.
+///
+/// The miss is also reported to the client errors endpoint from `report_rate` of page views, like the island loader's
+/// error reports, with the path of the page that linked to it when that's on this site, so `site client-errors` shows
+/// the broken links worth fixing. Mounted as an island into `#not-found-root`; the pages come from its `data-pages`.
+#[component]
+pub fn NotFoundSuggestions(pages: Vec<PageLink>, report_rate: f64) -> Element {
+    let requested = use_hook(|| {
+        let requested = requested_path();
+        report_miss(&requested, report_rate);
+        requested
+    });
+    let suggestions = not_found::suggestions(&requested, &pages);
+
+    if suggestions.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        section {
+            class: "not-found-suggestions",
+            aria_labelledby: "not-found-suggestions",
+            h2 { id: "not-found-suggestions", "Did you mean…" }
+            ul {
+                for page in suggestions {
+                    li {
+                        key: "{page.path}",
+                        a { href: "{page.path}", "{page.title}" }
+                        " "
+                        span { class: "not-found-path", "{page.path}" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The path the reader asked for. Hosts serve the 404 page in place of it, so it's still in the address bar.
+fn requested_path() -> String {
+    #[cfg(feature = "web")]
+    {
+        if let Some(path) = web_sys::window().and_then(|window| window.location().pathname().ok()) {
+            return path;
+        }
+    }
+    String::new()
+}
+
+/// Send a [`NotFound`](crate::api::ClientErrorKind::NotFound) report for `requested` from `rate` of page views.
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn report_miss(requested: &str, rate: f64) {
+    #[cfg(feature = "web")]
+    {
+        use crate::api::client_errors::{
+            ClientErrorKind, ClientErrorReport, CLIENT_ERRORS_ENDPOINT,
+        };
+
+        if requested.is_empty() || js_sys::Math::random() >= rate {
+            return;
+        }
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        // The page that linked here, when it's one of ours; other sites' pages aren't ours to log
+        let referrer = window
+            .document()
+            .map(|document| document.referrer())
+            .and_then(|referrer| web_sys::Url::new(&referrer).ok())
+            .filter(|referrer| {
+                window
+                    .location()
+                    .origin()
+                    .is_ok_and(|origin| origin == referrer.origin())
+            })
+            .map(|referrer| referrer.pathname())
+            .unwrap_or_default();
+
+        let report = ClientErrorReport {
+            kind: ClientErrorKind::NotFound,
+            page: requested.to_string(),
+            category: None,
+            island: None,
+            message: referrer,
+            source: None,
+            line: None,
+            column: None,
+        }
+        .scrubbed();
+        let Ok(body) = serde_json::to_string(&report) else {
+            return;
+        };
+        let _ = window
+            .navigator()
+            .send_beacon_with_opt_str(CLIENT_ERRORS_ENDPOINT, Some(&body));
+    }
+}
@@ -0,0 +1,46 @@
+use crate::posts;
+use crate::styles::Stylesheet;
+use crate::Route;
+use dioxus::prelude::*;
+
+pub(super) const ON_THIS_DAY_CSS: Asset = asset!("/assets/styling/on_this_day.css");
+
+/// "On this day": posts published on today's date in earlier years, on the home page and the blog index. Renders
+/// nothing on days without any.
+///
+/// Generated pages have the list from the day they were built in `#on-this-day-root`, which this mounts over as an
+/// island, so a page served from a cache for days still shows the reader's day. The posts are picked after the first
+/// render for the same reason: a prerendered SPA page from another day hydrates without a list that shouldn't be there.
+#[component]
+pub fn OnThisDay() -> Element {
+    let mut day = use_signal(|| None);
+    use_effect(move || day.set(Some(posts::today())));
+
+    let Some(today) = day() else {
+        return rsx! {};
+    };
+    let found = posts::on_this_day(today);
+    if found.is_empty() {
+        return rsx! {};
+    }
+
+    rsx! {
+        Stylesheet { href: ON_THIS_DAY_CSS }
+
+        section {
+            class: "on-this-day",
+            aria_labelledby: "on-this-day-title",
+            h2 { id: "on-this-day-title", class: "on-this-day-title", "On this day" }
+            ul {
+                for post in found {
+                    li {
+                        key: "{post.slug}",
+                        Link { to: Route::Blog { slug: post.slug.clone() }, "{post.title}" }
+                        " "
+                        span { class: "on-this-day-when", "{post.years_ago_label(today)}" }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::phone;
+use dioxus::prelude::*;
+
+/// Optional phone number field with a country-code selector.
+///
+/// The parent owns both values and validates them with [`phone::normalize`] on submit. `invalid` marks the input
+/// with the error style.
+#[component]
+pub fn PhoneInput(
+    label: String,
+    placeholder: String,
+    country: String,
+    number: String,
+    on_country: EventHandler<String>,
+    on_number: EventHandler<String>,
+    invalid: bool,
+) -> Element {
+    rsx! {
+        div {
+            class: "form-group",
+            label { "for": "phone", "{label}" }
+            div {
+                class: "phone-input",
+                select {
+                    id: "phone-country",
+                    name: "phone_country",
+                    class: "form-input phone-country",
+                    "aria-label": "Country calling code",
+                    value: "{country}",
+                    onchange: move |e| on_country.call(e.value()),
+                    for option in phone::COUNTRIES {
+                        option {
+                            key: "{option.iso}",
+                            value: "{option.iso}",
+                            selected: country == option.iso,
+                            "{option.name} (+{option.calling_code})"
+                        }
+                    }
+                }
+                input {
+                    r#type: "tel",
+                    id: "phone",
+                    name: "phone",
+                    class: if invalid { "form-input error" } else { "form-input" },
+                    autocomplete: "tel",
+                    placeholder: "{placeholder}",
+                    value: "{number}",
+                    oninput: move |e| on_number.call(e.value()),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::components::{Callout, Prose};
+use crate::html_sanitizer;
+use crate::shortcodes::{self, Segment};
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// The Rust Playground's endpoint that compiles and runs a program. It allows cross-origin requests, and the site's
+/// Content Security Policy lists it under `connect-src`.
+pub const PLAYGROUND_EXECUTE_URL: &str = "https://play.rust-lang.org/execute";
+
+#[cfg_attr(not(feature = "web"), allow(dead_code))]
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ExecuteRequest<'a> {
+    channel: &'a str,
+    mode: &'a str,
+    edition: &'a str,
+    crate_type: &'a str,
+    tests: bool,
+    backtrace: bool,
+    code: &'a str,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+struct ExecuteResponse {
+    success: bool,
+    /// Compiler output and anything the program wrote to stderr
+    stderr: String,
+    stdout: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum RunState {
+    Idle,
+    Running,
+    Finished(ExecuteResponse),
+    Failed(String),
+}
+
+/// An editable Rust example with a run button. The code is compiled and run on the Rust Playground, and what it
+/// printed is shown below the editor.
+#[component]
+pub fn Playground(code: String) -> Element {
+    let original = use_hook(|| code.clone());
+    let mut source = use_signal(|| code.clone());
+    let mut state = use_signal(|| RunState::Idle);
+    let running = state() == RunState::Running;
+
+    let run = move |_| {
+        state.set(RunState::Running);
+        spawn(async move {
+            let result = match execute(&source()).await {
+                Ok(response) => RunState::Finished(response),
+                Err(problem) => RunState::Failed(problem),
+            };
+            state.set(result);
+        });
+    };
+
+    let reset = {
+        let original = original.clone();
+        move |_| {
+            source.set(original.clone());
+            state.set(RunState::Idle);
+        }
+    };
+
+    let rows = source.read().lines().count().clamp(3, 30);
+
+    rsx! {
+        div {
+            class: "playground playground-live",
+            textarea {
+                class: "playground-editor",
+                aria_label: "Rust code",
+                spellcheck: "false",
+                rows: "{rows}",
+                value: "{source}",
+                oninput: move |e| source.set(e.value()),
+            }
+            div {
+                class: "playground-actions",
+                button {
+                    r#type: "button",
+                    class: "playground-run",
+                    disabled: running,
+                    onclick: run,
+                    if running { "Running…" } else { "▶ Run" }
+                }
+                if source() != original {
+                    button {
+                        r#type: "button",
+                        class: "playground-reset",
+                        onclick: reset,
+                        "Reset"
+                    }
+                }
+                a {
+                    href: shortcodes::playground_url(&source()),
+                    rel: "noopener",
+                    target: "_blank",
+                    "Open on the Rust Playground"
+                }
+            }
+            div {
+                class: "playground-output",
+                role: "status",
+                aria_live: "polite",
+                match state() {
+                    RunState::Idle => rsx! {},
+                    RunState::Running => rsx! {
+                        p { class: "playground-note", "Compiling and running…" }
+                    },
+                    RunState::Finished(response) => rsx! {
+                        if !response.success {
+                            p { class: "playground-note playground-error", "The program didn't build or exited with an error." }
+                        }
+                        if !response.stdout.is_empty() {
+                            pre { class: "playground-stdout", "{response.stdout}" }
+                        }
+                        if !response.stderr.is_empty() {
+                            pre { class: "playground-stderr", "{response.stderr}" }
+                        }
+                    },
+                    RunState::Failed(problem) => rsx! {
+                        p { class: "playground-note playground-error", "Couldn't reach the Rust Playground: {problem}" }
+                    },
+                }
+            }
+        }
+    }
+}
+
+/// A post body with its shortcodes as live components: HTML stays HTML (sanitized, like on the generated pages),
+/// playgrounds become [`Playground`]s and callouts [`Callout`]s.
+#[component]
+pub fn PostBody(body: &'static str) -> Element {
+    let segments = shortcodes::parse(body);
+
+    rsx! {
+        Prose {
+            class: "blog-content",
+            for (index, segment) in segments.into_iter().enumerate() {
+                match segment {
+                    Segment::Html(html) => rsx! {
+                        div { key: "{index}", dangerous_inner_html: html_sanitizer::sanitize(html) }
+                    },
+                    Segment::Playground { code } => rsx! {
+                        Playground { key: "{index}", code }
+                    },
+                    Segment::Callout { kind, html } => rsx! {
+                        Callout { key: "{index}", kind,
+                            div { dangerous_inner_html: html_sanitizer::sanitize(html) }
+                        }
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+async fn execute(code: &str) -> Result<ExecuteResponse, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let body = serde_json::to_string(&ExecuteRequest {
+        channel: "stable",
+        mode: "debug",
+        edition: "2021",
+        crate_type: "bin",
+        tests: false,
+        backtrace: false,
+        code,
+    })
+    .map_err(|e| e.to_string())?;
+
+    let headers = web_sys::Headers::new().map_err(|_| "couldn't create headers")?;
+    let _ = headers.set("Content-Type", "application/json");
+    let init = web_sys::RequestInit::new();
+    init.set_method("POST");
+    init.set_headers(&headers);
+    init.set_body(&wasm_bindgen::JsValue::from_str(&body));
+
+    let response = JsFuture::from(window.fetch_with_str_and_init(PLAYGROUND_EXECUTE_URL, &init))
+        .await
+        .map_err(|_| "network error".to_string())?
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| "not a response".to_string())?;
+    if !response.ok() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+    let text = JsFuture::from(response.text().map_err(|_| "unreadable response")?)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .unwrap_or_default();
+
+    serde_json::from_str(&text).map_err(|e| e.to_string())
+}
+
+#[cfg(not(feature = "web"))]
+async fn execute(_code: &str) -> Result<ExecuteResponse, String> {
+    Err("not running in a browser".to_string())
+}
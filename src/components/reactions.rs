@@ -0,0 +1,183 @@
+use crate::api::reactions::{
+    Reaction, ReactionCounts, ReactionRequest, ReactionResponse, REACTIONS_ENDPOINT,
+};
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+use std::collections::BTreeMap;
+
+pub(super) const REACTIONS_CSS: Asset = asset!("/assets/styling/reactions.css");
+
+/// The reactions this browser has left, by post, kept in localStorage so the buttons stay pressed.
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "reactions";
+
+/// 👍 ❤️ 🎉 buttons under a post, with how often each was left. No account is needed; the server counts a reaction
+/// once per visitor (see [`REACTIONS_ENDPOINT`]).
+///
+/// `counts` are the ones baked into the page at build time; the current counts replace them once loaded. A click
+/// counts straight away and is taken back if the server doesn't accept it. On generated pages the island mounts over
+/// a plain form with the same buttons, which posts the reaction without JavaScript.
+#[component]
+pub fn Reactions(post: String, #[props(default)] counts: ReactionCounts) -> Element {
+    let mut counts = use_signal(|| counts);
+    // Read once mounted, so hydration sees the same markup the page was generated with
+    let mut given = use_signal(Vec::new);
+    use_effect({
+        let post = post.clone();
+        move || given.set(load_given(&post))
+    });
+
+    use_future({
+        let post = post.clone();
+        move || {
+            let post = post.clone();
+            async move {
+                let url = format!("{}?post={}", REACTIONS_ENDPOINT, post);
+                if let Ok(ReactionResponse::Counted {
+                    counts: current, ..
+                }) = request("GET", &url, None).await
+                {
+                    counts.set(current);
+                }
+            }
+        }
+    });
+
+    let react = move |reaction: Reaction| {
+        let post = post.clone();
+        if given.peek().contains(&reaction) {
+            return;
+        }
+
+        // Optimistic: show the reaction now and undo it if the server doesn't take it
+        counts.write().add(reaction);
+        given.write().push(reaction);
+        store_given(&post, &given.peek());
+
+        spawn(async move {
+            let body = ReactionRequest {
+                post: post.clone(),
+                reaction,
+            };
+            match request("POST", REACTIONS_ENDPOINT, Some(&body)).await {
+                Ok(ReactionResponse::Counted {
+                    counts: current, ..
+                }) => counts.set(current),
+                result => {
+                    if let Err(problem) = result {
+                        crate::logger::warn(&format!("⚠️ Reaction not sent: {}", problem));
+                    }
+                    if let Some(count) = counts.write().0.get_mut(&reaction) {
+                        *count = count.saturating_sub(1);
+                    }
+                    given.write().retain(|given| *given != reaction);
+                    store_given(&post, &given.peek());
+                }
+            }
+        });
+    };
+
+    rsx! {
+        Stylesheet { href: REACTIONS_CSS }
+
+        div {
+            class: "reactions",
+            role: "group",
+            aria_label: "Reactions",
+            for reaction in Reaction::ALL {
+                button {
+                    key: "{reaction.id()}",
+                    r#type: "button",
+                    class: "reaction",
+                    aria_pressed: "{given.read().contains(&reaction)}",
+                    aria_label: "{reaction.label()} ({counts.read().get(reaction)})",
+                    onclick: {
+                        let mut react = react.clone();
+                        move |_| react(reaction)
+                    },
+                    span { aria_hidden: "true", "{reaction.emoji()}" }
+                    span { class: "reaction-count", "{counts.read().get(reaction)}" }
+                }
+            }
+        }
+    }
+}
+
+fn load_given(post: &str) -> Vec<Reaction> {
+    let mut all = load_all();
+    all.remove(post).unwrap_or_default()
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn store_given(post: &str, given: &[Reaction]) {
+    let mut all = load_all();
+    all.insert(post.to_string(), given.to_vec());
+
+    #[cfg(feature = "web")]
+    {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(&all)) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+fn load_all() -> BTreeMap<String, Vec<Reaction>> {
+    #[cfg(feature = "web")]
+    {
+        let stored = web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+        if let Some(json) = stored {
+            return serde_json::from_str(&json).unwrap_or_default();
+        }
+    }
+
+    BTreeMap::new()
+}
+
+/// Send a request to the endpoint and read its answer. Anything that isn't a [`ReactionResponse`] is a failure.
+#[cfg(feature = "web")]
+async fn request(
+    method: &str,
+    url: &str,
+    body: Option<&ReactionRequest>,
+) -> Result<ReactionResponse, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+    let headers = web_sys::Headers::new().map_err(|_| "couldn't create headers")?;
+    let _ = headers.set("Accept", "application/json");
+    let init = web_sys::RequestInit::new();
+    init.set_method(method);
+    if let Some(body) = body {
+        let body = serde_json::to_string(body).map_err(|e| e.to_string())?;
+        let _ = headers.set("Content-Type", "application/json");
+        init.set_body(&wasm_bindgen::JsValue::from_str(&body));
+    }
+    init.set_headers(&headers);
+
+    let response = JsFuture::from(window.fetch_with_str_and_init(url, &init))
+        .await
+        .map_err(|_| "network error".to_string())?
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| "not a response".to_string())?;
+    let status = response.status();
+    let text = JsFuture::from(response.text().map_err(|_| "unreadable response")?)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .unwrap_or_default();
+
+    serde_json::from_str(&text).map_err(|_| format!("HTTP {} without a reactions response", status))
+}
+
+#[cfg(not(feature = "web"))]
+async fn request(
+    _method: &str,
+    _url: &str,
+    _body: Option<&ReactionRequest>,
+) -> Result<ReactionResponse, String> {
+    Err("not running in a browser".to_string())
+}
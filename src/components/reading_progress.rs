@@ -0,0 +1,116 @@
+use crate::styles::Stylesheet;
+use dioxus::core::use_drop;
+use dioxus::prelude::*;
+
+pub(super) const READING_PROGRESS_CSS: Asset = asset!("/assets/styling/reading_progress.css");
+
+/// Thin bar along the top of the window that fills as the reader scrolls through the page.
+///
+/// It's fixed to the viewport and drawn with a transform, so it never moves the page, and it only animates between
+/// updates for readers who haven't asked for reduced motion. Only long posts get it (see
+/// [`reading_progress`](crate::reading_progress)): the SPA blog view renders it itself, and generated posts have a
+/// `#reading-progress-root` element it mounts into as an island.
+#[component]
+pub fn ReadingProgress() -> Element {
+    let progress = use_signal(scroll_fraction);
+    // Nothing to listen to outside the browser
+    #[cfg_attr(not(feature = "web"), allow(clippy::let_unit_value))]
+    let listener = use_hook(|| listen(progress));
+    use_drop(move || stop(&listener));
+
+    // Overscrolling (rubber-banding on touch screens) can take the position past either end
+    let fraction = progress().clamp(0.0, 1.0);
+    let percent = (fraction * 100.0).round();
+
+    rsx! {
+        Stylesheet { href: READING_PROGRESS_CSS }
+
+        div {
+            class: "reading-progress",
+            role: "progressbar",
+            aria_label: "Reading progress",
+            aria_valuemin: "0",
+            aria_valuemax: "100",
+            aria_valuenow: "{percent}",
+            div { class: "reading-progress-bar", style: "transform: scaleX({fraction})" }
+        }
+    }
+}
+
+/// How far down the page the reader has scrolled, from 0 at the top to 1 at the bottom. A page that fits in the window
+/// counts as read.
+fn scroll_fraction() -> f64 {
+    #[cfg(feature = "web")]
+    {
+        let Some(window) = web_sys::window() else {
+            return 0.0;
+        };
+        let scrolled = window.scroll_y().unwrap_or(0.0);
+        let viewport = window
+            .inner_height()
+            .ok()
+            .and_then(|height| height.as_f64())
+            .unwrap_or(0.0);
+        let height = window
+            .document()
+            .and_then(|document| document.document_element())
+            .map_or(0.0, |element| f64::from(element.scroll_height()));
+        let scrollable = height - viewport;
+        if scrollable <= 0.0 {
+            return 1.0;
+        }
+        return scrolled / scrollable;
+    }
+
+    #[allow(unreachable_code)]
+    0.0
+}
+
+/// The `scroll` and `resize` listener, removed again when the component is.
+#[cfg(feature = "web")]
+type Listener = std::rc::Rc<wasm_bindgen::closure::Closure<dyn FnMut()>>;
+#[cfg(not(feature = "web"))]
+type Listener = ();
+
+#[cfg(feature = "web")]
+fn listen(mut progress: Signal<f64>) -> Listener {
+    use dioxus::core::Runtime;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    // The events arrive outside the Dioxus runtime, so the update is run in this app's root scope
+    let runtime = Runtime::current();
+    let listener = Closure::<dyn FnMut()>::new(move || {
+        runtime.in_scope(ScopeId::ROOT, || {
+            if let Ok(mut progress) = progress.try_write() {
+                *progress = scroll_fraction();
+            }
+        });
+    });
+    if let Some(window) = web_sys::window() {
+        for event in ["scroll", "resize"] {
+            let _ =
+                window.add_event_listener_with_callback(event, listener.as_ref().unchecked_ref());
+        }
+    }
+    std::rc::Rc::new(listener)
+}
+
+#[cfg(not(feature = "web"))]
+fn listen(_progress: Signal<f64>) -> Listener {}
+
+#[cfg(feature = "web")]
+fn stop(listener: &Listener) {
+    use wasm_bindgen::JsCast;
+
+    if let Some(window) = web_sys::window() {
+        for event in ["scroll", "resize"] {
+            let _ = window.remove_event_listener_with_callback(
+                event,
+                listener.as_ref().as_ref().unchecked_ref(),
+            );
+        }
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn stop(_listener: &Listener) {}
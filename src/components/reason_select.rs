@@ -0,0 +1,28 @@
+use crate::contact_reasons::REASONS;
+use dioxus::prelude::*;
+
+/// "Reason for contact" selector. The chosen reason decides who receives the message.
+#[component]
+pub fn ReasonSelect(label: String, reason: String, onchange: EventHandler<String>) -> Element {
+    rsx! {
+        div {
+            class: "form-group",
+            label { "for": "reason", "{label}" }
+            select {
+                id: "reason",
+                name: "reason",
+                class: "form-input",
+                value: "{reason}",
+                onchange: move |e| onchange.call(e.value()),
+                for option in REASONS {
+                    option {
+                        key: "{option.id}",
+                        value: "{option.id}",
+                        selected: reason == option.id,
+                        "{option.label}"
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use crate::global_state::{use_global_state, StateChange};
+use crate::saved_posts;
+use crate::styles::{self, ScopedStylesheet};
+use dioxus::prelude::*;
+
+/// Toggle button that adds a blog post to (or removes it from) the reader's saved posts list.
+///
+/// Used inside the SPA blog view and mounted on its own as an island on static blog pages. The saved state comes from
+/// the [`GlobalState`](crate::global_state::GlobalState), so a saved posts list elsewhere on the page updates with it.
+#[component]
+pub fn SaveButton(post: String) -> Element {
+    let mut state = use_global_state();
+    let saved = use_memo({
+        let post = post.clone();
+        move || state.saved_posts().read().contains(&post)
+    });
+
+    rsx! {
+        ScopedStylesheet { style: &styles::SAVED }
+
+        button {
+            r#type: "button",
+            class: if saved() { "{styles::SAVED.scope} save-button saved" } else { "{styles::SAVED.scope} save-button" },
+            "aria-pressed": "{saved}",
+            onclick: move |_| {
+                saved_posts::toggle(&post);
+                state.publish(StateChange::SavedPosts(saved_posts::load()));
+            },
+            if saved() { "★ Saved" } else { "☆ Save for later" }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use crate::site_search::{self, QUERY_PARAM};
+use crate::styles::{self, Stylesheet};
+use crate::{links, posts, Route};
+use dioxus::prelude::*;
+
+pub(super) const SEARCH_CSS: Asset = asset!("/assets/styling/search.css");
+
+/// Search box over the posts and projects, with the results underneath.
+///
+/// Mounted as an island over the plain search form on the static `/search/` page. The query starts from `?q=` in the
+/// page URL, which is where the site's OpenSearch description sends browser searches, and the URL follows the query as
+/// it's typed so a search can be shared or bookmarked. Links are plain anchors since there is no router on the page.
+#[component]
+pub fn SiteSearch() -> Element {
+    let mut query = use_signal(initial_query);
+    let results = use_memo(move || site_search::search(&query.read(), posts::today()));
+
+    rsx! {
+        Stylesheet { href: SEARCH_CSS }
+
+        form {
+            class: "{styles::BLOG.scope} site-search",
+            role: "search",
+            onsubmit: move |event| event.prevent_default(),
+            label { r#for: "site-search-query", "Search posts and projects" }
+            input {
+                id: "site-search-query",
+                r#type: "search",
+                name: QUERY_PARAM,
+                value: "{query}",
+                autofocus: true,
+                oninput: move |event| {
+                    let value = event.value();
+                    replace_query(&value);
+                    query.set(value);
+                },
+            }
+        }
+
+        if !query.read().trim().is_empty() {
+            p {
+                class: "site-search-summary",
+                role: "status",
+                match results.read().len() {
+                    0 => "Nothing found. Try fewer words, or browse the keywords below.".to_string(),
+                    1 => "1 result".to_string(),
+                    count => format!("{} results", count),
+                }
+            }
+
+            ul {
+                class: "blog-index",
+                for post in results.read().posts.iter() {
+                    li {
+                        key: "{post.slug}",
+                        class: "blog-index-entry",
+                        a { href: links::link_to(Route::Blog { slug: post.slug.to_string() }), "{post.title}" }
+                        p { class: "blog-date", "{post.date_label()}" }
+                        p { "{post.excerpt()}" }
+                    }
+                }
+            }
+
+            ul {
+                class: "site-search-projects",
+                for project in results.read().projects.iter() {
+                    li {
+                        key: "{project.name}",
+                        a { href: "{project.url}", "{project.name}" }
+                        " — {project.description}"
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `?q=` from the page URL.
+fn initial_query() -> String {
+    #[cfg(feature = "web")]
+    {
+        let search = web_sys::window().and_then(|window| window.location().search().ok());
+        if let Some(search) = search {
+            if let Ok(params) = web_sys::UrlSearchParams::new_with_str(&search) {
+                return params.get(QUERY_PARAM).unwrap_or_default();
+            }
+        }
+    }
+    String::new()
+}
+
+/// Put `query` in the page URL without adding a history entry for every keystroke.
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn replace_query(query: &str) {
+    #[cfg(feature = "web")]
+    {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Ok(path) = window.location().pathname() else {
+            return;
+        };
+        let url = match query.trim() {
+            "" => path,
+            query => match web_sys::UrlSearchParams::new() {
+                Ok(params) => {
+                    params.set(QUERY_PARAM, query);
+                    format!("{}?{}", path, String::from(params.to_string()))
+                }
+                Err(_) => return,
+            },
+        };
+        if let Ok(history) = window.history() {
+            let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use crate::styles::Stylesheet;
+use dioxus::prelude::*;
+
+pub(super) const SLIDESHOW_CSS: Asset = asset!("/assets/styling/slideshow.css");
+
+/// Id of the horizontally scrolling element holding a talk's slides.
+pub const SLIDES_ID: &str = "slides";
+
+/// Class of each slide in it.
+pub const SLIDE_CLASS: &str = "slide";
+
+/// Class of the `<details>` holding a slide's speaker notes.
+pub const NOTES_CLASS: &str = "speaker-notes";
+
+/// Controls for the slides of a generated talk page: previous and next buttons, the slide number, and a switch that
+/// opens every slide's speaker notes. The arrow keys, Page Up and Down, Space, Home and End move between slides, and
+/// `N` shows or hides the notes.
+///
+/// The slides are static markup, scrolled sideways with scroll snapping, so they can be read without the island; it
+/// scrolls them and follows scrolling done by hand (a swipe, a trackpad), and keeps the slide in the URL fragment.
+/// Mounted as an island into `#slideshow-root` below the slides.
+#[component]
+pub fn Slideshow() -> Element {
+    let count = use_hook(slide_count);
+    let mut current = use_hook(|| {
+        let current = Signal::new_in_scope(initial_slide(count), ScopeId::ROOT);
+        listen(current, count);
+        current
+    });
+    let mut notes = use_signal(|| false);
+
+    use_effect(move || scroll_to(current()));
+    use_effect(move || show_notes(notes()));
+
+    let mut go = move |slide: usize| current.set(slide.min(count.saturating_sub(1)));
+
+    if count == 0 {
+        return rsx! {};
+    }
+
+    rsx! {
+        Stylesheet { href: SLIDESHOW_CSS }
+
+        nav {
+            class: "slideshow-controls",
+            aria_label: "Slides",
+            button {
+                r#type: "button",
+                class: "slideshow-button",
+                disabled: current() == 0,
+                onclick: move |_| go(current().saturating_sub(1)),
+                "← Previous"
+            }
+            span { class: "slideshow-position", aria_live: "polite", "{current() + 1} / {count}" }
+            button {
+                r#type: "button",
+                class: "slideshow-button",
+                disabled: current() + 1 >= count,
+                onclick: move |_| go(current() + 1),
+                "Next →"
+            }
+            button {
+                r#type: "button",
+                class: "slideshow-button slideshow-notes",
+                aria_pressed: "{notes()}",
+                onclick: move |_| notes.toggle(),
+                if notes() { "Hide notes" } else { "Show notes" }
+            }
+        }
+    }
+}
+
+/// Number of the slide in the URL fragment (`#slide-3`), counted from 0.
+fn initial_slide(count: usize) -> usize {
+    #[cfg(feature = "web")]
+    {
+        let hash = web_sys::window().and_then(|window| window.location().hash().ok());
+        if let Some(number) = hash
+            .as_deref()
+            .and_then(|hash| hash.strip_prefix("#slide-"))
+            .and_then(|number| number.parse::<usize>().ok())
+        {
+            return number.clamp(1, count.max(1)) - 1;
+        }
+    }
+    let _ = count;
+    0
+}
+
+#[cfg(feature = "web")]
+fn slides() -> Option<web_sys::Element> {
+    web_sys::window()?.document()?.get_element_by_id(SLIDES_ID)
+}
+
+fn slide_count() -> usize {
+    #[cfg(feature = "web")]
+    {
+        if let Some(slides) = slides() {
+            return slides.get_elements_by_class_name(SLIDE_CLASS).length() as usize;
+        }
+    }
+    0
+}
+
+/// Scroll the slides to `slide` and put it in the URL fragment, unless they are there already.
+fn scroll_to(slide: usize) {
+    #[cfg(feature = "web")]
+    {
+        let Some(slides) = slides() else {
+            return;
+        };
+        let left = slide as f64 * f64::from(slides.client_width());
+        if (f64::from(slides.scroll_left()) - left).abs() >= 1.0 {
+            slides.scroll_to_with_x_and_y(left, 0.0);
+        }
+        if let Some(history) = web_sys::window().and_then(|window| window.history().ok()) {
+            let fragment = format!("#slide-{}", slide + 1);
+            let _ =
+                history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&fragment));
+        }
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = slide;
+}
+
+/// Open or close the speaker notes of every slide.
+fn show_notes(open: bool) {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::JsCast;
+
+        let Some(slides) = slides() else {
+            return;
+        };
+        let notes = slides.get_elements_by_class_name(NOTES_CLASS);
+        for index in 0..notes.length() {
+            if let Some(details) = notes
+                .item(index)
+                .and_then(|element| element.dyn_into::<web_sys::HtmlDetailsElement>().ok())
+            {
+                details.set_open(open);
+            }
+        }
+    }
+    #[cfg(not(feature = "web"))]
+    let _ = open;
+}
+
+/// Follow the keyboard, and scrolling the slides by hand. The listeners live as long as the page, like the islands.
+#[cfg(feature = "web")]
+fn listen(mut current: Signal<usize>, count: usize) {
+    use dioxus::core::Runtime;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let (Some(document), Some(slides)) = (
+        web_sys::window().and_then(|window| window.document()),
+        slides(),
+    ) else {
+        return;
+    };
+    let last = count.saturating_sub(1);
+
+    // The events arrive outside the Dioxus runtime, so the updates are run in this app's root scope
+    let runtime = Runtime::current();
+    let keys = {
+        let runtime = runtime.clone();
+        Closure::<dyn FnMut(web_sys::KeyboardEvent)>::new(move |event: web_sys::KeyboardEvent| {
+            if event.default_prevented()
+                || event.meta_key()
+                || event.ctrl_key()
+                || event.alt_key()
+                || typing(&event)
+            {
+                return;
+            }
+            runtime.in_scope(ScopeId::ROOT, || {
+                let slide = *current.peek();
+                let next = match event.key().as_str() {
+                    "ArrowRight" | "PageDown" | " " => slide.saturating_add(1).min(last),
+                    "ArrowLeft" | "PageUp" => slide.saturating_sub(1),
+                    "Home" => 0,
+                    "End" => last,
+                    "n" | "N" => {
+                        toggle_notes();
+                        return;
+                    }
+                    _ => return,
+                };
+                event.prevent_default();
+                if next != slide {
+                    current.set(next);
+                }
+            });
+        })
+    };
+    let _ = document.add_event_listener_with_callback("keydown", keys.as_ref().unchecked_ref());
+    keys.forget();
+
+    let scrolled = {
+        let slides = slides.clone();
+        Closure::<dyn FnMut()>::new(move || {
+            let width = f64::from(slides.client_width());
+            if width <= 0.0 {
+                return;
+            }
+            let slide = (f64::from(slides.scroll_left()) / width).round() as usize;
+            runtime.in_scope(ScopeId::ROOT, || {
+                if *current.peek() != slide {
+                    current.set(slide.min(last));
+                }
+            });
+        })
+    };
+    let _ = slides.add_event_listener_with_callback("scrollend", scrolled.as_ref().unchecked_ref());
+    scrolled.forget();
+}
+
+#[cfg(not(feature = "web"))]
+fn listen(_current: Signal<usize>, _count: usize) {}
+
+/// Whether a key went to a text field, where the arrow keys and Space belong to the field.
+#[cfg(feature = "web")]
+fn typing(event: &web_sys::KeyboardEvent) -> bool {
+    use wasm_bindgen::JsCast;
+
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .is_some_and(|element| {
+            matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+                || element.has_attribute("contenteditable")
+        })
+}
+
+/// Click the notes switch, so the keyboard and the button share its state.
+#[cfg(feature = "web")]
+fn toggle_notes() {
+    use wasm_bindgen::JsCast;
+
+    if let Some(button) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.query_selector(".slideshow-notes").ok().flatten())
+        .and_then(|button| button.dyn_into::<web_sys::HtmlElement>().ok())
+    {
+        button.click();
+    }
+}
@@ -1,10 +1,11 @@
 //! Simple DOM-based Contact Form
 //! This creates an interactive contact form using web APIs instead of full Dioxus mounting
 
+use crate::logger;
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use web_sys::{
-    console, window, Document, Element, Event, HtmlElement, HtmlInputElement, HtmlTextAreaElement,
+    window, Document, Element, Event, HtmlElement, HtmlInputElement, HtmlTextAreaElement,
 };
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -16,13 +17,6 @@ struct FormData {
     submitted_at: String,
 }
 
-#[derive(Debug, Clone)]
-enum FormState {
-    Editing,
-    Submitted(FormData),
-    Error(String),
-}
-
 /// Initialize the Contact form and replace the placeholder with interactive elements
 pub fn mount_contact_form() -> Result<(), JsValue> {
     let window = window().ok_or("No global window exists")?;
@@ -31,7 +25,7 @@ pub fn mount_contact_form() -> Result<(), JsValue> {
     // Find the placeholder and replace it with the interactive form
     if let Some(placeholder) = document.get_element_by_id("contact-form-placeholder") {
         create_interactive_form(&document, &placeholder)?;
-        console::log_1(&"✅ Interactive contact form created".into());
+        logger::debug("✅ Interactive contact form created");
     }
 
     Ok(())
@@ -79,34 +73,34 @@ fn create_interactive_form(document: &Document, placeholder: &Element) -> Result
 
     <style>
         .error-message {
-            color: #dc2626;
+            color: var(--color-red-600);
             font-size: 0.875rem;
-            margin-top: 0.25rem;
+            margin-top: var(--space-1);
             min-height: 1.25rem;
         }
         .form-status {
-            margin-top: 1rem;
-            padding: 1rem;
-            border-radius: 0.5rem;
+            margin-top: var(--space-4);
+            padding: var(--space-4);
+            border-radius: var(--radius-md);
             text-align: center;
             font-weight: 500;
         }
         .form-status.success {
-            background-color: #dcfce7;
-            color: #166534;
-            border: 1px solid #bbf7d0;
+            background-color: var(--color-green-100);
+            color: var(--color-green-800);
+            border: 1px solid var(--color-green-200);
         }
         .form-status.error {
-            background-color: #fef2f2;
-            color: #dc2626;
-            border: 1px solid #fecaca;
+            background-color: var(--color-red-50);
+            color: var(--color-red-600);
+            border: 1px solid var(--color-red-200);
         }
         .form-input:focus, .form-textarea:focus {
-            outline: 2px solid #3b82f6;
+            outline: 2px solid var(--color-blue-500);
             outline-offset: 2px;
         }
         .form-input.error, .form-textarea.error {
-            border-color: #dc2626;
+            border-color: var(--color-red-600);
         }
         .btn:disabled {
             opacity: 0.6;
@@ -132,7 +126,7 @@ fn setup_form_listeners(document: &Document) -> Result<(), JsValue> {
     let submit_handler = Closure::wrap(Box::new(move |e: Event| {
         e.prevent_default();
         if let Err(err) = handle_form_submit() {
-            console::error_1(&format!("Form submission error: {:?}", err).into());
+            logger::error(&format!("Form submission error: {:?}", err));
         }
     }) as Box<dyn FnMut(_)>);
 
@@ -146,7 +140,7 @@ fn setup_form_listeners(document: &Document) -> Result<(), JsValue> {
 
     let reset_handler = Closure::wrap(Box::new(move |_e: Event| {
         if let Err(err) = handle_form_reset() {
-            console::error_1(&format!("Form reset error: {:?}", err).into());
+            logger::error(&format!("Form reset error: {:?}", err));
         }
     }) as Box<dyn FnMut(_)>);
 
@@ -172,7 +166,7 @@ fn setup_validation_listeners(document: &Document) -> Result<(), JsValue> {
             let input_id_owned = input_id.to_string();
             let validation_handler = Closure::wrap(Box::new(move |_e: Event| {
                 if let Err(err) = validate_field(&input_id_owned) {
-                    console::error_1(&format!("Validation error: {:?}", err).into());
+                    logger::error(&format!("Validation error: {:?}", err));
                 }
             }) as Box<dyn FnMut(_)>);
 
@@ -283,7 +277,7 @@ fn handle_form_submit() -> Result<(), JsValue> {
     };
 
     // Simulate form submission (in a real app, you'd send this to a server)
-    console::log_1(&format!("📧 Form submitted: {:?}", form_data).into());
+    logger::debug(&format!("📧 Form submitted: {:?}", form_data));
 
     // Show success message
     show_status(
@@ -355,7 +349,7 @@ fn handle_form_reset() -> Result<(), JsValue> {
         }
     }
 
-    console::log_1(&"🔄 Form reset".into());
+    logger::debug("🔄 Form reset");
     Ok(())
 }
 
@@ -392,11 +386,12 @@ fn show_status(document: &Document, message: &str, status_type: &str) -> Result<
 pub fn start_contact_app() {
     // Set up console error panic hook for better debugging
     console_error_panic_hook::set_once();
+    logger::init(Some("contact-form-placeholder"));
 
     // Initialize the contact form
     if let Err(err) = mount_contact_form() {
-        console::error_1(&format!("❌ Failed to mount contact form: {:?}", err).into());
+        logger::error(&format!("❌ Failed to mount contact form: {:?}", err));
     } else {
-        console::log_1(&"✅ Contact form initialized successfully".into());
+        logger::info("✅ Contact form initialized successfully");
     }
 }
@@ -0,0 +1,372 @@
+//! Sending the contact form from the WASM island, with retries and a circuit breaker.
+//!
+//! The island posts the submission as JSON to [`CONTACT_ENDPOINT`], or as `multipart/form-data` when it comes with
+//! files, and reads the [`ContactResponse`]. An attempt that
+//! fails (a network error, a server error or missing endpoint, or an `unavailable` answer) is retried after a growing
+//! delay, up to [`MAX_ATTEMPTS`] times per submission. Failures are also counted across submissions: after
+//! [`FAILURE_THRESHOLD`] in a row the circuit opens, and for [`COOLDOWN_MS`] the form offers an email link instead of
+//! letting the user hammer a broken endpoint. After the cooldown one attempt is let through again; if that fails too,
+//! the circuit opens straight away. The count lives in localStorage, so reloading the page doesn't reset it.
+
+use crate::api::{Attachment, ContactRequest, ContactResponse, ValidationProblem};
+use crate::contact_form;
+use crate::contact_prefill;
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "web")]
+use crate::CONTACT_ENDPOINT;
+
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "contact_circuit";
+
+/// Attempts made for one submission before giving up on it.
+pub const MAX_ATTEMPTS: u32 = 3;
+
+/// Consecutive failed attempts that open the circuit.
+pub const FAILURE_THRESHOLD: u32 = 5;
+
+/// How long the circuit stays open, in milliseconds.
+pub const COOLDOWN_MS: f64 = 10.0 * 60.0 * 1000.0;
+
+/// Delay before the first retry; it doubles for every further one.
+const BASE_DELAY_MS: u32 = 1_000;
+
+/// Longest delay between attempts, also for a server asking for more with `retry_after_seconds`.
+const MAX_DELAY_MS: u32 = 10_000;
+
+/// Failures of the contact endpoint as seen from this browser.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct CircuitBreaker {
+    pub consecutive_failures: u32,
+    /// When the circuit last opened, in milliseconds since the epoch
+    pub opened_at: Option<f64>,
+}
+
+impl CircuitBreaker {
+    /// Whether submissions should skip the endpoint at `now`.
+    pub fn is_open(&self, now: f64) -> bool {
+        self.opened_at.is_some_and(|at| now - at < COOLDOWN_MS)
+    }
+
+    pub fn record_failure(&mut self, now: f64) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= FAILURE_THRESHOLD {
+            self.opened_at = Some(now);
+        }
+    }
+
+    /// The endpoint answered; a rejected submission counts too, since the server is working.
+    pub fn record_success(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Delay before attempt `attempt + 1`, where `attempt` (from 1) is the one that just failed.
+pub fn backoff_ms(attempt: u32, retry_after_seconds: Option<u32>) -> u32 {
+    retry_after_seconds
+        .map(|seconds| seconds.saturating_mul(1_000))
+        .unwrap_or_else(|| BASE_DELAY_MS.saturating_mul(1 << attempt.saturating_sub(1).min(16)))
+        .min(MAX_DELAY_MS)
+}
+
+/// Where a submission sent through [`ContactDelivery`] is.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DeliveryStatus {
+    Idle,
+    /// Attempt `attempt` of [`MAX_ATTEMPTS`] is on its way
+    Sending {
+        attempt: u32,
+    },
+    /// Attempt `attempt` failed; the next one starts in `seconds`
+    Retrying {
+        attempt: u32,
+        seconds: u32,
+    },
+    /// Accepted by the server; the page is moving on to `redirect`
+    Accepted {
+        redirect: String,
+    },
+    /// The server found problems the client-side validation didn't
+    Rejected(Vec<ValidationProblem>),
+    /// Every attempt failed, but the circuit is still closed, so the user may try again
+    Failed,
+    /// The circuit is open; the form should offer [`mailto`] instead
+    Unavailable,
+}
+
+/// The island's connection to the contact endpoint, from [`use_contact_delivery`].
+#[derive(Clone, Copy)]
+pub struct ContactDelivery {
+    status: Signal<DeliveryStatus>,
+    breaker: Signal<CircuitBreaker>,
+}
+
+/// Delivery state for a contact form, starting out [`Unavailable`](DeliveryStatus::Unavailable) when the circuit
+/// is still open from earlier failures.
+pub fn use_contact_delivery() -> ContactDelivery {
+    let mut breaker = use_signal(CircuitBreaker::default);
+    let mut status = use_signal(|| DeliveryStatus::Idle);
+    // The stored circuit is read once mounted, so hydration matches the generated page
+    use_effect(move || {
+        let stored = load();
+        if stored.is_open(now()) {
+            status.set(DeliveryStatus::Unavailable);
+        }
+        breaker.set(stored);
+    });
+    ContactDelivery { status, breaker }
+}
+
+impl ContactDelivery {
+    pub fn status(&self) -> DeliveryStatus {
+        (self.status)()
+    }
+
+    /// Whether a submission is on its way or waiting for a retry.
+    pub fn is_busy(&self) -> bool {
+        matches!(
+            *self.status.read(),
+            DeliveryStatus::Sending { .. } | DeliveryStatus::Retrying { .. }
+        )
+    }
+
+    /// Back to [`Idle`](DeliveryStatus::Idle) after a rejection or failure, unless the circuit is open.
+    pub fn reset(&mut self) {
+        if !self.is_busy() && !matches!(*self.status.peek(), DeliveryStatus::Unavailable) {
+            self.status.set(DeliveryStatus::Idle);
+        }
+    }
+
+    /// Send `request` with `attachments`, retrying with backoff. `on_accepted` runs once the server has taken it.
+    pub fn send(
+        &self,
+        request: ContactRequest,
+        attachments: Vec<Attachment>,
+        on_accepted: impl FnOnce() + 'static,
+    ) {
+        let mut status = self.status;
+        let mut breaker = self.breaker;
+        if self.is_busy() {
+            return;
+        }
+
+        spawn(async move {
+            for attempt in 1..=MAX_ATTEMPTS {
+                if breaker.peek().is_open(now()) {
+                    status.set(DeliveryStatus::Unavailable);
+                    return;
+                }
+                status.set(DeliveryStatus::Sending { attempt });
+
+                let retry_after = match post(&request, &attachments).await {
+                    Ok(ContactResponse::Accepted { redirect }) => {
+                        breaker.write().record_success();
+                        store(&breaker.peek());
+                        on_accepted();
+                        status.set(DeliveryStatus::Accepted {
+                            redirect: redirect.clone(),
+                        });
+                        navigate(&redirect);
+                        return;
+                    }
+                    Ok(ContactResponse::Invalid { problems }) => {
+                        breaker.write().record_success();
+                        store(&breaker.peek());
+                        status.set(DeliveryStatus::Rejected(problems));
+                        return;
+                    }
+                    Ok(ContactResponse::Unavailable {
+                        retry_after_seconds,
+                    }) => retry_after_seconds,
+                    Err(problem) => {
+                        crate::logger::warn(&format!(
+                            "⚠️ Contact attempt {} failed: {}",
+                            attempt, problem
+                        ));
+                        None
+                    }
+                };
+
+                breaker.write().record_failure(now());
+                store(&breaker.peek());
+                if breaker.peek().is_open(now()) {
+                    status.set(DeliveryStatus::Unavailable);
+                    return;
+                }
+                if attempt < MAX_ATTEMPTS {
+                    let delay = backoff_ms(attempt, retry_after);
+                    status.set(DeliveryStatus::Retrying {
+                        attempt,
+                        seconds: delay.div_ceil(1_000),
+                    });
+                    sleep(delay).await;
+                }
+            }
+            status.set(DeliveryStatus::Failed);
+        });
+    }
+}
+
+/// `mailto:` link to `to` with the submission filled in, for when the form can't reach the server.
+pub fn mailto(to: &str, request: &ContactRequest) -> String {
+    let mut body = format!("{}\n\n{}", request.message.trim(), request.name.trim());
+    if let Some(phone) = request.normalized_phone() {
+        body.push_str(&format!("\n{}", phone));
+    }
+    for (field, value) in &request.extra {
+        if !value.trim().is_empty() {
+            body.push_str(&format!(
+                "\n{}: {}",
+                contact_form::label(field),
+                value.trim()
+            ));
+        }
+    }
+    format!(
+        "mailto:{}?subject={}&body={}",
+        to,
+        contact_prefill::encode(request.subject.trim()),
+        contact_prefill::encode(&body)
+    )
+}
+
+fn load() -> CircuitBreaker {
+    #[cfg(feature = "web")]
+    {
+        if let Some(storage) =
+            web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+        {
+            return storage
+                .get_item(STORAGE_KEY)
+                .ok()
+                .flatten()
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+        }
+    }
+
+    CircuitBreaker::default()
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn store(breaker: &CircuitBreaker) {
+    #[cfg(feature = "web")]
+    {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(breaker)) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+fn now() -> f64 {
+    #[cfg(feature = "web")]
+    {
+        js_sys::Date::now()
+    }
+
+    #[cfg(not(feature = "web"))]
+    {
+        0.0
+    }
+}
+
+/// Post `request` with `attachments` and read the endpoint's answer. Anything that isn't a [`ContactResponse`] is a
+/// failure.
+#[cfg(feature = "web")]
+async fn post(
+    request: &ContactRequest,
+    attachments: &[Attachment],
+) -> Result<ContactResponse, String> {
+    use wasm_bindgen::JsCast;
+    use wasm_bindgen_futures::JsFuture;
+
+    let window = web_sys::window().ok_or("no window")?;
+
+    let headers = web_sys::Headers::new().map_err(|_| "couldn't create headers")?;
+    let _ = headers.set("Accept", "application/json");
+    let init = web_sys::RequestInit::new();
+    init.set_method("POST");
+    if attachments.is_empty() {
+        let body = serde_json::to_string(request).map_err(|e| e.to_string())?;
+        let _ = headers.set("Content-Type", "application/json");
+        init.set_body(&wasm_bindgen::JsValue::from_str(&body));
+    } else {
+        // The browser sets the multipart Content-Type itself, with the boundary
+        init.set_body(&multipart(request, attachments)?.into());
+    }
+    init.set_headers(&headers);
+
+    let response = JsFuture::from(window.fetch_with_str_and_init(CONTACT_ENDPOINT, &init))
+        .await
+        .map_err(|_| "network error".to_string())?
+        .dyn_into::<web_sys::Response>()
+        .map_err(|_| "not a response".to_string())?;
+    let status = response.status();
+    let text = JsFuture::from(response.text().map_err(|_| "unreadable response")?)
+        .await
+        .ok()
+        .and_then(|text| text.as_string())
+        .unwrap_or_default();
+
+    serde_json::from_str(&text).map_err(|_| format!("HTTP {} without a contact response", status))
+}
+
+/// `request` as form fields, by the same names as in its JSON, and `attachments` as files in their fields.
+#[cfg(feature = "web")]
+fn multipart(
+    request: &ContactRequest,
+    attachments: &[Attachment],
+) -> Result<web_sys::FormData, String> {
+    let form = web_sys::FormData::new().map_err(|_| "couldn't create form data")?;
+    let fields: std::collections::BTreeMap<String, String> = serde_json::to_value(request)
+        .and_then(serde_json::from_value)
+        .map_err(|e| e.to_string())?;
+    for (name, value) in &fields {
+        if attachments
+            .iter()
+            .all(|attachment| &attachment.field != name)
+        {
+            let _ = form.append_with_str(name, value);
+        }
+    }
+    for attachment in attachments {
+        let bytes = js_sys::Array::of1(&js_sys::Uint8Array::from(attachment.bytes.as_slice()));
+        let blob = web_sys::Blob::new_with_u8_array_sequence(&bytes)
+            .map_err(|_| format!("couldn't read {}", attachment.filename))?;
+        form.append_with_blob_and_filename(&attachment.field, &blob, &attachment.filename)
+            .map_err(|_| format!("couldn't attach {}", attachment.filename))?;
+    }
+    Ok(form)
+}
+
+#[cfg(not(feature = "web"))]
+async fn post(
+    _request: &ContactRequest,
+    _attachments: &[Attachment],
+) -> Result<ContactResponse, String> {
+    Err("not running in a browser".to_string())
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+async fn sleep(ms: u32) {
+    #[cfg(feature = "web")]
+    {
+        let promise = js_sys::Promise::new(&mut |resolve, _| {
+            if let Some(window) = web_sys::window() {
+                let _ = window
+                    .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, ms as i32);
+            }
+        });
+        let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
+    }
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn navigate(url: &str) {
+    #[cfg(feature = "web")]
+    if let Some(window) = web_sys::window() {
+        let _ = window.location().set_href(url);
+    }
+}
@@ -0,0 +1,342 @@
+//! The site's forms, declared in `forms/`: the contact form (`forms/contact.toml`) and the speaking inquiry form
+//! (`forms/speak.toml`).
+//!
+//! A schema lists every field with its type, label, required flag and validators. The SPA pages and the WASM islands
+//! render it with [`ContactFields`](crate::components::ContactFields), the static generator writes the same fields as
+//! plain HTML, and [`ContactRequest::validate`](crate::api::ContactRequest::validate) checks submissions against it on
+//! both sides. Every form posts to the contact endpoint; a submission names its form, which decides where it's
+//! routed.
+//!
+//! This file is also compiled into `build.rs`, which refuses a broken schema, so it only uses what the build script
+//! depends on.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+/// Every form schema, as `(path, source)`. A new form is added here and in `[contact.forms]` in `site.toml`.
+pub const FORM_FILES: &[(&str, &str)] = &[
+    ("forms/contact.toml", include_str!("../forms/contact.toml")),
+    ("forms/speak.toml", include_str!("../forms/speak.toml")),
+];
+
+/// Id of the contact form, which submissions without a form come from. It's routed by the reason for contact.
+pub const CONTACT_FORM: &str = "contact";
+
+/// Fields with their own member of `ContactRequest`. Any other field is carried in its `extra` map.
+pub const CORE_FIELDS: &[&str] = &["name", "email", "reason", "subject", "message", "phone"];
+
+/// Submitted alongside a `phone` field, so no field may take it.
+pub const PHONE_COUNTRY: &str = "phone_country";
+
+/// A file in `forms/`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FormSchema {
+    /// Sent with every submission as `form`
+    pub id: String,
+    /// Values submitted for fields the form doesn't show, e.g. a fixed reason for contact
+    #[serde(default)]
+    pub defaults: BTreeMap<String, String>,
+    pub fields: Vec<FieldSpec>,
+}
+
+/// One `[[fields]]` entry.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldSpec {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub kind: FieldKind,
+    pub label: String,
+    #[serde(default)]
+    pub placeholder: String,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub validators: Vec<Validator>,
+    /// Consecutive fields with the same row are shown side by side
+    #[serde(default)]
+    pub row: Option<String>,
+    /// Height of a textarea in lines
+    #[serde(default)]
+    pub lines: Option<u32>,
+    #[serde(default)]
+    pub autocomplete: Option<String>,
+    /// Choices of a select, in order; the first one is selected to begin with
+    #[serde(default)]
+    pub options: Vec<FieldOption>,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FieldOption {
+    pub value: String,
+    pub label: String,
+}
+
+/// What a field is, which decides the input it's rendered as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldKind {
+    Text,
+    Email,
+    Tel,
+    Textarea,
+    /// A calendar date, submitted as `YYYY-MM-DD`
+    Date,
+    /// A whole number
+    Number,
+    /// One of the field's `options`
+    Select,
+    /// The reason-for-contact selector; the value must be one of the known reasons
+    Reason,
+    /// A phone number with a country code selector; the value must be a valid number for the country
+    Phone,
+    /// A file sent along with the submission, checked by the server's attachment scanners; the value is its name
+    File,
+}
+
+/// A check on a filled-in value. Empty values are only checked by `required`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Validator {
+    Email,
+    MinLength(usize),
+    MaxLength(usize),
+    /// Smallest value of a number
+    Min(i64),
+    /// Largest value of a number
+    Max(i64),
+    /// A date after today
+    Future,
+}
+
+impl FieldKind {
+    /// `type` attribute of the `<input>` for fields rendered as one.
+    pub fn input_type(self) -> &'static str {
+        match self {
+            FieldKind::Email => "email",
+            FieldKind::Tel | FieldKind::Phone => "tel",
+            FieldKind::Date => "date",
+            FieldKind::Number => "number",
+            FieldKind::File => "file",
+            _ => "text",
+        }
+    }
+}
+
+impl FieldSpec {
+    /// The label as shown, marked when the field is required.
+    pub fn display_label(&self) -> String {
+        if self.required {
+            format!("{} *", self.label)
+        } else {
+            self.label.clone()
+        }
+    }
+
+    pub fn min_length(&self) -> Option<usize> {
+        self.validators
+            .iter()
+            .find_map(|validator| match validator {
+                Validator::MinLength(min) => Some(*min),
+                _ => None,
+            })
+    }
+
+    pub fn max_length(&self) -> Option<usize> {
+        self.validators
+            .iter()
+            .find_map(|validator| match validator {
+                Validator::MaxLength(max) => Some(*max),
+                _ => None,
+            })
+    }
+
+    pub fn min(&self) -> Option<i64> {
+        self.validators
+            .iter()
+            .find_map(|validator| match validator {
+                Validator::Min(min) => Some(*min),
+                _ => None,
+            })
+    }
+
+    pub fn max(&self) -> Option<i64> {
+        self.validators
+            .iter()
+            .find_map(|validator| match validator {
+                Validator::Max(max) => Some(*max),
+                _ => None,
+            })
+    }
+
+    /// The value the field starts out with: the first option of a select, empty otherwise.
+    pub fn initial_value(&self) -> &str {
+        self.options
+            .first()
+            .map_or("", |option| option.value.as_str())
+    }
+}
+
+impl FormSchema {
+    /// Every compiled-in schema, in the order of [`FORM_FILES`].
+    pub fn all() -> &'static [FormSchema] {
+        static SCHEMAS: OnceLock<Vec<FormSchema>> = OnceLock::new();
+        SCHEMAS.get_or_init(|| {
+            FORM_FILES
+                .iter()
+                .map(|(path, source)| {
+                    FormSchema::parse(path, source)
+                        .expect("the form schemas are checked by build.rs")
+                })
+                .collect()
+        })
+    }
+
+    /// The compiled-in schema with `id`.
+    pub fn named(id: &str) -> Option<&'static FormSchema> {
+        Self::all().iter().find(|schema| schema.id == id)
+    }
+
+    /// The compiled-in `forms/contact.toml`.
+    pub fn contact() -> &'static FormSchema {
+        Self::named(CONTACT_FORM).expect("forms/contact.toml declares the contact form")
+    }
+
+    /// Parse and check the schema in `path`.
+    pub fn parse(path: &str, source: &str) -> Result<Self, String> {
+        let schema: FormSchema = toml::from_str(source).map_err(|e| format!("{}: {}", path, e))?;
+        let problems = schema.validate();
+        if problems.is_empty() {
+            Ok(schema)
+        } else {
+            Err(format!("{}: {}", path, problems.join("; ")))
+        }
+    }
+
+    /// Whether a `form` value sent with a submission names this form. Submissions without one come from the
+    /// contact form.
+    pub fn is(&self, form: &str) -> bool {
+        self.id == form || (form.is_empty() && self.id == CONTACT_FORM)
+    }
+
+    pub fn field(&self, name: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// The fields grouped into form rows, in order: side-by-side fields together, every other field on its own.
+    pub fn rows(&self) -> Vec<Vec<&FieldSpec>> {
+        let mut rows: Vec<Vec<&FieldSpec>> = Vec::new();
+        for field in &self.fields {
+            match rows.last_mut() {
+                Some(row) if field.row.is_some() && row[0].row == field.row => row.push(field),
+                _ => rows.push(vec![field]),
+            }
+        }
+        rows
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !is_name(&self.id) {
+            problems.push(format!(
+                "form id `{}` may only have lowercase letters and underscores",
+                self.id
+            ));
+        }
+        if self.fields.is_empty() {
+            problems.push("there are no fields".to_string());
+        }
+        for name in self.defaults.keys() {
+            if self.field(name).is_some() {
+                problems.push(format!(
+                    "`{}` has a default but is a field of the form, which submits it",
+                    name
+                ));
+            }
+        }
+
+        for (index, field) in self.fields.iter().enumerate() {
+            let name = &field.name;
+            if !is_name(name) {
+                problems.push(format!(
+                    "field name `{}` may only have lowercase letters and underscores",
+                    name
+                ));
+            }
+            if name == PHONE_COUNTRY {
+                problems.push(format!("`{}` is reserved for the phone field", name));
+            }
+            if self.fields[..index].iter().any(|other| other.name == *name) {
+                problems.push(format!("field `{}` is declared twice", name));
+            }
+            // The request keeps the reason and the phone number in their own members
+            for (kind, owner) in [(FieldKind::Reason, "reason"), (FieldKind::Phone, "phone")] {
+                if field.kind == kind && name != owner {
+                    problems.push(format!(
+                        "field `{}`: only the `{}` field can be of type {}",
+                        name, owner, owner
+                    ));
+                } else if field.kind != kind && name == owner {
+                    problems.push(format!("field `{}` must be of type {}", name, owner));
+                }
+            }
+            if field.label.trim().is_empty() {
+                problems.push(format!("field `{}` has no label", name));
+            }
+            if let (Some(min), Some(max)) = (field.min_length(), field.max_length()) {
+                if min > max {
+                    problems.push(format!(
+                        "field `{}`: min_length {} is more than max_length {}",
+                        name, min, max
+                    ));
+                }
+            }
+            if field.lines.is_some() && field.kind != FieldKind::Textarea {
+                problems.push(format!("field `{}`: only a textarea has lines", name));
+            }
+            if (field.kind == FieldKind::Select) == field.options.is_empty() {
+                problems.push(format!(
+                    "field `{}`: a select needs options, and only a select has them",
+                    name
+                ));
+            }
+            if let (Some(min), Some(max)) = (field.min(), field.max()) {
+                if min > max {
+                    problems.push(format!(
+                        "field `{}`: min {} is more than max {}",
+                        name, min, max
+                    ));
+                }
+            }
+            for validator in &field.validators {
+                let fits = match validator {
+                    Validator::Min(_) | Validator::Max(_) => field.kind == FieldKind::Number,
+                    Validator::Future => field.kind == FieldKind::Date,
+                    _ => true,
+                };
+                if !fits {
+                    problems.push(format!(
+                        "field `{}`: {:?} doesn't apply to a {:?} field",
+                        name, validator, field.kind
+                    ));
+                }
+            }
+        }
+        problems
+    }
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+}
+
+/// Label of the field called `name` in the first form that has it, or the name itself for unknown fields.
+pub fn label(name: &str) -> &str {
+    FormSchema::all()
+        .iter()
+        .find_map(|schema| schema.field(name))
+        .map_or(name, |field| field.label.as_str())
+}
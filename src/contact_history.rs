@@ -0,0 +1,104 @@
+//! History of the contact form submissions made in this browser.
+//!
+//! Like the saved posts, the history lives in localStorage as JSON: an array of [`Submission`]s, newest first, capped
+//! at [`MAX_ENTRIES`]. It's a demo of structured client-side storage; nothing here is sent anywhere.
+
+use crate::api::ContactRequest;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "contact_history";
+
+/// Where the form kept only the latest submission before the history existed; folded into the history on first load.
+#[cfg(feature = "web")]
+const LEGACY_KEY: &str = "last_contact_submission";
+
+/// Submissions kept; older ones are dropped as new ones come in.
+pub const MAX_ENTRIES: usize = 10;
+
+/// A validated submission as it's kept in the history.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct Submission {
+    pub name: String,
+    pub email: String,
+    /// Id of the selected reason for contact
+    pub reason: String,
+    pub subject: String,
+    pub message: String,
+    /// Phone number in E.164 form, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phone: Option<String>,
+    pub submitted_at: String,
+}
+
+impl Submission {
+    /// Record of `request`, made now. The request should have passed validation.
+    pub fn new(request: &ContactRequest) -> Self {
+        Self {
+            name: request.name.clone(),
+            email: request.email.clone(),
+            reason: request.reason.clone(),
+            subject: request.subject.clone(),
+            message: request.message.clone(),
+            phone: request.normalized_phone(),
+            submitted_at: chrono::Utc::now()
+                .format("%Y-%m-%d %H:%M:%S UTC")
+                .to_string(),
+        }
+    }
+}
+
+/// Load the history, newest first. Returns an empty list outside the browser or when storage is unavailable.
+pub fn load() -> Vec<Submission> {
+    #[cfg(feature = "web")]
+    {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+
+        if let Some(storage) = storage {
+            let mut history: Vec<Submission> = storage
+                .get_item(STORAGE_KEY)
+                .ok()
+                .flatten()
+                .and_then(|json| serde_json::from_str(&json).ok())
+                .unwrap_or_default();
+
+            if let Some(legacy) = storage.get_item(LEGACY_KEY).ok().flatten() {
+                if let Ok(submission) = serde_json::from_str(&legacy) {
+                    history.push(submission);
+                    history.truncate(MAX_ENTRIES);
+                    store(&history);
+                }
+                let _ = storage.remove_item(LEGACY_KEY);
+            }
+
+            return history;
+        }
+    }
+
+    Vec::new()
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn store(history: &[Submission]) {
+    #[cfg(feature = "web")]
+    {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(history)) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+/// Add `submission` to the front of the history, dropping the oldest entries past [`MAX_ENTRIES`]. Returns the
+/// updated history.
+pub fn record(submission: Submission) -> Vec<Submission> {
+    let mut history = load();
+    history.insert(0, submission);
+    history.truncate(MAX_ENTRIES);
+    store(&history);
+    history
+}
+
+pub fn clear() {
+    store(&[]);
+}
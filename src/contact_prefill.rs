@@ -0,0 +1,100 @@
+//! Prefilling the contact form from the URL.
+//!
+//! Links like `/contact?subject=Speaking%20inquiry&reason=talk` open the form with those fields filled in, so other
+//! pages can link to a ready-made enquiry (see [`link`]). Recognised parameters are `name`, `email`, `subject`,
+//! `message` and `reason`; a reason that isn't in [`REASONS`](crate::contact_reasons::REASONS) is ignored.
+
+use crate::contact_reasons;
+
+/// Initial values for the contact form fields.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Prefill {
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub subject: Option<String>,
+    pub message: Option<String>,
+    /// Id of a known reason for contact
+    pub reason: Option<&'static str>,
+}
+
+/// Read the prefill parameters from a query string, with or without the leading `?`.
+pub fn parse(query: &str) -> Prefill {
+    let mut prefill = Prefill::default();
+
+    for pair in query.trim_start_matches('?').split('&') {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = decode(value);
+        if value.trim().is_empty() {
+            continue;
+        }
+
+        match key {
+            "name" => prefill.name = Some(value),
+            "email" => prefill.email = Some(value),
+            "subject" => prefill.subject = Some(value),
+            "message" => prefill.message = Some(value),
+            "reason" => prefill.reason = contact_reasons::reason(&value).map(|reason| reason.id),
+            _ => {}
+        }
+    }
+
+    prefill
+}
+
+/// Contact page URL that opens the form with `subject` and, optionally, `reason` filled in.
+pub fn link(subject: &str, reason: Option<&str>) -> String {
+    let mut url = format!("/contact?subject={}", encode(subject));
+    if let Some(reason) = reason {
+        url.push_str("&reason=");
+        url.push_str(&encode(reason));
+    }
+    url
+}
+
+/// "Ask about this project" link for a project card.
+pub fn ask_about_project(project: &str) -> String {
+    link(&format!("Question about {}", project), Some("work"))
+}
+
+/// Percent-encode everything except the characters RFC 3986 leaves unreserved.
+pub(crate) fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes and `+` as a space. Malformed escapes are kept literally.
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' => {
+                let hex = bytes
+                    .get(i + 1..i + 3)
+                    .and_then(|hex| std::str::from_utf8(hex).ok())
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+                match hex {
+                    Some(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    None => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
@@ -0,0 +1,25 @@
+//! Reasons for contact offered by the contact form.
+//!
+//! The form sends the reason's `id`; `site.toml` maps each id to the recipients that get the message and the email
+//! template used for it (see `ssg::config::ContactConfig`).
+
+/// Entry in the contact form's "reason for contact" selector.
+pub struct Reason {
+    /// Value submitted with the form and used as the key in `[contact.routes]`
+    pub id: &'static str,
+    pub label: &'static str,
+}
+
+pub const DEFAULT_REASON: &str = "general";
+
+#[rustfmt::skip]
+pub const REASONS: &[Reason] = &[
+    Reason { id: "general", label: "General question" },
+    Reason { id: "work", label: "Work or collaboration" },
+    Reason { id: "talk", label: "Speaking or event invitation" },
+    Reason { id: "feedback", label: "Feedback about the site" },
+];
+
+pub fn reason(id: &str) -> Option<&'static Reason> {
+    REASONS.iter().find(|reason| reason.id == id)
+}
@@ -0,0 +1,35 @@
+//! Helpers for reading markdown content files.
+//!
+//! Content files may start with a YAML frontmatter block delimited by `---` lines:
+//!
+//! ```text
+//! ---
+//! title: Hello
+//! ---
+//! Markdown body...
+//! ```
+
+/// Split a markdown source into its raw frontmatter (without the `---` fences) and the body.
+///
+/// Files without a frontmatter block return `None` and the whole source as the body.
+pub fn split_frontmatter(source: &str) -> (Option<&str>, &str) {
+    let Some(rest) = source
+        .strip_prefix("---\n")
+        .or_else(|| source.strip_prefix("---\r\n"))
+    else {
+        return (None, source);
+    };
+
+    let mut offset = 0;
+    for line in rest.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            let frontmatter = &rest[..offset];
+            let body = &rest[offset + line.len()..];
+            return (Some(frontmatter), body);
+        }
+        offset += line.len();
+    }
+
+    // An unterminated block is treated as plain markdown
+    (None, source)
+}
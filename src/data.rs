@@ -0,0 +1,74 @@
+//! Page data from `assets/data/`, compiled in as typed statics.
+//!
+//! `build.rs` reads the JSON files and generates the [`ABOUT`] and [`PROJECTS`] statics below, so the views use the
+//! data without parsing anything at runtime, and an invalid file is a compile error.
+
+use crate::images::{self, ImageInfo};
+
+/// `assets/data/about.json`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct About {
+    pub name: &'static str,
+    pub title: &'static str,
+    pub location: &'static str,
+    pub bio: &'static str,
+    pub skills: &'static [&'static str],
+    pub experience: &'static [Experience],
+    pub interests: &'static [&'static str],
+    pub contact: AboutContact,
+    /// Date the data was last updated, as written in the file
+    pub updated: &'static str,
+    /// Portrait shown in the header
+    pub photo: Option<Photo>,
+    /// Photos shown in a grid under the interests
+    pub gallery: &'static [Photo],
+}
+
+/// A photo on the about page, one of the images in `assets/images/` (`build.rs` checks it is).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Photo {
+    /// Site-relative path, e.g. `/assets/images/about/portrait.jpg`
+    pub src: &'static str,
+    pub alt: &'static str,
+    pub caption: Option<&'static str>,
+}
+
+impl Photo {
+    /// The size and placeholder of the image.
+    pub fn image(&self) -> &'static ImageInfo {
+        images::find(self.src).expect("build.rs checks the about photos are in assets/images/")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Experience {
+    pub company: &'static str,
+    pub position: &'static str,
+    /// As shown on the about page, e.g. "2019 – 2023" or "Current"
+    pub duration: &'static str,
+    pub description: &'static str,
+    /// When the role started, as `YYYY`, `YYYY-MM` or `YYYY-MM-DD`, for the JSON Resume export
+    pub start: Option<&'static str>,
+    /// When the role ended, in the same form; `None` for a current role
+    pub end: Option<&'static str>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AboutContact {
+    pub email: &'static str,
+    pub website: &'static str,
+    /// GitHub user name
+    pub github: &'static str,
+}
+
+/// An entry of `assets/data/projects.json`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Project {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub url: &'static str,
+    /// Technologies used, as shown in the projects filter
+    pub tags: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/data.rs"));
@@ -0,0 +1,115 @@
+//! Decoding HTML character references, for the excerpts and meta text taken from content and for the URLs the
+//! [`html_sanitizer`](crate::html_sanitizer) checks.
+//!
+//! References are read the way browsers read them, so the sanitizer sees the URL a browser would follow: numeric
+//! ones end at the first character that isn't a digit and the `;` may be left out (`&#106avascript:`). Named ones are
+//! the common ones and those that spell out a URL (`&colon;`, `&Tab;`); unknown references are left as they are.
+
+/// `text` with its character references decoded.
+pub fn decode(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        match reference(&rest[1..]) {
+            Some((c, length)) => {
+                decoded.push(c);
+                let after = &rest[1 + length..];
+                rest = after.strip_prefix(';').unwrap_or(after);
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+/// The character a reference at the start of `text` (just after its `&`) stands for, with the length of the
+/// reference without its `;`.
+fn reference(text: &str) -> Option<(char, usize)> {
+    if let Some(number) = text.strip_prefix('#') {
+        let (digits, radix, prefix) = match number.strip_prefix(['x', 'X']) {
+            Some(hex) => (hex, 16, 2),
+            None => (number, 10, 1),
+        };
+        let end = digits
+            .find(|c: char| !c.is_digit(radix))
+            .unwrap_or(digits.len());
+        if end == 0 {
+            return None;
+        }
+        // Like browsers, a code point that can't be a character becomes the replacement character
+        let c = u32::from_str_radix(&digits[..end], radix)
+            .ok()
+            .and_then(char::from_u32)
+            .filter(|&c| c != '\0')
+            .unwrap_or(char::REPLACEMENT_CHARACTER);
+        return Some((c, prefix + end));
+    }
+
+    let end = text
+        .find(|c: char| !c.is_ascii_alphanumeric())
+        .unwrap_or(text.len());
+    named(&text[..end]).map(|c| (c, end))
+}
+
+fn named(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "colon" => Some(':'),
+        "tab" | "Tab" => Some('\t'),
+        "newline" | "NewLine" => Some('\n'),
+        "nbsp" => Some('\u{a0}'),
+        "hellip" => Some('…'),
+        "mdash" => Some('—'),
+        "ndash" => Some('–'),
+        "rsquo" => Some('’'),
+        "lsquo" => Some('‘'),
+        "rdquo" => Some('”'),
+        "ldquo" => Some('“'),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_named_and_numeric_references() {
+        assert_eq!(
+            decode("Tom &amp; Jerry&rsquo;s &lt;show&gt;"),
+            "Tom & Jerry’s <show>"
+        );
+        assert_eq!(decode("&#8212; &#x2014; &#X2014;"), "— — —");
+        assert_eq!(
+            decode("&#0; &#xD800; &#x110000;"),
+            "\u{fffd} \u{fffd} \u{fffd}"
+        );
+    }
+
+    #[test]
+    fn decodes_references_without_semicolons() {
+        assert_eq!(decode("&#106avascript&colon;"), "javascript:");
+        assert_eq!(decode("java&Tab;script&#x3a"), "java\tscript:");
+        assert_eq!(decode("a &amp b"), "a & b");
+    }
+
+    #[test]
+    fn leaves_anything_else_alone() {
+        assert_eq!(
+            decode("AT&T & friends &unknown; &#; &#x;"),
+            "AT&T & friends &unknown; &#; &#x;"
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! Plain-text excerpts of HTML content for listings, feeds and meta descriptions.
+
+use crate::entities;
+
+/// Marker that ends the excerpt when it appears in the content.
+pub const MORE_MARKER: &str = "<!--more-->";
+
+/// Longest meta description search engines show without cutting it off, in characters.
+pub const DESCRIPTION_LENGTH: usize = 155;
+
+/// Excerpt of `html` as plain text: everything before [`MORE_MARKER`] if present, otherwise the first `max_words`
+/// words followed by `…`. Tags are removed and character references decoded, so callers escape the result for
+/// wherever it ends up.
+pub fn excerpt(html: &str, max_words: usize) -> String {
+    let (source, has_marker) = match html.find(MORE_MARKER) {
+        Some(end) => (&html[..end], true),
+        None => (html, false),
+    };
+
+    // Words are split before decoding, so truncation can never cut a character reference in half
+    let text = strip_tags(source);
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let truncated = !has_marker && words.len() > max_words;
+    let words = if truncated {
+        &words[..max_words]
+    } else {
+        &words[..]
+    };
+
+    let mut excerpt = entities::decode(&words.join(" "));
+    if truncated {
+        let kept = excerpt
+            .trim_end_matches([',', ';', ':', '-', '–', '—'])
+            .len();
+        excerpt.truncate(kept);
+        excerpt.push('…');
+    }
+    excerpt
+}
+
+/// All of `html` as plain text: tags removed, character references decoded and whitespace collapsed.
+pub fn plain_text(html: &str) -> String {
+    entities::decode(
+        &strip_tags(html)
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+/// Meta description derived from `source` (HTML or markdown) as plain text of at most `max_chars` characters. Longer
+/// text is cut at the last word boundary that leaves room for `…`; a single word longer than that is cut mid-word.
+pub fn description(source: &str, max_chars: usize) -> String {
+    let text = strip_markdown(&strip_tags(source));
+    let text = entities::decode(&text.split_whitespace().collect::<Vec<_>>().join(" "));
+    if text.chars().count() <= max_chars {
+        return text;
+    }
+
+    let room = max_chars.saturating_sub(1);
+    let cut = text
+        .char_indices()
+        .nth(room)
+        .map_or(text.len(), |(index, _)| index);
+    let fits = &text[..cut];
+    // The cut falls inside a word unless the next character is the space after it
+    let fits = if text[cut..].starts_with(' ') {
+        fits
+    } else {
+        fits.rfind(' ').map_or(fits, |space| &fits[..space])
+    };
+
+    let mut description = fits
+        .trim_end_matches([' ', ',', ';', ':', '-', '–', '—'])
+        .to_string();
+    description.push('…');
+    description
+}
+
+/// Drop the markdown syntax a description would otherwise show literally: heading, quote and list markers, emphasis
+/// and code markers, and link and image targets (their text is kept).
+fn strip_markdown(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+
+    for line in text.lines() {
+        let line = line.trim_start();
+        let line = line.trim_start_matches('#').trim_start_matches('>');
+        let line = ["- ", "* ", "+ "]
+            .iter()
+            .find_map(|marker| line.strip_prefix(marker))
+            .unwrap_or(line);
+
+        let mut rest = line;
+        while let Some(start) = rest.find("](") {
+            let Some(end) = rest[start..].find(')') else {
+                break;
+            };
+            plain.push_str(&rest[..start]);
+            rest = &rest[start + end + 1..];
+        }
+        plain.push_str(rest);
+        plain.push('\n');
+    }
+
+    plain
+        .replace("![", "")
+        .split(|c: char| c.is_whitespace())
+        .map(|word| {
+            word.replace(['*', '`', '['], "")
+                .replace("~~", "")
+                .trim_matches('_')
+                .to_string()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace every tag (and comment) with a space, so words in adjacent elements stay separate.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        text.push_str(&rest[..start]);
+        text.push(' ');
+        let tag = &rest[start..];
+        let end = if tag.starts_with("<!--") {
+            tag.find("-->").map(|end| end + 3)
+        } else {
+            tag.find('>').map(|end| end + 1)
+        };
+        rest = match end {
+            Some(end) => &tag[end..],
+            // An unclosed tag runs to the end of the content
+            None => "",
+        };
+    }
+
+    text.push_str(rest);
+    text
+}
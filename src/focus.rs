@@ -0,0 +1,232 @@
+//! Keyboard focus helpers for overlays and focus styling.
+//!
+//! [`use_focus_trap`] keeps Tab and Shift+Tab inside an open overlay (a drawer, a dialog) and, once it closes, hands
+//! focus back to the element that had it before, usually the button that opened it. Without the trap, keyboard users
+//! tab straight out of the overlay into the page hidden behind it.
+//!
+//! [`install_focus_visible`] is a small `:focus-visible` polyfill: it adds [`POLYFILL_CLASS`] to `<html>` and
+//! [`FOCUS_VISIBLE_CLASS`] to elements focused from the keyboard. `main.css` only hides the outline of mouse-focused
+//! elements under the polyfill class, so pages where the script never runs keep an outline on everything.
+
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Elements that take part in the tab order.
+pub const FOCUSABLE: &str = "a[href], area[href], button:not([disabled]), input:not([disabled]):not([type=\"hidden\"]), \
+                             select:not([disabled]), textarea:not([disabled]), [tabindex]:not([tabindex=\"-1\"])";
+
+/// Class on `<html>` once the polyfill is running.
+pub const POLYFILL_CLASS: &str = "js-focus-visible";
+
+/// Class on the element that was focused from the keyboard.
+pub const FOCUS_VISIBLE_CLASS: &str = "focus-visible";
+
+/// Focus held inside one element. Dropping the trap releases it and restores the focus it started from.
+pub struct FocusTrap {
+    #[cfg(feature = "web")]
+    listener: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::KeyboardEvent)>,
+    #[cfg(feature = "web")]
+    previous: Option<web_sys::HtmlElement>,
+}
+
+impl FocusTrap {
+    /// Trap focus inside the element with `id` and move it to the first focusable element there (or the container).
+    /// `None` when there is no such element, or outside the browser.
+    #[cfg(feature = "web")]
+    pub fn activate(id: &str) -> Option<Self> {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let document = web_sys::window()?.document()?;
+        let container = document.get_element_by_id(id)?;
+        let previous = document
+            .active_element()
+            .and_then(|element| element.dyn_into::<web_sys::HtmlElement>().ok());
+
+        match focusable_in(&container).first() {
+            Some(first) => {
+                let _ = first.focus();
+            }
+            None => {
+                if let Some(container) = container.dyn_ref::<web_sys::HtmlElement>() {
+                    let _ = container.focus();
+                }
+            }
+        }
+
+        let listener = Closure::<dyn FnMut(web_sys::KeyboardEvent)>::new(
+            move |event: web_sys::KeyboardEvent| {
+                if event.key() != "Tab" {
+                    return;
+                }
+                let elements = focusable_in(&container);
+                let (Some(first), Some(last)) = (elements.first(), elements.last()) else {
+                    event.prevent_default();
+                    return;
+                };
+
+                let active = web_sys::window()
+                    .and_then(|window| window.document())
+                    .and_then(|document| document.active_element());
+                let outside = !active
+                    .as_ref()
+                    .is_some_and(|active| container.contains(Some(active)));
+                let at_edge = |edge: &web_sys::HtmlElement| {
+                    active
+                        .as_ref()
+                        .is_some_and(|active| active == edge.unchecked_ref::<web_sys::Element>())
+                };
+
+                let wrap_to = if event.shift_key() && (outside || at_edge(first)) {
+                    Some(last)
+                } else if !event.shift_key() && (outside || at_edge(last)) {
+                    Some(first)
+                } else {
+                    None
+                };
+                if let Some(target) = wrap_to {
+                    event.prevent_default();
+                    let _ = target.focus();
+                }
+            },
+        );
+        let _ =
+            document.add_event_listener_with_callback("keydown", listener.as_ref().unchecked_ref());
+
+        Some(Self { listener, previous })
+    }
+
+    #[cfg(not(feature = "web"))]
+    pub fn activate(_id: &str) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(feature = "web")]
+impl Drop for FocusTrap {
+    fn drop(&mut self) {
+        use wasm_bindgen::JsCast;
+
+        if let Some(document) = web_sys::window().and_then(|window| window.document()) {
+            let _ = document.remove_event_listener_with_callback(
+                "keydown",
+                self.listener.as_ref().unchecked_ref(),
+            );
+        }
+        if let Some(previous) = &self.previous {
+            let _ = previous.focus();
+        }
+    }
+}
+
+/// The focusable elements inside `container`, in document order.
+#[cfg(feature = "web")]
+fn focusable_in(container: &web_sys::Element) -> Vec<web_sys::HtmlElement> {
+    use wasm_bindgen::JsCast;
+
+    let Ok(nodes) = container.query_selector_all(FOCUSABLE) else {
+        return Vec::new();
+    };
+    (0..nodes.length())
+        .filter_map(|index| nodes.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+        .collect()
+}
+
+/// Trap focus inside the element with `id` while `active` is true; restore it when `active` turns false or the
+/// component goes away. The element must be rendered by the time `active` is set.
+pub fn use_focus_trap(id: &str, active: ReadSignal<bool>) {
+    let trap = use_hook(|| Rc::new(RefCell::new(None::<FocusTrap>)));
+
+    use_effect({
+        let id = id.to_string();
+        let trap = trap.clone();
+        move || {
+            let active = active();
+            let mut trap = trap.borrow_mut();
+            if !active {
+                trap.take();
+            } else if trap.is_none() {
+                *trap = FocusTrap::activate(&id);
+            }
+        }
+    });
+
+    dioxus::core::use_drop(move || {
+        trap.borrow_mut().take();
+    });
+}
+
+/// Start the `:focus-visible` polyfill. Safe to call more than once; only the first call installs the listeners.
+pub fn install_focus_visible() {
+    #[cfg(feature = "web")]
+    {
+        use std::cell::Cell;
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        static INSTALLED: AtomicBool = AtomicBool::new(false);
+        if INSTALLED.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+            return;
+        };
+        if let Some(root) = document.document_element() {
+            let _ = root.class_list().add_1(POLYFILL_CLASS);
+        }
+
+        // Listening in the capture phase sees every event before a component can stop it; the listeners live as
+        // long as the page
+        let listen = |name: &str, listener: Box<dyn FnMut(web_sys::Event)>| {
+            let listener = Closure::wrap(listener);
+            let _ = document.add_event_listener_with_callback_and_bool(
+                name,
+                listener.as_ref().unchecked_ref(),
+                true,
+            );
+            listener.forget();
+        };
+
+        // Whether the last input came from the keyboard; focus moved by a click or tap isn't shown
+        let keyboard = Rc::new(Cell::new(true));
+        listen("keydown", {
+            let keyboard = keyboard.clone();
+            Box::new(move |event: web_sys::Event| {
+                let modified = event
+                    .dyn_ref::<web_sys::KeyboardEvent>()
+                    .is_some_and(|key| key.meta_key() || key.ctrl_key() || key.alt_key());
+                if !modified {
+                    keyboard.set(true);
+                }
+            })
+        });
+        listen("pointerdown", {
+            let keyboard = keyboard.clone();
+            Box::new(move |_| keyboard.set(false))
+        });
+        listen(
+            "focusin",
+            Box::new(move |event: web_sys::Event| {
+                if let (true, Some(target)) = (keyboard.get(), event_element(&event)) {
+                    let _ = target.class_list().add_1(FOCUS_VISIBLE_CLASS);
+                }
+            }),
+        );
+        listen(
+            "focusout",
+            Box::new(|event: web_sys::Event| {
+                if let Some(target) = event_element(&event) {
+                    let _ = target.class_list().remove_1(FOCUS_VISIBLE_CLASS);
+                }
+            }),
+        );
+    }
+}
+
+#[cfg(feature = "web")]
+fn event_element(event: &web_sys::Event) -> Option<web_sys::Element> {
+    use wasm_bindgen::JsCast;
+
+    event.target()?.dyn_into::<web_sys::Element>().ok()
+}
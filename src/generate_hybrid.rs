@@ -0,0 +1,74 @@
+//! Hybrid page generator
+//! Adds the Dioxus islands listed in `hybrid.toml` to pages already written by the static generator.
+
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::csp::CspHook;
+use dioxus_site::ssg::headers::HeadersHook;
+use dioxus_site::ssg::hooks::Build;
+use dioxus_site::ssg::hybrid::{find_wasm_assets, generate_hybrid_pages, HybridManifest};
+use dioxus_site::ssg::integrity::IntegrityHook;
+use dioxus_site::ssg::islands_manifest::{IslandsManifest, ISLANDS_MANIFEST};
+use dioxus_site::ssg::meta::DescriptionHook;
+use dioxus_site::ssg::output::describe_files;
+use std::path::Path;
+
+// Only the contact placeholder page is used from the static generator module here
+#[allow(dead_code)]
+mod generate_static;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🏗️  Adding WASM islands to generated pages...");
+
+    let output_dir = Path::new("static_output");
+    let wasm_assets_dir = Path::new("target/dx/dioxus_site/release/web/public/assets");
+    let config = SiteConfig::load(Path::new("site.toml"))?;
+    let manifest = HybridManifest::load(Path::new("hybrid.toml"))?;
+
+    if !wasm_assets_dir.exists() {
+        return Err(
+            "WASM build not found. Please run 'dx build --release --features web' first".into(),
+        );
+    }
+
+    println!(
+        "🔍 Looking for WASM assets in: {}",
+        wasm_assets_dir.display()
+    );
+    let assets = find_wasm_assets(wasm_assets_dir, &manifest.mount_functions())?;
+    println!("🎯 Using JS file: {}", assets.js);
+    println!("🎯 Using WASM file: {}", assets.wasm);
+
+    // The WASM assets were copied into the output before this step, so the updated pages can be hashed like the others
+    // and the headers file picks up the fingerprinted WASM files. In strict CSP mode the island loaders are moved out
+    // of the pages too.
+    let mut build = Build::new(output_dir, &config)
+        .with_hook(DescriptionHook)
+        .with_hook(CspHook)
+        .with_hook(HeadersHook)
+        .with_hook(IntegrityHook);
+
+    // The hybrid build replaces the static contact page with the one whose form the island mounts over
+    generate_static::generate_contact_island_page(&mut build)?;
+
+    println!("🔨 Adding islands to the pages in hybrid.toml");
+    let pages = generate_hybrid_pages(
+        output_dir,
+        config.build.output_style,
+        &assets,
+        &manifest,
+        &config.telemetry,
+    )?;
+    let files: Vec<_> = pages.iter().map(|page| page.file.clone()).collect();
+    println!("✅ Updated: {}", describe_files(&files));
+
+    let islands = IslandsManifest::new(output_dir, &assets, &pages)?;
+    islands.write(output_dir)?;
+    println!("📦 Islands ({}):", ISLANDS_MANIFEST);
+    println!("{}", islands.table());
+
+    build.finish()?;
+
+    println!("✅ Hybrid page generation complete!");
+    Ok(())
+}
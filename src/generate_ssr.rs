@@ -0,0 +1,59 @@
+//! Server-side rendered SPA pages
+//! Renders every SPA route into the `index.html` of a `dx build --features hydrate` bundle, so each route's page has
+//! its content before the WASM hydrates it (see [`spa_ssr`]).
+//!
+//! Build it without the `web` feature: `cargo run --bin generate_ssr --no-default-features --features ssr`.
+
+use dioxus_site::posts;
+use dioxus_site::ssg::config::OutputStyle;
+use dioxus_site::ssg::output::describe_files;
+use dioxus_site::ssg::spa_ssr;
+use std::fs;
+use std::path::Path;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    if cfg!(feature = "web") {
+        return Err(
+            "generate_ssr renders outside the browser; run it with --no-default-features --features ssr".into(),
+        );
+    }
+
+    let bundle_dir = Path::new("target/dx/dioxus_site/release/web/public");
+    let shell_path = bundle_dir.join("index.html");
+    if !shell_path.exists() {
+        return Err(
+            "SPA build not found. Please run 'dx build --release --features hydrate' first".into(),
+        );
+    }
+    // Kept aside, since the root route's page replaces it
+    let shell_copy = bundle_dir.join("shell.html");
+    if !shell_copy.exists() {
+        fs::copy(&shell_path, &shell_copy)?;
+    }
+    let shell = fs::read_to_string(&shell_copy)?;
+
+    let bundled = spa_ssr::bundled_stylesheets(bundle_dir);
+
+    println!("🏗️  Rendering SPA routes into {}", bundle_dir.display());
+    let routes = spa_ssr::routes(posts::today());
+    for route in &routes {
+        let path = route.to_string();
+        let rendered = spa_ssr::render_route(route);
+        let stylesheets = spa_ssr::stylesheet_hrefs(&bundled, &rendered.stylesheets);
+        let page = spa_ssr::page(&shell, route, &rendered.content, &stylesheets)
+            .map_err(|e| format!("{}: {}", path, e))?;
+
+        let files = OutputStyle::Directory.page_files(&path);
+        for file in &files {
+            let file = bundle_dir.join(file);
+            if let Some(dir) = file.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(file, &page)?;
+        }
+        println!("✅ Rendered: {} -> {}", path, describe_files(&files));
+    }
+
+    println!("✅ {} SPA routes rendered", routes.len());
+    Ok(())
+}
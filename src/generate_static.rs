@@ -1,18 +1,76 @@
 //! Static site generator for Dioxus site
 //! This binary generates static HTML files for all routes
 
+use chrono::NaiveDate;
 use dioxus::prelude::*;
+use dioxus_site::api::content::PostMeta;
+use dioxus_site::api::reactions::{Reaction, ReactionCounts, REACTIONS_ENDPOINT};
+use dioxus_site::callouts::{self, CalloutKind};
+use dioxus_site::components::{ImgLayout, NOTES_CLASS, SLIDES_ID, SLIDE_CLASS};
+use dioxus_site::contact_form::{FieldKind, FieldSpec, FormSchema, CONTACT_FORM};
+use dioxus_site::images::ImageInfo;
+use dioxus_site::license::LicenseConfig;
+use dioxus_site::links::{self, link_to};
+use dioxus_site::not_found::PageLink;
+use dioxus_site::phone;
+use dioxus_site::posts::{self, Cover, Post, Status, POSTS};
+use dioxus_site::responsive_images::{self, ResponsiveImagesConfig};
+use dioxus_site::site_search;
+use dioxus_site::ssg::assets;
+use dioxus_site::ssg::blogroll::{self, BLOGROLL_PATH};
+use dioxus_site::ssg::changelog::{self, CHANGELOG_PATH};
+use dioxus_site::ssg::config::{AssetTransform, AssetsConfig, SiteConfig};
+use dioxus_site::ssg::content_api::ContentApiHook;
+use dioxus_site::ssg::csp::CspHook;
+use dioxus_site::ssg::feed::FeedHook;
+use dioxus_site::ssg::headers::HeadersHook;
+use dioxus_site::ssg::hooks::Build;
+use dioxus_site::ssg::html;
+use dioxus_site::ssg::integrity::IntegrityHook;
+use dioxus_site::ssg::legal::{self, IMPRINT, PRIVACY};
+use dioxus_site::ssg::meta::{self, DescriptionHook};
+use dioxus_site::ssg::notes::{self, NOTES_DIR, NOTES_FEED_PATH, NOTES_PATH};
+use dioxus_site::ssg::opensearch::{OpenSearchHook, OPENSEARCH_PATH, OPENSEARCH_TYPE};
+use dioxus_site::ssg::output::{describe_files, rewrite_links, write_redirect};
+use dioxus_site::ssg::page_manifest::PageManifestHook;
+use dioxus_site::ssg::post_markdown;
+use dioxus_site::ssg::posts_json::PostsJsonHook;
+use dioxus_site::ssg::proposals::{self, PROPOSE_PATH};
+use dioxus_site::ssg::reactions::ReactionStore;
+use dioxus_site::ssg::resume::{ResumeHook, RESUME_PATH};
+use dioxus_site::ssg::search;
+use dioxus_site::ssg::service_worker::{ServiceWorkerHook, OFFLINE_PATH, SERVICE_WORKER_PATH};
+use dioxus_site::ssg::sitemap::SitemapHook;
+use dioxus_site::ssg::talks::{self, Talk, TALKS_DIR, TALKS_PATH};
+use dioxus_site::ssg::translations::{self, Translation, Version, TRANSLATIONS_DIR};
+use dioxus_site::styles;
+use dioxus_site::theme::Theme;
+use dioxus_site::typography;
+use dioxus_site::view_transitions;
+use dioxus_site::{
+    contact_prefill, contact_reasons, data, tags, views, Route, CONTACT_THANKS_PATH,
+};
 use std::env;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("🏗️  Starting static site generation...");
 
     let args: Vec<String> = env::args().collect();
     let skip_contact = args.contains(&"--skip-contact".to_string());
+    let dry_run_assets = args.contains(&"--dry-run-assets".to_string());
+    let json_api = args.contains(&"--api".to_string());
+    let page_manifests = args.contains(&"--page-manifests".to_string());
 
     let output_dir = Path::new("static_output");
+    let config = SiteConfig::load(Path::new("site.toml"))?;
+    // Fail early on a broken theme.toml rather than generating pages without their design tokens
+    Theme::load()?;
+
+    if dry_run_assets {
+        return print_asset_plan(&config.assets, &config.responsive_images);
+    }
 
     // Clean and create output directory
     if output_dir.exists() {
@@ -20,20 +78,84 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     }
     fs::create_dir_all(output_dir)?;
 
+    // Posts dated after today stay out of this build; the first build on or after their date publishes them
+    let today = posts::today();
+    let published = posts::published(today);
+    for post in POSTS
+        .iter()
+        .filter(|post| post.status(today) != Status::Published)
+    {
+        println!(
+            "⏳ Scheduled: /blog/{} (publishes {})",
+            post.slug, post.published
+        );
+    }
+
+    // The contact page is listed even with --skip-contact, since the hybrid build adds it afterwards
+    let mut sitemap = SitemapHook::new();
+    if skip_contact {
+        sitemap = sitemap.with_path("/contact/");
+    }
+    let mut build = Build::new(output_dir, &config)
+        .with_hook(sitemap)
+        .with_hook(FeedHook {
+            posts: published.clone(),
+        })
+        .with_hook(PostsJsonHook {
+            posts: posts::index(today),
+        })
+        .with_hook(DescriptionHook)
+        .with_hook(ResumeHook)
+        .with_hook(OpenSearchHook)
+        .with_hook(ServiceWorkerHook);
+    if json_api {
+        build = build.with_hook(ContentApiHook {
+            posts: published.clone(),
+        });
+    }
+    let mut build = build
+        .with_hook(CspHook)
+        .with_hook(HeadersHook)
+        // Hashes the copied assets into the pages that reference them, so it stays last of the hooks that change pages
+        .with_hook(IntegrityHook);
+    if page_manifests {
+        build = build.with_hook(PageManifestHook);
+    }
+
     // Generate all pages
-    generate_home_page(output_dir)?;
-    generate_about_page(output_dir)?;
+    generate_home_page(&mut build)?;
+    generate_about_page(&mut build)?;
 
     if !skip_contact {
-        generate_contact_page(output_dir)?;
+        generate_contact_page(&mut build)?;
     } else {
         println!("⏭️  Skipping contact page generation");
     }
-
-    generate_blog_pages(output_dir)?;
+    generate_contact_thanks_page(&mut build)?;
+    generate_speak_page(&mut build)?;
+
+    generate_projects_page(&mut build)?;
+
+    generate_blog_index_page(&mut build, &posts::index(today), today)?;
+    let translations = translations::load(Path::new(TRANSLATIONS_DIR), &config.i18n)?;
+    generate_blog_pages(&mut build, &published, &translations)?;
+    generate_translated_pages(&mut build, &published, &translations)?;
+    generate_search_pages(&mut build, &published)?;
+    generate_blogroll_page(&mut build)?;
+    generate_propose_page(&mut build)?;
+    generate_legal_pages(&mut build, today)?;
+    generate_changelog_page(&mut build, today)?;
+    generate_talk_pages(&mut build)?;
+    generate_notes_page(&mut build)?;
+    generate_blog_redirects(&mut build, today)?;
+    generate_error_pages(&mut build)?;
+    generate_not_found_page(&mut build)?;
+    generate_offline_page(&mut build)?;
 
     // Copy assets
-    copy_assets(output_dir)?;
+    copy_assets(output_dir, &config.assets, &config.responsive_images)?;
+
+    build.finish()?;
 
     println!("✅ Static site generation complete!");
     println!("📂 Files generated in: {}", output_dir.display());
@@ -41,85 +163,100 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-fn generate_home_page(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+/// The navbar of a generated page: the SPA's links, and the mount point of the mobile nav island.
+fn navbar_html() -> String {
+    let links = links::nav_routes()
+        .into_iter()
+        .map(|(route, label)| format!(r#"<a href="{}">{}</a>"#, link_to(route), label))
+        .collect::<Vec<_>>()
+        .join("\n        ");
+    format!(
+        r#"<div id="navbar">
+        {links}
+        <div id="nav-search-root">
+            <form class="nav-search" role="search" method="get" action="{search}">
+                <input type="search" name="{param}" placeholder="Search" aria-label="Search posts and projects">
+            </form>
+        </div>
+        <div id="mobile-nav-root"></div>
+    </div>"#,
+        search = search::SEARCH_PATH,
+        param = site_search::QUERY_PARAM,
+    )
+}
+
+fn generate_home_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
     println!("🔨 Generating: /");
 
-    let content = r#"<div id="navbar">
-        <a href="/">Home</a>
-        <a href="/about">About</a>
-        <a href="/contact">Contact</a>
-        <a href="/blog/1">Blog</a>
-    </div>
+    let content = format!(
+        r#"{navbar}
     <div class="container">
         <h1>Welcome to Dioxus Site</h1>
         <p>This is the home page of my Dioxus-powered website.</p>
+        {on_this_day}
         <nav>
             <ul>
-                <li><a href="/about">Learn about me</a></li>
-                <li><a href="/blog/1">Read my blog</a></li>
+                <li><a href="{about}">Learn about me</a></li>
+                <li><a href="{blog}">Read my blog</a></li>
             </ul>
         </nav>
-    </div>"#;
-
-    let html = create_html_document(
-        "Home - Dioxus Site",
-        "Welcome to my Dioxus-powered website",
-        content,
-        None,
+    </div>"#,
+        about = link_to(Route::About {}),
+        blog = link_to(Route::BlogIndex {}),
+        navbar = navbar_html(),
+        on_this_day = on_this_day_html(posts::today()),
     );
 
-    fs::write(output_dir.join("index.html"), html)?;
-    println!("✅ Generated: index.html");
+    let meta = Route::Home {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
     Ok(())
 }
 
-fn generate_about_page(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+fn generate_about_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
     println!("🔨 Generating: /about");
 
-    // Read the about data
-    let about_data = include_str!("../assets/data/about.json");
-    let data: serde_json::Value = serde_json::from_str(about_data)?;
+    let data = &data::ABOUT;
 
     let content = format!(
-        r#"<div id="navbar">
-        <a href="/">Home</a>
-        <a href="/about">About</a>
-        <a href="/contact">Contact</a>
-        <a href="/blog/1">Blog</a>
-    </div>
-    <div class="about-container">
+        r#"{navbar}
+    <div class="{scope} about-container">
         <header class="about-header">
-            <h1 class="about-name">{name}</h1>
-            <h2 class="about-title">{title}</h2>
+            {photo}{name}
+            <p class="about-title">{title}</p>
             <p class="about-location">📍 {location}</p>
         </header>
 
         <section class="about-bio-section">
-            <h3 class="about-section-title">About Me</h3>
-            <p class="about-bio-text">{bio}</p>
+            {about_me}
+            <div class="prose">
+                <p class="about-bio-text">{bio}</p>
+            </div>
         </section>
 
         <section class="about-section">
-            <h3 class="about-section-title">Skills</h3>
+            {skills_heading}
             <div class="skills-grid">
                 {skills}
             </div>
         </section>
 
         <section class="about-section">
-            <h3 class="about-section-title">Experience</h3>
+            {experience_heading}
             {experience}
         </section>
 
         <section class="about-section">
-            <h3 class="about-section-title">Interests</h3>
+            {interests_heading}
             <div class="interests-grid">
                 {interests}
             </div>
         </section>
-
+{gallery}
         <section class="contact-section">
-            <h3 class="about-section-title">Contact</h3>
+            {contact_heading}
             <div class="contact-grid">
                 <div class="contact-item">
                     <span class="contact-icon">📧</span>
@@ -138,285 +275,2033 @@ fn generate_about_page(output_dir: &Path) -> Result<(), Box<dyn std::error::Erro
 
         <footer class="about-footer">
             <p class="footer-updated">Last updated: {updated}</p>
+            <p class="footer-note"><a href="{resume}" type="application/json">Also as a JSON Resume</a></p>
+            <p class="footer-note"><a href="{privacy}">Privacy</a> · <a href="{imprint}">Imprint</a></p>
             <p class="footer-note">Generated statically with Dioxus SSG 🦀</p>
         </footer>
     </div>"#,
-        name = data["name"].as_str().unwrap_or(""),
-        title = data["title"].as_str().unwrap_or(""),
-        location = data["location"].as_str().unwrap_or(""),
-        bio = data["bio"].as_str().unwrap_or(""),
-        skills = data["skills"]
-            .as_array()
-            .map(|skills| skills
-                .iter()
-                .map(|skill| format!(
-                    r#"<span class="skill-tag">{}</span>"#,
-                    skill.as_str().unwrap_or("")
-                ))
-                .collect::<Vec<_>>()
-                .join(""))
+        scope = styles::ABOUT.scope,
+        navbar = navbar_html(),
+        name = heading_html(1, None, Some("about-name"), data.name),
+        title = data.title,
+        about_me = section_heading("About Me"),
+        skills_heading = section_heading("Skills"),
+        experience_heading = section_heading("Experience"),
+        interests_heading = section_heading("Interests"),
+        contact_heading = section_heading("Contact"),
+        photo = data
+            .photo
+            .map(|photo| img_html(
+                photo.image(),
+                photo.alt,
+                "about-photo",
+                ImgLayout::Card,
+                true
+            ))
             .unwrap_or_default(),
-        experience = data["experience"]
-            .as_array()
-            .map(|exp| exp
-                .iter()
-                .map(|e| format!(
-                    r#"<div class="experience-card">
+        gallery = gallery_html(data.gallery),
+        location = data.location,
+        bio = data.bio,
+        skills = data
+            .skills
+            .iter()
+            .map(|skill| format!(r#"<span class="skill-tag">{}</span>"#, skill))
+            .collect::<Vec<_>>()
+            .join(""),
+        experience = data
+            .experience
+            .iter()
+            .map(|e| format!(
+                r#"<div class="experience-card">
                     <div class="experience-header">
                         <div>
-                            <h4 class="experience-position">{}</h4>
+                            {}
                             <p class="experience-company">{}</p>
                         </div>
                         <span class="experience-duration">{}</span>
                     </div>
                     <p class="experience-description">{}</p>
                 </div>"#,
-                    e["position"].as_str().unwrap_or(""),
-                    e["company"].as_str().unwrap_or(""),
-                    e["duration"].as_str().unwrap_or(""),
-                    e["description"].as_str().unwrap_or("")
-                ))
+                heading_html(3, None, Some("experience-position"), e.position),
+                e.company,
+                e.duration,
+                e.description
+            ))
+            .collect::<Vec<_>>()
+            .join(""),
+        interests = data
+            .interests
+            .iter()
+            .map(|interest| format!(
+                r#"<div class="interest-item">
+                    <span class="interest-bullet">•</span>
+                    {}</div>"#,
+                interest
+            ))
+            .collect::<Vec<_>>()
+            .join(""),
+        email = data.contact.email,
+        website = data.contact.website,
+        github = data.contact.github,
+        updated = data.updated,
+        resume = RESUME_PATH,
+        privacy = PRIVACY.path,
+        imprint = IMPRINT.path,
+    );
+
+    let meta = Route::About {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/about/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+/// A heading as static HTML, the same markup as the [`Heading`](dioxus_site::components::Heading) component.
+/// `content` is inserted as it is, like the rest of the templates.
+fn heading_html(level: u8, id: Option<&str>, class: Option<&str>, content: &str) -> String {
+    let level = typography::heading_level(level);
+    let id = id
+        .map(|id| format!(r#" id="{}""#, typography::anchor_id(id)))
+        .unwrap_or_default();
+    format!(
+        r#"<h{level}{id} class="{class}">{content}</h{level}>"#,
+        class = typography::heading_class(level, class),
+    )
+}
+
+/// The photos section of the about page, or nothing without photos.
+fn gallery_html(gallery: &[data::Photo]) -> String {
+    if gallery.is_empty() {
+        return String::new();
+    }
+    let photos: String = gallery
+        .iter()
+        .map(|photo| {
+            let caption = photo
+                .caption
+                .map(|caption| format!("<figcaption>{}</figcaption>", html::escape(caption)))
+                .unwrap_or_default();
+            format!(
+                "<figure>{}{}</figure>",
+                img_html(
+                    photo.image(),
+                    photo.alt,
+                    "about-gallery-image",
+                    ImgLayout::Card,
+                    false
+                ),
+                caption
+            )
+        })
+        .collect();
+    format!(
+        r#"
+        <section class="about-section">
+            {}
+            <div class="about-gallery">{}</div>
+        </section>
+"#,
+        section_heading("Photos"),
+        photos
+    )
+}
+
+/// Heading of a section of the about page, with its anchor.
+fn section_heading(title: &str) -> String {
+    heading_html(2, Some(title), Some("about-section-title"), title)
+}
+
+fn generate_contact_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /contact (static version)");
+    generate_static_contact_page(build)?;
+    Ok(())
+}
+
+/// The contact form as plain HTML, with the fields from `forms/contact.toml`. Without JavaScript it opens the visitor's
+/// mail app (see [`views::fallback_form_action`]); the hybrid build mounts the WASM island over it, which validates the
+/// same fields and then sends the submission itself.
+fn contact_form_html() -> String {
+    form_html(FormSchema::contact(), "Send Message ✨")
+}
+
+/// The form described by `schema` as plain HTML, going to the mail app without JavaScript. What the form submits
+/// without asking (its id and defaults) goes in hidden inputs.
+fn form_html(schema: &FormSchema, submit: &str) -> String {
+    let mut hidden: Vec<(&str, &str)> = Vec::new();
+    if schema.id != CONTACT_FORM {
+        hidden.push(("form", &schema.id));
+    }
+    hidden.extend(
+        schema
+            .defaults
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str())),
+    );
+    let hidden = hidden
+        .into_iter()
+        .map(|(name, value)| {
+            format!(
+                "                    <input type=\"hidden\" name=\"{}\" value=\"{}\" />\n",
+                html::escape(name),
+                html::escape(value)
+            )
+        })
+        .collect::<String>();
+
+    let fields = schema
+        .rows()
+        .into_iter()
+        .map(|row| {
+            if row.len() > 1 {
+                let fields: Vec<String> = row
+                    .iter()
+                    .map(|field| form_field_html(field, "    "))
+                    .collect();
+                format!(
+                    "                    <div class=\"form-row\">\n{}\n                    </div>",
+                    fields.join("\n")
+                )
+            } else {
+                form_field_html(row[0], "")
+            }
+        })
+        .filter(|row| !row.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!(
+        r#"<form class="contact-form" method="post" enctype="text/plain" action="{action}">
+{hidden}{fields}
+
+                    <div class="form-actions">
+                        <button type="submit" class="btn btn-primary">{submit}</button>
+                        <button type="reset" class="btn btn-secondary">Reset Form</button>
+                    </div>
+
+                    <div class="form-note">
+                        <p>* Required fields</p>
+                    </div>
+                </form>"#,
+        action = html::escape(&views::fallback_form_action()),
+    )
+}
+
+/// One field of a static form, indented by `indent` more than a top-level field.
+fn form_field_html(field: &FieldSpec, indent: &str) -> String {
+    let name = html::escape(&field.name);
+    let label = html::escape(&field.display_label());
+
+    let mut attributes = String::new();
+    if !field.placeholder.is_empty() {
+        attributes.push_str(&format!(
+            r#" placeholder="{}""#,
+            html::escape(&field.placeholder)
+        ));
+    }
+    if field.required {
+        attributes.push_str(" required");
+    }
+    if let Some(min) = field.min_length() {
+        attributes.push_str(&format!(r#" minlength="{}""#, min));
+    }
+    if let Some(max) = field.max_length() {
+        attributes.push_str(&format!(r#" maxlength="{}""#, max));
+    }
+    if let Some(min) = field.min() {
+        attributes.push_str(&format!(r#" min="{}""#, min));
+    }
+    if let Some(max) = field.max() {
+        attributes.push_str(&format!(r#" max="{}""#, max));
+    }
+    if let Some(autocomplete) = &field.autocomplete {
+        attributes.push_str(&format!(
+            r#" autocomplete="{}""#,
+            html::escape(autocomplete)
+        ));
+    }
+
+    let control = match field.kind {
+        FieldKind::Reason => {
+            let options = contact_reasons::REASONS
+                .iter()
+                .map(|reason| {
+                    format!(
+                        r#"<option value="{id}"{selected}>{label}</option>"#,
+                        id = reason.id,
+                        selected = if reason.id == contact_reasons::DEFAULT_REASON {
+                            " selected"
+                        } else {
+                            ""
+                        },
+                        label = reason.label,
+                    )
+                })
                 .collect::<Vec<_>>()
-                .join(""))
-            .unwrap_or_default(),
-        interests = data["interests"]
-            .as_array()
-            .map(|interests| interests
+                .join("\n                            ");
+            format!(
+                r#"<select id="{name}" name="{name}" class="form-input">
+                            {options}
+                        </select>"#
+            )
+        }
+        FieldKind::Phone => {
+            let options = phone::COUNTRIES
+                .iter()
+                .map(|country| {
+                    format!(
+                        r#"<option value="{iso}"{selected}>{name} (+{code})</option>"#,
+                        iso = country.iso,
+                        selected = if country.iso == phone::DEFAULT_COUNTRY {
+                            " selected"
+                        } else {
+                            ""
+                        },
+                        name = country.name,
+                        code = country.calling_code,
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n                                ");
+            format!(
+                r#"<div class="phone-input">
+                            <select id="phone-country" name="phone_country" class="form-input phone-country" aria-label="Country calling code">
+                                {options}
+                            </select>
+                            <input type="tel" id="{name}" name="{name}" class="form-input"{attributes} />
+                        </div>"#
+            )
+        }
+        FieldKind::Textarea => format!(
+            r#"<textarea id="{name}" name="{name}" class="form-textarea" rows="{lines}"{attributes}></textarea>"#,
+            lines = field.lines.unwrap_or(6),
+        ),
+        FieldKind::Select => {
+            let options = field
+                .options
+                .iter()
+                .enumerate()
+                .map(|(index, option)| {
+                    format!(
+                        r#"<option value="{value}"{selected}>{label}</option>"#,
+                        value = html::escape(&option.value),
+                        selected = if index == 0 { " selected" } else { "" },
+                        label = html::escape(&option.label),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n                            ");
+            format!(
+                r#"<select id="{name}" name="{name}" class="form-input">
+                            {options}
+                        </select>"#
+            )
+        }
+        FieldKind::Text
+        | FieldKind::Email
+        | FieldKind::Tel
+        | FieldKind::Date
+        | FieldKind::Number => format!(
+            r#"<input type="{kind}" id="{name}" name="{name}" class="form-input"{attributes} />"#,
+            kind = field.kind.input_type(),
+        ),
+        // The form without JavaScript goes to a mail app, and a mail link can't carry a file
+        FieldKind::File => return String::new(),
+    };
+
+    let html = format!(
+        r#"                    <div class="form-group">
+                        <label for="{name}">{label}</label>
+                        {control}
+                    </div>"#
+    );
+    html.lines()
+        .map(|line| format!("{}{}", indent, line))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn generate_static_contact_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} contact-container">
+        <header class="contact-header">
+            <h1 class="contact-title">Contact Me</h1>
+            <p class="contact-subtitle">Get in touch! This page will demonstrate dynamic JavaScript/WASM functionality.</p>
+        </header>
+
+        <div class="contact-content">
+            <div class="contact-info">
+                <h2>Contact Information</h2>
+                <div class="contact-methods">
+                    <div class="contact-method">
+                        <span class="contact-icon">📧</span>
+                        <div>
+                            <h3>Email</h3>
+                            <a href="mailto:{email}" class="contact-link">{email}</a>
+                        </div>
+                    </div>
+                    <div class="contact-method">
+                        <span class="contact-icon">💼</span>
+                        <div>
+                            <h3>LinkedIn</h3>
+                            <p>Connect with me professionally</p>
+                        </div>
+                    </div>
+                    <div class="contact-method">
+                        <span class="contact-icon">⚡</span>
+                        <div>
+                            <h3>GitHub</h3>
+                            <a href="https://github.com/marcinsydor" target="_blank" class="contact-link">@marcinsydor</a>
+                        </div>
+                    </div>
+                    <div class="contact-method">
+                        <span class="contact-icon">🎤</span>
+                        <div>
+                            <h3>Speaking</h3>
+                            <a href="{speak}" class="contact-link">Invite me to your event</a>
+                        </div>
+                    </div>
+                </div>
+            </div>
+
+            <div class="contact-form-section">
+                <h2>Send a Message</h2>
+
+                <div class="static-form-notice">
+                    <h3>📄 Works Without JavaScript</h3>
+                    <p>This form posts straight to the server, so it works in any browser. When JavaScript is enabled it is enhanced with:</p>
+                    <ul>
+                        <li>Real-time validation</li>
+                        <li>Phone number normalization</li>
+                        <li>WASM-powered functionality</li>
+                    </ul>
+                </div>
+
+                {contact_form}
+            </div>
+        </div>
+
+        <div class="tech-details">
+            <h2>🔧 Technical Implementation</h2>
+            <div class="tech-grid">
+                <div class="tech-item">
+                    <h3>🦀 WebAssembly</h3>
+                    <p>Dynamic version uses Rust compiled to WASM for all interactive functionality</p>
+                </div>
+                <div class="tech-item">
+                    <h3>⚡ Reactive State</h3>
+                    <p>Real-time form validation and state management using Dioxus signals</p>
+                </div>
+                <div class="tech-item">
+                    <h3>🏗️ Hybrid Architecture</h3>
+                    <p>Static HTML foundation with dynamic JavaScript/WASM enhancement</p>
+                </div>
+                <div class="tech-item">
+                    <h3>📱 Progressive Enhancement</h3>
+                    <p>Works without JS, enhanced with dynamic features when available</p>
+                </div>
+            </div>
+        </div>
+    </div>"#,
+        scope = styles::CONTACT.scope,
+        speak = link_to(Route::Speak {}),
+        navbar = navbar_html(),
+        email = data::ABOUT.contact.email,
+        contact_form = contact_form_html()
+    );
+
+    let meta = Route::Contact {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/contact/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+/// Page the server redirects to after a contact form submission. It's only reached through the form, so it's kept out
+/// of search results and the sitemap.
+fn generate_contact_thanks_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", CONTACT_THANKS_PATH);
+
+    let content = format!(
+        r#"{navbar}
+    <div class="container">
+        <h1>Thanks for your message!</h1>
+        <p>It has been sent and I'll get back to you as soon as I can.</p>
+        <p><a href="{home}">← Back to Home</a></p>
+    </div>"#,
+        home = link_to(Route::Home {}),
+        navbar = navbar_html(),
+    );
+
+    let html = create_html_document(
+        "Message Sent - Dioxus Site",
+        "Your message has been sent",
+        &content,
+        None,
+    )
+    .replacen(
+        "</head>",
+        "    <meta name=\"robots\" content=\"noindex\">\n</head>",
+        1,
+    );
+
+    let page = build.page(CONTACT_THANKS_PATH, &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+fn generate_speak_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /speak");
+
+    let schema = FormSchema::named("speak").ok_or("forms/speak.toml declares no speak form")?;
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} contact-container">
+        <header class="contact-header">
+            <h1 class="contact-title">Speaking &amp; Events</h1>
+            <p class="contact-subtitle">I talk about Rust, WebAssembly and building sites with Dioxus. Tell me about your event!</p>
+        </header>
+
+        <div class="contact-content">
+            <div class="contact-info">
+                <h2>What Helps</h2>
+                <ul>
+                    <li>The date and where the event takes place, or that it's online</li>
+                    <li>Who comes, and roughly how many</li>
+                    <li>Whether travel is covered or there's a speaker fee</li>
+                    <li>The topics you have in mind</li>
+                </ul>
+                <p>For anything else, use the <a href="{contact}" class="contact-link">contact form</a>.</p>
+                <p>Slides from past talks are on the <a href="{talks}" class="contact-link">talks page</a>.</p>
+            </div>
+
+            <div class="contact-form-section">
+                <h2>Send an Inquiry</h2>
+                <div id="speak-form-root">
+                {speak_form}
+                </div>
+            </div>
+        </div>
+    </div>"#,
+        scope = styles::CONTACT.scope,
+        contact = link_to(Route::Contact {}),
+        talks = TALKS_PATH,
+        navbar = navbar_html(),
+        speak_form = form_html(schema, "Send Inquiry 🎤")
+    );
+
+    let meta = Route::Speak {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/speak/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+fn generate_projects_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /projects");
+
+    // The full grid is rendered here; the projects filter island replaces it with the same markup plus the
+    // technology filter once the WASM has loaded
+    let cards = data::PROJECTS
+        .iter()
+        .map(project_card)
+        .collect::<Vec<_>>()
+        .join("\n                ");
+
+    let all_tags: Vec<&str> = data::PROJECTS
+        .iter()
+        .flat_map(|project| project.tags.iter().copied())
+        .collect();
+    let duplicates = tags::near_duplicates(&all_tags);
+    if !duplicates.is_empty() {
+        println!(
+            "⚠️  {} pairs of project tags look like duplicates (merge them in [tags.aliases]):",
+            duplicates.len()
+        );
+        for (a, b) in duplicates {
+            println!("   {} / {}", a, b);
+        }
+    }
+
+    let content = format!(
+        r#"{navbar}
+    <div id="projects-filter-root">
+        <div id="projects" class="{scope}">
+            <h1 class="heading heading-1">Projects</h1>
+            {tip}
+            <ul class="project-grid">
+                {cards}
+            </ul>
+        </div>
+    </div>"#,
+        scope = styles::PROJECTS.scope,
+        tip = callouts::static_html(CalloutKind::Tip, None, &html::escape(views::FILTER_TIP)),
+        navbar = navbar_html(),
+        cards = cards
+    );
+
+    let meta = Route::Projects {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/projects/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+/// A project as listed on the projects page, the same markup the projects filter island renders.
+fn project_card(project: &data::Project) -> String {
+    format!(
+        r#"<li class="project-card">
+                    <h2 class="heading heading-2"><a href="{url}">{name}</a></h2>
+                    <p>{description}</p>
+                    <ul class="project-tags">{tags}</ul>
+                    <a class="project-ask" href="{ask}">Ask about this project</a>
+                </li>"#,
+        url = project.url,
+        name = project.name,
+        description = project.description,
+        ask = html::escape(&contact_prefill::ask_about_project(project.name)),
+        tags = project
+            .tags
+            .iter()
+            .map(|tag| format!("<li>{}</li>", tag))
+            .collect::<Vec<_>>()
+            .join(""),
+    )
+}
+
+fn generate_blog_index_page(
+    build: &mut Build,
+    index: &[PostMeta],
+    today: NaiveDate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /blog");
+
+    let entries = index
+        .iter()
+        .map(|post| {
+            // Teasers give nothing away beyond the title and date
+            if post.coming_soon {
+                return format!(
+                    r#"<li class="blog-index-entry coming-soon">
+                    <h2 class="heading heading-2">{title}</h2>
+                    <p class="blog-date"><span class="blog-badge">Coming soon</span> {date}</p>
+                </li>"#,
+                    title = post.title,
+                    date = post.date_label(),
+                );
+            }
+            let cover = posts::find(&post.slug)
+                .and_then(|post| post.cover)
+                .map(|cover| cover_html(&cover, "blog-cover blog-cover-card", ImgLayout::Card, false))
+                .unwrap_or_default();
+            format!(
+                r#"<li class="blog-index-entry">
+                    {cover}<h2 class="heading heading-2" {transition_attribute}="{post_title}"><a href="{path}">{title}</a></h2>
+                    <p class="blog-date">{date} · {minutes} min read</p>
+                    <p>{excerpt}</p>
+                </li>"#,
+                cover = cover,
+                transition_attribute = view_transitions::TRANSITION_ATTRIBUTE,
+                post_title = view_transitions::POST_TITLE,
+                path = post.path,
+                title = post.title,
+                date = post.date_label(),
+                minutes = post.reading_minutes,
+                excerpt = html::escape(&post.summary),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Blog</h1>
+        {on_this_day}
+        <ul class="blog-index">
+                {entries}
+        </ul>
+        <p><a href="{search}">Browse posts by keyword →</a></p>
+        <p><a href="{notes}">Shorter notes between the posts →</a></p>
+        <p><a href="{blogroll}">Blogs I follow →</a></p>
+        <p><a href="{changelog}">What's new on the site →</a></p>
+        {propose}
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        on_this_day = on_this_day_html(today),
+        entries = entries,
+        search = search::SEARCH_PATH,
+        notes = NOTES_PATH,
+        blogroll = BLOGROLL_PATH,
+        changelog = CHANGELOG_PATH,
+        propose = if proposals::draft_url(&build.config().source).is_some() {
+            format!(r#"<p><a href="{}">Propose a post →</a></p>"#, PROPOSE_PATH)
+        } else {
+            String::new()
+        },
+    );
+
+    let meta = Route::BlogIndex {}.page_meta();
+    let html = create_html_document(&meta.title, &meta.description, &content, None);
+
+    let page = build.page("/blog/", &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+/// The "On this day" list for `today`, in the element the `mount_on_this_day` island refreshes for the reader's day.
+/// The element is there even when the list is empty, for the island to fill in when a cached page is read on a day
+/// with posts.
+fn on_this_day_html(today: NaiveDate) -> String {
+    let found = posts::on_this_day(today);
+    if found.is_empty() {
+        return r#"<div id="on-this-day-root"></div>"#.to_string();
+    }
+    let items = found
+        .iter()
+        .map(|post| {
+            format!(
+                r#"<li><a href="{path}">{title}</a> <span class="on-this-day-when">{when}</span></li>"#,
+                path = post.path,
+                title = html::escape(&post.title),
+                when = post.years_ago_label(today),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+
+    format!(
+        r#"<div id="on-this-day-root">
+            <section class="on-this-day" aria-labelledby="on-this-day-title">
+                <h2 id="on-this-day-title" class="on-this-day-title">On this day</h2>
+                <ul>
+                {items}
+                </ul>
+            </section>
+        </div>"#
+    )
+}
+
+fn generate_blog_pages(
+    build: &mut Build,
+    posts: &[Post],
+    translations: &[Translation],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let reactions = if build.config().reactions.enabled {
+        Some(ReactionStore::load(Path::new(
+            &build.config().reactions.store,
+        ))?)
+    } else {
+        None
+    };
+
+    for post in posts {
+        println!("🔨 Generating: /blog/{}", post.slug);
+        let (previous, next) = posts::neighbours(posts, post);
+        let alternates = translations::alternates(&build.config().i18n, post, translations);
+
+        let content = format!(
+            r#"{reading_progress}{navbar}
+        <div class="{scope} container">
+            <h1 class="heading heading-1 {post_title}">{title}</h1>
+            <p class="blog-date">{date}</p>
+            {languages}
+            {cover}
+            {original}
+            {updates}
+            {narration}
+            <div class="prose blog-content">
+                {body}
+
+                {reactions}
+
+                <nav class="blog-nav">
+                    <a href="{home}">← Back to Home</a>
+                    {prev_next}
+                </nav>
+                {footer}
+            </div>
+        </div>"#,
+            scope = styles::BLOG.scope,
+            home = link_to(Route::Home {}),
+            // Empty until the island mounts, and the bar is fixed to the window, so the page never moves for it
+            reading_progress = if build.config().reading_progress.shows_for(post) {
+                r#"<div id="reading-progress-root"></div>"#
+            } else {
+                ""
+            },
+            navbar = navbar_html(),
+            post_title = view_transitions::POST_TITLE,
+            title = post.title,
+            date = post.date_label(),
+            languages =
+                translations::links_html(&alternates, &build.config().i18n.default_language),
+            // Above the fold, so not deferred
+            cover = post
+                .cover
+                .map(|cover| cover_html(&cover, "blog-cover", ImgLayout::Content, true))
+                .unwrap_or_default(),
+            original = original_html(post),
+            updates = updates_html(post),
+            narration = narration_html(post),
+            body = post.body_html(),
+            reactions = match &reactions {
+                Some(reactions) => reactions_html(post.slug, &reactions.counts(post.slug))?,
+                None => String::new(),
+            },
+            prev_next = [
+                previous.map(|p| format!(r#"<a href="{}">← Previous</a>"#, p.path())),
+                next.map(|p| format!(r#"<a href="{}">Next →</a>"#, p.path())),
+            ]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(" "),
+            footer = footer_html(post, build.config()),
+        );
+
+        let meta = Route::Blog {
+            slug: post.slug.to_string(),
+        }
+        .page_meta();
+        let description = post.description(build.config().meta.max_description_length);
+        let html = create_html_document(&meta.title, &description, &content, None).replacen(
+            "</head>",
+            &format!(
+                "{}</head>",
+                translations::head_links(build.config(), &alternates)
+            ),
+            1,
+        );
+
+        let mut files = build.page(&post.path(), &html)?.files.clone();
+        if let Some(markdown_path) = post.markdown_path(&build.config().source) {
+            let config = build.config();
+            let url = format!(
+                "{}{}",
+                config.site.base_url.trim_end_matches('/'),
+                config.build.output_style.page_url(&post.path())
+            );
+            let file = PathBuf::from(markdown_path.trim_start_matches('/'));
+            let target = build.output_dir().join(&file);
+            if let Some(dir) = target.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(
+                target,
+                post_markdown::render(post, &url, post.license(&config.license)),
+            )?;
+            files.push(file);
+        }
+        println!("✅ Generated: {}", describe_files(&files));
+    }
+
+    Ok(())
+}
+
+/// The translations of `posts` in each language other than the default, with that language's blog index and feed,
+/// which fall back to the default-language post where a post isn't translated.
+fn generate_translated_pages(
+    build: &mut Build,
+    posts: &[Post],
+    translations: &[Translation],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let i18n = build.config().i18n.clone();
+    for lang in i18n.translated_languages() {
+        let name = i18n.language_name(lang);
+        let feed_path = translations::feed_path(lang);
+        let head = format!(
+            "    <link rel=\"alternate\" type=\"application/rss+xml\" title=\"Dioxus Site ({})\" href=\"{}\" hreflang=\"{}\">\n</head>",
+            html::escape(name),
+            feed_path,
+            lang
+        );
+
+        for post in posts {
+            let Some(translation) = translations::find(translations, post, lang) else {
+                continue;
+            };
+            println!("🔨 Generating: {}", translation.path());
+            let alternates = translations::alternates(&i18n, post, translations);
+            let content = format!(
+                r#"{navbar}
+        <div class="{scope} container">
+            <h1 class="heading heading-1">{title}</h1>
+            <p class="blog-date">{date}</p>
+            {languages}
+            <div class="prose blog-content">
+                {body}
+
+                <nav class="blog-nav">
+                    <a href="{index}">← {name}</a>
+                </nav>
+                {license}
+            </div>
+        </div>"#,
+                navbar = navbar_html(),
+                scope = styles::BLOG.scope,
+                title = html::escape(&translation.title),
+                date = post.date_label(),
+                languages = translations::links_html(&alternates, lang),
+                body = translation.html,
+                index = translations::index_path(lang),
+                name = html::escape(name),
+                license = license_html(post, &build.config().license),
+            );
+            let html = create_html_document(
+                &format!("{} - Dioxus Site", translation.title),
+                &translation.excerpt(),
+                &content,
+                None,
+            )
+            .replacen("<html>", &format!(r#"<html lang="{}">"#, lang), 1)
+            .replacen(
+                "</head>",
+                &format!(
+                    "{}{}",
+                    translations::head_links(build.config(), &alternates),
+                    head
+                ),
+                1,
+            );
+            let page = build.page(&translation.path(), &html)?;
+            println!("✅ Generated: {}", describe_files(&page.files));
+        }
+
+        let index_path = translations::index_path(lang);
+        println!("🔨 Generating: {}", index_path);
+        let entries = translations::versions(posts, translations, lang)
+            .iter()
+            .map(|(post, version)| {
+                let fallback = match version {
+                    Version::Translated(_) => String::new(),
+                    Version::Fallback(_) => format!(
+                        r#" <span class="blog-badge" lang="{}">{}</span>"#,
+                        i18n.default_language,
+                        html::escape(i18n.language_name(&i18n.default_language))
+                    ),
+                };
+                format!(
+                    r#"<li class="blog-index-entry">
+                    <h2 class="heading heading-2"><a href="{path}">{title}</a>{fallback}</h2>
+                    <p class="blog-date">{date}</p>
+                </li>"#,
+                    path = version.path(),
+                    title = html::escape(version.title()),
+                    fallback = fallback,
+                    date = post.date_label(),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n                ");
+        let content = format!(
+            r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Blog ({name})</h1>
+        <ul class="blog-index">
+                {entries}
+        </ul>
+        <p><a href="{feed}">RSS ({name}) →</a></p>
+    </div>"#,
+            navbar = navbar_html(),
+            scope = styles::BLOG.scope,
+            name = html::escape(name),
+            entries = entries,
+            feed = feed_path,
+        );
+        let meta = Route::BlogIndex {}.page_meta();
+        let html = create_html_document(&meta.title, &meta.description, &content, None)
+            .replacen("<html>", &format!(r#"<html lang="{}">"#, lang), 1)
+            .replacen("</head>", &head, 1);
+        let mut files = build.page(&index_path, &html)?.files.clone();
+
+        let feed = PathBuf::from(feed_path.trim_start_matches('/'));
+        let target = build.output_dir().join(&feed);
+        if let Some(dir) = target.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(
+            target,
+            translations::render_feed(build.config(), lang, posts, translations),
+        )?;
+        files.push(feed);
+        println!("✅ Generated: {}", describe_files(&files));
+    }
+    Ok(())
+}
+
+/// The footer of a post: its license, then links to its markdown and the GitHub editor for its source file, each when
+/// configured.
+fn footer_html(post: &Post, config: &SiteConfig) -> String {
+    let source = &config.source;
+    let links = [
+        post.markdown_path(source).map(|path| {
+            format!(
+                r#"<a href="{}" type="text/markdown">View source</a>"#,
+                html::escape(&path)
+            )
+        }),
+        post.edit_url(source).map(|url| {
+            format!(
+                r#"<a href="{}" rel="noopener">Suggest an edit on GitHub</a>"#,
+                html::escape(&url)
+            )
+        }),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+
+    let links = if links.is_empty() {
+        String::new()
+    } else {
+        format!(r#"<p class="blog-links">{}</p>"#, links.join(" · "))
+    };
+    format!(
+        r#"<footer class="blog-footer">{}{}</footer>"#,
+        license_html(post, &config.license),
+        links
+    )
+}
+
+/// The license badge of a post, linked with `rel="license"` to the license's deed when it has one.
+fn license_html(post: &Post, config: &LicenseConfig) -> String {
+    let license = post.license(config);
+    let badge = match license.url() {
+        Some(url) => format!(
+            r#"<a class="blog-license-badge" rel="license" href="{}">{}</a>"#,
+            html::escape(url),
+            license.name()
+        ),
+        None => format!(
+            r#"<span class="blog-license-badge">{}</span>"#,
+            license.name()
+        ),
+    };
+    format!(
+        r#"<p class="blog-license">{} {}</p>"#,
+        badge,
+        html::escape(&post.license_notice(config))
+    )
+}
+
+/// A post's cover image, as the `Img` component renders it.
+fn cover_html(cover: &Cover, class: &str, layout: ImgLayout, eager: bool) -> String {
+    img_html(cover.image(), cover.alt, class, layout, eager)
+}
+
+/// An image in `assets/images/` as the `Img` component renders it: its size and blurred placeholder from the build,
+/// and its smaller copies in `srcset`.
+fn img_html(image: &ImageInfo, alt: &str, class: &str, layout: ImgLayout, eager: bool) -> String {
+    let srcset = image
+        .srcset()
+        .map(|srcset| {
+            format!(
+                r#" srcset="{}" sizes="{}""#,
+                html::escape(&srcset),
+                layout.sizes()
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        r#"<span class="img-frame"><img class="img-lqip" src="{placeholder}" alt="" aria-hidden="true"><img src="{src}"{srcset} alt="{alt}" class="{class}" width="{width}" height="{height}" loading="{loading}" decoding="async"></span>"#,
+        placeholder = image.placeholder,
+        src = html::escape(image.src),
+        srcset = srcset,
+        alt = html::escape(alt),
+        class = class,
+        width = image.width,
+        height = image.height,
+        loading = if eager { "eager" } else { "lazy" },
+    )
+}
+
+/// Where a syndicated post first appeared, for posts with a `canonical_url`.
+fn original_html(post: &Post) -> String {
+    match (post.canonical_url, post.original_site()) {
+        (Some(url), Some(site)) => format!(
+            r#"<p class="blog-original">Originally published at <a href="{url}">{site}</a>.</p>"#,
+            url = html::escape(url),
+            site = html::escape(site),
+        ),
+        _ => String::new(),
+    }
+}
+
+/// The "Updates" box at the top of a post revised since publishing, newest update first.
+fn updates_html(post: &Post) -> String {
+    if post.updates.is_empty() {
+        return String::new();
+    }
+    let items = post
+        .updates_newest_first()
+        .iter()
+        .map(|update| {
+            format!(
+                r#"<li><time datetime="{date}">{label}</time>: {note}</li>"#,
+                date = update.date,
+                label = update.date_label(),
+                note = html::escape(update.note),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                    ");
+
+    format!(
+        r#"<aside class="blog-updates" aria-labelledby="post-updates">
+                <h2 id="post-updates" class="blog-updates-title">Updates</h2>
+                <ul>
+                    {items}
+                </ul>
+            </aside>"#
+    )
+}
+
+/// The narration player at the top of a post with an `audio` file: the browser's audio controls, which the
+/// `mount_narration` island mounts over to add the speed control.
+fn narration_html(post: &Post) -> String {
+    let Some(audio) = post.audio else {
+        return String::new();
+    };
+    let media_type = post.audio_type().unwrap_or_default();
+
+    format!(
+        r#"<div id="narration-root" data-src="{src}" data-type="{media_type}">
+                <figure class="narration">
+                    <figcaption id="narration-label">Listen to this post</figcaption>
+                    <audio controls preload="none" aria-labelledby="narration-label">
+                        <source src="{src}" type="{media_type}">
+                        Your browser can't play this audio. <a href="{src}" download>Download the narration</a>
+                    </audio>
+                </figure>
+            </div>"#,
+        src = html::escape(audio),
+        media_type = media_type,
+    )
+}
+
+/// The reaction buttons under a post, with the counts so far. Without JavaScript they post the plain form to
+/// [`REACTIONS_ENDPOINT`]; the `mount_reactions` island mounts over them with the same buttons. Only with `[reactions]
+/// enabled`, so posts on a host without the endpoint don't get the buttons or the island.
+fn reactions_html(slug: &str, counts: &ReactionCounts) -> Result<String, serde_json::Error> {
+    let buttons = Reaction::ALL
+        .iter()
+        .map(|&reaction| {
+            format!(
+                r#"<button type="submit" class="reaction" name="reaction" value="{id}" aria-label="{label} ({count})"><span aria-hidden="true">{emoji}</span> <span class="reaction-count">{count}</span></button>"#,
+                id = reaction.id(),
+                label = reaction.label(),
+                emoji = reaction.emoji(),
+                count = counts.get(reaction),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                        ");
+
+    Ok(format!(
+        r#"<div id="reactions-root" data-post="{post}" data-counts="{counts}">
+                    <form class="reactions" method="post" action="{endpoint}" aria-label="Reactions">
+                        <input type="hidden" name="post" value="{post}">
+                        {buttons}
+                    </form>
+                </div>"#,
+        post = html::escape(slug),
+        counts = html::escape(&serde_json::to_string(counts)?),
+        endpoint = REACTIONS_ENDPOINT,
+        buttons = buttons,
+    ))
+}
+
+/// Keyword and technology landing pages under `/search/`, for visitors without JavaScript and for search engines.
+fn generate_search_pages(
+    build: &mut Build,
+    posts: &[Post],
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", search::SEARCH_PATH);
+
+    let keywords = search::keywords(posts, &build.config().search);
+    let tags = search::tags(data::PROJECTS);
+
+    let keyword_links = keywords
+        .iter()
+        .map(|keyword| {
+            format!(
+                r#"<li><a href="{path}">{term}</a> ({count})</li>"#,
+                path = keyword.path(),
+                term = html::escape(&keyword.term),
+                count = keyword.posts.len(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+    let tag_links = tags
+        .iter()
+        .map(|(tag, projects)| {
+            format!(
+                r#"<li><a href="{path}">{tag}</a> ({count})</li>"#,
+                path = search::tag_path(tag),
+                tag = html::escape(tag),
+                count = projects.len(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Search</h1>
+        <div id="site-search-root">
+            <form class="site-search" role="search" method="get" action="{search}">
+                <label for="site-search-query">Search posts and projects</label>
+                <input id="site-search-query" type="search" name="{param}">
+                <button type="submit">Search</button>
+            </form>
+            <noscript><p>Searching needs JavaScript; the keywords and technologies below work without it.</p></noscript>
+        </div>
+        <h2 class="heading heading-2">Posts by keyword</h2>
+        <ul class="search-terms">
+                {keyword_links}
+        </ul>
+        <h2 class="heading heading-2">Projects by technology</h2>
+        <ul class="search-terms">
+                {tag_links}
+        </ul>
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        search = search::SEARCH_PATH,
+        param = site_search::QUERY_PARAM,
+        keyword_links = keyword_links,
+        tag_links = tag_links,
+    );
+    let html = create_html_document(
+        "Search - Dioxus Site",
+        "Search the blog posts and projects, or find them by the keywords they mention and the technologies they use.",
+        &content,
+        None,
+    );
+    let page = build.page(search::SEARCH_PATH, &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+
+    for keyword in &keywords {
+        let term = html::escape(&keyword.term);
+        let entries = keyword
+            .posts
+            .iter()
+            .map(|post| {
+                format!(
+                    r#"<li class="blog-index-entry">
+                    <h2 class="heading heading-2" {transition_attribute}="{post_title}"><a href="{path}">{title}</a></h2>
+                    <p class="blog-date">{date}</p>
+                    <p>{excerpt}</p>
+                </li>"#,
+                    transition_attribute = view_transitions::TRANSITION_ATTRIBUTE,
+                    post_title = view_transitions::POST_TITLE,
+                    path = post.path(),
+                    title = post.title,
+                    date = post.date_label(),
+                    excerpt = html::escape(&post.excerpt()),
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n                ");
+
+        let content = format!(
+            r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Posts about “{term}”</h1>
+        <ul class="blog-index">
+                {entries}
+        </ul>
+        <p><a href="{search}">← All keywords</a></p>
+    </div>"#,
+            scope = styles::BLOG.scope,
+            navbar = navbar_html(),
+            term = term,
+            entries = entries,
+            search = search::SEARCH_PATH,
+        );
+        let html = create_html_document(
+            &format!("Posts about {} - Dioxus Site", term),
+            &format!(
+                "Blog posts that mention {}, newest mentions first, with their date and a short excerpt.",
+                term
+            ),
+            &content,
+            None,
+        );
+        let page = build.page(&keyword.path(), &html)?;
+        println!("✅ Generated: {}", describe_files(&page.files));
+    }
+
+    for (tag, projects) in &tags {
+        let name = html::escape(tag);
+        let cards = projects
+            .iter()
+            .map(|project| project_card(project))
+            .collect::<Vec<_>>()
+            .join("\n                ");
+
+        let content = format!(
+            r#"{navbar}
+    <div id="projects" class="{scope}">
+        <h1 class="heading heading-1">Projects using {name}</h1>
+        <ul class="project-grid">
+                {cards}
+        </ul>
+        <p><a href="{search}">← All technologies</a></p>
+    </div>"#,
+            scope = styles::PROJECTS.scope,
+            navbar = navbar_html(),
+            name = name,
+            cards = cards,
+            search = search::SEARCH_PATH,
+        );
+        let html = create_html_document(
+            &format!("Projects using {} - Dioxus Site", name),
+            &format!(
+                "Projects built with {}, with a short description and a link to each one.",
+                name
+            ),
+            &content,
+            None,
+        );
+        let page = build.page(&search::tag_path(tag), &html)?;
+        println!("✅ Generated: {}", describe_files(&page.files));
+    }
+
+    Ok(())
+}
+
+/// The latest posts from the blogs in `[[blogroll.feeds]]`, fetched now or taken from the feed cache.
+fn generate_blogroll_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", BLOGROLL_PATH);
+
+    let config = &build.config().blogroll;
+    let feeds = blogroll::load(config);
+
+    let entries = blogroll::latest(&feeds, config.items)
+        .into_iter()
+        .map(|(feed, item)| {
+            format!(
+                r#"<li class="blog-index-entry">
+                    <h2 class="heading heading-2"><a href="{url}" rel="noopener">{title}</a></h2>
+                    <p class="blog-date">{source}</p>
+                </li>"#,
+                url = html::escape(&item.url),
+                title = html::escape(&item.title),
+                source = std::iter::once(html::escape(&feed.name))
+                    .chain(item.date_label())
+                    .collect::<Vec<_>>()
+                    .join(" · "),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+    let entries = if entries.is_empty() {
+        "<li>Nothing to show right now.</li>".to_string()
+    } else {
+        entries
+    };
+
+    let blogs = config
+        .feeds
+        .iter()
+        .map(|feed| {
+            format!(
+                r#"<li><a href="{site}" rel="noopener">{name}</a> (<a href="{url}" rel="noopener">feed</a>)</li>"#,
+                site = html::escape(feed.site_url()),
+                name = html::escape(&feed.name),
+                url = html::escape(&feed.url),
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Blogroll</h1>
+        <p>The latest posts from blogs I follow, as of the last time this site was built.</p>
+        <ul class="blog-index">
+                {entries}
+        </ul>
+        <h2 class="heading heading-2">Blogs I follow</h2>
+        <ul class="search-terms">
+                {blogs}
+        </ul>
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        entries = entries,
+        blogs = blogs,
+    );
+    let html = create_html_document(
+        "Blogroll - Dioxus Site",
+        "The latest posts from the blogs I follow, collected from their feeds whenever this site is built.",
+        &content,
+        None,
+    );
+
+    let page = build.page(BLOGROLL_PATH, &html)?;
+    println!(
+        "✅ Generated: {} ({} of {} feeds)",
+        describe_files(&page.files),
+        feeds.len(),
+        config.feeds.len()
+    );
+    Ok(())
+}
+
+/// How to contribute a post, with a link to a pre-filled draft on GitHub. Left out without a repository or a
+/// `[source] proposals` directory.
+fn generate_propose_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    let source = &build.config().source;
+    let Some(draft_url) = proposals::draft_url(source) else {
+        return Ok(());
+    };
+    println!("🔨 Generating: {}", PROPOSE_PATH);
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Propose a post</h1>
+        <p>Posts from readers are welcome: a write-up of something you built with Dioxus, a correction that grew into an
+        article, or a topic you'd like covered. Proposals are pull requests on GitHub, so they can be discussed and
+        edited in the open.</p>
+        <ol>
+            <li><a href="{draft_url}" rel="noopener">Start a draft on GitHub</a>. It opens a new file in
+            <code>{dir}</code> with a title, a summary and your name to fill in above the post.</li>
+            <li>Rename the file after the post, e.g. <code>hydrating-islands.md</code>, and write the post in markdown.</li>
+            <li>Choose "Propose changes". GitHub makes a copy of the repository for you if you can't write to it, and
+            then asks you to open a pull request.</li>
+            <li>I'll reply on the pull request. Once it's ready, the post goes on the blog with you credited as its
+            author.</li>
+        </ol>
+        <p><a href="{repository}" rel="noopener">Open pull requests</a> show what's being worked on.</p>
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        draft_url = html::escape(&draft_url),
+        dir = html::escape(&source.proposals),
+        repository = html::escape(&format!("https://github.com/{}/pulls", source.repository)),
+    );
+    let html = create_html_document(
+        "Propose a post - Dioxus Site",
+        "How to contribute a post to the blog: start a pre-filled draft on GitHub and open a pull request.",
+        &content,
+        None,
+    );
+
+    let page = build.page(PROPOSE_PATH, &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
+    Ok(())
+}
+
+/// The privacy policy and imprint, from the templates in `templates/legal/`.
+fn generate_legal_pages(
+    build: &mut Build,
+    today: NaiveDate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for page in legal::PAGES {
+        println!("🔨 Generating: {}", page.path);
+
+        let content = format!(
+            r#"{navbar}
+    <div class="{scope} container legal-page">
+        <h1 class="heading heading-1">{title}</h1>
+        {body}
+    </div>"#,
+            scope = styles::BLOG.scope,
+            navbar = navbar_html(),
+            title = page.title,
+            body = page.render(build.config(), today)?,
+        );
+        let html = create_html_document(
+            &format!("{} - Dioxus Site", page.title),
+            page.description,
+            &content,
+            None,
+        );
+
+        let page = build.page(page.path, &html)?;
+        println!("✅ Generated: {}", describe_files(&page.files));
+    }
+    Ok(())
+}
+
+/// What changed on the site, from the git history of the files in `[[changelog.sources]]`.
+fn generate_changelog_page(
+    build: &mut Build,
+    today: NaiveDate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", CHANGELOG_PATH);
+
+    let config = &build.config().changelog;
+    let months = changelog::load(config, today).unwrap_or_else(|e| {
+        println!("⚠️  Changelog: no history to show ({})", e);
+        Vec::new()
+    });
+    let count: usize = months.iter().map(|month| month.changes.len()).sum();
+
+    let sections = months
+        .iter()
+        .map(|month| {
+            let changes = month
+                .changes
+                .iter()
+                .map(|change| {
+                    let pages = change
+                        .sources
+                        .iter()
+                        .map(|source| {
+                            format!(
+                                r#"<a href="{page}">{name}</a>"#,
+                                page = html::escape(&source.page),
+                                name = html::escape(&source.name),
+                            )
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!(
+                        r#"<li class="blog-index-entry" data-date="{date}">
+                    <p class="blog-date"><span class="blog-badge" hidden>New</span> {label} · {pages}</p>
+                    <p>{summary}</p>
+                </li>"#,
+                        date = change.date,
+                        label = change.date.format("%B %-d"),
+                        pages = pages,
+                        summary = html::escape(&change.summary),
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n                ");
+            format!(
+                r#"<h2 class="heading heading-2">{label}</h2>
+        <ul class="blog-index">
+                {changes}
+        </ul>"#,
+                label = month.label(),
+                changes = changes,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n        ");
+    let sections = if sections.is_empty() {
+        "<p>Nothing to show right now.</p>".to_string()
+    } else {
+        sections
+    };
+
+    // Remembers the last visit in the browser, and marks what changed after it
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">What's new</h1>
+        <p>Changes to the site over the last {months} months, newest first.</p>
+        <p id="changelog-since" hidden></p>
+        {sections}
+    </div>
+    <script>
+    (function () {{
+        var key = 'changelog-last-visit';
+        var last = null;
+        try {{ last = localStorage.getItem(key); }} catch (e) {{}}
+        if (last) {{
+            var entries = document.querySelectorAll('[data-date]');
+            var count = 0;
+            for (var i = 0; i < entries.length; i++) {{
+                if (entries[i].getAttribute('data-date') > last) {{
+                    entries[i].querySelector('.blog-badge').hidden = false;
+                    count++;
+                }}
+            }}
+            var since = document.getElementById('changelog-since');
+            since.textContent = count === 0
+                ? 'Nothing has changed since your last visit on ' + last + '.'
+                : count + (count === 1 ? ' change' : ' changes') + ' since your last visit on ' + last + '.';
+            since.hidden = false;
+        }}
+        try {{ localStorage.setItem(key, new Date().toISOString().slice(0, 10)); }} catch (e) {{}}
+    }})();
+    </script>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        months = config.months,
+        sections = sections,
+    );
+    let html = create_html_document(
+        "What's new - Dioxus Site",
+        "Changes to the posts and pages of this site, month by month, with links to what changed.",
+        &content,
+        None,
+    );
+
+    let page = build.page(CHANGELOG_PATH, &html)?;
+    println!(
+        "✅ Generated: {} ({} changes)",
+        describe_files(&page.files),
+        count
+    );
+    Ok(())
+}
+
+fn generate_talk_pages(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", TALKS_PATH);
+
+    let talks = talks::load(Path::new(TALKS_DIR))?;
+
+    let entries = talks
+        .iter()
+        .map(|talk| {
+            let description = talk
+                .description
+                .as_deref()
+                .map(|description| {
+                    format!("\n                    <p>{}</p>", html::escape(description))
+                })
+                .unwrap_or_default();
+            format!(
+                r#"<li class="blog-index-entry">
+                    <h2 class="heading heading-2"><a href="{path}">{title}</a></h2>
+                    <p class="blog-date">{byline} · {count} slides</p>{description}
+                </li>"#,
+                path = talk.path(),
+                title = html::escape(&talk.title),
+                byline = html::escape(&talk.byline()),
+                count = talk.slides.len(),
+                description = description,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n                ");
+    let entries = if entries.is_empty() {
+        "<li>Nothing to show right now.</li>".to_string()
+    } else {
+        entries
+    };
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Talks</h1>
+        <p>Slides from talks I've given, with my speaker notes. Want one at your event? <a href="{speak}">Get in touch</a>.</p>
+        <ul class="blog-index">
+                {entries}
+        </ul>
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        speak = link_to(Route::Speak {}),
+        entries = entries,
+    );
+    let html = create_html_document(
+        "Talks - Dioxus Site",
+        "Slides and speaker notes from talks about Rust, WebAssembly and Dioxus.",
+        &content,
+        None,
+    );
+    let page = build.page(TALKS_PATH, &html)?;
+    println!(
+        "✅ Generated: {} ({} talks)",
+        describe_files(&page.files),
+        talks.len()
+    );
+
+    for talk in &talks {
+        generate_talk_page(build, talk)?;
+    }
+    Ok(())
+}
+
+/// A talk's slides, the title slide first. They read as a plain sideways-scrolling page; the Slideshow island below
+/// them adds the controls and the keyboard.
+fn generate_talk_page(build: &mut Build, talk: &Talk) -> Result<(), Box<dyn std::error::Error>> {
+    let path = talk.path();
+    println!("🔨 Generating: {}", path);
+
+    let total = talk.slides.len() + 1;
+    let title_slide = format!(
+        r#"<section class="{slide} slide-title" id="slide-1" aria-label="Slide 1 of {total}">
+                <h1 class="heading heading-1">{title}</h1>
+                <p class="blog-date">{byline}</p>
+                {intro}
+            </section>"#,
+        slide = SLIDE_CLASS,
+        total = total,
+        title = html::escape(&talk.title),
+        byline = html::escape(&talk.byline()),
+        intro = talk.intro_html,
+    );
+    let slides = talk
+        .slides
+        .iter()
+        .enumerate()
+        .map(|(index, slide)| {
+            let number = index + 2;
+            let notes = slide
+                .notes_html
+                .as_deref()
+                .map(|notes| {
+                    format!(
+                        r#"
+                <details class="{class}">
+                    <summary>Speaker notes</summary>
+                    {notes}
+                </details>"#,
+                        class = NOTES_CLASS,
+                        notes = notes,
+                    )
+                })
+                .unwrap_or_default();
+            format!(
+                r#"<section class="{slide}" id="slide-{number}" aria-label="Slide {number} of {total}">
+                <h2>{title}</h2>
+                {html}{notes}
+            </section>"#,
+                slide = SLIDE_CLASS,
+                number = number,
+                total = total,
+                title = html::escape(&slide.title),
+                html = slide.html,
+                notes = notes,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n            ");
+
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <p><a href="{talks}">← All talks</a></p>
+        <div id="{slides_id}" class="slides" tabindex="0" aria-label="Slides of {title}">
+            {title_slide}
+            {slides}
+        </div>
+        <div id="slideshow-root"></div>
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        talks = TALKS_PATH,
+        slides_id = SLIDES_ID,
+        title = html::escape(&talk.title),
+        title_slide = title_slide,
+        slides = slides,
+    );
+    let description = talk
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("Slides from {}, {}.", talk.title, talk.byline()));
+    let html = create_html_document(
+        &html::escape(&format!("{} - Talks - Dioxus Site", talk.title)),
+        &html::escape(&description),
+        &content,
+        None,
+    );
+
+    let page = build.page(&path, &html)?;
+    println!(
+        "✅ Generated: {} ({} slides)",
+        describe_files(&page.files),
+        total
+    );
+    Ok(())
+}
+
+/// Every note on one page, newest first under a heading for each week, with the notes feed linked from the head.
+fn generate_notes_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", NOTES_PATH);
+
+    let notes = notes::load(Path::new(NOTES_DIR))?;
+
+    let weeks = notes::by_week(&notes)
+        .into_iter()
+        .map(|(week, notes)| {
+            let entries = notes
                 .iter()
-                .map(|interest| format!(
-                    r#"<div class="interest-item">
-                    <span class="interest-bullet">•</span>
-                    {}</div>"#,
-                    interest.as_str().unwrap_or("")
-                ))
+                .map(|note| {
+                    format!(
+                        r##"<article class="note" id="{slug}">
+                <div class="note-body">{html}</div>
+                <p class="blog-date"><a href="#{slug}"><time datetime="{date}">{label}</time></a></p>
+            </article>"##,
+                        slug = html::escape(&note.slug),
+                        html = note.html,
+                        date = note.date,
+                        label = note.date_label(),
+                    )
+                })
                 .collect::<Vec<_>>()
-                .join(""))
-            .unwrap_or_default(),
-        email = data["contact"]["email"].as_str().unwrap_or(""),
-        website = data["contact"]["website"].as_str().unwrap_or(""),
-        github = data["contact"]["github"].as_str().unwrap_or(""),
-        updated = data["updated"].as_str().unwrap_or(""),
-    );
+                .join("\n            ");
+            format!(
+                r#"<section class="notes-week">
+            <h2 class="heading heading-2">{label}</h2>
+            {entries}
+        </section>"#,
+                label = notes::week_label(week),
+                entries = entries,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n        ");
+    let weeks = if weeks.is_empty() {
+        "<p>Nothing to show right now.</p>".to_string()
+    } else {
+        weeks
+    };
 
+    let content = format!(
+        r#"{navbar}
+    <div class="{scope} container">
+        <h1 class="heading heading-1">Notes</h1>
+        <p>Short notes, links and tips between the <a href="{blog}">blog posts</a>. They have <a href="{feed}">a feed of their own</a>.</p>
+        {weeks}
+    </div>"#,
+        scope = styles::BLOG.scope,
+        navbar = navbar_html(),
+        blog = link_to(Route::BlogIndex {}),
+        feed = NOTES_FEED_PATH,
+        weeks = weeks,
+    );
     let html = create_html_document(
-        "About - Dioxus Site",
-        "Learn more about me and my work",
+        "Notes - Dioxus Site",
+        "Short notes, links and tips about Rust, WebAssembly and Dioxus, written between the longer blog posts.",
         &content,
         None,
+    )
+    .replacen(
+        "</head>",
+        &format!(
+            "    <link rel=\"alternate\" type=\"application/rss+xml\" title=\"Dioxus Site - Notes\" href=\"{}\">\n</head>",
+            NOTES_FEED_PATH
+        ),
+        1,
     );
+    let mut files = build.page(NOTES_PATH, &html)?.files.clone();
 
-    let about_dir = output_dir.join("about");
-    fs::create_dir_all(&about_dir)?;
-    fs::write(about_dir.join("index.html"), html)?;
-    println!("✅ Generated: about/index.html");
-    Ok(())
-}
+    let feed = PathBuf::from(NOTES_FEED_PATH.trim_start_matches('/'));
+    let target = build.output_dir().join(&feed);
+    if let Some(dir) = target.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(target, notes::render_feed(build.config(), &notes))?;
+    files.push(feed);
 
-fn generate_contact_page(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔨 Generating: /contact (static version)");
-    generate_static_contact_page(output_dir)?;
+    println!(
+        "✅ Generated: {} ({} notes)",
+        describe_files(&files),
+        notes.len()
+    );
     Ok(())
 }
 
-fn generate_static_contact_page(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let content = r#"<div id="navbar">
-        <a href="/">Home</a>
-        <a href="/about">About</a>
-        <a href="/contact">Contact</a>
-        <a href="/blog/1">Blog</a>
-    </div>
-    <div class="contact-container">
-        <header class="contact-header">
-            <h1 class="contact-title">Contact Me</h1>
-            <p class="contact-subtitle">Get in touch! This page will demonstrate dynamic JavaScript/WASM functionality.</p>
-        </header>
-
-        <div class="contact-content">
-            <div class="contact-info">
-                <h2>Contact Information</h2>
-                <div class="contact-methods">
-                    <div class="contact-method">
-                        <span class="contact-icon">📧</span>
-                        <div>
-                            <h3>Email</h3>
-                            <a href="mailto:marcin.sydor@sky.uk" class="contact-link">marcin.sydor@sky.uk</a>
-                        </div>
-                    </div>
-                    <div class="contact-method">
-                        <span class="contact-icon">💼</span>
-                        <div>
-                            <h3>LinkedIn</h3>
-                            <p>Connect with me professionally</p>
-                        </div>
-                    </div>
-                    <div class="contact-method">
-                        <span class="contact-icon">⚡</span>
-                        <div>
-                            <h3>GitHub</h3>
-                            <a href="https://github.com/marcinsydor" target="_blank" class="contact-link">@marcinsydor</a>
-                        </div>
-                    </div>
-                </div>
-            </div>
+/// Posts used to live at `/blog/<number>`; keep those URLs working with redirect stubs to the slug URLs.
+fn generate_blog_redirects(
+    build: &mut Build,
+    today: NaiveDate,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (id, slug) in posts::legacy_redirects(today) {
+        let target = format!("/blog/{}/", slug);
+        let files = write_redirect(
+            build.output_dir(),
+            build.config(),
+            &format!("/blog/{}/", id),
+            &target,
+        )?;
+        println!("↪️  Redirect: {} -> {}", describe_files(&files), target);
+    }
 
-            <div class="contact-form-section">
-                <h2>Send a Message</h2>
+    Ok(())
+}
 
-                <div class="js-functionality-notice">
-                    <p>🚀 <strong>Dynamic Form Demo:</strong> This form will demonstrate JavaScript/WASM functionality when enhanced with dynamic features.</p>
-                </div>
+/// Mount point of the connectivity indicator island and the service worker registration, at the end of every
+/// generated page.
+fn offline_support() -> String {
+    format!(
+        r#"<div id="connectivity-root"></div>
+    <script>
+    if ('serviceWorker' in navigator) {{
+        navigator.serviceWorker.register('{}');
+    }}
+    </script>"#,
+        SERVICE_WORKER_PATH
+    )
+}
 
-                <div class="static-form-notice">
-                    <h3>📄 Static Version</h3>
-                    <p>You're viewing the static HTML version. The form below is for display purposes.</p>
-                    <p>When JavaScript is enabled, this becomes a fully interactive form with:</p>
-                    <ul>
-                        <li>Real-time validation</li>
-                        <li>Dynamic state management</li>
-                        <li>Client-side form processing</li>
-                        <li>WASM-powered functionality</li>
-                    </ul>
-                </div>
+/// Marker in the generated error pages that a server replaces with the request id of the failed request, so a
+/// visitor's report can be matched to the server logs. Static hosts serve it as an invisible comment.
+const REQUEST_ID_MARKER: &str = "<!--request-id-->";
+
+fn generate_error_pages(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    let pages = [
+        (
+            500,
+            "Something went wrong",
+            "An unexpected error occurred while handling your request. It has been logged and I'll look into it.",
+        ),
+        (
+            503,
+            "Temporarily unavailable",
+            "The site is down for maintenance or under heavy load. Please try again in a few minutes.",
+        ),
+    ];
+
+    for (status, heading, explanation) in pages {
+        println!("🔨 Generating: /{}.html", status);
 
-                <form class="contact-form">
-                    <div class="form-row">
-                        <div class="form-group">
-                            <label for="name">Name *</label>
-                            <input type="text" id="name" class="form-input" placeholder="Your full name" />
-                        </div>
-                        <div class="form-group">
-                            <label for="email">Email *</label>
-                            <input type="email" id="email" class="form-input" placeholder="your.email@example.com" />
-                        </div>
-                    </div>
+        let content = format!(
+            r#"{navbar}
+    <div class="container error-page">
+        <p class="error-status">{status}</p>
+        <h1>{heading}</h1>
+        <p>{explanation}</p>
+        <p>If you were sending a message, nothing was lost on your side: you can email me directly at
+            <a href="mailto:{email}">{email}</a>.</p>
+        <p class="error-request-id">{marker}</p>
+        <nav class="blog-nav">
+            <a href="{home}">← Back to Home</a>
+        </nav>
+    </div>"#,
+            home = link_to(Route::Home {}),
+            navbar = navbar_html(),
+            status = status,
+            heading = heading,
+            explanation = explanation,
+            email = data::ABOUT.contact.email,
+            marker = REQUEST_ID_MARKER,
+        );
 
-                    <div class="form-group">
-                        <label for="subject">Subject *</label>
-                        <input type="text" id="subject" class="form-input" placeholder="What's this about?" />
-                    </div>
+        let html = create_html_document(
+            &format!("{} - Dioxus Site", heading),
+            explanation,
+            &content,
+            None,
+        );
 
-                    <div class="form-group">
-                        <label for="message">Message *</label>
-                        <textarea id="message" class="form-textarea" placeholder="Tell me what's on your mind..." rows="6"></textarea>
-                    </div>
+        // Error pages are served by the host in place of the failed URL rather than routed to, so they keep their
+        // fixed file names and get no canonical link
+        fs::write(
+            build.output_dir().join(format!("{}.html", status)),
+            rewrite_links(&html, build.config().build.output_style),
+        )?;
+        println!("✅ Generated: {}.html", status);
+    }
 
-                    <div class="form-actions">
-                        <button type="button" class="btn btn-primary disabled">Send Message ✨ (Demo)</button>
-                        <button type="button" class="btn btn-secondary">Reset Form</button>
-                    </div>
+    Ok(())
+}
 
-                    <div class="form-note">
-                        <p>* This is a static demo form. Enable JavaScript to see dynamic functionality.</p>
-                    </div>
-                </form>
-            </div>
-        </div>
+/// The page hosts serve for paths that don't exist, with a "Did you mean…" island over the pages written so far.
+/// Like the other error pages it's served in place of the missing URL, so it keeps a fixed file name, and every URL in
+/// it is root-relative.
+fn generate_not_found_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /404.html");
+
+    let site_title = &build.config().site.title;
+    let pages: Vec<PageLink> = build
+        .pages()
+        .iter()
+        .filter(|page| !page.is_noindex())
+        .map(|page| PageLink {
+            path: build.config().build.output_style.page_url(&page.path),
+            title: meta::title(&page.html)
+                .map(|title| {
+                    title
+                        .strip_suffix(&format!(" - {}", site_title))
+                        .unwrap_or(&title)
+                        .to_string()
+                })
+                .unwrap_or_else(|| page.path.clone()),
+        })
+        .collect();
 
-        <div class="tech-details">
-            <h2>🔧 Technical Implementation</h2>
-            <div class="tech-grid">
-                <div class="tech-item">
-                    <h3>🦀 WebAssembly</h3>
-                    <p>Dynamic version uses Rust compiled to WASM for all interactive functionality</p>
-                </div>
-                <div class="tech-item">
-                    <h3>⚡ Reactive State</h3>
-                    <p>Real-time form validation and state management using Dioxus signals</p>
-                </div>
-                <div class="tech-item">
-                    <h3>🏗️ Hybrid Architecture</h3>
-                    <p>Static HTML foundation with dynamic JavaScript/WASM enhancement</p>
-                </div>
-                <div class="tech-item">
-                    <h3>📱 Progressive Enhancement</h3>
-                    <p>Works without JS, enhanced with dynamic features when available</p>
-                </div>
-            </div>
-        </div>
-    </div>"#;
+    let content = format!(
+        r#"{navbar}
+    <div class="container error-page">
+        <p class="error-status">404</p>
+        <h1>Page not found</h1>
+        <p>There's nothing at this address. The page may have moved, or the link may have a typo in it.</p>
+        <div id="not-found-root" data-pages="{pages}" data-report-rate="{report_rate}"></div>
+        <p>Try <a href="{search}">searching the site</a> or the <a href="{blog}">list of posts</a>.</p>
+        <nav class="blog-nav">
+            <a href="{home}">← Back to Home</a>
+        </nav>
+    </div>"#,
+        navbar = navbar_html(),
+        pages = html::escape(&serde_json::to_string(&pages)?),
+        report_rate = build.config().telemetry.sample_rate,
+        search = search::SEARCH_PATH,
+        blog = link_to(Route::BlogIndex {}),
+        home = link_to(Route::Home {}),
+    );
 
     let html = create_html_document(
-        "Contact - Dioxus Site",
-        "Get in touch with me through this contact form",
-        content,
+        "Page not found - Dioxus Site",
+        "There's nothing at this address; here are the pages that come closest.",
+        &content,
         None,
     );
 
-    let contact_dir = output_dir.join("contact");
-    fs::create_dir_all(&contact_dir)?;
-    fs::write(contact_dir.join("index.html"), html)?;
-    println!("✅ Generated: contact/index.html");
+    fs::write(
+        build.output_dir().join("404.html"),
+        rewrite_links(&html, build.config().build.output_style),
+    )?;
+    println!("✅ Generated: 404.html");
     Ok(())
 }
 
-fn generate_blog_pages(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    let blog_ids = vec![1, 2, 3];
-
-    for id in blog_ids {
-        println!("🔨 Generating: /blog/{}", id);
-
-        let content = format!(
-            r#"<div id="navbar">
-            <a href="/">Home</a>
-            <a href="/about">About</a>
-            <a href="/contact">Contact</a>
-            <a href="/blog/1">Blog</a>
-        </div>
-        <div class="container">
-            <h1>Blog Post {id}</h1>
-            <div class="blog-content">
-                <p>This is blog post number {id}.</p>
-                <p>In a real application, this content would be loaded from a database or markdown files.</p>
-
-                <h2>Sample Content</h2>
-                <p>Here's some sample content for blog post {id}. This demonstrates how static site generation works with Dioxus.</p>
-
-                <h3>Benefits of SSG</h3>
-                <ul>
-                    <li>Fast loading times</li>
-                    <li>Great SEO</li>
-                    <li>Works without JavaScript</li>
-                    <li>Easy to deploy</li>
-                </ul>
-
-                <nav class="blog-nav">
-                    <a href="/">← Back to Home</a>
-                    {prev_next}
-                </nav>
-            </div>
-        </div>"#,
-            id = id,
-            prev_next = if id > 1 && id < 3 {
-                format!(
-                    r#"<a href="/blog/{}">← Previous</a> <a href="/blog/{}">Next →</a>"#,
-                    id - 1,
-                    id + 1
-                )
-            } else if id > 1 {
-                format!(r#"<a href="/blog/{}">← Previous</a>"#, id - 1)
-            } else if id < 3 {
-                format!(r#"<a href="/blog/{}">Next →</a>"#, id + 1)
-            } else {
-                String::new()
-            }
-        );
+/// The page the service worker shows for pages that weren't cached before the connection dropped.
+fn generate_offline_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: {}", OFFLINE_PATH);
 
-        let html = create_html_document(
-            &format!("Blog Post {} - Dioxus Site", id),
-            &format!("Blog post number {}", id),
-            &content,
-            None,
-        );
+    let content = format!(
+        r#"{navbar}
+    <div class="container error-page offline-page">
+        <p class="error-status">📡</p>
+        <h1 class="heading heading-1">You're offline</h1>
+        <p>This page hasn't been saved on this device yet, and there's no connection to fetch it.</p>
+        <p>Pages you've read before still open without a connection. Messages can't be sent from the contact form until
+            you're back online.</p>
+        <nav class="blog-nav">
+            <a href="{home}">← Back to Home</a>
+            <a href="{blog}">Blog</a>
+        </nav>
+    </div>"#,
+        home = link_to(Route::Home {}),
+        blog = link_to(Route::BlogIndex {}),
+        navbar = navbar_html(),
+    );
 
-        let blog_dir = output_dir.join("blog").join(id.to_string());
-        fs::create_dir_all(&blog_dir)?;
-        fs::write(blog_dir.join("index.html"), html)?;
-        println!("✅ Generated: blog/{}/index.html", id);
-    }
+    let html = create_html_document(
+        "Offline - Dioxus Site",
+        "You're offline and this page isn't saved on this device. Pages you've read before are still available.",
+        &content,
+        None,
+    )
+    .replacen(
+        "</head>",
+        "    <meta name=\"robots\" content=\"noindex\">\n</head>",
+        1,
+    );
 
+    let page = build.page(OFFLINE_PATH, &html)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
     Ok(())
 }
 
+/// The design tokens as a `:root` rule for the page head. The theme is validated at startup, so a failure here can't
+/// happen in practice and just leaves the tokens out.
+fn theme_css() -> String {
+    Theme::load()
+        .map(|theme| theme.css_variables())
+        .unwrap_or_default()
+}
+
 fn create_html_document(
     title: &str,
     description: &str,
@@ -443,34 +2328,48 @@ fn create_html_document(
     <meta name="description" content="{description}">
 
     <!-- Styles -->
+    <style>
+{theme_css}    </style>
     <link rel="stylesheet" href="/assets/styling/main.css">
     <link rel="stylesheet" href="/assets/styling/navbar.css">
     <link rel="stylesheet" href="/assets/styling/about.css">
     <link rel="stylesheet" href="/assets/styling/contact.css">
     <link rel="stylesheet" href="/assets/styling/blog.css">
+    <link rel="stylesheet" href="/assets/styling/reactions.css">
+    <link rel="stylesheet" href="/assets/styling/reading_progress.css">
+    <link rel="stylesheet" href="/assets/styling/search.css">
+    <link rel="stylesheet" href="/assets/styling/projects.css">
     <link rel="stylesheet" href="/assets/styling/echo.css">
+    <link rel="stylesheet" href="/assets/styling/slideshow.css">
+    <link rel="stylesheet" href="/assets/styling/narration.css">
+    <link rel="stylesheet" href="/assets/styling/hints.css">
+    <link rel="stylesheet" href="/assets/styling/on_this_day.css">
+    <link rel="stylesheet" href="/assets/styling/img.css">
     <link rel="stylesheet" href="/assets/tailwind.css">
+    <link rel="stylesheet" href="/assets/styling/transitions.css">
+    <script>
+    {view_transitions}
+    </script>
 
     <!-- Favicon -->
     <link rel="icon" href="/assets/favicon.ico">
 
-    <!-- Additional meta tags for SEO -->
-    <meta property="og:title" content="{title}">
-    <meta property="og:description" content="{description}">
-    <meta property="og:type" content="website">
-    <meta name="twitter:card" content="summary">
-    <meta name="twitter:title" content="{title}">
-    <meta name="twitter:description" content="{description}">
+    <!-- Feed -->
+    <link rel="alternate" type="application/rss+xml" title="Dioxus Site" href="/feed.xml">
+    <link rel="search" type="{opensearch_type}" title="Dioxus Site" href="{opensearch}">
+
     {js_preload}
 </head>
 <body>
     <div id="main">{body_content}</div>
+    {offline_support}
+    <div id="hints-root"></div>
 
     <!-- Static site notice -->
     <noscript>
-        <div style="position: fixed; bottom: 1rem; right: 1rem; padding: 0.5rem 1rem; background: #f0f9ff; border: 1px solid #0ea5e9; border-radius: 0.5rem; font-size: 0.875rem; max-width: 300px; z-index: 1000;">
-            <p style="margin: 0; font-weight: bold; color: #0369a1;">📄 Static HTML</p>
-            <p style="margin: 0.25rem 0 0 0; color: #0369a1;">This page works without JavaScript!</p>
+        <div class="static-notice">
+            <p class="static-notice-title">📄 Static HTML</p>
+            <p>This page works without JavaScript!</p>
         </div>
     </noscript>
 
@@ -481,117 +2380,194 @@ fn create_html_document(
         margin: 0;
         padding: 0;
         line-height: 1.6;
-        color: #333;
+        color: var(--color-charcoal);
     }}
 
     .container {{
         max-width: 1200px;
         margin: 0 auto;
-        padding: 2rem;
+        padding: var(--space-8);
+    }}
+
+    .static-notice {{
+        position: fixed;
+        bottom: 1rem;
+        right: 1rem;
+        padding: var(--space-2) var(--space-4);
+        background: var(--color-sky-50);
+        border: 1px solid var(--color-sky-500);
+        border-radius: var(--radius-md);
+        font-size: 0.875rem;
+        max-width: 300px;
+        z-index: 1000;
+    }}
+
+    .static-notice p {{
+        margin: var(--space-1) 0 0 0;
+        color: var(--color-sky-700);
+    }}
+
+    .static-notice .static-notice-title {{
+        margin: 0;
+        font-weight: bold;
     }}
 
     #navbar {{
-        background: #f8fafc;
-        padding: 1rem;
-        border-bottom: 1px solid #e2e8f0;
-        margin-bottom: 2rem;
+        background: var(--color-slate-50);
+        padding: var(--space-4);
+        border-bottom: 1px solid var(--color-slate-200);
+        margin-bottom: var(--space-8);
     }}
 
     #navbar a {{
-        margin-right: 1rem;
+        margin-right: var(--space-4);
         text-decoration: none;
-        color: #2563eb;
+        color: var(--color-blue-600);
         font-weight: 500;
-        padding: 0.5rem 1rem;
-        border-radius: 0.375rem;
+        padding: var(--space-2) var(--space-4);
+        border-radius: var(--radius-md);
         transition: background-color 0.2s;
     }}
 
     #navbar a:hover {{
-        background: #dbeafe;
+        background: var(--color-blue-100);
     }}
 
     .blog-nav {{
-        margin-top: 2rem;
-        padding-top: 2rem;
-        border-top: 1px solid #e2e8f0;
+        margin-top: var(--space-8);
+        padding-top: var(--space-8);
+        border-top: 1px solid var(--color-slate-200);
     }}
 
     .blog-nav a {{
-        margin-right: 1rem;
+        margin-right: var(--space-4);
         text-decoration: none;
-        color: #2563eb;
+        color: var(--color-blue-600);
     }}
 
     .blog-nav a:hover {{
         text-decoration: underline;
     }}
 
+    .blog-footer {{
+        margin-top: var(--space-4);
+        font-size: 0.875rem;
+    }}
+
+    .blog-footer a {{
+        color: var(--color-gray-500);
+    }}
+
     h1 {{
-        color: #1f2937;
-        margin-bottom: 1rem;
+        color: var(--color-gray-800);
+        margin-bottom: var(--space-4);
     }}
 
     h2 {{
-        color: #374151;
-        margin-top: 2rem;
-        margin-bottom: 1rem;
+        color: var(--color-gray-700);
+        margin-top: var(--space-8);
+        margin-bottom: var(--space-4);
     }}
 
     h3 {{
-        color: #4b5563;
-        margin-top: 1.5rem;
-        margin-bottom: 0.5rem;
+        color: var(--color-gray-600);
+        margin-top: var(--space-6);
+        margin-bottom: var(--space-2);
     }}
 
     ul {{
-        padding-left: 1.5rem;
+        padding-left: var(--space-6);
     }}
 
     li {{
-        margin-bottom: 0.25rem;
+        margin-bottom: var(--space-1);
+    }}
+
+    .error-status {{
+        font-size: 4rem;
+        font-weight: bold;
+        color: var(--color-blue-600);
+        margin: 0;
+    }}
+
+    .error-request-id {{
+        color: var(--color-gray-500);
+        font-size: 0.875rem;
+    }}
+
+    .not-found-path {{
+        color: var(--color-gray-500);
+        font-size: 0.875rem;
     }}
     </style>
 </body>
 </html>"#,
-        title = title,
+        title = html::escape(title),
         js_preload = js_preload,
-        description = description,
-        body_content = body_content
+        theme_css = theme_css(),
+        description = html::escape(description),
+        body_content = body_content,
+        offline_support = offline_support(),
+        opensearch = OPENSEARCH_PATH,
+        opensearch_type = OPENSEARCH_TYPE,
+        view_transitions = view_transitions::STATIC_SCRIPT,
     )
 }
 
-fn copy_assets(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+fn copy_assets(
+    output_dir: &Path,
+    config: &AssetsConfig,
+    responsive: &ResponsiveImagesConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
     println!("📦 Copying assets...");
 
     let assets_src = Path::new("assets");
-    let assets_dest = output_dir.join("assets");
-
     if assets_src.exists() {
-        copy_dir_recursive(assets_src, &assets_dest)?;
+        let plan = assets::plan(assets_src, config, responsive)?;
+        assets::copy(assets_src, &output_dir.join("assets"), &plan)?;
+
+        let skipped = plan
+            .iter()
+            .filter(|asset| asset.transform.is_none())
+            .count();
+        if skipped > 0 {
+            println!("⏭️  Skipped {} excluded asset(s)", skipped);
+        }
     }
 
     println!("✅ Assets copied");
     Ok(())
 }
 
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<(), Box<dyn std::error::Error>> {
-    if !dst.exists() {
-        std::fs::create_dir_all(dst)?;
-    }
+/// Print what `copy_assets` would do with every file under `assets/`, without writing anything.
+fn print_asset_plan(
+    config: &AssetsConfig,
+    responsive: &ResponsiveImagesConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    println!("📦 Asset plan (dry run):");
 
-    for entry in std::fs::read_dir(src)? {
-        let entry = entry?;
-        let src_path = entry.path();
-        let dst_path = dst.join(entry.file_name());
+    let assets_src = Path::new("assets");
+    if !assets_src.exists() {
+        println!("   (no assets directory)");
+        return Ok(());
+    }
 
-        if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
-        } else {
-            std::fs::copy(&src_path, &dst_path)?;
+    for asset in assets::plan(assets_src, config, responsive)? {
+        let action = match asset.transform {
+            Some(AssetTransform::Copy) => "copy",
+            Some(AssetTransform::Minify) => "minify",
+            Some(AssetTransform::Strip) => "strip",
+            None => "skip",
+        };
+        println!("   {:<6} assets/{}", action, asset.path);
+        for width in asset.widths {
+            println!(
+                "   {:<6} assets/{}",
+                "resize",
+                responsive_images::variant_path(&asset.path, width)
+            );
         }
     }
-
     Ok(())
 }
 
@@ -600,30 +2576,15 @@ fn create_html_document_with_css(
     title: &str,
     description: &str,
     content: &str,
-    js_path: Option<&str>,
-    wasm_path: Option<&str>,
     additional_css: Option<&str>,
 ) -> String {
-    let js_import = js_path
-        .map(|path| format!(r#"<script type="module" src="{}"></script>"#, path))
-        .unwrap_or_default();
-
-    let wasm_preload = wasm_path
-        .map(|path| {
-            format!(
-                r#"<link rel="preload" as="fetch" href="{}" crossorigin>"#,
-                path
-            )
-        })
-        .unwrap_or_default();
-
     let extra_css = additional_css.unwrap_or("");
 
     // Include base CSS and preload hints
     let base_css = r#"
 body {
-    background-color: #0f1116;
-    color: #ffffff;
+    background-color: var(--color-background);
+    color: var(--color-white);
     font-family: 'Segoe UI', Tahoma, Geneva, Verdana, sans-serif;
     margin: 20px;
 }
@@ -651,12 +2612,12 @@ body {
     margin-top: 20px;
     margin: 10px 0px;
     border: white 1px solid;
-    border-radius: 5px;
+    border-radius: var(--radius-sm);
     padding: 10px;
 }
 
 #links a:hover {
-    background-color: #1f1f1f;
+    background-color: var(--color-code-background);
     cursor: pointer;
 }
 
@@ -668,19 +2629,19 @@ body {
 .contact-container {
     max-width: 1200px;
     margin: 0 auto;
-    padding: 2rem;
+    padding: var(--space-8);
 }
 
 .contact-header {
     text-align: center;
-    margin-bottom: 3rem;
+    margin-bottom: var(--space-12);
 }
 
 .contact-title {
     font-size: 3rem;
     font-weight: bold;
-    margin-bottom: 1rem;
-    background: linear-gradient(135deg, #667eea 0%, #764ba2 100%);
+    margin-bottom: var(--space-4);
+    background: linear-gradient(135deg, var(--color-hero-start) 0%, var(--color-hero-end) 100%);
     -webkit-background-clip: text;
     -webkit-text-fill-color: transparent;
     background-clip: text;
@@ -688,7 +2649,7 @@ body {
 
 .contact-subtitle {
     font-size: 1.25rem;
-    color: #9ca3af;
+    color: var(--color-gray-400);
     max-width: 600px;
     margin: 0 auto;
 }
@@ -696,45 +2657,45 @@ body {
 .contact-content {
     display: grid;
     grid-template-columns: 1fr 1fr;
-    gap: 3rem;
-    margin-bottom: 3rem;
+    gap: var(--space-12);
+    margin-bottom: var(--space-12);
 }
 
 @media (max-width: 768px) {
     .contact-content {
         grid-template-columns: 1fr;
-        gap: 2rem;
+        gap: var(--space-8);
     }
 }
 
 .contact-info h2 {
     font-size: 1.5rem;
     font-weight: 600;
-    margin-bottom: 1.5rem;
-    color: #f9fafb;
+    margin-bottom: var(--space-6);
+    color: var(--color-gray-50);
 }
 
 .contact-methods {
     display: flex;
     flex-direction: column;
-    gap: 1.5rem;
+    gap: var(--space-6);
 }
 
 .contact-method {
     display: flex;
     align-items: flex-start;
-    gap: 1rem;
-    padding: 1.5rem;
-    background: #1f2937;
-    border-radius: 0.75rem;
-    border: 1px solid #374151;
+    gap: var(--space-4);
+    padding: var(--space-6);
+    background: var(--color-gray-800);
+    border-radius: var(--radius-lg);
+    border: 1px solid var(--color-gray-700);
 }
 
 .contact-icon {
     font-size: 1.5rem;
-    padding: 0.75rem;
-    background: #3b82f6;
-    border-radius: 0.5rem;
+    padding: var(--space-3);
+    background: var(--color-blue-500);
+    border-radius: var(--radius-md);
     display: flex;
     align-items: center;
     justify-content: center;
@@ -743,52 +2704,52 @@ body {
 .contact-method h3 {
     font-size: 1.125rem;
     font-weight: 600;
-    margin: 0 0 0.25rem 0;
-    color: #f9fafb;
+    margin: 0 0 var(--space-1) 0;
+    color: var(--color-gray-50);
 }
 
 .contact-method p {
     margin: 0;
-    color: #9ca3af;
+    color: var(--color-gray-400);
     font-size: 0.875rem;
 }
 
 .contact-link {
-    color: #60a5fa !important;
+    color: var(--color-blue-400) !important;
     text-decoration: none;
 }
 
 .contact-link:hover {
-    color: #93c5fd !important;
+    color: var(--color-blue-300) !important;
     text-decoration: underline;
 }
 
 .contact-form-section h2 {
     font-size: 1.5rem;
     font-weight: 600;
-    margin-bottom: 1.5rem;
-    color: #f9fafb;
+    margin-bottom: var(--space-6);
+    color: var(--color-gray-50);
 }
 
 .tech-details {
-    padding: 2rem;
-    background: #1f2937;
-    border-radius: 1rem;
-    border: 1px solid #374151;
+    padding: var(--space-8);
+    background: var(--color-gray-800);
+    border-radius: var(--radius-xl);
+    border: 1px solid var(--color-gray-700);
 }
 
 .tech-details h2 {
     font-size: 1.5rem;
     font-weight: 600;
-    margin-bottom: 1.5rem;
-    color: #f9fafb;
+    margin-bottom: var(--space-6);
+    color: var(--color-gray-50);
     text-align: center;
 }
 
 .tech-grid {
     display: grid;
     grid-template-columns: repeat(2, 1fr);
-    gap: 1.5rem;
+    gap: var(--space-6);
 }
 
 @media (max-width: 768px) {
@@ -798,22 +2759,22 @@ body {
 }
 
 .tech-item {
-    padding: 1.5rem;
-    background: #111827;
-    border-radius: 0.75rem;
-    border: 1px solid #374151;
+    padding: var(--space-6);
+    background: var(--color-gray-900);
+    border-radius: var(--radius-lg);
+    border: 1px solid var(--color-gray-700);
 }
 
 .tech-item h3 {
     font-size: 1.125rem;
     font-weight: 600;
-    margin: 0 0 0.75rem 0;
-    color: #f9fafb;
+    margin: 0 0 var(--space-3) 0;
+    color: var(--color-gray-50);
 }
 
 .tech-item p {
     margin: 0;
-    color: #9ca3af;
+    color: var(--color-gray-400);
     font-size: 0.875rem;
     line-height: 1.5;
 }
@@ -827,72 +2788,35 @@ body {
     <meta name="viewport" content="width=device-width, initial-scale=1.0">
     <title>{}</title>
     <meta name="description" content="{}">
-    {}
     <style>
         {}
         {}
+        {}
     </style>
-    {}
 </head>
 <body>
     {}
+    {}
 </body>
 </html>"#,
-        title, description, wasm_preload, base_css, extra_css, js_import, content
+        html::escape(title),
+        html::escape(description),
+        theme_css(),
+        base_css,
+        extra_css,
+        content,
+        offline_support()
     )
 }
 
-pub fn generate_hybrid_contact_page(
-    output_dir: &Path,
-    wasm_assets_dir: &Path,
-) -> Result<(), Box<dyn std::error::Error>> {
-    println!("🔨 Generating: /contact (hybrid with WASM)");
-
-    // Since we just built WASM, there should be exactly one JS file in the fresh build
-    let mut js_file = None;
-    let mut wasm_file = None;
-
-    println!(
-        "🔍 Looking for WASM assets in: {}",
-        wasm_assets_dir.display()
-    );
-
-    for entry in std::fs::read_dir(wasm_assets_dir)? {
-        let entry = entry?;
-        let file_name = entry.file_name().to_string_lossy().to_string();
-
-        if file_name.starts_with("dioxus_site-") && file_name.ends_with(".js") {
-            println!("📄 Found JS file: {}", file_name);
-            // Verify this file has our exports
-            let file_path = entry.path();
-            if let Ok(content) = std::fs::read_to_string(&file_path) {
-                if content.contains("mount_contact_component") {
-                    js_file = Some(format!("/assets/{}", file_name));
-                    println!("✅ JS file contains mount_contact_component export");
-                } else {
-                    println!("⚠️  JS file does not contain mount_contact_component export");
-                }
-            }
-        } else if file_name.starts_with("dioxus_site_bg-") && file_name.ends_with(".wasm") {
-            wasm_file = Some(format!("/assets/{}", file_name));
-            println!("🦀 Found WASM file: {}", file_name);
-        }
-    }
-
-    let js_path = js_file.ok_or("JS file with mount_contact_component export not found")?;
-    let wasm_path = wasm_file.ok_or("WASM file not found")?;
-
-    println!("🎯 Using JS file: {}", js_path);
-    println!("🎯 Using WASM file: {}", wasm_path);
+/// Write the contact page used by the hybrid build. The plain POST form sits in a placeholder that the contact island
+/// mounts over; `hybrid.toml` lists the island and the hybrid generator adds the loader.
+pub fn generate_contact_island_page(build: &mut Build) -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔨 Generating: /contact (island placeholder)");
 
     let content = format!(
-        r#"<div id="navbar">
-        <a href="/">Home</a>
-        <a href="/about">About</a>
-        <a href="/contact">Contact</a>
-        <a href="/blog/1">Blog</a>
-    </div>
-    <div class="contact-container">
+        r#"{navbar}
+    <div class="{scope} contact-container">
         <header class="contact-header">
             <h1 class="contact-title">Contact Me</h1>
             <p class="contact-subtitle">Get in touch! This form is powered by WebAssembly for interactive functionality.</p>
@@ -906,7 +2830,7 @@ pub fn generate_hybrid_contact_page(
                         <span class="contact-icon">📧</span>
                         <div>
                             <h3>Email</h3>
-                            <a href="mailto:marcin.sydor@sky.uk" class="contact-link">marcin.sydor@sky.uk</a>
+                            <a href="mailto:{email}" class="contact-link">{email}</a>
                         </div>
                     </div>
                     <div class="contact-method">
@@ -923,25 +2847,29 @@ pub fn generate_hybrid_contact_page(
                             <a href="https://github.com/marcinsydor" target="_blank" class="contact-link">@marcinsydor</a>
                         </div>
                     </div>
+                    <div class="contact-method">
+                        <span class="contact-icon">🎤</span>
+                        <div>
+                            <h3>Speaking</h3>
+                            <a href="{speak}" class="contact-link">Invite me to your event</a>
+                        </div>
+                    </div>
                 </div>
             </div>
 
             <div class="contact-form-section">
                 <h2>Send a Message</h2>
 
-                <div class="wasm-loading-notice" style="padding: 1rem; margin-bottom: 1rem; background: #f0f9ff; border: 1px solid #0ea5e9; border-radius: 0.5rem; color: #0369a1;">
-                    <p style="margin: 0;">🚀 <strong>Interactive WASM Form:</strong> Loading Dioxus Contact component...</p>
-                    <div style="width: 100%; height: 4px; background: #e0f2fe; border-radius: 2px; margin-top: 0.5rem; overflow: hidden;">
-                        <div style="height: 100%; background: #0ea5e9; animation: loading 2s infinite;"></div>
+                <div class="wasm-loading-notice" data-island-loading>
+                    <p>🚀 <strong>Interactive WASM Form:</strong> Loading Dioxus Contact component...</p>
+                    <div class="wasm-loading-bar">
+                        <div data-island-progress role="progressbar" aria-label="Loading the interactive form" aria-valuemin="0" aria-valuemax="100"></div>
                     </div>
                 </div>
 
-                <!-- This will be replaced by the WASM contact app -->
-                <div id="contact-form-placeholder" style="min-height: 400px; display: flex; align-items: center; justify-content: center; background: #f9fafb; border-radius: 0.5rem; border: 2px dashed #d1d5db;">
-                    <div style="text-align: center; color: #6b7280;">
-                        <div style="font-size: 2rem; margin-bottom: 0.5rem;">⏳</div>
-                        <p style="margin: 0;">Initializing interactive Dioxus form...</p>
-                    </div>
+                <!-- The contact island mounts over this form; until then, or without JavaScript, it opens the mail app -->
+                <div id="contact-form-placeholder">
+                    {contact_form}
                 </div>
             </div>
         </div>
@@ -967,86 +2895,51 @@ pub fn generate_hybrid_contact_page(
                 </div>
             </div>
         </div>
-    </div>
-
-<script type="module">
-    import {{ mount_contact_component, wasm_main }} from '{js_path}';
-
-    async function loadWasmContactForm() {{
-        try {{
-            console.log('🚀 Loading WASM Contact Form...');
-
-            // Wait for the auto-initialization to complete
-            // The module auto-initializes on import, so we need to wait for it
-            let retries = 0;
-            const maxRetries = 500; // 5 seconds max (500 * 10ms)
-
-            while (!globalThis.__dx_mainWasm && retries < maxRetries) {{
-                await new Promise(resolve => setTimeout(resolve, 10));
-                retries++;
-            }}
-
-            if (!globalThis.__dx_mainWasm) {{
-                throw new Error('WASM module failed to initialize within timeout');
-            }}
-
-            console.log('✅ WASM module initialized successfully');
-
-            // Initialize the Dioxus runtime
-            wasm_main();
-
-            // Mount the Dioxus Contact component
-            mount_contact_component();
-            console.log('✅ Dioxus Contact component mounted');
+    </div>"#,
+        scope = styles::CONTACT.scope,
+        speak = link_to(Route::Speak {}),
+        navbar = navbar_html(),
+        email = data::ABOUT.contact.email,
+        contact_form = contact_form_html()
+    );
 
-            // Hide the loading notice
-            const loadingNotice = document.querySelector('.wasm-loading-notice');
-            if (loadingNotice) {{
-                loadingNotice.style.display = 'none';
-            }}
+    // Add CSS for loading animation
+    let additional_css = r#"
+    .wasm-loading-notice {
+        padding: var(--space-4);
+        margin-bottom: var(--space-4);
+        background: var(--color-sky-50);
+        border: 1px solid var(--color-sky-500);
+        border-radius: var(--radius-md);
+        color: var(--color-sky-700);
+    }
 
-        }} catch (error) {{
-            console.error('❌ Failed to load WASM Contact Form:', error);
-
-            // Show fallback message
-            const placeholder = document.getElementById('contact-form-placeholder');
-            if (placeholder) {{
-                placeholder.innerHTML = `
-                    <div style="padding: 2rem; text-align: center; background: #fef2f2; border: 1px solid #fecaca; border-radius: 0.5rem; color: #dc2626;">
-                        <h3>⚠️ Contact Form Loading Error</h3>
-                        <p>The interactive contact form failed to load. Please try refreshing the page or contact me directly at <a href="mailto:marcin.sydor@sky.uk">marcin.sydor@sky.uk</a></p>
-                        <p><small>Error: ${{error.message || 'WASM module failed to initialize'}}</small></p>
-                    </div>
-                `;
-            }}
+    .wasm-loading-notice p {
+        margin: 0;
+    }
 
-            // Hide the loading notice on error too
-            const loadingNotice = document.querySelector('.wasm-loading-notice');
-            if (loadingNotice) {{
-                loadingNotice.style.display = 'none';
-            }}
-        }}
-    }}
+    .wasm-loading-bar {
+        width: 100%;
+        height: 4px;
+        background: var(--color-sky-100);
+        border-radius: var(--radius-xs);
+        margin-top: var(--space-2);
+        overflow: hidden;
+    }
 
-    // Load WASM when DOM is ready
-    if (document.readyState === 'loading') {{
-        document.addEventListener('DOMContentLoaded', loadWasmContactForm);
-    }} else {{
-        loadWasmContactForm();
-    }}
-</script>
+    .wasm-loading-bar div {
+        height: 100%;
+        background: var(--color-sky-500);
+        animation: loading 2s infinite;
+    }
 
-<noscript>
-    <div style="position: fixed; bottom: 1rem; right: 1rem; padding: 1rem; background: #fee; border: 1px solid #fcc; border-radius: 0.5rem; font-size: 0.875rem; max-width: 300px; z-index: 1000;">
-        <p style="margin: 0; font-weight: bold; color: #c33;">⚠️ JavaScript Required</p>
-        <p style="margin: 0.5rem 0 0 0; color: #c33;">This page requires JavaScript for interactive functionality.</p>
-    </div>
-</noscript>"#,
-        js_path = js_path
-    );
+    /* Set by the island loader once the download size is known */
+    .wasm-loading-bar div[data-determinate] {
+        width: 0;
+        animation: none;
+        transition: width 0.2s ease-out;
+    }
 
-    // Add CSS for loading animation
-    let additional_css = r#"
     @keyframes loading {
         0% { transform: translateX(-100%); }
         100% { transform: translateX(100%); }
@@ -1066,16 +2959,10 @@ pub fn generate_hybrid_contact_page(
         "Contact - Dioxus Site",
         "Get in touch with me through this interactive contact form",
         &content,
-        None, // Don't add script tag here - we import it manually in the inline script
-        Some(&wasm_path), // Preload the WASM file for faster loading
         Some(additional_css),
     );
 
-    let contact_dir = output_dir.join("contact");
-    std::fs::create_dir_all(&contact_dir)?;
-    let index_path = contact_dir.join("index.html");
-    std::fs::write(&index_path, html_doc)?;
-
-    println!("✅ Generated: contact/index.html (hybrid with WASM)");
+    let page = build.page("/contact/", &html_doc)?;
+    println!("✅ Generated: {}", describe_files(&page.files));
     Ok(())
 }
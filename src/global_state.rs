@@ -0,0 +1,148 @@
+//! State shared between the islands on a page.
+//!
+//! Every island on a static page is its own Dioxus app, so a signal in one can't be read by another. Components get
+//! the shared state through [`use_global_state`] instead: the first call in an app provides a [`GlobalState`] context
+//! at its root, and every change made with [`GlobalState::publish`] is also dispatched as a [`STATE_EVENT`] custom
+//! event on `document`. The stores of the other islands listen for it and update their signals, so a save button and
+//! the saved posts list on the same page stay in step. In the SPA all components already share one store, and it
+//! ignores its own events.
+//!
+//! The event's `detail` is `{ source, change: { key, value } }`, with `change` serialized from [`StateChange`], so
+//! plain scripts on the page can follow along too.
+
+use dioxus::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Name of the custom event changes are broadcast with.
+pub const STATE_EVENT: &str = "site-state";
+
+/// A change to one piece of shared state.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "key", content = "value", rename_all = "snake_case")]
+pub enum StateChange {
+    /// The reader's saved posts, as stored by [`saved_posts`](crate::saved_posts)
+    SavedPosts(Vec<String>),
+}
+
+#[cfg(feature = "web")]
+#[derive(Serialize, Deserialize)]
+struct Broadcast {
+    /// Id of the store that made the change
+    source: u32,
+    change: StateChange,
+}
+
+/// One app's copy of the shared state. Read the signals in components; change them with [`publish`](Self::publish) so
+/// the other islands hear about it.
+#[derive(Clone, Copy)]
+pub struct GlobalState {
+    saved_posts: Signal<Vec<String>>,
+    id: u32,
+}
+
+/// Islands share one WASM instance, so a counter tells their stores apart.
+static NEXT_ID: AtomicU32 = AtomicU32::new(0);
+
+impl GlobalState {
+    fn new() -> Self {
+        Self {
+            saved_posts: Signal::new_in_scope(Vec::new(), ScopeId::ROOT),
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn saved_posts(&self) -> ReadSignal<Vec<String>> {
+        self.saved_posts.into()
+    }
+
+    /// Apply `change` here and broadcast it to the other islands.
+    pub fn publish(&mut self, change: StateChange) {
+        broadcast(self.id, &change);
+        self.apply(change);
+    }
+
+    fn apply(&mut self, change: StateChange) {
+        match change {
+            StateChange::SavedPosts(posts) => self.saved_posts.set(posts),
+        }
+    }
+}
+
+/// The shared state of the current app, provided at its root on first use. It starts out empty and is filled from
+/// browser storage once mounted, so hydration sees the same markup the page was generated with.
+pub fn use_global_state() -> GlobalState {
+    let (mut state, provided) = use_hook(|| match try_consume_context::<GlobalState>() {
+        Some(state) => (state, false),
+        None => {
+            let state = dioxus::core::provide_root_context(GlobalState::new());
+            listen(state);
+            (state, true)
+        }
+    });
+    use_effect(move || {
+        if provided {
+            state.apply(StateChange::SavedPosts(crate::saved_posts::load()));
+        }
+    });
+    state
+}
+
+#[cfg(feature = "web")]
+fn broadcast(source: u32, change: &StateChange) {
+    let broadcast = Broadcast {
+        source,
+        change: change.clone(),
+    };
+    let (Some(document), Ok(json)) = (
+        web_sys::window().and_then(|window| window.document()),
+        serde_json::to_string(&broadcast),
+    ) else {
+        return;
+    };
+
+    let init = web_sys::CustomEventInit::new();
+    init.set_detail(&js_sys::JSON::parse(&json).unwrap_or_default());
+    if let Ok(event) = web_sys::CustomEvent::new_with_event_init_dict(STATE_EVENT, &init) {
+        let _ = document.dispatch_event(&event);
+    }
+}
+
+#[cfg(not(feature = "web"))]
+fn broadcast(_source: u32, _change: &StateChange) {}
+
+/// Follow the changes other stores broadcast. The listener lives as long as the page, like the islands.
+#[cfg(feature = "web")]
+fn listen(mut state: GlobalState) {
+    use dioxus::core::Runtime;
+    use wasm_bindgen::{closure::Closure, JsCast};
+
+    let Some(document) = web_sys::window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    // The event arrives outside the Dioxus runtime, so the update is run in this app's root scope
+    let runtime = Runtime::current();
+    let listener =
+        Closure::<dyn FnMut(web_sys::CustomEvent)>::new(move |event: web_sys::CustomEvent| {
+            let broadcast = js_sys::JSON::stringify(&event.detail())
+                .ok()
+                .and_then(|json| json.as_string())
+                .and_then(|json| serde_json::from_str::<Broadcast>(&json).ok());
+
+            match broadcast {
+                Some(broadcast) if broadcast.source != state.id => {
+                    runtime.in_scope(ScopeId::ROOT, || state.apply(broadcast.change));
+                }
+                Some(_) => {}
+                None => crate::logger::error("❌ Ignoring malformed shared state event"),
+            }
+        });
+
+    let _ =
+        document.add_event_listener_with_callback(STATE_EVENT, listener.as_ref().unchecked_ref());
+    listener.forget();
+}
+
+#[cfg(not(feature = "web"))]
+fn listen(_state: GlobalState) {}
@@ -0,0 +1,112 @@
+//! First-visit hints: short notes about the site ("your saved posts stay in this browser") from the `[[hints]]` list in
+//! `site.toml`, shown by the [`Hints`](crate::components::Hints) banner until the reader dismisses them.
+//!
+//! The hints form a queue in the order they're listed: a page shows the first one that hasn't been dismissed, so they
+//! never stack, and the next one waits for a later page view. Dismissed ids are kept in localStorage as a JSON array,
+//! so nothing about the reader leaves the browser.
+
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "dismissed_hints";
+
+/// One entry of `[[hints]]`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hint {
+    /// Remembered once dismissed; a new id shows the hint again to readers who dismissed the old one
+    pub id: String,
+    pub text: String,
+    /// Where to find out more, e.g. `/saved`
+    #[serde(default)]
+    pub link: Option<String>,
+    /// Text of the link
+    #[serde(default)]
+    pub link_label: Option<String>,
+}
+
+/// The `[[hints]]` of the compiled-in `site.toml`, or none if it can't be read.
+pub fn compiled() -> &'static [Hint] {
+    #[derive(Default, Deserialize)]
+    #[serde(default)]
+    struct SiteToml {
+        hints: Vec<Hint>,
+    }
+
+    static HINTS: OnceLock<Vec<Hint>> = OnceLock::new();
+    HINTS.get_or_init(|| {
+        toml::from_str::<SiteToml>(SITE_TOML)
+            .map(|site| site.hints)
+            .unwrap_or_default()
+    })
+}
+
+/// Problems with `hints`, for `SiteConfig::load`.
+pub fn validate(hints: &[Hint]) -> Vec<String> {
+    let mut problems = Vec::new();
+    for (index, hint) in hints.iter().enumerate() {
+        if hint.id.trim().is_empty() || hint.text.trim().is_empty() {
+            problems.push(format!(
+                "[[hints]] entry {} needs an id and a text",
+                index + 1
+            ));
+        }
+        if hints[..index].iter().any(|earlier| earlier.id == hint.id) {
+            problems.push(format!("[[hints]] id {:?} is used twice", hint.id));
+        }
+        if hint.link.is_some() != hint.link_label.is_some() {
+            problems.push(format!(
+                "[[hints]] {:?} needs both link and link_label, or neither",
+                hint.id
+            ));
+        }
+        if let Some(link) = &hint.link {
+            if !crate::html_sanitizer::is_safe_url(link) {
+                problems.push(format!("[[hints]] {:?} links to an unsafe URL", hint.id));
+            }
+        }
+    }
+    problems
+}
+
+/// The hint to show next: the first one in `hints` that isn't in `dismissed`.
+pub fn next<'a>(hints: &'a [Hint], dismissed: &[String]) -> Option<&'a Hint> {
+    hints.iter().find(|hint| !dismissed.contains(&hint.id))
+}
+
+/// Ids of the hints this browser has dismissed. Empty outside the browser or when storage is unavailable.
+pub fn dismissed() -> Vec<String> {
+    #[cfg(feature = "web")]
+    {
+        let stored = web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+
+        if let Some(json) = stored {
+            return serde_json::from_str(&json).unwrap_or_default();
+        }
+    }
+
+    Vec::new()
+}
+
+/// Remember that the hint `id` was dismissed.
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+pub fn dismiss(id: &str) {
+    #[cfg(feature = "web")]
+    {
+        let mut ids = dismissed();
+        if ids.iter().any(|dismissed| dismissed == id) {
+            return;
+        }
+        ids.push(id.to_string());
+
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(&ids)) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
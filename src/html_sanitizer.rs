@@ -0,0 +1,483 @@
+//! An allowlist HTML sanitizer for markup that comes from content rather than code: the HTML in post bodies and
+//! the raw HTML and links in markdown (see `ssg::markdown`).
+//!
+//! Only the elements and attributes listed here are kept, and everything else goes:
+//!
+//! - elements that run or embed something (`<script>`, `<style>`, `<iframe>`, `<object>`, `<svg>`, ...) are removed
+//!   with their contents
+//! - any other element that isn't allowed is removed, keeping its text
+//! - event handlers, `style` and attributes that aren't allowed are dropped
+//! - URLs in `href`, `src`, `srcset` and `cite` must be relative or use `http`, `https`, `mailto` or `tel`; an
+//!   attribute with any other scheme (`javascript:`, `data:`) is dropped
+//! - comments, doctypes and processing instructions are removed
+//!
+//! What's kept is written back out with its attributes quoted and escaped, end tags that close nothing are dropped,
+//! and elements left open are closed, so the result can't escape the element it's inserted into. Text and character
+//! references are left as they are.
+
+use crate::entities;
+
+/// Elements kept, with their allowed attributes besides the [`GLOBAL_ATTRIBUTES`].
+const ELEMENTS: &[(&str, &[&str])] = &[
+    ("a", &["href", "rel", "hreflang"]),
+    ("abbr", &[]),
+    ("aside", &[]),
+    ("b", &[]),
+    ("blockquote", &["cite"]),
+    ("br", &[]),
+    ("caption", &[]),
+    ("code", &[]),
+    ("dd", &[]),
+    ("del", &["cite", "datetime"]),
+    ("details", &["open"]),
+    ("div", &[]),
+    ("dl", &[]),
+    ("dt", &[]),
+    ("em", &[]),
+    ("figcaption", &[]),
+    ("figure", &[]),
+    ("h1", &[]),
+    ("h2", &[]),
+    ("h3", &[]),
+    ("h4", &[]),
+    ("h5", &[]),
+    ("h6", &[]),
+    ("hr", &[]),
+    ("i", &[]),
+    (
+        "img",
+        &[
+            "src", "alt", "width", "height", "loading", "decoding", "srcset", "sizes",
+        ],
+    ),
+    ("ins", &["cite", "datetime"]),
+    ("kbd", &[]),
+    ("li", &["value"]),
+    ("mark", &[]),
+    ("ol", &["start", "reversed", "type"]),
+    ("p", &[]),
+    ("picture", &[]),
+    ("pre", &[]),
+    ("q", &["cite"]),
+    ("s", &[]),
+    ("section", &[]),
+    ("small", &[]),
+    (
+        "source",
+        &["srcset", "sizes", "type", "media", "width", "height"],
+    ),
+    ("span", &[]),
+    ("strong", &[]),
+    ("sub", &[]),
+    ("summary", &[]),
+    ("sup", &[]),
+    ("table", &[]),
+    ("tbody", &[]),
+    ("td", &["colspan", "rowspan", "headers"]),
+    ("tfoot", &[]),
+    ("th", &["colspan", "rowspan", "headers", "scope"]),
+    ("thead", &[]),
+    ("time", &["datetime"]),
+    ("tr", &[]),
+    ("u", &[]),
+    ("ul", &[]),
+    ("var", &[]),
+];
+
+/// Attributes kept on every allowed element, besides any `aria-*` and `data-*` ones.
+const GLOBAL_ATTRIBUTES: &[&str] = &["id", "class", "title", "lang", "dir", "role"];
+
+/// Elements without an end tag.
+const VOID_ELEMENTS: &[&str] = &["br", "hr", "img", "source", "wbr"];
+
+/// Elements removed together with everything inside them.
+const DROPPED_WITH_CONTENTS: &[&str] = &[
+    "applet", "embed", "frame", "frameset", "iframe", "math", "noembed", "noframes", "noscript",
+    "object", "script", "select", "style", "svg", "template", "textarea", "title", "xmp",
+];
+
+/// Attributes holding a URL, or (`srcset`) a list of them.
+const URL_ATTRIBUTES: &[&str] = &["href", "src", "cite", "srcset"];
+
+/// Schemes a URL may have.
+const URL_SCHEMES: &[&str] = &["http", "https", "mailto", "tel"];
+
+/// `html` with everything that isn't allowed removed.
+pub fn sanitize(html: &str) -> String {
+    let mut out = String::with_capacity(html.len());
+    let mut open: Vec<&'static str> = Vec::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(tag) = Tag::parse(rest) else {
+            // A `<` that starts no tag is text
+            out.push_str("&lt;");
+            rest = &rest[1..];
+            continue;
+        };
+        rest = &rest[tag.len..];
+
+        match tag.kind {
+            TagKind::Other => {}
+            TagKind::Start { self_closing } => {
+                if DROPPED_WITH_CONTENTS.contains(&tag.name.as_str()) {
+                    if !self_closing {
+                        rest = skip_contents(rest, &tag.name);
+                    }
+                    continue;
+                }
+                let Some((name, allowed)) = element(&tag.name) else {
+                    continue;
+                };
+                out.push('<');
+                out.push_str(name);
+                for (attribute, value) in &tag.attributes {
+                    if let Some(value) = allowed_value(attribute, value, allowed) {
+                        out.push_str(&format!(" {}=\"{}\"", attribute, escape_attribute(&value)));
+                    }
+                }
+                out.push('>');
+                if !VOID_ELEMENTS.contains(&name) && !self_closing {
+                    open.push(name);
+                }
+            }
+            TagKind::End => {
+                if let Some(index) = open.iter().rposition(|name| *name == tag.name) {
+                    for name in open.drain(index..).rev() {
+                        out.push_str(&format!("</{}>", name));
+                    }
+                }
+            }
+        }
+    }
+    out.push_str(rest);
+
+    for name in open.into_iter().rev() {
+        out.push_str(&format!("</{}>", name));
+    }
+    out
+}
+
+/// Whether `url` may be linked to: relative, or with one of the [`URL_SCHEMES`].
+pub fn is_safe_url(url: &str) -> bool {
+    // Browsers ignore whitespace and control characters in a scheme (`java\tscript:`), so they're ignored here too
+    let url: String = entities::decode(url)
+        .chars()
+        .filter(|c| !c.is_whitespace() && !c.is_control())
+        .collect();
+    let scheme_end = url.find(|c: char| !(c.is_ascii_alphanumeric() || "+-.".contains(c)));
+    match scheme_end {
+        Some(end) if url[end..].starts_with(':') => URL_SCHEMES
+            .iter()
+            .any(|scheme| url[..end].eq_ignore_ascii_case(scheme)),
+        _ => true,
+    }
+}
+
+/// The allowed element called `name`, with its attributes.
+fn element(name: &str) -> Option<(&'static str, &'static [&'static str])> {
+    ELEMENTS
+        .iter()
+        .find(|(element, _)| *element == name)
+        .map(|&(element, attributes)| (element, attributes))
+}
+
+/// The value to write for `attribute`, when it's kept.
+fn allowed_value(attribute: &str, value: &str, allowed: &[&str]) -> Option<String> {
+    let known = GLOBAL_ATTRIBUTES.contains(&attribute)
+        || allowed.contains(&attribute)
+        || attribute.strip_prefix("aria-").is_some_and(is_name)
+        || attribute.strip_prefix("data-").is_some_and(is_name);
+    if !known {
+        return None;
+    }
+
+    let value = entities::decode(value);
+    let safe = match attribute {
+        "srcset" => value
+            .split(',')
+            .all(|candidate| is_safe_url(candidate.split_whitespace().next().unwrap_or(""))),
+        _ if URL_ATTRIBUTES.contains(&attribute) => is_safe_url(&value),
+        _ => true,
+    };
+    safe.then_some(value)
+}
+
+fn is_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// What follows the end of the element called `name` whose start tag ended where `html` starts.
+fn skip_contents<'a>(html: &'a str, name: &str) -> &'a str {
+    let lower = html.to_ascii_lowercase();
+    let close = format!("</{}", name);
+    let mut from = 0;
+    while let Some(found) = lower[from..].find(&close) {
+        let end = from + found + close.len();
+        if lower[end..].starts_with(|c: char| c == '>' || c.is_ascii_whitespace() || c == '/') {
+            return lower[end..]
+                .find('>')
+                .map_or("", |close_end| &html[end + close_end + 1..]);
+        }
+        from = end;
+    }
+    ""
+}
+
+#[derive(Debug)]
+enum TagKind {
+    Start {
+        self_closing: bool,
+    },
+    End,
+    /// A comment, doctype or processing instruction
+    Other,
+}
+
+/// A tag at the start of some HTML.
+#[derive(Debug)]
+struct Tag {
+    kind: TagKind,
+    /// Lowercased
+    name: String,
+    /// Lowercased names, values as written
+    attributes: Vec<(String, String)>,
+    /// Length of the tag's text, `<` to `>`
+    len: usize,
+}
+
+impl Tag {
+    /// The tag `html` starts with, or `None` when its `<` starts no tag or the tag has no `>`.
+    fn parse(html: &str) -> Option<Self> {
+        let after = &html[1..];
+        if let Some(comment) = after.strip_prefix("!--") {
+            // As in browsers: `<!-->` and `<!--->` are whole comments, and `--!>` closes one like `-->`, so what
+            // follows is parsed as the browser will show it
+            let len = if comment.starts_with('>') {
+                5
+            } else if comment.starts_with("->") {
+                6
+            } else {
+                ["-->", "--!>"]
+                    .iter()
+                    .filter_map(|close| comment.find(close).map(|end| 4 + end + close.len()))
+                    .min()
+                    .unwrap_or(html.len())
+            };
+            return Some(Self::other(len));
+        }
+        if after.starts_with(['!', '?']) {
+            let len = after.find('>').map_or(html.len(), |end| end + 2);
+            return Some(Self::other(len));
+        }
+
+        let (end_tag, after) = match after.strip_prefix('/') {
+            Some(after) => (true, after),
+            None => (false, after),
+        };
+        if !after.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            return None;
+        }
+        let name_len = after
+            .find(|c: char| c.is_ascii_whitespace() || c == '/' || c == '>')
+            .unwrap_or(after.len());
+        let name = after[..name_len].to_ascii_lowercase();
+
+        // Attributes, up to the `>` that isn't inside a quoted value
+        let mut attributes = Vec::new();
+        let mut rest = &after[name_len..];
+        let mut self_closing = false;
+        loop {
+            rest = rest.trim_start_matches(|c: char| c.is_ascii_whitespace());
+            if let Some(after_slash) = rest.strip_prefix('/') {
+                self_closing = after_slash.starts_with('>');
+                rest = after_slash;
+                continue;
+            }
+            if rest.is_empty() || rest.starts_with('>') {
+                break;
+            }
+
+            let attribute_len = rest
+                .find(|c: char| c.is_ascii_whitespace() || c == '=' || c == '>' || c == '/')
+                .unwrap_or(rest.len())
+                .max(1);
+            let attribute = rest[..attribute_len].to_ascii_lowercase();
+            rest = rest[attribute_len..].trim_start_matches(|c: char| c.is_ascii_whitespace());
+
+            let mut value = "";
+            if let Some(after_equals) = rest.strip_prefix('=') {
+                let after_equals =
+                    after_equals.trim_start_matches(|c: char| c.is_ascii_whitespace());
+                match after_equals.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let body = &after_equals[1..];
+                        let end = body.find(quote).unwrap_or(body.len());
+                        value = &body[..end];
+                        rest = body.get(end + 1..).unwrap_or("");
+                    }
+                    _ => {
+                        let end = after_equals
+                            .find(|c: char| c.is_ascii_whitespace() || c == '>')
+                            .unwrap_or(after_equals.len());
+                        value = &after_equals[..end];
+                        rest = &after_equals[end..];
+                    }
+                }
+            }
+            attributes.push((attribute, value.to_string()));
+        }
+
+        // A tag that's never closed is text
+        if !rest.starts_with('>') {
+            return None;
+        }
+        let len = html.len() - rest.len() + 1;
+        Some(Self {
+            kind: if end_tag {
+                TagKind::End
+            } else {
+                TagKind::Start { self_closing }
+            },
+            name,
+            attributes,
+            len,
+        })
+    }
+
+    fn other(len: usize) -> Self {
+        Self {
+            kind: TagKind::Other,
+            name: String::new(),
+            attributes: Vec::new(),
+            len,
+        }
+    }
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('"', "&quot;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_markup_is_kept() {
+        assert_eq!(
+            sanitize(r#"<p class="lead">Hi <a href="/about/" title='me'>there</a><br></p>"#),
+            r#"<p class="lead">Hi <a href="/about/" title="me">there</a><br></p>"#
+        );
+    }
+
+    #[test]
+    fn scripts_and_embeds_go_with_their_contents() {
+        assert_eq!(
+            sanitize("<p>a<script>alert(1)</script>b<iframe src=x></iframe>c<svg><g/></svg></p>"),
+            "<p>abc</p>"
+        );
+        assert_eq!(
+            sanitize("<p>a<SCRIPT >alert(1)</Script >b</p>"),
+            "<p>ab</p>"
+        );
+        assert_eq!(sanitize("a<style>p{}"), "a");
+    }
+
+    #[test]
+    fn unknown_elements_keep_their_text() {
+        assert_eq!(sanitize("<blink>hi</blink> <form>there</form>"), "hi there");
+    }
+
+    #[test]
+    fn handlers_styles_and_unknown_attributes_are_dropped() {
+        assert_eq!(
+            sanitize(
+                r#"<img src="a.png" onerror="alert(1)" style="x" alt="A" data-id="1" aria-hidden="true" foo>"#
+            ),
+            r#"<img src="a.png" alt="A" data-id="1" aria-hidden="true">"#
+        );
+    }
+
+    #[test]
+    fn only_safe_urls_are_kept() {
+        assert_eq!(
+            sanitize(r#"<a href="javascript:alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize(r#"<a href="java&#x09;script:alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(
+            sanitize(r#"<a href=" JavaScript:alert(1)">x</a>"#),
+            "<a>x</a>"
+        );
+        assert_eq!(sanitize(r#"<img src="data:image/png;base64,AA">"#), "<img>");
+        assert_eq!(
+            sanitize(r#"<a href="mailto:a@b.c">x</a><a href="post/">y</a>"#),
+            r#"<a href="mailto:a@b.c">x</a><a href="post/">y</a>"#
+        );
+        assert_eq!(
+            sanitize(r#"<img srcset="a.png 1x, javascript:alert(1) 2x">"#),
+            "<img>"
+        );
+        assert_eq!(
+            sanitize(r#"<img srcset="a.png 1x, b.png 2x">"#),
+            r#"<img srcset="a.png 1x, b.png 2x">"#
+        );
+    }
+
+    #[test]
+    fn values_are_quoted_and_escaped() {
+        assert_eq!(
+            sanitize(r#"<a title="&quot;><script>">x</a>"#),
+            r#"<a title="&quot;&gt;&lt;script&gt;">x</a>"#
+        );
+        assert_eq!(
+            sanitize("<a title=a\"b>x</a>"),
+            r#"<a title="a&quot;b">x</a>"#
+        );
+    }
+
+    #[test]
+    fn the_result_closes_what_it_opens() {
+        assert_eq!(sanitize("<p><em>open"), "<p><em>open</em></p>");
+        assert_eq!(sanitize("</div>text</p>"), "text");
+        assert_eq!(sanitize("<p><em>a</p>b"), "<p><em>a</em></p>b");
+    }
+
+    #[test]
+    fn a_lone_angle_bracket_is_text() {
+        assert_eq!(sanitize("1 < 2 and <3"), "1 &lt; 2 and &lt;3");
+        assert_eq!(sanitize("<p class=\"x"), "&lt;p class=\"x");
+    }
+
+    #[test]
+    fn comments_and_doctypes_are_removed() {
+        assert_eq!(sanitize("a<!-- note -->b<!DOCTYPE html><?xml?>c"), "abc");
+        assert_eq!(sanitize("a<!-- unclosed <p>b"), "a");
+    }
+
+    #[test]
+    fn comments_end_where_browsers_end_them() {
+        // Browsers read `<!-->` and `<!--->` as empty comments, so the script after them would run
+        assert_eq!(sanitize("<!--><script>alert(1)</script>-->x"), "-->x");
+        assert_eq!(
+            sanitize("<!---><img src=x onerror=alert(1)>"),
+            r#"<img src="x">"#
+        );
+        assert_eq!(sanitize("<!---->a"), "a");
+        assert_eq!(sanitize("<!-- a --!><b>b</b>"), "<b>b</b>");
+    }
+}
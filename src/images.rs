@@ -0,0 +1,58 @@
+//! Size and placeholder of each image under `assets/images/`, read at build time.
+//!
+//! `build.rs` decodes every PNG and JPEG there, turned upright by its EXIF orientation as browsers draw it, and
+//! generates [`IMAGES`] below. The size goes in the `width` and `height` of an [`Img`](crate::components::Img), so the
+//! browser reserves the box before the file arrives, and the placeholder is a copy at most 16px across, as a `data:`
+//! URI the frame shows blurred until the image has painted. Adding an image there is all it takes: nothing is typed in
+//! by hand to go stale when the file changes.
+
+use crate::responsive_images;
+
+/// An image in `assets/images/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageInfo {
+    /// Site-relative path, e.g. `/assets/images/blog-post-3-cover.png`
+    pub src: &'static str,
+    /// Size in pixels, upright
+    pub width: u32,
+    pub height: u32,
+    /// The low-quality placeholder, a `data:image/png` URI
+    pub placeholder: &'static str,
+}
+
+impl ImageInfo {
+    /// The image and its smaller copies, for `srcset`. The generator writes the copies for `assets/images/`.
+    pub fn srcset(&self) -> Option<String> {
+        responsive_images::srcset(self.src, self.width)
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/images.rs"));
+
+/// The image at the site-relative path `src`, if it is one of [`IMAGES`].
+pub fn find(src: &str) -> Option<&'static ImageInfo> {
+    IMAGES.iter().find(|image| image.src == src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images_have_their_size_and_a_placeholder() {
+        let cover =
+            find("/assets/images/blog-post-3-cover.png").expect("the cover is in assets/images/");
+        assert!(cover.width > 0 && cover.height > 0);
+        assert!(cover.placeholder.starts_with("data:image/png;base64,"));
+        assert_eq!(find("/assets/images/missing.png"), None);
+    }
+
+    #[test]
+    fn post_covers_are_images() {
+        for post in crate::posts::POSTS {
+            if let Some(cover) = post.cover {
+                assert!(find(cover.src).is_some(), "{}: {}", post.slug, cover.src);
+            }
+        }
+    }
+}
@@ -5,12 +5,92 @@
 
 use dioxus::prelude::*;
 
-pub use views::{About, Blog, Contact, ContactFormOnly, Home, Navbar};
+pub use views::{
+    About, Blog, BlogIndex, Components, Contact, ContactFormOnly, DraftPreview, Drafts, Home,
+    Navbar, Projects, Saved, Speak, SpeakForm, CONTACT_ENDPOINT, CONTACT_THANKS_PATH,
+};
 
+/// Define an api module with the request and response types shared by the WASM client and the server
+pub mod api;
+/// Define a callouts module with the notes, warnings and tips shared by post bodies and views
+pub mod callouts;
 /// Define a components module that contains all shared components for our app.
-mod components;
+pub mod components;
 /// Define a contact_app module for standalone WASM contact functionality
 mod contact_app;
+/// Define a contact_delivery module that sends the contact form with retries and a circuit breaker
+pub mod contact_delivery;
+/// Define a contact_form module with the contact form's fields from forms/contact.toml
+pub mod contact_form;
+/// Define a contact_history module that keeps past contact form submissions in localStorage
+pub mod contact_history;
+/// Define a contact_prefill module that fills in the contact form from its URL
+pub mod contact_prefill;
+/// Define a contact_reasons module with the reasons for contact that decide where messages are routed
+pub mod contact_reasons;
+/// Define a content module with helpers for markdown content files
+pub mod content;
+/// Define a data module with the page data from assets/data compiled into typed statics
+pub mod data;
+/// Define an entities module that decodes the character references in HTML
+pub mod entities;
+/// Define an excerpt module that turns post HTML into short plain-text summaries
+pub mod excerpt;
+/// Define a focus module with focus trapping and focus styling for keyboard users
+pub mod focus;
+/// Define a global_state module with the state islands on the same page share
+pub mod global_state;
+/// Define a hints module with the first-visit hints readers dismiss one at a time
+pub mod hints;
+/// Define an html_sanitizer module that strips anything but allowed markup from the HTML in content
+pub mod html_sanitizer;
+/// Define an images module with the size and placeholder of each image in assets/images, read at build time
+pub mod images;
+/// Define a license module with the license blog posts are published under
+pub mod license;
+/// Define a links module that builds page links from the Route enum
+pub mod links;
+/// Define a logger module with leveled console output for the WASM islands
+pub mod logger;
+/// Define a not_found module that suggests pages for a path that doesn't exist
+pub mod not_found;
+/// Define a page_meta module with the title and description of every route
+pub mod page_meta;
+/// Define a phone module that validates and normalizes contact phone numbers
+pub mod phone;
+/// Define a posts module with the blog posts shared by the SPA and the static generator
+pub mod posts;
+/// Define a prefetch module that warms the cache for other routes once the SPA is idle
+mod prefetch;
+/// Define a reading_progress module that decides which posts show the reading progress bar
+pub mod reading_progress;
+/// Define a responsive_images module with the smaller copies of images listed in `srcset`
+pub mod responsive_images;
+/// Define a saved_posts module that keeps the reader's saved posts in localStorage
+mod saved_posts;
+/// Define a scoped_css module that limits the page stylesheets to their views
+pub mod scoped_css;
+/// Define a shortcodes module with the markup for runnable examples and other extras in post bodies
+pub mod shortcodes;
+/// Define a site_search module that searches the posts and projects for the search page
+pub mod site_search;
+/// Define a source module that links blog posts to their source on GitHub
+pub mod source;
+/// Define an ssg module with build-time helpers for the static site generator
+#[cfg(feature = "ssr")]
+pub mod ssg;
+/// Define a styles module with the scoped page stylesheets compiled in by the build script
+pub mod styles;
+/// Define a tags module that normalizes tags and finds near-duplicates
+pub mod tags;
+/// Define a theme module with the design tokens shared by the SPA and the generated pages
+pub mod theme;
+/// Define a typography module with the heading scale and anchor ids shared by the SPA and the generated pages
+pub mod typography;
+/// Define an unsaved_guard module that warns before leaving a form with unsubmitted changes
+pub mod unsaved_guard;
+/// Define a view_transitions module that animates navigation between pages in browsers that support it
+pub mod view_transitions;
 /// Define a views module that contains the UI for all Layouts and Routes for our app.
 pub mod views;
 
@@ -35,12 +115,37 @@ pub enum Route {
         About {},
         #[route("/contact")]
         Contact {},
+        #[route("/speak")]
+        Speak {},
+        #[route("/projects")]
+        Projects {},
+        #[route("/blog")]
+        BlogIndex {},
+        // Posts used to be addressed by number. A redirect runs its function instead of rendering a component; this
+        // one only matches numeric segments and sends known ids on to the post, like the static redirect stubs.
+        // Unknown ids fall through to the post page as a missing post.
+        #[redirect("/blog/:id", |id: i32| Route::Blog { slug: legacy_blog_slug(id) })]
         // The route attribute can include dynamic parameters that implement [`std::str::FromStr`] and [`std::fmt::Display`] with the `:` syntax.
-        // In this case, id will match any integer like `/blog/123` or `/blog/-456`.
-        #[route("/blog/:id")]
+        // In this case, slug will match any path segment like `/blog/hello-world`.
+        #[route("/blog/:slug")]
         // Fields of the route variant will be passed to the component as props. In this case, the blog component must accept
-        // an `id` prop of type `i32`.
-        Blog { id: i32 },
+        // a `slug` prop of type `String`.
+        Blog { slug: String },
+        #[route("/saved")]
+        Saved {},
+        // Unpublished posts, previewed in development builds only. The static generator never writes these pages.
+        #[route("/drafts")]
+        Drafts {},
+        #[route("/drafts/:slug")]
+        DraftPreview { slug: String },
+        // Every component in each of its variants, in development builds only, like the drafts
+        #[route("/components")]
+        Components {},
+}
+
+/// Slug an old numeric `/blog/<id>` URL redirects to, or the id itself when no published post had it.
+fn legacy_blog_slug(id: i32) -> String {
+    posts::legacy_slug(id, posts::today()).map_or_else(|| id.to_string(), str::to_string)
 }
 
 // We can import assets in dioxus with the `asset!` macro. This macro takes a path to an asset relative to the crate root.
@@ -49,6 +154,7 @@ const FAVICON: Asset = asset!("/assets/favicon.ico");
 // The asset macro also minifies some assets like CSS and JS to make bundled smaller
 const MAIN_CSS: Asset = asset!("/assets/styling/main.css");
 const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
+const TRANSITIONS_CSS: Asset = asset!("/assets/styling/transitions.css");
 
 /// App is the main component of our app. Components are the building blocks of dioxus apps. Each component is a function
 /// that takes some props and returns an Element. In this case, App takes no props because it is the root of our app.
@@ -56,13 +162,32 @@ const TAILWIND_CSS: Asset = asset!("/assets/tailwind.css");
 /// Components should be annotated with `#[component]` to support props, better error messages, and autocomplete
 #[component]
 pub fn App() -> Element {
+    // Runs after the first render, so prefetching never competes with the initial page load
+    use_effect(prefetch::schedule);
+
+    // Provide the shared state at the root so every route uses the same store
+    global_state::use_global_state();
+
+    // The stylesheets reference the design tokens, so they have to be declared before anything renders
+    let theme_css = use_hook(|| {
+        theme::Theme::load()
+            .map(|theme| theme.css_variables())
+            .unwrap_or_else(|e| {
+                logger::error(&e);
+                String::new()
+            })
+    });
+
     // The `rsx!` macro lets us define HTML inside of rust. It expands to an Element with all of our HTML inside.
     rsx! {
         // In addition to element and text (which we will see later), rsx can contain other components. In this case,
-        // we are using the `document::Link` component to add a link to our favicon and main CSS file into the head of our app.
+        // we are using the `document::Link` component to add a link to our favicon into the head of our app. The site-wide
+        // stylesheets go through `Stylesheet`, first, so the components' stylesheets come after them in the head.
         document::Link { rel: "icon", href: FAVICON }
-        document::Link { rel: "stylesheet", href: MAIN_CSS }
-        document::Link { rel: "stylesheet", href: TAILWIND_CSS }
+        styles::Stylesheet { href: MAIN_CSS }
+        styles::Stylesheet { href: TAILWIND_CSS }
+        styles::Stylesheet { href: TRANSITIONS_CSS }
+        document::Style { {theme_css} }
 
         // The router component renders the route enum we defined above. It will handle synchronization of the URL and render
         // the layouts and components for the active route.
@@ -0,0 +1,112 @@
+//! The license blog posts are published under: a badge under each post with a `rel="license"` link, and the rights of
+//! each feed item.
+//!
+//! The `[license]` section of `site.toml` is compiled in, like `[source]`, so the SPA shows the same license as the
+//! generated pages. A post can set its own [`Post::license`](crate::posts::Post::license) in place of the default.
+
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+/// A license a post can be published under, spelled in `site.toml` as in the `serde` renames.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+pub enum License {
+    /// Creative Commons Attribution 4.0: anyone may share and adapt the post, crediting the author
+    #[default]
+    #[serde(rename = "cc-by-4.0")]
+    CcBy,
+    /// Creative Commons Attribution-ShareAlike 4.0: as CC BY, with adaptations under the same license
+    #[serde(rename = "cc-by-sa-4.0")]
+    CcBySa,
+    #[serde(rename = "all-rights-reserved")]
+    AllRightsReserved,
+}
+
+impl License {
+    /// The license as spelled in `site.toml`, e.g. `cc-by-4.0`.
+    pub fn id(self) -> &'static str {
+        match self {
+            License::CcBy => "cc-by-4.0",
+            License::CcBySa => "cc-by-sa-4.0",
+            License::AllRightsReserved => "all-rights-reserved",
+        }
+    }
+
+    /// Short name for the badge, e.g. `CC BY 4.0`.
+    pub fn name(self) -> &'static str {
+        match self {
+            License::CcBy => "CC BY 4.0",
+            License::CcBySa => "CC BY-SA 4.0",
+            License::AllRightsReserved => "All rights reserved",
+        }
+    }
+
+    /// The license's deed, for the `rel="license"` link. `None` for all rights reserved, which has none.
+    pub fn url(self) -> Option<&'static str> {
+        match self {
+            License::CcBy => Some("https://creativecommons.org/licenses/by/4.0/"),
+            License::CcBySa => Some("https://creativecommons.org/licenses/by-sa/4.0/"),
+            License::AllRightsReserved => None,
+        }
+    }
+
+    /// The copyright line of a post first published in `year`, e.g. `© 2025 Marcin Sydor. Licensed under CC BY 4.0.`
+    pub fn notice(self, year: i32, holder: &str) -> String {
+        let copyright = if holder.is_empty() {
+            format!("© {}", year)
+        } else {
+            format!("© {} {}", year, holder)
+        };
+        match self {
+            License::AllRightsReserved => format!("{}. All rights reserved.", copyright),
+            _ => format!("{}. Licensed under {}.", copyright, self.name()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct LicenseConfig {
+    /// License of the posts that don't set their own
+    pub default: License,
+    /// Who holds the copyright, named in the notice; empty for `[site] author`
+    pub holder: String,
+}
+
+impl LicenseConfig {
+    /// The `[license]` section of the compiled-in `site.toml`, with the holder filled in from `[site] author`, or the
+    /// defaults if it can't be read.
+    pub fn compiled() -> &'static LicenseConfig {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct SiteToml {
+            site: Site,
+            license: LicenseConfig,
+        }
+
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct Site {
+            author: String,
+        }
+
+        static LICENSE: OnceLock<LicenseConfig> = OnceLock::new();
+        LICENSE.get_or_init(|| {
+            toml::from_str::<SiteToml>(SITE_TOML)
+                .map(|site| {
+                    let mut license = site.license;
+                    license.default_holder(&site.site.author);
+                    license
+                })
+                .unwrap_or_default()
+        })
+    }
+
+    /// Name `author` as the holder unless one is set.
+    pub fn default_holder(&mut self, author: &str) {
+        if self.holder.is_empty() {
+            self.holder = author.to_string();
+        }
+    }
+}
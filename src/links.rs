@@ -0,0 +1,24 @@
+//! Links to the site's pages built from [`Route`], so no template spells out a path by hand.
+//!
+//! The SPA links with the router's `Link` or [`NavLink`](crate::components::NavLink), and the static generator with
+//! [`link_to`]. Both take the path from the route's `#[route]` attribute, so changing a route moves every link to it.
+//! The generator rewrites page links to the output style afterwards, so `/about` becomes `/about/` or `/about.html`.
+
+use crate::Route;
+
+/// Site-relative URL of `route`, e.g. `/blog/hello-world` for `Route::Blog { slug: "hello-world".into() }`.
+pub fn link_to(route: Route) -> String {
+    route.to_string()
+}
+
+/// The pages in the navbar with their labels, in order. [`Route::Saved`] isn't one of them, since only the SPA has
+/// that page; the SPA's navbar adds it at the end.
+pub fn nav_routes() -> Vec<(Route, &'static str)> {
+    vec![
+        (Route::Home {}, "Home"),
+        (Route::About {}, "About"),
+        (Route::Contact {}, "Contact"),
+        (Route::Projects {}, "Projects"),
+        (Route::BlogIndex {}, "Blog"),
+    ]
+}
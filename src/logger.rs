@@ -0,0 +1,91 @@
+//! Leveled console logger for the WASM islands
+//!
+//! Production pages only print warnings and errors. Verbose diagnostics can be turned on per page load by adding a
+//! `debug` query parameter (`?debug`, `?debug=info`) or a `data-debug` attribute on the island's root element.
+
+use std::sync::atomic::{AtomicU8, Ordering};
+use web_sys::console;
+
+/// Log levels, ordered from least to most verbose.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 0,
+    Warn = 1,
+    Info = 2,
+    Debug = 3,
+}
+
+impl Level {
+    /// Parse the value of a `debug` query parameter or `data-debug` attribute. A bare flag means full debug output.
+    fn from_flag(value: &str) -> Option<Level> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "1" | "true" | "debug" => Some(Level::Debug),
+            "info" => Some(Level::Info),
+            "warn" => Some(Level::Warn),
+            "error" => Some(Level::Error),
+            _ => None,
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Warn as u8);
+
+/// Set the maximum level that will be written to the console.
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+fn enabled(level: Level) -> bool {
+    level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+/// Pick the log level for this page load from the URL query string and, if given, the island root element's
+/// `data-debug` attribute. Falls back to warnings and errors only.
+pub fn init(island_root_id: Option<&str>) {
+    set_level(detect_level(island_root_id).unwrap_or(Level::Warn));
+}
+
+fn detect_level(island_root_id: Option<&str>) -> Option<Level> {
+    let window = web_sys::window()?;
+
+    let from_query = window.location().search().ok().and_then(|search| {
+        search
+            .trim_start_matches('?')
+            .split('&')
+            .find_map(|pair| match pair.split_once('=') {
+                Some(("debug", value)) => Level::from_flag(value),
+                None if pair == "debug" => Some(Level::Debug),
+                _ => None,
+            })
+    });
+
+    from_query.or_else(|| {
+        let document = window.document()?;
+        let root = document.get_element_by_id(island_root_id?)?;
+        Level::from_flag(&root.get_attribute("data-debug")?)
+    })
+}
+
+pub fn error(message: &str) {
+    if enabled(Level::Error) {
+        console::error_1(&message.into());
+    }
+}
+
+pub fn warn(message: &str) {
+    if enabled(Level::Warn) {
+        console::warn_1(&message.into());
+    }
+}
+
+pub fn info(message: &str) {
+    if enabled(Level::Info) {
+        console::info_1(&message.into());
+    }
+}
+
+pub fn debug(message: &str) {
+    if enabled(Level::Debug) {
+        console::log_1(&message.into());
+    }
+}
@@ -1,10 +1,17 @@
 use dioxus::prelude::*;
 use dioxus_site::{
-    views::{Contact, ContactFormOnly},
+    components::{
+        ConnectivityStatus, Hints, NarrationPlayer, NarrationPlayerProps, NavSearch,
+        NotFoundSuggestions, NotFoundSuggestionsProps, OnThisDay, Playground, PlaygroundProps,
+        Reactions, ReactionsProps, ReadingProgress, SaveButton, SaveButtonProps, SiteSearch,
+        Slideshow, StaticMobileNav,
+    },
+    focus, logger, shortcodes,
+    views::{ContactFormOnly, Projects, Saved, SpeakForm},
     App,
 };
 use wasm_bindgen::prelude::*;
-use web_sys::{console, window};
+use web_sys::window;
 
 fn main() {
     // Launch the full app (for regular SPA mode)
@@ -27,34 +34,35 @@ fn ContactApp() -> Element {
 #[wasm_bindgen]
 pub fn mount_contact_component() {
     console_error_panic_hook::set_once();
+    logger::init(Some("contact-form-placeholder"));
 
-    console::log_1(&"🚀 Starting Dioxus Contact component mount...".into());
+    logger::info("🚀 Starting Dioxus Contact component mount...");
 
     let window = window().expect("should have a window");
     let document = window.document().expect("should have a document");
 
     // Find the contact form placeholder
     if let Some(placeholder) = document.get_element_by_id("contact-form-placeholder") {
-        console::log_1(&"✅ Found contact form placeholder".into());
+        logger::debug("✅ Found contact form placeholder");
 
         // Clear the placeholder and create a mount point
         placeholder.set_inner_html(r#"<div id="dioxus-contact-root"></div>"#);
 
         // Get the mount point
         if let Some(_root_element) = document.get_element_by_id("dioxus-contact-root") {
-            console::log_1(&"✅ Created Dioxus mount point".into());
+            logger::debug("✅ Created Dioxus mount point");
 
             // Use Dioxus web-specific mounting
             let config = dioxus_web::Config::new().rootname("dioxus-contact-root");
 
             dioxus_web::launch::launch_cfg(ContactApp, config);
 
-            console::log_1(&"✅ Dioxus Contact component mounted successfully".into());
+            logger::info("✅ Dioxus Contact component mounted successfully");
         } else {
-            console::error_1(&"❌ Failed to create Dioxus mount point".into());
+            logger::error("❌ Failed to create Dioxus mount point");
         }
     } else {
-        console::error_1(&"❌ Contact form placeholder not found".into());
+        logger::error("❌ Contact form placeholder not found");
     }
 }
 
@@ -62,19 +70,311 @@ pub fn mount_contact_component() {
 #[wasm_bindgen]
 pub fn mount_contact_to_element(element_id: &str) {
     console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
 
-    console::log_1(&format!("🚀 Mounting Dioxus Contact to element: {}", element_id).into());
+    logger::info(&format!(
+        "🚀 Mounting Dioxus Contact to element: {}",
+        element_id
+    ));
 
     let config = dioxus_web::Config::new().rootname(element_id);
 
     dioxus_web::launch::launch_cfg(ContactApp, config);
 
-    console::log_1(&"✅ Contact component mounted to specified element".into());
+    logger::info("✅ Contact component mounted to specified element");
+}
+
+// Mount the save-for-later button island. The post to save is read from the element's `data-post` attribute.
+#[wasm_bindgen]
+pub fn mount_save_button(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let post = window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id))
+        .and_then(|element| element.get_attribute("data-post"));
+
+    let Some(post) = post else {
+        logger::error(&format!("❌ No data-post attribute on #{}", element_id));
+        return;
+    };
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    let vdom = VirtualDom::new_with_props(SaveButton, SaveButtonProps { post });
+    dioxus_web::launch::launch_virtual_dom(vdom, config);
+
+    logger::info("✅ Save button mounted");
+}
+
+// Mount the reaction buttons over the static reactions form on a blog page
+#[wasm_bindgen]
+pub fn mount_reactions(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let element = window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id));
+    let Some(post) = element
+        .as_ref()
+        .and_then(|element| element.get_attribute("data-post"))
+    else {
+        logger::error(&format!("❌ No data-post attribute on #{}", element_id));
+        return;
+    };
+    // The counts baked in at build time; the island fetches the current ones itself
+    let counts = element
+        .and_then(|element| element.get_attribute("data-counts"))
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default();
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    let vdom = VirtualDom::new_with_props(Reactions, ReactionsProps { post, counts });
+    dioxus_web::launch::launch_virtual_dom(vdom, config);
+
+    logger::info("✅ Reactions mounted");
+}
+
+// Mount the narration player over the static audio controls at the top of a blog post
+#[wasm_bindgen]
+pub fn mount_narration(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let element = window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id));
+    let Some(src) = element
+        .as_ref()
+        .and_then(|element| element.get_attribute("data-src"))
+    else {
+        logger::error(&format!("❌ No data-src attribute on #{}", element_id));
+        return;
+    };
+    let media_type = element
+        .and_then(|element| element.get_attribute("data-type"))
+        .filter(|media_type| !media_type.is_empty());
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    let vdom =
+        VirtualDom::new_with_props(NarrationPlayer, NarrationPlayerProps { src, media_type });
+    dioxus_web::launch::launch_virtual_dom(vdom, config);
+
+    logger::info("✅ Narration player mounted");
+}
+
+// Mount the reading progress bar on a long blog post
+#[wasm_bindgen]
+pub fn mount_reading_progress(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(ReadingProgress, config);
+
+    logger::info("✅ Reading progress mounted");
+}
+
+// Mount the slide controls below the slides of a talk page
+#[wasm_bindgen]
+pub fn mount_slideshow(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(Slideshow, config);
+
+    logger::info("✅ Slideshow mounted");
+}
+
+// Mount the search box over the plain search form on the static /search page
+#[wasm_bindgen]
+pub fn mount_site_search(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(SiteSearch, config);
+
+    logger::info("✅ Site search mounted");
+}
+
+// Mount the saved posts list island
+#[wasm_bindgen]
+pub fn mount_saved_posts(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(Saved, config);
+
+    logger::info("✅ Saved posts list mounted");
+}
+
+// Mount the projects technology filter over the statically rendered projects grid
+#[wasm_bindgen]
+pub fn mount_projects_filter(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(Projects, config);
+
+    logger::info("✅ Projects filter mounted");
+}
+
+// Mount the speaking inquiry form over the plain form on the static /speak page
+#[wasm_bindgen]
+pub fn mount_speak_form(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(SpeakForm, config);
+
+    logger::info("✅ Speaking inquiry form mounted");
+}
+
+// Mount the offline banner at the top of a generated page
+#[wasm_bindgen]
+pub fn mount_connectivity_status(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(ConnectivityStatus, config);
+
+    logger::info("✅ Connectivity status mounted");
+}
+
+// Mount the "Did you mean…" suggestions on the generated 404 page
+#[wasm_bindgen]
+pub fn mount_not_found(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let element = window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(element_id));
+    let Some(pages) = element
+        .as_ref()
+        .and_then(|element| element.get_attribute("data-pages"))
+        .and_then(|json| serde_json::from_str(&json).ok())
+    else {
+        logger::error(&format!(
+            "❌ No usable data-pages attribute on #{}",
+            element_id
+        ));
+        return;
+    };
+    let report_rate = element
+        .and_then(|element| element.get_attribute("data-report-rate"))
+        .and_then(|rate| rate.parse().ok())
+        .unwrap_or(0.0);
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    let vdom = VirtualDom::new_with_props(
+        NotFoundSuggestions,
+        NotFoundSuggestionsProps { pages, report_rate },
+    );
+    dioxus_web::launch::launch_virtual_dom(vdom, config);
+
+    logger::info("✅ Not found suggestions mounted");
+}
+
+// Mount the first-visit hint banner at the end of a generated page
+#[wasm_bindgen]
+pub fn mount_hints(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(Hints, config);
+
+    logger::info("✅ Hints mounted");
+}
+
+// Mount the "On this day" list over the one from build time on the home page and the blog index
+#[wasm_bindgen]
+pub fn mount_on_this_day(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(OnThisDay, config);
+
+    logger::info("✅ On this day mounted");
+}
+
+// Mount the instant search box over the plain search form in the navbar of a generated page
+#[wasm_bindgen]
+pub fn mount_nav_search(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(NavSearch, config);
+
+    logger::info("✅ Navbar search mounted");
+}
+
+// Mount the mobile navigation drawer inside the navbar of a generated page
+#[wasm_bindgen]
+pub fn mount_mobile_nav(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let config = dioxus_web::Config::new().rootname(element_id);
+    dioxus_web::launch::launch_cfg(StaticMobileNav, config);
+
+    logger::info("✅ Mobile navigation mounted");
+}
+
+// Mount a playground over every static playground block of a post. The loader calls this for the first block
+// (`element_id`), whose contents it has already cleared; the others are found by their numbered ids and cleared here.
+// The code comes from each block's data attribute.
+#[wasm_bindgen]
+pub fn mount_playgrounds(element_id: &str) {
+    console_error_panic_hook::set_once();
+    logger::init(Some(element_id));
+
+    let Some(document) = window().and_then(|window| window.document()) else {
+        return;
+    };
+
+    let mut mounted = 0;
+    for index in 1.. {
+        let id = shortcodes::playground_id(index);
+        let Some(element) = document.get_element_by_id(&id) else {
+            break;
+        };
+        let Some(code) = element.get_attribute(shortcodes::PLAYGROUND_CODE_ATTR) else {
+            logger::error(&format!(
+                "❌ No {} attribute on #{}",
+                shortcodes::PLAYGROUND_CODE_ATTR,
+                id
+            ));
+            continue;
+        };
+        if id != element_id {
+            element.set_inner_html("");
+        }
+
+        let config = dioxus_web::Config::new().rootname(&id);
+        let vdom = VirtualDom::new_with_props(Playground, PlaygroundProps { code });
+        dioxus_web::launch::launch_virtual_dom(vdom, config);
+        mounted += 1;
+    }
+
+    logger::info(&format!("✅ {} playground(s) mounted", mounted));
 }
 
 // Utility function for initializing WASM
 #[wasm_bindgen(start)]
 pub fn wasm_main() {
     console_error_panic_hook::set_once();
-    console::log_1(&"🦀 Dioxus WASM module initialized".into());
+    logger::init(None);
+    focus::install_focus_visible();
+    logger::debug("🦀 Dioxus WASM module initialized");
 }
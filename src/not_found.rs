@@ -0,0 +1,110 @@
+//! "Did you mean…" suggestions for the 404 page: the pages whose path or title come closest to the path that wasn't
+//! found, so a mistyped or outdated link still gets the reader where they were going.
+//!
+//! A path is compared whole and by its last segment, where a slug usually is, with the edit distance between them, and
+//! its words are looked for in the page titles. `/blog/blog-post1`, `/blog-post-1` and `/blog/post-1` all lead to
+//! `/blog/blog-post-1/`.
+
+use serde::{Deserialize, Serialize};
+
+/// Most suggestions shown.
+pub const MAX_SUGGESTIONS: usize = 3;
+
+/// Lowest score, from 0 to 1, that's still worth suggesting.
+const MIN_SCORE: f64 = 0.6;
+
+/// A page that can be suggested.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageLink {
+    /// Site-relative URL, e.g. `/blog/blog-post-1/`
+    pub path: String,
+    pub title: String,
+}
+
+/// Up to [`MAX_SUGGESTIONS`] of `pages` for the missing path `requested`, the closest first.
+pub fn suggestions<'a>(requested: &str, pages: &'a [PageLink]) -> Vec<&'a PageLink> {
+    let requested = normalize(requested);
+    if requested.is_empty() {
+        return Vec::new();
+    }
+
+    let mut scored: Vec<(f64, &PageLink)> = pages
+        .iter()
+        .filter(|page| normalize(&page.path) != requested)
+        .map(|page| (score(&requested, page), page))
+        .filter(|(score, _)| *score >= MIN_SCORE)
+        .collect();
+    scored.sort_by(|a, b| b.0.total_cmp(&a.0));
+    scored
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, page)| page)
+        .collect()
+}
+
+/// How close `page` is to the normalized path `requested`, from 0 to 1.
+fn score(requested: &str, page: &PageLink) -> f64 {
+    let path = normalize(&page.path);
+    let whole = similarity(requested, &path);
+    let slug = similarity(last_segment(requested), last_segment(&path));
+
+    // Words of the missing path that appear in the title
+    let title = page.title.to_lowercase();
+    let title_words: Vec<&str> = title
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    let words: Vec<&str> = requested
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .collect();
+    let in_title = if words.is_empty() {
+        0.0
+    } else {
+        let found = words
+            .iter()
+            .filter(|word| title_words.contains(word))
+            .count();
+        found as f64 / words.len() as f64
+    };
+
+    whole.max(slug).max(in_title * 0.9)
+}
+
+/// `path` lowercased, without slashes at either end, a trailing `.html` or `index.html`, a query or a fragment.
+fn normalize(path: &str) -> String {
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+    let path = path.to_lowercase();
+    let path = path.trim_matches('/');
+    let path = path.strip_suffix("index.html").unwrap_or(path);
+    let path = path.strip_suffix(".html").unwrap_or(path);
+    path.trim_matches('/').to_string()
+}
+
+fn last_segment(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// 1 for equal strings, down to 0 for nothing in common, from the edit distance between them.
+fn similarity(a: &str, b: &str) -> f64 {
+    let longest = a.chars().count().max(b.chars().count());
+    if longest == 0 {
+        return 0.0;
+    }
+    1.0 - edit_distance(a, b) as f64 / longest as f64
+}
+
+/// Levenshtein distance between `a` and `b`, in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,78 @@
+//! Title and description of every route.
+//!
+//! The SPA uses these to keep `document.title` current and to announce navigation to screen readers; the static
+//! generator uses the same values for the pages it writes, so both modes name pages alike.
+
+use crate::excerpt;
+use crate::posts::{self, Status};
+use crate::Route;
+
+const SITE_NAME: &str = "Dioxus Site";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PageMeta {
+    /// Short page name, read out when navigating to the page
+    pub name: String,
+    /// Full document title
+    pub title: String,
+    pub description: String,
+}
+
+impl PageMeta {
+    fn new(name: impl Into<String>, description: impl Into<String>) -> Self {
+        let name = name.into();
+        Self {
+            title: format!("{} - {}", name, SITE_NAME),
+            name,
+            description: description.into(),
+        }
+    }
+}
+
+impl Route {
+    pub fn page_meta(&self) -> PageMeta {
+        match self {
+            Route::Home {} => PageMeta::new(
+                "Home",
+                "Welcome to my Dioxus-powered website: projects, blog posts and a way to get in touch.",
+            ),
+            Route::About {} => PageMeta::new(
+                "About",
+                "Learn more about me, my work experience and the skills I bring to a project.",
+            ),
+            Route::Contact {} => {
+                PageMeta::new("Contact", "Get in touch with me through this contact form about work, talks or feedback.")
+            }
+            Route::Speak {} => PageMeta::new(
+                "Speaking",
+                "Invite me to speak at your conference or meetup about Rust, WebAssembly and Dioxus.",
+            ),
+            Route::Projects {} => {
+                PageMeta::new("Projects", "Things I have built, with the technologies behind each one and filters to narrow them down.")
+            }
+            Route::BlogIndex {} => {
+                PageMeta::new("Blog", "Posts about building this site with Dioxus, Rust and static site generation.")
+            }
+            Route::Blog { slug } => match posts::find(slug)
+                .filter(|post| post.status(posts::today()) != Status::Hidden)
+            {
+                Some(post) => {
+                    PageMeta::new(post.title, post.description(excerpt::DESCRIPTION_LENGTH))
+                }
+                None => PageMeta::new("Post not found", "There is no blog post at this address."),
+            },
+            Route::Saved {} => PageMeta::new("Saved Posts", "Posts you saved to read later"),
+            Route::Drafts {} => PageMeta::new("Drafts", "Unpublished posts, previewed in development"),
+            Route::Components {} => {
+                PageMeta::new("Components", "Every component in each of its variants, previewed in development")
+            }
+            Route::DraftPreview { slug } => match posts::find(slug) {
+                Some(post) => PageMeta::new(
+                    format!("Draft: {}", post.title),
+                    post.description(excerpt::DESCRIPTION_LENGTH),
+                ),
+                None => PageMeta::new("Draft not found", "There is no post at this address."),
+            },
+        }
+    }
+}
@@ -0,0 +1,159 @@
+//! Phone number validation and E.164 normalization for the contact form.
+//!
+//! This covers a curated list of countries rather than the full libphonenumber metadata: each entry knows its calling
+//! code, whether national numbers start with a trunk prefix that is dropped internationally, and the allowed length of
+//! the national significant number.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+
+/// Country offered in the phone field's country-code selector.
+pub struct Country {
+    /// ISO 3166-1 alpha-2 code
+    pub iso: &'static str,
+    pub name: &'static str,
+    /// Calling code without the leading `+`
+    pub calling_code: &'static str,
+    /// Prefix dialled before national numbers that isn't part of the international number
+    trunk_prefix: Option<char>,
+    /// Allowed number of digits after the calling code
+    lengths: RangeInclusive<usize>,
+}
+
+pub const DEFAULT_COUNTRY: &str = "GB";
+
+#[rustfmt::skip]
+pub const COUNTRIES: &[Country] = &[
+    Country { iso: "GB", name: "United Kingdom", calling_code: "44", trunk_prefix: Some('0'), lengths: 9..=10 },
+    Country { iso: "IE", name: "Ireland", calling_code: "353", trunk_prefix: Some('0'), lengths: 7..=9 },
+    Country { iso: "PL", name: "Poland", calling_code: "48", trunk_prefix: None, lengths: 9..=9 },
+    Country { iso: "DE", name: "Germany", calling_code: "49", trunk_prefix: Some('0'), lengths: 6..=13 },
+    Country { iso: "FR", name: "France", calling_code: "33", trunk_prefix: Some('0'), lengths: 9..=9 },
+    Country { iso: "NL", name: "Netherlands", calling_code: "31", trunk_prefix: Some('0'), lengths: 9..=9 },
+    Country { iso: "ES", name: "Spain", calling_code: "34", trunk_prefix: None, lengths: 9..=9 },
+    // Italian numbers keep their leading zero internationally
+    Country { iso: "IT", name: "Italy", calling_code: "39", trunk_prefix: None, lengths: 6..=11 },
+    Country { iso: "SE", name: "Sweden", calling_code: "46", trunk_prefix: Some('0'), lengths: 7..=9 },
+    Country { iso: "US", name: "United States", calling_code: "1", trunk_prefix: Some('1'), lengths: 10..=10 },
+    Country { iso: "CA", name: "Canada", calling_code: "1", trunk_prefix: Some('1'), lengths: 10..=10 },
+    Country { iso: "AU", name: "Australia", calling_code: "61", trunk_prefix: Some('0'), lengths: 9..=9 },
+    Country { iso: "IN", name: "India", calling_code: "91", trunk_prefix: Some('0'), lengths: 10..=10 },
+];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PhoneError {
+    InvalidCharacters,
+    UnknownCountry(String),
+    UnknownCallingCode,
+    WrongLength {
+        /// Country name, as shown in the message
+        country: String,
+        expected: RangeInclusive<usize>,
+        found: usize,
+    },
+}
+
+impl fmt::Display for PhoneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PhoneError::InvalidCharacters => {
+                write!(
+                    f,
+                    "phone numbers may only contain digits, spaces, dashes, dots and brackets"
+                )
+            }
+            PhoneError::UnknownCountry(iso) => write!(f, "unsupported country {}", iso),
+            PhoneError::UnknownCallingCode => write!(f, "unrecognised international calling code"),
+            PhoneError::WrongLength {
+                country,
+                expected,
+                found,
+            } => {
+                if expected.start() == expected.end() {
+                    write!(
+                        f,
+                        "{} numbers have {} digits, got {}",
+                        country,
+                        expected.start(),
+                        found
+                    )
+                } else {
+                    write!(
+                        f,
+                        "{} numbers have {} to {} digits, got {}",
+                        country,
+                        expected.start(),
+                        expected.end(),
+                        found
+                    )
+                }
+            }
+        }
+    }
+}
+
+pub fn country(iso: &str) -> Option<&'static Country> {
+    COUNTRIES.iter().find(|country| country.iso == iso)
+}
+
+/// Validate `input` and return it in E.164 form (`+447700900123`).
+///
+/// National numbers are interpreted for the selected country. Numbers typed in international form (`+48 ...` or
+/// `0048 ...`) are matched by their calling code and the selector is ignored.
+pub fn normalize(country_iso: &str, input: &str) -> Result<String, PhoneError> {
+    let trimmed = input.trim();
+    let (international, rest) = match trimmed.strip_prefix('+') {
+        Some(rest) => (true, rest),
+        None => match trimmed.strip_prefix("00") {
+            Some(rest) => (true, rest),
+            None => (false, trimmed),
+        },
+    };
+
+    let mut digits = String::new();
+    for c in rest.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            ' ' | '-' | '.' | '(' | ')' => {}
+            _ => return Err(PhoneError::InvalidCharacters),
+        }
+    }
+
+    let (country, national) = if international {
+        // Prefer the selected country when several share a calling code (US and Canada)
+        let selected = country(country_iso).filter(|c| digits.starts_with(c.calling_code));
+        let country = selected
+            .or_else(|| {
+                COUNTRIES
+                    .iter()
+                    .filter(|c| digits.starts_with(c.calling_code))
+                    .max_by_key(|c| c.calling_code.len())
+            })
+            .ok_or(PhoneError::UnknownCallingCode)?;
+        (country, digits[country.calling_code.len()..].to_string())
+    } else {
+        let country = country(country_iso)
+            .ok_or_else(|| PhoneError::UnknownCountry(country_iso.to_string()))?;
+        // A leading zero is always a trunk prefix; other prefixes only when the number is one digit too long
+        let national = match country.trunk_prefix {
+            Some(prefix) if prefix == '0' || digits.len() > *country.lengths.end() => digits
+                .strip_prefix(prefix)
+                .map(str::to_string)
+                .unwrap_or(digits),
+            _ => digits,
+        };
+        (country, national)
+    };
+
+    if !country.lengths.contains(&national.len()) {
+        return Err(PhoneError::WrongLength {
+            country: country.name.to_string(),
+            expected: country.lengths.clone(),
+            found: national.len(),
+        });
+    }
+
+    Ok(format!("+{}{}", country.calling_code, national))
+}
@@ -0,0 +1,387 @@
+//! Blog posts shared by the SPA router and the static site generator.
+//!
+//! Posts are addressed by slug (`/blog/blog-post-1`). They used to be addressed by number (`/blog/1`); those numbers
+//! are kept as `legacy_id` so old links can be redirected.
+//!
+//! Posts can be written ahead of time: one dated in the future stays out of the site until a build runs on or after
+//! its publish date. Marked as a `teaser`, it's listed on the blog index as "coming soon" in the meantime.
+
+use crate::api::content::PostMeta;
+use crate::excerpt;
+use crate::images::{self, ImageInfo};
+use crate::license::{License, LicenseConfig};
+use crate::shortcodes;
+use crate::source::SourceConfig;
+use chrono::{Datelike, NaiveDate};
+
+/// A blog post. The body is trusted HTML written in this repository, which may contain [`shortcodes`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Post {
+    pub slug: &'static str,
+    /// Number the post was published under before slugs, if any
+    pub legacy_id: Option<i32>,
+    pub title: &'static str,
+    /// Hand-written summary; without one an excerpt of the body is used
+    pub summary: Option<&'static str>,
+    pub body: &'static str,
+    /// File the post is written in, relative to the repository root, for its "suggest an edit" link
+    pub source: &'static str,
+    pub published: NaiveDate,
+    /// Content that doesn't go out of date, which the freshness check leaves alone
+    pub evergreen: bool,
+    /// List the post on the blog index (title and date only) before it's published
+    pub teaser: bool,
+    /// Corrections and additions made since publishing, in any order, shown in an "Updates" box at the top of the post.
+    /// The latest one is when the post was last revised (see [`last_modified`](Self::last_modified)).
+    pub updates: &'static [Update],
+    /// Narration of the post: a site-relative path to an audio file under `assets/`, e.g.
+    /// `/assets/audio/blog-post-1.mp3`. It gets a player at the top of the post and an enclosure in the feed.
+    pub audio: Option<&'static str>,
+    /// Topics of the post, spelled as they should be shown
+    pub tags: &'static [&'static str],
+    /// Address of the original when the post is syndicated from elsewhere (dev.to, Medium): the page's canonical link
+    /// points there, and the post says where it first appeared
+    pub canonical_url: Option<&'static str>,
+    /// License of the post, in place of `[license] default`
+    pub license: Option<License>,
+    /// Image shown above the post and on its card on the blog index
+    pub cover: Option<Cover>,
+}
+
+/// A post's cover image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cover {
+    /// Site-relative path to the image under `assets/`, e.g. `/assets/images/blog-post-3-cover.png`
+    pub src: &'static str,
+    /// Empty for a purely decorative image
+    pub alt: &'static str,
+}
+
+impl Cover {
+    /// The size and placeholder of the image, read from the file at build time. Covers live under `assets/images/`.
+    pub fn image(&self) -> &'static ImageInfo {
+        images::find(self.src)
+            .unwrap_or_else(|| panic!("{} is not an image in assets/images/", self.src))
+    }
+}
+
+/// A dated note about a change to a published post, e.g. a corrected example or a newer API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Update {
+    pub date: NaiveDate,
+    /// Plain text saying what changed
+    pub note: &'static str,
+}
+
+impl Update {
+    /// Date as shown to readers, e.g. `1 March 2025`.
+    pub fn date_label(&self) -> String {
+        self.date.format("%-d %B %Y").to_string()
+    }
+}
+
+/// Where a post stands on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Status {
+    Published,
+    /// Not out yet, but listed as a teaser
+    ComingSoon,
+    /// Not out yet and not mentioned anywhere
+    Hidden,
+}
+
+/// Length of automatic excerpts for posts without a summary.
+const EXCERPT_WORDS: usize = 30;
+
+/// Reading speed the reading time is estimated at.
+const WORDS_PER_MINUTE: usize = 200;
+
+impl Post {
+    /// Site-relative path of the post page, e.g. `/blog/blog-post-1/`.
+    pub fn path(&self) -> String {
+        format!("/blog/{}/", self.slug)
+    }
+
+    pub fn status(&self, today: NaiveDate) -> Status {
+        if self.published <= today {
+            Status::Published
+        } else if self.teaser {
+            Status::ComingSoon
+        } else {
+            Status::Hidden
+        }
+    }
+
+    /// The last time the content was written or revised: the latest of the publish date and the
+    /// [`updates`](Self::updates).
+    pub fn last_modified(&self) -> NaiveDate {
+        self.updates
+            .iter()
+            .map(|update| update.date)
+            .fold(self.published, NaiveDate::max)
+    }
+
+    /// The [`updates`](Self::updates), newest first.
+    pub fn updates_newest_first(&self) -> Vec<Update> {
+        let mut updates = self.updates.to_vec();
+        updates.sort_by_key(|update| std::cmp::Reverse(update.date));
+        updates
+    }
+
+    /// Publish date as shown to readers, e.g. `1 March 2025`.
+    pub fn date_label(&self) -> String {
+        self.published.format("%-d %B %Y").to_string()
+    }
+
+    /// GitHub editor link for the post's source file, if a repository is configured.
+    pub fn edit_url(&self, source: &SourceConfig) -> Option<String> {
+        source.edit_url(self.source)
+    }
+
+    /// The license the post is published under: its own, or the site's default.
+    pub fn license(&self, config: &LicenseConfig) -> License {
+        self.license.unwrap_or(config.default)
+    }
+
+    /// The post's copyright line, e.g. `© 2025 Marcin Sydor. Licensed under CC BY 4.0.`
+    pub fn license_notice(&self, config: &LicenseConfig) -> String {
+        self.license(config)
+            .notice(self.published.year(), &config.holder)
+    }
+
+    /// Site-relative URL of the post as markdown, e.g. `/blog/blog-post-1/index.md`, if `[source] markdown` publishes
+    /// it.
+    pub fn markdown_path(&self, source: &SourceConfig) -> Option<String> {
+        source.markdown.then(|| format!("{}index.md", self.path()))
+    }
+
+    /// Media type of the narration, from its extension, for players and feed enclosures. `None` without a
+    /// narration or for an extension that isn't a known audio format.
+    pub fn audio_type(&self) -> Option<&'static str> {
+        let (_, extension) = self.audio?.rsplit_once('.')?;
+        match extension.to_ascii_lowercase().as_str() {
+            "mp3" => Some("audio/mpeg"),
+            "m4a" | "mp4" => Some("audio/mp4"),
+            "aac" => Some("audio/aac"),
+            "ogg" | "oga" | "opus" => Some("audio/ogg"),
+            "wav" => Some("audio/wav"),
+            _ => None,
+        }
+    }
+
+    /// The body with its shortcodes expanded, for the generated pages, feeds and the content API.
+    pub fn body_html(&self) -> String {
+        shortcodes::render(self.body)
+    }
+
+    /// Words in the body as readers see it, with the shortcodes expanded.
+    pub fn word_count(&self) -> usize {
+        excerpt::plain_text(&self.body_html())
+            .split_whitespace()
+            .count()
+    }
+
+    /// Estimated minutes to read the post, at least 1.
+    pub fn reading_minutes(&self) -> u32 {
+        self.word_count().div_ceil(WORDS_PER_MINUTE).max(1) as u32
+    }
+
+    /// The post's entry in the [`index`]. Teasers only have their title and date.
+    pub fn meta(&self, today: NaiveDate) -> PostMeta {
+        let published = self.status(today) == Status::Published;
+        PostMeta {
+            slug: self.slug.to_string(),
+            title: self.title.to_string(),
+            path: self.path(),
+            published: self.published,
+            coming_soon: !published,
+            tags: if published {
+                self.tags.iter().map(|tag| tag.to_string()).collect()
+            } else {
+                Vec::new()
+            },
+            summary: if published {
+                self.excerpt()
+            } else {
+                String::new()
+            },
+            reading_minutes: if published { self.reading_minutes() } else { 0 },
+        }
+    }
+
+    /// The site a syndicated post first appeared on, by host name without `www.`, e.g. `dev.to`.
+    pub fn original_site(&self) -> Option<&'static str> {
+        let url = self.canonical_url?;
+        let host = url
+            .split_once("://")
+            .map_or(url, |(_, rest)| rest)
+            .split(['/', '?', '#'])
+            .next()
+            .unwrap_or_default();
+        Some(host.strip_prefix("www.").unwrap_or(host))
+    }
+
+    /// Plain-text summary for the blog index, feeds and meta descriptions.
+    pub fn excerpt(&self) -> String {
+        match self.summary {
+            Some(summary) => summary.to_string(),
+            None => excerpt::excerpt(&self.body_html(), EXCERPT_WORDS),
+        }
+    }
+
+    /// Meta description: the hand-written summary as it is, or one derived from the body of at most `max_chars`
+    /// characters.
+    pub fn description(&self, max_chars: usize) -> String {
+        match self.summary {
+            Some(summary) => summary.to_string(),
+            None => excerpt::description(&self.body_html(), max_chars),
+        }
+    }
+}
+
+macro_rules! sample_post {
+    ($number:literal, $year:literal - $month:literal - $day:literal) => {
+        Post {
+            slug: concat!("blog-post-", $number),
+            legacy_id: Some($number),
+            title: concat!("Blog Post ", $number),
+            summary: None,
+            body: concat!(
+                "<p>This is blog post number ", $number, ".</p>
+                <p>In a real application, this content would be loaded from a database or markdown files.</p>
+
+                <h2>Sample Content</h2>
+                <p>Here's some sample content for blog post ", $number, ". This demonstrates how static site generation works with Dioxus.</p>
+
+                <h3>Benefits of SSG</h3>
+                <ul>
+                    <li>Fast loading times</li>
+                    <li>Great SEO</li>
+                    <li>Works without JavaScript</li>
+                    <li>Easy to deploy</li>
+                </ul>"
+            ),
+            source: file!(),
+            published: match NaiveDate::from_ymd_opt($year, $month, $day) {
+                Some(date) => date,
+                None => panic!("invalid publish date"),
+            },
+            evergreen: false,
+            teaser: false,
+            updates: &[],
+            audio: None,
+            tags: &["Dioxus", "Static sites"],
+            canonical_url: None,
+            license: None,
+            cover: None,
+        }
+    };
+}
+
+/// All posts, oldest first, including ones that aren't published yet. Go through [`published`] or [`listed`] to get
+/// what readers may see.
+pub const POSTS: &[Post] = &[
+    sample_post!(1, 2025 - 3 - 1),
+    sample_post!(2, 2025 - 4 - 12),
+    Post {
+        cover: Some(Cover {
+            src: "/assets/images/blog-post-3-cover.png",
+            alt: "",
+        }),
+        ..sample_post!(3, 2025 - 6 - 20)
+    },
+];
+
+/// The day publishing is gated on: the day of the build for generated pages, the day of the visit in the SPA.
+pub fn today() -> NaiveDate {
+    chrono::Utc::now().date_naive()
+}
+
+/// Posts that are out as of `today`, oldest first.
+pub fn published(today: NaiveDate) -> Vec<Post> {
+    POSTS
+        .iter()
+        .filter(|post| post.status(today) == Status::Published)
+        .copied()
+        .collect()
+}
+
+/// Posts published on the day and month of `today` in earlier years, newest first, for the "On this day" list. Posts
+/// from 29 February show on the 28th in other years.
+pub fn on_this_day(today: NaiveDate) -> Vec<PostMeta> {
+    let leap_day_too = today.month() == 2
+        && today.day() == 28
+        && NaiveDate::from_ymd_opt(today.year(), 2, 29).is_none();
+    let mut posts: Vec<PostMeta> = index(today)
+        .into_iter()
+        .filter(|post| !post.coming_soon && post.published.year() < today.year())
+        .filter(|post| {
+            let (month, day) = (post.published.month(), post.published.day());
+            (month, day) == (today.month(), today.day())
+                || (leap_day_too && (month, day) == (2, 29))
+        })
+        .collect();
+    posts.sort_by_key(|post| std::cmp::Reverse(post.published));
+    posts
+}
+
+/// Posts on the blog index as of `today`: the published ones and teasers for upcoming ones.
+pub fn listed(today: NaiveDate) -> Vec<Post> {
+    POSTS
+        .iter()
+        .filter(|post| post.status(today) != Status::Hidden)
+        .copied()
+        .collect()
+}
+
+/// The metadata of the posts on the blog index as of `today`, oldest first: what the blog index, the "On this day" list
+/// and `/posts.json` are built from.
+pub fn index(today: NaiveDate) -> Vec<PostMeta> {
+    listed(today).iter().map(|post| post.meta(today)).collect()
+}
+
+/// Posts that aren't out as of `today`, teasers included, soonest first. Only the development draft previews show them.
+pub fn drafts(today: NaiveDate) -> Vec<Post> {
+    POSTS
+        .iter()
+        .filter(|post| post.status(today) != Status::Published)
+        .copied()
+        .collect()
+}
+
+pub fn find(slug: &str) -> Option<&'static Post> {
+    POSTS.iter().find(|post| post.slug == slug)
+}
+
+/// Old numeric ids of the posts published as of `today`, mapped to their slugs. The static generator writes a redirect
+/// stub for each entry and the SPA router redirects the same ids, so both agree on which old URLs still work.
+pub fn legacy_redirects(today: NaiveDate) -> Vec<(i32, &'static str)> {
+    POSTS
+        .iter()
+        .filter(|post| post.status(today) == Status::Published)
+        .filter_map(|post| Some((post.legacy_id?, post.slug)))
+        .collect()
+}
+
+/// Slug `/blog/<id>` redirects to, if the id is in [`legacy_redirects`].
+pub fn legacy_slug(id: i32, today: NaiveDate) -> Option<&'static str> {
+    legacy_redirects(today)
+        .into_iter()
+        .find(|(legacy_id, _)| *legacy_id == id)
+        .map(|(_, slug)| slug)
+}
+
+/// Look up a post by its old numeric URL segment (`"2"` for `/blog/2`).
+pub fn find_legacy(id: &str) -> Option<&'static Post> {
+    let id: i32 = id.parse().ok()?;
+    POSTS.iter().find(|post| post.legacy_id == Some(id))
+}
+
+/// The posts before and after `post` in `posts`, for previous/next navigation.
+pub fn neighbours<'a>(posts: &'a [Post], post: &Post) -> (Option<&'a Post>, Option<&'a Post>) {
+    let Some(index) = posts.iter().position(|p| p.slug == post.slug) else {
+        return (None, None);
+    };
+    let previous = index.checked_sub(1).map(|i| &posts[i]);
+    (previous, posts.get(index + 1))
+}
@@ -0,0 +1,60 @@
+//! Idle-time prefetching for the SPA.
+//!
+//! Components outside the first page load their stylesheets only when they first render, so the first visit to a route
+//! that has them waits on a network request. After the first page has painted, this fetches those files while the
+//! browser is idle so they are in the HTTP cache before the reader navigates. The list is the components' own
+//! [`STYLESHEETS`](crate::components::STYLESHEETS), so a new component's stylesheet is fetched without touching this
+//! file; those the first page already loaded come straight from the cache. The page stylesheets are scoped and
+//! compiled into the WASM bundle (see [`styles`](crate::styles)), as is the page data (the about data, blog posts and
+//! project list), so neither needs prefetching. The generated indexes the site loads at runtime are in
+//! [`PREFETCH_PATHS`].
+
+#[cfg(feature = "web")]
+use crate::api::content::POSTS_JSON_PATH;
+#[cfg(feature = "web")]
+use crate::site_search::SEARCH_PATH;
+
+/// Generated files fetched once the browser is idle, besides the stylesheets: the post metadata the islands read, and
+/// the search page the navbar search submits to.
+#[cfg(feature = "web")]
+const PREFETCH_PATHS: &[&str] = &[POSTS_JSON_PATH, SEARCH_PATH];
+
+/// How long to wait before prefetching in browsers without `requestIdleCallback` (Safari).
+#[cfg(feature = "web")]
+const FALLBACK_DELAY_MS: i32 = 2000;
+
+/// Schedule the prefetch. Does nothing outside the browser.
+pub fn schedule() {
+    #[cfg(feature = "web")]
+    {
+        use wasm_bindgen::{closure::Closure, JsCast};
+
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+
+        let callback = Closure::once_into_js(prefetch_all);
+        let callback: &js_sys::Function = callback.unchecked_ref();
+        if window.request_idle_callback(callback).is_err() {
+            let _ = window
+                .set_timeout_with_callback_and_timeout_and_arguments_0(callback, FALLBACK_DELAY_MS);
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+fn prefetch_all() {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+
+    let stylesheets = crate::components::STYLESHEETS
+        .iter()
+        .map(|asset| asset.to_string());
+    let paths = PREFETCH_PATHS.iter().map(|path| path.to_string());
+    for url in stylesheets.chain(paths) {
+        crate::logger::debug(&format!("⏬ Prefetching {}", url));
+        // Only the cached response matters, so the promise is dropped; failures just mean a normal load later
+        let _ = window.fetch_with_str(&url);
+    }
+}
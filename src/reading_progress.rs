@@ -0,0 +1,57 @@
+//! Which posts get the [`ReadingProgress`](crate::components::ReadingProgress) bar: the ones of at least
+//! `[reading_progress] min_words` words in `site.toml`. Short posts fit in a screen or two and don't need one.
+//!
+//! The SPA reads the setting from the compiled-in `site.toml`, like the tag aliases, and the static generator from the
+//! loaded [`SiteConfig`](crate::ssg::config::SiteConfig), so both put the bar on the same posts.
+
+use crate::posts::Post;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+/// The `[reading_progress]` section of `site.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReadingProgressConfig {
+    /// Fewest words a post needs for the bar
+    pub min_words: usize,
+}
+
+impl Default for ReadingProgressConfig {
+    fn default() -> Self {
+        Self { min_words: 1200 }
+    }
+}
+
+impl ReadingProgressConfig {
+    /// The `[reading_progress]` section of the compiled-in `site.toml`, or the defaults if it can't be read.
+    pub fn compiled() -> &'static ReadingProgressConfig {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct SiteToml {
+            reading_progress: ReadingProgressConfig,
+        }
+
+        static CONFIG: OnceLock<ReadingProgressConfig> = OnceLock::new();
+        CONFIG.get_or_init(|| {
+            toml::from_str::<SiteToml>(SITE_TOML)
+                .map(|site| site.reading_progress)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Whether `post` is long enough for the bar.
+    pub fn shows_for(&self, post: &Post) -> bool {
+        post.word_count() >= self.min_words
+    }
+
+    /// Problems with the section, for `SiteConfig::load`.
+    pub fn validate(&self) -> Vec<String> {
+        if self.min_words == 0 {
+            vec!["[reading_progress] min_words must be at least 1".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
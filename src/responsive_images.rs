@@ -0,0 +1,127 @@
+//! Smaller copies of the site's images for `srcset`, so a phone or a card thumbnail doesn't download the full-size
+//! file.
+//!
+//! The static generator writes a copy of every image matching `[responsive_images] include` at each of the `widths`
+//! below its own width, named after the width (`cover.png` → `cover-640w.png`, see [`variant_path`]). [`srcset`]
+//! lists the same copies for an image, so the [`Img`](crate::components::Img) callers and the generated pages point at
+//! exactly the files that were written. The SPA reads the setting from the compiled-in `site.toml`, like the reading
+//! progress bar, and the generator from the loaded [`SiteConfig`](crate::ssg::config::SiteConfig).
+
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+/// The `[responsive_images]` section of `site.toml`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ResponsiveImagesConfig {
+    /// Widths in pixels the copies are made at; those not below an image's own width are skipped
+    pub widths: Vec<u32>,
+    /// Globs relative to `assets/` of the images to make copies of (PNG and JPEG only)
+    pub include: Vec<String>,
+}
+
+impl Default for ResponsiveImagesConfig {
+    fn default() -> Self {
+        Self {
+            widths: vec![320, 640, 960, 1280],
+            include: vec!["images/**".to_string()],
+        }
+    }
+}
+
+impl ResponsiveImagesConfig {
+    /// The `[responsive_images]` section of the compiled-in `site.toml`, or the defaults if it can't be read.
+    pub fn compiled() -> &'static ResponsiveImagesConfig {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct SiteToml {
+            responsive_images: ResponsiveImagesConfig,
+        }
+
+        static CONFIG: OnceLock<ResponsiveImagesConfig> = OnceLock::new();
+        CONFIG.get_or_init(|| {
+            toml::from_str::<SiteToml>(SITE_TOML)
+                .map(|site| site.responsive_images)
+                .unwrap_or_default()
+        })
+    }
+
+    /// The widths copies of an image `width` pixels wide are made at, smallest first.
+    pub fn widths_below(&self, width: u32) -> Vec<u32> {
+        let mut widths: Vec<u32> = self
+            .widths
+            .iter()
+            .copied()
+            .filter(|&variant| variant < width)
+            .collect();
+        widths.sort_unstable();
+        widths.dedup();
+        widths
+    }
+
+    /// Problems with the section, for `SiteConfig::load`.
+    pub fn validate(&self) -> Vec<String> {
+        if self.widths.contains(&0) {
+            vec!["[responsive_images] widths must be at least 1".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Path of the copy of the image at `src` that is `width` pixels wide: `-{width}w` before the extension.
+pub fn variant_path(src: &str, width: u32) -> String {
+    let name_start = src.rfind('/').map_or(0, |slash| slash + 1);
+    match src[name_start..].rfind('.') {
+        Some(dot) => {
+            let dot = name_start + dot;
+            format!("{}-{}w{}", &src[..dot], width, &src[dot..])
+        }
+        None => format!("{}-{}w", src, width),
+    }
+}
+
+/// `srcset` for the image at `src`, `width` pixels wide: its copies from the compiled-in `[responsive_images]`, then
+/// the image itself. `None` when the image is no wider than the smallest copy, so there is nothing to choose from.
+pub fn srcset(src: &str, width: u32) -> Option<String> {
+    let widths = ResponsiveImagesConfig::compiled().widths_below(width);
+    if widths.is_empty() {
+        return None;
+    }
+
+    let mut candidates: Vec<String> = widths
+        .into_iter()
+        .map(|variant| format!("{} {}w", variant_path(src, variant), variant))
+        .collect();
+    candidates.push(format!("{} {}w", src, width));
+    Some(candidates.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn variant_path_puts_the_width_before_the_extension() {
+        assert_eq!(
+            variant_path("/assets/images/cover.png", 640),
+            "/assets/images/cover-640w.png"
+        );
+        assert_eq!(
+            variant_path("images/v1.2/cover", 320),
+            "images/v1.2/cover-320w"
+        );
+    }
+
+    #[test]
+    fn widths_below_leaves_out_upscaled_copies() {
+        let config = ResponsiveImagesConfig {
+            widths: vec![960, 320, 640, 320],
+            include: Vec::new(),
+        };
+        assert_eq!(config.widths_below(800), [320, 640]);
+        assert_eq!(config.widths_below(320), Vec::<u32>::new());
+    }
+}
@@ -0,0 +1,55 @@
+//! Reading list of saved blog posts.
+//!
+//! The list is kept in the browser's localStorage as a JSON array of post identifiers, so it works on static hosting
+//! without any server.
+
+#[cfg(feature = "web")]
+const STORAGE_KEY: &str = "saved_posts";
+
+/// Load the saved posts, oldest first. Returns an empty list outside the browser or when storage is unavailable.
+pub fn load() -> Vec<String> {
+    #[cfg(feature = "web")]
+    {
+        let stored = web_sys::window()
+            .and_then(|window| window.local_storage().ok().flatten())
+            .and_then(|storage| storage.get_item(STORAGE_KEY).ok().flatten());
+
+        if let Some(json) = stored {
+            return serde_json::from_str(&json).unwrap_or_default();
+        }
+    }
+
+    Vec::new()
+}
+
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn store(posts: &[String]) {
+    #[cfg(feature = "web")]
+    {
+        let storage = web_sys::window().and_then(|window| window.local_storage().ok().flatten());
+        if let (Some(storage), Ok(json)) = (storage, serde_json::to_string(posts)) {
+            let _ = storage.set_item(STORAGE_KEY, &json);
+        }
+    }
+}
+
+/// Save the post if it isn't saved yet, otherwise remove it. Returns whether the post is saved afterwards.
+pub fn toggle(post: &str) -> bool {
+    let mut posts = load();
+    let saved = if let Some(index) = posts.iter().position(|saved| saved == post) {
+        posts.remove(index);
+        false
+    } else {
+        posts.push(post.to_string());
+        true
+    };
+
+    store(&posts);
+    saved
+}
+
+pub fn remove(post: &str) {
+    let mut posts = load();
+    posts.retain(|saved| saved != post);
+    store(&posts);
+}
@@ -0,0 +1,168 @@
+//! Scoping of the page stylesheets, done by `build.rs` (see [`styles`](crate::styles) for the result).
+//!
+//! Every selector in a scoped stylesheet is limited to elements inside, or carrying, the stylesheet's scope class,
+//! a prefix with a hash of the stylesheet like `scope-contact-3fa2c1`. So `.contact-link` in `about.css` and in
+//! `contact.css` no longer style each other's pages, and the rules gain a class of specificity over the generic rules
+//! of `main.css` and the base CSS the generator inlines. `@media` and `@supports` blocks are scoped inside; other at-rules
+//! such as `@keyframes` and `@font-face` are kept as they are.
+
+/// The stylesheets in `assets/styling/` that are scoped, by file name without the extension.
+pub const STYLESHEETS: &[&str] = &["about", "blog", "contact", "projects", "saved"];
+
+/// At-rules whose blocks hold ordinary rules, which are scoped like the top level.
+const NESTING_AT_RULES: &[&str] = &["@media", "@supports", "@container", "@layer"];
+
+/// Selectors for the document root, which become the scope itself.
+const ROOT_SELECTORS: &[&str] = &[":root", "html", "body"];
+
+/// The scope class for the stylesheet `name` with the contents `css`. It changes whenever the stylesheet does.
+pub fn scope_class(name: &str, css: &str) -> String {
+    // FNV-1a, which needs no dependencies in the build script
+    let hash = css.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    format!("scope-{}-{:06x}", name, hash & 0xff_ffff)
+}
+
+/// `css` with every rule limited to elements inside, or carrying, the class `scope`. Comments are dropped.
+pub fn scope(css: &str, scope: &str) -> Result<String, String> {
+    let css = strip_comments(css);
+    let mut scoped = String::with_capacity(css.len() * 2);
+    let mut rest = css.as_str();
+
+    while let Some(start) = rest.find(|c: char| !c.is_whitespace()) {
+        scoped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let Some(open) = find_outside_quotes(rest, &['{', ';']) else {
+            return Err(format!("`{}` isn't followed by a block", rest.trim()));
+        };
+        let prelude = rest[..open].trim();
+        if rest.as_bytes()[open] == b';' {
+            // A statement such as `@import`
+            scoped.push_str(&rest[..=open]);
+            rest = &rest[open + 1..];
+            continue;
+        }
+
+        let close = matching_brace(rest, open)
+            .ok_or_else(|| format!("the block after `{}` isn't closed", prelude))?;
+        let block = &rest[open + 1..close];
+        if prelude.starts_with('@') {
+            let name = prelude.split_whitespace().next().unwrap_or(prelude);
+            if NESTING_AT_RULES.contains(&name) {
+                scoped.push_str(&format!("{} {{{}}}", prelude, self::scope(block, scope)?));
+            } else {
+                scoped.push_str(&rest[..=close]);
+            }
+        } else {
+            scoped.push_str(&format!(
+                "{} {{{}}}",
+                scope_selectors(prelude, scope),
+                block
+            ));
+        }
+        rest = &rest[close + 1..];
+    }
+
+    scoped.push_str(rest);
+    Ok(scoped)
+}
+
+/// A selector list with each selector matching inside the scope and on the scope's own element.
+fn scope_selectors(selectors: &str, scope: &str) -> String {
+    split_outside_brackets(selectors, |c| c == ',')
+        .into_iter()
+        .map(str::trim)
+        .filter(|selector| !selector.is_empty())
+        .map(|selector| {
+            let first_end = split_outside_brackets(selector, |c| {
+                c.is_whitespace() || matches!(c, '>' | '+' | '~')
+            })[0]
+                .len();
+            let (first, rest) = selector.split_at(first_end);
+            if ROOT_SELECTORS.contains(&first) {
+                return format!(".{}{}", scope, rest);
+            }
+            // Before any pseudo-class or pseudo-element, where a class is always allowed
+            let at = find_outside_quotes(first, &[':']).unwrap_or(first.len());
+            format!(
+                ".{scope} {selector}, {}.{scope}{}{rest}",
+                &first[..at],
+                &first[at..],
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// `text` split at the characters matching `split` that aren't inside brackets, parentheses or quotes.
+fn split_outside_brackets(text: &str, split: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut quote = None;
+    let mut from = 0;
+    for (at, c) in text.char_indices() {
+        match (quote, c) {
+            (Some(open), _) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(' | '[') => depth += 1,
+            (None, ')' | ']') => depth -= 1,
+            (None, _) if depth == 0 && split(c) => {
+                parts.push(&text[from..at]);
+                from = at + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[from..]);
+    parts
+}
+
+/// Position of the first of `targets` in `text` that isn't inside a quoted string.
+fn find_outside_quotes(text: &str, targets: &[char]) -> Option<usize> {
+    let mut quote = None;
+    for (at, c) in text.char_indices() {
+        match quote {
+            Some(open) if c == open => quote = None,
+            Some(_) => {}
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if targets.contains(&c) => return Some(at),
+            None => {}
+        }
+    }
+    None
+}
+
+/// Position of the `}` closing the `{` at `open`.
+fn matching_brace(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut at = open;
+    loop {
+        let found = at + find_outside_quotes(&text[at..], &['{', '}'])?;
+        if text.as_bytes()[found] == b'{' {
+            depth += 1;
+        } else {
+            depth -= 1;
+            if depth == 0 {
+                return Some(found);
+            }
+        }
+        at = found + 1;
+    }
+}
+
+fn strip_comments(css: &str) -> String {
+    let mut stripped = String::with_capacity(css.len());
+    let mut rest = css;
+    while let Some(start) = rest.find("/*") {
+        stripped.push_str(&rest[..start]);
+        match rest[start + 2..].find("*/") {
+            Some(end) => rest = &rest[start + 2 + end + 2..],
+            None => rest = "",
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
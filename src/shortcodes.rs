@@ -0,0 +1,163 @@
+//! Shortcodes: markup in post bodies that stands for something richer than HTML.
+//!
+//! A runnable Rust example:
+//!
+//! ```text
+//! {{< playground >}}
+//! fn main() {
+//!     println!("Hello from the playground");
+//! }
+//! {{< /playground >}}
+//! ```
+//!
+//! The code between the tags is plain text, not HTML, and its common indentation is removed. The static pages get it
+//! as a highlighted block with a link to the Rust Playground, plus the attributes the `mount_playgrounds` island
+//! looks for to turn it into an editor with a run button; the SPA renders the [`Playground`](crate::components::Playground)
+//! component in its place directly.
+//!
+//! A callout (see [`callouts`](crate::callouts)), around HTML: `{{< note >}}`, `{{< warning >}}` or `{{< tip >}}`,
+//! closed by `{{< /note >}}` and so on. Shortcodes don't nest.
+
+use crate::callouts::{self, CalloutKind};
+use crate::contact_prefill;
+use crate::html_sanitizer;
+
+const PLAYGROUND: &str = "playground";
+
+/// Attribute holding the code on a static playground, for the island to read before it replaces the markup.
+pub const PLAYGROUND_CODE_ATTR: &str = "data-playground-code";
+
+/// Id of the `index`th playground on a page (from 1). The first id is the one `hybrid.toml` mounts the island on.
+pub fn playground_id(index: usize) -> String {
+    format!("playground-{}", index)
+}
+
+/// A piece of a post body.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a> {
+    /// HTML as written, sanitized when it's rendered (see [`html_sanitizer`])
+    Html(&'a str),
+    /// A runnable Rust example
+    Playground { code: String },
+    /// A callout around HTML, sanitized like [`Segment::Html`]
+    Callout { kind: CalloutKind, html: &'a str },
+}
+
+/// Split `body` into HTML and shortcodes. An opening tag without its closing tag is left in as HTML.
+pub fn parse(body: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut rest = body;
+
+    // The earliest shortcode that is closed, with where it starts, its contents and where it ends
+    let next = |rest: &str| {
+        let names = std::iter::once(PLAYGROUND).chain(CalloutKind::ALL.map(CalloutKind::name));
+        names
+            .filter_map(|name| {
+                let (open, close) = (
+                    format!("{{{{< {} >}}}}", name),
+                    format!("{{{{< /{} >}}}}", name),
+                );
+                let start = rest.find(&open)?;
+                let inner = start + open.len();
+                let end = inner + rest[inner..].find(&close)?;
+                Some((name, start, inner..end, end + close.len()))
+            })
+            .min_by_key(|(_, start, _, _)| *start)
+    };
+
+    while let Some((name, start, inner, end)) = next(rest) {
+        if start > 0 {
+            segments.push(Segment::Html(&rest[..start]));
+        }
+        let contents = &rest[inner];
+        segments.push(
+            match CalloutKind::ALL
+                .into_iter()
+                .find(|kind| kind.name() == name)
+            {
+                Some(kind) => Segment::Callout {
+                    kind,
+                    html: contents,
+                },
+                None => Segment::Playground {
+                    code: dedent(contents),
+                },
+            },
+        );
+        rest = &rest[end..];
+    }
+
+    if !rest.is_empty() {
+        segments.push(Segment::Html(rest));
+    }
+    segments
+}
+
+/// `body` with its shortcodes expanded into static HTML, and its own HTML sanitized.
+pub fn render(body: &str) -> String {
+    let mut index = 0;
+    parse(body)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Html(html) => html_sanitizer::sanitize(html),
+            Segment::Playground { code } => {
+                index += 1;
+                static_playground(index, &code)
+            }
+            Segment::Callout { kind, html } => {
+                callouts::static_html(kind, None, &html_sanitizer::sanitize(html))
+            }
+        })
+        .collect()
+}
+
+/// Rust Playground page with `code` loaded, for readers without the island.
+pub fn playground_url(code: &str) -> String {
+    format!(
+        "https://play.rust-lang.org/?version=stable&mode=debug&edition=2021&code={}",
+        contact_prefill::encode(code)
+    )
+}
+
+fn static_playground(index: usize, code: &str) -> String {
+    format!(
+        r#"<div class="playground" id="{id}" {attr}="{code}">
+    <pre class="playground-code"><code class="language-rust">{code}</code></pre>
+    <p class="playground-actions"><a href="{url}" rel="noopener">Run on the Rust Playground →</a></p>
+</div>"#,
+        id = playground_id(index),
+        attr = PLAYGROUND_CODE_ATTR,
+        code = escape(code),
+        url = escape(&playground_url(code)),
+    )
+}
+
+/// The code without the blank lines around it and the indentation every line shares.
+fn dedent(code: &str) -> String {
+    let lines: Vec<&str> = code.lines().collect();
+    let first = lines.iter().position(|line| !line.trim().is_empty());
+    let last = lines.iter().rposition(|line| !line.trim().is_empty());
+    let lines = match (first, last) {
+        (Some(first), Some(last)) => &lines[first..=last],
+        _ => return String::new(),
+    };
+    let indent = lines
+        .iter()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|line| line.get(indent..).unwrap_or("").trim_end())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
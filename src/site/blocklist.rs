@@ -0,0 +1,115 @@
+//! `site blocklist`: the rules contact submissions are turned away by, in `[contact.store] blocklist` (see
+//! [`blocklist`]).
+//!
+//! - `list` shows the rules, with how many submissions each has blocked
+//! - `add <RULE> [--note TEXT]` adds a rule written as `email:PATTERN`, `ip:RANGE` or `keyword:TEXT`
+//! - `remove <RULE>` removes a rule, written the same way
+//! - `log [--since DAYS]` shows the blocked submissions from `[contact.store] blocked`, newest first
+
+use chrono::{Duration, Utc};
+use dioxus_site::ssg::blocklist::{self, Entry, Rule};
+use dioxus_site::ssg::config::SiteConfig;
+use std::path::Path;
+
+const USAGE: &str =
+    "usage: site blocklist <list | add <RULE> [--note TEXT] | remove <RULE> | log [--since DAYS]>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?.contact.store;
+    let path = Path::new(&config.blocklist);
+    let mut entries = blocklist::load(path)?;
+
+    match args {
+        ["list"] => {
+            if entries.is_empty() {
+                println!("✅ Nothing is blocked");
+                return Ok(());
+            }
+            let attempts = blocklist::attempts(Path::new(&config.blocked))?;
+            for entry in &entries {
+                let blocked = attempts
+                    .iter()
+                    .filter(|attempt| attempt.rule == entry.rule)
+                    .count();
+                println!(
+                    "⛔ {} (added {}, blocked {}){}",
+                    entry.rule,
+                    entry.added_at.format("%Y-%m-%d"),
+                    blocked,
+                    if entry.note.is_empty() {
+                        String::new()
+                    } else {
+                        format!(": {}", entry.note)
+                    }
+                );
+            }
+            Ok(())
+        }
+        ["add", rule, rest @ ..] => {
+            let note = match rest {
+                [] => "",
+                ["--note", note] => note,
+                _ => return Err(USAGE.into()),
+            };
+            let rule: Rule = rule.parse()?;
+            if entries.iter().any(|entry| entry.rule == rule) {
+                return Err(format!("{} is already blocked", rule).into());
+            }
+            println!("⛔ Blocking {}", rule);
+            entries.push(Entry {
+                rule,
+                added_at: Utc::now(),
+                note: note.to_string(),
+            });
+            blocklist::save(path, &entries)
+        }
+        ["remove", rule] => {
+            let rule: Rule = rule.parse()?;
+            let before = entries.len();
+            entries.retain(|entry| entry.rule != rule);
+            if entries.len() == before {
+                return Err(format!("{} isn't blocked", rule).into());
+            }
+            println!("🗑️  No longer blocking {}", rule);
+            blocklist::save(path, &entries)
+        }
+        ["log", rest @ ..] => {
+            let since = match rest {
+                [] => None,
+                ["--since", days] => {
+                    let days: i64 = days.parse().map_err(|_| USAGE)?;
+                    Some(Utc::now() - Duration::days(days))
+                }
+                _ => return Err(USAGE.into()),
+            };
+            let mut attempts = blocklist::attempts(Path::new(&config.blocked))?;
+            if let Some(since) = since {
+                attempts.retain(|attempt| attempt.at >= since);
+            }
+            if attempts.is_empty() {
+                println!("✅ No submissions blocked");
+                return Ok(());
+            }
+            for attempt in attempts.iter().rev() {
+                println!(
+                    "⛔ {} <{}>{}: \"{}\" by {}",
+                    attempt.at.format("%Y-%m-%d %H:%M"),
+                    attempt.email,
+                    attempt
+                        .client_ip
+                        .map(|ip| format!(" from {}", ip))
+                        .unwrap_or_default(),
+                    attempt.subject,
+                    attempt.rule
+                );
+            }
+            println!(
+                "📂 {} blocked submission(s) in {}",
+                attempts.len(),
+                config.blocked
+            );
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
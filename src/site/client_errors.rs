@@ -0,0 +1,81 @@
+//! `site client-errors`: the error reports from readers' browsers in `[telemetry] log`, grouped so the most frequent
+//! come first. `--since DAYS` leaves out older reports.
+
+use chrono::{Duration, Utc};
+use dioxus_site::api::client_errors::ClientErrorKind;
+use dioxus_site::ssg::client_errors;
+use dioxus_site::ssg::config::SiteConfig;
+use std::path::Path;
+
+const USAGE: &str = "usage: site client-errors [--since DAYS]";
+
+/// Pages listed for each error.
+const MAX_PAGES: usize = 3;
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let since = match args {
+        [] => None,
+        ["--since", days] => {
+            let days: i64 = days.parse().map_err(|_| USAGE)?;
+            Some(Utc::now() - Duration::days(days))
+        }
+        _ => return Err(USAGE.into()),
+    };
+
+    let config = SiteConfig::load(Path::new("site.toml"))?.telemetry;
+    let mut errors = client_errors::load(Path::new(&config.log))?;
+    if let Some(since) = since {
+        errors.retain(|error| error.received_at >= since);
+    }
+    if errors.is_empty() {
+        println!("✅ No client errors reported");
+        return Ok(());
+    }
+
+    for group in client_errors::group(&errors) {
+        let what = match group.kind {
+            ClientErrorKind::Island => format!(
+                "🏝️  {} failed ({})",
+                group.island.unwrap_or("an island"),
+                group.category.unwrap_or("unknown")
+            ),
+            ClientErrorKind::Exception => "💥 Exception".to_string(),
+            ClientErrorKind::NotFound => "🔗 Not found".to_string(),
+        };
+        // For missing pages the message is the page on this site that links to them, if any
+        let message = match group.kind {
+            ClientErrorKind::NotFound if group.message.is_empty() => {
+                "typed in or linked from another site".to_string()
+            }
+            ClientErrorKind::NotFound => format!("linked from {}", group.message),
+            _ => group.message.to_string(),
+        };
+        println!("{} × {}: {}", what, group.count, message);
+
+        let mut pages: Vec<(&str, usize)> = group.pages.into_iter().collect();
+        pages.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        let shown: Vec<String> = pages
+            .iter()
+            .take(MAX_PAGES)
+            .map(|(page, count)| format!("{} ({})", page, count))
+            .collect();
+        let more = pages.len().saturating_sub(MAX_PAGES);
+        println!(
+            "   on {}{}, last at {}",
+            shown.join(", "),
+            if more > 0 {
+                format!(" and {} more", more)
+            } else {
+                String::new()
+            },
+            group.last_seen.format("%Y-%m-%d %H:%M")
+        );
+    }
+    println!(
+        "📂 {} report(s) in {} (sampling {}% of page views)",
+        errors.len(),
+        config.log,
+        config.sample_rate * 100.0
+    );
+    Ok(())
+}
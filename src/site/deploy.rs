@@ -0,0 +1,55 @@
+//! `site deploy`: publish the build as a new release and switch the live site to it in one step (see [`deploy`]).
+//!
+//! - with no arguments, copies `[deploy] source` into a new release, points `[deploy] current` at it and removes the
+//!   releases beyond `[deploy] keep`
+//! - `--rollback` points `current` back at the release before the live one
+//! - `--list` shows the releases kept, marking the live one
+
+use chrono::Utc;
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::deploy;
+use dioxus_site::ssg::watch;
+use std::path::Path;
+
+const USAGE: &str = "usage: site deploy [--rollback | --list]";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?.deploy;
+
+    match args {
+        [] => {
+            let commit = watch::head().unwrap_or_else(|_| "unknown".to_string());
+            println!("📦 Copying {} into a new release", config.source);
+            let release = deploy::prepare(&config, &commit, Utc::now())?;
+            deploy::activate(&config, &release)?;
+            println!("🚀 Live: {} -> {}", config.current, release.name);
+            for name in deploy::prune(&config)? {
+                println!("🗑️  Removed the old release {}", name);
+            }
+            Ok(())
+        }
+        ["--rollback"] => {
+            let release = deploy::previous(&config)?;
+            deploy::activate(&config, &release)?;
+            println!("⏪ Rolled back: {} -> {}", config.current, release.name);
+            Ok(())
+        }
+        ["--list"] => {
+            let live = deploy::live(&config)?;
+            let releases = deploy::releases(&config)?;
+            if releases.is_empty() {
+                println!("📭 No releases in {}", config.releases);
+                return Ok(());
+            }
+            for release in releases.iter().rev() {
+                if Some(release) == live.as_ref() {
+                    println!("🟢 {} (live)", release.name);
+                } else {
+                    println!("   {}", release.name);
+                }
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
@@ -0,0 +1,93 @@
+//! `site emails preview`: the contact emails rendered with sample data, for checking templates in a browser.
+
+use dioxus_site::contact_reasons::{DEFAULT_REASON, REASONS};
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::email::{self, EmailTemplate, CONFIRMATION_TEMPLATE};
+use dioxus_site::ssg::html;
+use std::fs;
+use std::path::Path;
+
+const DEFAULT_OUT_DIR: &str = "target/email-preview";
+
+pub fn preview(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let out_dir = match args {
+        [] => DEFAULT_OUT_DIR,
+        ["--out", dir] => dir,
+        _ => return Err("usage: site emails preview [--out DIR]".into()),
+    };
+    let out_dir = Path::new(out_dir);
+
+    println!("📧 Rendering email previews...");
+    let config = SiteConfig::load(Path::new("site.toml"))?;
+
+    // Each template once, with the sample for the first reason routed to it so `{reason}` reads naturally
+    let mut templates: Vec<(&str, &str)> = Vec::new();
+    for reason in REASONS {
+        if let Some(route) = config.contact.route(reason.id) {
+            if !templates.iter().any(|(name, _)| *name == route.template) {
+                templates.push((&route.template, reason.id));
+            }
+        }
+    }
+    for route in config.contact.forms.values() {
+        if !templates.iter().any(|(name, _)| *name == route.template) {
+            templates.push((&route.template, DEFAULT_REASON));
+        }
+    }
+    templates.push((CONFIRMATION_TEMPLATE, DEFAULT_REASON));
+
+    // The notifications link to a made-up reply page, so the link shows when replies are on
+    let reply_url = if config.contact.replies.is_enabled() {
+        format!("{}sample/", config.contact.replies.url)
+    } else {
+        String::new()
+    };
+
+    fs::create_dir_all(out_dir)?;
+    let mut rows = Vec::new();
+    for (name, reason) in templates {
+        let rendered = EmailTemplate::load(name)?
+            .render_with_reply_url(&email::sample_request(reason), &reply_url)?;
+
+        fs::write(out_dir.join(format!("{}.html", name)), &rendered.html)?;
+        fs::write(
+            out_dir.join(format!("{}.txt", name)),
+            format!("Subject: {}\n\n{}", rendered.subject, rendered.text),
+        )?;
+        println!("✅ Rendered: {} ({}.html, {}.txt)", name, name, name);
+
+        rows.push(format!(
+            r#"<tr><td>{name}</td><td>{subject}</td><td><a href="{name}.html">HTML</a></td><td><a href="{name}.txt">Text</a></td></tr>"#,
+            name = html::escape(name),
+            subject = html::escape(&rendered.subject),
+        ));
+    }
+
+    fs::write(
+        out_dir.join("index.html"),
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>Email previews</title>
+</head>
+<body style="font-family: sans-serif; padding: 24px;">
+    <h1>Email previews</h1>
+    <table cellpadding="8">
+        <tr><th>Template</th><th>Subject</th><th colspan="2">Variants</th></tr>
+        {}
+    </table>
+</body>
+</html>
+"#,
+            rows.join("\n        ")
+        ),
+    )?;
+
+    println!(
+        "📂 Previews written to {}",
+        out_dir.join("index.html").display()
+    );
+    Ok(())
+}
@@ -0,0 +1,31 @@
+//! `site health`: the reports the health endpoints of `site serve` answer with (see [`health`]), without a server.
+//!
+//! - `live` prints the liveness report, with the build
+//! - `ready` checks the store, the SMTP server and the webhook and prints the readiness report
+//!
+//! The report is printed as JSON, and the command fails when it isn't healthy, so an uptime monitor or a container
+//! health check can run it as it is.
+
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::health::{self, HEALTHZ_PATH, READYZ_PATH};
+use std::path::Path;
+
+const USAGE: &str = "usage: site health <live|ready>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let (path, report) = match args {
+        ["live"] => (HEALTHZ_PATH, health::liveness()),
+        ["ready"] => {
+            let config = SiteConfig::load(Path::new("site.toml"))?.contact;
+            (READYZ_PATH, health::readiness(&config))
+        }
+        _ => return Err(USAGE.into()),
+    };
+
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if report.is_healthy() {
+        Ok(())
+    } else {
+        Err(format!("{} would answer {}", path, report.http_status()).into())
+    }
+}
@@ -0,0 +1,62 @@
+//! Site maintenance commands
+//!
+//! Usage: `cargo run --bin site --features ssr -- <command>`
+//!
+//! - `blocklist <list|add|remove|log>` manages the rules contact submissions are turned away by, and shows the ones
+//!   they caught
+//! - `client-errors [--since DAYS]` summarises the error reports from readers' browsers
+//! - `deploy [--rollback | --list]` publishes the build as a new release and switches the live site to it, or back to
+//!   the release before
+//! - `emails preview [--out DIR]` renders every contact email template with sample data to HTML and text files in
+//!   DIR (default `target/email-preview`), with an `index.html` linking them all
+//! - `health <live|ready>` prints the liveness or readiness report of the contact backend, failing when it isn't healthy
+//! - `messages <list|archive|unarchive|delete|export>` pages through, searches, tidies and exports the stored
+//!   contact submissions
+//! - `notify <list|retry>` shows and resends the contact submissions that didn't reach every channel
+//! - `preview <list|link>` prints the links `site serve` shows drafts at, to share them before they're out
+//! - `replies <list|link>` shows the replies sent to contact submissions from the site, and the reply page of one
+//! - `serve [--address HOST:PORT]` runs the backend taking contact submissions, answering them from the reply pages,
+//!   counting page views, showing drafts and answering the health checks, behind the web server
+//! - `stats [--days N]` prints the page views counted over the last N days
+//! - `watch-remote [--once] [--interval MINUTES]` pulls the content on an interval, and builds and deploys it when it
+//!   changed
+//! - `webhooks <list|retry|test>` shows and resends the contact submissions the webhook didn't take, or sends it a
+//!   sample submission
+
+mod blocklist;
+mod client_errors;
+mod deploy;
+mod emails;
+mod health;
+mod messages;
+mod notify;
+mod preview;
+mod replies;
+mod serve;
+mod stats;
+mod watch_remote;
+mod webhooks;
+
+use std::env;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    match args.as_slice() {
+        ["blocklist", rest @ ..] => blocklist::run(rest),
+        ["client-errors", rest @ ..] => client_errors::run(rest),
+        ["deploy", rest @ ..] => deploy::run(rest),
+        ["emails", "preview", rest @ ..] => emails::preview(rest),
+        ["health", rest @ ..] => health::run(rest),
+        ["messages", rest @ ..] => messages::run(rest),
+        ["notify", rest @ ..] => notify::run(rest),
+        ["preview", rest @ ..] => preview::run(rest),
+        ["replies", rest @ ..] => replies::run(rest),
+        ["serve", rest @ ..] => serve::run(rest),
+        ["stats", rest @ ..] => stats::run(rest),
+        ["watch-remote", rest @ ..] => watch_remote::run(rest),
+        ["webhooks", rest @ ..] => webhooks::run(rest),
+        _ => Err("usage: site <blocklist <list|add|remove|log> | client-errors [--since DAYS] | deploy [--rollback | --list] | emails preview [--out DIR] | health <live|ready> | messages <list|archive|unarchive|delete|export> | notify <list|retry> | preview <list | link SLUG> | replies <list [ID] | link ID> | serve [--address HOST:PORT] | stats [--days N] | watch-remote [--once] [--interval MINUTES] | webhooks <list|retry|test>>".into()),
+    }
+}
@@ -0,0 +1,219 @@
+//! `site messages`: the contact submissions kept in `[contact.store] submissions` (see [`messages`]).
+//!
+//! - `list [--page N]` shows a page of messages, newest first
+//! - `archive <ID>...`, `unarchive <ID>...` and `delete <ID>...` act on the messages with those ids, or on every match
+//!   of the filters when no ids are given
+//! - `export [--format csv|json] [--out FILE]` writes the matching messages as CSV (the default) or JSON, to standard
+//!   output by default
+//!
+//! Every command takes the filters `--search TEXT` (words that must all appear in the message), `--reason ID`,
+//! `--since DATE` and `--until DATE` (the days, `YYYY-MM-DD` in UTC, the messages arrived between, both included)
+//! and `--archived`, which includes archived messages.
+
+use chrono::NaiveDate;
+use dioxus_site::contact_reasons;
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::messages::{self, MessageQuery, StoredMessage};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+const USAGE: &str = "usage: site messages <list [--page N] | archive [ID...] | unarchive [ID...] | delete [ID...] | export [--format csv|json] [--out FILE]> [--search TEXT] [--reason ID] [--since YYYY-MM-DD] [--until YYYY-MM-DD] [--archived]";
+
+/// Characters of a message shown in the listing.
+const PREVIEW_LENGTH: usize = 100;
+
+/// The command line after the action.
+#[derive(Default)]
+struct Options<'a> {
+    query: MessageQuery,
+    page: usize,
+    json: bool,
+    out: Option<&'a str>,
+    ids: Vec<&'a str>,
+}
+
+fn parse<'a>(args: &[&'a str]) -> Result<Options<'a>, Box<dyn std::error::Error>> {
+    let mut options = Options {
+        page: 1,
+        ..Options::default()
+    };
+    let mut args = args.iter();
+    while let Some(&arg) = args.next() {
+        let mut value = || args.next().copied().ok_or(USAGE);
+        match arg {
+            "--search" => options.query.search = value()?.to_string(),
+            "--reason" => {
+                let reason = value()?;
+                if contact_reasons::reason(reason).is_none() {
+                    return Err(format!("no reason for contact has the id `{}`", reason).into());
+                }
+                options.query.reason = Some(reason.to_string());
+            }
+            "--since" => options.query.since = Some(date(value()?)?),
+            "--until" => options.query.until = Some(date(value()?)?),
+            "--archived" => options.query.include_archived = true,
+            "--page" => options.page = value()?.parse().map_err(|_| USAGE)?,
+            "--format" => {
+                options.json = match value()? {
+                    "csv" => false,
+                    "json" => true,
+                    _ => return Err(USAGE.into()),
+                }
+            }
+            "--out" => options.out = Some(value()?),
+            _ if arg.starts_with("--") => return Err(USAGE.into()),
+            id => options.ids.push(id),
+        }
+    }
+    if let (Some(since), Some(until)) = (options.query.since, options.query.until) {
+        if since > until {
+            return Err(format!("--since {} is after --until {}", since, until).into());
+        }
+    }
+    Ok(options)
+}
+
+fn date(value: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map_err(|_| format!("`{}` isn't a date written as YYYY-MM-DD", value))
+}
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let Some((&action, rest)) = args.split_first() else {
+        return Err(USAGE.into());
+    };
+    let options = parse(rest)?;
+    let config = SiteConfig::load(Path::new("site.toml"))?.contact.store;
+    let path = Path::new(&config.submissions);
+    let mut stored = messages::load(path)?;
+
+    match action {
+        "list" => {
+            let found = messages::search(&stored, &options.query);
+            if found.is_empty() {
+                println!("📭 No messages");
+                return Ok(());
+            }
+            let page = messages::page(found, options.page);
+            for message in &page.messages {
+                print_message(message);
+            }
+            println!(
+                "📄 Page {} of {} ({} message(s){})",
+                page.number,
+                page.pages,
+                page.total,
+                if options.query.is_filtered() {
+                    " matching"
+                } else {
+                    ""
+                }
+            );
+            Ok(())
+        }
+        "archive" | "unarchive" | "delete" => {
+            let selected = selected_ids(&stored, &options)?;
+            let done = match action {
+                "delete" => {
+                    stored.retain(|message| !selected.contains(&message.id()));
+                    "Deleted"
+                }
+                _ => {
+                    for message in stored
+                        .iter_mut()
+                        .filter(|message| selected.contains(&message.id()))
+                    {
+                        message.archived = action == "archive";
+                    }
+                    if action == "archive" {
+                        "Archived"
+                    } else {
+                        "Unarchived"
+                    }
+                }
+            };
+            messages::save(path, &stored)?;
+            println!("✅ {} {} message(s)", done, selected.len());
+            Ok(())
+        }
+        "export" => {
+            let found = messages::search(&stored, &options.query);
+            let export = if options.json {
+                messages::to_json(&found)? + "\n"
+            } else {
+                messages::to_csv(&found)
+            };
+            match options.out {
+                Some(out) => {
+                    fs::write(out, export)?;
+                    println!("✅ Exported {} message(s) to {}", found.len(), out);
+                }
+                None => print!("{}", export),
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+/// Ids of the messages a bulk action applies to: the ones given, which must exist, or else every match of the
+/// filters. Acting on every message at once needs a filter, so a bare `delete` can't empty the file.
+fn selected_ids(
+    stored: &[StoredMessage],
+    options: &Options,
+) -> Result<BTreeSet<String>, Box<dyn std::error::Error>> {
+    if options.ids.is_empty() {
+        if !options.query.is_filtered() {
+            return Err(
+                "give the ids of the messages, or --search, --reason, --since or --until to act on every match"
+                    .into(),
+            );
+        }
+        return Ok(messages::search(stored, &options.query)
+            .iter()
+            .map(|message| message.id())
+            .collect());
+    }
+
+    let known: BTreeSet<String> = stored.iter().map(StoredMessage::id).collect();
+    if let Some(unknown) = options.ids.iter().find(|id| !known.contains(**id)) {
+        return Err(format!("no message has the id `{}`", unknown).into());
+    }
+    Ok(options.ids.iter().map(|id| id.to_string()).collect())
+}
+
+fn print_message(message: &StoredMessage) {
+    let request = &message.request;
+    let reason = contact_reasons::reason(&request.reason)
+        .map_or(request.reason.as_str(), |reason| reason.label);
+    println!(
+        "{} {} {} from {} <{}> ({}): \"{}\"",
+        if message.archived {
+            "🗄️ "
+        } else {
+            "✉️ "
+        },
+        message.id(),
+        message.received_at.format("%Y-%m-%d %H:%M"),
+        request.name.trim(),
+        request.email.trim(),
+        reason,
+        request.subject.trim()
+    );
+    let text = request
+        .message
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    let preview: String = text.chars().take(PREVIEW_LENGTH).collect();
+    println!(
+        "   {}{}",
+        preview,
+        if text.chars().count() > PREVIEW_LENGTH {
+            "…"
+        } else {
+            ""
+        }
+    );
+}
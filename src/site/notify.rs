@@ -0,0 +1,76 @@
+//! `site notify`: how the contact submissions went on each channel (see [`notify`]).
+//!
+//! - `list` shows the submissions that didn't reach every channel, oldest first
+//! - `retry` sends them again to the channels they missed, keeping the statuses of the ones that still fail
+
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::notify::{self, DeliveryRecord};
+use std::path::Path;
+
+const USAGE: &str = "usage: site notify <list|retry>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?.contact;
+
+    match args {
+        ["list"] => {
+            let records = notify::records(Path::new(&config.store.deliveries))?;
+            let incomplete: Vec<&DeliveryRecord> = records
+                .iter()
+                .filter(|record| record.failed().next().is_some())
+                .collect();
+            if incomplete.is_empty() {
+                println!("✅ Every submission reached all of its channels");
+                return Ok(());
+            }
+            for record in &incomplete {
+                let marker = if record.is_kept() { "📭" } else { "🚨" };
+                println!(
+                    "{} {} from {} <{}>: \"{}\"",
+                    marker,
+                    record.received_at.format("%Y-%m-%d %H:%M"),
+                    record.request.name.trim(),
+                    record.request.email.trim(),
+                    record.request.subject.trim()
+                );
+                for status in &record.channels {
+                    match &status.error {
+                        None => println!("   ✅ {}", status.channel.name()),
+                        Some(error) => println!(
+                            "   ❌ {} ({} attempts, last at {}): {}",
+                            status.channel.name(),
+                            status.attempts,
+                            status.at.format("%Y-%m-%d %H:%M"),
+                            error
+                        ),
+                    }
+                }
+            }
+            let lost = incomplete.iter().filter(|record| !record.is_kept()).count();
+            println!(
+                "📂 {} submission(s) missing a channel in {}, {} on no channel at all",
+                incomplete.len(),
+                config.store.deliveries,
+                lost
+            );
+            Ok(())
+        }
+        ["retry"] => {
+            let retried = notify::retry_failed(&config)?;
+            let failed = retried
+                .iter()
+                .filter(|record| record.failed().next().is_some())
+                .count();
+            println!(
+                "📬 Completed {} of {} submission(s)",
+                retried.len() - failed,
+                retried.len()
+            );
+            if failed > 0 {
+                return Err(format!("{} submission(s) still missing a channel", failed).into());
+            }
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
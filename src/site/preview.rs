@@ -0,0 +1,47 @@
+//! `site preview`: links to the drafts `site serve` renders (see [`preview`]), to share before a post is out.
+//!
+//! - `list` prints every post that isn't published yet with its link, soonest first
+//! - `link <SLUG>` prints the link of one
+
+use dioxus_site::posts;
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::preview;
+use std::path::Path;
+
+const USAGE: &str = "usage: site preview <list | link SLUG>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?;
+    let secret = preview::secret(&config.preview).ok_or_else(|| {
+        format!(
+            "previews are off, or ${} isn't set",
+            config.preview.secret_env
+        )
+    })?;
+    let today = posts::today();
+
+    match args {
+        ["list"] => {
+            let drafts = posts::drafts(today);
+            if drafts.is_empty() {
+                println!("📭 No drafts");
+                return Ok(());
+            }
+            for post in &drafts {
+                println!("📝 {} ({})", post.title, post.date_label());
+                println!("   {}", preview::url(&config, &secret, post));
+            }
+            Ok(())
+        }
+        ["link", slug] => {
+            let post =
+                posts::find(slug).ok_or_else(|| format!("no post has the slug `{}`", slug))?;
+            if post.status(today) == posts::Status::Published {
+                return Err(format!("`{}` is published already, at {}", slug, post.path()).into());
+            }
+            println!("{}", preview::url(&config, &secret, post));
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
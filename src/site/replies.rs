@@ -0,0 +1,55 @@
+//! `site replies`: the replies sent to contact submissions from the site (see [`replies`]).
+//!
+//! - `list [ID]` shows the replies sent, or those to the message with the id, oldest first
+//! - `link <ID>` prints the reply page of a message, for one that came in before replies were turned on
+
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::messages;
+use dioxus_site::ssg::replies;
+use std::path::Path;
+
+const USAGE: &str = "usage: site replies <list [ID] | link ID>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?.contact;
+
+    match args {
+        ["list", rest @ ..] if rest.len() <= 1 => {
+            let sent: Vec<replies::Reply> = replies::load(Path::new(&config.replies.log))?
+                .into_iter()
+                .filter(|reply| rest.first().is_none_or(|id| reply.message_id == *id))
+                .collect();
+            if sent.is_empty() {
+                println!("📭 No replies");
+                return Ok(());
+            }
+            for reply in &sent {
+                println!(
+                    "↩️  {} to {}:",
+                    reply.sent_at.format("%Y-%m-%d %H:%M"),
+                    reply.message_id
+                );
+                for line in reply.text.lines() {
+                    println!("   {}", line);
+                }
+            }
+            println!("✅ {} reply(ies)", sent.len());
+            Ok(())
+        }
+        ["link", id] => {
+            let secret = replies::secret(&config.replies).ok_or_else(|| {
+                format!(
+                    "replies are off, or ${} isn't set",
+                    config.replies.secret_env
+                )
+            })?;
+            let stored = messages::load(Path::new(&config.store.submissions))?;
+            if !stored.iter().any(|message| message.id() == *id) {
+                return Err(format!("no message has the id `{}`", id).into());
+            }
+            println!("{}{}/", config.replies.url, replies::token(&secret, id));
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
@@ -0,0 +1,22 @@
+//! `site serve`: run the backend the static output is deployed with (see [`server`]), on `[server] address` or
+//! `--address HOST:PORT`.
+
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::server;
+use std::net::SocketAddr;
+use std::path::Path;
+
+const USAGE: &str = "usage: site serve [--address HOST:PORT]";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?;
+    let address: SocketAddr = match args {
+        [] => config.server.address.parse()?,
+        ["--address", address] => address.parse().map_err(|_| USAGE)?,
+        _ => return Err(USAGE.into()),
+    };
+
+    println!("🚀 Serving the contact backend on http://{}", address);
+    tokio::runtime::Runtime::new()?.block_on(server::serve(config, address))?;
+    Ok(())
+}
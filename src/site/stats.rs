@@ -0,0 +1,64 @@
+//! `site stats`: the page views `site serve` counted (see [`analytics`]), as the dashboard at `/admin/stats` shows
+//! them. `--days N` covers the last N days (default 30).
+
+use chrono::Utc;
+use dioxus_site::ssg::analytics;
+use dioxus_site::ssg::config::SiteConfig;
+use std::path::Path;
+
+const USAGE: &str = "usage: site stats [--days N]";
+
+/// Width of the longest bar of the daily chart, in characters.
+const BAR_WIDTH: u64 = 40;
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let days = match args {
+        [] => 30,
+        ["--days", days] => days
+            .parse::<u32>()
+            .ok()
+            .filter(|&days| days > 0)
+            .ok_or(USAGE)?,
+        _ => return Err(USAGE.into()),
+    };
+
+    let config = SiteConfig::load(Path::new("site.toml"))?.analytics;
+    let path = Path::new(&config.database);
+    if !path.exists() {
+        println!("✅ No page views counted yet");
+        return Ok(());
+    }
+    let stats = analytics::Store::open(path)?.stats(Utc::now().date_naive(), days)?;
+
+    println!("📈 {} page views in the last {} days", stats.total, days);
+    let busiest = stats
+        .per_day
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for (day, count) in &stats.per_day {
+        let bar = "█".repeat(usize::try_from(count * BAR_WIDTH / busiest).unwrap_or(0));
+        println!("  {} {:>6} {}", day, count, bar);
+    }
+
+    for (title, rows, empty) in [
+        ("Pages", &stats.pages, "(none)"),
+        ("Referrers", &stats.referrers, "direct or this site"),
+        ("Countries", &stats.countries, "unknown"),
+    ] {
+        println!("\n{}", title);
+        for (label, count) in rows {
+            println!(
+                "  {:>6}  {}",
+                count,
+                if label.is_empty() { empty } else { label }
+            );
+        }
+    }
+    if !config.enabled {
+        println!("\n⚠️ [analytics] is off, so no new page views are counted");
+    }
+    Ok(())
+}
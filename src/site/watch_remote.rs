@@ -0,0 +1,82 @@
+//! `site watch-remote`: poll the content for changes and publish them (see [`watch`]).
+//!
+//! Every `[watch] interval_minutes` (or `--interval MINUTES`) it pulls the branch, and when the content differs from
+//! the last deploy runs `[watch] build` and `deploy`. A failed pull, build or deploy is reported and tried again on
+//! the next poll. `--once` polls a single time, for cron; it fails when the build or deploy does.
+
+use dioxus_site::posts;
+use dioxus_site::ssg::config::{SiteConfig, WatchConfig};
+use dioxus_site::ssg::watch::{self, State};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+
+const USAGE: &str = "usage: site watch-remote [--once] [--interval MINUTES]";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut config = SiteConfig::load(Path::new("site.toml"))?.watch;
+    let mut once = false;
+    let mut rest = args;
+    while !rest.is_empty() {
+        rest = match rest {
+            ["--once", rest @ ..] => {
+                once = true;
+                rest
+            }
+            ["--interval", minutes, rest @ ..] => {
+                config.interval_minutes = minutes.parse().ok().filter(|&m| m > 0).ok_or(USAGE)?;
+                rest
+            }
+            _ => return Err(USAGE.into()),
+        };
+    }
+
+    if once {
+        return poll(&config).map_err(Into::into);
+    }
+
+    println!(
+        "👀 Polling {} every {} minute(s)",
+        config.paths.join(", "),
+        config.interval_minutes
+    );
+    loop {
+        if let Err(e) = poll(&config) {
+            println!("❌ {}", e);
+        }
+        thread::sleep(Duration::from_secs(config.interval_minutes * 60));
+    }
+}
+
+/// Pull, and build and deploy when the content changed.
+fn poll(config: &WatchConfig) -> Result<(), String> {
+    watch::pull(config)?;
+    let commit = watch::head()?;
+    let fingerprint = watch::fingerprint(config, posts::today())?;
+
+    let state_path = Path::new(&config.state);
+    let last = State::load(state_path)?;
+    if last
+        .as_ref()
+        .is_some_and(|last| last.fingerprint == fingerprint)
+    {
+        println!("✅ {}: nothing changed since the last deploy", commit);
+        return Ok(());
+    }
+
+    println!("🔨 {}: content changed, building", commit);
+    watch::run(&config.build)?;
+    if !config.deploy.is_empty() {
+        println!("🚀 Deploying");
+        watch::run(&config.deploy)?;
+    }
+
+    State {
+        fingerprint,
+        commit: commit.clone(),
+        published_at: chrono::Utc::now(),
+    }
+    .store(state_path)?;
+    println!("✅ {}: published", commit);
+    Ok(())
+}
@@ -0,0 +1,83 @@
+//! `site webhooks`: the contact submissions the webhook in `[contact.webhook]` didn't take, and a test delivery.
+//!
+//! - `list` shows the dead letters, oldest first
+//! - `retry` sends them again, keeping the ones that still fail
+//! - `test` delivers a sample submission, so a new webhook can be tried out without filling in the form
+
+use chrono::Utc;
+use dioxus_site::contact_reasons::DEFAULT_REASON;
+use dioxus_site::ssg::config::SiteConfig;
+use dioxus_site::ssg::email;
+use dioxus_site::ssg::webhook::{self, Delivery};
+use std::path::Path;
+
+const USAGE: &str = "usage: site webhooks <list|retry|test>";
+
+pub fn run(args: &[&str]) -> Result<(), Box<dyn std::error::Error>> {
+    let config = SiteConfig::load(Path::new("site.toml"))?.contact.webhook;
+
+    match args {
+        ["list"] => {
+            let letters = webhook::dead_letters(Path::new(&config.dead_letters))?;
+            if letters.is_empty() {
+                println!("✅ No undelivered submissions");
+                return Ok(());
+            }
+            for letter in &letters {
+                println!(
+                    "📭 {} from {} <{}>: \"{}\"",
+                    letter.received_at.format("%Y-%m-%d %H:%M"),
+                    letter.request.name.trim(),
+                    letter.request.email.trim(),
+                    letter.request.subject.trim()
+                );
+                println!(
+                    "   {} attempts, last at {}: {}",
+                    letter.attempts,
+                    letter.failed_at.format("%Y-%m-%d %H:%M"),
+                    letter.error
+                );
+            }
+            println!(
+                "📂 {} undelivered submission(s) in {}",
+                letters.len(),
+                config.dead_letters
+            );
+            Ok(())
+        }
+        ["retry"] => {
+            let deliveries = webhook::retry_dead_letters(&config)?;
+            let failed = deliveries
+                .iter()
+                .filter(|delivery| matches!(delivery, Delivery::DeadLettered(_)))
+                .count();
+            println!(
+                "📬 Delivered {} of {} submission(s)",
+                deliveries.len() - failed,
+                deliveries.len()
+            );
+            if failed > 0 {
+                return Err(format!("{} submission(s) still undelivered", failed).into());
+            }
+            Ok(())
+        }
+        ["test"] => {
+            if !config.is_enabled() {
+                return Err("[contact.webhook] has no url".into());
+            }
+            println!("📤 Sending a sample submission to {}...", config.url);
+            match webhook::deliver(&config, &email::sample_request(DEFAULT_REASON), Utc::now())? {
+                Delivery::Delivered { attempts } => {
+                    println!("✅ Delivered after {} attempt(s)", attempts);
+                    Ok(())
+                }
+                Delivery::DeadLettered(letter) => Err(format!(
+                    "not delivered after {} attempt(s): {} (recorded in {})",
+                    letter.attempts, letter.error, config.dead_letters
+                )
+                .into()),
+            }
+        }
+        _ => Err(USAGE.into()),
+    }
+}
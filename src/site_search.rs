@@ -0,0 +1,112 @@
+//! Full-text search over the published posts and the projects, for the search box on the `/search/` page and the
+//! site's OpenSearch description (which sends browser searches to `/search/?q=<terms>`).
+//!
+//! Everything searched is compiled in, so the island answers without fetching anything. A post or project matches when
+//! every word of the query appears in it, in any case; posts with all the words in the title come first, then the
+//! newest.
+
+use crate::data::{Project, PROJECTS};
+use crate::excerpt;
+use crate::links;
+use crate::posts::{self, Post};
+use crate::Route;
+use chrono::NaiveDate;
+
+/// Name of the query parameter holding the search terms.
+pub const QUERY_PARAM: &str = "q";
+
+/// The search page of the generated site, which lists every result and the keyword pages.
+pub const SEARCH_PATH: &str = "/search/";
+
+/// What a query found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchResults {
+    pub posts: Vec<Post>,
+    pub projects: Vec<&'static Project>,
+}
+
+impl SearchResults {
+    pub fn len(&self) -> usize {
+        self.posts.len() + self.projects.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The first `limit` results, posts before projects, as links for a short list such as the navbar's.
+    pub fn suggestions(&self, limit: usize) -> Vec<Suggestion> {
+        let posts = self.posts.iter().map(|post| Suggestion {
+            title: post.title.to_string(),
+            detail: post.date_label(),
+            href: links::link_to(Route::Blog {
+                slug: post.slug.to_string(),
+            }),
+        });
+        let projects = self.projects.iter().map(|project| Suggestion {
+            title: project.name.to_string(),
+            detail: "Project".to_string(),
+            href: project.url.to_string(),
+        });
+        posts.chain(projects).take(limit).collect()
+    }
+}
+
+/// A search result as a single link.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    pub title: String,
+    /// The post's date, or what kind of result it is
+    pub detail: String,
+    pub href: String,
+}
+
+/// The posts published as of `today` and the projects matching `query`. An empty query matches nothing.
+pub fn search(query: &str, today: NaiveDate) -> SearchResults {
+    let terms = terms(query);
+    if terms.is_empty() {
+        return SearchResults::default();
+    }
+    let matches = |text: &str| {
+        let text = text.to_lowercase();
+        terms.iter().all(|term| text.contains(term.as_str()))
+    };
+
+    let mut found: Vec<(bool, Post)> = posts::published(today)
+        .into_iter()
+        .filter(|post| {
+            matches(&format!(
+                "{} {} {}",
+                post.title,
+                post.summary.unwrap_or_default(),
+                excerpt::plain_text(&post.body_html())
+            ))
+        })
+        .map(|post| (matches(post.title), post))
+        .collect();
+    found.sort_by(|(a_title, a), (b_title, b)| {
+        b_title.cmp(a_title).then(b.published.cmp(&a.published))
+    });
+
+    let projects = PROJECTS
+        .iter()
+        .filter(|project| {
+            matches(&format!(
+                "{} {} {}",
+                project.name,
+                project.description,
+                project.tags.join(" ")
+            ))
+        })
+        .collect();
+
+    SearchResults {
+        posts: found.into_iter().map(|(_, post)| post).collect(),
+        projects,
+    }
+}
+
+/// The lowercased words of `query`.
+fn terms(query: &str) -> Vec<String> {
+    query.split_whitespace().map(str::to_lowercase).collect()
+}
@@ -0,0 +1,101 @@
+//! Where the site's source lives, for "suggest an edit" links on blog posts and the "propose a post" page, and whether
+//! posts are published as markdown for their "view source" links.
+//!
+//! The `[source]` section of `site.toml` is compiled in, like the theme, so the SPA links to the same repository and
+//! branch as the generated pages.
+
+use crate::contact_prefill;
+use serde::Deserialize;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SourceConfig {
+    /// GitHub repository as `owner/name`; empty to leave the edit links out
+    pub repository: String,
+    pub branch: String,
+    /// Publish each post's markdown at `<post>/index.md`, linked as "View source" under the post
+    pub markdown: bool,
+    /// Directory, relative to the repository root, that proposed posts are opened in; empty to leave out the
+    /// "propose a post" page
+    pub proposals: String,
+}
+
+impl Default for SourceConfig {
+    fn default() -> Self {
+        Self {
+            repository: "marcinsydor/dioxus_site".to_string(),
+            branch: "main".to_string(),
+            markdown: false,
+            proposals: "content/proposals".to_string(),
+        }
+    }
+}
+
+impl SourceConfig {
+    /// The `[source]` section of the compiled-in `site.toml`, or the defaults if it can't be read.
+    pub fn compiled() -> &'static SourceConfig {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct SiteToml {
+            source: SourceConfig,
+        }
+
+        static SOURCE: OnceLock<SourceConfig> = OnceLock::new();
+        SOURCE.get_or_init(|| {
+            toml::from_str::<SiteToml>(SITE_TOML)
+                .map(|site| site.source)
+                .unwrap_or_default()
+        })
+    }
+
+    /// GitHub's editor for `path` (relative to the repository root) on the configured branch.
+    pub fn edit_url(&self, path: &str) -> Option<String> {
+        if self.repository.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "https://github.com/{}/edit/{}/{}",
+            self.repository, self.branch, path
+        ))
+    }
+
+    /// GitHub's editor for a new file `filename` in `dir` (relative to the repository root) on the configured branch,
+    /// filled in with `value`. Readers without write access are offered a fork, and the commit becomes a pull request.
+    pub fn new_file_url(&self, dir: &str, filename: &str, value: &str) -> Option<String> {
+        if self.repository.is_empty() {
+            return None;
+        }
+        Some(format!(
+            "https://github.com/{}/new/{}/{}?filename={}&value={}",
+            self.repository,
+            self.branch,
+            dir.trim_matches('/'),
+            contact_prefill::encode(filename),
+            contact_prefill::encode(value)
+        ))
+    }
+
+    /// Problems with the section, for `SiteConfig::load`.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.repository.is_empty() && self.repository.split('/').count() != 2 {
+            problems.push(format!(
+                "[source] repository `{}` must be `owner/name`",
+                self.repository
+            ));
+        }
+        if !self.repository.is_empty() && self.branch.trim().is_empty() {
+            problems.push("[source] branch must not be empty".to_string());
+        }
+        if self.proposals.starts_with('/') || self.proposals.split('/').any(|part| part == "..") {
+            problems.push(format!(
+                "[source] proposals `{}` must be a directory inside the repository",
+                self.proposals
+            ));
+        }
+        problems
+    }
+}
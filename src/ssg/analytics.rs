@@ -0,0 +1,433 @@
+//! First-party analytics, without a third-party service: with `[analytics] enabled`, every generated page sends a
+//! beacon to [`HIT_ENDPOINT`] (see [`script`]), and `site serve` keeps each hit in the SQLite database in
+//! `[analytics] database`. `site serve` shows them on the password-protected dashboard at [`STATS_PATH`], with daily
+//! charts, and `site stats` prints the same summary.
+//!
+//! A hit is the page's path, the host of the referrer (empty for links within the site and for direct visits), and the
+//! visitor's country. The country comes from the header a CDN in front sets (`[analytics] country_header`), or else a
+//! lookup of the client's address in a MaxMind country database (`[analytics] geoip_database`). Neither the address
+//! nor the user agent is stored, no cookie is set, and browsers sending Do Not Track or Global Privacy Control don't
+//! send hits at all.
+
+use crate::ssg::config::AnalyticsConfig;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use rusqlite::{params, Connection};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use std::net::IpAddr;
+use std::path::Path;
+
+/// Where the pages send hits.
+pub const HIT_ENDPOINT: &str = "/api/hit";
+
+/// Where `site serve` shows the dashboard.
+pub const STATS_PATH: &str = "/admin/stats";
+
+/// Longest page path kept, in characters; anything longer isn't a page of the site.
+const MAX_PATH_LENGTH: usize = 300;
+
+/// Rows in each of the dashboard's top lists.
+const TOP_ROWS: usize = 10;
+
+/// A hit as the beacon sends it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct Hit {
+    /// Path of the page, from `location.pathname`
+    pub page: String,
+    /// `document.referrer`: the page linking here, or empty
+    #[serde(default)]
+    pub referrer: String,
+}
+
+impl Hit {
+    /// The hit as it's stored: the path without query or fragment, and the referrer reduced to its host, empty when
+    /// it's `site_host`. `None` for a path that isn't one, which no page of the site would send.
+    pub fn scrubbed(&self, site_host: &str) -> Option<Self> {
+        let page = self.page.split(['?', '#']).next().unwrap_or_default();
+        if !page.starts_with('/') || page.chars().count() > MAX_PATH_LENGTH {
+            return None;
+        }
+        let referrer = host(&self.referrer).unwrap_or_default();
+        Some(Self {
+            page: page.to_string(),
+            referrer: if referrer.eq_ignore_ascii_case(site_host) {
+                String::new()
+            } else {
+                referrer.to_lowercase()
+            },
+        })
+    }
+}
+
+/// Host of an absolute `http(s)` URL.
+pub fn host(url: &str) -> Option<&str> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = host.split(':').next()?;
+    (!host.is_empty()).then_some(host)
+}
+
+/// Whether the user agent is a crawler or a script rather than a reader, so its hits aren't counted.
+pub fn is_bot(user_agent: &str) -> bool {
+    let user_agent = user_agent.to_ascii_lowercase();
+    user_agent.is_empty()
+        || [
+            "bot", "crawl", "spider", "headless", "curl", "wget", "python",
+        ]
+        .iter()
+        .any(|marker| user_agent.contains(marker))
+}
+
+/// The `<script>` the generator puts in the head of every page while analytics is on.
+pub fn script() -> String {
+    format!(
+        r#"<script>if (navigator.doNotTrack !== "1" && !navigator.globalPrivacyControl && navigator.sendBeacon) navigator.sendBeacon("{}", JSON.stringify({{ page: location.pathname, referrer: document.referrer }}));</script>"#,
+        HIT_ENDPOINT
+    )
+}
+
+/// Countries of client addresses, from a MaxMind country database.
+pub struct Geoip {
+    reader: Option<maxminddb::Reader<Vec<u8>>>,
+}
+
+impl Geoip {
+    /// The database in `[analytics] geoip_database`, or none when it's empty.
+    pub fn open(config: &AnalyticsConfig) -> Result<Self, String> {
+        if config.geoip_database.is_empty() {
+            return Ok(Self { reader: None });
+        }
+        maxminddb::Reader::open_readfile(&config.geoip_database)
+            .map(|reader| Self {
+                reader: Some(reader),
+            })
+            .map_err(|e| format!("{}: {}", config.geoip_database, e))
+    }
+
+    /// ISO code of the country `ip` is in, or empty when it isn't known.
+    pub fn country(&self, ip: IpAddr) -> String {
+        self.reader
+            .as_ref()
+            .and_then(|reader| reader.lookup(ip).ok())
+            .and_then(|found| found.decode::<maxminddb::geoip2::Country>().ok().flatten())
+            .and_then(|found| found.country.iso_code)
+            .unwrap_or_default()
+            .to_string()
+    }
+}
+
+/// The hits database.
+pub struct Store {
+    connection: Connection,
+}
+
+impl Store {
+    /// Open the database at `path`, creating it and its table when needed.
+    pub fn open(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let connection = Connection::open(path)?;
+        connection.busy_timeout(std::time::Duration::from_secs(5))?;
+        connection.execute_batch(
+            "CREATE TABLE IF NOT EXISTS hits (
+                at TEXT NOT NULL,
+                day TEXT NOT NULL,
+                page TEXT NOT NULL,
+                referrer TEXT NOT NULL,
+                country TEXT NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS hits_day ON hits (day);",
+        )?;
+        Ok(Self { connection })
+    }
+
+    /// Keep a [scrubbed](Hit::scrubbed) hit from `country` at `at`.
+    pub fn record(
+        &self,
+        hit: &Hit,
+        country: &str,
+        at: DateTime<Utc>,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        self.connection.execute(
+            "INSERT INTO hits (at, day, page, referrer, country) VALUES (?1, ?2, ?3, ?4, ?5)",
+            params![
+                at.to_rfc3339(),
+                at.date_naive().to_string(),
+                hit.page,
+                hit.referrer,
+                country
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// The hits of the `days` days up to and including `today`.
+    pub fn stats(&self, today: NaiveDate, days: u32) -> Result<Stats, Box<dyn std::error::Error>> {
+        let first = today - Duration::days(i64::from(days.max(1)) - 1);
+        let since = first.to_string();
+
+        let mut per_day: Vec<(NaiveDate, u64)> = first
+            .iter_days()
+            .take_while(|day| *day <= today)
+            .map(|day| (day, 0))
+            .collect();
+        for (day, count) in self.counts("day", &since, usize::MAX)? {
+            if let Some(slot) = per_day.iter_mut().find(|(slot, _)| slot.to_string() == day) {
+                slot.1 = count;
+            }
+        }
+
+        Ok(Stats {
+            total: per_day.iter().map(|(_, count)| count).sum(),
+            per_day,
+            pages: self.counts("page", &since, TOP_ROWS)?,
+            referrers: self.counts("referrer", &since, TOP_ROWS)?,
+            countries: self.counts("country", &since, TOP_ROWS)?,
+        })
+    }
+
+    /// Hits since the day `since` by each value of `column`, most first, at most `limit` of them.
+    fn counts(
+        &self,
+        column: &str,
+        since: &str,
+        limit: usize,
+    ) -> Result<Vec<(String, u64)>, Box<dyn std::error::Error>> {
+        // `column` is one of the names above, never input
+        let mut statement = self.connection.prepare(&format!(
+            "SELECT {column}, COUNT(*) AS hits FROM hits WHERE day >= ?1 GROUP BY {column} ORDER BY hits DESC, {column} LIMIT ?2"
+        ))?;
+        let limit = i64::try_from(limit).unwrap_or(i64::MAX);
+        let rows = statement.query_map(params![since, limit], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)? as u64))
+        })?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+}
+
+/// Hits over a range of days.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Stats {
+    pub total: u64,
+    /// Every day of the range, oldest first, including those without hits
+    pub per_day: Vec<(NaiveDate, u64)>,
+    /// The most visited pages
+    pub pages: Vec<(String, u64)>,
+    /// The hosts sending the most visitors; empty is within the site or direct
+    pub referrers: Vec<(String, u64)>,
+    /// The countries with the most hits by ISO code; empty is unknown
+    pub countries: Vec<(String, u64)>,
+}
+
+/// The password of the dashboard, from `[analytics] password_env`. With the variable unset there's no dashboard.
+pub fn password(config: &AnalyticsConfig) -> Option<String> {
+    std::env::var(&config.password_env)
+        .ok()
+        .filter(|password| !password.is_empty())
+}
+
+/// Whether the `Authorization` header of a dashboard request has `password`, with any user name.
+pub fn authorized(password: &str, authorization: Option<&str>) -> bool {
+    use base64::Engine;
+
+    let given = authorization
+        .and_then(|value| value.strip_prefix("Basic "))
+        .and_then(|encoded| {
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .ok()
+        })
+        .and_then(|decoded| String::from_utf8(decoded).ok())
+        .and_then(|credentials| {
+            credentials
+                .split_once(':')
+                .map(|(_, password)| password.to_string())
+        });
+    // Compared by digest, so how long the check takes says nothing about the password
+    given.is_some_and(|given| Sha256::digest(given) == Sha256::digest(password))
+}
+
+/// The dashboard page for `stats` over `days` days.
+pub fn dashboard(stats: &Stats, days: u32) -> String {
+    let max = stats
+        .per_day
+        .iter()
+        .map(|(_, count)| *count)
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let bar_width = 100.0 / stats.per_day.len().max(1) as f64;
+    let bars: String = stats
+        .per_day
+        .iter()
+        .enumerate()
+        .map(|(index, (day, count))| {
+            let height = *count as f64 / max as f64 * 100.0;
+            format!(
+                r#"            <rect x="{x:.2}%" y="{y:.2}%" width="{width:.2}%" height="{height:.2}%"><title>{day}: {count}</title></rect>
+"#,
+                x = index as f64 * bar_width,
+                y = 100.0 - height,
+                width = bar_width * 0.8,
+            )
+        })
+        .collect();
+    let first = stats
+        .per_day
+        .first()
+        .map_or(String::new(), |(day, _)| day.to_string());
+    let last = stats
+        .per_day
+        .last()
+        .map_or(String::new(), |(day, _)| day.to_string());
+
+    let ranges: String = [7, 30, 90, 365]
+        .iter()
+        .map(|range| {
+            if *range == days {
+                format!("<strong>{} days</strong>", range)
+            } else {
+                format!(r#"<a href="?days={range}">{range} days</a>"#)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" · ");
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <meta name="robots" content="noindex">
+    <title>Stats</title>
+    <style>
+        body {{ max-width: 60rem; margin: 2rem auto; padding: 0 1rem; font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; color: #1f2937; }}
+        svg {{ width: 100%; height: 12rem; background: #f4f4f5; border-radius: 8px; }}
+        rect {{ fill: #2563eb; }}
+        .axis {{ display: flex; justify-content: space-between; color: #6b7280; font-size: 0.875rem; }}
+        .tables {{ display: grid; grid-template-columns: repeat(auto-fit, minmax(16rem, 1fr)); gap: 1.5rem; }}
+        table {{ width: 100%; border-collapse: collapse; }}
+        td {{ padding: 0.25rem 0; border-bottom: 1px solid #e5e7eb; }}
+        td:last-child {{ text-align: right; }}
+    </style>
+</head>
+<body>
+    <h1>{total} page views</h1>
+    <p>{ranges}</p>
+    <svg viewBox="0 0 100 100" preserveAspectRatio="none" role="img" aria-label="Page views per day">
+{bars}    </svg>
+    <div class="axis"><span>{first}</span><span>busiest day: {max}</span><span>{last}</span></div>
+    <div class="tables">
+{pages}{referrers}{countries}    </div>
+</body>
+</html>
+"#,
+        total = stats.total,
+        max = if stats.total == 0 { 0 } else { max },
+        pages = top_table("Pages", &stats.pages, "(none)"),
+        referrers = top_table("Referrers", &stats.referrers, "direct or this site"),
+        countries = top_table("Countries", &stats.countries, "unknown"),
+    )
+}
+
+fn top_table(title: &str, rows: &[(String, u64)], empty_label: &str) -> String {
+    let rows: String = rows
+        .iter()
+        .map(|(label, count)| {
+            let label = if label.is_empty() { empty_label } else { label };
+            format!(
+                "                <tr><td>{}</td><td>{}</td></tr>\n",
+                crate::ssg::html::escape(label),
+                count
+            )
+        })
+        .collect();
+    format!(
+        "        <section>\n            <h2>{}</h2>\n            <table>\n{}            </table>\n        </section>\n",
+        title, rows
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hits_keep_the_path_and_the_referrer_host() {
+        let hit = Hit {
+            page: "/blog/islands/?utm_source=feed#comments".to_string(),
+            referrer: "https://News.example.com:443/item?id=1".to_string(),
+        };
+        assert_eq!(
+            hit.scrubbed("sydor.co"),
+            Some(Hit {
+                page: "/blog/islands/".to_string(),
+                referrer: "news.example.com".to_string(),
+            })
+        );
+
+        let internal = Hit {
+            page: "/about/".to_string(),
+            referrer: "https://sydor.co/".to_string(),
+        };
+        assert_eq!(internal.scrubbed("sydor.co").unwrap().referrer, "");
+
+        let not_a_path = Hit {
+            page: "https://elsewhere.example/".to_string(),
+            referrer: String::new(),
+        };
+        assert_eq!(not_a_path.scrubbed("sydor.co"), None);
+    }
+
+    #[test]
+    fn the_dashboard_takes_the_password_with_any_user() {
+        assert!(authorized("hunter2", Some("Basic YWRtaW46aHVudGVyMg==")));
+        assert!(!authorized("hunter2", Some("Basic YWRtaW46aHVudGVyMw==")));
+        assert!(!authorized("hunter2", None));
+    }
+
+    #[test]
+    fn stats_count_every_day_of_the_range() {
+        let store = Store {
+            connection: Connection::open_in_memory().unwrap(),
+        };
+        store
+            .connection
+            .execute_batch(
+                "CREATE TABLE hits (at TEXT, day TEXT, page TEXT, referrer TEXT, country TEXT)",
+            )
+            .unwrap();
+        let hit = |page: &str| Hit {
+            page: page.to_string(),
+            referrer: String::new(),
+        };
+        let at = |at: &str| at.parse::<DateTime<Utc>>().unwrap();
+        store
+            .record(&hit("/"), "PL", at("2026-10-13T08:00:00Z"))
+            .unwrap();
+        store
+            .record(&hit("/about/"), "GB", at("2026-10-15T08:00:00Z"))
+            .unwrap();
+        store
+            .record(&hit("/"), "PL", at("2026-10-15T09:00:00Z"))
+            .unwrap();
+        // Outside the range
+        store
+            .record(&hit("/"), "PL", at("2026-10-01T09:00:00Z"))
+            .unwrap();
+
+        let stats = store
+            .stats(NaiveDate::from_ymd_opt(2026, 10, 15).unwrap(), 3)
+            .unwrap();
+        assert_eq!(stats.total, 3);
+        let per_day: Vec<u64> = stats.per_day.iter().map(|(_, count)| *count).collect();
+        assert_eq!(per_day, [1, 0, 2]);
+        assert_eq!(stats.pages[0], ("/".to_string(), 2));
+        assert_eq!(stats.countries[0], ("PL".to_string(), 2));
+    }
+}
@@ -0,0 +1,352 @@
+//! Copying `assets/` into the output according to the `[assets]` rules in `site.toml`.
+//!
+//! Copying happens in two steps: [`plan`] decides what happens to every file, and [`copy`] carries the plan out. The
+//! generator's `--dry-run-assets` flag prints the plan without touching the output directory.
+//!
+//! Images matching `[responsive_images] include` are also written at the smaller widths their `srcset` lists, next
+//! to the original (see [`responsive_images`](crate::responsive_images)).
+
+use crate::responsive_images::{self, ResponsiveImagesConfig};
+use crate::ssg::config::{AssetTransform, AssetsConfig};
+use crate::ssg::image_metadata;
+use crate::styles;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::png::{CompressionType, FilterType as PngFilter, PngEncoder};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageDecoder, ImageFormat, ImageReader};
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+/// What happens to one file under the assets directory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlannedAsset {
+    /// Path relative to the assets directory, with `/` separators
+    pub path: String,
+    /// `None` when an exclude rule (or a missing include) skips the file
+    pub transform: Option<AssetTransform>,
+    /// Widths smaller copies are written at, for a responsive image
+    pub widths: Vec<u32>,
+}
+
+/// Extensions of the images smaller copies can be made of.
+const RESIZABLE_EXTENSIONS: &[&str] = &["jpg", "jpeg", "png"];
+
+/// Quality the smaller copies of JPEGs are encoded at, out of 100.
+const JPEG_QUALITY: u8 = 85;
+
+/// Walk `src` and decide for every file whether it's copied, and with which transformation. Images are stripped of
+/// their metadata unless `[assets.transform]` says otherwise for their extension or they match `keep_metadata`, and
+/// the responsive ones get the widths below their own.
+pub fn plan(
+    src: &Path,
+    config: &AssetsConfig,
+    responsive: &ResponsiveImagesConfig,
+) -> Result<Vec<PlannedAsset>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    collect_files(src, src, &mut files)?;
+    files.sort();
+
+    files
+        .into_iter()
+        .map(|path| {
+            let included = config
+                .include
+                .iter()
+                .any(|pattern| glob_match(pattern, &path))
+                && !config
+                    .exclude
+                    .iter()
+                    .any(|pattern| glob_match(pattern, &path));
+            let extension = Path::new(&path)
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            let transform = included.then(|| {
+                let transform = config.transform.get(&extension).copied().unwrap_or(
+                    if image_metadata::EXTENSIONS.contains(&extension.as_str()) {
+                        AssetTransform::Strip
+                    } else {
+                        AssetTransform::Copy
+                    },
+                );
+                let keep_metadata = config
+                    .keep_metadata
+                    .iter()
+                    .any(|pattern| glob_match(pattern, &path));
+                if transform == AssetTransform::Strip && keep_metadata {
+                    AssetTransform::Copy
+                } else {
+                    transform
+                }
+            });
+
+            let resizable = included
+                && RESIZABLE_EXTENSIONS.contains(&extension.as_str())
+                && responsive
+                    .include
+                    .iter()
+                    .any(|pattern| glob_match(pattern, &path));
+            let widths = if resizable {
+                let (width, _) = image::image_dimensions(src.join(&path))
+                    .map_err(|e| format!("assets/{}: {}", path, e))?;
+                responsive.widths_below(width)
+            } else {
+                Vec::new()
+            };
+
+            Ok(PlannedAsset {
+                path,
+                transform,
+                widths,
+            })
+        })
+        .collect()
+}
+
+fn collect_files(
+    dir: &Path,
+    root: &Path,
+    files: &mut Vec<String>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_files(&path, root, files)?;
+        } else {
+            let relative: PathBuf = path.strip_prefix(root)?.into();
+            let segments: Vec<_> = relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy())
+                .collect();
+            files.push(segments.join("/"));
+        }
+    }
+    Ok(())
+}
+
+/// Copy the planned files from `src` to `dest`, applying their transformations. Skipped files are left out, and the
+/// stylesheets in [`styles`] are written with their rules scoped.
+pub fn copy(
+    src: &Path,
+    dest: &Path,
+    plan: &[PlannedAsset],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for asset in plan {
+        let Some(transform) = asset.transform else {
+            continue;
+        };
+
+        let from = src.join(&asset.path);
+        let to = dest.join(&asset.path);
+        if let Some(parent) = to.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // The page stylesheets go out scoped, as the SPA has them
+        let scoped = styles::ALL
+            .iter()
+            .find(|style| asset.path == format!("styling/{}.css", style.name));
+
+        match (transform, scoped) {
+            (AssetTransform::Copy, Some(style)) => {
+                fs::write(&to, style.css)?;
+            }
+            (AssetTransform::Copy, None) => {
+                fs::copy(&from, &to)?;
+            }
+            (AssetTransform::Minify, scoped) => {
+                let source = match scoped {
+                    Some(style) => style.css.to_string(),
+                    None => fs::read_to_string(&from)?,
+                };
+                let minified = match from.extension().and_then(|extension| extension.to_str()) {
+                    Some("css") => minify_css(&source),
+                    Some("json") => {
+                        minify_json(&source).map_err(|e| format!("{}: {}", from.display(), e))?
+                    }
+                    _ => source,
+                };
+                fs::write(&to, minified)?;
+            }
+            (AssetTransform::Strip, _) => {
+                let extension = from
+                    .extension()
+                    .and_then(|extension| extension.to_str())
+                    .unwrap_or_default();
+                let stripped = image_metadata::strip(&fs::read(&from)?, extension)
+                    .map_err(|e| format!("{}: {}", from.display(), e))?;
+                fs::write(&to, stripped)?;
+            }
+        }
+
+        if !asset.widths.is_empty() {
+            write_variants(&from, &to, &asset.widths)?;
+        }
+    }
+    Ok(())
+}
+
+/// Write the copies of the image at `from` at each of `widths`, next to `to`, keeping its aspect ratio.
+fn write_variants(
+    from: &Path,
+    to: &Path,
+    widths: &[u32],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let format = ImageFormat::from_path(from).map_err(|e| format!("{}: {}", from.display(), e))?;
+    let image =
+        decode_upright(&fs::read(from)?).map_err(|e| format!("{}: {}", from.display(), e))?;
+    let to = to.to_string_lossy();
+
+    for &width in widths {
+        let height =
+            (u64::from(image.height()) * u64::from(width) / u64::from(image.width())).max(1) as u32;
+        let resized = image.resize_exact(width, height, FilterType::Lanczos3);
+        let mut encoded = Vec::new();
+        let written = match format {
+            // JPEG has no alpha channel to write
+            ImageFormat::Jpeg => image::DynamicImage::ImageRgb8(resized.to_rgb8())
+                .write_with_encoder(JpegEncoder::new_with_quality(&mut encoded, JPEG_QUALITY)),
+            // The encoder's default favours speed, which can leave a copy larger than the original
+            _ => resized.write_with_encoder(PngEncoder::new_with_quality(
+                &mut encoded,
+                CompressionType::Best,
+                PngFilter::Adaptive,
+            )),
+        };
+        written.map_err(|e| format!("{}: {}", from.display(), e))?;
+        fs::write(responsive_images::variant_path(&to, width), encoded)?;
+    }
+    Ok(())
+}
+
+/// `image` decoded and turned the way its EXIF orientation says, as browsers show it. The copies are written without
+/// EXIF, so they have to be the right way up as stored.
+fn decode_upright(image: &[u8]) -> image::ImageResult<DynamicImage> {
+    let mut decoder = ImageReader::new(Cursor::new(image))
+        .with_guessed_format()?
+        .into_decoder()?;
+    let orientation = decoder.orientation()?;
+    let mut image = DynamicImage::from_decoder(decoder)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+/// Whether `path` (relative, `/`-separated) matches the glob `pattern`.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<&str> = pattern.split('/').collect();
+    let path: Vec<&str> = path.split('/').collect();
+    match_segments(&pattern, &path)
+}
+
+fn match_segments(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        // `**` swallows zero or more whole segments
+        Some((&"**", rest)) => (0..=path.len()).any(|skip| match_segments(rest, &path[skip..])),
+        Some((segment, rest)) => match path.split_first() {
+            Some((name, path_rest)) => {
+                match_segment(segment.as_bytes(), name.as_bytes())
+                    && match_segments(rest, path_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+fn match_segment(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_segment(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_segment(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_segment(rest, &name[1..]),
+    }
+}
+
+/// Remove comments and collapse whitespace. Strings are copied verbatim, and whitespace is only dropped next to
+/// punctuation where it can't change meaning (descendant selectors like `a :hover` keep their space).
+pub fn minify_css(source: &str) -> String {
+    const TIGHT: &[char] = &['{', '}', ';', ',', '>'];
+
+    let mut output = String::with_capacity(source.len());
+    let mut chars = source.chars().peekable();
+    let mut pending_space = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = '\0';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+                pending_space = true;
+            }
+            '"' | '\'' => {
+                if pending_space && !output.is_empty() && !output.ends_with(TIGHT) {
+                    output.push(' ');
+                }
+                pending_space = false;
+                output.push(c);
+                let mut escaped = false;
+                for inner in chars.by_ref() {
+                    output.push(inner);
+                    if escaped {
+                        escaped = false;
+                    } else if inner == '\\' {
+                        escaped = true;
+                    } else if inner == c {
+                        break;
+                    }
+                }
+            }
+            c if c.is_whitespace() => pending_space = true,
+            c => {
+                if pending_space
+                    && !output.is_empty()
+                    && !output.ends_with(TIGHT)
+                    && !TIGHT.contains(&c)
+                {
+                    output.push(' ');
+                }
+                pending_space = false;
+                output.push(c);
+            }
+        }
+    }
+
+    output
+}
+
+/// Re-serialize JSON without whitespace.
+pub fn minify_json(source: &str) -> Result<String, serde_json::Error> {
+    let value: serde_json::Value = serde_json::from_str(source)?;
+    serde_json::to_string(&value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn copies_are_turned_by_the_exif_orientation() {
+        let mut jpeg = Vec::new();
+        DynamicImage::new_rgb8(4, 2)
+            .write_with_encoder(JpegEncoder::new_with_quality(&mut jpeg, JPEG_QUALITY))
+            .unwrap();
+        // Orientation 6: stored on its side, shown turned 90° clockwise
+        let mut app1 =
+            b"Exif\0\0MM\0*\0\0\0\x08\0\x01\x01\x12\0\x03\0\0\0\x01\0\x06\0\0\0\0\0\0".to_vec();
+        app1.splice(0..0, ((app1.len() + 2) as u16).to_be_bytes());
+        app1.splice(0..0, [0xff, 0xe1]);
+        jpeg.splice(2..2, app1);
+
+        let image = decode_upright(&jpeg).unwrap();
+        assert_eq!((image.width(), image.height()), (2, 4));
+        assert!(decode_upright(&jpeg[..jpeg.len() / 2]).is_err());
+    }
+}
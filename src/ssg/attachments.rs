@@ -0,0 +1,396 @@
+//! Scanning of files sent with a contact submission, done by [`notify::receive`](crate::ssg::notify::receive) after the
+//! [`blocklist`](crate::ssg::blocklist) and before the submission is stored or sent anywhere. A file any scanner turns
+//! away fails the whole submission with a [`ValidationProblem::RejectedAttachment`] saying why, which the form lists
+//! with the file's name, so the sender can pick another one. The files of an accepted submission are [`save`]d in
+//! `[contact.store] attachments`.
+//!
+//! The scanners run in order, cheapest first, and a file stops at the first one that rejects it:
+//!
+//! - [`SizeLimit`]: `[contact.attachments] max_size_mb`
+//! - [`TypeCheck`]: the content, by its leading bytes, must be one of `[contact.attachments] accept`, and the file
+//!   must be named with an extension of that type, so a page or script can't come through as text (`page.html`)
+//! - [`Clamav`]: with `[contact.attachments] clamav_socket` set, the file is streamed to the ClamAV daemon
+//!
+//! Anything implementing [`Scanner`] can be added to the list. A scanner that can't give a verdict (the daemon is down)
+//! fails the submission with an error, never lets the file through unscanned.
+
+pub use crate::api::Attachment;
+
+use crate::api::{AttachmentRejection, ValidationProblem};
+use crate::ssg::config::AttachmentsConfig;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// A type a file's content can be recognized as.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FileType {
+    Pdf,
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    /// UTF-8 text without control characters other than whitespace
+    Text,
+}
+
+impl FileType {
+    pub const ALL: &'static [FileType] = &[
+        FileType::Pdf,
+        FileType::Png,
+        FileType::Jpeg,
+        FileType::Gif,
+        FileType::Webp,
+        FileType::Text,
+    ];
+
+    /// Name shown to senders, e.g. `PDF`.
+    pub fn label(self) -> &'static str {
+        match self {
+            FileType::Pdf => "PDF",
+            FileType::Png => "PNG",
+            FileType::Jpeg => "JPEG",
+            FileType::Gif => "GIF",
+            FileType::Webp => "WebP",
+            FileType::Text => "plain text",
+        }
+    }
+
+    /// File extensions of the type, lowercase.
+    pub fn extensions(self) -> &'static [&'static str] {
+        match self {
+            FileType::Pdf => &["pdf"],
+            FileType::Png => &["png"],
+            FileType::Jpeg => &["jpg", "jpeg"],
+            FileType::Gif => &["gif"],
+            FileType::Webp => &["webp"],
+            FileType::Text => &["txt", "md", "csv"],
+        }
+    }
+
+    /// The type of `bytes`, from their leading bytes, or `None` for a type not in [`ALL`](Self::ALL).
+    pub fn detect(bytes: &[u8]) -> Option<FileType> {
+        if bytes.starts_with(b"%PDF-") {
+            Some(FileType::Pdf)
+        } else if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+            Some(FileType::Png)
+        } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+            Some(FileType::Jpeg)
+        } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+            Some(FileType::Gif)
+        } else if bytes.len() >= 12 && &bytes[..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+            Some(FileType::Webp)
+        } else if is_text(bytes) {
+            Some(FileType::Text)
+        } else {
+            None
+        }
+    }
+
+    /// Whether `filename` ends in one of the type's [`extensions`](Self::extensions), in any case.
+    pub fn names(self, filename: &str) -> bool {
+        filename.rsplit_once('.').is_some_and(|(_, extension)| {
+            self.extensions()
+                .contains(&extension.to_ascii_lowercase().as_str())
+        })
+    }
+}
+
+fn is_text(bytes: &[u8]) -> bool {
+    std::str::from_utf8(bytes).is_ok_and(|text| {
+        text.chars()
+            .all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+    })
+}
+
+/// A check every attachment goes through.
+pub trait Scanner {
+    fn name(&self) -> &str;
+
+    /// `Ok(None)` to let the file through, `Ok(Some(..))` to turn it away, `Err` when the scanner couldn't decide.
+    fn scan(&self, attachment: &Attachment) -> Result<Option<AttachmentRejection>, String>;
+}
+
+/// Turns away files over `max_bytes`.
+pub struct SizeLimit {
+    pub max_bytes: u64,
+}
+
+impl Scanner for SizeLimit {
+    fn name(&self) -> &str {
+        "size limit"
+    }
+
+    fn scan(&self, attachment: &Attachment) -> Result<Option<AttachmentRejection>, String> {
+        Ok((attachment.bytes.len() as u64 > self.max_bytes).then_some(
+            AttachmentRejection::TooLarge {
+                max_bytes: self.max_bytes,
+            },
+        ))
+    }
+}
+
+/// Turns away files whose content isn't one of the `accept`ed types, or that aren't named with an extension of the type
+/// their content is.
+pub struct TypeCheck {
+    pub accept: Vec<FileType>,
+}
+
+impl Scanner for TypeCheck {
+    fn name(&self) -> &str {
+        "type check"
+    }
+
+    fn scan(&self, attachment: &Attachment) -> Result<Option<AttachmentRejection>, String> {
+        let detected = FileType::detect(&attachment.bytes);
+        let Some(detected) = detected.filter(|kind| self.accept.contains(kind)) else {
+            return Ok(Some(AttachmentRejection::TypeNotAccepted {
+                accepted: self
+                    .accept
+                    .iter()
+                    .map(|kind| kind.label().to_string())
+                    .collect(),
+            }));
+        };
+        Ok((!detected.names(&attachment.filename)).then(|| {
+            AttachmentRejection::ExtensionMismatch {
+                detected: detected.label().to_string(),
+                extensions: detected
+                    .extensions()
+                    .iter()
+                    .map(|extension| format!(".{}", extension))
+                    .collect(),
+            }
+        }))
+    }
+}
+
+/// Streams files to a ClamAV daemon (`clamd`) listening on the Unix socket at `socket`, with its `INSTREAM` command.
+pub struct Clamav {
+    pub socket: PathBuf,
+    pub timeout: Duration,
+}
+
+/// Bytes sent to the daemon per chunk; well under its default `StreamMaxLength`.
+#[cfg(unix)]
+const CLAMAV_CHUNK: usize = 64 * 1024;
+
+impl Scanner for Clamav {
+    fn name(&self) -> &str {
+        "ClamAV"
+    }
+
+    #[cfg(unix)]
+    fn scan(&self, attachment: &Attachment) -> Result<Option<AttachmentRejection>, String> {
+        use std::io::{Read, Write};
+        use std::os::unix::net::UnixStream;
+
+        let error = |e: std::io::Error| format!("{}: {}", self.socket.display(), e);
+        let mut stream = UnixStream::connect(&self.socket).map_err(error)?;
+        stream.set_read_timeout(Some(self.timeout)).map_err(error)?;
+        stream
+            .set_write_timeout(Some(self.timeout))
+            .map_err(error)?;
+
+        stream.write_all(b"zINSTREAM\0").map_err(error)?;
+        for chunk in attachment.bytes.chunks(CLAMAV_CHUNK) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .map_err(error)?;
+            stream.write_all(chunk).map_err(error)?;
+        }
+        stream.write_all(&0u32.to_be_bytes()).map_err(error)?;
+
+        let mut reply = String::new();
+        stream.read_to_string(&mut reply).map_err(error)?;
+        clamav_verdict(reply.trim_end_matches(['\0', '\n']))
+    }
+
+    #[cfg(not(unix))]
+    fn scan(&self, _attachment: &Attachment) -> Result<Option<AttachmentRejection>, String> {
+        Err(format!(
+            "{}: the ClamAV daemon is reached over a Unix socket, which this platform doesn't have",
+            self.socket.display()
+        ))
+    }
+}
+
+/// The verdict in a reply to `INSTREAM`: `stream: OK`, `stream: <signature> FOUND` or `... ERROR`.
+#[cfg_attr(not(unix), allow(dead_code))]
+fn clamav_verdict(reply: &str) -> Result<Option<AttachmentRejection>, String> {
+    let result = reply.strip_prefix("stream: ").unwrap_or(reply);
+    if result == "OK" {
+        Ok(None)
+    } else if let Some(signature) = result.strip_suffix(" FOUND") {
+        Ok(Some(AttachmentRejection::Infected {
+            signature: signature.to_string(),
+        }))
+    } else {
+        Err(format!("ClamAV: {}", reply))
+    }
+}
+
+/// The scanners `config` turns on, in the order they run.
+pub fn scanners(config: &AttachmentsConfig) -> Vec<Box<dyn Scanner>> {
+    let mut scanners: Vec<Box<dyn Scanner>> = vec![
+        Box::new(SizeLimit {
+            max_bytes: config.max_bytes(),
+        }),
+        Box::new(TypeCheck {
+            accept: config.accept.clone(),
+        }),
+    ];
+    if !config.clamav_socket.is_empty() {
+        scanners.push(Box::new(Clamav {
+            socket: PathBuf::from(&config.clamav_socket),
+            timeout: Duration::from_secs(30),
+        }));
+    }
+    scanners
+}
+
+/// Run every attachment through `scanners`. Returns a problem for each file turned away, empty when they may all be
+/// sent; the error is for a scanner that couldn't decide.
+pub fn scan(
+    scanners: &[Box<dyn Scanner>],
+    attachments: &[Attachment],
+) -> Result<Vec<ValidationProblem>, String> {
+    let mut problems = Vec::new();
+    for attachment in attachments {
+        for scanner in scanners {
+            let verdict = scanner
+                .scan(attachment)
+                .map_err(|e| format!("{} ({}): {}", attachment.filename, scanner.name(), e))?;
+            if let Some(rejection) = verdict {
+                problems.push(ValidationProblem::RejectedAttachment {
+                    field: attachment.field.clone(),
+                    filename: attachment.filename.clone(),
+                    rejection,
+                });
+                break;
+            }
+        }
+    }
+    Ok(problems)
+}
+
+/// Write `attachments`, received at `received_at`, into `dir`. Each is named after when it came and its field, then
+/// the sender's file name with anything but letters, digits, `.`, `-` and `_` replaced, so it can't reach outside the
+/// directory. Returns the paths written.
+pub fn save(
+    dir: &Path,
+    received_at: DateTime<Utc>,
+    attachments: &[Attachment],
+) -> std::io::Result<Vec<PathBuf>> {
+    if attachments.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    fs::create_dir_all(dir)?;
+    let stamp = received_at.format("%Y%m%dT%H%M%S%.3fZ");
+    attachments
+        .iter()
+        .map(|attachment| {
+            let path = dir.join(format!(
+                "{}-{}-{}",
+                stamp,
+                safe_name(&attachment.field),
+                safe_name(&attachment.filename)
+            ));
+            fs::write(&path, &attachment.bytes)?;
+            Ok(path)
+        })
+        .collect()
+}
+
+fn safe_name(name: &str) -> String {
+    let name = name.rsplit(['/', '\\']).next().unwrap_or_default();
+    let safe: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_') {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    match safe.trim_start_matches('.') {
+        "" => "file".to_string(),
+        trimmed => trimmed.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn safe_name_keeps_files_inside_the_directory() {
+        assert_eq!(safe_name("brief v2.pdf"), "brief_v2.pdf");
+        assert_eq!(safe_name("../../etc/passwd"), "passwd");
+        assert_eq!(safe_name("C:\\Users\\me\\cv.pdf"), "cv.pdf");
+        assert_eq!(safe_name(".."), "file");
+        assert_eq!(safe_name(".env"), "env");
+    }
+
+    fn scan(filename: &str, bytes: &[u8]) -> Option<AttachmentRejection> {
+        let check = TypeCheck {
+            accept: vec![FileType::Pdf, FileType::Png, FileType::Text],
+        };
+        let attachment = Attachment {
+            field: "attachment".to_string(),
+            filename: filename.to_string(),
+            bytes: bytes.to_vec(),
+        };
+        check.scan(&attachment).unwrap()
+    }
+
+    #[test]
+    fn type_check_accepts_files_named_for_their_content() {
+        assert_eq!(scan("notes.txt", b"Hello"), None);
+        assert_eq!(scan("README.MD", b"# Hello"), None);
+        assert_eq!(scan("brief.pdf", b"%PDF-1.7"), None);
+        assert_eq!(scan("logo.Png", b"\x89PNG\r\n\x1a\n"), None);
+    }
+
+    #[test]
+    fn type_check_rejects_other_or_missing_extensions() {
+        for filename in [
+            "page.html",
+            "icon.svg",
+            "app.js",
+            "setup.exe",
+            "notes",
+            "notes.",
+            "logo.png",
+        ] {
+            assert!(
+                matches!(
+                    scan(filename, b"<script>alert(1)</script>"),
+                    Some(AttachmentRejection::ExtensionMismatch { .. })
+                ),
+                "{filename}"
+            );
+        }
+        assert!(matches!(
+            scan("brief.txt", b"%PDF-1.7"),
+            Some(AttachmentRejection::ExtensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn type_check_rejects_types_not_accepted() {
+        assert!(matches!(
+            scan("photo.jpg", &[0xff, 0xd8, 0xff, 0xe0]),
+            Some(AttachmentRejection::TypeNotAccepted { .. })
+        ));
+        assert!(matches!(
+            scan("setup.exe", b"MZ\x90\x00\x03\x00"),
+            Some(AttachmentRejection::TypeNotAccepted { .. })
+        ));
+    }
+}
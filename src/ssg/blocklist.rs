@@ -0,0 +1,241 @@
+//! Abuse blocklist for the contact backend: submissions from blocked email addresses or IP ranges, or containing a
+//! blocked keyword, are turned away by [`notify::receive`](crate::ssg::notify::receive) before they reach any channel.
+//!
+//! The rules are kept in `[contact.store] blocklist`, one per line, and managed with `site blocklist`. A rule is
+//! written as `kind:value`:
+//!
+//! - `email:*@spam.example`: the sender's address, where `*` stands for any run of characters
+//! - `ip:203.0.113.0/24`: the address the submission came from, in a range (or a single address without a prefix)
+//! - `keyword:casino`: a word or phrase anywhere in the submission's text
+//!
+//! `ip` rules match the address `site serve` sees the submission come from: behind a proxy, the client it names in
+//! `X-Forwarded-For` when the proxy is one of `[server] trusted_proxies`. Patterns and keywords match in any case. Every blocked attempt is appended to `[contact.store] blocked`, with the
+//! rule that caught it, so a rule that's too broad shows up in `site blocklist log`.
+
+use crate::api::ContactRequest;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::Path;
+use std::str::FromStr;
+
+/// What a rule blocks.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "lowercase")]
+pub enum Rule {
+    /// Sender addresses matching the pattern
+    Email(String),
+    /// Client addresses in the range, as `address/prefix`
+    Ip(String),
+    /// Submissions with the word or phrase in any field
+    Keyword(String),
+}
+
+impl Rule {
+    /// Whether `request`, sent from `client_ip`, is caught by the rule.
+    pub fn matches(&self, request: &ContactRequest, client_ip: Option<IpAddr>) -> bool {
+        match self {
+            Rule::Email(pattern) => glob_matches(
+                &pattern.to_lowercase(),
+                &request.email.trim().to_lowercase(),
+            ),
+            Rule::Ip(range) => client_ip.is_some_and(|ip| in_range(range, ip)),
+            Rule::Keyword(keyword) => {
+                let keyword = keyword.to_lowercase();
+                [
+                    &request.name,
+                    &request.email,
+                    &request.subject,
+                    &request.message,
+                ]
+                .into_iter()
+                .chain(request.extra.values())
+                .any(|field| field.to_lowercase().contains(&keyword))
+            }
+        }
+    }
+}
+
+impl FromStr for Rule {
+    type Err = String;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let (kind, value) = rule
+            .split_once(':')
+            .map(|(kind, value)| (kind, value.trim()))
+            .ok_or_else(|| format!("`{}` isn't written as kind:value", rule))?;
+        if value.is_empty() {
+            return Err(format!("`{}` has nothing to block", rule));
+        }
+        match kind {
+            // Lowercased, since they match in any case
+            "email" => Ok(Rule::Email(value.to_lowercase())),
+            "ip" => {
+                parse_range(value)
+                    .ok_or_else(|| format!("`{}` isn't an IP address or range", value))?;
+                Ok(Rule::Ip(value.to_string()))
+            }
+            "keyword" => Ok(Rule::Keyword(value.to_lowercase())),
+            _ => Err(format!(
+                "`{}` isn't a kind of rule; use email, ip or keyword",
+                kind
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Email(pattern) => write!(f, "email:{}", pattern),
+            Rule::Ip(range) => write!(f, "ip:{}", range),
+            Rule::Keyword(keyword) => write!(f, "keyword:{}", keyword),
+        }
+    }
+}
+
+/// A rule as a line of the blocklist file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Entry {
+    #[serde(flatten)]
+    pub rule: Rule,
+    pub added_at: DateTime<Utc>,
+    /// Why the rule was added
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+/// A submission the blocklist turned away, as a line of the audit log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockedAttempt {
+    pub at: DateTime<Utc>,
+    pub rule: Rule,
+    pub email: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_ip: Option<IpAddr>,
+    /// Id of the form it was sent from; empty for the contact form
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub form: String,
+    pub subject: String,
+}
+
+/// The rules in the blocklist file, oldest first. A missing file has none.
+pub fn load(path: &Path) -> Result<Vec<Entry>, Box<dyn std::error::Error>> {
+    read_lines(path)
+}
+
+/// Replace the blocklist file with `entries`.
+pub fn save(path: &Path, entries: &[Entry]) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut lines = String::new();
+    for entry in entries {
+        lines.push_str(&serde_json::to_string(entry)?);
+        lines.push('\n');
+    }
+    fs::write(path, lines)?;
+    Ok(())
+}
+
+/// The first of `entries` that catches `request`, sent from `client_ip`.
+pub fn matching<'a>(
+    entries: &'a [Entry],
+    request: &ContactRequest,
+    client_ip: Option<IpAddr>,
+) -> Option<&'a Entry> {
+    entries
+        .iter()
+        .find(|entry| entry.rule.matches(request, client_ip))
+}
+
+/// The blocked attempts in the audit log, oldest first. A missing file has none.
+pub fn attempts(path: &Path) -> Result<Vec<BlockedAttempt>, Box<dyn std::error::Error>> {
+    read_lines(path)
+}
+
+/// Append a blocked attempt to the audit log.
+pub fn log_attempt(
+    path: &Path,
+    attempt: &BlockedAttempt,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(attempt)?)?;
+    Ok(())
+}
+
+fn read_lines<T: for<'de> Deserialize<'de>>(
+    path: &Path,
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+/// Whether `text` matches `pattern`, where `*` stands for any run of characters.
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first().unwrap_or((&"", &[]));
+    let Some(mut remaining) = text.strip_prefix(first) else {
+        return false;
+    };
+    let Some((last, middle)) = rest.split_last() else {
+        return remaining.is_empty();
+    };
+    for part in middle {
+        match remaining.find(part) {
+            Some(index) => remaining = &remaining[index + part.len()..],
+            None => return false,
+        }
+    }
+    remaining.len() >= last.len() && remaining.ends_with(last)
+}
+
+/// The address and prefix length of `range`; a single address covers just itself.
+pub fn parse_range(range: &str) -> Option<(IpAddr, u32)> {
+    let (address, prefix) = match range.split_once('/') {
+        Some((address, prefix)) => (address.parse::<IpAddr>().ok()?, Some(prefix.parse().ok()?)),
+        None => (range.parse::<IpAddr>().ok()?, None),
+    };
+    let bits = if address.is_ipv4() { 32 } else { 128 };
+    let prefix = prefix.unwrap_or(bits);
+    (prefix <= bits).then_some((address, prefix))
+}
+
+/// Whether `ip` is in `range`. IPv4 addresses mapped into IPv6 count as IPv4.
+pub fn in_range(range: &str, ip: IpAddr) -> bool {
+    let Some((network, prefix)) = parse_range(range) else {
+        return false;
+    };
+    let ip = match ip {
+        IpAddr::V6(v6) => v6.to_ipv4_mapped().map_or(ip, IpAddr::V4),
+        v4 => v4,
+    };
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = u32::MAX.checked_shl(32 - prefix).unwrap_or(0);
+            u32::from(network) & mask == u32::from(ip) & mask
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = u128::MAX.checked_shl(128 - prefix).unwrap_or(0);
+            u128::from(network) & mask == u128::from(ip) & mask
+        }
+        _ => false,
+    }
+}
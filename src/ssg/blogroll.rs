@@ -0,0 +1,290 @@
+//! The blogroll at [`BLOGROLL_PATH`]: the latest posts from the blogs listed under `[[blogroll.feeds]]` in `site.toml`.
+//!
+//! Feeds are fetched with `curl` while the site is built, and every response that parses is kept under
+//! [`CACHE_DIR`]. A copy younger than `[blogroll] max_age_hours` is used without fetching again; when a fetch fails (no
+//! connection, an error status, a feed that doesn't parse, or no `curl` at all) the cached copy is used however old it
+//! is. A feed with neither is left out with a warning, so one broken blog never fails the build.
+//!
+//! Both RSS 2.0 (`<item>`) and Atom (`<entry>`) feeds are read, with the same minimal scanning as [`html`].
+
+use crate::entities;
+use crate::excerpt;
+use crate::ssg::config::{BlogrollConfig, BlogrollFeed};
+use crate::ssg::html;
+use chrono::{DateTime, FixedOffset};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{Duration, SystemTime};
+
+/// Route of the blogroll page.
+pub const BLOGROLL_PATH: &str = "/blogroll/";
+
+/// Where fetched feeds are cached between builds, one file per feed URL.
+pub const CACHE_DIR: &str = ".cache/blogroll";
+
+/// Longest a single feed may take to download.
+const FETCH_TIMEOUT_SECS: u64 = 10;
+
+/// A post from one of the feeds.
+#[derive(Debug, Clone)]
+pub struct FeedItem {
+    pub title: String,
+    /// Absolute URL of the post
+    pub url: String,
+    pub published: Option<DateTime<FixedOffset>>,
+}
+
+impl FeedItem {
+    /// The date as shown on the page, like a post's date on the blog index.
+    pub fn date_label(&self) -> Option<String> {
+        self.published
+            .map(|published| published.format("%-d %B %Y").to_string())
+    }
+}
+
+/// Where a feed's items came from in this build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeedSource {
+    /// Downloaded just now
+    Fetched,
+    /// A cached copy young enough not to fetch again
+    Cached,
+    /// An older cached copy, used because the fetch failed
+    Stale,
+}
+
+/// A feed's newest items, newest first.
+#[derive(Debug, Clone)]
+pub struct LoadedFeed<'a> {
+    pub feed: &'a BlogrollFeed,
+    pub items: Vec<FeedItem>,
+    pub source: FeedSource,
+}
+
+/// Load every configured feed, from the cache or the network. Feeds that can't be loaded are reported and left out.
+pub fn load(config: &BlogrollConfig) -> Vec<LoadedFeed<'_>> {
+    let max_age = Duration::from_secs(config.max_age_hours * 60 * 60);
+    let mut loaded = Vec::new();
+
+    for feed in &config.feeds {
+        match load_feed(feed, max_age) {
+            Ok((mut items, source)) => {
+                if source == FeedSource::Stale {
+                    println!(
+                        "⚠️  Blogroll: using an old cached copy of {} ({})",
+                        feed.name, feed.url
+                    );
+                }
+                items.sort_by_key(|item| Reverse(item.published));
+                items.truncate(config.per_feed);
+                loaded.push(LoadedFeed {
+                    feed,
+                    items,
+                    source,
+                });
+            }
+            Err(e) => println!(
+                "⚠️  Blogroll: leaving out {} ({}): {}",
+                feed.name, feed.url, e
+            ),
+        }
+    }
+
+    loaded
+}
+
+/// The `limit` newest items across `feeds`, each with the feed it came from. Undated items go last.
+pub fn latest<'a>(
+    feeds: &'a [LoadedFeed<'a>],
+    limit: usize,
+) -> Vec<(&'a BlogrollFeed, &'a FeedItem)> {
+    let mut items: Vec<(&BlogrollFeed, &FeedItem)> = feeds
+        .iter()
+        .flat_map(|loaded| loaded.items.iter().map(move |item| (loaded.feed, item)))
+        .collect();
+    items.sort_by_key(|(_, item)| Reverse(item.published));
+    items.truncate(limit);
+    items
+}
+
+fn load_feed(
+    feed: &BlogrollFeed,
+    max_age: Duration,
+) -> Result<(Vec<FeedItem>, FeedSource), Box<dyn std::error::Error>> {
+    let cache = cache_path(&feed.url);
+    let cached = fs::read_to_string(&cache).ok();
+    let age = fs::metadata(&cache)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+
+    if let (Some(xml), Some(age)) = (&cached, age) {
+        if age < max_age {
+            if let Ok(items) = parse(xml) {
+                return Ok((items, FeedSource::Cached));
+            }
+        }
+    }
+
+    let fetched = fetch(&feed.url).and_then(|xml| {
+        let items = parse(&xml)?;
+        Ok((xml, items))
+    });
+    match (fetched, cached) {
+        (Ok((xml, items)), _) => {
+            if let Some(dir) = cache.parent() {
+                fs::create_dir_all(dir)?;
+            }
+            fs::write(&cache, xml)?;
+            Ok((items, FeedSource::Fetched))
+        }
+        (Err(e), Some(xml)) => match parse(&xml) {
+            Ok(items) => Ok((items, FeedSource::Stale)),
+            Err(_) => Err(e),
+        },
+        (Err(e), None) => Err(format!("{} (and no cached copy)", e).into()),
+    }
+}
+
+fn fetch(url: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location"])
+        .args(["--max-time", &FETCH_TIMEOUT_SECS.to_string()])
+        .arg(url)
+        .output()
+        .map_err(|e| format!("couldn't run curl: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr)
+            .trim()
+            .to_string()
+            .into());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `<CACHE_DIR>/<hash of the url>.xml`.
+fn cache_path(url: &str) -> PathBuf {
+    let hash: String = Sha256::digest(url.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    Path::new(CACHE_DIR).join(format!("{}.xml", hash))
+}
+
+/// The posts in an RSS or Atom feed, in feed order. Items without a title or an absolute link are skipped; a document
+/// with neither `<item>` nor `<entry>` elements, or with no usable ones, isn't a feed.
+pub fn parse(xml: &str) -> Result<Vec<FeedItem>, String> {
+    let mut blocks = elements(xml, "item");
+    let atom = blocks.is_empty();
+    if atom {
+        blocks = elements(xml, "entry");
+    }
+    if blocks.is_empty() {
+        return Err("no <item> or <entry> elements, so not an RSS or Atom feed".to_string());
+    }
+
+    let items: Vec<FeedItem> = blocks
+        .into_iter()
+        .filter_map(|block| {
+            let title = excerpt::plain_text(&unwrap_cdata(element_text(block, "title")?));
+            let url = if atom {
+                atom_link(block)?
+            } else {
+                rss_link(block)?
+            };
+            if title.is_empty() || !(url.starts_with("https://") || url.starts_with("http://")) {
+                return None;
+            }
+
+            let published = ["pubDate", "published", "updated", "dc:date"]
+                .iter()
+                .filter_map(|name| element_text(block, name))
+                .find_map(|text| parse_date(text.trim()));
+            Some(FeedItem {
+                title,
+                url,
+                published,
+            })
+        })
+        .collect();
+
+    if items.is_empty() {
+        return Err("no items with a title and a link".to_string());
+    }
+    Ok(items)
+}
+
+/// The contents of every `<name>` element, without the element's own tags.
+fn elements<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let lower = xml.to_ascii_lowercase();
+    let close = format!("</{}>", name.to_ascii_lowercase());
+    let mut found = Vec::new();
+    let mut from = 0;
+
+    for span in html::tag_spans(xml, name) {
+        if span.start < from {
+            continue;
+        }
+        if xml[span.clone()].ends_with("/>") {
+            found.push("");
+            continue;
+        }
+        let Some(end) = lower[span.end..].find(&close) else {
+            break;
+        };
+        found.push(&xml[span.end..span.end + end]);
+        from = span.end + end + close.len();
+    }
+
+    found
+}
+
+fn element_text<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    elements(xml, name).into_iter().next()
+}
+
+/// Text inside a `<![CDATA[...]]>` section, or `text` itself when it has none.
+fn unwrap_cdata(text: &str) -> String {
+    let text = text.trim();
+    match text
+        .strip_prefix("<![CDATA[")
+        .and_then(|inner| inner.strip_suffix("]]>"))
+    {
+        // CDATA holds text as it is, so escape it for the entity decoding that follows
+        Some(inner) => html::escape(inner),
+        None => text.to_string(),
+    }
+}
+
+/// RSS `<link>url</link>`, falling back to a `<guid>` that is a permalink.
+fn rss_link(item: &str) -> Option<String> {
+    element_text(item, "link")
+        .map(|link| entities::decode(&unwrap_cdata(link)))
+        .filter(|link| !link.is_empty())
+        .or_else(|| {
+            let guid = element_text(item, "guid")?;
+            Some(entities::decode(&unwrap_cdata(guid)))
+        })
+}
+
+/// Atom `<link href="..."/>`, preferring the `alternate` link (the default when `rel` is left out).
+fn atom_link(entry: &str) -> Option<String> {
+    let links = html::tags(entry, "link");
+    links
+        .iter()
+        .find(|tag| html::attr(tag, "rel").is_none_or(|rel| rel == "alternate"))
+        .or(links.first())
+        .and_then(|tag| html::attr(tag, "href"))
+        .map(entities::decode)
+}
+
+/// RFC 2822 (RSS) or RFC 3339 (Atom) dates. Feeds get the day of the week wrong often enough that it's ignored.
+fn parse_date(text: &str) -> Option<DateTime<FixedOffset>> {
+    let without_weekday = text.split_once(", ").map_or(text, |(_, rest)| rest);
+    DateTime::parse_from_rfc2822(without_weekday)
+        .or_else(|_| DateTime::parse_from_rfc3339(text))
+        .ok()
+}
@@ -0,0 +1,123 @@
+//! The changelog at [`CHANGELOG_PATH`]: what changed on the site, read from the git history of the content files
+//! listed under `[[changelog.sources]]` in `site.toml`.
+//!
+//! Every commit touching one of the files is an entry, with its subject (less a leading `[...]` reference) and links
+//! to the pages the files are shown on. Entries are grouped by month, newest first, going back `[changelog] months`.
+//! Without git or a history (a tarball, a shallow clone) the page says there's nothing to show, so the build doesn't
+//! fail over it.
+
+use crate::ssg::config::{ChangelogConfig, ChangelogSource};
+use chrono::{Datelike, Months, NaiveDate};
+use std::process::Command;
+
+/// Route of the changelog page.
+pub const CHANGELOG_PATH: &str = "/changelog/";
+
+/// Separates the fields of a commit in the `git log` output.
+const FIELD_SEPARATOR: char = '\u{1f}';
+
+/// A commit that changed content.
+#[derive(Debug, Clone)]
+pub struct Change<'a> {
+    pub date: NaiveDate,
+    pub summary: String,
+    /// Sources of the files it touched, in the order they are configured
+    pub sources: Vec<&'a ChangelogSource>,
+}
+
+/// The changes made in one month, newest first.
+#[derive(Debug, Clone)]
+pub struct Month<'a> {
+    /// First day of the month
+    pub start: NaiveDate,
+    pub changes: Vec<Change<'a>>,
+}
+
+impl Month<'_> {
+    /// The month as shown on the page, e.g. "October 2026".
+    pub fn label(&self) -> String {
+        self.start.format("%B %Y").to_string()
+    }
+}
+
+/// The changes to the configured sources from `[changelog] months` months before `today` on, by month, newest first.
+pub fn load(config: &ChangelogConfig, today: NaiveDate) -> Result<Vec<Month<'_>>, String> {
+    let since = today
+        .with_day(1)
+        .and_then(|start| start.checked_sub_months(Months::new(config.months.saturating_sub(1))))
+        .unwrap_or(today);
+
+    let output = Command::new("git")
+        .arg("log")
+        .arg(format!("--since={}", since))
+        .arg(format!("--format={sep}%cs{sep}%s", sep = FIELD_SEPARATOR))
+        .arg("--name-only")
+        .arg("--")
+        .args(config.sources.iter().map(|source| &source.path))
+        .output()
+        .map_err(|e| format!("couldn't run git: {}", e))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let mut months: Vec<Month> = Vec::new();
+    for change in parse_log(&String::from_utf8_lossy(&output.stdout), &config.sources) {
+        let start = change.date.with_day(1).unwrap_or(change.date);
+        match months.last_mut() {
+            Some(month) if month.start == start => month.changes.push(change),
+            _ => months.push(Month {
+                start,
+                changes: vec![change],
+            }),
+        }
+    }
+    Ok(months)
+}
+
+/// The commits in `git log --name-only` output, newest first, with the sources each one touched.
+fn parse_log<'a>(log: &str, sources: &'a [ChangelogSource]) -> Vec<Change<'a>> {
+    let mut changes: Vec<Change> = Vec::new();
+    for line in log.lines().filter(|line| !line.trim().is_empty()) {
+        if let Some(fields) = line.strip_prefix(FIELD_SEPARATOR) {
+            let (date, subject) = fields.split_once(FIELD_SEPARATOR).unwrap_or((fields, ""));
+            let Ok(date) = NaiveDate::parse_from_str(date, "%Y-%m-%d") else {
+                continue;
+            };
+            changes.push(Change {
+                date,
+                summary: summary(subject),
+                sources: Vec::new(),
+            });
+        } else if let Some(change) = changes.last_mut() {
+            for source in sources.iter().filter(|source| source.contains(line)) {
+                if !change
+                    .sources
+                    .iter()
+                    .any(|known| std::ptr::eq(*known, source))
+                {
+                    change.sources.push(source);
+                }
+            }
+        }
+    }
+    for change in &mut changes {
+        change.sources.sort_by_key(|source| {
+            sources
+                .iter()
+                .position(|known| std::ptr::eq(known, *source))
+        });
+    }
+    changes
+}
+
+/// A commit subject without the issue reference it may start with, e.g. `[site#12] Fix typo` → `Fix typo`.
+fn summary(subject: &str) -> String {
+    let subject = subject.trim();
+    match subject
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+    {
+        Some((_, rest)) if !rest.trim().is_empty() => rest.trim().to_string(),
+        _ => subject.to_string(),
+    }
+}
@@ -0,0 +1,102 @@
+//! The log behind [`CLIENT_ERRORS_ENDPOINT`](crate::api::client_errors::CLIENT_ERRORS_ENDPOINT): one JSON line per
+//! report in the file named by `[telemetry] log` in `site.toml`, appended by the server and summarised by
+//! `site client-errors`.
+
+use crate::api::client_errors::{ClientErrorKind, ClientErrorReport};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// A report as stored, with when it arrived.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggedError {
+    pub received_at: DateTime<Utc>,
+    #[serde(flatten)]
+    pub report: ClientErrorReport,
+}
+
+/// Append `report`, scrubbed, to the log at `path`.
+pub fn record(
+    path: &Path,
+    report: &ClientErrorReport,
+    received_at: DateTime<Utc>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let entry = LoggedError {
+        received_at,
+        report: report.scrubbed(),
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Every logged report, oldest first. A missing file has none.
+pub fn load(path: &Path) -> Result<Vec<LoggedError>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+/// Reports that are the same error: same kind, island, category and message.
+#[derive(Debug, Clone)]
+pub struct ErrorGroup<'a> {
+    pub kind: ClientErrorKind,
+    pub island: Option<&'a str>,
+    pub category: Option<&'a str>,
+    pub message: &'a str,
+    pub count: usize,
+    /// Pages it happened on, with how often
+    pub pages: BTreeMap<&'a str, usize>,
+    pub last_seen: DateTime<Utc>,
+}
+
+/// `errors` grouped into [`ErrorGroup`]s, the most frequent first.
+pub fn group(errors: &[LoggedError]) -> Vec<ErrorGroup<'_>> {
+    let mut groups: Vec<ErrorGroup> = Vec::new();
+    for error in errors {
+        let report = &error.report;
+        let existing = groups.iter_mut().find(|group| {
+            group.kind == report.kind
+                && group.island == report.island.as_deref()
+                && group.category == report.category.as_deref()
+                && group.message == report.message
+        });
+        let group = match existing {
+            Some(group) => group,
+            None => {
+                groups.push(ErrorGroup {
+                    kind: report.kind,
+                    island: report.island.as_deref(),
+                    category: report.category.as_deref(),
+                    message: &report.message,
+                    count: 0,
+                    pages: BTreeMap::new(),
+                    last_seen: error.received_at,
+                });
+                groups.last_mut().expect("a group was just added")
+            }
+        };
+        group.count += 1;
+        *group.pages.entry(&report.page).or_default() += 1;
+        group.last_seen = group.last_seen.max(error.received_at);
+    }
+
+    groups.sort_by_key(|group| std::cmp::Reverse(group.count));
+    groups
+}
@@ -0,0 +1,1572 @@
+//! Site-wide settings loaded from `site.toml` in the project root.
+//!
+//! Every field has a default, so the file (or any section of it) can be omitted.
+
+use crate::api::ContactRequest;
+use crate::contact_form::{FormSchema, CONTACT_FORM};
+use crate::contact_reasons::{DEFAULT_REASON, REASONS};
+use crate::data::ABOUT;
+use crate::hints::{self, Hint};
+use crate::license::LicenseConfig;
+use crate::reading_progress::ReadingProgressConfig;
+use crate::responsive_images::ResponsiveImagesConfig;
+use crate::source::SourceConfig;
+use crate::ssg::attachments::FileType;
+use crate::ssg::blocklist;
+use crate::ssg::email::{self, EmailTemplate};
+use crate::ssg::image_metadata;
+use crate::ssg::legal;
+use crate::tags::TagsConfig;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Directory holding the contact email templates named in `[contact.routes]`, as `<name>.txt` and `<name>.html` (see
+/// [`email`]).
+pub const CONTACT_TEMPLATES_DIR: &str = "templates/contact";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SiteConfig {
+    pub site: SiteInfo,
+    pub build: BuildConfig,
+    pub feed: FeedConfig,
+    pub i18n: I18nConfig,
+    pub contact: ContactConfig,
+    pub assets: AssetsConfig,
+    pub responsive_images: ResponsiveImagesConfig,
+    pub headers: HeadersConfig,
+    pub meta: MetaConfig,
+    pub social: SocialConfig,
+    pub source: SourceConfig,
+    pub tags: TagsConfig,
+    pub sitemap: SitemapConfig,
+    pub search: SearchConfig,
+    pub blogroll: BlogrollConfig,
+    pub reactions: ReactionsConfig,
+    pub telemetry: TelemetryConfig,
+    pub freshness: FreshnessConfig,
+    pub reading_progress: ReadingProgressConfig,
+    pub changelog: ChangelogConfig,
+    pub watch: WatchConfig,
+    pub server: ServerConfig,
+    pub analytics: AnalyticsConfig,
+    pub preview: PreviewConfig,
+    pub deploy: DeployConfig,
+    pub legal: LegalConfig,
+    pub license: LicenseConfig,
+    pub hints: Vec<Hint>,
+}
+
+/// General information about the site used in page metadata and feeds.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SiteInfo {
+    pub title: String,
+    pub description: String,
+    /// Absolute origin the site is served from, without a trailing slash
+    pub base_url: String,
+    pub author: String,
+}
+
+impl Default for SiteInfo {
+    fn default() -> Self {
+        Self {
+            title: "Dioxus Site".to_string(),
+            description: "Welcome to my Dioxus-powered website".to_string(),
+            base_url: "https://sydor.co".to_string(),
+            author: "Marcin Sydor".to_string(),
+        }
+    }
+}
+
+/// How page files are laid out in the output directory. Hosts differ in which URLs they map to which files.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputStyle {
+    /// `about/index.html`, linked as `/about/` (Netlify, GitHub Pages)
+    #[default]
+    Directory,
+    /// `about.html`, linked as `/about.html` (plain S3 buckets and file servers)
+    Flat,
+    /// Both files, linked as `/about/`, with a canonical link so the duplicate isn't indexed separately
+    Both,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct BuildConfig {
+    pub output_style: OutputStyle,
+}
+
+/// What feed items contain.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedContent {
+    /// Only the post summary
+    Summary,
+    /// The summary plus the complete post HTML
+    #[default]
+    Full,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct FeedConfig {
+    pub content: FeedContent,
+}
+
+/// The languages posts are written in (see [`translations`](crate::ssg::translations)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct I18nConfig {
+    /// Language of the posts in `src/posts.rs`, and of the pages without a language of their own
+    pub default_language: String,
+    /// Codes of the languages, as in the translations' file names and paths, with the name each is shown by
+    pub languages: BTreeMap<String, String>,
+}
+
+impl Default for I18nConfig {
+    fn default() -> Self {
+        Self {
+            default_language: "en".to_string(),
+            languages: BTreeMap::from([("en".to_string(), "English".to_string())]),
+        }
+    }
+}
+
+impl I18nConfig {
+    /// The name `lang` is shown by, or the code when it has none.
+    pub fn language_name<'a>(&'a self, lang: &'a str) -> &'a str {
+        self.languages.get(lang).map_or(lang, String::as_str)
+    }
+
+    /// The languages other than the default, which translations are in.
+    pub fn translated_languages(&self) -> impl Iterator<Item = &str> {
+        self.languages
+            .keys()
+            .map(String::as_str)
+            .filter(|lang| *lang != self.default_language)
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.languages.contains_key(&self.default_language) {
+            problems.push(format!(
+                "[i18n] default_language `{}` must be one of languages",
+                self.default_language
+            ));
+        }
+        for lang in self.languages.keys() {
+            // The code is a directory of the output
+            if !(2..=3).contains(&lang.len()) || !lang.bytes().all(|byte| byte.is_ascii_lowercase())
+            {
+                problems.push(format!(
+                    "[i18n] languages: `{}` isn't a language code like `en`",
+                    lang
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Which files under `assets/` are copied into the output, and how.
+///
+/// Patterns are globs relative to `assets/` using `/` separators: `*` and `?` match within one path segment, `**`
+/// matches any number of segments. A file is copied when it matches an include pattern and no exclude pattern.
+/// Images lose their metadata on the way unless they match a `keep_metadata` pattern.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AssetsConfig {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// Transformation applied to files by extension (without the dot); images not listed are stripped of their
+    /// metadata and anything else is copied as-is
+    pub transform: BTreeMap<String, AssetTransform>,
+    /// Images published with their metadata, such as photos whose camera data a page shows
+    pub keep_metadata: Vec<String>,
+}
+
+impl Default for AssetsConfig {
+    fn default() -> Self {
+        Self {
+            include: vec!["**".to_string()],
+            exclude: [
+                "**/*.psd",
+                "**/*.map",
+                "**/*.scss",
+                "**/*.sass",
+                "**/.DS_Store",
+            ]
+            .map(str::to_string)
+            .to_vec(),
+            transform: BTreeMap::new(),
+            keep_metadata: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AssetTransform {
+    /// Copy the file unchanged
+    Copy,
+    /// Strip comments and insignificant whitespace (`css` and `json` only)
+    Minify,
+    /// Remove EXIF, GPS and other metadata (images only, see [`image_metadata`](crate::ssg::image_metadata))
+    Strip,
+}
+
+/// Extensions [`AssetTransform::Minify`] knows how to handle.
+pub const MINIFIABLE_EXTENSIONS: &[&str] = &["css", "json"];
+
+impl AssetsConfig {
+    fn validate(&self) -> Vec<String> {
+        self.transform
+            .iter()
+            .filter_map(|(extension, transform)| {
+                let (verb, supported) = match transform {
+                    AssetTransform::Copy => return None,
+                    AssetTransform::Minify => ("minify", MINIFIABLE_EXTENSIONS),
+                    AssetTransform::Strip => ("strip", image_metadata::EXTENSIONS),
+                };
+                (!supported.contains(&extension.as_str())).then(|| {
+                    format!(
+                        "[assets.transform] can't {} `{}` files (only {})",
+                        verb,
+                        extension,
+                        supported.join(", ")
+                    )
+                })
+            })
+            .collect()
+    }
+}
+
+/// Which host the response headers file is written for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HeadersFormat {
+    /// No headers file (GitHub Pages can't set headers)
+    #[default]
+    None,
+    /// `_headers` (Netlify, Cloudflare Pages)
+    Netlify,
+    /// `vercel.json`
+    Vercel,
+    /// `nginx-headers.conf`, to `include` in the site's `server` block
+    Nginx,
+}
+
+/// How the generated pages deal with inline code under a Content Security Policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CspMode {
+    /// Pages keep their inline `<style>` and `<script>` blocks, so the policy needs `'unsafe-inline'`
+    #[default]
+    Relaxed,
+    /// Inline blocks are moved into content-hashed files under `assets/`, so the policy can do without `'unsafe-inline'`
+    Strict,
+}
+
+/// Response headers for the host: long-lived caching for fingerprinted assets plus security headers on every path.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct HeadersConfig {
+    pub format: HeadersFormat,
+    pub csp_mode: CspMode,
+    /// `Content-Security-Policy` value; empty to leave the header out
+    pub content_security_policy: String,
+    /// `Referrer-Policy` value; empty to leave the header out
+    pub referrer_policy: String,
+}
+
+impl Default for HeadersConfig {
+    fn default() -> Self {
+        Self {
+            format: HeadersFormat::default(),
+            csp_mode: CspMode::default(),
+            // Pages use inline styles and module loaders, the islands need to compile WASM, and the playground
+            // island runs code on the Rust Playground
+            content_security_policy: "default-src 'self'; script-src 'self' 'unsafe-inline' 'wasm-unsafe-eval'; \
+                                      style-src 'self' 'unsafe-inline'; img-src 'self' data:; \
+                                      connect-src 'self' https://play.rust-lang.org; object-src 'none'; \
+                                      base-uri 'self'; frame-ancestors 'none'"
+                .to_string(),
+            referrer_policy: "strict-origin-when-cross-origin".to_string(),
+        }
+    }
+}
+
+impl HeadersConfig {
+    fn validate(&self) -> Vec<String> {
+        if self.csp_mode == CspMode::Strict
+            && self.content_security_policy.contains("'unsafe-inline'")
+        {
+            vec!["[headers] csp_mode = \"strict\" needs a content_security_policy without 'unsafe-inline'".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Limits for page meta descriptions. Descriptions derived from content are trimmed to `max_description_length`, and
+/// the build warns about every page whose description is missing or outside the limits.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct MetaConfig {
+    pub min_description_length: usize,
+    pub max_description_length: usize,
+}
+
+impl Default for MetaConfig {
+    fn default() -> Self {
+        Self {
+            min_description_length: 50,
+            max_description_length: crate::excerpt::DESCRIPTION_LENGTH,
+        }
+    }
+}
+
+impl MetaConfig {
+    fn validate(&self) -> Vec<String> {
+        if self.max_description_length == 0 {
+            vec!["[meta] max_description_length must be at least 1".to_string()]
+        } else if self.min_description_length > self.max_description_length {
+            vec!["[meta] min_description_length is longer than max_description_length".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Link previews on social platforms (see [`social`](crate::ssg::social)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SocialConfig {
+    /// Longest `og:description`, in characters
+    pub og_description_length: usize,
+    /// Longest `twitter:description`, in characters
+    pub twitter_description_length: usize,
+    /// Mastodon account credited on every page, as `@name@instance`; empty to leave the tag out
+    pub fediverse_creator: String,
+    /// Per-page overrides, by route path (`/about/`)
+    pub pages: BTreeMap<String, SocialOverrides>,
+}
+
+impl Default for SocialConfig {
+    fn default() -> Self {
+        Self {
+            og_description_length: 200,
+            twitter_description_length: 120,
+            fediverse_creator: String::new(),
+            pages: BTreeMap::new(),
+        }
+    }
+}
+
+/// Text for one page's previews, used as it is instead of the derived text.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SocialOverrides {
+    pub og_description: Option<String>,
+    pub twitter_description: Option<String>,
+    pub fediverse_creator: Option<String>,
+}
+
+impl SocialConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.og_description_length == 0 || self.twitter_description_length == 0 {
+            problems.push("[social] description lengths must be at least 1".to_string());
+        }
+        let creators = std::iter::once((
+            "[social] fediverse_creator".to_string(),
+            &self.fediverse_creator,
+        ))
+        .chain(self.pages.iter().filter_map(|(path, overrides)| {
+            let creator = overrides.fediverse_creator.as_ref()?;
+            Some((
+                format!("[social.pages.\"{}\"] fediverse_creator", path),
+                creator,
+            ))
+        }));
+        for (key, creator) in creators {
+            if !creator.is_empty() && !is_fediverse_account(creator) {
+                problems.push(format!(
+                    "{} must look like @name@instance, not {:?}",
+                    key, creator
+                ));
+            }
+        }
+        for path in self.pages.keys() {
+            if !path.starts_with('/') || !path.ends_with('/') {
+                problems.push(format!(
+                    "[social.pages] {:?} must be a route path starting and ending with /",
+                    path
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Whether `account` is a fediverse handle, `@name@instance.tld`.
+fn is_fediverse_account(account: &str) -> bool {
+    let Some((name, instance)) = account
+        .strip_prefix('@')
+        .and_then(|account| account.split_once('@'))
+    else {
+        return false;
+    };
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.' || c == '-')
+        && instance.contains('.')
+        && !instance.starts_with('.')
+        && !instance.ends_with('.')
+        && instance
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+}
+
+/// Static search landing pages (see [`search`](crate::ssg::search)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// How many of the most common keywords in the posts get a page; 0 for none
+    pub keyword_pages: usize,
+    /// Fewest posts a keyword has to be mentioned in to get a page
+    pub min_posts: usize,
+    /// The only words that may get a page, when set; otherwise any word in the posts that isn't a stop word can
+    pub keywords: Vec<String>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            keyword_pages: 20,
+            min_posts: 2,
+            keywords: Vec::new(),
+        }
+    }
+}
+
+/// The blogroll page and the feeds it lists (see [`blogroll`](crate::ssg::blogroll)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct BlogrollConfig {
+    /// How many of the newest items across all feeds are listed
+    pub items: usize,
+    /// Most items listed from any one feed, so a prolific blog doesn't fill the page
+    pub per_feed: usize,
+    /// Hours a cached feed is used as it is before it's fetched again
+    pub max_age_hours: u64,
+    pub feeds: Vec<BlogrollFeed>,
+}
+
+impl Default for BlogrollConfig {
+    fn default() -> Self {
+        Self {
+            items: 20,
+            per_feed: 5,
+            max_age_hours: 6,
+            feeds: Vec::new(),
+        }
+    }
+}
+
+/// A blog on the blogroll.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlogrollFeed {
+    pub name: String,
+    /// RSS or Atom feed URL
+    pub url: String,
+    /// Home page of the blog, linked from the list of blogs; defaults to the feed URL
+    #[serde(default)]
+    pub site: Option<String>,
+}
+
+impl BlogrollFeed {
+    pub fn site_url(&self) -> &str {
+        self.site.as_deref().unwrap_or(&self.url)
+    }
+}
+
+impl BlogrollConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.items == 0 || self.per_feed == 0 {
+            problems.push("[blogroll] items and per_feed must be at least 1".to_string());
+        }
+        for (index, feed) in self.feeds.iter().enumerate() {
+            if feed.name.trim().is_empty() {
+                problems.push(format!("[[blogroll.feeds]] `{}` has no name", feed.url));
+            }
+            for url in std::iter::once(&feed.url).chain(&feed.site) {
+                if !url.starts_with("https://") && !url.starts_with("http://") {
+                    problems.push(format!(
+                        "[[blogroll.feeds]] `{}` URL `{}` isn't an http(s) URL",
+                        feed.name, url
+                    ));
+                }
+            }
+            if self.feeds[..index]
+                .iter()
+                .any(|other| other.url == feed.url)
+            {
+                problems.push(format!("[[blogroll.feeds]] `{}` is listed twice", feed.url));
+            }
+        }
+        problems
+    }
+}
+
+/// Where the reaction counts are kept (see [`reactions`](crate::ssg::reactions)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ReactionsConfig {
+    /// Show the buttons under posts; only with a server answering at the endpoint
+    pub enabled: bool,
+    /// JSON file with the counts, read by the server and the static generator
+    pub store: String,
+    /// Environment variable holding the salt the server hashes visitors' addresses with
+    pub salt_env: String,
+}
+
+impl ReactionsConfig {
+    /// The salt from the environment. The server refuses to count reactions without one rather than store hashes
+    /// anyone could recompute.
+    pub fn salt(&self) -> Result<String, String> {
+        std::env::var(&self.salt_env)
+            .ok()
+            .filter(|salt| !salt.is_empty())
+            .ok_or_else(|| format!("the reactions salt ${} isn't set", self.salt_env))
+    }
+}
+
+impl Default for ReactionsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            store: ".data/reactions.json".to_string(),
+            salt_env: "REACTIONS_SALT".to_string(),
+        }
+    }
+}
+
+/// Error reports from readers' browsers (see [`client_errors`](crate::api::client_errors)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct TelemetryConfig {
+    /// Share of page views that report errors, from 0 (none) to 1 (all). None by default, as the reports need the
+    /// endpoint deployed
+    pub sample_rate: f64,
+    /// JSON lines file the server appends reports to
+    pub log: String,
+}
+
+impl TelemetryConfig {
+    fn validate(&self) -> Vec<String> {
+        if (0.0..=1.0).contains(&self.sample_rate) {
+            Vec::new()
+        } else {
+            vec![format!(
+                "[telemetry] sample_rate must be between 0 and 1, not {}",
+                self.sample_rate
+            )]
+        }
+    }
+}
+
+impl Default for TelemetryConfig {
+    fn default() -> Self {
+        Self {
+            sample_rate: 0.0,
+            log: ".data/client-errors.jsonl".to_string(),
+        }
+    }
+}
+
+/// When the checker's `--freshness` pass calls content stale.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FreshnessConfig {
+    /// Days since content was last modified after which it needs a review
+    pub max_age_days: u32,
+}
+
+impl FreshnessConfig {
+    fn validate(&self) -> Vec<String> {
+        if self.max_age_days == 0 {
+            vec!["[freshness] max_age_days must be at least 1".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Default for FreshnessConfig {
+    fn default() -> Self {
+        Self { max_age_days: 365 }
+    }
+}
+
+/// The changelog page and the content files it follows (see [`changelog`](crate::ssg::changelog)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ChangelogConfig {
+    /// How many months back the page goes, counting the current one
+    pub months: u32,
+    pub sources: Vec<ChangelogSource>,
+}
+
+impl Default for ChangelogConfig {
+    fn default() -> Self {
+        Self {
+            months: 12,
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// Content whose changes are listed, and the page it's shown on.
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChangelogSource {
+    /// Shown as the link to `page`
+    pub name: String,
+    /// File or directory, relative to the repository root
+    pub path: String,
+    /// Route path of the page, e.g. `/about/`
+    pub page: String,
+}
+
+impl ChangelogSource {
+    /// Whether `file`, relative to the repository root, is this source or is in it.
+    pub fn contains(&self, file: &str) -> bool {
+        let path = self.path.trim_end_matches('/');
+        file == path
+            || file
+                .strip_prefix(path)
+                .is_some_and(|rest| rest.starts_with('/'))
+    }
+}
+
+impl ChangelogConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.months == 0 {
+            problems.push("[changelog] months must be at least 1".to_string());
+        }
+        for (index, source) in self.sources.iter().enumerate() {
+            if source.name.trim().is_empty() {
+                problems.push(format!(
+                    "[[changelog.sources]] `{}` has no name",
+                    source.path
+                ));
+            }
+            if source.path.trim().is_empty() || source.path.starts_with('/') {
+                problems.push(format!(
+                    "[[changelog.sources]] `{}` path `{}` must be relative to the repository root",
+                    source.name, source.path
+                ));
+            }
+            if !source.page.starts_with('/') {
+                problems.push(format!(
+                    "[[changelog.sources]] `{}` page `{}` must start with `/`",
+                    source.name, source.page
+                ));
+            }
+            if self.sources[..index]
+                .iter()
+                .any(|other| other.path == source.path)
+            {
+                problems.push(format!(
+                    "[[changelog.sources]] `{}` is listed twice",
+                    source.path
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// How `site watch-remote` polls for content changes and publishes them (see [`watch`](crate::ssg::watch)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    /// Minutes between polls
+    pub interval_minutes: u64,
+    /// Git remote and branch pulled before each poll; an empty remote polls the checkout as it is
+    pub remote: String,
+    pub branch: String,
+    /// Files and directories whose changes call for a build, relative to the repository root
+    pub paths: Vec<String>,
+    /// Shell command building the site
+    pub build: String,
+    /// Shell command publishing the build; empty when the build command publishes too
+    pub deploy: String,
+    /// JSON file remembering what was last published
+    pub state: String,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            interval_minutes: 15,
+            remote: "origin".to_string(),
+            branch: "main".to_string(),
+            paths: vec![
+                "src/posts.rs".to_string(),
+                "assets/data".to_string(),
+                "forms".to_string(),
+                "content".to_string(),
+            ],
+            build: "make deploy".to_string(),
+            deploy: "make push-docs".to_string(),
+            state: ".cache/watch/state.json".to_string(),
+        }
+    }
+}
+
+impl WatchConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.interval_minutes == 0 {
+            problems.push("[watch] interval_minutes must be at least 1".to_string());
+        }
+        if !self.remote.is_empty() && self.branch.trim().is_empty() {
+            problems.push(format!("[watch] remote `{}` needs a branch", self.remote));
+        }
+        if self.paths.is_empty() {
+            problems.push("[watch] paths must list the content to watch".to_string());
+        }
+        if self.build.trim().is_empty() {
+            problems.push("[watch] build must be a command".to_string());
+        }
+        problems
+    }
+}
+
+/// Where `site serve` listens for what the static files can't answer (see [`server`](crate::ssg::server)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ServerConfig {
+    /// `host:port` to listen on
+    pub address: String,
+    /// Addresses or ranges of the proxies in front, whose `X-Forwarded-For` names the client
+    pub trusted_proxies: Vec<String>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            address: "127.0.0.1:8787".to_string(),
+            trusted_proxies: vec!["127.0.0.1".to_string(), "::1".to_string()],
+        }
+    }
+}
+
+impl ServerConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.address.parse::<std::net::SocketAddr>().is_err() {
+            problems.push(format!(
+                "[server] address `{}` must be host:port, e.g. 127.0.0.1:8787",
+                self.address
+            ));
+        }
+        for proxy in &self.trusted_proxies {
+            if blocklist::parse_range(proxy).is_none() {
+                problems.push(format!(
+                    "[server] trusted_proxies: `{}` isn't an IP address or range",
+                    proxy
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// Page views counted by `site serve` (see [`analytics`](crate::ssg::analytics)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AnalyticsConfig {
+    /// Whether the pages send hits
+    pub enabled: bool,
+    /// SQLite database the hits are kept in
+    pub database: String,
+    /// MaxMind country database (`.mmdb`) the clients' countries are looked up in; empty for none
+    pub geoip_database: String,
+    /// Header a CDN in front sets to the client's country code (e.g. `CF-IPCountry`), read before the lookup; empty
+    /// for none
+    pub country_header: String,
+    /// Environment variable holding the password of the dashboard
+    pub password_env: String,
+}
+
+impl Default for AnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            database: ".data/analytics.sqlite3".to_string(),
+            geoip_database: String::new(),
+            country_header: String::new(),
+            password_env: "ANALYTICS_PASSWORD".to_string(),
+        }
+    }
+}
+
+impl AnalyticsConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.enabled && self.database.trim().is_empty() {
+            problems.push("[analytics] database must be a path".to_string());
+        }
+        if self.password_env.trim().is_empty() {
+            problems.push("[analytics] password_env must name an environment variable".to_string());
+        }
+        problems
+    }
+}
+
+/// Links to drafts served by `site serve` (see [`preview`](crate::ssg::preview)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct PreviewConfig {
+    /// Whether `site serve` renders drafts
+    pub enabled: bool,
+    /// Environment variable holding the secret the links are signed with; without it there are no previews
+    pub secret_env: String,
+}
+
+impl Default for PreviewConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            secret_env: "DRAFT_PREVIEW_SECRET".to_string(),
+        }
+    }
+}
+
+impl PreviewConfig {
+    fn validate(&self) -> Vec<String> {
+        if self.secret_env.trim().is_empty() {
+            vec!["[preview] secret_env must name a variable".to_string()]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// Where `site deploy` keeps the releases of the site and which one is live (see [`deploy`](crate::ssg::deploy)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DeployConfig {
+    /// Build output a release is made from
+    pub source: String,
+    /// Files copied into every release next to the build, by file name
+    pub extra_files: Vec<String>,
+    /// Directory with one subdirectory per release
+    pub releases: String,
+    /// Symlink to the live release, for the web server to serve
+    pub current: String,
+    /// Releases kept, the live one included; older ones are removed after a deploy
+    pub keep: usize,
+}
+
+impl Default for DeployConfig {
+    fn default() -> Self {
+        Self {
+            source: "static_output".to_string(),
+            extra_files: vec!["assets/robots.txt".to_string(), "CNAME".to_string()],
+            releases: "releases".to_string(),
+            current: "releases/current".to_string(),
+            keep: 5,
+        }
+    }
+}
+
+impl DeployConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.source.trim().is_empty() {
+            problems.push("[deploy] source must be a directory".to_string());
+        }
+        if self.releases.trim().is_empty() {
+            problems.push("[deploy] releases must be a directory".to_string());
+        }
+        if self.current.trim().is_empty() {
+            problems.push("[deploy] current must be a path".to_string());
+        }
+        if Path::new(&self.current) == Path::new(&self.source) {
+            problems.push("[deploy] current can't be the build output it copies".to_string());
+        }
+        // Rolling back needs the release before the live one
+        if self.keep < 2 {
+            problems.push(
+                "[deploy] keep must be at least 2, so there is a release to roll back to"
+                    .to_string(),
+            );
+        }
+        problems
+    }
+}
+
+/// What the privacy policy and imprint are filled in with (see [`legal`]).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct LegalConfig {
+    /// Address for questions about the policy and for the imprint
+    pub contact_email: String,
+    /// Name of the analytics service, or empty when there is none
+    pub analytics_provider: String,
+    /// When the policy last changed, as `YYYY-MM-DD`; empty for the day of the build
+    pub updated: String,
+}
+
+impl Default for LegalConfig {
+    fn default() -> Self {
+        Self {
+            contact_email: "contact@sydor.co".to_string(),
+            analytics_provider: String::new(),
+            updated: String::new(),
+        }
+    }
+}
+
+impl LegalConfig {
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.contact_email.contains('@') {
+            problems.push(format!(
+                "[legal] contact_email `{}` isn't an email address",
+                self.contact_email
+            ));
+        }
+        if !self.updated.is_empty() && self.updated.parse::<NaiveDate>().is_err() {
+            problems.push(format!(
+                "[legal] updated `{}` isn't a date (YYYY-MM-DD)",
+                self.updated
+            ));
+        }
+        problems
+    }
+}
+
+/// How often a page is expected to change, as a sitemap `<changefreq>` hint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeFreq {
+    Always,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+    Never,
+}
+
+impl ChangeFreq {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ChangeFreq::Always => "always",
+            ChangeFreq::Hourly => "hourly",
+            ChangeFreq::Daily => "daily",
+            ChangeFreq::Weekly => "weekly",
+            ChangeFreq::Monthly => "monthly",
+            ChangeFreq::Yearly => "yearly",
+            ChangeFreq::Never => "never",
+        }
+    }
+}
+
+/// `<priority>` and `<changefreq>` hints in `sitemap.xml`. Without any, URLs are listed without hints.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct SitemapConfig {
+    /// Priority of pages no route sets one for
+    pub priority: Option<f32>,
+    /// Change frequency of pages no route sets one for
+    pub changefreq: Option<ChangeFreq>,
+    pub routes: Vec<SitemapRoute>,
+    /// Leave out posts syndicated from elsewhere, whose canonical address is the original
+    pub exclude_syndicated: bool,
+}
+
+/// Hints for the pages matching `path`: a route path such as `/about/`, or a collection such as `/blog/*`, which
+/// matches every page below `/blog/` but not `/blog/` itself.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SitemapRoute {
+    pub path: String,
+    pub priority: Option<f32>,
+    pub changefreq: Option<ChangeFreq>,
+}
+
+impl SitemapRoute {
+    /// How well the route matches the page at `path`: `None` when it doesn't, and higher for exact paths and longer
+    /// collections.
+    fn specificity(&self, path: &str) -> Option<usize> {
+        let path = path.trim_end_matches('/');
+        match self.path.strip_suffix('*') {
+            Some(prefix) => {
+                let prefix = prefix.trim_end_matches('/');
+                let below = path.strip_prefix(prefix)?;
+                (below.len() > 1 && below.starts_with('/')).then_some(prefix.len())
+            }
+            // Exact matches beat every collection
+            None => (self.path.trim_end_matches('/') == path).then_some(usize::MAX),
+        }
+    }
+}
+
+impl SitemapConfig {
+    /// Priority and change frequency for the page at route `path`. Each comes from the most specific route that sets
+    /// it, or the section-wide default.
+    pub fn hints(&self, path: &str) -> (Option<f32>, Option<ChangeFreq>) {
+        let mut matching: Vec<(usize, &SitemapRoute)> = self
+            .routes
+            .iter()
+            .filter_map(|route| Some((route.specificity(path)?, route)))
+            .collect();
+        matching.sort_by_key(|(specificity, _)| std::cmp::Reverse(*specificity));
+
+        let priority = matching
+            .iter()
+            .find_map(|(_, route)| route.priority)
+            .or(self.priority);
+        let changefreq = matching
+            .iter()
+            .find_map(|(_, route)| route.changefreq)
+            .or(self.changefreq);
+        (priority, changefreq)
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        let out_of_range =
+            |priority: Option<f32>| priority.is_some_and(|p| !(0.0..=1.0).contains(&p));
+        if out_of_range(self.priority) {
+            problems.push("[sitemap] priority must be between 0.0 and 1.0".to_string());
+        }
+        for route in &self.routes {
+            if !route.path.starts_with('/') {
+                problems.push(format!(
+                    "[[sitemap.routes]] path `{}` must start with /",
+                    route.path
+                ));
+            }
+            if route.path.trim_end_matches('*').contains('*') {
+                problems.push(format!(
+                    "[[sitemap.routes]] path `{}` may only end in *",
+                    route.path
+                ));
+            }
+            if out_of_range(route.priority) {
+                problems.push(format!(
+                    "[[sitemap.routes]] `{}` priority must be between 0.0 and 1.0",
+                    route.path
+                ));
+            }
+        }
+
+        problems
+    }
+}
+
+/// Where contact messages go: submissions of the contact form by the reason for contact selected in it, submissions
+/// of the other forms in `forms/` by form. Every submission also goes to the webhook and the store, if they are
+/// configured, at the same time as the email (see [`notify`](crate::ssg::notify)).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct ContactConfig {
+    pub routes: BTreeMap<String, ContactRoute>,
+    /// Route of each form other than the contact form, by form id
+    pub forms: BTreeMap<String, ContactRoute>,
+    pub webhook: WebhookConfig,
+    pub smtp: SmtpConfig,
+    pub store: StoreConfig,
+    pub attachments: AttachmentsConfig,
+    pub replies: RepliesConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ContactRoute {
+    /// Email addresses that receive messages for this reason; may be empty when a webhook takes the submissions. Left
+    /// out, the address in `assets/data/about.json`
+    #[serde(default = "default_recipients")]
+    pub recipients: Vec<String>,
+    /// Name of the template in [`CONTACT_TEMPLATES_DIR`] used for the email
+    #[serde(default = "default_contact_template")]
+    pub template: String,
+}
+
+fn default_contact_template() -> String {
+    "general".to_string()
+}
+
+fn default_recipients() -> Vec<String> {
+    vec![ABOUT.contact.email.to_string()]
+}
+
+/// Contact submissions posted as JSON to a webhook (see [`webhook`](crate::ssg::webhook)). Off while `url` is empty.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    pub url: String,
+    pub format: WebhookFormat,
+    /// Environment variable holding the secret the body is signed with; empty to send unsigned
+    pub secret_env: String,
+    /// Attempts per submission before it goes to the dead letters
+    pub max_attempts: u32,
+    /// JSON lines file of the submissions that couldn't be delivered
+    pub dead_letters: String,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            format: WebhookFormat::default(),
+            secret_env: "CONTACT_WEBHOOK_SECRET".to_string(),
+            max_attempts: 4,
+            dead_letters: ".data/webhook-dead-letters.jsonl".to_string(),
+        }
+    }
+}
+
+/// What the webhook body looks like.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    /// The submission with its form and reason, for Zapier, n8n and other automation tools
+    #[default]
+    Json,
+    /// A chat message for a Discord channel webhook
+    Discord,
+    /// A chat message for a Slack incoming webhook
+    Slack,
+}
+
+impl WebhookConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.url.is_empty()
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.is_enabled()
+            && !self.url.starts_with("https://")
+            && !self.url.starts_with("http://")
+        {
+            problems.push(format!(
+                "[contact.webhook] url `{}` isn't an http(s) URL",
+                self.url
+            ));
+        }
+        if self.max_attempts == 0 {
+            problems.push("[contact.webhook] max_attempts must be at least 1".to_string());
+        }
+        problems
+    }
+}
+
+/// The server the notification emails are sent through. Emails are off while `url` is empty.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct SmtpConfig {
+    /// `smtps://host:465`, or `smtp://host:587` to upgrade with STARTTLS
+    pub url: String,
+    /// Address the notifications are sent from; replies go to whoever wrote in
+    pub from: String,
+    pub username: String,
+    /// Environment variable holding the password; empty for a server that doesn't need one
+    pub password_env: String,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            from: "contact@sydor.co".to_string(),
+            username: String::new(),
+            password_env: "CONTACT_SMTP_PASSWORD".to_string(),
+        }
+    }
+}
+
+impl SmtpConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.url.is_empty()
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.is_enabled()
+            && !self.url.starts_with("smtps://")
+            && !self.url.starts_with("smtp://")
+        {
+            problems.push(format!(
+                "[contact.smtp] url `{}` isn't an smtp(s) URL",
+                self.url
+            ));
+        }
+        if self.is_enabled() && !self.from.contains('@') {
+            problems.push(format!(
+                "[contact.smtp] from `{}` isn't an email address",
+                self.from
+            ));
+        }
+        problems
+    }
+}
+
+/// Files kept on the server: every submission as it arrived, and how its delivery to each channel went.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct StoreConfig {
+    /// JSON lines file every submission is appended to; empty to keep none
+    pub submissions: String,
+    /// JSON lines file of the channels each submission was delivered to, which `site notify retry` works through
+    pub deliveries: String,
+    /// JSON lines file of the rules submissions are turned away by (see [`blocklist`](crate::ssg::blocklist))
+    pub blocklist: String,
+    /// JSON lines file every blocked submission is logged to
+    pub blocked: String,
+    /// Directory the files sent with accepted submissions are saved to; empty to keep none
+    pub attachments: String,
+}
+
+impl Default for StoreConfig {
+    fn default() -> Self {
+        Self {
+            submissions: ".data/contact-submissions.jsonl".to_string(),
+            deliveries: ".data/contact-deliveries.jsonl".to_string(),
+            blocklist: ".data/contact-blocklist.jsonl".to_string(),
+            blocked: ".data/contact-blocked.jsonl".to_string(),
+            attachments: ".data/contact-attachments".to_string(),
+        }
+    }
+}
+
+impl StoreConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.submissions.is_empty()
+    }
+
+    fn validate(&self) -> Vec<String> {
+        [
+            ("deliveries", &self.deliveries),
+            ("blocklist", &self.blocklist),
+            ("blocked", &self.blocked),
+        ]
+        .into_iter()
+        .filter(|(_, file)| file.is_empty())
+        .map(|(key, _)| format!("[contact.store] {} must name a file", key))
+        .collect()
+    }
+}
+
+/// Answering contact submissions from the reply page of `site serve` (see [`replies`](crate::ssg::replies)). Off while
+/// `url` is empty.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct RepliesConfig {
+    /// Absolute URL the reply pages are served under, which the notification emails link to with a token after it
+    pub url: String,
+    /// Environment variable holding the secret the tokens are signed with; without it the emails carry no link
+    pub secret_env: String,
+    /// Days a reply link works after the submission came in
+    pub max_age_days: u32,
+    /// JSON lines file of the replies sent
+    pub log: String,
+}
+
+impl Default for RepliesConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            secret_env: "CONTACT_REPLY_SECRET".to_string(),
+            max_age_days: 90,
+            log: ".data/contact-replies.jsonl".to_string(),
+        }
+    }
+}
+
+impl RepliesConfig {
+    pub fn is_enabled(&self) -> bool {
+        !self.url.is_empty()
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if !self.is_enabled() {
+            return problems;
+        }
+        if !(self.url.starts_with("https://") || self.url.starts_with("http://"))
+            || !self.url.ends_with('/')
+        {
+            problems.push(format!(
+                "[contact.replies] url `{}` must be an http(s) URL ending in /",
+                self.url
+            ));
+        }
+        if self.secret_env.is_empty() {
+            problems.push("[contact.replies] secret_env must name a variable".to_string());
+        }
+        if self.max_age_days == 0 {
+            problems.push("[contact.replies] max_age_days must be at least 1".to_string());
+        }
+        if self.log.is_empty() {
+            problems.push("[contact.replies] log must name a file".to_string());
+        }
+        problems
+    }
+}
+
+/// What files sent with a submission may be, checked by the [`attachments`](crate::ssg::attachments) scanners before
+/// the submission is stored or sent anywhere.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct AttachmentsConfig {
+    /// Largest file accepted, in megabytes
+    pub max_size_mb: u64,
+    /// Types a file's content may be, whatever its name or declared type says
+    pub accept: Vec<FileType>,
+    /// Unix socket of a ClamAV daemon every file is streamed to; empty to skip the virus scan
+    pub clamav_socket: String,
+}
+
+impl Default for AttachmentsConfig {
+    fn default() -> Self {
+        Self {
+            max_size_mb: 5,
+            accept: vec![
+                FileType::Pdf,
+                FileType::Png,
+                FileType::Jpeg,
+                FileType::Gif,
+                FileType::Webp,
+                FileType::Text,
+            ],
+            clamav_socket: String::new(),
+        }
+    }
+}
+
+impl AttachmentsConfig {
+    pub fn max_bytes(&self) -> u64 {
+        self.max_size_mb * 1024 * 1024
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.max_size_mb == 0 {
+            problems.push("[contact.attachments] max_size_mb must be at least 1".to_string());
+        }
+        if self.accept.is_empty() {
+            problems.push("[contact.attachments] accept must list at least one type".to_string());
+        }
+        problems
+    }
+}
+
+impl Default for ContactConfig {
+    fn default() -> Self {
+        let route = ContactRoute {
+            recipients: default_recipients(),
+            template: default_contact_template(),
+        };
+        Self {
+            routes: REASONS
+                .iter()
+                .map(|reason| (reason.id.to_string(), route.clone()))
+                .collect(),
+            forms: other_forms()
+                .map(|schema| (schema.id.clone(), route.clone()))
+                .collect(),
+            webhook: WebhookConfig::default(),
+            smtp: SmtpConfig::default(),
+            store: StoreConfig::default(),
+            attachments: AttachmentsConfig::default(),
+            replies: RepliesConfig::default(),
+        }
+    }
+}
+
+impl ContactConfig {
+    /// Route for a submitted reason, if it's a known one.
+    pub fn route(&self, reason: &str) -> Option<&ContactRoute> {
+        self.routes.get(reason)
+    }
+
+    /// Route for a submission: by its form, or by its reason when it comes from the contact form.
+    pub fn route_for(&self, request: &ContactRequest) -> Option<&ContactRoute> {
+        match request.schema() {
+            Some(schema) if schema.id == CONTACT_FORM => self.route(&request.reason),
+            Some(schema) => self.forms.get(&schema.id),
+            None => None,
+        }
+    }
+
+    /// Check that every reason offered in the form and every other form reaches at least one recipient through an
+    /// existing template (or the webhook), and that no route is configured for a reason or form that doesn't exist. The templates,
+    /// including the confirmation email, are rendered with sample data to catch mistakes in them.
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+
+        for reason in REASONS {
+            if !self.routes.contains_key(reason.id) {
+                problems.push(format!(
+                    "reason `{}` has no [contact.routes.{}] entry",
+                    reason.id, reason.id
+                ));
+            }
+        }
+        for schema in other_forms() {
+            if !self.forms.contains_key(&schema.id) {
+                problems.push(format!(
+                    "form `{}` has no [contact.forms.{}] entry",
+                    schema.id, schema.id
+                ));
+            }
+        }
+
+        for (id, route) in &self.routes {
+            if !REASONS.iter().any(|reason| reason.id == id) {
+                problems.push(format!(
+                    "[contact.routes.{}] doesn't match any reason for contact",
+                    id
+                ));
+            }
+            problems.extend(route.validate(
+                &format!("[contact.routes.{}]", id),
+                id,
+                self.webhook.is_enabled(),
+            ));
+        }
+        for (id, route) in &self.forms {
+            match other_forms().find(|schema| schema.id == *id) {
+                Some(schema) => {
+                    let reason = schema
+                        .defaults
+                        .get("reason")
+                        .map_or(DEFAULT_REASON, String::as_str);
+                    problems.extend(route.validate(
+                        &format!("[contact.forms.{}]", id),
+                        reason,
+                        self.webhook.is_enabled(),
+                    ));
+                }
+                None => problems.push(format!(
+                    "[contact.forms.{}] doesn't match any form in forms/",
+                    id
+                )),
+            }
+        }
+
+        if let Err(e) = check_template(email::CONFIRMATION_TEMPLATE, DEFAULT_REASON) {
+            problems.push(format!("confirmation email template: {}", e));
+        }
+        problems.extend(self.webhook.validate());
+        problems.extend(self.smtp.validate());
+        problems.extend(self.store.validate());
+        problems.extend(self.attachments.validate());
+        problems.extend(self.replies.validate());
+        if self.replies.is_enabled() && !self.store.is_enabled() {
+            problems.push(
+                "[contact.replies] needs [contact.store] submissions, where the messages are replied to from"
+                    .to_string(),
+            );
+        }
+
+        problems
+    }
+}
+
+impl ContactRoute {
+    /// Problems with the route in `section`, rendering its template with the sample for `reason`. Without `webhook`
+    /// the recipients are the only way the messages arrive, so there must be some.
+    fn validate(&self, section: &str, reason: &str, webhook: bool) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.recipients.is_empty() && !webhook {
+            problems.push(format!("{} has no recipients", section));
+        }
+        for recipient in &self.recipients {
+            if !recipient.contains('@') {
+                problems.push(format!(
+                    "{} recipient `{}` isn't an email address",
+                    section, recipient
+                ));
+            }
+        }
+        if let Err(e) = check_template(&self.template, reason) {
+            problems.push(format!("{} template `{}`: {}", section, self.template, e));
+        }
+        problems
+    }
+}
+
+/// The forms in `forms/` besides the contact form, which is routed by reason.
+fn other_forms() -> impl Iterator<Item = &'static FormSchema> {
+    FormSchema::all()
+        .iter()
+        .filter(|schema| schema.id != CONTACT_FORM)
+}
+
+/// Load the template and render it with sample data for `reason`, so syntax errors show up at load time too.
+fn check_template(name: &str, reason: &str) -> Result<(), Box<dyn std::error::Error>> {
+    EmailTemplate::load(name)?.render(&email::sample_request(reason))?;
+    Ok(())
+}
+
+impl SiteConfig {
+    /// Load the config from `path`, falling back to the defaults when the file doesn't exist. Contact routing is
+    /// validated here so a reason without recipients fails the build instead of dropping messages later.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let source = fs::read_to_string(path)?;
+        let mut config: Self =
+            toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e))?;
+        config.license.default_holder(&config.site.author);
+
+        let mut problems = config.contact.validate();
+        problems.extend(config.i18n.validate());
+        problems.extend(config.assets.validate());
+        problems.extend(config.headers.validate());
+        problems.extend(config.meta.validate());
+        problems.extend(config.social.validate());
+        problems.extend(config.tags.validate());
+        problems.extend(config.sitemap.validate());
+        problems.extend(config.blogroll.validate());
+        problems.extend(config.telemetry.validate());
+        problems.extend(config.freshness.validate());
+        problems.extend(config.reading_progress.validate());
+        problems.extend(config.responsive_images.validate());
+        problems.extend(config.changelog.validate());
+        problems.extend(config.watch.validate());
+        problems.extend(config.server.validate());
+        problems.extend(config.analytics.validate());
+        problems.extend(config.preview.validate());
+        problems.extend(config.deploy.validate());
+        problems.extend(config.legal.validate());
+        if problems.is_empty() {
+            problems.extend(legal::validate(&config));
+        }
+        problems.extend(config.source.validate());
+        problems.extend(hints::validate(&config.hints));
+        if !problems.is_empty() {
+            return Err(format!("{}: {}", path.display(), problems.join("; ")).into());
+        }
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,82 @@
+//! Writes the JSON content API described in [`crate::api::content`].
+
+use crate::api::content::{self, PostDocument, PostIndex, PostSummary};
+use crate::posts::Post;
+use crate::ssg::feed::absolutize_urls;
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+const ABOUT_DATA: &str = include_str!("../../assets/data/about.json");
+
+/// Writes the post index, one document per post and the about data. `posts` should only hold published posts.
+pub struct ContentApiHook {
+    pub posts: Vec<Post>,
+}
+
+impl BuildHook for ContentApiHook {
+    fn name(&self) -> &str {
+        "api"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        let base_url = site.config.site.base_url.trim_end_matches('/');
+        let style = site.config.build.output_style;
+
+        let summaries: Vec<PostSummary> = self
+            .posts
+            .iter()
+            .map(|post| PostSummary {
+                slug: post.slug.to_string(),
+                title: post.title.to_string(),
+                published: post.published,
+                summary: post.excerpt(),
+                url: format!("{}{}", base_url, style.page_url(&post.path())),
+                api_path: content::post_path(post.slug),
+            })
+            .collect();
+
+        for (post, summary) in self.posts.iter().zip(&summaries) {
+            let document = PostDocument {
+                summary: summary.clone(),
+                body_html: absolutize_urls(
+                    &post.body_html(),
+                    base_url,
+                    &style.page_url(&post.path()),
+                ),
+            };
+            write_json(site.output_dir, &summary.api_path, &document)?;
+        }
+
+        write_json(
+            site.output_dir,
+            content::POSTS_INDEX_PATH,
+            &PostIndex { posts: summaries },
+        )?;
+
+        // Parsed rather than copied, so a broken data file fails the build instead of being served
+        let about: serde_json::Value = serde_json::from_str(ABOUT_DATA)
+            .map_err(|e| format!("assets/data/about.json: {}", e))?;
+        write_json(site.output_dir, content::ABOUT_PATH, &about)?;
+
+        println!(
+            "✅ Generated: JSON API for {} posts under /api/",
+            self.posts.len()
+        );
+        Ok(())
+    }
+}
+
+fn write_json(
+    output_dir: &Path,
+    path: &str,
+    value: &impl Serialize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let file = output_dir.join(path.trim_start_matches('/'));
+    if let Some(parent) = file.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file, serde_json::to_string_pretty(value)?)?;
+    Ok(())
+}
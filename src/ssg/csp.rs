@@ -0,0 +1,155 @@
+//! Output for a strict Content Security Policy.
+//!
+//! With `csp_mode = "strict"` under `[headers]`, every inline `<style>` and `<script>` block in the generated pages is
+//! moved into a file under `assets/` named after a hash of its contents and referenced from where the block was. The
+//! files count as `'self'`, so the policy needs no `'unsafe-inline'`, the headers file caches them as immutable, and
+//! the integrity hook hashes them like any other asset. Identical blocks on different pages share one file.
+
+use crate::ssg::config::CspMode;
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use crate::ssg::html;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+
+/// `type` values of scripts the browser runs. Other types (`application/ld+json`) are data and stay inline.
+const SCRIPT_TYPES: &[&str] = &["", "text/javascript", "application/javascript", "module"];
+
+/// Moves inline code out of every page in strict mode; does nothing in relaxed mode. Register it before the headers
+/// and integrity hooks so they see the new files.
+pub struct CspHook;
+
+impl BuildHook for CspHook {
+    fn name(&self) -> &str {
+        "csp"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        if site.config.headers.csp_mode != CspMode::Strict {
+            return Ok(());
+        }
+
+        let moved = externalize_in(site.output_dir, site.output_dir)?;
+        println!(
+            "🛡️  Moved {} inline blocks into assets for a strict CSP",
+            moved
+        );
+        Ok(())
+    }
+}
+
+fn externalize_in(dir: &Path, output_dir: &Path) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut moved = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            moved += externalize_in(&path, output_dir)?;
+        } else if path.extension().is_some_and(|ext| ext == "html") {
+            let original = fs::read_to_string(&path)?;
+            let (updated, count) = externalize_inline(&original, output_dir)?;
+            if count > 0 {
+                fs::write(&path, updated)?;
+                moved += count;
+            }
+        }
+    }
+    Ok(moved)
+}
+
+/// Return `page` with its inline styles and scripts replaced by references to files written to `output_dir/assets`,
+/// plus the number of blocks moved. The blocks are moved verbatim, so relative `url()`s in inline CSS would resolve
+/// against `/assets/` afterwards; the generator only emits root-relative ones.
+pub fn externalize_inline(
+    page: &str,
+    output_dir: &Path,
+) -> Result<(String, usize), Box<dyn std::error::Error>> {
+    let lower = page.to_ascii_lowercase();
+    let mut spans: Vec<_> = html::tag_spans(page, "style")
+        .into_iter()
+        .map(|span| ("style", span))
+        .collect();
+    spans.extend(
+        html::tag_spans(page, "script")
+            .into_iter()
+            .map(|span| ("script", span)),
+    );
+    spans.sort_by_key(|(_, span)| span.start);
+
+    let mut output = String::with_capacity(page.len());
+    let mut last = 0;
+    let mut moved = 0;
+
+    for (name, span) in spans {
+        // Tags inside a block that was already moved
+        if span.start < last {
+            continue;
+        }
+
+        let tag = &page[span.clone()];
+        let Some(close) = lower[span.end..].find(&format!("</{}", name)) else {
+            continue;
+        };
+        let content_end = span.end + close;
+        let Some(close_len) = lower[content_end..].find('>') else {
+            continue;
+        };
+        let content = &page[span.end..content_end];
+
+        let replacement = match name {
+            "style" => {
+                let href = write_asset(output_dir, content, "css")?;
+                let media = html::attr(tag, "media")
+                    .map(|media| format!(r#" media="{}""#, media))
+                    .unwrap_or_default();
+                format!(r#"<link rel="stylesheet" href="{}"{}>"#, href, media)
+            }
+            _ => {
+                let kind = html::attr(tag, "type").unwrap_or("").to_ascii_lowercase();
+                if html::attr(tag, "src").is_some()
+                    || !SCRIPT_TYPES.contains(&kind.as_str())
+                    || content.trim().is_empty()
+                {
+                    continue;
+                }
+                let src = write_asset(output_dir, content, "js")?;
+                let kind = if kind == "module" {
+                    r#" type="module""#
+                } else {
+                    ""
+                };
+                format!(r#"<script src="{}"{}></script>"#, src, kind)
+            }
+        };
+
+        output.push_str(&page[last..span.start]);
+        output.push_str(&replacement);
+        last = content_end + close_len + 1;
+        moved += 1;
+    }
+
+    output.push_str(&page[last..]);
+    Ok((output, moved))
+}
+
+/// Write `content` to `assets/inline-dxh<hash>.<extension>` and return its site path. The name follows the same
+/// `-dxh<hex>` convention as the Dioxus-built assets, so the headers file treats it as fingerprinted.
+fn write_asset(
+    output_dir: &Path,
+    content: &str,
+    extension: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let hash: String = Sha256::digest(content.as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let name = format!("inline-dxh{}.{}", hash, extension);
+
+    let assets_dir = output_dir.join("assets");
+    fs::create_dir_all(&assets_dir)?;
+    let path = assets_dir.join(&name);
+    if !path.exists() {
+        fs::write(path, content)?;
+    }
+
+    Ok(format!("/assets/{}", name))
+}
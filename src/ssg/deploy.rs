@@ -0,0 +1,187 @@
+//! Atomic deploys: `site deploy` copies the build into a new release directory under `[deploy] releases`, and only
+//! once it's complete points the `[deploy] current` symlink at it. The web server serves `current`, so visitors see
+//! either the old site or the new one, never a mix of the two, and a build that fails halfway never reaches them.
+//!
+//! Releases are named after the time of the deploy and the commit built, e.g. `20261015-093000-df9b2bc`, so they sort
+//! oldest first. `site deploy --rollback` points `current` back at the release before the live one; the newer ones are
+//! kept until they age out of `[deploy] keep`.
+//!
+//! The symlink is replaced with a rename, which is atomic on the same filesystem, so `current` must live on the same
+//! filesystem as the releases. GitHub Pages serves the committed `docs/` folder and can't follow a symlink; `make
+//! deploy` stays the way to publish there.
+
+use crate::ssg::config::DeployConfig;
+use chrono::{DateTime, Utc};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Suffix of a release still being copied; [`releases`] leaves these out.
+const PARTIAL_SUFFIX: &str = ".partial";
+
+/// A complete release of the site.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// Directory name, e.g. `20261015-093000-df9b2bc`
+    pub name: String,
+    pub path: PathBuf,
+}
+
+/// The complete releases in `[deploy] releases`, oldest first.
+pub fn releases(config: &DeployConfig) -> Result<Vec<Release>, String> {
+    let dir = Path::new(&config.releases);
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+
+    let mut releases: Vec<Release> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|kind| kind.is_dir()))
+        .map(|entry| Release {
+            name: entry.file_name().to_string_lossy().into_owned(),
+            path: entry.path(),
+        })
+        .filter(|release| !release.name.ends_with(PARTIAL_SUFFIX))
+        .collect();
+    releases.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(releases)
+}
+
+/// The release `[deploy] current` points at, or `None` before the first deploy.
+pub fn live(config: &DeployConfig) -> Result<Option<Release>, String> {
+    let current = Path::new(&config.current);
+    let target = match fs::read_link(current) {
+        Ok(target) => target,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => {
+            return Err(format!(
+                "{} isn't a symlink ({}); move it aside so deploys can manage it",
+                current.display(),
+                e
+            ))
+        }
+    };
+    let name = target
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    Ok(releases(config)?
+        .into_iter()
+        .find(|release| release.name == name))
+}
+
+/// Copy `[deploy] source` and `extra_files` into a new release for `commit`. The copy is made under a temporary name
+/// and renamed when complete, so an interrupted deploy leaves no release behind.
+pub fn prepare(config: &DeployConfig, commit: &str, now: DateTime<Utc>) -> Result<Release, String> {
+    let source = Path::new(&config.source);
+    if !source.join("index.html").is_file() {
+        return Err(format!(
+            "{} has no index.html; build the site before deploying",
+            source.display()
+        ));
+    }
+
+    let name = format!("{}-{}", now.format("%Y%m%d-%H%M%S"), commit);
+    let path = Path::new(&config.releases).join(&name);
+    if path.exists() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    let partial = Path::new(&config.releases).join(format!("{}{}", name, PARTIAL_SUFFIX));
+    if partial.exists() {
+        fs::remove_dir_all(&partial).map_err(|e| format!("{}: {}", partial.display(), e))?;
+    }
+
+    copy_dir(source, &partial)?;
+    for file in &config.extra_files {
+        let file = Path::new(file);
+        let Some(file_name) = file.file_name() else {
+            continue;
+        };
+        fs::copy(file, partial.join(file_name))
+            .map_err(|e| format!("{}: {}", file.display(), e))?;
+    }
+    fs::rename(&partial, &path).map_err(|e| format!("{}: {}", path.display(), e))?;
+
+    Ok(Release { name, path })
+}
+
+/// Point `[deploy] current` at `release`, replacing the old link in one step.
+pub fn activate(config: &DeployConfig, release: &Release) -> Result<(), String> {
+    let current = Path::new(&config.current);
+    // Fails on a real directory, which a deploy shouldn't replace
+    live(config)?;
+
+    let target = fs::canonicalize(&release.path)
+        .map_err(|e| format!("{}: {}", release.path.display(), e))?;
+    let link = current.with_file_name(format!(
+        ".{}.next",
+        current
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    ));
+    if let Some(dir) = current.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+    }
+    if fs::symlink_metadata(&link).is_ok() {
+        fs::remove_file(&link).map_err(|e| format!("{}: {}", link.display(), e))?;
+    }
+    symlink(&target, &link)?;
+    fs::rename(&link, current).map_err(|e| format!("{}: {}", current.display(), e))
+}
+
+/// The release before the live one, to roll back to.
+pub fn previous(config: &DeployConfig) -> Result<Release, String> {
+    let live = live(config)?.ok_or("nothing is deployed yet")?;
+    releases(config)?
+        .into_iter()
+        .rfind(|release| release.name < live.name)
+        .ok_or_else(|| format!("{} is the oldest release kept", live.name))
+}
+
+/// Remove the oldest releases beyond `[deploy] keep`, never the live one. Returns the names of the removed releases.
+pub fn prune(config: &DeployConfig) -> Result<Vec<String>, String> {
+    let live = live(config)?;
+    let releases = releases(config)?;
+    let excess = releases.len().saturating_sub(config.keep);
+
+    let mut removed = Vec::new();
+    for release in releases.into_iter().take(excess) {
+        if Some(&release) == live.as_ref() {
+            continue;
+        }
+        fs::remove_dir_all(&release.path)
+            .map_err(|e| format!("{}: {}", release.path.display(), e))?;
+        removed.push(release.name);
+    }
+    Ok(removed)
+}
+
+fn copy_dir(from: &Path, to: &Path) -> Result<(), String> {
+    fs::create_dir_all(to).map_err(|e| format!("{}: {}", to.display(), e))?;
+    for entry in fs::read_dir(from).map_err(|e| format!("{}: {}", from.display(), e))? {
+        let entry = entry.map_err(|e| format!("{}: {}", from.display(), e))?;
+        let target = to.join(entry.file_name());
+        if entry.path().is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), &target)
+                .map_err(|e| format!("{}: {}", entry.path().display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> Result<(), String> {
+    std::os::unix::fs::symlink(target, link).map_err(|e| format!("{}: {}", link.display(), e))
+}
+
+#[cfg(not(unix))]
+fn symlink(_target: &Path, link: &Path) -> Result<(), String> {
+    Err(format!(
+        "{}: atomic deploys need symlinks, which this platform doesn't have",
+        link.display()
+    ))
+}
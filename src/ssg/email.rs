@@ -0,0 +1,234 @@
+//! Contact emails rendered from the templates in [`CONTACT_TEMPLATES_DIR`].
+//!
+//! A template comes in two variants that are sent together: `<name>.txt`, whose first line is `Subject: ...`, and
+//! `<name>.html`. The routes in `[contact.routes]` and `[contact.forms]` pick the template of the notification sent to
+//! the recipients; [`CONFIRMATION_TEMPLATE`] is the copy sent back to whoever wrote in.
+//!
+//! Templates use a small Handlebars-like syntax over the fields of a [`ContactRequest`]:
+//!
+//! - `{name}` inserts a field (`name`, `email`, `reason`, `subject`, `message`, `phone`, or any other field declared in
+//!   in `forms/`); the HTML variant escapes it (see [`sanitize::html_text`])
+//! - `{reply_url}` is the link to answer the sender from the site (see [`replies`](crate::ssg::replies)), empty when
+//!   replies are off
+//! - `{#if phone}...{/if}` keeps its contents only when the field isn't empty
+//!
+//! Any other braces, such as CSS in a `<style>` block, are left as they are. An unknown field or an unclosed block is
+//! an error, so `SiteConfig::load` catches a broken template before it could drop a message.
+
+use crate::api::ContactRequest;
+use crate::contact_form::{FieldKind, FieldSpec, FormSchema, CORE_FIELDS};
+use crate::contact_reasons;
+use crate::ssg::config::CONTACT_TEMPLATES_DIR;
+use crate::ssg::sanitize;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Template of the confirmation sent to the sender of a message.
+pub const CONFIRMATION_TEMPLATE: &str = "confirmation";
+
+/// A rendered email, ready to send as `multipart/alternative`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Email {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+/// Both variants of one template, as read from disk.
+#[derive(Debug, Clone)]
+pub struct EmailTemplate {
+    pub name: String,
+    text: String,
+    html: String,
+}
+
+impl EmailTemplate {
+    /// Read the template called `name` from [`CONTACT_TEMPLATES_DIR`].
+    pub fn load(name: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let read = |path: PathBuf| {
+            fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))
+        };
+        Ok(Self {
+            name: name.to_string(),
+            text: read(template_path(name, "txt"))?,
+            html: read(template_path(name, "html"))?,
+        })
+    }
+
+    /// Fill in both variants with the fields of `request`, without a reply link.
+    pub fn render(&self, request: &ContactRequest) -> Result<Email, Box<dyn std::error::Error>> {
+        self.render_with_reply_url(request, "")
+    }
+
+    /// Fill in both variants with the fields of `request` and `reply_url`.
+    pub fn render_with_reply_url(
+        &self,
+        request: &ContactRequest,
+        reply_url: &str,
+    ) -> Result<Email, Box<dyn std::error::Error>> {
+        let mut fields = fields(request);
+        fields.push(("reply_url", reply_url.to_string()));
+        let text = render(&self.text, &fields, false)
+            .map_err(|e| format!("{}: {}", template_path(&self.name, "txt").display(), e))?;
+        let html = render(&self.html, &fields, true)
+            .map_err(|e| format!("{}: {}", template_path(&self.name, "html").display(), e))?;
+
+        let (subject, text) = text
+            .strip_prefix("Subject:")
+            .and_then(|rest| rest.split_once('\n'))
+            .ok_or_else(|| {
+                format!(
+                    "{}: the first line must be `Subject: ...`",
+                    template_path(&self.name, "txt").display()
+                )
+            })?;
+
+        Ok(Email {
+            subject: sanitize::header_text(subject),
+            text: text.trim_start_matches('\n').to_string(),
+            html,
+        })
+    }
+}
+
+/// Path of one variant (`txt` or `html`) of the template called `name`.
+pub fn template_path(name: &str, extension: &str) -> PathBuf {
+    Path::new(CONTACT_TEMPLATES_DIR).join(format!("{}.{}", name, extension))
+}
+
+/// A made-up submission for the given reason, for previews and for checking templates. The fields of the forms other
+/// than the contact form are filled in too.
+pub fn sample_request(reason: &str) -> ContactRequest {
+    ContactRequest {
+        name: "Ada Lovelace".to_string(),
+        email: "ada@example.com".to_string(),
+        reason: reason.to_string(),
+        subject: "Your post about <Dioxus> islands".to_string(),
+        message: "Hi!\n\nI enjoyed the post about mounting islands on static pages. \
+                  Would the same approach work for a documentation site?\n\nThanks, Ada"
+            .to_string(),
+        phone_country: "GB".to_string(),
+        phone: "07700 900123".to_string(),
+        form: String::new(),
+        extra: extra_fields()
+            .map(|field| (field.name.clone(), sample_value(field)))
+            .collect(),
+    }
+}
+
+/// The values of the fields a template can use for `request`: the reason by its label, the phone number normalized
+/// when it's valid, a select by the label of the chosen option, and the other fields as they were submitted (empty
+/// when the submission's form doesn't have them).
+fn fields(request: &ContactRequest) -> Vec<(&'static str, String)> {
+    let reason = contact_reasons::reason(&request.reason)
+        .map(|reason| reason.label.to_string())
+        .unwrap_or_else(|| request.reason.clone());
+    let phone = request
+        .normalized_phone()
+        .unwrap_or_else(|| request.phone.trim().to_string());
+
+    let mut fields = vec![
+        ("name", request.name.trim().to_string()),
+        ("email", request.email.trim().to_string()),
+        ("reason", reason),
+        ("subject", request.subject.trim().to_string()),
+        ("message", request.message.trim().to_string()),
+        ("phone", phone),
+    ];
+    for field in extra_fields() {
+        let value = request.value(&field.name).trim();
+        let value = field
+            .options
+            .iter()
+            .find(|option| option.value == value)
+            .map_or(value, |option| option.label.as_str());
+        fields.push((field.name.as_str(), value.to_string()));
+    }
+    fields
+}
+
+/// The fields of every form in `forms/` besides the ones every submission has, each once.
+fn extra_fields() -> impl Iterator<Item = &'static FieldSpec> {
+    let mut seen: Vec<&str> = CORE_FIELDS.to_vec();
+    FormSchema::all()
+        .iter()
+        .flat_map(|schema| &schema.fields)
+        .filter(move |field| {
+            let new = !seen.contains(&field.name.as_str());
+            seen.push(&field.name);
+            new
+        })
+}
+
+fn sample_value(field: &FieldSpec) -> String {
+    match field.kind {
+        FieldKind::Date => "2030-06-15".to_string(),
+        FieldKind::Number => field.min().unwrap_or(1).max(150).to_string(),
+        FieldKind::Select => field.initial_value().to_string(),
+        FieldKind::File => "brief.pdf".to_string(),
+        _ => format!("Sample {}", field.label.to_lowercase()),
+    }
+}
+
+/// Render `template` with `fields`, HTML-escaping the values when `escape` is set.
+pub(crate) fn render(
+    template: &str,
+    fields: &[(&str, String)],
+    escape: bool,
+) -> Result<String, String> {
+    let value = |name: &str| {
+        fields
+            .iter()
+            .find(|(field, _)| *field == name)
+            .map(|(_, value)| value.as_str())
+            .ok_or_else(|| format!("unknown field `{}`", name))
+    };
+
+    let mut output = String::new();
+    // One entry per open `{#if}`: whether its contents are kept
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        let emitting = blocks.iter().all(|keep| *keep);
+        if emitting {
+            output.push_str(&rest[..start]);
+        }
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('}') else {
+            rest = &rest[start..];
+            break;
+        };
+
+        let tag = &after[..end];
+        if let Some(name) = tag.strip_prefix("#if ").map(str::trim) {
+            blocks.push(!value(name)?.is_empty());
+        } else if tag == "/if" {
+            blocks.pop().ok_or("`{/if}` without an `{#if}`")?;
+        } else if is_field_name(tag) {
+            let value = value(tag)?;
+            if emitting {
+                if escape {
+                    output.push_str(&sanitize::html_text(value));
+                } else {
+                    output.push_str(value);
+                }
+            }
+        } else if emitting {
+            output.push('{');
+            output.push_str(tag);
+            output.push('}');
+        }
+        rest = &after[end + 1..];
+    }
+
+    if !blocks.is_empty() {
+        return Err("`{#if}` without a closing `{/if}`".to_string());
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
+fn is_field_name(tag: &str) -> bool {
+    !tag.is_empty() && tag.chars().all(|c| c.is_ascii_lowercase() || c == '_')
+}
@@ -0,0 +1,374 @@
+//! RSS feed generation.
+//!
+//! Feed readers show item content outside the site, so every link and image URL in embedded post HTML is rewritten
+//! to an absolute URL before it goes into the feed. Each item carries the post's copyright line in `dc:rights` and,
+//! for a Creative Commons license, the license's deed in `creativeCommons:license`.
+
+use crate::posts::Post;
+use crate::ssg::config::{FeedContent, SiteConfig};
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use std::fs;
+use std::path::Path;
+
+/// A feed as a whole: the blog's, or another collection's such as the [`notes`](crate::ssg::notes).
+pub struct Channel<'a> {
+    pub title: &'a str,
+    pub description: &'a str,
+    /// Site-relative URL of the page the feed follows, e.g. `/`
+    pub path: &'a str,
+    /// Site path of the feed itself, e.g. `/feed.xml`
+    pub feed_path: &'a str,
+}
+
+/// A single entry in the feed.
+pub struct FeedItem<'a> {
+    /// Empty for an untitled entry, which only has its description
+    pub title: &'a str,
+    /// Site-relative path of the page, e.g. `/blog/blog-post-1/`
+    pub path: &'a str,
+    pub summary: &'a str,
+    pub published: NaiveDate,
+    /// When the content was last revised, if it has been since it was published
+    pub updated: Option<NaiveDate>,
+    /// Rendered post HTML, with URLs as they appear on the page
+    pub content_html: &'a str,
+    /// Media file attached to the entry, for podcast apps
+    pub enclosure: Option<Enclosure<'a>>,
+    /// Copyright line, e.g. `© 2025 Marcin Sydor. Licensed under CC BY 4.0.`
+    pub rights: &'a str,
+    /// Deed of the license the entry is published under, if it has one
+    pub license_url: Option<&'a str>,
+}
+
+/// A media file attached to a feed entry: a post's narration.
+pub struct Enclosure<'a> {
+    /// Site-relative path of the file, e.g. `/assets/audio/blog-post-1.mp3`
+    pub path: &'a str,
+    /// Size in bytes
+    pub length: u64,
+    pub media_type: &'a str,
+}
+
+/// Writes `feed.xml` for `posts`, which should only hold published posts.
+pub struct FeedHook {
+    pub posts: Vec<Post>,
+}
+
+impl BuildHook for FeedHook {
+    fn name(&self) -> &str {
+        "feed"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔨 Generating: /feed.xml");
+
+        let style = site.config.build.output_style;
+        let paths: Vec<String> = self
+            .posts
+            .iter()
+            .map(|post| style.page_url(&post.path()))
+            .collect();
+        let summaries: Vec<String> = self.posts.iter().map(Post::excerpt).collect();
+        let bodies: Vec<String> = self.posts.iter().map(Post::body_html).collect();
+        let notices: Vec<String> = self
+            .posts
+            .iter()
+            .map(|post| post.license_notice(&site.config.license))
+            .collect();
+        let items = self
+            .posts
+            .iter()
+            .zip(
+                paths
+                    .iter()
+                    .zip(summaries.iter().zip(bodies.iter().zip(&notices))),
+            )
+            .map(|(post, (path, (summary, (body, notice))))| {
+                Ok(FeedItem {
+                    title: post.title,
+                    path,
+                    summary,
+                    published: post.published,
+                    updated: Some(post.last_modified())
+                        .filter(|&modified| modified > post.published),
+                    content_html: body,
+                    enclosure: enclosure(post, site.output_dir)?,
+                    rights: notice,
+                    license_url: post.license(&site.config.license).url(),
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        fs::write(
+            site.output_dir.join("feed.xml"),
+            render_rss(site.config, &items),
+        )?;
+        println!("✅ Generated: feed.xml");
+        Ok(())
+    }
+}
+
+/// The narration of `post` as an enclosure, with its size from the copy in `output_dir`. A narration that isn't there
+/// or isn't in a known audio format fails the build, rather than reach podcast apps as a broken episode.
+fn enclosure<'a>(post: &Post, output_dir: &Path) -> Result<Option<Enclosure<'a>>, String> {
+    let Some(audio) = post.audio else {
+        return Ok(None);
+    };
+    let media_type = post.audio_type().ok_or_else(|| {
+        format!(
+            "{}: the narration {} isn't an mp3, m4a, aac, ogg, opus or wav file",
+            post.slug, audio
+        )
+    })?;
+    let length = fs::metadata(output_dir.join(audio.trim_start_matches('/')))
+        .map_err(|e| format!("{}: the narration {}: {}", post.slug, audio, e))?
+        .len();
+
+    Ok(Some(Enclosure {
+        path: audio,
+        length,
+        media_type,
+    }))
+}
+
+/// Render an RSS 2.0 document for `items`. Depending on the feed config, items carry only their summary or the summary
+/// plus the full post HTML in `content:encoded`. Items revised since publishing carry the date of the last revision in
+/// `atom:updated`, which feed readers use to show the post again as updated.
+pub fn render_rss(config: &SiteConfig, items: &[FeedItem]) -> String {
+    render_channel(
+        config,
+        &Channel {
+            title: &config.site.title,
+            description: &config.site.description,
+            path: "/",
+            feed_path: "/feed.xml",
+        },
+        items,
+    )
+}
+
+/// Render an RSS 2.0 document for the `items` of `channel`, as [`render_rss`] does for the blog.
+pub fn render_channel(config: &SiteConfig, channel: &Channel, items: &[FeedItem]) -> String {
+    let base_url = config.site.base_url.trim_end_matches('/');
+
+    let items = items
+        .iter()
+        .map(|item| {
+            let link = format!("{}{}", base_url, item.path);
+            let enclosure = item
+                .enclosure
+                .as_ref()
+                .map(|enclosure| {
+                    format!(
+                        r#"
+      <enclosure url="{}" length="{}" type="{}"/>"#,
+                        escape_xml(&format!("{}{}", base_url, enclosure.path)),
+                        enclosure.length,
+                        escape_xml(enclosure.media_type),
+                    )
+                })
+                .unwrap_or_default();
+            let rights = if item.rights.is_empty() {
+                String::new()
+            } else {
+                format!("\n      <dc:rights>{}</dc:rights>", escape_xml(item.rights))
+            };
+            let license = item
+                .license_url
+                .map(|url| {
+                    format!(
+                        "\n      <creativeCommons:license>{}</creativeCommons:license>",
+                        escape_xml(url)
+                    )
+                })
+                .unwrap_or_default();
+            let content = match config.feed.content {
+                FeedContent::Summary => String::new(),
+                FeedContent::Full => format!(
+                    "\n      <content:encoded><![CDATA[{}]]></content:encoded>",
+                    escape_cdata(&absolutize_urls(item.content_html, base_url, item.path))
+                ),
+            };
+
+            let title = if item.title.is_empty() {
+                String::new()
+            } else {
+                format!("\n      <title>{}</title>", escape_xml(item.title))
+            };
+
+            format!(
+                r#"    <item>{title}
+      <link>{link}</link>
+      <guid isPermaLink="true">{link}</guid>
+      <pubDate>{published}</pubDate>{updated}
+      <description>{summary}</description>{rights}{license}{enclosure}{content}
+    </item>"#,
+                title = title,
+                link = escape_xml(&link),
+                published = rfc2822(item.published),
+                updated = item
+                    .updated
+                    .map(|date| format!(
+                        "\n      <atom:updated>{}</atom:updated>",
+                        midnight_utc(date).to_rfc3339()
+                    ))
+                    .unwrap_or_default(),
+                summary = escape_xml(item.summary),
+                rights = rights,
+                license = license,
+                enclosure = enclosure,
+                content = content,
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rss version="2.0" xmlns:content="http://purl.org/rss/1.0/modules/content/" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:dc="http://purl.org/dc/elements/1.1/" xmlns:creativeCommons="http://backend.userland.com/creativeCommonsRssModule">
+  <channel>
+    <title>{title}</title>
+    <link>{base_url}{path}</link>
+    <description>{description}</description>
+    <atom:link href="{base_url}{feed_path}" rel="self" type="application/rss+xml"/>
+    <lastBuildDate>{build_date}</lastBuildDate>
+{items}
+  </channel>
+</rss>
+"#,
+        title = escape_xml(channel.title),
+        base_url = escape_xml(base_url),
+        path = escape_xml(channel.path),
+        feed_path = escape_xml(channel.feed_path),
+        description = escape_xml(channel.description),
+        build_date = chrono::Utc::now().to_rfc2822(),
+        items = items,
+    )
+}
+
+/// `date` in the RFC 822 form RSS uses, at midnight UTC.
+fn rfc2822(date: NaiveDate) -> String {
+    midnight_utc(date).to_rfc2822()
+}
+
+fn midnight_utc(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Rewrite `href`, `src` and `srcset` attribute values in `html` to absolute URLs.
+///
+/// Root-relative URLs are joined to `base_url`; relative ones are resolved against the directory of `page_path`.
+/// Fragments, protocol-relative URLs and URLs with a scheme (`https:`, `mailto:`) are left alone. In a `srcset`, the
+/// URL of each candidate is rewritten and its width or density descriptor kept.
+pub fn absolutize_urls(html: &str, base_url: &str, page_path: &str) -> String {
+    const ATTRIBUTES: &[&str] = &[
+        "href=\"",
+        "href='",
+        "src=\"",
+        "src='",
+        "srcset=\"",
+        "srcset='",
+    ];
+
+    let base_url = base_url.trim_end_matches('/');
+    let mut output = String::with_capacity(html.len());
+    let mut rest = html;
+
+    loop {
+        let next = ATTRIBUTES
+            .iter()
+            .filter_map(|attr| rest.find(attr).map(|pos| (pos, attr.len())))
+            .min_by_key(|(pos, _)| *pos);
+        let Some((pos, attr_len)) = next else {
+            break;
+        };
+
+        let value_start = pos + attr_len;
+        let quote = &rest[value_start - 1..value_start];
+        let Some(value_len) = rest[value_start..].find(quote) else {
+            break;
+        };
+
+        let value = &rest[value_start..value_start + value_len];
+        output.push_str(&rest[..value_start]);
+        if rest[..value_start].ends_with("srcset=\"") || rest[..value_start].ends_with("srcset='") {
+            output.push_str(&absolute_srcset(value, base_url, page_path));
+        } else {
+            output.push_str(&absolute_url(value, base_url, page_path));
+        }
+        rest = &rest[value_start + value_len..];
+    }
+
+    output.push_str(rest);
+    output
+}
+
+/// Each candidate of a `srcset` (`url [descriptor]`, separated by commas) with its URL made absolute.
+fn absolute_srcset(srcset: &str, base_url: &str, page_path: &str) -> String {
+    srcset
+        .split(',')
+        .map(str::trim)
+        .filter(|candidate| !candidate.is_empty())
+        .map(
+            |candidate| match candidate.split_once(char::is_whitespace) {
+                Some((url, descriptor)) => format!(
+                    "{} {}",
+                    absolute_url(url, base_url, page_path),
+                    descriptor.trim()
+                ),
+                None => absolute_url(candidate, base_url, page_path),
+            },
+        )
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn absolute_url(url: &str, base_url: &str, page_path: &str) -> String {
+    let has_scheme = url.split_once(':').is_some_and(|(scheme, _)| {
+        !scheme.is_empty()
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+    });
+
+    if url.is_empty() || url.starts_with('#') || url.starts_with("//") || has_scheme {
+        url.to_string()
+    } else if url.starts_with('/') {
+        format!("{}{}", base_url, url)
+    } else {
+        let directory = match page_path.rfind('/') {
+            Some(end) => &page_path[..=end],
+            None => "/",
+        };
+        format!("{}{}{}", base_url, directory, url)
+    }
+}
+
+/// Escape text for use in XML element content and attribute values.
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// A CDATA section can't contain `]]>`, so split it across two sections.
+fn escape_cdata(text: &str) -> String {
+    text.replace("]]>", "]]]]><![CDATA[>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolutizes_every_srcset_candidate() {
+        let html = r#"<img src="/assets/images/cover.png" srcset="/assets/images/cover-640w.png 640w, cover.png 1600w" alt="">"#;
+        assert_eq!(
+            absolutize_urls(html, "https://example.com/", "/blog/post/"),
+            r#"<img src="https://example.com/assets/images/cover.png" srcset="https://example.com/assets/images/cover-640w.png 640w, https://example.com/blog/post/cover.png 1600w" alt="">"#
+        );
+    }
+}
@@ -0,0 +1,154 @@
+//! Response headers file for the host, configured by `[headers]` in `site.toml`.
+//!
+//! Security headers apply to every path. Assets whose names carry a content hash (`main-dxh774dfa83869ca4bd.css`)
+//! never change under the same URL, so they're cached for a year as `immutable`; everything else keeps the host's
+//! default caching so new deploys show up straight away.
+
+use crate::ssg::config::{HeadersConfig, HeadersFormat};
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use std::fs;
+use std::path::Path;
+
+/// `Cache-Control` for fingerprinted assets.
+pub const IMMUTABLE_CACHE_CONTROL: &str = "public, max-age=31536000, immutable";
+
+/// Writes the headers file for the configured host, or nothing for [`HeadersFormat::None`]. Register it after any
+/// step that copies assets, since it lists the fingerprinted files in the output.
+pub struct HeadersHook;
+
+impl BuildHook for HeadersHook {
+    fn name(&self) -> &str {
+        "headers"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        let config = &site.config.headers;
+        let (file, contents) = match config.format {
+            HeadersFormat::None => return Ok(()),
+            HeadersFormat::Netlify => (
+                "_headers",
+                render_netlify(config, &fingerprinted_assets(site.output_dir)?),
+            ),
+            HeadersFormat::Vercel => (
+                "vercel.json",
+                render_vercel(config, &fingerprinted_assets(site.output_dir)?)?,
+            ),
+            HeadersFormat::Nginx => ("nginx-headers.conf", render_nginx(config)),
+        };
+
+        println!("🔨 Generating: /{}", file);
+        fs::write(site.output_dir.join(file), contents)?;
+        println!("✅ Generated: {}", file);
+        Ok(())
+    }
+}
+
+/// Whether a file name carries a content hash: a final `-dxh<hex>` segment before the extension, as in the assets
+/// Dioxus builds and the files the strict CSP mode writes.
+pub fn is_fingerprinted(file_name: &str) -> bool {
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+    stem.rsplit_once("-dxh").is_some_and(|(name, hash)| {
+        !name.is_empty() && hash.len() >= 8 && hash.bytes().all(|b| b.is_ascii_hexdigit())
+    })
+}
+
+/// Site paths of the fingerprinted files under `assets/` in the output, sorted.
+fn fingerprinted_assets(output_dir: &Path) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut paths = Vec::new();
+    let assets_dir = output_dir.join("assets");
+    if assets_dir.exists() {
+        for entry in fs::read_dir(assets_dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.path().is_file() && is_fingerprinted(&name) {
+                paths.push(format!("/assets/{}", name));
+            }
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Headers sent with every response.
+fn security_headers(config: &HeadersConfig) -> Vec<(&'static str, &str)> {
+    let mut headers = vec![("X-Content-Type-Options", "nosniff")];
+    if !config.referrer_policy.is_empty() {
+        headers.push(("Referrer-Policy", &config.referrer_policy));
+    }
+    if !config.content_security_policy.is_empty() {
+        headers.push(("Content-Security-Policy", &config.content_security_policy));
+    }
+    headers
+}
+
+/// Netlify `_headers`: a path line followed by indented headers. Every matching block applies, so assets get their
+/// caching on top of the site-wide headers.
+pub fn render_netlify(config: &HeadersConfig, assets: &[String]) -> String {
+    let mut output = String::from("/*\n");
+    for (name, value) in security_headers(config) {
+        output.push_str(&format!("  {}: {}\n", name, value));
+    }
+    for asset in assets {
+        output.push_str(&format!(
+            "\n{}\n  Cache-Control: {}\n",
+            asset, IMMUTABLE_CACHE_CONTROL
+        ));
+    }
+    output
+}
+
+/// `vercel.json` with a `headers` rule for the whole site and one per fingerprinted asset.
+pub fn render_vercel(
+    config: &HeadersConfig,
+    assets: &[String],
+) -> Result<String, serde_json::Error> {
+    let rule = |source: &str, headers: Vec<(&str, &str)>| {
+        serde_json::json!({
+            "source": source,
+            "headers": headers
+                .into_iter()
+                .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+                .collect::<Vec<_>>(),
+        })
+    };
+
+    let mut rules = vec![rule("/(.*)", security_headers(config))];
+    rules.extend(
+        assets
+            .iter()
+            .map(|asset| rule(asset, vec![("Cache-Control", IMMUTABLE_CACHE_CONTROL)])),
+    );
+
+    serde_json::to_string_pretty(&serde_json::json!({ "headers": rules }))
+}
+
+/// nginx snippet for the site's `server` block. `add_header` in a `location` replaces the inherited ones instead of
+/// adding to them, so the asset location repeats the security headers. Hashed names are matched with a regex (quoted,
+/// since nginx would read its braces as a block), so the snippet doesn't need regenerating when the hashes change.
+pub fn render_nginx(config: &HeadersConfig) -> String {
+    let headers: String = security_headers(config)
+        .into_iter()
+        .map(|(name, value)| {
+            format!(
+                "add_header {} \"{}\" always;\n",
+                name,
+                value.replace('"', "\\\"")
+            )
+        })
+        .collect();
+    let indented: String = headers
+        .lines()
+        .map(|line| format!("    {}\n", line))
+        .collect();
+
+    format!(
+        "# Generated by generate_static; include it in the site's server block\n\
+         {headers}\n\
+         location ~ \"^/assets/.+-dxh[0-9a-f]{{8,}}\\.[^/]+$\" {{\n\
+         {indented}    add_header Cache-Control \"{cache}\" always;\n\
+         }}\n",
+        headers = headers,
+        indented = indented,
+        cache = IMMUTABLE_CACHE_CONTROL,
+    )
+}
@@ -0,0 +1,194 @@
+//! Health and readiness reports for the contact backend, as served on [`HEALTHZ_PATH`] and [`READYZ_PATH`] by
+//! `site serve` (see [`server`](crate::ssg::server)) and shown by `site health`.
+//!
+//! - liveness only says the process runs and which build it is, so a load balancer doesn't restart it when a
+//!   dependency is down
+//! - readiness also checks what a submission needs: that the `[contact.store]` files can be written (the site keeps
+//!   its submissions there rather than in a database) and that the SMTP server and the webhook accept connections.
+//!   A channel that isn't configured is skipped.
+//!
+//! Reports are JSON, with [`Report::http_status`] giving 200 when everything passes and 503 otherwise.
+
+use crate::ssg::config::ContactConfig;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+pub const HEALTHZ_PATH: &str = "/healthz";
+pub const READYZ_PATH: &str = "/readyz";
+
+/// Longest a dependency may take to accept a connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The build the report comes from.
+#[derive(Debug, Clone, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    /// Short hash of the commit built, or `unknown` outside a git checkout
+    pub commit: &'static str,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION"),
+            commit: env!("SITE_BUILD_COMMIT"),
+        }
+    }
+}
+
+/// How one dependency is doing.
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: &'static str,
+    pub status: CheckStatus,
+    /// What was checked, or why it failed
+    pub detail: String,
+    pub duration_ms: u128,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Failed,
+    /// Not configured, so not needed
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Report {
+    /// `ok` when no check failed, otherwise `unavailable`
+    pub status: &'static str,
+    pub build: BuildInfo,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub checks: Vec<Check>,
+}
+
+impl Report {
+    fn new(checks: Vec<Check>) -> Self {
+        let healthy = checks
+            .iter()
+            .all(|check| check.status != CheckStatus::Failed);
+        Self {
+            status: if healthy { "ok" } else { "unavailable" },
+            build: BuildInfo::current(),
+            checks,
+        }
+    }
+
+    pub fn is_healthy(&self) -> bool {
+        self.status == "ok"
+    }
+
+    /// Status code to answer the endpoint with.
+    pub fn http_status(&self) -> u16 {
+        if self.is_healthy() {
+            200
+        } else {
+            503
+        }
+    }
+}
+
+/// The report for [`HEALTHZ_PATH`]: the build, without checking any dependency.
+pub fn liveness() -> Report {
+    Report::new(Vec::new())
+}
+
+/// The report for [`READYZ_PATH`], checking every dependency of the contact backend.
+pub fn readiness(config: &ContactConfig) -> Report {
+    let store = &config.store;
+    let checks = vec![
+        timed("store", || {
+            // The deliveries file is always written; the submissions file only when it's configured
+            let mut paths = vec![&store.deliveries];
+            if store.is_enabled() {
+                paths.push(&store.submissions);
+            }
+            for path in &paths {
+                writable(Path::new(path)).map_err(|e| format!("{}: {}", path, e))?;
+            }
+            let paths: Vec<&str> = paths.iter().map(|path| path.as_str()).collect();
+            Ok(Some(format!("can write {}", paths.join(" and "))))
+        }),
+        timed("smtp", || {
+            if !config.smtp.is_enabled() {
+                return Ok(None);
+            }
+            reachable(&config.smtp.url).map(Some)
+        }),
+        timed("webhook", || {
+            if !config.webhook.is_enabled() {
+                return Ok(None);
+            }
+            reachable(&config.webhook.url).map(Some)
+        }),
+    ];
+    Report::new(checks)
+}
+
+/// Run a check, which gives `None` when it's skipped, and time it.
+fn timed(name: &'static str, check: impl FnOnce() -> Result<Option<String>, String>) -> Check {
+    let started = Instant::now();
+    let (status, detail) = match check() {
+        Ok(Some(detail)) => (CheckStatus::Ok, detail),
+        Ok(None) => (CheckStatus::Skipped, "not configured".to_string()),
+        Err(error) => (CheckStatus::Failed, error),
+    };
+    Check {
+        name,
+        status,
+        detail,
+        duration_ms: started.elapsed().as_millis(),
+    }
+}
+
+/// Whether `path` can be appended to, creating it and its directory when they're missing, as the first submission
+/// would.
+fn writable(path: &Path) -> std::io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    OpenOptions::new().create(true).append(true).open(path)?;
+    Ok(())
+}
+
+/// Whether the server in `url` accepts a connection.
+fn reachable(url: &str) -> Result<String, String> {
+    let (scheme, rest) = url
+        .split_once("://")
+        .ok_or_else(|| format!("`{}` isn't a URL", url))?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    let default_port = match scheme {
+        "https" => 443,
+        "http" => 80,
+        "smtps" => 465,
+        "smtp" => 25,
+        _ => return Err(format!("unsupported scheme `{}`", scheme)),
+    };
+    let address = if authority
+        .rsplit_once(':')
+        .is_some_and(|(_, port)| port.parse::<u16>().is_ok())
+    {
+        authority.to_string()
+    } else {
+        format!("{}:{}", authority, default_port)
+    };
+
+    let addresses: Vec<_> = address
+        .to_socket_addrs()
+        .map_err(|e| format!("{}: {}", address, e))?
+        .collect();
+    let mut last_error = format!("{} doesn't resolve", address);
+    for socket in addresses {
+        match TcpStream::connect_timeout(&socket, CONNECT_TIMEOUT) {
+            Ok(_) => return Ok(format!("{} accepts connections", address)),
+            Err(e) => last_error = format!("{}: {}", address, e),
+        }
+    }
+    Err(last_error)
+}
@@ -0,0 +1,130 @@
+//! Build hooks: the extension points of the static site generator.
+//!
+//! Generators write their pages through [`Build::page`], which records each page and hands it to every registered
+//! [`BuildHook`]. Once all pages and assets are in place, [`Build::finish`] runs the hooks' `after_build` step over the
+//! whole output. Site-wide outputs such as the sitemap, the feed and the integrity hashes are hooks rather than fixed
+//! steps in `main`, so a search index or social image generator can be added the same way.
+
+use crate::ssg::config::SiteConfig;
+use crate::ssg::html;
+use crate::ssg::output::{prepare_page, write_files};
+use std::path::{Path, PathBuf};
+
+/// A page as it was written to the output directory.
+pub struct RenderedPage {
+    /// Route path with a trailing slash, e.g. `/about/`
+    pub path: String,
+    /// Final HTML, after link rewriting
+    pub html: String,
+    /// Files written for the page, relative to the output directory
+    pub files: Vec<PathBuf>,
+}
+
+impl RenderedPage {
+    /// Whether the page asked search engines to stay away with a robots `noindex` meta tag.
+    pub fn is_noindex(&self) -> bool {
+        html::tags(&self.html, "meta").into_iter().any(|tag| {
+            html::attr(tag, "name").is_some_and(|name| name.eq_ignore_ascii_case("robots"))
+                && html::attr(tag, "content").is_some_and(|content| content.contains("noindex"))
+        })
+    }
+}
+
+/// Everything the build produced, passed to [`BuildHook::after_build`].
+pub struct SiteOutput<'a> {
+    pub output_dir: &'a Path,
+    pub config: &'a SiteConfig,
+    /// Pages in the order they were written
+    pub pages: &'a [RenderedPage],
+}
+
+pub trait BuildHook {
+    /// Short name used in build output.
+    fn name(&self) -> &str;
+
+    /// Called after each page is written.
+    fn after_page(&self, _page: &RenderedPage) {}
+
+    /// Called once after every page has been written and the assets copied, in registration order.
+    fn after_build(&self, _site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        Ok(())
+    }
+}
+
+/// A site build in progress: where pages go, and the hooks that run on them.
+pub struct Build<'a> {
+    output_dir: &'a Path,
+    config: &'a SiteConfig,
+    hooks: Vec<Box<dyn BuildHook>>,
+    pages: Vec<RenderedPage>,
+}
+
+impl<'a> Build<'a> {
+    pub fn new(output_dir: &'a Path, config: &'a SiteConfig) -> Self {
+        Self {
+            output_dir,
+            config,
+            hooks: Vec::new(),
+            pages: Vec::new(),
+        }
+    }
+
+    /// Register a hook. Hooks run in the order they were added.
+    pub fn with_hook(mut self, hook: impl BuildHook + 'static) -> Self {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    pub fn output_dir(&self) -> &'a Path {
+        self.output_dir
+    }
+
+    pub fn config(&self) -> &'a SiteConfig {
+        self.config
+    }
+
+    /// The pages written so far, in order.
+    pub fn pages(&self) -> &[RenderedPage] {
+        &self.pages
+    }
+
+    /// Write the page for route `path` like [`write_page`](crate::ssg::output::write_page) and pass it to the hooks.
+    pub fn page(
+        &mut self,
+        path: &str,
+        html: &str,
+    ) -> Result<&RenderedPage, Box<dyn std::error::Error>> {
+        let html = prepare_page(self.config, path, html);
+        let files = self.config.build.output_style.page_files(path);
+        write_files(self.output_dir, &files, &html)?;
+
+        let page = RenderedPage {
+            path: path.to_string(),
+            html,
+            files,
+        };
+
+        for hook in &self.hooks {
+            hook.after_page(&page);
+        }
+
+        self.pages.push(page);
+        Ok(self.pages.last().expect("page was just added"))
+    }
+
+    /// Run every hook's `after_build` step.
+    pub fn finish(self) -> Result<(), Box<dyn std::error::Error>> {
+        let site = SiteOutput {
+            output_dir: self.output_dir,
+            config: self.config,
+            pages: &self.pages,
+        };
+
+        for hook in &self.hooks {
+            hook.after_build(&site)
+                .map_err(|e| format!("{} hook: {}", hook.name(), e))?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+//! Minimal HTML scanning used by the generator and the check passes. This is not a full parser; it only needs to find
+//! opening tags and read their attributes in the HTML the generator emits and in raw HTML embedded in markdown.
+
+use std::ops::Range;
+
+/// Return every opening tag called `name` (e.g. `img`) as the full `<img ...>` text.
+pub fn tags<'a>(html: &'a str, name: &str) -> Vec<&'a str> {
+    tag_spans(html, name)
+        .into_iter()
+        .map(|span| &html[span])
+        .collect()
+}
+
+/// Byte ranges of every opening tag called `name`, from `<` to `>` inclusive.
+pub fn tag_spans(html: &str, name: &str) -> Vec<Range<usize>> {
+    // ASCII lowercasing keeps byte offsets intact, so positions found here index straight into `html`
+    let lower = html.to_ascii_lowercase();
+    let needle = format!("<{}", name.to_ascii_lowercase());
+    let mut found = Vec::new();
+    let mut from = 0;
+
+    while let Some(pos) = lower[from..].find(&needle) {
+        let start = from + pos;
+        let after_name = start + needle.len();
+        let boundary = lower[after_name..]
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_whitespace() || c == '>' || c == '/');
+
+        match lower[after_name..].find('>') {
+            Some(end) if boundary => {
+                found.push(start..after_name + end + 1);
+                from = after_name + end + 1;
+            }
+            Some(_) => from = after_name,
+            None => break,
+        }
+    }
+
+    found
+}
+
+/// Read an attribute from a single opening tag. Bare attributes (`<input required>`) yield an empty string.
+pub fn attr<'a>(tag: &'a str, name: &str) -> Option<&'a str> {
+    let inner = tag
+        .trim_start_matches('<')
+        .trim_end_matches('>')
+        .trim_end_matches('/');
+    // Skip the tag name itself
+    let mut rest = inner.trim_start_matches(|c: char| !c.is_ascii_whitespace());
+
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            return None;
+        }
+
+        let name_end = rest
+            .find(|c: char| c.is_ascii_whitespace() || c == '=')
+            .unwrap_or(rest.len());
+        let attr_name = &rest[..name_end];
+        rest = rest[name_end..].trim_start();
+
+        let value = if let Some(after_eq) = rest.strip_prefix('=') {
+            let after_eq = after_eq.trim_start();
+            let (value, remaining) = match after_eq.chars().next() {
+                Some(quote @ ('"' | '\'')) => {
+                    let body = &after_eq[1..];
+                    let end = body.find(quote).unwrap_or(body.len());
+                    (&body[..end], body.get(end + 1..).unwrap_or(""))
+                }
+                _ => {
+                    let end = after_eq
+                        .find(|c: char| c.is_ascii_whitespace())
+                        .unwrap_or(after_eq.len());
+                    (&after_eq[..end], &after_eq[end..])
+                }
+            };
+            rest = remaining;
+            value
+        } else {
+            ""
+        };
+
+        if attr_name.eq_ignore_ascii_case(name) {
+            return Some(value);
+        }
+    }
+}
+
+/// Escape text for use in HTML element content and attribute values.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
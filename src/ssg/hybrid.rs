@@ -0,0 +1,596 @@
+//! Hybrid pages: generated static HTML enhanced with Dioxus components compiled to WASM.
+//!
+//! Which pages get islands is listed in `hybrid.toml` at the project root:
+//!
+//! ```toml
+//! [[page]]
+//! route = "/projects/"
+//!
+//! [[page.islands]]
+//! mount = "mount_projects_filter"   # #[wasm_bindgen] export in main.rs, called with the element id
+//! element = "projects-filter-root"  # id of the element the island replaces
+//! fallback = "<p>...</p>"           # optional HTML shown in the element if the WASM fails to load
+//! load = "visible"                  # optional: when to mount, see below
+//!
+//! [[shared_islands]]                  # added to every generated page that has the element
+//! mount = "mount_mobile_nav"
+//! element = "mobile-nav-root"
+//! ```
+//!
+//! Without a fallback the element keeps its static markup when loading fails, so islands that enhance content
+//! (rather than replacing a placeholder) degrade to the plain page.
+//!
+//! `load` picks when an island is mounted ([`Load`]): `eager` (the default) as soon as the page has loaded, `lazy` once
+//! the browser is idle, `visible` when its element scrolls near the viewport. The WASM is downloaded when the first
+//! island on a page needs it, so a page with only `visible` islands doesn't fetch it until then.
+//!
+//! The static generator writes the pages as usual; [`generate_hybrid_pages`] then adds the WASM preloads and a loader
+//! script that mounts every island once the module has initialised and the island is due. While the WASM downloads,
+//! elements marked `data-island-progress` get their width and `aria-valuenow` set to the percentage loaded (and a
+//! `data-determinate` attribute once the size is known). Elements marked `data-island-loading` are hidden when loading
+//! finishes, successfully or not.
+//!
+//! When loading fails, the element of every island that was due gets `data-island-error` set to `network` (the glue or the WASM couldn't
+//! be fetched), `compile` (the binary is invalid) or `init` (the module or a mount function failed to start), before
+//! any fallback is inserted. The same category is sent with an `island-error` event on `document`.
+//!
+//! On a sample of page views (`[telemetry] sample_rate`) the loader also reports those failures, and any uncaught
+//! exception on the page, to [`CLIENT_ERRORS_ENDPOINT`] as [`ClientErrorReport`](crate::api::ClientErrorReport)s.
+
+use crate::api::client_errors::{CLIENT_ERRORS_ENDPOINT, MAX_MESSAGE_LENGTH};
+use crate::ssg::config::{OutputStyle, TelemetryConfig};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct HybridManifest {
+    #[serde(default, rename = "page")]
+    pub pages: Vec<HybridPage>,
+    /// Islands added to every generated page that contains their element, such as one in the shared navbar
+    #[serde(default)]
+    pub shared_islands: Vec<Island>,
+}
+
+/// A generated page and the islands mounted on it.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HybridPage {
+    /// Route path in directory form, as passed to [`write_page`](crate::ssg::output::write_page)
+    pub route: String,
+    pub islands: Vec<Island>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Island {
+    /// Name of the exported mount function
+    pub mount: String,
+    /// Id of the element to mount into
+    pub element: String,
+    #[serde(default)]
+    pub fallback: Option<String>,
+    #[serde(default)]
+    pub load: Load,
+}
+
+/// When the loader mounts an island.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Load {
+    /// As soon as the page has loaded
+    #[default]
+    Eager,
+    /// Once the browser is idle (`requestIdleCallback`)
+    Lazy,
+    /// When its element comes within 200px of the viewport
+    Visible,
+}
+
+impl Load {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Load::Eager => "eager",
+            Load::Lazy => "lazy",
+            Load::Visible => "visible",
+        }
+    }
+}
+
+/// A generated page the islands were added to.
+pub struct IslandPage {
+    /// Relative to the output directory
+    pub file: PathBuf,
+    pub islands: Vec<Island>,
+}
+
+impl HybridManifest {
+    /// Load the manifest from `path`. A missing file means no hybrid pages.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let source = fs::read_to_string(path)?;
+        toml::from_str(&source).map_err(|e| format!("{}: {}", path.display(), e).into())
+    }
+
+    /// Every mount function the manifest uses, for checking the WASM build exports them.
+    pub fn mount_functions(&self) -> Vec<&str> {
+        let mut mounts: Vec<&str> = self
+            .pages
+            .iter()
+            .flat_map(|page| &page.islands)
+            .chain(&self.shared_islands)
+            .map(|island| island.mount.as_str())
+            .collect();
+        mounts.sort_unstable();
+        mounts.dedup();
+        mounts
+    }
+}
+
+/// Site-relative URLs of the WASM bundle copied into the output.
+pub struct WasmAssets {
+    /// The wasm-bindgen JS glue module
+    pub js: String,
+    pub wasm: String,
+}
+
+/// Find the JS glue and WASM binary in a fresh `dx build` asset directory. The glue must export every function in
+/// `exports`, which catches building without the `web` feature or a stale bundle.
+pub fn find_wasm_assets(
+    wasm_assets_dir: &Path,
+    exports: &[&str],
+) -> Result<WasmAssets, Box<dyn std::error::Error>> {
+    let mut js = None;
+    let mut wasm = None;
+
+    for entry in fs::read_dir(wasm_assets_dir)? {
+        let entry = entry?;
+        let file_name = entry.file_name().to_string_lossy().to_string();
+
+        if file_name.starts_with("dioxus_site-") && file_name.ends_with(".js") {
+            let glue = fs::read_to_string(entry.path())?;
+            let missing: Vec<&str> = exports
+                .iter()
+                .copied()
+                .filter(|export| !glue.contains(export))
+                .collect();
+            if !missing.is_empty() {
+                return Err(format!("{} does not export {}", file_name, missing.join(", ")).into());
+            }
+            js = Some(format!("/assets/{}", file_name));
+        } else if file_name.starts_with("dioxus_site_bg-") && file_name.ends_with(".wasm") {
+            wasm = Some(format!("/assets/{}", file_name));
+        }
+    }
+
+    Ok(WasmAssets {
+        js: js.ok_or("WASM JS glue file not found")?,
+        wasm: wasm.ok_or("WASM file not found")?,
+    })
+}
+
+/// Add the islands in `manifest` to the already generated pages: each `[[page]]` gets its own islands, and every page
+/// containing the element of a shared island gets that island as well, all mounted by one loader. Returns the updated
+/// pages with the islands each one got.
+pub fn generate_hybrid_pages(
+    output_dir: &Path,
+    style: OutputStyle,
+    assets: &WasmAssets,
+    manifest: &HybridManifest,
+    telemetry: &TelemetryConfig,
+) -> Result<Vec<IslandPage>, Box<dyn std::error::Error>> {
+    let mut islands_by_file: BTreeMap<PathBuf, Vec<Island>> = BTreeMap::new();
+    for page in &manifest.pages {
+        for file in style.page_files(&page.route) {
+            if !output_dir.join(&file).exists() {
+                return Err(format!(
+                    "{}: not found (generate the static site before adding islands)",
+                    output_dir.join(&file).display()
+                )
+                .into());
+            }
+            islands_by_file
+                .entry(file)
+                .or_default()
+                .extend(page.islands.iter().cloned());
+        }
+    }
+    if !manifest.shared_islands.is_empty() {
+        for file in html_files(output_dir, Path::new(""))? {
+            islands_by_file.entry(file).or_default();
+        }
+    }
+
+    let mut updated = Vec::new();
+    for (file, mut islands) in islands_by_file {
+        let path = output_dir.join(&file);
+        let html = fs::read_to_string(&path)?;
+        islands.extend(
+            manifest
+                .shared_islands
+                .iter()
+                .filter(|island| html.contains(&format!(r#"id="{}""#, island.element)))
+                .cloned(),
+        );
+        if islands.is_empty() {
+            continue;
+        }
+
+        fs::write(&path, add_islands(&html, assets, &islands, telemetry))?;
+        updated.push(IslandPage { file, islands });
+    }
+
+    Ok(updated)
+}
+
+/// Every HTML page under `dir` (relative to the output directory), leaving out the copied assets.
+fn html_files(output_dir: &Path, dir: &Path) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let mut files = Vec::new();
+    for entry in fs::read_dir(output_dir.join(dir))? {
+        let entry = entry?;
+        let relative = dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            if relative != Path::new("assets") {
+                files.extend(html_files(output_dir, &relative)?);
+            }
+        } else if relative.extension().is_some_and(|ext| ext == "html") {
+            files.push(relative);
+        }
+    }
+    Ok(files)
+}
+
+/// Insert the WASM preloads into the head of `html` and the island loader before the end of its body.
+pub fn add_islands(
+    html: &str,
+    assets: &WasmAssets,
+    islands: &[Island],
+    telemetry: &TelemetryConfig,
+) -> String {
+    // Modules imported from inline scripts can't carry an integrity attribute, but a modulepreload can, and the
+    // import then reuses the verified module. The WASM itself is only preloaded when an island needs it right away.
+    let mut preloads = format!("    <link rel=\"modulepreload\" href=\"{}\">\n", assets.js);
+    if islands.iter().any(|island| island.load == Load::Eager) {
+        preloads.push_str(&format!(
+            "    <link rel=\"preload\" as=\"fetch\" href=\"{}\" crossorigin>\n",
+            assets.wasm
+        ));
+    }
+
+    let mut html = html.to_string();
+    if let Some(head_end) = html.find("</head>") {
+        html.insert_str(head_end, &preloads);
+    }
+    if let Some(body_end) = html.rfind("</body>") {
+        html.insert_str(body_end, &loader_script(assets, islands, telemetry));
+    }
+    html
+}
+
+fn loader_script(assets: &WasmAssets, islands: &[Island], telemetry: &TelemetryConfig) -> String {
+    let islands_js = islands
+        .iter()
+        .map(|island| {
+            format!(
+                "{{ mount: {}, element: {}, fallback: {}, load: {} }}",
+                js_string(&island.mount),
+                js_string(&island.element),
+                island
+                    .fallback
+                    .as_deref()
+                    .map_or("null".to_string(), js_string),
+                js_string(island.load.as_str())
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(",\n        ");
+
+    format!(
+        r#"<script type="module">
+    const jsPath = {js_path};
+    const wasmUrl = new URL({wasm_path}, location.href).href;
+
+    const islands = [
+        {islands_js}
+    ];
+
+    // Mirror the WASM logger: verbose output only with ?debug or a data-debug attribute on an island root
+    const debug = new URLSearchParams(location.search).has('debug')
+        || islands.some(island => document.getElementById(island.element)?.hasAttribute('data-debug'));
+    const log = (...args) => {{ if (debug) console.log(...args); }};
+
+    // Error reports: whether this page view sends any is decided once, and it sends only a few. Nothing about the
+    // reader goes with them, just the page path and the error.
+    const reporting = Math.random() < {sample_rate};
+    let reportsLeft = 5;
+    let starting = false;
+    const pathOf = url => {{
+        try {{
+            return url ? new URL(url, location.href).pathname : undefined;
+        }} catch {{
+            return undefined;
+        }}
+    }};
+    function report(details) {{
+        if (!reporting || reportsLeft <= 0) return;
+        reportsLeft -= 1;
+        const body = JSON.stringify({{
+            ...details,
+            page: location.pathname,
+            message: String(details.message ?? '').slice(0, {max_message_length}),
+        }});
+        const sent = navigator.sendBeacon?.({endpoint}, new Blob([body], {{ type: 'application/json' }}));
+        if (!sent) {{
+            fetch({endpoint}, {{ method: 'POST', headers: {{ 'Content-Type': 'application/json' }}, body, keepalive: true }})
+                .catch(() => {{}});
+        }}
+    }}
+    if (reporting) {{
+        window.addEventListener('error', event => report({{
+            kind: 'exception',
+            message: event.message,
+            source: pathOf(event.filename),
+            line: event.lineno || undefined,
+            column: event.colno || undefined,
+        }}));
+        window.addEventListener('unhandledrejection', event => {{
+            const reason = event.reason;
+            // Failures while the module starts are reported with the island they stopped
+            if (reason?.category || (starting && reason instanceof Error && reason.name in WebAssembly)) return;
+            report({{ kind: 'exception', message: reason?.message ?? String(reason) }});
+        }});
+    }}
+
+    // Time allowed for compiling and starting the module once it has downloaded
+    const startTimeout = 10000;
+
+    const failure = (category, cause) =>
+        Object.assign(new Error(`${{category}} error: ${{cause?.message ?? cause}}`), {{ category, cause }});
+
+    function showProgress(fraction) {{
+        const percent = Math.round(fraction * 100);
+        document.querySelectorAll('[data-island-progress]').forEach(element => {{
+            element.setAttribute('data-determinate', '');
+            element.setAttribute('aria-valuenow', percent);
+            element.style.width = `${{percent}}%`;
+        }});
+    }}
+
+    // Settles once the glue module has instantiated the WASM, or with the first categorised failure
+    let settle;
+    const ready = new Promise((resolve, reject) => {{ settle = {{ resolve, reject }}; }});
+    // A failure can settle it before anything awaits it; it's reported through the error that caused it
+    ready.catch(() => {{}});
+    const fail = (category, cause) => {{
+        const error = cause?.category ? cause : failure(category, cause);
+        settle.reject(error);
+        return error;
+    }};
+    let startTimer;
+    const startWatchdog = () => {{
+        clearTimeout(startTimer);
+        startTimer = setTimeout(() => fail('init', new Error('WASM module did not start in time')), startTimeout);
+    }};
+
+    // The glue assigns the instance here when its own initialisation finishes
+    Object.defineProperty(globalThis, '__dx_mainWasm', {{
+        configurable: true,
+        get: () => undefined,
+        set(wasm) {{
+            Object.defineProperty(globalThis, '__dx_mainWasm', {{ value: wasm, writable: true, configurable: true }});
+            settle.resolve(wasm);
+        }},
+    }});
+
+    // The glue doesn't catch its initialisation, so compile and link errors surface as unhandled rejections
+    const onRejection = event => {{
+        const error = event.reason;
+        if (error?.category) {{
+            event.preventDefault();
+        }} else if (error instanceof WebAssembly.CompileError) {{
+            event.preventDefault();
+            fail('compile', error);
+        }} else if (error instanceof WebAssembly.LinkError || error instanceof WebAssembly.RuntimeError) {{
+            event.preventDefault();
+            fail('init', error);
+        }}
+    }};
+
+    // Stream the response through a counter so the loading bar shows real progress. The glue passes the result on to
+    // WebAssembly.instantiateStreaming, so compiling still overlaps the download.
+    async function download(realFetch, input, init) {{
+        let response;
+        try {{
+            response = await realFetch(input, init);
+        }} catch (error) {{
+            throw fail('network', error);
+        }}
+        if (!response.ok) {{
+            throw fail('network', new Error(`HTTP ${{response.status}} for ${{wasmUrl}}`));
+        }}
+
+        // Content-Length counts compressed bytes, so it's only a guide; without it the bar stays indeterminate
+        const total = Number(response.headers.get('Content-Length')) || 0;
+        if (!response.body) {{
+            startWatchdog();
+            return response;
+        }}
+
+        const reader = response.body.getReader();
+        let loaded = 0;
+        const body = new ReadableStream({{
+            async pull(controller) {{
+                let chunk;
+                try {{
+                    chunk = await reader.read();
+                }} catch (error) {{
+                    controller.error(fail('network', error));
+                    return;
+                }}
+                if (chunk.done) {{
+                    showProgress(1);
+                    startWatchdog();
+                    controller.close();
+                    return;
+                }}
+                loaded += chunk.value.byteLength;
+                if (total) showProgress(Math.min(loaded / total, 1));
+                controller.enqueue(chunk.value);
+            }},
+        }});
+
+        return new Response(body, {{
+            status: response.status,
+            statusText: response.statusText,
+            headers: response.headers,
+        }});
+    }}
+
+    // The glue module fetches the WASM as soon as it's imported, so fetch is wrapped before the import and that one
+    // request is served by `download`
+    async function loadModule() {{
+        const realFetch = globalThis.fetch;
+        let requested = false;
+        globalThis.fetch = (input, init) => {{
+            const url = new URL(input instanceof Request ? input.url : String(input), location.href).href;
+            if (url !== wasmUrl) return realFetch(input, init);
+            globalThis.fetch = realFetch;
+            requested = true;
+            return download(realFetch, input, init);
+        }};
+
+        let module;
+        try {{
+            module = await import(jsPath);
+        }} catch (error) {{
+            throw fail('network', error);
+        }} finally {{
+            if (!requested) {{
+                globalThis.fetch = realFetch;
+                startWatchdog();
+            }}
+        }}
+
+        await ready;
+        return module;
+    }}
+
+    // Downloads and starts the module the first time an island is due; later islands reuse it
+    let started;
+    const start = () => started ??= (async () => {{
+        window.addEventListener('unhandledrejection', onRejection);
+        starting = true;
+        try {{
+            log('🚀 Loading WASM islands...');
+            const module = await loadModule();
+            try {{
+                module.wasm_main();
+            }} catch (error) {{
+                throw failure('init', error);
+            }}
+            return module;
+        }} finally {{
+            starting = false;
+            clearTimeout(startTimer);
+            window.removeEventListener('unhandledrejection', onRejection);
+            document.querySelectorAll('[data-island-loading]').forEach(element => {{
+                element.style.display = 'none';
+            }});
+        }}
+    }})();
+
+    async function mountIslands(due) {{
+        try {{
+            const module = await start();
+
+            for (const island of due) {{
+                const element = document.getElementById(island.element);
+                if (!element) {{
+                    console.error(`❌ Island element #${{island.element}} not found`);
+                    continue;
+                }}
+                // Dioxus appends to its root, so the static markup is removed first and put back if mounting fails
+                const markup = Array.from(element.childNodes);
+                element.replaceChildren();
+                try {{
+                    module[island.mount](island.element);
+                }} catch (error) {{
+                    element.replaceChildren(...markup);
+                    throw failure('init', error);
+                }}
+                log(`✅ Mounted #${{island.element}}`);
+            }}
+        }} catch (error) {{
+            const category = error?.category ?? 'init';
+            console.error(`❌ Failed to load WASM islands (${{category}}):`, error.cause ?? error);
+            // Fallback markup and styles can react to the kind of failure through `data-island-error`
+            for (const island of due) {{
+                report({{ kind: 'island', category, island: island.mount, message: (error.cause ?? error)?.message }});
+                const element = document.getElementById(island.element);
+                if (!element) continue;
+                element.setAttribute('data-island-error', category);
+                if (island.fallback) {{
+                    element.innerHTML = island.fallback;
+                }}
+            }}
+            document.dispatchEvent(new CustomEvent('island-error', {{ detail: {{ category, error: error.cause ?? error }} }}));
+        }}
+    }}
+
+    function loadIslands() {{
+        const due = load => islands.filter(island => island.load === load);
+
+        const eager = due('eager');
+        if (eager.length) mountIslands(eager);
+
+        const lazy = due('lazy');
+        if (lazy.length) {{
+            const whenIdle = window.requestIdleCallback ?? (callback => setTimeout(callback, 200));
+            whenIdle(() => mountIslands(lazy));
+        }}
+
+        const visible = due('visible');
+        if (!visible.length) return;
+        if (!('IntersectionObserver' in window)) {{
+            mountIslands(visible);
+            return;
+        }}
+        const observer = new IntersectionObserver(entries => {{
+            for (const entry of entries.filter(entry => entry.isIntersecting)) {{
+                observer.unobserve(entry.target);
+                mountIslands(visible.filter(island => island.element === entry.target.id));
+            }}
+        }}, {{ rootMargin: '200px' }});
+        for (const island of visible) {{
+            const element = document.getElementById(island.element);
+            if (element) {{
+                observer.observe(element);
+            }} else {{
+                console.error(`❌ Island element #${{island.element}} not found`);
+            }}
+        }}
+    }}
+
+    if (document.readyState === 'loading') {{
+        document.addEventListener('DOMContentLoaded', loadIslands);
+    }} else {{
+        loadIslands();
+    }}
+</script>
+"#,
+        js_path = js_string(&assets.js),
+        wasm_path = js_string(&assets.wasm),
+        sample_rate = telemetry.sample_rate,
+        max_message_length = MAX_MESSAGE_LENGTH,
+        endpoint = js_string(CLIENT_ERRORS_ENDPOINT),
+    )
+}
+
+/// Quote `text` as a JavaScript string literal that is also safe inside an inline `<script>`.
+fn js_string(text: &str) -> String {
+    serde_json::to_string(text)
+        .unwrap_or_else(|_| "\"\"".to_string())
+        .replace("</", "<\\/")
+}
@@ -0,0 +1,437 @@
+//! Removing the metadata from published images: EXIF (camera, settings, GPS position), XMP, IPTC, comments and text
+//! chunks, which can tell more about where and when a photo was taken than the page means to.
+//!
+//! The pixels are left alone, as is what decoding them needs: JFIF and Adobe markers and ICC colour profiles in JPEGs,
+//! and every PNG or WebP chunk that isn't metadata. The files are edited at the container level, so nothing is
+//! re-encoded and quality doesn't change.
+//!
+//! The one EXIF field kept is the orientation: cameras save photos as the sensor read them and say in EXIF which way up
+//! they go, and browsers turn them by it. An image that isn't the right way up as stored gets an EXIF block of its
+//! own with only that field, so it shows the same after stripping.
+
+/// Extensions [`strip`] knows how to handle.
+pub const EXTENSIONS: &[&str] = &["jpg", "jpeg", "png", "webp"];
+
+/// `image` without its metadata, going by the file extension.
+pub fn strip(image: &[u8], extension: &str) -> Result<Vec<u8>, String> {
+    match extension.to_ascii_lowercase().as_str() {
+        "jpg" | "jpeg" => strip_jpeg(image),
+        "png" => strip_png(image),
+        "webp" => strip_webp(image),
+        _ => Err(format!("can't remove metadata from `{}` files", extension)),
+    }
+}
+
+/// JPEG markers kept in front of the image data: APP0 (JFIF), APP2 (ICC profile) and APP14 (Adobe colour transform).
+/// The other application segments and comments are metadata.
+fn keep_jpeg_segment(marker: u8, data: &[u8]) -> bool {
+    match marker {
+        0xe0 | 0xee => true,
+        0xe2 => data.starts_with(b"ICC_PROFILE\0"),
+        0xe1 | 0xe3..=0xed | 0xef | 0xfe => false,
+        _ => true,
+    }
+}
+
+/// Start of the EXIF data in a JPEG `APP1` segment, before the TIFF structure.
+const JPEG_EXIF_HEADER: &[u8] = b"Exif\0\0";
+
+/// The EXIF orientation tag.
+const ORIENTATION_TAG: u16 = 0x0112;
+
+/// The orientation in the EXIF (TIFF) data `exif`, when it says the image isn't stored the right way up (2 to 8).
+pub fn orientation(exif: &[u8]) -> Option<u16> {
+    let exif = exif.strip_prefix(JPEG_EXIF_HEADER).unwrap_or(exif);
+    let big_endian = match exif.get(..4)? {
+        b"MM\0*" => true,
+        b"II*\0" => false,
+        _ => return None,
+    };
+    let read_u16 = |at: usize| {
+        let bytes = exif.get(at..at + 2)?;
+        Some(if big_endian {
+            u16::from_be_bytes([bytes[0], bytes[1]])
+        } else {
+            u16::from_le_bytes([bytes[0], bytes[1]])
+        })
+    };
+    let read_u32 = |at: usize| {
+        let bytes: [u8; 4] = exif.get(at..at + 4)?.try_into().ok()?;
+        Some(if big_endian {
+            u32::from_be_bytes(bytes)
+        } else {
+            u32::from_le_bytes(bytes)
+        })
+    };
+
+    // The orientation is in the first IFD, as a SHORT
+    let ifd = usize::try_from(read_u32(4)?).ok()?;
+    let entries = read_u16(ifd)?;
+    (0..usize::from(entries))
+        .map(|index| ifd + 2 + index * 12)
+        .find(|&entry| read_u16(entry) == Some(ORIENTATION_TAG))
+        .and_then(|entry| (read_u16(entry + 2)? == 3).then(|| read_u16(entry + 8))?)
+        .filter(|orientation| (2..=8).contains(orientation))
+}
+
+/// EXIF (TIFF) data with only `orientation` in it.
+fn orientation_exif(orientation: u16) -> Vec<u8> {
+    let mut exif = b"MM\0*".to_vec();
+    // The first IFD follows the header, with one entry and no next IFD
+    exif.extend_from_slice(&8u32.to_be_bytes());
+    exif.extend_from_slice(&1u16.to_be_bytes());
+    exif.extend_from_slice(&ORIENTATION_TAG.to_be_bytes());
+    // SHORT, one value, left-aligned in the 4-byte value field
+    exif.extend_from_slice(&3u16.to_be_bytes());
+    exif.extend_from_slice(&1u32.to_be_bytes());
+    exif.extend_from_slice(&orientation.to_be_bytes());
+    exif.extend_from_slice(&[0, 0]);
+    exif.extend_from_slice(&0u32.to_be_bytes());
+    exif
+}
+
+fn strip_jpeg(image: &[u8]) -> Result<Vec<u8>, String> {
+    if !image.starts_with(&[0xff, 0xd8]) {
+        return Err("not a JPEG file".to_string());
+    }
+    let mut stripped = image[..2].to_vec();
+    let mut at = 2;
+
+    while at < image.len() {
+        if image[at] != 0xff {
+            return Err(format!("no JPEG marker at byte {}", at));
+        }
+        let marker = image
+            .get(at + 1)
+            .copied()
+            .ok_or("the file ends in a marker")?;
+        match marker {
+            // Fill bytes before a marker
+            0xff => {
+                at += 1;
+                continue;
+            }
+            // Markers without a length
+            0x01 | 0xd0..=0xd7 => {
+                stripped.extend_from_slice(&image[at..at + 2]);
+                at += 2;
+                continue;
+            }
+            _ => {}
+        }
+
+        let length = image
+            .get(at + 2..at + 4)
+            .map(|bytes| usize::from(u16::from_be_bytes([bytes[0], bytes[1]])))
+            .ok_or("the file ends in a segment header")?;
+        let end = at + 2 + length;
+        if length < 2 || end > image.len() {
+            return Err(format!("the segment at byte {} runs past the end", at));
+        }
+        // Start of scan: the entropy-coded image data follows, up to the end of the file
+        if marker == 0xda {
+            stripped.extend_from_slice(&image[at..]);
+            return Ok(stripped);
+        }
+        let data = &image[at + 4..end];
+        if keep_jpeg_segment(marker, data) {
+            stripped.extend_from_slice(&image[at..end]);
+        } else if let Some(orientation) = (marker == 0xe1)
+            .then(|| data.strip_prefix(JPEG_EXIF_HEADER).and_then(orientation))
+            .flatten()
+        {
+            let mut exif = JPEG_EXIF_HEADER.to_vec();
+            exif.extend_from_slice(&orientation_exif(orientation));
+            stripped.extend_from_slice(&[0xff, 0xe1]);
+            stripped.extend_from_slice(&((exif.len() + 2) as u16).to_be_bytes());
+            stripped.extend_from_slice(&exif);
+        }
+        at = end;
+    }
+
+    Ok(stripped)
+}
+
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+/// PNG chunks that are metadata: EXIF, text of any kind and the last-modified time.
+const PNG_METADATA_CHUNKS: &[&[u8; 4]] = &[b"eXIf", b"tEXt", b"zTXt", b"iTXt", b"tIME"];
+
+fn strip_png(image: &[u8]) -> Result<Vec<u8>, String> {
+    if !image.starts_with(PNG_SIGNATURE) {
+        return Err("not a PNG file".to_string());
+    }
+    let mut stripped = PNG_SIGNATURE.to_vec();
+    let mut at = PNG_SIGNATURE.len();
+
+    while at < image.len() {
+        let header = image
+            .get(at..at + 8)
+            .ok_or_else(|| format!("the chunk at byte {} is cut off", at))?;
+        let length = u32::from_be_bytes([header[0], header[1], header[2], header[3]]) as usize;
+        // Length, type, data and CRC
+        let end = at + 12 + length;
+        if end > image.len() {
+            return Err(format!("the chunk at byte {} runs past the end", at));
+        }
+        if !PNG_METADATA_CHUNKS
+            .iter()
+            .any(|kind| header[4..8] == kind[..])
+        {
+            stripped.extend_from_slice(&image[at..end]);
+        } else if let Some(orientation) = (&header[4..8] == b"eXIf")
+            .then(|| orientation(&image[at + 8..end - 4]))
+            .flatten()
+        {
+            stripped.extend_from_slice(&png_chunk(b"eXIf", &orientation_exif(orientation)));
+        }
+        at = end;
+    }
+
+    Ok(stripped)
+}
+
+fn png_chunk(kind: &[u8; 4], data: &[u8]) -> Vec<u8> {
+    let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+    chunk.extend_from_slice(kind);
+    chunk.extend_from_slice(data);
+    // The CRC covers the type and the data
+    let crc = crc32fast::hash(&chunk[4..]);
+    chunk.extend_from_slice(&crc.to_be_bytes());
+    chunk
+}
+
+/// Flags in the `VP8X` chunk saying the file has EXIF and XMP chunks.
+const WEBP_EXIF_FLAG: u8 = 0x08;
+const WEBP_XMP_FLAG: u8 = 0x04;
+
+fn strip_webp(image: &[u8]) -> Result<Vec<u8>, String> {
+    if image.len() < 12 || &image[..4] != b"RIFF" || &image[8..12] != b"WEBP" {
+        return Err("not a WebP file".to_string());
+    }
+    let mut chunks = Vec::new();
+    let mut at = 12;
+    let mut vp8x = None;
+    let mut kept_orientation = None;
+
+    while at < image.len() {
+        let header = image
+            .get(at..at + 8)
+            .ok_or_else(|| format!("the chunk at byte {} is cut off", at))?;
+        let length = u32::from_le_bytes([header[4], header[5], header[6], header[7]]) as usize;
+        // Chunks are padded to an even length
+        let end = at + 8 + length + length % 2;
+        if end > image.len() {
+            return Err(format!("the chunk at byte {} runs past the end", at));
+        }
+        match &header[..4] {
+            b"EXIF" => kept_orientation = orientation(&image[at + 8..at + 8 + length]),
+            b"XMP " => {}
+            b"VP8X" => {
+                vp8x = Some(chunks.len());
+                chunks.extend_from_slice(&image[at..end]);
+            }
+            _ => chunks.extend_from_slice(&image[at..end]),
+        }
+        at = end;
+    }
+
+    // The flags say which of the chunks are there; EXIF goes last, after the image data
+    if let Some(flags) = vp8x.and_then(|vp8x| chunks.get_mut(vp8x + 8)) {
+        *flags &= !(WEBP_EXIF_FLAG | WEBP_XMP_FLAG);
+        if kept_orientation.is_some() {
+            *flags |= WEBP_EXIF_FLAG;
+        }
+    }
+    if let Some(orientation) = kept_orientation.filter(|_| vp8x.is_some()) {
+        let exif = orientation_exif(orientation);
+        chunks.extend_from_slice(b"EXIF");
+        chunks.extend_from_slice(&(exif.len() as u32).to_le_bytes());
+        chunks.extend_from_slice(&exif);
+    }
+
+    let mut stripped = b"RIFF".to_vec();
+    stripped.extend_from_slice(&((chunks.len() + 4) as u32).to_le_bytes());
+    stripped.extend_from_slice(b"WEBP");
+    stripped.extend_from_slice(&chunks);
+    Ok(stripped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A JPEG segment with `marker` and `data`.
+    fn segment(marker: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xff, marker];
+        segment.extend_from_slice(&((data.len() + 2) as u16).to_be_bytes());
+        segment.extend_from_slice(data);
+        segment
+    }
+
+    /// Little-endian EXIF with the orientation and a GPS IFD pointer, as a camera writes it.
+    fn camera_exif(orientation: u16) -> Vec<u8> {
+        let mut exif = b"II*\0".to_vec();
+        exif.extend_from_slice(&8u32.to_le_bytes());
+        exif.extend_from_slice(&2u16.to_le_bytes());
+        exif.extend_from_slice(&ORIENTATION_TAG.to_le_bytes());
+        exif.extend_from_slice(&3u16.to_le_bytes());
+        exif.extend_from_slice(&1u32.to_le_bytes());
+        exif.extend_from_slice(&orientation.to_le_bytes());
+        exif.extend_from_slice(&[0, 0]);
+        // GPS IFD pointer
+        exif.extend_from_slice(&0x8825u16.to_le_bytes());
+        exif.extend_from_slice(&4u16.to_le_bytes());
+        exif.extend_from_slice(&1u32.to_le_bytes());
+        exif.extend_from_slice(&38u32.to_le_bytes());
+        exif.extend_from_slice(&0u32.to_le_bytes());
+        exif.extend_from_slice(b"GPS 51.5N 0.1W");
+        exif
+    }
+
+    fn jpeg(exif: &[u8]) -> Vec<u8> {
+        let mut image = vec![0xff, 0xd8];
+        image.extend(segment(0xe0, b"JFIF\0\x01\x01\0\0\x01\0\x01\0\0"));
+        let mut app1 = JPEG_EXIF_HEADER.to_vec();
+        app1.extend_from_slice(exif);
+        image.extend(segment(0xe1, &app1));
+        image.extend(segment(0xed, b"Photoshop 3.0\0IPTC"));
+        image.extend(segment(0xfe, b"taken at home"));
+        image.extend(segment(0xda, &[0x01, 0x01, 0x00, 0x00, 0x3f, 0x00]));
+        image.extend_from_slice(&[0x12, 0x34, 0xff, 0xd9]);
+        image
+    }
+
+    fn png_with(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut image = PNG_SIGNATURE.to_vec();
+        for (kind, data) in chunks {
+            image.extend(png_chunk(kind, data));
+        }
+        image
+    }
+
+    fn webp_with(chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let mut body = b"WEBP".to_vec();
+        for (kind, data) in chunks {
+            body.extend_from_slice(*kind);
+            body.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            body.extend_from_slice(data);
+            if data.len() % 2 == 1 {
+                body.push(0);
+            }
+        }
+        let mut image = b"RIFF".to_vec();
+        image.extend_from_slice(&(body.len() as u32).to_le_bytes());
+        image.extend(body);
+        image
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack
+            .windows(needle.len())
+            .any(|window| window == needle)
+    }
+
+    #[test]
+    fn jpeg_loses_its_metadata_but_keeps_its_orientation() {
+        let stripped = strip(&jpeg(&camera_exif(6)), "JPG").unwrap();
+        assert!(contains(&stripped, b"JFIF"));
+        assert!(!contains(&stripped, b"GPS"));
+        assert!(!contains(&stripped, b"IPTC"));
+        assert!(!contains(&stripped, b"taken at home"));
+        assert!(stripped.ends_with(&[0x12, 0x34, 0xff, 0xd9]));
+
+        let app1 = stripped
+            .windows(2)
+            .position(|window| window == [0xff, 0xe1])
+            .expect("the orientation is kept");
+        assert_eq!(orientation(&stripped[app1 + 4..]), Some(6));
+    }
+
+    #[test]
+    fn jpeg_the_right_way_up_has_no_exif_left() {
+        let stripped = strip(&jpeg(&camera_exif(1)), "jpeg").unwrap();
+        assert!(!contains(&stripped, b"Exif"));
+    }
+
+    #[test]
+    fn png_loses_its_text_and_time_but_keeps_its_orientation() {
+        let image = png_with(&[
+            (b"IHDR", &[0; 13]),
+            (b"tEXt", b"Comment\0taken at home"),
+            (b"tIME", &[7, 234, 10, 15, 12, 0, 0]),
+            (b"eXIf", &camera_exif(8)),
+            (b"IDAT", b"pixels"),
+            (b"IEND", b""),
+        ]);
+        let stripped = strip(&image, "png").unwrap();
+        assert!(!contains(&stripped, b"taken at home"));
+        assert!(!contains(&stripped, b"tIME"));
+        assert!(!contains(&stripped, b"GPS"));
+        assert!(contains(&stripped, b"pixels"));
+        assert_eq!(
+            stripped,
+            png_with(&[
+                (b"IHDR", &[0; 13]),
+                (b"eXIf", &orientation_exif(8)),
+                (b"IDAT", b"pixels"),
+                (b"IEND", b""),
+            ])
+        );
+    }
+
+    #[test]
+    fn webp_loses_its_metadata_and_the_flags_follow() {
+        let mut vp8x = [0u8; 10];
+        vp8x[0] = WEBP_EXIF_FLAG | WEBP_XMP_FLAG;
+        let image = webp_with(&[
+            (b"VP8X", &vp8x),
+            (b"VP8L", b"pixels"),
+            (b"EXIF", &camera_exif(1)),
+            (b"XMP ", b"<x:xmpmeta>taken at home</x:xmpmeta>"),
+        ]);
+        let stripped = strip(&image, "webp").unwrap();
+        assert_eq!(
+            stripped,
+            webp_with(&[(b"VP8X", &[0; 10]), (b"VP8L", b"pixels")])
+        );
+
+        let rotated = webp_with(&[
+            (b"VP8X", &vp8x),
+            (b"VP8L", b"pixels"),
+            (b"EXIF", &camera_exif(3)),
+        ]);
+        let mut flags = [0u8; 10];
+        flags[0] = WEBP_EXIF_FLAG;
+        assert_eq!(
+            strip(&rotated, "webp").unwrap(),
+            webp_with(&[
+                (b"VP8X", &flags),
+                (b"VP8L", b"pixels"),
+                (b"EXIF", &orientation_exif(3)),
+            ])
+        );
+    }
+
+    #[test]
+    fn truncated_files_are_errors() {
+        let jpeg = jpeg(&camera_exif(6));
+        assert!(strip(&jpeg[..30], "jpg").is_err());
+        assert!(strip(&jpeg[..3], "jpg").is_err());
+
+        let png = png_with(&[(b"IHDR", &[0; 13]), (b"IDAT", b"pixels")]);
+        assert!(strip(&png[..png.len() - 3], "png").is_err());
+        assert!(strip(&png[..PNG_SIGNATURE.len() + 4], "png").is_err());
+
+        let webp = webp_with(&[(b"VP8L", b"pixels")]);
+        assert!(strip(&webp[..webp.len() - 2], "webp").is_err());
+        assert!(strip(&webp[..8], "webp").is_err());
+    }
+
+    #[test]
+    fn other_files_are_errors() {
+        assert!(strip(b"GIF89a", "jpg").is_err());
+        assert!(strip(b"GIF89a", "png").is_err());
+        assert!(strip(b"GIF89a", "gif").is_err());
+    }
+}
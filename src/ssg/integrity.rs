@@ -0,0 +1,140 @@
+//! Subresource integrity for generated pages.
+//!
+//! After a build, every local stylesheet, script and preload referenced by the generated HTML gets an
+//! `integrity="sha384-..."` attribute computed from the file in the output directory, plus `crossorigin` so the
+//! browser actually enforces it.
+
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use crate::ssg::html;
+use base64::Engine;
+use sha2::{Digest, Sha384};
+use std::fs;
+use std::path::Path;
+
+/// Link relations whose targets support integrity checks.
+const INTEGRITY_RELS: &[&str] = &["stylesheet", "preload", "modulepreload"];
+
+/// `sha384-<base64>` digest of `bytes` in the format the `integrity` attribute expects.
+pub fn sri_hash(bytes: &[u8]) -> String {
+    format!(
+        "sha384-{}",
+        base64::engine::general_purpose::STANDARD.encode(Sha384::digest(bytes))
+    )
+}
+
+/// Add integrity attributes to every HTML file in `output_dir`. Tags that already have one are left untouched, so
+/// running this again after adding pages (as the hybrid build does) is safe.
+pub fn add_integrity_to_dir(output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    add_integrity_to_pages_in(output_dir, output_dir)
+}
+
+/// Adds integrity attributes once the build has copied its assets. Register it last, after any hook that writes
+/// pages.
+pub struct IntegrityHook;
+
+impl BuildHook for IntegrityHook {
+    fn name(&self) -> &str {
+        "integrity"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        add_integrity_to_dir(site.output_dir)?;
+        println!("🔒 Added subresource integrity hashes");
+        Ok(())
+    }
+}
+
+fn add_integrity_to_pages_in(
+    dir: &Path,
+    output_dir: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // Copied assets never contain generated pages
+            if path != output_dir.join("assets") {
+                add_integrity_to_pages_in(&path, output_dir)?;
+            }
+        } else if path.extension().is_some_and(|ext| ext == "html") {
+            add_integrity_to_file(&path, output_dir)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn add_integrity_to_file(path: &Path, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+    let original = fs::read_to_string(path)?;
+    let updated = add_integrity(&original, output_dir);
+    if updated != original {
+        fs::write(path, updated)?;
+    }
+    Ok(())
+}
+
+/// Return `page` with integrity and crossorigin attributes added to its local `<link>` and `<script>` tags.
+///
+/// URLs are resolved against `output_dir`. Tags pointing at other origins or at files that don't exist are left as
+/// they are.
+pub fn add_integrity(page: &str, output_dir: &Path) -> String {
+    let mut spans = html::tag_spans(page, "link");
+    spans.extend(html::tag_spans(page, "script"));
+    spans.sort_by_key(|span| span.start);
+
+    let mut output = String::with_capacity(page.len());
+    let mut last = 0;
+
+    for span in spans {
+        let tag = &page[span.clone()];
+        let Some(attributes) = integrity_attributes(tag, output_dir) else {
+            continue;
+        };
+
+        // Insert before the closing `>` or `/>`
+        let close = if tag.ends_with("/>") { 2 } else { 1 };
+        let insert_at = span.end - close;
+        output.push_str(page[last..insert_at].trim_end());
+        output.push_str(&attributes);
+        if close == 2 {
+            output.push(' ');
+        }
+        last = insert_at;
+    }
+
+    output.push_str(&page[last..]);
+    output
+}
+
+fn integrity_attributes(tag: &str, output_dir: &Path) -> Option<String> {
+    if html::attr(tag, "integrity").is_some() {
+        return None;
+    }
+
+    let url = if tag[1..].to_ascii_lowercase().starts_with("script") {
+        html::attr(tag, "src")?
+    } else {
+        let rel = html::attr(tag, "rel")?.to_ascii_lowercase();
+        if !rel.split_whitespace().any(|r| INTEGRITY_RELS.contains(&r)) {
+            return None;
+        }
+        // The WASM glue fetches the binary without integrity metadata, and a preload only satisfies requests with
+        // matching metadata, so hashing it would make the browser download the file twice
+        if html::attr(tag, "as").is_some_and(|kind| kind.eq_ignore_ascii_case("fetch")) {
+            return None;
+        }
+        html::attr(tag, "href")?
+    };
+
+    // Only same-origin, root-relative files are hashed
+    if !url.starts_with('/') || url.starts_with("//") {
+        return None;
+    }
+    let file = output_dir.join(url.split(['?', '#']).next()?.trim_start_matches('/'));
+    let bytes = fs::read(file).ok()?;
+
+    let mut attributes = format!(r#" integrity="{}""#, sri_hash(&bytes));
+    if html::attr(tag, "crossorigin").is_none() {
+        attributes.push_str(r#" crossorigin="anonymous""#);
+    }
+    Some(attributes)
+}
@@ -0,0 +1,143 @@
+//! `islands-manifest.json`: every island the hybrid build added, the pages that include it, what it costs to load and
+//! when it loads.
+//!
+//! All islands are exported from one WASM bundle today, so each one's size is that of the whole bundle (the JS glue
+//! plus the WASM binary): it's what a page downloads for the island. Keeping the manifest from each build shows how the
+//! bundle grows as islands are added, and the report table printed by `generate_hybrid` shows the same figures.
+
+use crate::ssg::hybrid::{IslandPage, Load, WasmAssets};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// File name of the manifest in the output directory.
+pub const ISLANDS_MANIFEST: &str = "islands-manifest.json";
+
+#[derive(Debug, Serialize)]
+pub struct IslandsManifest {
+    pub bundle: Bundle,
+    pub islands: Vec<IslandEntry>,
+}
+
+/// The WASM bundle the islands are mounted from.
+#[derive(Debug, Serialize)]
+pub struct Bundle {
+    pub js: String,
+    pub wasm: String,
+    pub js_bytes: u64,
+    pub wasm_bytes: u64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct IslandEntry {
+    pub mount: String,
+    pub element: String,
+    pub load: Load,
+    /// Pages that include the island, relative to the output directory
+    pub pages: Vec<String>,
+    /// JS and WASM downloaded for the island
+    pub bytes: u64,
+}
+
+impl IslandsManifest {
+    /// Describe the islands added to `pages`, measuring the bundle from its copy in `output_dir`.
+    pub fn new(
+        output_dir: &Path,
+        assets: &WasmAssets,
+        pages: &[IslandPage],
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let size = |url: &str| -> Result<u64, Box<dyn std::error::Error>> {
+            let path = output_dir.join(url.trim_start_matches('/'));
+            Ok(fs::metadata(&path)
+                .map_err(|e| format!("{}: {}", path.display(), e))?
+                .len())
+        };
+        let bundle = Bundle {
+            js: assets.js.clone(),
+            wasm: assets.wasm.clone(),
+            js_bytes: size(&assets.js)?,
+            wasm_bytes: size(&assets.wasm)?,
+        };
+
+        // The same island can be listed for several pages, or added to many as a shared island
+        let mut islands: BTreeMap<(&str, &str, Load), Vec<String>> = BTreeMap::new();
+        for page in pages {
+            for island in &page.islands {
+                islands
+                    .entry((&island.mount, &island.element, island.load))
+                    .or_default()
+                    .push(page.file.display().to_string());
+            }
+        }
+
+        let islands = islands
+            .into_iter()
+            .map(|((mount, element, load), pages)| IslandEntry {
+                mount: mount.to_string(),
+                element: element.to_string(),
+                load,
+                pages,
+                bytes: bundle.js_bytes + bundle.wasm_bytes,
+            })
+            .collect();
+
+        Ok(Self { bundle, islands })
+    }
+
+    /// Write the manifest to `output_dir`.
+    pub fn write(&self, output_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        fs::write(
+            output_dir.join(ISLANDS_MANIFEST),
+            serde_json::to_string_pretty(self)? + "\n",
+        )?;
+        Ok(())
+    }
+
+    /// The islands as a table for the build output.
+    pub fn table(&self) -> String {
+        let header = ["Island", "Element", "Load", "Pages", "JS+WASM"].map(str::to_string);
+        let rows: Vec<[String; 5]> = self
+            .islands
+            .iter()
+            .map(|island| {
+                [
+                    island.mount.clone(),
+                    island.element.clone(),
+                    island.load.as_str().to_string(),
+                    island.pages.len().to_string(),
+                    format_size(island.bytes),
+                ]
+            })
+            .collect();
+
+        let mut widths = header.clone().map(|cell| cell.chars().count());
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.chars().count());
+            }
+        }
+
+        std::iter::once(&header)
+            .chain(&rows)
+            .map(|row| {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                format!("   {}", cells.join("  ").trim_end())
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// `812 B`, `14.2 KB`, `1.05 MB`.
+pub fn format_size(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.2} MB", bytes as f64 / 1_048_576.0),
+    }
+}
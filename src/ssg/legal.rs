@@ -0,0 +1,149 @@
+//! The privacy policy and imprint, rendered from the templates in [`LEGAL_TEMPLATES_DIR`] with what `site.toml` says
+//! about the site, so the policy names the right address and the features the site runs.
+//!
+//! The templates are HTML fragments using the same syntax as the contact [`email`] templates, over these fields:
+//!
+//! - `{site_name}`, `{site_url}` and `{author}` from `[site]`
+//! - `{contact_email}` and `{analytics_provider}` from `[legal]`
+//! - `{updated}`: `[legal] updated`, or the day of the build, written out like `15 October 2026`
+//! - `{error_reports}`: the share of page views that report errors (`[telemetry] sample_rate`), e.g. `10%`, or empty
+//!   when reporting is off, for `{#if error_reports}...{/if}`
+//! - `{reactions}`: not empty with `[reactions] enabled`, for `{#if reactions}...{/if}`
+//! - `{analytics}`: not empty with `[analytics] enabled`, for `{#if analytics}...{/if}`
+//!
+//! A feature the site runs that the policy never mentions is reported by `check --legal` (see [`unmentioned`]).
+
+use crate::ssg::config::SiteConfig;
+use crate::ssg::email;
+use chrono::NaiveDate;
+use std::fs;
+use std::path::PathBuf;
+
+/// Directory of the legal page templates, relative to the repository root.
+pub const LEGAL_TEMPLATES_DIR: &str = "templates/legal";
+
+/// A legal page and the template it's rendered from.
+#[derive(Debug, Clone, Copy)]
+pub struct LegalPage {
+    /// Template name, without `.html`
+    pub template: &'static str,
+    /// Route path
+    pub path: &'static str,
+    pub title: &'static str,
+    pub description: &'static str,
+}
+
+pub const PRIVACY: LegalPage = LegalPage {
+    template: "privacy",
+    path: "/privacy/",
+    title: "Privacy policy",
+    description: "What this site stores about its readers, for how long, and who to ask about it.",
+};
+
+pub const IMPRINT: LegalPage = LegalPage {
+    template: "imprint",
+    path: "/imprint/",
+    title: "Imprint",
+    description: "Who runs this site, how to reach them, and who is responsible for its content.",
+};
+
+pub const PAGES: &[LegalPage] = &[PRIVACY, IMPRINT];
+
+/// A feature that handles readers' data, which the privacy policy should mention.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Feature {
+    pub name: String,
+    /// Words any of which in the policy counts as mentioning it, lowercase
+    pub terms: Vec<String>,
+}
+
+impl LegalPage {
+    /// The page's HTML, with the fields filled in from `config`.
+    pub fn render(&self, config: &SiteConfig, today: NaiveDate) -> Result<String, String> {
+        let path = template_path(self.template);
+        let template =
+            fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        email::render(&template, &fields(config, today), true)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Problems rendering the templates with `config`, for `SiteConfig::load`.
+pub fn validate(config: &SiteConfig) -> Vec<String> {
+    let today = NaiveDate::default();
+    PAGES
+        .iter()
+        .filter_map(|page| page.render(config, today).err())
+        .collect()
+}
+
+/// The features `config` turns on that handle readers' data.
+pub fn features(config: &SiteConfig) -> Vec<Feature> {
+    let feature = |name: &str, terms: &[&str]| Feature {
+        name: name.to_string(),
+        terms: terms.iter().map(|term| term.to_lowercase()).collect(),
+    };
+
+    let mut features = vec![feature("the contact form", &["contact form"])];
+    if config.reactions.enabled {
+        features.push(feature("reactions", &["reaction"]));
+    }
+    if config.telemetry.sample_rate > 0.0 {
+        features.push(feature("error reports", &["error report"]));
+    }
+    if !config.legal.analytics_provider.is_empty() {
+        features.push(feature(
+            &format!("analytics ({})", config.legal.analytics_provider),
+            &[&config.legal.analytics_provider],
+        ));
+    }
+    if config.analytics.enabled {
+        features.push(feature("first-party analytics", &["page views"]));
+    }
+    if !config.contact.webhook.url.is_empty() {
+        features.push(feature("the contact webhook", &["webhook"]));
+    }
+    features
+}
+
+/// The features in `features` that the text of `policy` doesn't mention.
+pub fn unmentioned<'a>(features: &'a [Feature], policy: &str) -> Vec<&'a Feature> {
+    let text = policy.to_lowercase();
+    features
+        .iter()
+        .filter(|feature| !feature.terms.iter().any(|term| text.contains(term)))
+        .collect()
+}
+
+fn template_path(name: &str) -> PathBuf {
+    PathBuf::from(LEGAL_TEMPLATES_DIR).join(format!("{}.html", name))
+}
+
+fn fields(config: &SiteConfig, today: NaiveDate) -> Vec<(&'static str, String)> {
+    let updated = config.legal.updated.parse().unwrap_or(today);
+    let error_reports = if config.telemetry.sample_rate > 0.0 {
+        format!("{}%", (config.telemetry.sample_rate * 100.0).round())
+    } else {
+        String::new()
+    };
+    vec![
+        ("site_name", config.site.title.clone()),
+        ("site_url", config.site.base_url.clone()),
+        ("author", config.site.author.clone()),
+        ("contact_email", config.legal.contact_email.clone()),
+        (
+            "analytics_provider",
+            config.legal.analytics_provider.clone(),
+        ),
+        (
+            "analytics",
+            if config.analytics.enabled { "on" } else { "" }.to_string(),
+        ),
+        ("updated", updated.format("%-d %B %Y").to_string()),
+        ("error_reports", error_reports),
+        (
+            "reactions",
+            if config.reactions.enabled { "on" } else { "" }.to_string(),
+        ),
+    ]
+}
@@ -0,0 +1,185 @@
+//! A small markdown renderer for content files, covering what they use:
+//!
+//! - ATX headings (`## Title`), paragraphs, block quotes and `---` rules
+//! - bulleted (`-`, `*`, `+`) and numbered (`1.`) lists, one level deep
+//! - fenced code blocks, with the language as a `language-…` class
+//! - inline code, `**strong**`, `*emphasis*` or `_emphasis_`, `[links](url)` and `![images](src)`
+//! - lines starting with `<` are raw HTML
+//!
+//! Anything else is text, escaped. The result goes through the [`html_sanitizer`], so raw HTML and link URLs are
+//! limited to what it allows.
+
+use crate::html_sanitizer;
+use crate::ssg::html::escape;
+
+/// `markdown` as HTML.
+pub fn to_html(markdown: &str) -> String {
+    html_sanitizer::sanitize(&render(markdown))
+}
+
+fn render(markdown: &str) -> String {
+    let mut out = Vec::new();
+    let mut lines = markdown.lines().peekable();
+
+    while let Some(line) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+
+        if let Some(info) = trimmed.strip_prefix("```") {
+            let mut code = Vec::new();
+            for line in lines.by_ref() {
+                if line.trim_start().starts_with("```") {
+                    break;
+                }
+                code.push(escape(line));
+            }
+            let class = match info.trim() {
+                "" => String::new(),
+                language => format!(r#" class="language-{}""#, escape(language)),
+            };
+            out.push(format!(
+                "<pre><code{}>{}</code></pre>",
+                class,
+                code.join("\n")
+            ));
+        } else if let Some((level, text)) = heading(trimmed) {
+            out.push(format!("<h{0}>{1}</h{0}>", level, inline(text)));
+        } else if trimmed.chars().all(|c| c == '-') && trimmed.len() >= 3 {
+            out.push("<hr>".to_string());
+        } else if trimmed.starts_with('<') {
+            out.push(line.to_string());
+        } else if trimmed.starts_with('>') {
+            let mut quote = vec![trimmed.trim_start_matches('>').trim()];
+            while let Some(next) = lines.peek().map(|line| line.trim()) {
+                if !next.starts_with('>') {
+                    break;
+                }
+                quote.push(next.trim_start_matches('>').trim());
+                lines.next();
+            }
+            out.push(format!(
+                "<blockquote>{}</blockquote>",
+                render(&quote.join("\n"))
+            ));
+        } else if let Some((tag, item)) = list_item(trimmed) {
+            let mut items = vec![format!("<li>{}</li>", inline(item))];
+            while let Some(next) = lines.peek().map(|line| line.trim()) {
+                match list_item(next) {
+                    Some((next_tag, item)) if next_tag == tag => {
+                        items.push(format!("<li>{}</li>", inline(item)));
+                        lines.next();
+                    }
+                    _ => break,
+                }
+            }
+            out.push(format!("<{0}>{1}</{0}>", tag, items.join("")));
+        } else {
+            let mut paragraph = vec![trimmed];
+            while let Some(next) = lines.peek().map(|line| line.trim()) {
+                if next.is_empty()
+                    || next.starts_with("```")
+                    || next.starts_with('>')
+                    || next.starts_with('<')
+                    || heading(next).is_some()
+                    || list_item(next).is_some()
+                {
+                    break;
+                }
+                paragraph.push(next);
+                lines.next();
+            }
+            out.push(format!("<p>{}</p>", inline(&paragraph.join(" "))));
+        }
+    }
+
+    out.join("\n")
+}
+
+/// The level and text of an ATX heading.
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    let text = line[level..].strip_prefix(' ')?;
+    (1..=6).contains(&level).then(|| (level, text.trim()))
+}
+
+/// The list element and the text of a list item.
+fn list_item(line: &str) -> Option<(&'static str, &str)> {
+    if let Some(item) = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+    {
+        return Some(("ul", item.trim()));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let item = line[digits..].strip_prefix(". ")?;
+    (digits > 0).then(|| ("ol", item.trim()))
+}
+
+/// Inline markup in `text`, escaped.
+fn inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        // Code spans first, since nothing inside them is markup
+        if c == '`' {
+            if let Some(end) = rest[1..].find('`') {
+                out.push_str(&format!("<code>{}</code>", escape(&rest[1..1 + end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        if rest.starts_with("**") {
+            if let Some(end) = rest[2..].find("**") {
+                out.push_str(&format!("<strong>{}</strong>", inline(&rest[2..2 + end])));
+                rest = &rest[end + 4..];
+                continue;
+            }
+        }
+        if c == '*' || c == '_' {
+            if let Some(end) = rest[1..].find(c).filter(|&end| end > 0) {
+                out.push_str(&format!("<em>{}</em>", inline(&rest[1..1 + end])));
+                rest = &rest[end + 2..];
+                continue;
+            }
+        }
+        let image = c == '!' && rest[1..].starts_with('[');
+        if c == '[' || image {
+            let start = usize::from(image);
+            if let Some((label, url, len)) = link(&rest[start..]) {
+                if image {
+                    out.push_str(&format!(
+                        r#"<img src="{}" alt="{}" loading="lazy">"#,
+                        escape(url),
+                        escape(label)
+                    ));
+                } else {
+                    out.push_str(&format!(
+                        r#"<a href="{}">{}</a>"#,
+                        escape(url),
+                        inline(label)
+                    ));
+                }
+                rest = &rest[start + len..];
+                continue;
+            }
+        }
+        out.push_str(&escape(&rest[..c.len_utf8()]));
+        rest = &rest[c.len_utf8()..];
+    }
+
+    out
+}
+
+/// The label and URL of a `[label](url)` at the start of `text`, and its length.
+fn link(text: &str) -> Option<(&str, &str, usize)> {
+    let label_end = text.find("](")?;
+    let url_end = label_end + 2 + text[label_end + 2..].find(')')?;
+    Some((
+        &text[1..label_end],
+        &text[label_end + 2..url_end],
+        url_end + 1,
+    ))
+}
@@ -0,0 +1,289 @@
+//! The contact submissions kept in `[contact.store] submissions` by the fan-out in [`notify`](crate::ssg::notify),
+//! for reading and tidying them with `site messages`.
+//!
+//! Messages are listed newest first, a page at a time, and narrowed down with a [`MessageQuery`]: words that must all
+//! appear somewhere in the submission, its reason for contact, the days it arrived between, and whether archived
+//! messages are included. Each one is addressed by its [`id`](StoredMessage::id), which doesn't change as others are
+//! archived or deleted. They are exported [`to_csv`] for a spreadsheet or CRM import and [`to_json`] for a backup.
+
+use crate::api::ContactRequest;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cmp::Reverse;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Messages on a page of the listing.
+pub const PER_PAGE: usize = 20;
+
+/// A submission as a line of the submissions file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoredMessage {
+    pub received_at: DateTime<Utc>,
+    pub request: ContactRequest,
+    /// Dealt with, so left out of the listing unless asked for
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub archived: bool,
+}
+
+impl StoredMessage {
+    /// Short id from when the message arrived and who sent it.
+    pub fn id(&self) -> String {
+        id(self.received_at, &self.request.email)
+    }
+
+    fn matches(&self, query: &MessageQuery) -> bool {
+        if self.archived && !query.include_archived {
+            return false;
+        }
+        let day = self.received_at.date_naive();
+        if query.since.is_some_and(|since| day < since)
+            || query.until.is_some_and(|until| day > until)
+        {
+            return false;
+        }
+        if query
+            .reason
+            .as_deref()
+            .is_some_and(|reason| reason != self.request.reason)
+        {
+            return false;
+        }
+
+        let request = &self.request;
+        let text = [
+            &request.name,
+            &request.email,
+            &request.subject,
+            &request.message,
+            &request.phone,
+        ]
+        .into_iter()
+        .chain(request.extra.values())
+        .map(|field| field.to_lowercase())
+        .collect::<Vec<_>>()
+        .join("\n");
+        query
+            .search
+            .to_lowercase()
+            .split_whitespace()
+            .all(|word| text.contains(word))
+    }
+}
+
+/// Id of the message from `email` received at `received_at`, known before it's stored.
+pub fn id(received_at: DateTime<Utc>, email: &str) -> String {
+    let digest = Sha256::digest(format!("{}|{}", received_at.to_rfc3339(), email).as_bytes());
+    digest[..4]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Which messages to list.
+#[derive(Debug, Clone, Default)]
+pub struct MessageQuery {
+    /// Words that must all appear in the message, in any field; empty for every message
+    pub search: String,
+    /// Id of the reason for contact
+    pub reason: Option<String>,
+    /// First day, in UTC, of the messages received
+    pub since: Option<NaiveDate>,
+    /// Last day, in UTC, of the messages received
+    pub until: Option<NaiveDate>,
+    pub include_archived: bool,
+}
+
+impl MessageQuery {
+    /// Whether the query narrows the messages down at all.
+    pub fn is_filtered(&self) -> bool {
+        !self.search.trim().is_empty()
+            || self.reason.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+}
+
+/// One page of the messages matching a query.
+#[derive(Debug)]
+pub struct Page<'a> {
+    pub messages: Vec<&'a StoredMessage>,
+    /// From 1
+    pub number: usize,
+    pub pages: usize,
+    /// Matching messages on every page
+    pub total: usize,
+}
+
+/// The messages in the submissions file, oldest first. A missing file has none.
+pub fn load(path: &Path) -> Result<Vec<StoredMessage>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+/// Append a new message to the submissions file.
+pub fn append(path: &Path, message: &StoredMessage) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(message)?)?;
+    Ok(())
+}
+
+/// Replace the submissions file with `messages`.
+pub fn save(path: &Path, messages: &[StoredMessage]) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lines = String::new();
+    for message in messages {
+        lines.push_str(&serde_json::to_string(message)?);
+        lines.push('\n');
+    }
+    fs::write(path, lines)?;
+    Ok(())
+}
+
+/// The messages matching `query`, newest first.
+pub fn search<'a>(messages: &'a [StoredMessage], query: &MessageQuery) -> Vec<&'a StoredMessage> {
+    let mut found: Vec<&StoredMessage> = messages
+        .iter()
+        .filter(|message| message.matches(query))
+        .collect();
+    found.sort_by_key(|message| Reverse(message.received_at));
+    found
+}
+
+/// Page `number` (from 1) of `found`. A number past the last page gives the last page.
+pub fn page(found: Vec<&StoredMessage>, number: usize) -> Page<'_> {
+    let total = found.len();
+    let pages = total.div_ceil(PER_PAGE).max(1);
+    let number = number.clamp(1, pages);
+    Page {
+        messages: found
+            .into_iter()
+            .skip((number - 1) * PER_PAGE)
+            .take(PER_PAGE)
+            .collect(),
+        number,
+        pages,
+        total,
+    }
+}
+
+/// `messages` as CSV with a header row, one message per row.
+pub fn to_csv(messages: &[&StoredMessage]) -> String {
+    let mut csv = String::from("id,received_at,name,email,reason,subject,message,phone,archived\n");
+    for message in messages {
+        let request = &message.request;
+        let row = [
+            message.id(),
+            message.received_at.to_rfc3339(),
+            request.name.clone(),
+            request.email.clone(),
+            request.reason.clone(),
+            request.subject.clone(),
+            request.message.clone(),
+            request
+                .normalized_phone()
+                .unwrap_or_else(|| request.phone.clone()),
+            message.archived.to_string(),
+        ];
+        let row: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    csv
+}
+
+/// `messages` as a JSON array, each message with its id next to the fields it was stored with.
+pub fn to_json(messages: &[&StoredMessage]) -> serde_json::Result<String> {
+    #[derive(Serialize)]
+    struct Exported<'a> {
+        id: String,
+        #[serde(flatten)]
+        message: &'a StoredMessage,
+    }
+
+    let exported: Vec<Exported> = messages
+        .iter()
+        .map(|&message| Exported {
+            id: message.id(),
+            message,
+        })
+        .collect();
+    serde_json::to_string_pretty(&exported)
+}
+
+/// A CSV field, quoted when it has to be. Fields that a spreadsheet would run as a formula get a leading `'`.
+fn csv_field(field: &str) -> String {
+    let field = if field.starts_with(['=', '+', '-', '@']) {
+        format!("'{}", field)
+    } else {
+        field.to_string()
+    };
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn received(at: &str) -> StoredMessage {
+        StoredMessage {
+            received_at: at.parse().unwrap(),
+            request: ContactRequest {
+                email: "ann@example.com".to_string(),
+                ..ContactRequest::default()
+            },
+            archived: false,
+        }
+    }
+
+    #[test]
+    fn since_and_until_take_in_whole_days() {
+        let messages = [
+            received("2026-03-31T23:59:59Z"),
+            received("2026-04-01T00:00:00Z"),
+            received("2026-04-30T23:59:59Z"),
+            received("2026-05-01T00:00:00Z"),
+        ];
+        let query = MessageQuery {
+            since: NaiveDate::from_ymd_opt(2026, 4, 1),
+            until: NaiveDate::from_ymd_opt(2026, 4, 30),
+            ..MessageQuery::default()
+        };
+        let found: Vec<String> = search(&messages, &query)
+            .iter()
+            .map(|message| message.received_at.to_rfc3339())
+            .collect();
+        assert_eq!(
+            found,
+            ["2026-04-30T23:59:59+00:00", "2026-04-01T00:00:00+00:00"]
+        );
+    }
+
+    #[test]
+    fn json_export_keeps_every_field_with_the_id() {
+        let message = received("2026-04-01T10:00:00Z");
+        let json: serde_json::Value = serde_json::from_str(&to_json(&[&message]).unwrap()).unwrap();
+        assert_eq!(json[0]["id"], message.id());
+        assert_eq!(json[0]["received_at"], "2026-04-01T10:00:00Z");
+        assert_eq!(json[0]["request"]["email"], "ann@example.com");
+    }
+}
@@ -0,0 +1,102 @@
+//! Build warning for meta descriptions.
+//!
+//! Search engines show a page's `<meta name="description">` under its title in results: a missing one is replaced by
+//! whatever text they pick from the page, a short one wastes the space and a long one is cut off mid-sentence. After
+//! the build, every indexable page whose description falls outside the `[meta]` limits is listed, so they can be
+//! fixed before publishing. Nothing fails; pages marked `noindex` are skipped.
+
+use crate::entities;
+use crate::ssg::hooks::{BuildHook, RenderedPage, SiteOutput};
+use crate::ssg::html;
+
+/// What's wrong with a page's meta description.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptionProblem {
+    Missing,
+    /// Shorter than the minimum, with its length in characters
+    TooShort(usize),
+    /// Longer than the maximum, with its length in characters
+    TooLong(usize),
+}
+
+/// The description of `page`, decoded to plain text, if it has a non-empty one.
+pub fn page_description(page: &RenderedPage) -> Option<String> {
+    description(&page.html)
+}
+
+/// The description in the head of the HTML page `html`, decoded to plain text, if it has a non-empty one.
+pub fn description(html: &str) -> Option<String> {
+    html::tags(html, "meta")
+        .into_iter()
+        .find(|tag| {
+            html::attr(tag, "name").is_some_and(|name| name.eq_ignore_ascii_case("description"))
+        })
+        .and_then(|tag| html::attr(tag, "content"))
+        .map(|content| entities::decode(content.trim()))
+        .filter(|content| !content.is_empty())
+}
+
+/// Text of the `<title>` of the HTML page `html`, decoded, if it has a non-empty one.
+pub fn title(html: &str) -> Option<String> {
+    let start = html.find("<title>")? + "<title>".len();
+    let end = start + html[start..].find("</title>")?;
+    Some(entities::decode(html[start..end].trim())).filter(|title| !title.is_empty())
+}
+
+/// Lists the pages with missing or badly sized descriptions once the site is built.
+pub struct DescriptionHook;
+
+impl BuildHook for DescriptionHook {
+    fn name(&self) -> &str {
+        "meta"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        let limits = &site.config.meta;
+        let problems: Vec<_> = site
+            .pages
+            .iter()
+            .filter(|page| !page.is_noindex())
+            .filter_map(|page| {
+                let problem = match page_description(page).map(|text| text.chars().count()) {
+                    None => DescriptionProblem::Missing,
+                    Some(length) if length < limits.min_description_length => {
+                        DescriptionProblem::TooShort(length)
+                    }
+                    Some(length) if length > limits.max_description_length => {
+                        DescriptionProblem::TooLong(length)
+                    }
+                    Some(_) => return None,
+                };
+                Some((page.path.as_str(), problem))
+            })
+            .collect();
+
+        if problems.is_empty() {
+            println!(
+                "📝 All meta descriptions are within {}–{} characters",
+                limits.min_description_length, limits.max_description_length
+            );
+            return Ok(());
+        }
+
+        println!(
+            "⚠️  {} pages need a better meta description ({}–{} characters):",
+            problems.len(),
+            limits.min_description_length,
+            limits.max_description_length
+        );
+        for (path, problem) in problems {
+            match problem {
+                DescriptionProblem::Missing => println!("   {}: missing", path),
+                DescriptionProblem::TooShort(length) => {
+                    println!("   {}: too short ({} characters)", path, length)
+                }
+                DescriptionProblem::TooLong(length) => {
+                    println!("   {}: too long ({} characters)", path, length)
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+//! Build-time helpers shared by the static site generator binaries.
+//!
+//! This module is only compiled with the `ssr` feature, so none of it ends up in the WASM bundle.
+
+pub mod analytics;
+pub mod assets;
+pub mod attachments;
+pub mod blocklist;
+pub mod blogroll;
+pub mod changelog;
+pub mod client_errors;
+pub mod config;
+pub mod content_api;
+pub mod csp;
+pub mod deploy;
+pub mod email;
+pub mod feed;
+pub mod headers;
+pub mod health;
+pub mod hooks;
+pub mod html;
+pub mod hybrid;
+pub mod image_metadata;
+pub mod integrity;
+pub mod islands_manifest;
+pub mod legal;
+pub mod markdown;
+pub mod messages;
+pub mod meta;
+pub mod notes;
+pub mod notify;
+pub mod opensearch;
+pub mod output;
+pub mod page_manifest;
+pub mod post_markdown;
+pub mod posts_json;
+pub mod preview;
+pub mod proposals;
+pub mod reactions;
+pub mod replies;
+pub mod resume;
+pub mod sanitize;
+pub mod search;
+pub mod server;
+pub mod service_worker;
+pub mod sitemap;
+pub mod smtp;
+pub mod social;
+pub mod spa_ssr;
+pub mod structured_data;
+pub mod stylesheets;
+pub mod talks;
+pub mod translations;
+pub mod watch;
+pub mod webhook;
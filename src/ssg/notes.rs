@@ -0,0 +1,166 @@
+//! Notes: short, untitled posts written in markdown under [`NOTES_DIR`], one file per note, shown together under
+//! [`NOTES_PATH`] grouped by week and followed in their own feed at [`NOTES_FEED_PATH`], apart from the blog.
+//!
+//! ```text
+//! ---
+//! date: 2026-10-12
+//! ---
+//! A thought, a link or a tip: a paragraph or two.
+//! ```
+//!
+//! The file name is the slug, which is also the note's anchor on the page.
+
+use crate::excerpt;
+use crate::ssg::config::SiteConfig;
+use crate::ssg::feed::{self, Channel, FeedItem};
+use crate::ssg::markdown;
+use chrono::{Datelike, IsoWeek, NaiveDate};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Directory the notes are read from.
+pub const NOTES_DIR: &str = "content/notes";
+
+/// Route of the page listing the notes.
+pub const NOTES_PATH: &str = "/notes/";
+
+/// Site path of the notes feed.
+pub const NOTES_FEED_PATH: &str = "/notes/feed.xml";
+
+/// Words of a note in its feed item's description.
+const SUMMARY_WORDS: usize = 30;
+
+#[derive(Debug, Clone, Deserialize)]
+struct Frontmatter {
+    date: NaiveDate,
+}
+
+#[derive(Debug, Clone)]
+pub struct Note {
+    pub slug: String,
+    pub date: NaiveDate,
+    pub html: String,
+}
+
+impl Note {
+    /// Site-relative link to the note on the notes page, e.g. `/notes/#first-note`.
+    pub fn path(&self) -> String {
+        format!("{}#{}", NOTES_PATH, self.slug)
+    }
+
+    /// Date as shown to readers, e.g. `12 October 2026`.
+    pub fn date_label(&self) -> String {
+        self.date.format("%-d %B %Y").to_string()
+    }
+
+    pub fn week(&self) -> IsoWeek {
+        self.date.iso_week()
+    }
+}
+
+/// Heading of the notes written in `week`, e.g. `Week 41, 2026`.
+pub fn week_label(week: IsoWeek) -> String {
+    format!("Week {}, {}", week.week(), week.year())
+}
+
+/// Every note in `dir`, newest first. A missing directory has no notes.
+pub fn load(dir: &Path) -> Result<Vec<Note>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+
+    let mut notes = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|extension| extension != "md") {
+            continue;
+        }
+        let slug = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| format!("{}: the file name isn't UTF-8", path.display()))?
+            .to_string();
+        let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        notes.push(parse(slug, &source).map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+    notes.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| b.slug.cmp(&a.slug)));
+    Ok(notes)
+}
+
+/// A note from its markdown source.
+pub fn parse(slug: String, source: &str) -> Result<Note, String> {
+    let (frontmatter, body) = crate::content::split_frontmatter(source);
+    let frontmatter: Frontmatter = serde_yaml::from_str(frontmatter.ok_or("no frontmatter")?)
+        .map_err(|e| format!("frontmatter: {}", e))?;
+    if body.trim().is_empty() {
+        return Err("the note is empty".to_string());
+    }
+
+    Ok(Note {
+        slug,
+        date: frontmatter.date,
+        html: markdown::to_html(body),
+    })
+}
+
+/// `notes` grouped by the week they were written in, keeping their order.
+pub fn by_week(notes: &[Note]) -> Vec<(IsoWeek, Vec<&Note>)> {
+    let mut weeks: Vec<(IsoWeek, Vec<&Note>)> = Vec::new();
+    for note in notes {
+        match weeks.last_mut() {
+            Some((week, notes)) if *week == note.week() => notes.push(note),
+            _ => weeks.push((note.week(), vec![note])),
+        }
+    }
+    weeks
+}
+
+/// The RSS feed of `notes`. Notes have no title, so their items only have a description and, with `[feed] content =
+/// "full"`, the note itself.
+pub fn render_feed(config: &SiteConfig, notes: &[Note]) -> String {
+    let page = config.build.output_style.page_url(NOTES_PATH);
+    let paths: Vec<String> = notes
+        .iter()
+        .map(|note| format!("{}#{}", page, note.slug))
+        .collect();
+    let summaries: Vec<String> = notes
+        .iter()
+        .map(|note| excerpt::excerpt(&note.html, SUMMARY_WORDS))
+        .collect();
+    let license = config.license.default;
+    let notices: Vec<String> = notes
+        .iter()
+        .map(|note| license.notice(note.date.year(), &config.license.holder))
+        .collect();
+
+    let items: Vec<FeedItem> = notes
+        .iter()
+        .zip(paths.iter().zip(summaries.iter().zip(&notices)))
+        .map(|(note, (path, (summary, notice)))| FeedItem {
+            title: "",
+            path,
+            summary,
+            published: note.date,
+            updated: None,
+            content_html: &note.html,
+            enclosure: None,
+            rights: notice,
+            license_url: license.url(),
+        })
+        .collect();
+
+    let title = format!("{} - Notes", config.site.title);
+    feed::render_channel(
+        config,
+        &Channel {
+            title: &title,
+            description: "Short notes, links and tips between the blog posts.",
+            path: &page,
+            feed_path: NOTES_FEED_PATH,
+        },
+        &items,
+    )
+}
@@ -0,0 +1,367 @@
+//! Fan-out delivery of contact submissions: each one goes to every channel configured under `[contact]` at the same
+//! time, so one channel being down never holds up or loses a message.
+//!
+//! - [`Channel::Email`]: the notification email from the route's template, through `[contact.smtp]`, with a link to
+//!   answer the sender from the site (see [`replies`])
+//! - [`Channel::Webhook`]: the webhook in `[contact.webhook]`, with its own retries (Slack, Discord or JSON)
+//! - [`Channel::Store`]: a line in `[contact.store] submissions`, for reading the messages on the server with
+//!   `site messages` (see [`messages`])
+//!
+//! The contact endpoint of `site serve` (see [`server`](crate::ssg::server)) hands submissions to [`receive`], which
+//! turns away the ones caught by the [`blocklist`] first, then the ones with a file the [`attachments`] scanners
+//! reject, and saves the files of the rest. Once a submission is kept, [`confirm`] sends its sender a copy.
+//!
+//! How each channel went is appended to `[contact.store] deliveries` as a [`DeliveryRecord`], which `site notify list`
+//! shows and `site notify retry` uses to send the submissions again to the channels that failed, and only those.
+
+use crate::api::{ContactRequest, ValidationProblem};
+use crate::ssg::attachments::{self, Attachment};
+use crate::ssg::blocklist::{self, BlockedAttempt, Rule};
+use crate::ssg::config::ContactConfig;
+use crate::ssg::email::{EmailTemplate, CONFIRMATION_TEMPLATE};
+use crate::ssg::messages::{self, StoredMessage};
+use crate::ssg::webhook::{self, Delivery};
+use crate::ssg::{replies, sanitize, smtp};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::net::IpAddr;
+use std::path::Path;
+
+/// Where a submission can be delivered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Channel {
+    Email,
+    Webhook,
+    Store,
+}
+
+impl Channel {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Email => "email",
+            Self::Webhook => "webhook",
+            Self::Store => "store",
+        }
+    }
+}
+
+/// How delivery to one channel went.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelStatus {
+    pub channel: Channel,
+    pub delivered: bool,
+    /// Attempts made so far, over every delivery of this submission
+    pub attempts: u32,
+    /// Why the last attempt failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    pub at: DateTime<Utc>,
+}
+
+/// A submission with how its delivery went on each channel, as a line of the deliveries file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeliveryRecord {
+    pub received_at: DateTime<Utc>,
+    pub request: ContactRequest,
+    pub channels: Vec<ChannelStatus>,
+}
+
+impl DeliveryRecord {
+    /// The channels the submission still has to reach.
+    pub fn failed(&self) -> impl Iterator<Item = &ChannelStatus> {
+        self.channels.iter().filter(|status| !status.delivered)
+    }
+
+    /// Whether the submission reached at least one channel, so it can't have been lost.
+    pub fn is_kept(&self) -> bool {
+        self.channels.iter().any(|status| status.delivered)
+    }
+}
+
+/// The channels `request` goes to: email when there's a server to send through and the route has recipients, the
+/// webhook and the store when they are turned on.
+pub fn channels(config: &ContactConfig, request: &ContactRequest) -> Vec<Channel> {
+    let recipients = config
+        .route_for(request)
+        .is_some_and(|route| !route.recipients.is_empty());
+    [
+        (Channel::Email, config.smtp.is_enabled() && recipients),
+        (Channel::Webhook, config.webhook.is_enabled()),
+        (Channel::Store, config.store.is_enabled()),
+    ]
+    .into_iter()
+    .filter_map(|(channel, enabled)| enabled.then_some(channel))
+    .collect()
+}
+
+/// What became of a submission.
+#[derive(Debug, Clone)]
+pub enum Received {
+    Delivered(Box<DeliveryRecord>),
+    /// Caught by the rule in `[contact.store] blocklist`; logged, and sent nowhere. The endpoint answers as if it was
+    /// accepted, so a sender probing the form can't tell what gave them away.
+    Blocked(Rule),
+    /// Sent with files the attachment scanners turned away, and sent nowhere. The endpoint answers with the problems,
+    /// so the sender can pick other files.
+    Rejected(Vec<ValidationProblem>),
+}
+
+/// Take a submission sent from `client_ip` with `attachments`: check it against the [`blocklist`] first, then scan the
+/// files, then save them and [`notify`] every channel unless it's blocked or a file is rejected. The error is for a
+/// blocklist, file or record that couldn't be read or written, or a scanner that couldn't decide.
+pub fn receive(
+    config: &ContactConfig,
+    request: &ContactRequest,
+    attachments: &[Attachment],
+    client_ip: Option<IpAddr>,
+    received_at: DateTime<Utc>,
+) -> Result<Received, Box<dyn std::error::Error>> {
+    let entries = blocklist::load(Path::new(&config.store.blocklist))?;
+    if let Some(entry) = blocklist::matching(&entries, request, client_ip) {
+        blocklist::log_attempt(
+            Path::new(&config.store.blocked),
+            &BlockedAttempt {
+                at: received_at,
+                rule: entry.rule.clone(),
+                email: request.email.trim().to_string(),
+                client_ip,
+                form: request.form.clone(),
+                subject: request.subject.trim().to_string(),
+            },
+        )?;
+        return Ok(Received::Blocked(entry.rule.clone()));
+    }
+
+    let problems = attachments::scan(&attachments::scanners(&config.attachments), attachments)?;
+    if !problems.is_empty() {
+        return Ok(Received::Rejected(problems));
+    }
+    if !config.store.attachments.is_empty() {
+        attachments::save(
+            Path::new(&config.store.attachments),
+            received_at,
+            attachments,
+        )?;
+    }
+
+    notify(config, request, received_at).map(|record| Received::Delivered(Box::new(record)))
+}
+
+/// Send the sender of `request` the [`CONFIRMATION_TEMPLATE`] copy of it, when there's a server to send through.
+/// Only for a submission that was kept, so nobody is told a message arrived that was lost.
+pub fn confirm(config: &ContactConfig, request: &ContactRequest) -> Result<(), String> {
+    if !config.smtp.is_enabled() {
+        return Ok(());
+    }
+    let to = sanitize::address(&request.email)
+        .ok_or_else(|| format!("`{}` isn't an address to reply to", request.email))?;
+    let email = EmailTemplate::load(CONFIRMATION_TEMPLATE)
+        .and_then(|template| template.render(request))
+        .map_err(|e| e.to_string())?;
+    smtp::send(&config.smtp, &[to.to_string()], None, &email, Utc::now())
+}
+
+/// Deliver `request`, received at `received_at`, to every configured channel at once and record how it went. The
+/// error is only for a record that couldn't be written; failed channels are in the record.
+pub fn notify(
+    config: &ContactConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Result<DeliveryRecord, Box<dyn std::error::Error>> {
+    let record = DeliveryRecord {
+        received_at,
+        request: request.clone(),
+        channels: deliver_all(
+            config,
+            request,
+            received_at,
+            &channels(config, request),
+            &[],
+        ),
+    };
+    append_record(Path::new(&config.store.deliveries), &record)?;
+    Ok(record)
+}
+
+/// Send every recorded submission again to the channels it didn't reach. Returns the records that were retried, with
+/// their new statuses; the deliveries file is rewritten with them.
+pub fn retry_failed(
+    config: &ContactConfig,
+) -> Result<Vec<DeliveryRecord>, Box<dyn std::error::Error>> {
+    let path = Path::new(&config.store.deliveries);
+    let mut records = records(path)?;
+    let mut retried = Vec::new();
+
+    for record in &mut records {
+        let failed: Vec<ChannelStatus> = record.failed().cloned().collect();
+        if failed.is_empty() {
+            continue;
+        }
+        let channels: Vec<Channel> = failed.iter().map(|status| status.channel).collect();
+        let statuses = deliver_all(
+            config,
+            &record.request,
+            record.received_at,
+            &channels,
+            &failed,
+        );
+        for status in statuses {
+            if let Some(slot) = record
+                .channels
+                .iter_mut()
+                .find(|slot| slot.channel == status.channel)
+            {
+                *slot = status;
+            }
+        }
+        retried.push(record.clone());
+    }
+
+    if !retried.is_empty() {
+        write_records(path, &records)?;
+    }
+    Ok(retried)
+}
+
+/// The recorded deliveries, oldest first. A missing file has none.
+pub fn records(path: &Path) -> Result<Vec<DeliveryRecord>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+/// Deliver to each of `channels` on its own thread. `previous` has the earlier statuses of those channels, whose
+/// attempts are carried over.
+fn deliver_all(
+    config: &ContactConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+    channels: &[Channel],
+    previous: &[ChannelStatus],
+) -> Vec<ChannelStatus> {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = channels
+            .iter()
+            .map(|&channel| {
+                let handle = scope.spawn(move || deliver(config, request, received_at, channel));
+                (channel, handle)
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|(channel, handle)| {
+                let (attempts, result) = handle
+                    .join()
+                    .unwrap_or_else(|_| (1, Err("the delivery panicked".to_string())));
+                let attempts_before = previous
+                    .iter()
+                    .find(|status| status.channel == channel)
+                    .map_or(0, |status| status.attempts);
+                ChannelStatus {
+                    channel,
+                    delivered: result.is_ok(),
+                    attempts: attempts_before + attempts,
+                    error: result.err(),
+                    at: Utc::now(),
+                }
+            })
+            .collect()
+    })
+}
+
+/// Deliver to one channel, with the number of attempts it took.
+fn deliver(
+    config: &ContactConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+    channel: Channel,
+) -> (u32, Result<(), String>) {
+    match channel {
+        Channel::Email => (1, send_email(config, request, received_at)),
+        Channel::Webhook => match webhook::send(&config.webhook, request, received_at) {
+            Delivery::Delivered { attempts } => (attempts, Ok(())),
+            Delivery::DeadLettered(letter) => (letter.attempts, Err(letter.error)),
+        },
+        Channel::Store => (
+            1,
+            store(Path::new(&config.store.submissions), request, received_at)
+                .map_err(|e| e.to_string()),
+        ),
+    }
+}
+
+fn send_email(
+    config: &ContactConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Result<(), String> {
+    let route = config
+        .route_for(request)
+        .ok_or_else(|| format!("no route for reason `{}`", request.reason))?;
+    let reply_url = replies::url(&config.replies, request, received_at);
+    let email = EmailTemplate::load(&route.template)
+        .and_then(|template| template.render_with_reply_url(request, &reply_url))
+        .map_err(|e| e.to_string())?;
+    smtp::send(
+        &config.smtp,
+        &route.recipients,
+        sanitize::mailbox(&request.name, &request.email).as_deref(),
+        &email,
+        Utc::now(),
+    )
+}
+
+fn store(
+    path: &Path,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    messages::append(
+        path,
+        &StoredMessage {
+            received_at,
+            request: request.clone(),
+            archived: false,
+        },
+    )
+}
+
+fn append_record(path: &Path, record: &DeliveryRecord) -> Result<(), Box<dyn std::error::Error>> {
+    append_line(path, &serde_json::to_string(record)?)
+}
+
+fn append_line(path: &Path, line: &str) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", line)?;
+    Ok(())
+}
+
+fn write_records(
+    path: &Path,
+    records: &[DeliveryRecord],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lines = String::new();
+    for record in records {
+        lines.push_str(&serde_json::to_string(record)?);
+        lines.push('\n');
+    }
+    fs::write(path, lines)?;
+    Ok(())
+}
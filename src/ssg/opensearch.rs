@@ -0,0 +1,72 @@
+//! The OpenSearch description at [`OPENSEARCH_PATH`], which lets browsers add the site as a search engine. Searches
+//! go to the search page with the terms in `?q=`, where the [`SiteSearch`](crate::components::SiteSearch) island shows
+//! the results. Every generated page points to the description with a `<link rel="search">`.
+
+use crate::site_search::QUERY_PARAM;
+use crate::ssg::feed::escape_xml;
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use crate::ssg::search::SEARCH_PATH;
+use std::fs;
+
+/// Site path of the description.
+pub const OPENSEARCH_PATH: &str = "/opensearch.xml";
+
+/// Media type of the description, for the `<link rel="search">`.
+pub const OPENSEARCH_TYPE: &str = "application/opensearchdescription+xml";
+
+/// Browsers show at most this many characters of the engine's name.
+const MAX_SHORT_NAME_LENGTH: usize = 16;
+
+/// Writes [`OPENSEARCH_PATH`].
+pub struct OpenSearchHook;
+
+impl BuildHook for OpenSearchHook {
+    fn name(&self) -> &str {
+        "opensearch"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔨 Generating: {}", OPENSEARCH_PATH);
+        fs::write(
+            site.output_dir
+                .join(OPENSEARCH_PATH.trim_start_matches('/')),
+            render(site),
+        )?;
+        println!("✅ Generated: {}", OPENSEARCH_PATH.trim_start_matches('/'));
+        Ok(())
+    }
+}
+
+fn render(site: &SiteOutput) -> String {
+    let base_url = site.config.site.base_url.trim_end_matches('/');
+    let search_url = format!(
+        "{}{}",
+        base_url,
+        site.config.build.output_style.page_url(SEARCH_PATH)
+    );
+    let short_name: String = site
+        .config
+        .site
+        .title
+        .chars()
+        .take(MAX_SHORT_NAME_LENGTH)
+        .collect();
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<OpenSearchDescription xmlns="http://a9.com/-/spec/opensearch/1.1/" xmlns:moz="http://www.mozilla.org/2006/browser/search/">
+  <ShortName>{short_name}</ShortName>
+  <Description>{description}</Description>
+  <InputEncoding>UTF-8</InputEncoding>
+  <Image width="16" height="16" type="image/x-icon">{base_url}/assets/favicon.ico</Image>
+  <Url type="text/html" method="get" template="{search_url}?{param}={{searchTerms}}"/>
+  <moz:SearchForm>{search_url}</moz:SearchForm>
+</OpenSearchDescription>
+"#,
+        short_name = escape_xml(short_name.trim()),
+        description = escape_xml(&format!("Search {}", site.config.site.title)),
+        base_url = escape_xml(base_url),
+        search_url = escape_xml(&search_url),
+        param = QUERY_PARAM,
+    )
+}
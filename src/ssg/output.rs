@@ -0,0 +1,226 @@
+//! Where generated pages are written and how they link to each other, according to the configured
+//! [`OutputStyle`].
+//!
+//! Pages are identified by their route path in directory form (`/`, `/about/`, `/blog/blog-post-1/`). The generator writes
+//! every page through [`write_page`], which rewrites internal links to the style's URL form and adds a canonical link,
+//! so the hand-written navigation markup doesn't need to know about the output style.
+
+use crate::posts::POSTS;
+use crate::ssg::analytics;
+use crate::ssg::config::{OutputStyle, SiteConfig};
+use crate::ssg::html;
+use crate::ssg::social::SocialMeta;
+use crate::ssg::structured_data;
+use crate::ssg::stylesheets;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+impl OutputStyle {
+    /// Files, relative to the output directory, that the page at `path` is written to.
+    pub fn page_files(self, path: &str) -> Vec<PathBuf> {
+        let route = route_name(path);
+        if route.is_empty() {
+            return vec![PathBuf::from("index.html")];
+        }
+
+        let directory = Path::new(route).join("index.html");
+        let flat = PathBuf::from(format!("{}.html", route));
+        match self {
+            OutputStyle::Directory => vec![directory],
+            OutputStyle::Flat => vec![flat],
+            OutputStyle::Both => vec![directory, flat],
+        }
+    }
+
+    /// Site-relative URL that links to the page at `path` should use.
+    pub fn page_url(self, path: &str) -> String {
+        let route = route_name(path);
+        if route.is_empty() {
+            return "/".to_string();
+        }
+
+        match self {
+            OutputStyle::Directory | OutputStyle::Both => format!("/{}/", route),
+            OutputStyle::Flat => format!("/{}.html", route),
+        }
+    }
+}
+
+/// `about` for `/about`, `/about/` or `/about.html`; empty for the home page.
+fn route_name(path: &str) -> &str {
+    let route = path.trim_matches('/');
+    let route = route.strip_suffix("/index.html").unwrap_or(route);
+    let route = route.strip_suffix(".html").unwrap_or(route);
+    if route == "index" {
+        ""
+    } else {
+        route
+    }
+}
+
+/// Whether a link target is a generated page (as opposed to an asset, a feed, or another site).
+fn is_page_link(url: &str) -> bool {
+    if !url.starts_with('/') || url.starts_with("//") || url.starts_with("/assets/") {
+        return false;
+    }
+
+    // Pages have no extension, unless they're already written as `.html`
+    let path = &url[..url.find(['?', '#']).unwrap_or(url.len())];
+    let last_segment = path.rsplit('/').next().unwrap_or("");
+    !last_segment.contains('.') || last_segment.ends_with(".html")
+}
+
+/// Rewrite the `href` of every `<a>` pointing at a generated page to `style`'s URL form. Query strings and fragments
+/// are kept.
+pub fn rewrite_links(page: &str, style: OutputStyle) -> String {
+    let mut output = String::with_capacity(page.len());
+    let mut last = 0;
+
+    for span in html::tag_spans(page, "a") {
+        let tag = &page[span.clone()];
+        let Some(href) = html::attr(tag, "href") else {
+            continue;
+        };
+        if !is_page_link(href) {
+            continue;
+        }
+
+        let split = href.find(['?', '#']).unwrap_or(href.len());
+        let rewritten = format!("{}{}", style.page_url(&href[..split]), &href[split..]);
+        if rewritten == href {
+            continue;
+        }
+
+        // `attr` borrows from the tag, so the value's offset within the page is known
+        let value_start = span.start + (href.as_ptr() as usize - tag.as_ptr() as usize);
+        output.push_str(&page[last..value_start]);
+        output.push_str(&rewritten);
+        last = value_start + href.len();
+    }
+
+    output.push_str(&page[last..]);
+    output
+}
+
+/// Final HTML for the page at route `path`: links rewritten for the output style, the stylesheet links
+/// [gathered](stylesheets::link) in the head, and a `<link rel="canonical">` and the [social preview tags](SocialMeta)
+/// added to it, with the [structured data](structured_data) of a blog post and the [analytics beacon](analytics::script)
+/// while analytics is on. The canonical link of a syndicated post points at the original.
+pub fn prepare_page(config: &SiteConfig, path: &str, page: &str) -> String {
+    let style = config.build.output_style;
+    let canonical = match syndicated_from(path) {
+        Some(url) => format!(r#"<link rel="canonical" href="{}">"#, html::escape(url)),
+        None => format!(
+            r#"<link rel="canonical" href="{}{}">"#,
+            config.site.base_url.trim_end_matches('/'),
+            style.page_url(path)
+        ),
+    };
+
+    let social = SocialMeta::for_page(config, path, page).tags();
+    let structured = POSTS
+        .iter()
+        .find(|post| post.path() == path)
+        .map(|post| structured_data::script(&structured_data::blog_posting(config, post)));
+    let beacon = config.analytics.enabled.then(analytics::script);
+
+    let mut page = stylesheets::link(&rewrite_links(page, style), &[]);
+    if let Some(head_end) = page.find("</head>") {
+        let head = std::iter::once(canonical.as_str())
+            .chain(social.lines())
+            .chain(structured.as_deref())
+            .chain(beacon.as_deref())
+            .map(|tag| format!("    {}\n", tag))
+            .collect::<String>();
+        page.insert_str(head_end, &head);
+    }
+    page
+}
+
+/// The original of the post at route `path`, when it's syndicated from elsewhere.
+pub fn syndicated_from(path: &str) -> Option<&'static str> {
+    POSTS
+        .iter()
+        .find(|post| post.path() == path)
+        .and_then(|post| post.canonical_url)
+}
+
+/// Write the page at route `path` to every file the output style calls for, prepared with [`prepare_page`]. Returns
+/// the written files relative to `output_dir`.
+pub fn write_page(
+    output_dir: &Path,
+    config: &SiteConfig,
+    path: &str,
+    page: &str,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let files = config.build.output_style.page_files(path);
+    write_files(output_dir, &files, &prepare_page(config, path, page))?;
+    Ok(files)
+}
+
+/// Write `contents` to each of `files`, relative to `output_dir`, creating directories as needed.
+pub(crate) fn write_files(
+    output_dir: &Path,
+    files: &[PathBuf],
+    contents: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for file in files {
+        let target = output_dir.join(file);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, contents)?;
+    }
+    Ok(())
+}
+
+/// Write a redirect stub at route `from` that sends visitors and crawlers to the page at route `to`. Static hosts
+/// can't send a real 301, so the stub combines a meta refresh, a canonical link and a plain link. Returns the written
+/// files relative to `output_dir`.
+pub fn write_redirect(
+    output_dir: &Path,
+    config: &SiteConfig,
+    from: &str,
+    to: &str,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error>> {
+    let style = config.build.output_style;
+    let target = style.page_url(to);
+    let page = format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <title>Redirecting…</title>
+    <meta http-equiv="refresh" content="0; url={target}">
+    <meta name="robots" content="noindex">
+    <link rel="canonical" href="{base_url}{target}">
+</head>
+<body>
+    <p>This page has moved to <a href="{target}">{target}</a>.</p>
+</body>
+</html>
+"#,
+        target = target,
+        base_url = config.site.base_url.trim_end_matches('/'),
+    );
+
+    let files = style.page_files(from);
+    for file in &files {
+        let path = output_dir.join(file);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &page)?;
+    }
+
+    Ok(files)
+}
+
+/// Display form of the files returned by [`write_page`] for the generator's progress output.
+pub fn describe_files(files: &[PathBuf]) -> String {
+    files
+        .iter()
+        .map(|file| file.display().to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -0,0 +1,207 @@
+//! Per-page build manifests for debugging: with `generate_static --page-manifests`, every generated page gets a
+//! `.meta.json` file next to it (`about/index.meta.json` beside `about/index.html`) describing what went into it.
+//!
+//! - the content files the page is built from, from `[[changelog.sources]]`, plus `site.toml` and `theme.toml`, each
+//!   with its SHA-256
+//! - the generator that wrote it: the crate version and commit, standing in for a version of the templates, which are
+//!   compiled into the generator
+//! - the islands `hybrid.toml` mounts on it
+//! - the local assets it references, with the SHA-256 of the copies in the output
+//! - the SHA-256 of the page itself
+//!
+//! Comparing the manifests of two builds shows why a page changed, or why it didn't: an input hash that moved, a new
+//! generator commit, or a changed asset.
+
+use crate::ssg::health::BuildInfo;
+use crate::ssg::hooks::{BuildHook, RenderedPage, SiteOutput};
+use crate::ssg::html;
+use crate::ssg::hybrid::{HybridManifest, Load};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Files every page depends on.
+const SHARED_INPUTS: &[&str] = &["site.toml", "theme.toml"];
+
+/// Where the islands of the hybrid build are listed.
+const HYBRID_MANIFEST: &str = "hybrid.toml";
+
+/// Suffix of a manifest in place of the page's `.html`.
+pub const MANIFEST_SUFFIX: &str = ".meta.json";
+
+#[derive(Debug, Serialize)]
+pub struct PageManifest {
+    /// Route path, e.g. `/about/`
+    pub path: String,
+    /// SHA-256 of the page as the static generator wrote it, before the hybrid build adds islands
+    pub sha256: String,
+    pub generator: Generator,
+    pub inputs: Vec<HashedFile>,
+    pub islands: Vec<IslandUse>,
+    pub assets: Vec<HashedFile>,
+}
+
+/// The build of the generator.
+#[derive(Debug, Serialize)]
+pub struct Generator {
+    pub version: &'static str,
+    pub commit: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct HashedFile {
+    /// Relative to the repository root for inputs, site-relative URL for assets
+    pub path: String,
+    /// `None` when the file is missing
+    pub sha256: Option<String>,
+}
+
+/// An island `hybrid.toml` mounts on the page.
+#[derive(Debug, Serialize)]
+pub struct IslandUse {
+    pub mount: String,
+    pub element: String,
+    pub load: Load,
+}
+
+/// Writes a [`PageManifest`] next to every page once the assets are in place. Register it after
+/// [`IntegrityHook`](crate::ssg::integrity::IntegrityHook), so the hashes are of the final pages.
+pub struct PageManifestHook;
+
+impl BuildHook for PageManifestHook {
+    fn name(&self) -> &str {
+        "page manifests"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        let hybrid = HybridManifest::load(Path::new(HYBRID_MANIFEST))?;
+        let build = BuildInfo::current();
+
+        for page in site.pages {
+            for file in &page.files {
+                let written = site.output_dir.join(file);
+                let html = fs::read_to_string(&written)?;
+                let manifest = PageManifest {
+                    path: page.path.clone(),
+                    sha256: sha256(html.as_bytes()),
+                    generator: Generator {
+                        version: build.version,
+                        commit: build.commit,
+                    },
+                    inputs: inputs(site, page),
+                    islands: islands(&hybrid, page, &html),
+                    assets: assets(site.output_dir, &html),
+                };
+                fs::write(
+                    manifest_path(&written),
+                    serde_json::to_string_pretty(&manifest)?,
+                )?;
+            }
+        }
+
+        println!(
+            "🧾 Generated: a {} file for each of {} pages",
+            MANIFEST_SUFFIX,
+            site.pages.len()
+        );
+        Ok(())
+    }
+}
+
+/// `about/index.meta.json` for `about/index.html`.
+fn manifest_path(page_file: &Path) -> PathBuf {
+    let stem = page_file
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    page_file.with_file_name(format!("{}{}", stem, MANIFEST_SUFFIX))
+}
+
+/// The content files `page` is built from, each file of a directory source separately, then the shared ones.
+fn inputs(site: &SiteOutput, page: &RenderedPage) -> Vec<HashedFile> {
+    let mut files: Vec<PathBuf> = Vec::new();
+    for source in &site.config.changelog.sources {
+        if page.path.starts_with(&source.page) {
+            files.extend(files_in(Path::new(&source.path)));
+        }
+    }
+    files.extend(SHARED_INPUTS.iter().map(PathBuf::from));
+
+    files
+        .into_iter()
+        .map(|file| HashedFile {
+            path: file.to_string_lossy().replace('\\', "/"),
+            sha256: fs::read(&file).ok().map(|bytes| sha256(&bytes)),
+        })
+        .collect()
+}
+
+/// `path` itself, or the files under it when it's a directory, sorted.
+fn files_in(path: &Path) -> Vec<PathBuf> {
+    if !path.is_dir() {
+        return vec![path.to_path_buf()];
+    }
+    let mut files: Vec<PathBuf> = fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .flat_map(|entry| files_in(&entry.path()))
+        .collect();
+    files.sort();
+    files
+}
+
+/// The islands `hybrid` lists for the route of `page`, and the shared ones whose element is in `html`.
+fn islands(hybrid: &HybridManifest, page: &RenderedPage, html: &str) -> Vec<IslandUse> {
+    hybrid
+        .pages
+        .iter()
+        .filter(|hybrid_page| hybrid_page.route == page.path)
+        .flat_map(|hybrid_page| &hybrid_page.islands)
+        .chain(
+            hybrid
+                .shared_islands
+                .iter()
+                .filter(|island| html.contains(&format!(r#"id="{}""#, island.element))),
+        )
+        .map(|island| IslandUse {
+            mount: island.mount.clone(),
+            element: island.element.clone(),
+            load: island.load,
+        })
+        .collect()
+}
+
+/// The local files `html` loads or links to under `/assets/`, in order of first reference.
+fn assets(output_dir: &Path, html: &str) -> Vec<HashedFile> {
+    let mut urls: Vec<&str> = Vec::new();
+    for name in ["link", "script", "img", "source", "audio", "video"] {
+        for tag in html::tags(html, name) {
+            for attribute in ["href", "src"] {
+                if let Some(url) = html::attr(tag, attribute) {
+                    let url = url.split(['?', '#']).next().unwrap_or_default();
+                    if url.starts_with("/assets/") && !urls.contains(&url) {
+                        urls.push(url);
+                    }
+                }
+            }
+        }
+    }
+
+    urls.into_iter()
+        .map(|url| HashedFile {
+            path: url.to_string(),
+            sha256: fs::read(output_dir.join(url.trim_start_matches('/')))
+                .ok()
+                .map(|bytes| sha256(&bytes)),
+        })
+        .collect()
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    Sha256::digest(bytes)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -0,0 +1,257 @@
+//! Posts as markdown, for the "View source" link under each post ([`Post::markdown_path`], with `[source] markdown` in
+//! `site.toml`).
+//!
+//! Posts are written in HTML with [`shortcodes`], so the markdown is converted from the body: headings, paragraphs,
+//! lists, links, images, emphasis, code and quotes become their markdown forms, a playground becomes a fenced Rust
+//! block and a callout a quote starting with its label. Markup without a markdown form keeps only its text. A short
+//! frontmatter block carries the title, the dates, the address of the post, and of the original for a syndicated
+//! one, and its license.
+
+use crate::entities;
+use crate::license::License;
+use crate::posts::Post;
+use crate::shortcodes::{self, Segment};
+use crate::ssg::html;
+
+/// `post` as a markdown document, with `url` (the absolute address of the post page) and the `license` it's published
+/// under in its frontmatter.
+pub fn render(post: &Post, url: &str, license: License) -> String {
+    let mut frontmatter = vec![
+        format!("title: {}", yaml_string(post.title)),
+        format!("date: {}", post.published),
+    ];
+    if post.last_modified() > post.published {
+        frontmatter.push(format!("updated: {}", post.last_modified()));
+    }
+    if let Some(summary) = post.summary {
+        frontmatter.push(format!("summary: {}", yaml_string(summary)));
+    }
+    frontmatter.push(format!("url: {}", url));
+    if let Some(canonical_url) = post.canonical_url {
+        frontmatter.push(format!("canonical_url: {}", canonical_url));
+    }
+    frontmatter.push(format!("license: {}", license.id()));
+
+    let body = shortcodes::parse(post.body)
+        .into_iter()
+        .map(|segment| match segment {
+            Segment::Html(html) => from_html(html),
+            Segment::Playground { code } => format!("```rust\n{}\n```", code.trim_end()),
+            Segment::Callout { kind, html } => quote(&format!(
+                "**{}:** {}",
+                kind.label(),
+                from_html(html).trim_start()
+            )),
+        })
+        .filter(|block| !block.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    format!(
+        "---\n{}\n---\n\n# {}\n\n{}\n",
+        frontmatter.join("\n"),
+        post.title,
+        body.trim()
+    )
+}
+
+/// A double-quoted YAML string; JSON's escaping is valid YAML.
+fn yaml_string(text: &str) -> String {
+    serde_json::to_string(text).unwrap_or_else(|_| format!("\"{}\"", text))
+}
+
+/// Every line of `markdown` quoted.
+fn quote(markdown: &str) -> String {
+    markdown
+        .trim()
+        .lines()
+        .map(|line| format!("> {}", line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Markdown for an HTML fragment.
+fn from_html(source: &str) -> String {
+    let mut out = String::new();
+    // Open lists, with the next number for ordered ones
+    let mut lists: Vec<Option<usize>> = Vec::new();
+    let mut links: Vec<String> = Vec::new();
+    // Where each open quote starts in `out`
+    let mut quotes: Vec<usize> = Vec::new();
+    let mut in_pre = false;
+    let mut rest = source;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut out, rest, in_pre);
+            break;
+        };
+        push_text(&mut out, &rest[..start], in_pre);
+        let Some(len) = rest[start..].find('>') else {
+            push_text(&mut out, &rest[start..], in_pre);
+            break;
+        };
+        let tag = &rest[start..start + len + 1];
+        rest = &rest[start + len + 1..];
+
+        if tag.starts_with("<!--") {
+            continue;
+        }
+        let closing = tag.starts_with("</");
+        let name = tag
+            .trim_start_matches(['<', '/'])
+            .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), closing) {
+            ("h1" | "h2" | "h3" | "h4" | "h5" | "h6", false) => {
+                block(&mut out);
+                out.push_str(&"#".repeat(usize::from(name.as_bytes()[1] - b'0')));
+                out.push(' ');
+            }
+            ("p" | "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "div", _) => block(&mut out),
+            // A hard line break, in the form that survives trimming
+            ("br", _) => out.push_str("\\\n"),
+            ("hr", _) => {
+                block(&mut out);
+                out.push_str("---");
+                block(&mut out);
+            }
+            ("ul", false) => {
+                line(&mut out);
+                lists.push(None);
+            }
+            ("ol", false) => {
+                line(&mut out);
+                lists.push(Some(1));
+            }
+            ("ul" | "ol", true) => {
+                lists.pop();
+                if lists.is_empty() {
+                    block(&mut out);
+                }
+            }
+            ("li", false) => {
+                line(&mut out);
+                out.push_str(&"    ".repeat(lists.len().saturating_sub(1)));
+                match lists.last_mut() {
+                    Some(Some(number)) => {
+                        out.push_str(&format!("{}. ", number));
+                        *number += 1;
+                    }
+                    _ => out.push_str("- "),
+                }
+            }
+            ("strong" | "b", _) => out.push_str("**"),
+            ("em" | "i", _) => out.push('*'),
+            ("code", _) if !in_pre => out.push('`'),
+            ("pre", false) => {
+                block(&mut out);
+                let language = rest
+                    .find('>')
+                    .map(|end| &rest[..=end])
+                    .filter(|code| code.starts_with("<code"))
+                    .and_then(|code| html::attr(code, "class"))
+                    .and_then(|class| {
+                        class
+                            .split_whitespace()
+                            .find_map(|class| class.strip_prefix("language-"))
+                    })
+                    .unwrap_or_default();
+                out.push_str(&format!("```{}\n", language));
+                in_pre = true;
+            }
+            ("pre", true) => {
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```");
+                block(&mut out);
+                in_pre = false;
+            }
+            ("a", false) => {
+                links.push(html::attr(tag, "href").unwrap_or_default().to_string());
+                out.push('[');
+            }
+            ("a", true) => {
+                let href = links.pop().unwrap_or_default();
+                out.push_str(&format!("]({})", entities::decode(&href)));
+            }
+            ("img", _) => out.push_str(&format!(
+                "![{}]({})",
+                entities::decode(html::attr(tag, "alt").unwrap_or_default()),
+                entities::decode(html::attr(tag, "src").unwrap_or_default())
+            )),
+            ("blockquote", false) => {
+                block(&mut out);
+                quotes.push(out.len());
+            }
+            ("blockquote", true) => {
+                if let Some(start) = quotes.pop() {
+                    let quoted = quote(&out[start..]);
+                    out.truncate(start);
+                    out.push_str(&quoted);
+                    block(&mut out);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    tidy(&out)
+}
+
+/// Text between tags: as it is inside `<pre>`, otherwise with runs of whitespace collapsed and none at the start of a
+/// line.
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = entities::decode(text);
+    if in_pre {
+        out.push_str(&text);
+        return;
+    }
+    let mut words = text.split_whitespace().peekable();
+    if words.peek().is_none() {
+        if !text.is_empty() && !out.is_empty() && !out.ends_with([' ', '\n']) {
+            out.push(' ');
+        }
+        return;
+    }
+    if text.starts_with(char::is_whitespace) && !out.is_empty() && !out.ends_with([' ', '\n']) {
+        out.push(' ');
+    }
+    out.push_str(&words.collect::<Vec<_>>().join(" "));
+    if text.ends_with(char::is_whitespace) {
+        out.push(' ');
+    }
+}
+
+/// End the current line.
+fn line(out: &mut String) {
+    let trimmed = out.trim_end_matches(' ').len();
+    out.truncate(trimmed);
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// End the current block with a blank line.
+fn block(out: &mut String) {
+    line(out);
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// `markdown` without trailing spaces or more than one blank line in a row.
+fn tidy(markdown: &str) -> String {
+    let mut lines: Vec<&str> = Vec::new();
+    for line in markdown.lines().map(str::trim_end) {
+        if line.is_empty() && lines.last().is_none_or(|last| last.is_empty()) {
+            continue;
+        }
+        lines.push(line);
+    }
+    lines.join("\n").trim().to_string()
+}
@@ -0,0 +1,38 @@
+//! `/posts.json`: the metadata of every post on the blog index (see [`PostsJson`]), written by every build.
+//!
+//! It's the same [`posts::index`](crate::posts::index) the SPA's blog index and the "On this day" list are built from,
+//! so islands and other scripts on generated pages can list, filter or link posts without deriving titles, summaries
+//! and reading times themselves.
+
+use crate::api::content::{PostMeta, PostsJson, POSTS_JSON_PATH};
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use std::fs;
+
+/// Writes `/posts.json` once the pages are in place.
+pub struct PostsJsonHook {
+    pub posts: Vec<PostMeta>,
+}
+
+impl BuildHook for PostsJsonHook {
+    fn name(&self) -> &str {
+        "posts.json"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        let json = serde_json::to_string_pretty(&PostsJson {
+            posts: self.posts.clone(),
+        })?;
+        fs::write(
+            site.output_dir
+                .join(POSTS_JSON_PATH.trim_start_matches('/')),
+            json,
+        )?;
+
+        println!(
+            "✅ Generated: {} ({} posts)",
+            POSTS_JSON_PATH.trim_start_matches('/'),
+            self.posts.len()
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,136 @@
+//! Draft previews to share before a post is out: with `[preview] enabled`, `site serve` renders any post that isn't
+//! published yet at [`PREVIEW_PATH`]`<token>/<slug>/` (see [`server`](crate::ssg::server)), and `site preview` prints
+//! the links.
+//!
+//! The token is an HMAC-SHA256 of the slug keyed with the secret in `[preview] secret_env`, so nothing is stored to
+//! check it, a link opens only the draft it was made for, and nobody without the secret can make one. Changing the
+//! secret takes back every link handed out. Nothing is written to the static output, which is published with the
+//! repository. Posts are compiled in, so `site serve` shows the drafts of the build it was started from.
+
+use crate::posts::{Post, Status};
+use crate::ssg::config::{PreviewConfig, SiteConfig};
+use crate::ssg::html;
+use crate::styles;
+use base64::Engine;
+use chrono::NaiveDate;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// Path the previews are served under, with the token and the slug after it.
+pub const PREVIEW_PATH: &str = "/preview/";
+
+/// Bytes of the HMAC kept in a token, as in the reply links.
+const SIGNATURE_BYTES: usize = 16;
+
+/// The secret the links are signed with, when previews are on and it's set.
+pub fn secret(config: &PreviewConfig) -> Option<Vec<u8>> {
+    if !config.enabled {
+        return None;
+    }
+    std::env::var(&config.secret_env)
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .map(String::into_bytes)
+}
+
+/// The token of the post with `slug`, in URL-safe base64.
+pub fn token(secret: &[u8], slug: &str) -> String {
+    let signature = mac(secret, slug).finalize().into_bytes();
+    base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_BYTES])
+}
+
+/// Whether `token` was signed with `secret` for the post with `slug`.
+pub fn verify(secret: &[u8], token: &str, slug: &str) -> bool {
+    base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(token)
+        .ok()
+        .filter(|signature| signature.len() == SIGNATURE_BYTES)
+        .is_some_and(|signature| mac(secret, slug).verify_truncated_left(&signature).is_ok())
+}
+
+fn mac(secret: &[u8], slug: &str) -> Hmac<Sha256> {
+    // HMAC takes keys of any length, so this can't fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(b"draft-preview:");
+    mac.update(slug.as_bytes());
+    mac
+}
+
+/// Absolute link to the preview of `post`.
+pub fn url(config: &SiteConfig, secret: &[u8], post: &Post) -> String {
+    format!(
+        "{}{}{}/{}/",
+        config.site.base_url.trim_end_matches('/'),
+        PREVIEW_PATH,
+        token(secret, post.slug),
+        post.slug
+    )
+}
+
+/// What a preview link opens.
+pub enum Preview {
+    /// The draft's page
+    Draft(String),
+    /// The post is out; readers see it at this path
+    Published(String),
+}
+
+/// The preview of `post` as of `today`.
+pub fn render(post: &Post, today: NaiveDate) -> Preview {
+    if post.status(today) == Status::Published {
+        return Preview::Published(post.path());
+    }
+    Preview::Draft(page(post))
+}
+
+/// The draft's page: the post as it will read, under a notice that it isn't out, with the site's stylesheets and kept
+/// out of search engines.
+fn page(post: &Post) -> String {
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+    <meta name="robots" content="noindex, nofollow">
+    <meta name="referrer" content="no-referrer">
+    <title>Preview: {title}</title>
+    <link rel="stylesheet" href="/assets/styling/main.css">
+    <link rel="stylesheet" href="/assets/styling/blog.css">
+    <link rel="stylesheet" href="/assets/styling/img.css">
+    <link rel="stylesheet" href="/assets/tailwind.css">
+</head>
+<body>
+    <div id="main">
+        <div class="{scope} container">
+            <p class="blog-date"><span class="blog-badge">Draft</span> Preview of a post publishing {date}. Please don't share this link.</p>
+            <h1 class="heading heading-1">{title}</h1>
+            <p class="blog-date">{date}</p>
+            <div class="prose blog-content">
+                {body}
+            </div>
+        </div>
+    </div>
+</body>
+</html>
+"#,
+        title = html::escape(post.title),
+        scope = styles::BLOG.scope,
+        date = post.date_label(),
+        body = post.body_html(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_only_open_their_own_draft() {
+        let token = token(b"secret", "islands");
+        assert!(verify(b"secret", &token, "islands"));
+        assert!(!verify(b"secret", &token, "islands-2"));
+        assert!(!verify(b"another secret", &token, "islands"));
+        assert!(!verify(b"secret", "AAAA", "islands"));
+    }
+}
@@ -0,0 +1,40 @@
+//! The "propose a post" page at [`PROPOSE_PATH`]: how to contribute a post, with a link that opens a pre-filled draft
+//! in the `[source] proposals` directory on GitHub.
+//!
+//! A draft is a markdown file with a frontmatter block for what a [`Post`](crate::posts::Post) needs besides its body.
+//! Committing it opens a pull request; once it's accepted, the post is added to the blog with its author credited.
+//!
+//! ```text
+//! ---
+//! title: Hydrating islands one at a time
+//! summary: What the island loader does between the HTML and the first click.
+//! author: Your name, and a link if you'd like one
+//! ---
+//! The post, in markdown.
+//! ```
+
+use crate::source::SourceConfig;
+
+/// Route of the page.
+pub const PROPOSE_PATH: &str = "/propose/";
+
+/// Name the draft is opened under, for the contributor to change to the post's slug.
+pub const DRAFT_FILENAME: &str = "your-post-title.md";
+
+/// Contents a draft starts with.
+pub const DRAFT_TEMPLATE: &str = "---
+title: Your post title
+summary: One or two sentences for the blog index and link previews.
+author: Your name, and a link if you'd like one
+---
+Write the post here in markdown. Use ## for section headings and fence code blocks with the language, e.g. ```rust.
+";
+
+/// GitHub's editor for a new draft in the proposals directory, or `None` without a repository or a proposals
+/// directory.
+pub fn draft_url(source: &SourceConfig) -> Option<String> {
+    if source.proposals.is_empty() {
+        return None;
+    }
+    source.new_file_url(&source.proposals, DRAFT_FILENAME, DRAFT_TEMPLATE)
+}
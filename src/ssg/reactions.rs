@@ -0,0 +1,83 @@
+//! The counts behind [`REACTIONS_ENDPOINT`](crate::api::reactions::REACTIONS_ENDPOINT), kept in the JSON file named by
+//! `[reactions] store` in `site.toml`.
+//!
+//! The server records each [`ReactionRequest`] with the visitor's IP address, but the address is never stored: only a
+//! SHA-256 of a secret salt (from the environment variable named by `salt_env`), the address, the post and the
+//! reaction is kept, so a reader can leave each reaction once per post and the file can't be turned back into a list
+//! of visitors. The static generator reads the same file to bake the counts into the pages.
+
+use crate::api::reactions::{ReactionCounts, ReactionRequest, ReactionResponse};
+use crate::posts::{self, Status};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+
+/// Every post's counts and the hashes of the reactions already counted.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReactionStore {
+    pub posts: BTreeMap<String, ReactionCounts>,
+    seen: BTreeSet<String>,
+}
+
+impl ReactionStore {
+    /// Read the store, starting empty when the file doesn't exist yet.
+    pub fn load(path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| format!("{}: {}", path.display(), e))?)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+
+    /// The counts for the post with `slug`.
+    pub fn counts(&self, slug: &str) -> ReactionCounts {
+        self.posts.get(slug).cloned().unwrap_or_default()
+    }
+
+    /// Count `request` from the visitor at `ip`, unless they've left the same reaction on the post before. Only
+    /// published posts take reactions.
+    pub fn record(&mut self, salt: &str, ip: &str, request: &ReactionRequest) -> ReactionResponse {
+        let published = posts::find(&request.post)
+            .is_some_and(|post| post.status(posts::today()) == Status::Published);
+        if !published {
+            return ReactionResponse::UnknownPost {
+                post: request.post.clone(),
+            };
+        }
+
+        let counted = self.seen.insert(visitor_hash(salt, ip, request));
+        let counts = self.posts.entry(request.post.clone()).or_default();
+        if counted {
+            counts.add(request.reaction);
+        }
+        ReactionResponse::Counted {
+            counts: counts.clone(),
+            counted,
+        }
+    }
+}
+
+/// Hex SHA-256 of the salt, the address, the post and the reaction, separated so no two combinations run together.
+fn visitor_hash(salt: &str, ip: &str, request: &ReactionRequest) -> String {
+    let mut hasher = Sha256::new();
+    for part in [salt, ip, &request.post, request.reaction.id()] {
+        hasher.update(part.as_bytes());
+        hasher.update([0]);
+    }
+    hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
@@ -0,0 +1,326 @@
+//! Answering contact submissions from the site, without accounts: the notification email links to a reply page of
+//! `site serve` (see [`server`](crate::ssg::server)) whose address carries a token signed for that one message.
+//!
+//! The token is the message's [`id`](messages::id) with an HMAC-SHA256 of it, keyed with the secret in
+//! `[contact.replies] secret_env`, so nothing is stored to check it and nobody without the secret can make one for
+//! another message. The link is as private as the notification email it's in, and stops working `max_age_days` after
+//! the message came in. A reply sent from the page is emailed to the sender through `[contact.smtp]`, quoting their
+//! message, and appended to `[contact.replies] log`; the page shows the thread so far above the form.
+
+use crate::api::ContactRequest;
+use crate::ssg::config::{ContactConfig, RepliesConfig};
+use crate::ssg::email::Email;
+use crate::ssg::messages::{self, StoredMessage};
+use crate::ssg::{html, sanitize, smtp};
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+/// Path the reply pages are served under, with the token after it.
+pub const REPLY_PATH: &str = "/contact/reply/";
+
+/// Bytes of the HMAC kept in a token; 128 bits can't be guessed, and the link stays short.
+const SIGNATURE_BYTES: usize = 16;
+
+/// A reply as a line of the replies log.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reply {
+    /// Id of the message answered
+    pub message_id: String,
+    pub sent_at: DateTime<Utc>,
+    pub text: String,
+}
+
+/// A message with the replies sent to it, oldest first.
+#[derive(Debug, Clone)]
+pub struct Thread {
+    pub message: StoredMessage,
+    pub replies: Vec<Reply>,
+}
+
+/// The secret the tokens are signed with, when replies are on and it's set.
+pub fn secret(config: &RepliesConfig) -> Option<Vec<u8>> {
+    if !config.is_enabled() {
+        return None;
+    }
+    std::env::var(&config.secret_env)
+        .ok()
+        .filter(|secret| !secret.is_empty())
+        .map(String::into_bytes)
+}
+
+/// The token of the message with `message_id`: the id, `.`, and its signature in URL-safe base64.
+pub fn token(secret: &[u8], message_id: &str) -> String {
+    let signature = mac(secret, message_id).finalize().into_bytes();
+    format!(
+        "{}.{}",
+        message_id,
+        base64::engine::general_purpose::URL_SAFE_NO_PAD.encode(&signature[..SIGNATURE_BYTES])
+    )
+}
+
+/// The id of the message `token` was signed for, or `None` when it wasn't signed with `secret`.
+pub fn verify<'a>(secret: &[u8], token: &'a str) -> Option<&'a str> {
+    let (message_id, signature) = token.split_once('.')?;
+    let signature = base64::engine::general_purpose::URL_SAFE_NO_PAD
+        .decode(signature)
+        .ok()?;
+    if signature.len() != SIGNATURE_BYTES {
+        return None;
+    }
+    mac(secret, message_id)
+        .verify_truncated_left(&signature)
+        .ok()?;
+    Some(message_id)
+}
+
+fn mac(secret: &[u8], message_id: &str) -> Hmac<Sha256> {
+    // HMAC takes keys of any length, so this can't fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(b"contact-reply:");
+    mac.update(message_id.as_bytes());
+    mac
+}
+
+/// Link to the reply page of `request`, received at `received_at`, for its notification email. Empty when replies
+/// are off or the secret isn't set, which the templates leave out.
+pub fn url(config: &RepliesConfig, request: &ContactRequest, received_at: DateTime<Utc>) -> String {
+    match secret(config) {
+        Some(secret) => format!(
+            "{}{}/",
+            config.url,
+            token(&secret, &messages::id(received_at, &request.email))
+        ),
+        None => String::new(),
+    }
+}
+
+/// The thread `token` opens at `now`: `None` for a token that isn't signed, a link that has expired, or a message
+/// that's no longer in the submissions file.
+pub fn open(
+    config: &ContactConfig,
+    token: &str,
+    now: DateTime<Utc>,
+) -> Result<Option<Thread>, Box<dyn std::error::Error>> {
+    let Some(secret) = secret(&config.replies) else {
+        return Ok(None);
+    };
+    let Some(message_id) = verify(&secret, token) else {
+        return Ok(None);
+    };
+
+    let max_age = Duration::days(i64::from(config.replies.max_age_days));
+    let Some(message) = messages::load(Path::new(&config.store.submissions))?
+        .into_iter()
+        .find(|message| message.id() == message_id && now - message.received_at <= max_age)
+    else {
+        return Ok(None);
+    };
+    let replies = load(Path::new(&config.replies.log))?
+        .into_iter()
+        .filter(|reply| reply.message_id == message_id)
+        .collect();
+    Ok(Some(Thread { message, replies }))
+}
+
+/// Email `text` to the sender of `message` and log it. The error is for a reply that couldn't be sent; one that was
+/// sent but not logged is reported too, so it isn't sent twice by mistake.
+pub fn send(
+    config: &ContactConfig,
+    message: &StoredMessage,
+    text: &str,
+    sent_at: DateTime<Utc>,
+) -> Result<Reply, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err("the reply is empty".to_string());
+    }
+    if !config.smtp.is_enabled() {
+        return Err("there's no [contact.smtp] server to send the reply through".to_string());
+    }
+    let to = sanitize::address(&message.request.email)
+        .ok_or_else(|| format!("`{}` isn't an address to reply to", message.request.email))?;
+
+    smtp::send(
+        &config.smtp,
+        &[to.to_string()],
+        None,
+        &email(message, text),
+        sent_at,
+    )?;
+    let reply = Reply {
+        message_id: message.id(),
+        sent_at,
+        text: text.to_string(),
+    };
+    append(Path::new(&config.replies.log), &reply)
+        .map_err(|e| format!("the reply was sent, but not logged: {}", e))?;
+    Ok(reply)
+}
+
+/// The reply as an email: `Re:` the subject, with the sender's message quoted under it.
+pub fn email(message: &StoredMessage, text: &str) -> Email {
+    let request = &message.request;
+    let intro = format!(
+        "On {}, {} wrote:",
+        message.received_at.format("%-d %B %Y"),
+        request.name.trim()
+    );
+    let quoted: String = request
+        .message
+        .trim()
+        .lines()
+        .map(|line| format!("> {}\n", line))
+        .collect();
+
+    Email {
+        subject: sanitize::header_text(&format!("Re: {}", request.subject.trim())),
+        text: format!("{}\n\n{}\n{}", text, intro, quoted),
+        html: format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+<body style="font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; color: #1f2937;">
+    <p style="white-space: pre-wrap;">{}</p>
+    <p style="margin: 24px 0 4px; color: #6b7280;">{}</p>
+    <blockquote style="margin: 0; padding-left: 12px; border-left: 3px solid #d1d5db; color: #6b7280; white-space: pre-wrap;">{}</blockquote>
+</body>
+</html>"#,
+            sanitize::html_text(text),
+            sanitize::html_text(&intro),
+            sanitize::html_text(request.message.trim())
+        ),
+    }
+}
+
+/// The reply page of `thread`: the message, the replies so far, and the form for the next one, with `notice` above
+/// it after a reply was sent or failed.
+pub fn page(thread: &Thread, notice: Option<&str>) -> String {
+    let request = &thread.message.request;
+    let replies: String = thread
+        .replies
+        .iter()
+        .map(|reply| {
+            format!(
+                r#"        <article class="reply">
+            <p class="meta">You replied on {}</p>
+            <p class="text">{}</p>
+        </article>
+"#,
+                reply.sent_at.format("%Y-%m-%d %H:%M UTC"),
+                html::escape(&reply.text)
+            )
+        })
+        .collect();
+    let notice = notice
+        .map(|notice| format!("        <p class=\"notice\">{}</p>\n", html::escape(notice)))
+        .unwrap_or_default();
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+    <meta charset="UTF-8">
+    <meta name="viewport" content="width=device-width, initial-scale=1.0">
+    <meta name="robots" content="noindex">
+    <title>Reply: {subject}</title>
+    <style>
+        body {{ max-width: 40rem; margin: 2rem auto; padding: 0 1rem; font-family: -apple-system, BlinkMacSystemFont, 'Segoe UI', Roboto, sans-serif; color: #1f2937; }}
+        article {{ margin: 0 0 1rem; padding: 1rem; border-radius: 8px; background: #f4f4f5; }}
+        article.reply {{ background: #eff6ff; }}
+        .meta {{ margin: 0 0 0.5rem; color: #6b7280; font-size: 0.875rem; }}
+        .text {{ margin: 0; white-space: pre-wrap; }}
+        .notice {{ padding: 0.75rem; border-radius: 8px; background: #fef9c3; }}
+        textarea {{ width: 100%; box-sizing: border-box; font: inherit; }}
+    </style>
+</head>
+<body>
+    <h1>{subject}</h1>
+    <main>
+        <article class="message">
+            <p class="meta">{name} &lt;{email}&gt; wrote on {received}</p>
+            <p class="text">{message}</p>
+        </article>
+{replies}{notice}        <form method="post">
+            <label for="reply">Reply to {name}</label>
+            <textarea id="reply" name="message" rows="8" required></textarea>
+            <button type="submit">Send reply</button>
+        </form>
+    </main>
+</body>
+</html>
+"#,
+        subject = html::escape(request.subject.trim()),
+        name = html::escape(request.name.trim()),
+        email = html::escape(request.email.trim()),
+        received = thread.message.received_at.format("%Y-%m-%d %H:%M UTC"),
+        message = html::escape(request.message.trim()),
+    )
+}
+
+/// The replies in the log, oldest first. A missing file has none.
+pub fn load(path: &Path) -> Result<Vec<Reply>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+fn append(path: &Path, reply: &Reply) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(reply)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_only_verify_with_their_secret_and_message() {
+        let token = token(b"secret", "649c84c1");
+        assert_eq!(verify(b"secret", &token), Some("649c84c1"));
+        assert_eq!(verify(b"another secret", &token), None);
+
+        let (_, signature) = token.split_once('.').unwrap();
+        assert_eq!(verify(b"secret", &format!("8912e83e.{}", signature)), None);
+        assert_eq!(verify(b"secret", "649c84c1"), None);
+        assert_eq!(verify(b"secret", "649c84c1.AAAA"), None);
+    }
+
+    #[test]
+    fn the_reply_email_quotes_the_message() {
+        let message = StoredMessage {
+            received_at: "2026-04-01T10:00:00Z".parse().unwrap(),
+            request: ContactRequest {
+                name: "Ann".to_string(),
+                subject: "Islands".to_string(),
+                message: "Does it work?\nThanks".to_string(),
+                ..ContactRequest::default()
+            },
+            archived: false,
+        };
+        let email = email(&message, "It does.");
+        assert_eq!(email.subject, "Re: Islands");
+        assert_eq!(
+            email.text,
+            "It does.\n\nOn 1 April 2026, Ann wrote:\n> Does it work?\n> Thanks\n"
+        );
+    }
+}
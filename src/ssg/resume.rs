@@ -0,0 +1,152 @@
+//! The about page data as a [JSON Resume](https://jsonresume.org/schema) at [`RESUME_PATH`], so resume tooling
+//! (themes, PDF exporters, job boards) can use it without scraping the page.
+//!
+//! Everything comes from `assets/data/about.json`: the basics, a GitHub profile, the experience as `work` (with
+//! `startDate` and `endDate` when the entry has `start` and `end`), and the skills and interests. The checker's
+//! `--resume` pass validates the written file against the copy of the schema at [`SCHEMA_PATH`].
+
+use crate::data::About;
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use serde::Serialize;
+use std::fs;
+
+/// Site path of the export.
+pub const RESUME_PATH: &str = "/resume.json";
+
+/// The version of the schema the export follows, linked from its `$schema`.
+pub const SCHEMA_URL: &str =
+    "https://raw.githubusercontent.com/jsonresume/resume-schema/v1.0.0/schema.json";
+
+/// The copy of that schema in the repository, used by the build check.
+pub const SCHEMA_PATH: &str = "schemas/resume.schema.json";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Resume {
+    #[serde(rename = "$schema")]
+    pub schema: String,
+    pub basics: Basics,
+    pub work: Vec<Work>,
+    pub skills: Vec<Named>,
+    pub interests: Vec<Named>,
+    pub meta: Meta,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Basics {
+    pub name: String,
+    pub label: String,
+    pub email: String,
+    pub url: String,
+    pub summary: String,
+    pub location: Location,
+    pub profiles: Vec<Profile>,
+}
+
+/// about.json has a single free-form location, which is closest to the schema's `region`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Location {
+    pub region: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Profile {
+    pub network: String,
+    pub username: String,
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Work {
+    pub name: String,
+    pub position: String,
+    pub summary: String,
+    #[serde(rename = "startDate", skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(rename = "endDate", skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+}
+
+/// A skill or an interest: the schema's entries for both start with a name, and about.json has nothing more.
+#[derive(Debug, Clone, Serialize)]
+pub struct Named {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Meta {
+    /// Absolute URL of the export itself
+    pub canonical: String,
+    #[serde(rename = "lastModified")]
+    pub last_modified: String,
+}
+
+impl Resume {
+    /// `about` as a resume, published on the site at `base_url`.
+    pub fn from_about(about: &About, base_url: &str) -> Self {
+        let base_url = base_url.trim_end_matches('/');
+        let strings = |items: &[&str]| {
+            items
+                .iter()
+                .map(|item| Named {
+                    name: item.to_string(),
+                })
+                .collect()
+        };
+
+        Self {
+            schema: SCHEMA_URL.to_string(),
+            basics: Basics {
+                name: about.name.to_string(),
+                label: about.title.to_string(),
+                email: about.contact.email.to_string(),
+                url: about.contact.website.to_string(),
+                summary: about.bio.to_string(),
+                location: Location {
+                    region: about.location.to_string(),
+                },
+                profiles: vec![Profile {
+                    network: "GitHub".to_string(),
+                    username: about.contact.github.to_string(),
+                    url: format!("https://github.com/{}", about.contact.github),
+                }],
+            },
+            work: about
+                .experience
+                .iter()
+                .map(|experience| Work {
+                    name: experience.company.to_string(),
+                    position: experience.position.to_string(),
+                    summary: experience.description.to_string(),
+                    start_date: experience.start.map(str::to_string),
+                    end_date: experience.end.map(str::to_string),
+                })
+                .collect(),
+            skills: strings(about.skills),
+            interests: strings(about.interests),
+            meta: Meta {
+                canonical: format!("{}{}", base_url, RESUME_PATH),
+                last_modified: about.updated.to_string(),
+            },
+        }
+    }
+}
+
+/// Writes [`RESUME_PATH`] from the compiled-in about data.
+pub struct ResumeHook;
+
+impl BuildHook for ResumeHook {
+    fn name(&self) -> &str {
+        "resume"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔨 Generating: {}", RESUME_PATH);
+        let resume = Resume::from_about(&crate::data::ABOUT, &site.config.site.base_url);
+        fs::write(
+            site.output_dir.join(RESUME_PATH.trim_start_matches('/')),
+            serde_json::to_string_pretty(&resume)?,
+        )?;
+        println!("✅ Generated: {}", RESUME_PATH.trim_start_matches('/'));
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+//! Making submitted text safe to put in a contact email.
+//!
+//! Everything in a submission is written by whoever filled in the form, so none of it may shape the message:
+//!
+//! - header values ([`header_text`]) lose control characters and line breaks of any kind, so a name or subject can't
+//!   end its header and start another (`Bcc: ...`)
+//! - the `Reply-To` address ([`mailbox`]) is used only when it's one plain address, with the name quoted in front
+//! - text in an HTML body ([`html_text`]) is escaped, so a message can't add markup or links of its own
+
+use crate::ssg::html;
+use base64::Engine;
+
+/// Longest encoded word allowed by RFC 2047, `=?utf-8?B?` and `?=` included.
+const ENCODED_WORD_LENGTH: usize = 75;
+
+/// `value` as a single line for a header: control characters and line or paragraph separators removed, runs of
+/// whitespace collapsed to one space.
+pub fn header_text(value: &str) -> String {
+    value
+        .split(|c: char| c.is_control() || c.is_whitespace())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// `value` trimmed, when it's a single plain address (`name@example.com`): one `@` with something on both sides, and
+/// nothing that could add another address, a comment or a header (whitespace, control characters, `<>()[],;:"\`).
+pub fn address(value: &str) -> Option<&str> {
+    let value = value.trim();
+    let (local, domain) = value.split_once('@')?;
+    let plain = |part: &str| {
+        !part.is_empty()
+            && !part
+                .chars()
+                .any(|c| c.is_control() || c.is_whitespace() || "<>()[],;:\"\\@".contains(c))
+    };
+    (plain(local) && plain(domain) && domain.contains('.')).then_some(value)
+}
+
+/// The mailbox of whoever wrote in, for `Reply-To`: `"Name" <address>`, or the bare address without a name. `None`
+/// when `email` isn't a single plain address (see [`address`]); the message is better sent without `Reply-To` than
+/// with someone else's.
+pub fn mailbox(name: &str, email: &str) -> Option<String> {
+    let address = address(email)?;
+    let name = header_text(name);
+    if name.is_empty() {
+        return Some(address.to_string());
+    }
+    let name = if name.is_ascii() {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        encoded_words(&name)
+    };
+    Some(format!("{} <{}>", name, address))
+}
+
+/// `text` as RFC 2047 encoded words when it isn't plain ASCII, split so none is longer than the standard allows.
+pub fn encoded_words(text: &str) -> String {
+    if text.is_ascii() {
+        return text.to_string();
+    }
+
+    // Base64 turns every 3 bytes into 4 characters; what's left of a word after `=?utf-8?B?` and `?=`
+    let max_bytes = (ENCODED_WORD_LENGTH - "=?utf-8?B??=".len()) / 4 * 3;
+    let mut words = Vec::new();
+    let mut chunk = String::new();
+    for c in text.chars() {
+        // Characters aren't split across words, so each one decodes on its own
+        if chunk.len() + c.len_utf8() > max_bytes {
+            words.push(encoded_word(&chunk));
+            chunk.clear();
+        }
+        chunk.push(c);
+    }
+    if !chunk.is_empty() {
+        words.push(encoded_word(&chunk));
+    }
+    words.join(" ")
+}
+
+fn encoded_word(text: &str) -> String {
+    format!(
+        "=?utf-8?B?{}?=",
+        base64::engine::general_purpose::STANDARD.encode(text.as_bytes())
+    )
+}
+
+/// `text` escaped for an HTML body, with control characters other than line breaks and tabs removed.
+pub fn html_text(text: &str) -> String {
+    let text: String = text
+        .chars()
+        .filter(|&c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'))
+        .collect();
+    html::escape(&text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ssg::config::SmtpConfig;
+    use crate::ssg::email::Email;
+    use crate::ssg::smtp;
+    use chrono::{TimeZone, Utc};
+
+    /// The text an encoded word stands for.
+    fn decode_word(word: &str) -> String {
+        let encoded = word
+            .strip_prefix("=?utf-8?B?")
+            .and_then(|word| word.strip_suffix("?="))
+            .expect("an encoded word");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .expect("valid base64");
+        String::from_utf8(bytes).expect("whole characters in every word")
+    }
+
+    #[test]
+    fn header_text_removes_line_breaks() {
+        assert_eq!(
+            header_text("Hello\r\nBcc: x@example.com"),
+            "Hello Bcc: x@example.com"
+        );
+        assert_eq!(header_text("one\rtwo\nthree"), "one two three");
+        assert_eq!(
+            header_text("line\u{2028}paragraph\u{2029}end"),
+            "line paragraph end"
+        );
+        assert_eq!(header_text("  tab\tand\u{0}null  "), "tab and null");
+    }
+
+    #[test]
+    fn address_accepts_one_plain_address() {
+        assert_eq!(address(" ada@example.com "), Some("ada@example.com"));
+        assert_eq!(
+            address("ada.lovelace+site@mail.example.org"),
+            Some("ada.lovelace+site@mail.example.org")
+        );
+    }
+
+    #[test]
+    fn address_rejects_anything_more() {
+        for value in [
+            "ada@example.com, eve@example.com",
+            "ada@example.com;eve@example.com",
+            "ada@example.com eve@example.com",
+            "ada@example.com@example.org",
+            "Ada <ada@example.com>",
+            "<ada@example.com>",
+            "\"ada\"@example.com",
+            "ada@example.com (Ada)",
+            "ada@example.com\r\nBcc: eve@example.com",
+            "ada@localhost",
+            "@example.com",
+            "ada@",
+            "",
+        ] {
+            assert_eq!(address(value), None, "{value:?}");
+        }
+    }
+
+    #[test]
+    fn mailbox_quotes_the_name() {
+        assert_eq!(
+            mailbox("Ada Lovelace", "ada@example.com").as_deref(),
+            Some("\"Ada Lovelace\" <ada@example.com>")
+        );
+        assert_eq!(
+            mailbox("Ada \"The\" \\ Countess", "ada@example.com").as_deref(),
+            Some("\"Ada \\\"The\\\" \\\\ Countess\" <ada@example.com>")
+        );
+        assert_eq!(
+            mailbox("  ", "ada@example.com").as_deref(),
+            Some("ada@example.com")
+        );
+        assert_eq!(
+            mailbox("Zoë", "zoe@example.com").as_deref(),
+            Some("=?utf-8?B?Wm/Dqw==?= <zoe@example.com>")
+        );
+    }
+
+    #[test]
+    fn mailbox_rejects_addresses_that_are_not_plain() {
+        assert_eq!(mailbox("Ada", "ada@example.com, eve@example.com"), None);
+        assert_eq!(mailbox("Ada", "Eve <eve@example.com>"), None);
+        assert_eq!(mailbox("Ada", "\"eve\"@example.com"), None);
+        assert_eq!(mailbox("Ada", "ada@example.com>, <eve@example.com"), None);
+    }
+
+    #[test]
+    fn encoded_words_leaves_ascii_alone() {
+        assert_eq!(encoded_words("Hello there"), "Hello there");
+    }
+
+    #[test]
+    fn encoded_words_splits_long_values() {
+        let text = "Zażółć gęślą jaźń — ".repeat(6);
+        let encoded = encoded_words(&text);
+        let words: Vec<_> = encoded.split(' ').collect();
+
+        assert!(words.len() > 1);
+        for word in &words {
+            assert!(word.len() <= ENCODED_WORD_LENGTH, "{word} is too long");
+        }
+        assert_eq!(
+            words
+                .iter()
+                .map(|word| decode_word(word))
+                .collect::<String>(),
+            text
+        );
+    }
+
+    #[test]
+    fn html_text_escapes_markup() {
+        assert_eq!(
+            html_text("<a href=\"https://example.com\">Tom & Jerry's</a>"),
+            "&lt;a href=&quot;https://example.com&quot;&gt;Tom &amp; Jerry&#39;s&lt;/a&gt;"
+        );
+        assert_eq!(html_text("one\ntwo\tthree\u{0}\u{1b}"), "one\ntwo\tthree");
+    }
+
+    #[test]
+    fn message_headers_cannot_be_injected() {
+        let config = SmtpConfig::default();
+        let email = Email {
+            subject: "Hi\r\nBcc: eve@example.com\nX-Injected: subject".to_string(),
+            text: "Body".to_string(),
+            html: "<p>Body</p>".to_string(),
+        };
+        let reply_to = mailbox(
+            "Ada\r\nBcc: eve@example.com\u{2028}X-Injected: name",
+            "ada@example.com",
+        );
+        let sent_at = Utc.with_ymd_and_hms(2025, 6, 1, 12, 0, 0).unwrap();
+        let message = smtp::message(
+            &config,
+            &["owner@example.com".to_string()],
+            reply_to.as_deref(),
+            &email,
+            sent_at,
+        );
+
+        let (headers, _) = message.split_once("\r\n\r\n").expect("headers and body");
+        let names: Vec<_> = headers
+            .split("\r\n")
+            .map(|line| line.split_once(':').expect("a header").0)
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "From",
+                "To",
+                "Reply-To",
+                "Subject",
+                "Date",
+                "MIME-Version",
+                "Content-Type"
+            ]
+        );
+        assert!(!headers
+            .replace("\r\n", "")
+            .contains(['\r', '\n', '\u{2028}']));
+
+        // An address that would add recipients leaves `Reply-To` out rather than passing them on
+        assert_eq!(
+            mailbox("Ada", "ada@example.com\r\nBcc: eve@example.com"),
+            None
+        );
+        let message = smtp::message(
+            &config,
+            &["owner@example.com".to_string()],
+            mailbox("Ada", "ada@example.com, eve@example.com").as_deref(),
+            &email,
+            sent_at,
+        );
+        assert!(!message.contains("Reply-To"));
+    }
+}
@@ -0,0 +1,142 @@
+//! Search-like landing pages for visitors without JavaScript and for search engines.
+//!
+//! The words of every published post are counted, leaving out short and common ones, or only the `[search] keywords`
+//! when the list is set; the `[search] keyword_pages` keywords mentioned in the most posts, and in at least
+//! `min_posts` of them, each get a page at `/search/<keyword>/` listing those posts. A page for a word that's only
+//! mentioned in passing would be thin content search engines rank the whole site down for. Every project tag gets one
+//! at `/search/tags/<tag>/` listing the projects using it, and [`SEARCH_PATH`] links to them all.
+
+use crate::data::Project;
+use crate::excerpt;
+use crate::posts::Post;
+use crate::ssg::config::SearchConfig;
+use crate::typography;
+use std::collections::BTreeMap;
+
+/// Page listing every keyword and tag page.
+pub use crate::site_search::SEARCH_PATH;
+
+/// Words shorter than this are never keywords.
+const MIN_WORD_LENGTH: usize = 4;
+
+/// Common words that say nothing about a post, and filler that comes up in any post (`free`, `great`, `files`).
+/// `tags` is here so no keyword page takes the tag pages' directory.
+#[rustfmt::skip]
+const STOP_WORDS: &[&str] = &[
+    "about", "after", "again", "also", "always", "another", "any", "around", "away", "back", "because", "been",
+    "before", "being", "best", "better", "both", "but", "came", "can", "come", "could", "does", "done", "down", "each",
+    "easy", "else", "enough", "even", "every", "fast", "file", "files", "find", "first", "free", "from", "full", "gets",
+    "give", "goes", "going", "good", "great", "have", "here", "host", "into", "just", "keep", "kind", "know", "last",
+    "less", "let", "like", "little", "long", "look", "lots", "made", "make", "many", "more", "most", "much", "must",
+    "need", "never", "next", "nice", "number", "often", "once", "only", "other", "over", "part", "real", "really",
+    "right", "same", "says", "see", "should", "show", "since", "some", "something", "still", "such", "sure", "take",
+    "tags", "than", "that", "their", "them", "then", "there", "these", "they", "thing", "things", "this", "those",
+    "though", "through", "time", "times", "today", "under", "until", "upon", "used", "uses", "using", "very", "want",
+    "ways", "well", "went", "were", "what", "when", "where", "which", "while", "will", "with", "within", "without",
+    "work", "works", "would", "year", "years", "your",
+];
+
+/// A keyword with the posts that mention it, the most mentions first.
+#[derive(Debug, Clone)]
+pub struct Keyword<'a> {
+    pub term: String,
+    pub posts: Vec<&'a Post>,
+}
+
+impl Keyword<'_> {
+    pub fn path(&self) -> String {
+        keyword_path(&self.term)
+    }
+}
+
+/// The `keyword_pages` keywords mentioned in the most posts, ties broken by total mentions and then alphabetically,
+/// leaving out those in fewer than `min_posts` posts.
+pub fn keywords<'a>(posts: &'a [Post], config: &SearchConfig) -> Vec<Keyword<'a>> {
+    let allowed: Vec<String> = config
+        .keywords
+        .iter()
+        .map(|keyword| keyword.to_lowercase())
+        .collect();
+    // term -> (post index, mentions in that post)
+    let mut mentions: BTreeMap<String, Vec<(usize, usize)>> = BTreeMap::new();
+    for (index, post) in posts.iter().enumerate() {
+        let text = format!("{} {}", post.title, excerpt::plain_text(&post.body_html()));
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        let candidates: Box<dyn Iterator<Item = String>> = if allowed.is_empty() {
+            Box::new(words(&text))
+        } else {
+            // Listed keywords may be shorter than the automatic ones, like `seo`
+            Box::new(
+                text.split(|c: char| !c.is_alphanumeric())
+                    .map(str::to_lowercase)
+                    .filter(|word| allowed.contains(word)),
+            )
+        };
+        for word in candidates {
+            *counts.entry(word).or_default() += 1;
+        }
+        for (word, count) in counts {
+            mentions.entry(word).or_default().push((index, count));
+        }
+    }
+
+    let mut ranked: Vec<(String, Vec<(usize, usize)>)> = mentions
+        .into_iter()
+        .filter(|(_, found)| found.len() >= config.min_posts)
+        .collect();
+    let total = |found: &[(usize, usize)]| found.iter().map(|(_, count)| count).sum::<usize>();
+    ranked.sort_by(|(a_term, a), (b_term, b)| {
+        b.len()
+            .cmp(&a.len())
+            .then(total(b).cmp(&total(a)))
+            .then(a_term.cmp(b_term))
+    });
+
+    ranked
+        .into_iter()
+        .take(config.keyword_pages)
+        .map(|(term, mut found)| {
+            found.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+            Keyword {
+                term,
+                posts: found.into_iter().map(|(index, _)| &posts[index]).collect(),
+            }
+        })
+        .collect()
+}
+
+/// Every project tag with the projects using it, alphabetically.
+pub fn tags(projects: &[Project]) -> Vec<(&'static str, Vec<&Project>)> {
+    let mut tags: BTreeMap<String, (&'static str, Vec<&Project>)> = BTreeMap::new();
+    for project in projects {
+        for tag in project.tags {
+            tags.entry(tag.to_lowercase())
+                .or_insert_with(|| (tag, Vec::new()))
+                .1
+                .push(project);
+        }
+    }
+    tags.into_values().collect()
+}
+
+pub fn keyword_path(term: &str) -> String {
+    format!("{}{}/", SEARCH_PATH, slug(term))
+}
+
+pub fn tag_path(tag: &str) -> String {
+    format!("{}tags/{}/", SEARCH_PATH, slug(tag))
+}
+
+/// URL segment for a term: lowercase letters and digits, with `-` between words.
+pub fn slug(term: &str) -> String {
+    typography::anchor_id(term)
+}
+
+/// The candidate keywords in `text`, lowercased, in order.
+fn words(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() >= MIN_WORD_LENGTH)
+        .filter(|word| !word.chars().all(|c| c.is_ascii_digit()))
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+}
@@ -0,0 +1,550 @@
+//! The backend run by `site serve`, answering what the static files can't. It sits behind the web server that serves
+//! the static output, which proxies these paths to `[server] address`:
+//!
+//! - `POST` [`CONTACT_ENDPOINT`]: a contact submission, as JSON from the WASM island, as `multipart/form-data` when
+//!   it comes with files, or as a plain form post. It's validated again with [`ContactRequest::validate`] and handed
+//!   to [`notify::receive`]; once kept, its sender gets the confirmation email. JSON requests are answered with a
+//!   [`ContactResponse`], plain form posts with a redirect to [`CONTACT_THANKS_PATH`].
+//! - `GET` and `POST` [`REPLY_PATH`]`<token>/`: the page answering a contact submission from the link in its
+//!   notification email, and the reply sent from it (see [`replies`])
+//! - `POST` [`HIT_ENDPOINT`]: a page view sent by the analytics beacon, kept with the visitor's country, and `GET`
+//!   [`STATS_PATH`]: the dashboard of them behind HTTP Basic auth (see [`analytics`])
+//! - `GET` [`PREVIEW_PATH`]`<token>/<slug>/`: a draft, from a link made by `site preview` (see [`preview`]); a post
+//!   that's out by now redirects to its page
+//! - `GET` [`HEALTHZ_PATH`] and [`READYZ_PATH`]: the liveness and readiness [`health`] reports, with 503 when a
+//!   dependency of the contact backend is down, for the load balancer or container health check
+//!
+//! A request from one of `[server] trusted_proxies` is taken to come from the client its `X-Forwarded-For` names (see
+//! [`client_ip`]), so the blocklist's `ip` rules and the country lookup see the sender rather than the proxy. Delivery blocks (SMTP and the
+//! webhook go through `curl`), so it runs on tokio's blocking threads.
+
+use crate::api::{Attachment, ContactRequest, ContactResponse, ValidationProblem};
+use crate::posts;
+use crate::ssg::analytics::{self, Geoip, Hit, HIT_ENDPOINT, STATS_PATH};
+use crate::ssg::blocklist;
+use crate::ssg::config::SiteConfig;
+use crate::ssg::health::{self, Report, HEALTHZ_PATH, READYZ_PATH};
+use crate::ssg::notify::{self, Received};
+use crate::ssg::preview::{self, Preview, PREVIEW_PATH};
+use crate::ssg::replies::{self, REPLY_PATH};
+use crate::{CONTACT_ENDPOINT, CONTACT_THANKS_PATH};
+use axum::extract::{
+    ConnectInfo, DefaultBodyLimit, FromRef, FromRequest, Multipart, Path, Query, Request, State,
+};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::{get, post};
+use axum::{Form, Json, Router};
+use chrono::Utc;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+
+/// Seconds a sender is asked to wait before trying again when a submission couldn't be kept.
+const RETRY_AFTER_SECONDS: u32 = 30;
+
+/// Room for the fields of a multipart submission on top of its files.
+const FORM_OVERHEAD_BYTES: usize = 1024 * 1024;
+
+/// Days the dashboard shows unless it's asked for others, and the most it shows.
+const STATS_DAYS: u32 = 30;
+const MAX_STATS_DAYS: u32 = 366;
+
+/// What the handlers share: the configuration, and the GeoIP database, opened once.
+#[derive(Clone)]
+struct AppState {
+    config: Arc<SiteConfig>,
+    geoip: Arc<Geoip>,
+}
+
+impl FromRef<AppState> for Arc<SiteConfig> {
+    fn from_ref(state: &AppState) -> Self {
+        state.config.clone()
+    }
+}
+
+/// The routes of `site serve`, answering with `config`. Fails when `[analytics] geoip_database` can't be opened.
+pub fn router(config: SiteConfig) -> Result<Router, String> {
+    let geoip = Geoip::open(&config.analytics)?;
+    // Every file may be as large as allowed, and a form can have more than one
+    let body_limit = config
+        .contact
+        .attachments
+        .max_bytes()
+        .saturating_mul(2)
+        .saturating_add(FORM_OVERHEAD_BYTES as u64);
+    Ok(Router::new()
+        .route(CONTACT_ENDPOINT, post(contact))
+        .route(
+            &format!("{}{{token}}", REPLY_PATH),
+            get(reply_page).post(send_reply),
+        )
+        .route(
+            &format!("{}{{token}}/", REPLY_PATH),
+            get(reply_page).post(send_reply),
+        )
+        .route(HIT_ENDPOINT, post(hit))
+        .route(STATS_PATH, get(stats))
+        .route(
+            &format!("{}{{token}}/{{slug}}", PREVIEW_PATH),
+            get(draft_preview),
+        )
+        .route(
+            &format!("{}{{token}}/{{slug}}/", PREVIEW_PATH),
+            get(draft_preview),
+        )
+        .route(HEALTHZ_PATH, get(|| async { report(health::liveness()) }))
+        .route(READYZ_PATH, get(ready))
+        .layer(DefaultBodyLimit::max(
+            usize::try_from(body_limit).unwrap_or(usize::MAX),
+        ))
+        .with_state(AppState {
+            config: Arc::new(config),
+            geoip: Arc::new(geoip),
+        }))
+}
+
+/// Listen on `address` until the process is stopped.
+pub async fn serve(
+    config: SiteConfig,
+    address: SocketAddr,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let router = router(config)?;
+    let listener = tokio::net::TcpListener::bind(address).await?;
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
+    Ok(())
+}
+
+/// The reply form's field.
+#[derive(Deserialize)]
+struct ReplyForm {
+    message: String,
+}
+
+async fn reply_page(State(config): State<Arc<SiteConfig>>, Path(token): Path<String>) -> Response {
+    reply(config, token, None).await
+}
+
+async fn send_reply(
+    State(config): State<Arc<SiteConfig>>,
+    Path(token): Path<String>,
+    Form(form): Form<ReplyForm>,
+) -> Response {
+    reply(config, token, Some(form.message)).await
+}
+
+/// The reply page of `token`, after sending `text` to the sender when there is one.
+async fn reply(config: Arc<SiteConfig>, token: String, text: Option<String>) -> Response {
+    let page = tokio::task::spawn_blocking(move || {
+        let contact = &config.contact;
+        let Some(thread) = replies::open(contact, &token, Utc::now()).map_err(|e| e.to_string())?
+        else {
+            return Ok(None);
+        };
+        let Some(text) = text else {
+            return Ok(Some((StatusCode::OK, replies::page(&thread, None))));
+        };
+
+        let (status, notice) = match replies::send(contact, &thread.message, &text, Utc::now()) {
+            Ok(_) => (
+                StatusCode::OK,
+                format!(
+                    "Your reply was sent to {}.",
+                    thread.message.request.email.trim()
+                ),
+            ),
+            Err(e) => (
+                StatusCode::BAD_GATEWAY,
+                format!("Your reply couldn't be sent: {}", e),
+            ),
+        };
+        // Opened again, so the page shows the reply just sent
+        let thread = replies::open(contact, &token, Utc::now())
+            .map_err(|e| e.to_string())?
+            .unwrap_or(thread);
+        Ok::<_, String>(Some((status, replies::page(&thread, Some(&notice)))))
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+
+    match page {
+        Ok(Some((status, page))) => (status, Html(page)).into_response(),
+        Ok(None) => (
+            StatusCode::NOT_FOUND,
+            "This reply link isn't valid, or it has expired.",
+        )
+            .into_response(),
+        Err(e) => {
+            eprintln!("❌ Reply page not shown: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "The reply page couldn't be shown.",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Keep a page view. The beacon doesn't read the answer, so anything short of analytics being off gets a 204.
+async fn hit(
+    State(state): State<AppState>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    headers: HeaderMap,
+    body: String,
+) -> StatusCode {
+    let config = state.config.clone();
+    if !config.analytics.enabled {
+        return StatusCode::NOT_FOUND;
+    }
+    let user_agent = headers
+        .get(header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default();
+    if analytics::is_bot(user_agent) {
+        return StatusCode::NO_CONTENT;
+    }
+    let site_host = analytics::host(&config.site.base_url).unwrap_or_default();
+    // sendBeacon posts the JSON as text/plain, so it's read from the body as it is
+    let Some(hit) = serde_json::from_str::<Hit>(&body)
+        .ok()
+        .and_then(|hit| hit.scrubbed(site_host))
+    else {
+        return StatusCode::NO_CONTENT;
+    };
+
+    let header_country = headers
+        .get(config.analytics.country_header.as_str())
+        .filter(|_| !config.analytics.country_header.is_empty())
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_ascii_uppercase)
+        .filter(|code| code.len() == 2 && code.bytes().all(|byte| byte.is_ascii_uppercase()));
+    let country = header_country.unwrap_or_else(|| {
+        state.geoip.country(client_ip(
+            peer.ip(),
+            &headers,
+            &config.server.trusted_proxies,
+        ))
+    });
+
+    let recorded = tokio::task::spawn_blocking(move || {
+        analytics::Store::open(std::path::Path::new(&config.analytics.database))
+            .and_then(|store| store.record(&hit, &country, Utc::now()))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    if let Err(e) = recorded {
+        eprintln!("❌ Page view not kept: {}", e);
+    }
+    StatusCode::NO_CONTENT
+}
+
+/// The dashboard's `?days=`.
+#[derive(Deserialize)]
+struct StatsQuery {
+    days: Option<u32>,
+}
+
+async fn stats(
+    State(config): State<Arc<SiteConfig>>,
+    Query(query): Query<StatsQuery>,
+    headers: HeaderMap,
+) -> Response {
+    let Some(password) = analytics::password(&config.analytics) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+    let authorization = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if !analytics::authorized(&password, authorization) {
+        return (
+            StatusCode::UNAUTHORIZED,
+            [(header::WWW_AUTHENTICATE, r#"Basic realm="stats""#)],
+        )
+            .into_response();
+    }
+
+    let days = query.days.unwrap_or(STATS_DAYS).clamp(1, MAX_STATS_DAYS);
+    let page = tokio::task::spawn_blocking(move || {
+        analytics::Store::open(std::path::Path::new(&config.analytics.database))
+            .and_then(|store| store.stats(Utc::now().date_naive(), days))
+            .map(|stats| analytics::dashboard(&stats, days))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .unwrap_or_else(|e| Err(e.to_string()));
+    match page {
+        Ok(page) => Html(page).into_response(),
+        Err(e) => {
+            eprintln!("❌ Stats not shown: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "The stats couldn't be read.",
+            )
+                .into_response()
+        }
+    }
+}
+
+/// The draft `slug`, when `token` was signed for it. Anything else is a 404, so a link that doesn't work says nothing
+/// about which drafts there are.
+async fn draft_preview(
+    State(config): State<Arc<SiteConfig>>,
+    Path((token, slug)): Path<(String, String)>,
+) -> Response {
+    let post = preview::secret(&config.preview)
+        .filter(|secret| preview::verify(secret, &token, &slug))
+        .and_then(|_| posts::find(&slug));
+    match post.map(|post| preview::render(post, posts::today())) {
+        Some(Preview::Draft(page)) => {
+            ([(header::CACHE_CONTROL, "no-store")], Html(page)).into_response()
+        }
+        Some(Preview::Published(path)) => Redirect::to(&path).into_response(),
+        None => (StatusCode::NOT_FOUND, "There's no draft at this link.").into_response(),
+    }
+}
+
+async fn ready(State(config): State<Arc<SiteConfig>>) -> Response {
+    // The checks connect to the SMTP server and the webhook and wait for them
+    match tokio::task::spawn_blocking(move || health::readiness(&config.contact)).await {
+        Ok(readiness) => report(readiness),
+        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+fn report(report: Report) -> Response {
+    let status =
+        StatusCode::from_u16(report.http_status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    (status, Json(report)).into_response()
+}
+
+async fn contact(
+    State(config): State<Arc<SiteConfig>>,
+    ConnectInfo(peer): ConnectInfo<SocketAddr>,
+    request: Request,
+) -> Response {
+    let json = wants_json(request.headers());
+    let client_ip = client_ip(peer.ip(), request.headers(), &config.server.trusted_proxies);
+    let (submission, attachments) = match read_submission(request).await {
+        Ok(read) => read,
+        Err(problem) => return (StatusCode::BAD_REQUEST, problem).into_response(),
+    };
+
+    let problems = submission.validate();
+    if !problems.is_empty() {
+        return answer(json, ContactResponse::Invalid { problems });
+    }
+
+    let received = tokio::task::spawn_blocking(move || {
+        let received = notify::receive(
+            &config.contact,
+            &submission,
+            &attachments,
+            Some(client_ip),
+            Utc::now(),
+        )
+        .map_err(|e| e.to_string())?;
+        if let Received::Delivered(record) = &received {
+            if record.is_kept() {
+                if let Err(e) = notify::confirm(&config.contact, &submission) {
+                    eprintln!("⚠️ Confirmation to {} not sent: {}", submission.email, e);
+                }
+            }
+        }
+        Ok::<_, String>(received)
+    })
+    .await
+    .unwrap_or_else(|e| Err(format!("the delivery panicked: {}", e)));
+
+    let response = match received {
+        Ok(Received::Delivered(record)) if !record.is_kept() => {
+            eprintln!(
+                "❌ Submission from {} reached no channel",
+                record.request.email
+            );
+            unavailable()
+        }
+        Ok(Received::Delivered(_) | Received::Blocked(_)) => ContactResponse::Accepted {
+            redirect: CONTACT_THANKS_PATH.to_string(),
+        },
+        Ok(Received::Rejected(problems)) => ContactResponse::Invalid { problems },
+        Err(e) => {
+            eprintln!("❌ Submission not received: {}", e);
+            unavailable()
+        }
+    };
+    answer(json, response)
+}
+
+fn unavailable() -> ContactResponse {
+    ContactResponse::Unavailable {
+        retry_after_seconds: Some(RETRY_AFTER_SECONDS),
+    }
+}
+
+/// The submission in the body of `request`, whatever way it was posted, with the files sent along.
+async fn read_submission(request: Request) -> Result<(ContactRequest, Vec<Attachment>), String> {
+    let content_type = request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    if content_type.starts_with("application/json") {
+        let Json(submission) = Json::<ContactRequest>::from_request(request, &())
+            .await
+            .map_err(|e| e.body_text())?;
+        Ok((submission, Vec::new()))
+    } else if content_type.starts_with("multipart/form-data") {
+        let multipart = Multipart::from_request(request, &())
+            .await
+            .map_err(|e| e.body_text())?;
+        read_multipart(multipart).await
+    } else {
+        let Form(submission) = Form::<ContactRequest>::from_request(request, &())
+            .await
+            .map_err(|e| e.body_text())?;
+        Ok((submission, Vec::new()))
+    }
+}
+
+/// Fields of a `multipart/form-data` submission go into the [`ContactRequest`], and files into [`Attachment`]s, with
+/// the file's name as the value of its field.
+async fn read_multipart(
+    mut multipart: Multipart,
+) -> Result<(ContactRequest, Vec<Attachment>), String> {
+    let mut fields = BTreeMap::new();
+    let mut attachments = Vec::new();
+    while let Some(part) = multipart.next_field().await.map_err(|e| e.body_text())? {
+        let name = part.name().unwrap_or_default().to_string();
+        match part.file_name().map(str::to_string) {
+            Some(filename) => {
+                let bytes = part.bytes().await.map_err(|e| e.body_text())?;
+                // A file input left empty is still sent, without a name or content
+                if filename.is_empty() && bytes.is_empty() {
+                    continue;
+                }
+                fields.insert(name.clone(), filename.clone());
+                attachments.push(Attachment {
+                    field: name,
+                    filename,
+                    bytes: bytes.to_vec(),
+                });
+            }
+            None => {
+                let value = part.text().await.map_err(|e| e.body_text())?;
+                fields.insert(name, value);
+            }
+        }
+    }
+
+    let submission = serde_json::to_value(fields)
+        .and_then(serde_json::from_value)
+        .map_err(|e| format!("Failed to deserialize form: {}", e))?;
+    Ok((submission, attachments))
+}
+
+/// The address a request from `peer` was sent from. `peer` is the client unless it's one of `trusted_proxies`; then
+/// `X-Forwarded-For` is read from the right, past the trusted proxies that added to it, to the address the nearest
+/// of them saw. Anything before that could have been written by the client itself.
+pub fn client_ip(peer: IpAddr, headers: &HeaderMap, trusted_proxies: &[String]) -> IpAddr {
+    let trusted = |ip: IpAddr| {
+        trusted_proxies
+            .iter()
+            .any(|range| blocklist::in_range(range, ip))
+    };
+    if !trusted(peer) {
+        return peer;
+    }
+
+    let forwarded: Vec<IpAddr> = headers
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map_while(|address| address.trim().parse().ok())
+        .collect();
+    forwarded
+        .into_iter()
+        .rev()
+        .find(|&ip| !trusted(ip))
+        .unwrap_or(peer)
+}
+
+/// Whether the sender reads a [`ContactResponse`] rather than following a redirect: the island asks for JSON.
+fn wants_json(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|accept| accept.contains("application/json"))
+}
+
+fn answer(json: bool, response: ContactResponse) -> Response {
+    let status = match &response {
+        ContactResponse::Accepted { .. } => StatusCode::OK,
+        ContactResponse::Invalid { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+        ContactResponse::Unavailable { .. } => StatusCode::SERVICE_UNAVAILABLE,
+    };
+    let retry_after = match &response {
+        ContactResponse::Unavailable {
+            retry_after_seconds: Some(seconds),
+        } => Some([(header::RETRY_AFTER, seconds.to_string())]),
+        _ => None,
+    };
+
+    if json {
+        return (status, retry_after, Json(response)).into_response();
+    }
+    match response {
+        ContactResponse::Accepted { redirect } => Redirect::to(&redirect).into_response(),
+        ContactResponse::Invalid { problems } => (status, problem_list(&problems)).into_response(),
+        ContactResponse::Unavailable { .. } => (
+            status,
+            retry_after,
+            "Your message couldn't be sent right now. Please try again in a moment.",
+        )
+            .into_response(),
+    }
+}
+
+fn problem_list(problems: &[ValidationProblem]) -> String {
+    let mut text = "Your message couldn't be sent:\n".to_string();
+    for problem in problems {
+        text.push_str(&format!("- {}\n", problem));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    fn forwarded_for(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn client_ip_is_read_from_trusted_proxies_only() {
+        let proxies = ["127.0.0.1".to_string(), "10.0.0.0/8".to_string()];
+        let client: IpAddr = "203.0.113.7".parse().unwrap();
+        let localhost: IpAddr = "127.0.0.1".parse().unwrap();
+
+        let headers = forwarded_for("198.51.100.1, 203.0.113.7, 10.1.2.3");
+        assert_eq!(client_ip(localhost, &headers, &proxies), client);
+        // A client talking to the server directly can't claim another address
+        assert_eq!(
+            client_ip(client, &forwarded_for("192.0.2.1"), &proxies),
+            client
+        );
+        assert_eq!(client_ip(localhost, &HeaderMap::new(), &proxies), localhost);
+    }
+}
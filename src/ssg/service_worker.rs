@@ -0,0 +1,142 @@
+//! The service worker (`/sw.js`) that keeps the site readable offline.
+//!
+//! Pages are fetched from the network first and the copy kept in the cache, so anything visited before can be read
+//! again without a connection; fingerprinted files under `/assets/` never change, so they come from the cache once
+//! they're in it. A page that was never visited gets the offline page ([`OFFLINE_PATH`]), which is cached up front
+//! with its stylesheets when the worker installs. Requests to other origins and anything but `GET` (the contact form's
+//! POST) are left to the browser.
+//!
+//! The cache is named after a hash of the generated pages, so every deploy that changes a page starts a fresh cache
+//! and the worker drops the old one.
+
+use crate::ssg::hooks::{BuildHook, RenderedPage, SiteOutput};
+use crate::ssg::html;
+use sha2::{Digest, Sha256};
+use std::fs;
+
+/// Route of the page shown for pages that aren't cached while offline.
+pub const OFFLINE_PATH: &str = "/offline/";
+
+/// Where the worker is written, at the root so its scope covers the whole site.
+pub const SERVICE_WORKER_PATH: &str = "/sw.js";
+
+/// Writes [`SERVICE_WORKER_PATH`] once every page is known. The offline page must be one of them.
+pub struct ServiceWorkerHook;
+
+impl BuildHook for ServiceWorkerHook {
+    fn name(&self) -> &str {
+        "service worker"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔨 Generating: {}", SERVICE_WORKER_PATH);
+
+        let offline = site
+            .pages
+            .iter()
+            .find(|page| page.path == OFFLINE_PATH)
+            .ok_or("the offline page wasn't generated")?;
+        let offline_url = site.config.build.output_style.page_url(OFFLINE_PATH);
+
+        fs::write(
+            site.output_dir
+                .join(SERVICE_WORKER_PATH.trim_start_matches('/')),
+            render_service_worker(&cache_name(site.pages), &offline_url, &stylesheets(offline)),
+        )?;
+        println!(
+            "✅ Generated: {}",
+            SERVICE_WORKER_PATH.trim_start_matches('/')
+        );
+        Ok(())
+    }
+}
+
+/// `site-<hash of every page>`.
+fn cache_name(pages: &[RenderedPage]) -> String {
+    let mut hasher = Sha256::new();
+    for page in pages {
+        hasher.update(page.path.as_bytes());
+        hasher.update(page.html.as_bytes());
+    }
+    let hash: String = hasher.finalize()[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("site-{}", hash)
+}
+
+/// Site-relative stylesheets the page links to, so the offline page is styled without a connection.
+fn stylesheets(page: &RenderedPage) -> Vec<String> {
+    html::tags(&page.html, "link")
+        .into_iter()
+        .filter(|tag| {
+            html::attr(tag, "rel").is_some_and(|rel| rel.eq_ignore_ascii_case("stylesheet"))
+        })
+        .filter_map(|tag| html::attr(tag, "href"))
+        .filter(|href| href.starts_with('/') && !href.starts_with("//"))
+        .map(str::to_string)
+        .collect()
+}
+
+/// The worker script: `precache` (with the offline page first) is cached on install into `cache`.
+pub fn render_service_worker(cache: &str, offline_url: &str, precache: &[String]) -> String {
+    let precache = std::iter::once(offline_url)
+        .chain(precache.iter().map(String::as_str))
+        .map(|url| serde_json::to_string(url).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        r#"// Generated by the static site generator (src/ssg/service_worker.rs)
+const CACHE = {cache};
+const OFFLINE_URL = {offline_url};
+const PRECACHE = [{precache}];
+
+self.addEventListener('install', event => {{
+    event.waitUntil(caches.open(CACHE).then(cache => cache.addAll(PRECACHE)).then(() => self.skipWaiting()));
+}});
+
+self.addEventListener('activate', event => {{
+    event.waitUntil(
+        caches.keys()
+            .then(names => Promise.all(names.filter(name => name !== CACHE).map(name => caches.delete(name))))
+            .then(() => self.clients.claim())
+    );
+}});
+
+async function networkFirst(request) {{
+    const cache = await caches.open(CACHE);
+    try {{
+        const response = await fetch(request);
+        if (response.ok) cache.put(request, response.clone());
+        return response;
+    }} catch (error) {{
+        const cached = await cache.match(request);
+        if (cached) return cached;
+        if (request.mode === 'navigate') return cache.match(OFFLINE_URL);
+        throw error;
+    }}
+}}
+
+async function cacheFirst(request) {{
+    const cache = await caches.open(CACHE);
+    const cached = await cache.match(request);
+    if (cached) return cached;
+    const response = await fetch(request);
+    if (response.ok) cache.put(request, response.clone());
+    return response;
+}}
+
+self.addEventListener('fetch', event => {{
+    const request = event.request;
+    const url = new URL(request.url);
+    if (request.method !== 'GET' || url.origin !== location.origin) return;
+    // Fingerprinted assets never change under the same URL
+    const fingerprinted = url.pathname.startsWith('/assets/') && /-dxh[0-9a-f]{{8,}}\./.test(url.pathname);
+    event.respondWith(fingerprinted ? cacheFirst(request) : networkFirst(request));
+}});
+"#,
+        cache = serde_json::to_string(cache).unwrap_or_default(),
+        offline_url = serde_json::to_string(offline_url).unwrap_or_default(),
+    )
+}
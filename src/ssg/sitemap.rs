@@ -0,0 +1,102 @@
+//! XML sitemap listing every generated page under its canonical URL, with the priority and change frequency hints
+//! from `[sitemap]` in `site.toml`.
+
+use crate::ssg::config::SiteConfig;
+use crate::ssg::feed::escape_xml;
+use crate::ssg::hooks::{BuildHook, SiteOutput};
+use crate::ssg::output::syndicated_from;
+use std::fs;
+
+/// Writes `sitemap.xml` for every page the build wrote, except those marked `noindex` and, with `[sitemap]
+/// exclude_syndicated`, posts syndicated from elsewhere.
+#[derive(Default)]
+pub struct SitemapHook {
+    extra_paths: Vec<String>,
+}
+
+impl SitemapHook {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also list the page at route `path`, for pages another step writes after this build.
+    pub fn with_path(mut self, path: &str) -> Self {
+        self.extra_paths.push(path.to_string());
+        self
+    }
+}
+
+impl BuildHook for SitemapHook {
+    fn name(&self) -> &str {
+        "sitemap"
+    }
+
+    fn after_build(&self, site: &SiteOutput) -> Result<(), Box<dyn std::error::Error>> {
+        println!("🔨 Generating: /sitemap.xml");
+
+        let mut paths: Vec<String> = site
+            .pages
+            .iter()
+            .filter(|page| !page.is_noindex())
+            .filter(|page| {
+                !(site.config.sitemap.exclude_syndicated && syndicated_from(&page.path).is_some())
+            })
+            .map(|page| page.path.clone())
+            .collect();
+        paths.extend(self.extra_paths.iter().cloned());
+
+        fs::write(
+            site.output_dir.join("sitemap.xml"),
+            render_sitemap(site.config, &paths),
+        )?;
+        println!("✅ Generated: sitemap.xml");
+        Ok(())
+    }
+}
+
+/// Render a sitemap for the pages at route `paths`, using the URL form of the configured output style.
+pub fn render_sitemap(config: &SiteConfig, paths: &[String]) -> String {
+    let base_url = config.site.base_url.trim_end_matches('/');
+    let style = config.build.output_style;
+
+    let urls = paths
+        .iter()
+        .map(|path| {
+            let mut url = format!(
+                "  <url>\n    <loc>{}</loc>\n",
+                escape_xml(&format!("{}{}", base_url, style.page_url(path)))
+            );
+            let (priority, changefreq) = config.sitemap.hints(path);
+            if let Some(changefreq) = changefreq {
+                url.push_str(&format!(
+                    "    <changefreq>{}</changefreq>\n",
+                    changefreq.as_str()
+                ));
+            }
+            if let Some(priority) = priority {
+                url.push_str(&format!(
+                    "    <priority>{}</priority>\n",
+                    format_priority(priority)
+                ));
+            }
+            url.push_str("  </url>");
+            url
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9">
+{}
+</urlset>
+"#,
+        urls
+    )
+}
+
+/// `0.8`, `1.0`, `0.75`: at least one decimal, at most two.
+fn format_priority(priority: f32) -> String {
+    let text = format!("{:.2}", priority);
+    text.strip_suffix('0').map(str::to_string).unwrap_or(text)
+}
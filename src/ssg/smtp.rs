@@ -0,0 +1,192 @@
+//! Contact emails sent through the server in `[contact.smtp]`.
+//!
+//! An [`Email`] goes out as `multipart/alternative` with both variants base64-encoded, so any text survives the trip.
+//! It comes from the configured `from` address with `Reply-To` set to whoever wrote in, and every header value goes
+//! through [`sanitize`] first, so a submission can't add headers of its own. Mail is handed to the server with `curl`,
+//! like the webhook's requests. The password goes to `curl` in a config file only the site's user can read, never on
+//! its command line, which any user on the machine can see in `ps`.
+
+use crate::ssg::config::SmtpConfig;
+use crate::ssg::email::Email;
+use crate::ssg::sanitize;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+/// Longest the server may take to accept a message.
+const TIMEOUT_SECS: u64 = 30;
+
+/// Length of the lines of base64 in the message body.
+const LINE_LENGTH: usize = 76;
+
+/// Send `email` to `recipients`, with replies going to `reply_to` (a mailbox from [`sanitize::mailbox`]) when there is
+/// one.
+pub fn send(
+    config: &SmtpConfig,
+    recipients: &[String],
+    reply_to: Option<&str>,
+    email: &Email,
+    sent_at: DateTime<Utc>,
+) -> Result<(), String> {
+    if recipients.is_empty() {
+        return Err("the route has no recipients".to_string());
+    }
+    let password = if config.password_env.is_empty() {
+        None
+    } else {
+        Some(
+            std::env::var(&config.password_env)
+                .map_err(|_| format!("the SMTP password ${} isn't set", config.password_env))?,
+        )
+    };
+
+    let mut command = Command::new("curl");
+    command
+        .args(["--silent", "--show-error", "--ssl-reqd"])
+        .args(["--max-time", &TIMEOUT_SECS.to_string()])
+        .args(["--url", &config.url])
+        .args(["--mail-from", &config.from])
+        .args(["--upload-file", "-"]);
+    for recipient in recipients {
+        command.args(["--mail-rcpt", recipient]);
+    }
+    // Removed when it goes out of scope, once curl is done with it
+    let credentials = match password {
+        Some(password) => Some(CredentialsFile::write(&config.username, &password)?),
+        None => None,
+    };
+    if let Some(credentials) = &credentials {
+        command.arg("--config").arg(&credentials.path);
+    }
+
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("couldn't run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(message(config, recipients, reply_to, email, sent_at).as_bytes())
+            .map_err(|e| format!("couldn't send the message to curl: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+/// A `curl` config file setting `user`, readable only by its owner, deleted when dropped.
+struct CredentialsFile {
+    path: PathBuf,
+}
+
+impl CredentialsFile {
+    fn write(username: &str, password: &str) -> Result<Self, String> {
+        let path = std::env::temp_dir().join(format!(
+            "site-smtp-{}-{}.curlrc",
+            std::process::id(),
+            Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        ));
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let error = |e: std::io::Error| format!("{}: {}", path.display(), e);
+        let mut file = options.open(&path).map_err(error)?;
+        // Constructed before writing, so the file is removed even if writing fails
+        let credentials = CredentialsFile { path };
+        writeln!(
+            file,
+            "user = \"{}\"",
+            curl_config_escape(&format!("{}:{}", username, password))
+        )
+        .map_err(|e| format!("{}: {}", credentials.path.display(), e))?;
+        Ok(credentials)
+    }
+}
+
+impl Drop for CredentialsFile {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+/// `value` for a double-quoted parameter in a `curl` config file.
+fn curl_config_escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The message as handed to the server, headers and body.
+pub fn message(
+    config: &SmtpConfig,
+    recipients: &[String],
+    reply_to: Option<&str>,
+    email: &Email,
+    sent_at: DateTime<Utc>,
+) -> String {
+    // Derived from the contents, so the parts can't contain it
+    let boundary: String = Sha256::digest(format!("{}{}", email.text, email.html).as_bytes())[..12]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    let boundary = format!("part-{}", boundary);
+
+    let mut message = String::new();
+    let mut header = |name: &str, value: &str| {
+        message.push_str(&format!("{}: {}\r\n", name, sanitize::header_text(value)));
+    };
+    header("From", &config.from);
+    header("To", &recipients.join(", "));
+    if let Some(reply_to) = reply_to {
+        header("Reply-To", reply_to);
+    }
+    header(
+        "Subject",
+        &sanitize::encoded_words(&sanitize::header_text(&email.subject)),
+    );
+    header("Date", &sent_at.to_rfc2822());
+    header("MIME-Version", "1.0");
+    header(
+        "Content-Type",
+        &format!("multipart/alternative; boundary=\"{}\"", boundary),
+    );
+    message.push_str("\r\n");
+
+    for (content_type, body) in [("text/plain", &email.text), ("text/html", &email.html)] {
+        message.push_str(&format!("--{}\r\n", boundary));
+        message.push_str(&format!(
+            "Content-Type: {}; charset=utf-8\r\nContent-Transfer-Encoding: base64\r\n\r\n",
+            content_type
+        ));
+        let encoded = base64::engine::general_purpose::STANDARD.encode(body.as_bytes());
+        for line in encoded.as_bytes().chunks(LINE_LENGTH) {
+            message.push_str(&String::from_utf8_lossy(line));
+            message.push_str("\r\n");
+        }
+    }
+    message.push_str(&format!("--{}--\r\n", boundary));
+
+    message
+}
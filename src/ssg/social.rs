@@ -0,0 +1,99 @@
+//! Link previews on social platforms.
+//!
+//! Each platform shows a shared link its own way: Facebook, LinkedIn and Slack read the Open Graph tags and have room
+//! for a few lines of `og:description`, X cuts `twitter:description` short on its cards, and Mastodon credits the
+//! account named in `fediverse:creator`. [`SocialMeta::for_page`] derives each platform's text from the page's title and
+//! description (the route's [`PageMeta`](crate::page_meta::PageMeta)), or from a post's summary or body for the longer
+//! one, and `[social.pages]` in site.toml overrides any of them for a single page.
+//! [`prepare_page`](crate::ssg::output::prepare_page) adds the tags to every generated page.
+
+use crate::excerpt;
+use crate::posts::POSTS;
+use crate::ssg::config::SiteConfig;
+use crate::ssg::html;
+use crate::ssg::meta;
+
+/// The social preview tags of one page.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SocialMeta {
+    pub title: String,
+    /// `article` for blog posts, `website` for everything else
+    pub og_type: &'static str,
+    pub og_description: Option<String>,
+    pub twitter_description: Option<String>,
+    /// Mastodon account credited for the page, as `@name@instance`
+    pub fediverse_creator: Option<String>,
+}
+
+impl SocialMeta {
+    /// The tags for the page at route `path`, taking the title and description from the head of `page`.
+    pub fn for_page(config: &SiteConfig, path: &str, page: &str) -> Self {
+        let social = &config.social;
+        let overrides = social.pages.get(path);
+        let post = POSTS.iter().find(|post| post.path() == path);
+        let description = meta::description(page);
+
+        let og_description = overrides
+            .and_then(|overrides| overrides.og_description.clone())
+            .or_else(|| post.map(|post| post.description(social.og_description_length)))
+            .or_else(|| {
+                description
+                    .as_deref()
+                    .map(|text| excerpt::description(text, social.og_description_length))
+            });
+        let twitter_description = overrides
+            .and_then(|overrides| overrides.twitter_description.clone())
+            .or_else(|| {
+                description
+                    .as_deref()
+                    .map(|text| excerpt::description(text, social.twitter_description_length))
+            });
+        let fediverse_creator = overrides
+            .and_then(|overrides| overrides.fediverse_creator.clone())
+            .unwrap_or_else(|| social.fediverse_creator.clone());
+
+        Self {
+            title: meta::title(page).unwrap_or_else(|| config.site.title.clone()),
+            og_type: if post.is_some() { "article" } else { "website" },
+            og_description,
+            twitter_description,
+            fediverse_creator: Some(fediverse_creator).filter(|creator| !creator.is_empty()),
+        }
+    }
+
+    /// The `<meta>` tags, one per line.
+    pub fn tags(&self) -> String {
+        let mut tags = vec![
+            property("og:title", &self.title),
+            property("og:type", self.og_type),
+        ];
+        if let Some(description) = &self.og_description {
+            tags.push(property("og:description", description));
+        }
+        tags.push(name("twitter:card", "summary"));
+        tags.push(name("twitter:title", &self.title));
+        if let Some(description) = &self.twitter_description {
+            tags.push(name("twitter:description", description));
+        }
+        if let Some(creator) = &self.fediverse_creator {
+            tags.push(name("fediverse:creator", creator));
+        }
+        tags.join("\n")
+    }
+}
+
+fn property(property: &str, content: &str) -> String {
+    format!(
+        r#"<meta property="{}" content="{}">"#,
+        property,
+        html::escape(content)
+    )
+}
+
+fn name(name: &str, content: &str) -> String {
+    format!(
+        r#"<meta name="{}" content="{}">"#,
+        name,
+        html::escape(content)
+    )
+}
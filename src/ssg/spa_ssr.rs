@@ -0,0 +1,166 @@
+//! Server-side rendering of the SPA, so a visitor opening any route directly gets its content in the first response
+//! rather than an empty `#main` waiting for the WASM.
+//!
+//! [`render_route`] renders the [`App`] at a route the way the browser would, with the hydration markers the client
+//! needs to take the markup over instead of rendering it again. The `generate_ssr` binary writes every route from
+//! [`routes`] into the `index.html` of a `dx build --features hydrate` bundle; a server can call [`render_route`] and
+//! [`page`] per request for the same result.
+//!
+//! The page links the stylesheets the route's components use in its head, from the [`Stylesheets`] of the render,
+//! so it's styled before the WASM arrives rather than when the components add them on hydration.
+//!
+//! Rendering happens outside the browser, so it needs a build without the `web` feature: the components only touch
+//! browser APIs (storage, the URL, `matchMedia`) under that feature, and read their defaults otherwise. State kept in
+//! localStorage, such as saved posts, reactions and the contact history, is read in an effect rather than for the
+//! first render, so the client's first render matches this one; it shows once the page has hydrated.
+
+use crate::posts;
+use crate::ssg::html;
+use crate::ssg::stylesheets;
+use crate::styles::{sheet_name, Stylesheets};
+use crate::{App, Route};
+use chrono::NaiveDate;
+use dioxus::history::{History, MemoryHistory};
+use dioxus::prelude::*;
+use std::fs;
+use std::path::Path;
+use std::rc::Rc;
+
+/// Id of the element the SPA renders into, as in the `index.html` of `dx build`.
+pub const ROOT_ID: &str = "main";
+
+/// Hydration data the client reads before taking over the markup: CBOR for an empty list, base64 encoded. The app
+/// has no server futures, so there is nothing to pass along, but the client expects the variable to be set.
+const HYDRATION_DATA: &str = "gA==";
+
+/// Every route with content known at build time: the pages and the published posts. Drafts and the component
+/// previews are left out like in the static generator.
+pub fn routes(today: NaiveDate) -> Vec<Route> {
+    let mut routes = vec![
+        Route::Home {},
+        Route::About {},
+        Route::Contact {},
+        Route::Speak {},
+        Route::Projects {},
+        Route::BlogIndex {},
+        Route::Saved {},
+    ];
+    routes.extend(posts::published(today).into_iter().map(|post| Route::Blog {
+        slug: post.slug.to_string(),
+    }));
+    routes
+}
+
+#[component]
+fn RouteRoot(path: String) -> Element {
+    // The router reads the current path from the history in the context
+    use_hook(|| {
+        provide_context(Rc::new(MemoryHistory::with_initial_path(path.clone())) as Rc<dyn History>)
+    });
+    rsx! {
+        App {}
+    }
+}
+
+/// A route as [`render_route`] rendered it.
+pub struct RenderedRoute {
+    /// The markup of `#main`, marked up for hydration
+    pub content: String,
+    /// The stylesheets its components link, by [`sheet_name`], the site-wide ones first
+    pub stylesheets: Vec<String>,
+}
+
+/// `route` rendered the way the browser would.
+pub fn render_route(route: &Route) -> RenderedRoute {
+    let stylesheets = Stylesheets::default();
+    let mut dom = VirtualDom::new_with_props(
+        RouteRoot,
+        RouteRootProps {
+            path: route.to_string(),
+        },
+    )
+    .with_root_context(stylesheets.clone());
+    dom.rebuild_in_place();
+    RenderedRoute {
+        content: dioxus_ssr::pre_render(&dom),
+        stylesheets: stylesheets.names(),
+    }
+}
+
+/// The site-relative addresses of the stylesheets in the `assets` directory of the bundle in `bundle_dir`.
+pub fn bundled_stylesheets(bundle_dir: &Path) -> Vec<String> {
+    let mut hrefs: Vec<String> = fs::read_dir(bundle_dir.join("assets"))
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .filter(|file| file.ends_with(".css"))
+        .map(|file| format!("/assets/{}", file))
+        .collect();
+    hrefs.sort();
+    hrefs
+}
+
+/// The addresses in `bundled` of the stylesheets `names`, in order. Names without a bundled file are left to the
+/// client.
+pub fn stylesheet_hrefs(bundled: &[String], names: &[String]) -> Vec<String> {
+    names
+        .iter()
+        .filter_map(|name| bundled.iter().find(|href| sheet_name(href) == name))
+        .cloned()
+        .collect()
+}
+
+/// `shell`, the `index.html` of the SPA bundle, with `content` from [`render_route`] in its root element, the title
+/// and description of `route`, links to `stylesheets` after the ones the shell has, and the hydration data the client
+/// reads on start.
+pub fn page(
+    shell: &str,
+    route: &Route,
+    content: &str,
+    stylesheets: &[String],
+) -> Result<String, String> {
+    let meta = route.page_meta();
+    let shell = match (shell.find("<title>"), shell.find("</title>")) {
+        (Some(start), Some(end)) if start < end => format!(
+            "{}<title>{}{}",
+            &shell[..start],
+            html::escape(&meta.title),
+            &shell[end..]
+        ),
+        _ => shell.to_string(),
+    };
+
+    let root = format!(r#"<div id="{}">"#, ROOT_ID);
+    let start = shell
+        .find(&root)
+        .ok_or_else(|| format!("the shell has no `{}`", root))?
+        + root.len();
+    let end = start
+        + shell[start..]
+            .find("</div>")
+            .ok_or_else(|| format!("`{}` isn't closed", root))?;
+    if !shell[start..end].trim().is_empty() {
+        return Err(format!(
+            "`{}` isn't empty; was the shell rendered already?",
+            root
+        ));
+    }
+    let head_end = shell.find("</head>").ok_or("the shell has no `</head>`")?;
+
+    let head = format!(
+        r#"<meta name="description" content="{description}">
+    <script>window.initial_dioxus_hydration_data = "{data}";</script>
+"#,
+        description = html::escape(&meta.description),
+        data = HYDRATION_DATA,
+    );
+
+    let mut page = String::with_capacity(shell.len() + content.len() + head.len());
+    page.push_str(&shell[..head_end]);
+    page.push_str(&head);
+    page.push_str(&shell[head_end..start]);
+    page.push_str(content);
+    page.push_str(&shell[end..]);
+    Ok(stylesheets::link(&page, stylesheets))
+}
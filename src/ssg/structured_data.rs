@@ -0,0 +1,163 @@
+//! Structured data for search engines: a schema.org [`blog_posting`] in JSON-LD in the head of every post page, added
+//! by [`prepare_page`](crate::ssg::output::prepare_page), and the properties each type needs, which `check --seo` holds
+//! every JSON-LD block of the generated pages to (see [`problems`]).
+
+use crate::posts::Post;
+use crate::ssg::config::SiteConfig;
+use chrono::NaiveDate;
+use serde_json::{json, Value};
+
+/// The vocabulary every block uses, as its `@context`.
+pub const CONTEXT: &str = "https://schema.org";
+
+/// Properties a type needs for search engines to show it, by `@type`. Types not listed only need `@type`.
+pub const REQUIRED: &[(&str, &[&str])] = &[
+    ("BlogPosting", &["headline", "datePublished", "author"]),
+    ("Article", &["headline", "datePublished", "author"]),
+    ("WebSite", &["name", "url"]),
+    ("Person", &["name"]),
+];
+
+/// Properties holding a date, as `YYYY-MM-DD` or a full RFC 3339 timestamp.
+const DATE_PROPERTIES: &[&str] = &["datePublished", "dateModified"];
+
+/// Longest `headline` search engines show in full, in characters.
+pub const HEADLINE_LENGTH: usize = 110;
+
+/// The `BlogPosting` of `post`, with the address of its page on the site.
+pub fn blog_posting(config: &SiteConfig, post: &Post) -> Value {
+    let base_url = config.site.base_url.trim_end_matches('/');
+    let url = format!(
+        "{}{}",
+        base_url,
+        config.build.output_style.page_url(&post.path())
+    );
+    let mut posting = json!({
+        "@context": CONTEXT,
+        "@type": "BlogPosting",
+        "headline": post.title,
+        "description": post.description(crate::excerpt::DESCRIPTION_LENGTH),
+        "datePublished": post.published.to_string(),
+        "dateModified": post.last_modified().to_string(),
+        "author": {
+            "@type": "Person",
+            "name": config.site.author,
+            "url": format!("{}/", base_url),
+        },
+        "mainEntityOfPage": url,
+        "url": url,
+    });
+    if !post.tags.is_empty() {
+        posting["keywords"] = json!(post.tags.join(", "));
+    }
+    if let Some(license) = post.license(&config.license).url() {
+        posting["license"] = json!(license);
+    }
+    posting
+}
+
+/// `value` as a `<script type="application/ld+json">` element. `</` is written as `<\/`, which is the same JSON but
+/// can't close the element early.
+pub fn script(value: &Value) -> String {
+    format!(
+        r#"<script type="application/ld+json">{}</script>"#,
+        value.to_string().replace("</", r"<\/")
+    )
+}
+
+/// What's wrong with a JSON-LD document: a missing `@context` or `@type`, a property [`REQUIRED`] by its type that's
+/// missing or empty, a date that doesn't parse, or a headline too long to be shown. Nested objects with a `@type`
+/// (the author) are held to their own type's properties; an array is checked item by item.
+pub fn problems(document: &Value) -> Vec<String> {
+    match document {
+        Value::Array(items) => items.iter().flat_map(problems).collect(),
+        Value::Object(object) => {
+            let mut problems = Vec::new();
+            match object.get("@context").and_then(Value::as_str) {
+                Some(context) if context.trim_end_matches('/').ends_with("schema.org") => {}
+                Some(context) => problems.push(format!("@context `{}` isn't schema.org", context)),
+                None => problems.push("no @context".to_string()),
+            }
+            if let Some(graph) = object.get("@graph") {
+                problems.extend(graph_problems(graph));
+                return problems;
+            }
+            problems.extend(node_problems(document));
+            problems
+        }
+        _ => vec!["isn't a JSON object".to_string()],
+    }
+}
+
+/// The problems of the nodes in a `@graph`, which share the document's `@context`.
+fn graph_problems(graph: &Value) -> Vec<String> {
+    match graph {
+        Value::Array(nodes) => nodes.iter().flat_map(node_problems).collect(),
+        _ => vec!["@graph isn't an array".to_string()],
+    }
+}
+
+fn node_problems(node: &Value) -> Vec<String> {
+    let Some(kind) = node.get("@type").and_then(Value::as_str) else {
+        return vec!["no @type".to_string()];
+    };
+
+    let mut problems = Vec::new();
+    let required = REQUIRED
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map_or(&[][..], |(_, required)| *required);
+    for property in required {
+        if node.get(*property).is_none_or(is_empty) {
+            problems.push(format!("{} has no {}", kind, property));
+        }
+    }
+
+    for property in DATE_PROPERTIES {
+        if let Some(value) = node.get(*property).and_then(Value::as_str) {
+            if !is_date(value) {
+                problems.push(format!("{} {} `{}` isn't a date", kind, property, value));
+            }
+        }
+    }
+    if let Some(headline) = node.get("headline").and_then(Value::as_str) {
+        let length = headline.chars().count();
+        if length > HEADLINE_LENGTH {
+            problems.push(format!(
+                "{} headline is {} characters; search engines cut it after {}",
+                kind, length, HEADLINE_LENGTH
+            ));
+        }
+    }
+
+    // The author, publisher and the like, when they're typed
+    for value in node
+        .as_object()
+        .into_iter()
+        .flat_map(|object| object.values())
+    {
+        for nested in value
+            .as_array()
+            .map_or(std::slice::from_ref(value), Vec::as_slice)
+        {
+            if nested.get("@type").is_some() {
+                problems.extend(node_problems(nested));
+            }
+        }
+    }
+    problems
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Null => true,
+        Value::String(text) => text.trim().is_empty(),
+        Value::Array(items) => items.is_empty(),
+        Value::Object(object) => object.is_empty(),
+        _ => false,
+    }
+}
+
+fn is_date(value: &str) -> bool {
+    value.parse::<NaiveDate>().is_ok() || chrono::DateTime::parse_from_rfc3339(value).is_ok()
+}
@@ -0,0 +1,89 @@
+//! The stylesheet links of a finished page, the HTML counterpart of [`Stylesheets`](crate::styles::Stylesheets):
+//! [`link`] gathers every `<link rel="stylesheet">` of the page into one run in the head, each file once, in the order
+//! the page first links them. A component's stylesheet written into the body, or the same file linked by two
+//! templates, ends up as a single link after the site-wide ones.
+
+use crate::ssg::html;
+
+/// `page` with its stylesheet links gathered where the first of them is in the head, or at the end of the head when
+/// it has none, followed by the ones in `extra` it doesn't link yet. Links to the same address are kept once, with
+/// the attributes of the first.
+pub fn link(page: &str, extra: &[String]) -> String {
+    let spans: Vec<_> = html::tag_spans(page, "link")
+        .into_iter()
+        .filter(|span| {
+            html::attr(&page[span.clone()], "rel").is_some_and(|rel| {
+                rel.split_whitespace()
+                    .any(|rel| rel.eq_ignore_ascii_case("stylesheet"))
+            })
+        })
+        .collect();
+    let Some(head_end) = page.find("</head>") else {
+        return page.to_string();
+    };
+
+    let mut hrefs: Vec<&str> = Vec::new();
+    let mut links: Vec<String> = Vec::new();
+    for span in &spans {
+        let tag = &page[span.clone()];
+        let href = html::attr(tag, "href").unwrap_or_default();
+        if !hrefs.contains(&href) {
+            hrefs.push(href);
+            links.push(tag.to_string());
+        }
+    }
+    for href in extra {
+        if !hrefs.contains(&href.as_str()) {
+            hrefs.push(href);
+            links.push(format!(
+                r#"<link rel="stylesheet" href="{}">"#,
+                html::escape(href)
+            ));
+        }
+    }
+    if links.is_empty() {
+        return page.to_string();
+    }
+
+    let first_in_head = spans.first().is_some_and(|span| span.start < head_end);
+    let mut out = String::with_capacity(page.len() + extra.len() * 48);
+    let mut last = 0;
+    for (index, span) in spans.iter().enumerate() {
+        let line_start = page[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let indent = &page[line_start..span.start];
+        if index == 0 && first_in_head {
+            // One to a line, lined up with the first
+            let indent = if indent.trim().is_empty() { indent } else { "" };
+            out.push_str(&page[last..span.start]);
+            out.push_str(&links.join(&format!("\n{}", indent)));
+            last = span.end;
+            continue;
+        }
+
+        // The tag goes, with its line when nothing else is on it
+        let rest = &page[span.end..];
+        match rest.find('\n') {
+            Some(end)
+                if indent.trim().is_empty()
+                    && rest[..end].trim().is_empty()
+                    && line_start >= last =>
+            {
+                out.push_str(&page[last..line_start]);
+                last = span.end + end + 1;
+            }
+            _ => {
+                out.push_str(&page[last..span.start]);
+                last = span.end;
+            }
+        }
+    }
+    out.push_str(&page[last..]);
+
+    if !first_in_head {
+        if let Some(head_end) = out.find("</head>") {
+            let lines: String = links.iter().map(|link| format!("    {}\n", link)).collect();
+            out.insert_str(head_end, &lines);
+        }
+    }
+    out
+}
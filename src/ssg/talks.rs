@@ -0,0 +1,170 @@
+//! Talks: conference decks written in markdown under [`TALKS_DIR`], one file per talk, published as slide pages
+//! under [`TALKS_PATH`].
+//!
+//! ```text
+//! ---
+//! title: Building a site with Dioxus
+//! event: RustConf 2026
+//! date: 2026-09-10
+//! description: From an SPA to static pages with WASM islands.
+//! ---
+//! Shown on the title slide.
+//!
+//! ## First slide
+//! What the audience sees.
+//!
+//! Note:
+//! What the speaker says; hidden until the notes are shown.
+//! ```
+//!
+//! Each `##` heading starts a slide, and a `Note:` line starts the slide's speaker notes. The file name is the slug.
+
+use crate::ssg::markdown;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Directory the talks are read from.
+pub const TALKS_DIR: &str = "content/talks";
+
+/// Route of the list of talks; each talk is below it.
+pub const TALKS_PATH: &str = "/talks/";
+
+/// Line starting a slide's speaker notes.
+const NOTES_MARKER: &str = "Note:";
+
+#[derive(Debug, Clone, Deserialize)]
+struct Frontmatter {
+    title: String,
+    #[serde(default)]
+    event: Option<String>,
+    date: NaiveDate,
+    #[serde(default)]
+    description: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Talk {
+    pub slug: String,
+    pub title: String,
+    /// Conference or meetup it was given at
+    pub event: Option<String>,
+    pub date: NaiveDate,
+    pub description: Option<String>,
+    /// What the title slide shows below the title
+    pub intro_html: String,
+    pub slides: Vec<Slide>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Slide {
+    pub title: String,
+    pub html: String,
+    pub notes_html: Option<String>,
+}
+
+impl Talk {
+    pub fn path(&self) -> String {
+        format!("{}{}/", TALKS_PATH, self.slug)
+    }
+
+    /// The event and date, as shown under the title.
+    pub fn byline(&self) -> String {
+        let date = self.date.format("%B %-d, %Y").to_string();
+        match &self.event {
+            Some(event) => format!("{} · {}", event, date),
+            None => date,
+        }
+    }
+}
+
+/// Every talk in `dir`, newest first. A missing directory has no talks.
+pub fn load(dir: &Path) -> Result<Vec<Talk>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+
+    let mut talks = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|extension| extension != "md") {
+            continue;
+        }
+        let slug = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| format!("{}: the file name isn't UTF-8", path.display()))?
+            .to_string();
+        let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        talks.push(parse(slug, &source).map_err(|e| format!("{}: {}", path.display(), e))?);
+    }
+    talks.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.slug.cmp(&b.slug)));
+    Ok(talks)
+}
+
+/// A talk from its markdown source.
+pub fn parse(slug: String, source: &str) -> Result<Talk, String> {
+    let (frontmatter, body) = crate::content::split_frontmatter(source);
+    let frontmatter: Frontmatter = serde_yaml::from_str(frontmatter.ok_or("no frontmatter")?)
+        .map_err(|e| format!("frontmatter: {}", e))?;
+
+    // Split on the `##` headings outside code blocks
+    let mut intro = String::new();
+    let mut slides: Vec<(String, String)> = Vec::new();
+    let mut in_code = false;
+    for line in body.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code = !in_code;
+        }
+        match line.strip_prefix("## ") {
+            Some(title) if !in_code => slides.push((title.trim().to_string(), String::new())),
+            _ => {
+                let text = match slides.last_mut() {
+                    Some((_, text)) => text,
+                    None => &mut intro,
+                };
+                text.push_str(line);
+                text.push('\n');
+            }
+        }
+    }
+    if slides.is_empty() {
+        return Err("no slides; start each one with a `## ` heading".to_string());
+    }
+
+    let slides = slides
+        .into_iter()
+        .map(|(title, text)| {
+            let (content, notes) = match text.lines().position(|line| line.trim() == NOTES_MARKER) {
+                Some(index) => {
+                    let lines: Vec<&str> = text.lines().collect();
+                    (
+                        lines[..index].join("\n"),
+                        Some(lines[index + 1..].join("\n")),
+                    )
+                }
+                None => (text, None),
+            };
+            Slide {
+                title,
+                html: markdown::to_html(&content),
+                notes_html: notes
+                    .filter(|notes| !notes.trim().is_empty())
+                    .map(|notes| markdown::to_html(&notes)),
+            }
+        })
+        .collect();
+
+    Ok(Talk {
+        slug,
+        title: frontmatter.title,
+        event: frontmatter.event,
+        date: frontmatter.date,
+        description: frontmatter.description,
+        intro_html: markdown::to_html(&intro),
+        slides,
+    })
+}
@@ -0,0 +1,406 @@
+//! Translated posts: a post from `src/posts.rs`, written in `[i18n] default_language`, can have versions in the other
+//! `[i18n] languages` as markdown files under [`TRANSLATIONS_DIR`], named after the post's slug and the language.
+//!
+//! ```text
+//! content/posts/blog-post-1.pl.md
+//! ---
+//! title: Pierwszy wpis
+//! summary: Krótko o tym, co dalej.   # optional, an excerpt otherwise
+//! ---
+//! The post in Polish, in markdown.
+//! ```
+//!
+//! A translation is published with its post at [`path`] (`/pl/blog/blog-post-1/`), and every version of a post links
+//! to the others, with `hreflang` alternates in the head (see [`alternates`]). Each language other than the default
+//! has a blog index at [`index_path`] and a feed at [`feed_path`]; both list every published post, falling back to the
+//! default-language version where there's no translation, so following one language misses nothing. The default
+//! language keeps `/blog/` and `/feed.xml`.
+
+use crate::excerpt;
+use crate::posts::{self, Post};
+use crate::ssg::config::{I18nConfig, SiteConfig};
+use crate::ssg::feed::{self, Channel, FeedItem};
+use crate::ssg::html;
+use crate::ssg::markdown;
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+
+/// Directory the translations are read from.
+pub const TRANSLATIONS_DIR: &str = "content/posts";
+
+/// Words of a translation's excerpt without a summary, as for posts.
+const EXCERPT_WORDS: usize = 30;
+
+#[derive(Debug, Clone, Deserialize)]
+struct Frontmatter {
+    title: String,
+    #[serde(default)]
+    summary: Option<String>,
+}
+
+/// A post in another language.
+#[derive(Debug, Clone)]
+pub struct Translation {
+    /// Slug of the post translated
+    pub slug: String,
+    /// Code of the language, as in `[i18n] languages`
+    pub lang: String,
+    pub title: String,
+    pub summary: Option<String>,
+    pub html: String,
+}
+
+impl Translation {
+    pub fn path(&self) -> String {
+        path(&self.lang, &self.slug)
+    }
+
+    /// The summary, or an excerpt of the translation.
+    pub fn excerpt(&self) -> String {
+        match &self.summary {
+            Some(summary) => summary.clone(),
+            None => excerpt::excerpt(&self.html, EXCERPT_WORDS),
+        }
+    }
+}
+
+/// Site-relative path of the version of the post with `slug` in `lang`, other than the default language.
+pub fn path(lang: &str, slug: &str) -> String {
+    format!("/{}/blog/{}/", lang, slug)
+}
+
+/// Site-relative path of the blog index in `lang`.
+pub fn index_path(lang: &str) -> String {
+    format!("/{}/blog/", lang)
+}
+
+/// Site path of the feed in `lang`.
+pub fn feed_path(lang: &str) -> String {
+    format!("/{}/feed.xml", lang)
+}
+
+/// Every translation in `dir`, by slug and then language. A missing directory has none. A file for a language that
+/// isn't in `config`, for the default language, or for a slug that isn't a post is an error, so a typo doesn't leave a
+/// translation unpublished.
+pub fn load(dir: &Path, config: &I18nConfig) -> Result<Vec<Translation>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("{}: {}", dir.display(), e)),
+    };
+
+    let mut translations = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| e.to_string())?.path();
+        if path.extension().is_none_or(|extension| extension != "md") {
+            continue;
+        }
+        let stem = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .ok_or_else(|| format!("{}: the file name isn't UTF-8", path.display()))?;
+        let (slug, lang) = stem
+            .rsplit_once('.')
+            .ok_or_else(|| format!("{}: name it <slug>.<language>.md", path.display()))?;
+        if lang == config.default_language {
+            return Err(format!(
+                "{}: posts in {} are written in src/posts.rs",
+                path.display(),
+                config.language_name(lang)
+            ));
+        }
+        if !config.languages.contains_key(lang) {
+            return Err(format!(
+                "{}: `{}` isn't in [i18n] languages",
+                path.display(),
+                lang
+            ));
+        }
+        if posts::find(slug).is_none() {
+            return Err(format!(
+                "{}: there's no post with the slug `{}`",
+                path.display(),
+                slug
+            ));
+        }
+        let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        translations.push(
+            parse(slug.to_string(), lang.to_string(), &source)
+                .map_err(|e| format!("{}: {}", path.display(), e))?,
+        );
+    }
+    translations.sort_by(|a, b| (&a.slug, &a.lang).cmp(&(&b.slug, &b.lang)));
+    Ok(translations)
+}
+
+/// A translation from its markdown source.
+pub fn parse(slug: String, lang: String, source: &str) -> Result<Translation, String> {
+    let (frontmatter, body) = crate::content::split_frontmatter(source);
+    let frontmatter: Frontmatter = serde_yaml::from_str(frontmatter.ok_or("no frontmatter")?)
+        .map_err(|e| format!("frontmatter: {}", e))?;
+    if body.trim().is_empty() {
+        return Err("the translation is empty".to_string());
+    }
+
+    Ok(Translation {
+        slug,
+        lang,
+        title: frontmatter.title,
+        summary: frontmatter.summary,
+        html: markdown::to_html(body),
+    })
+}
+
+/// The translation of `post` into `lang`, if there is one.
+pub fn find<'a>(
+    translations: &'a [Translation],
+    post: &Post,
+    lang: &str,
+) -> Option<&'a Translation> {
+    translations
+        .iter()
+        .find(|translation| translation.slug == post.slug && translation.lang == lang)
+}
+
+/// A version of a post, for the language links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Alternate {
+    pub lang: String,
+    /// Name of the language, in itself
+    pub name: String,
+    /// Site-relative path of the version
+    pub path: String,
+}
+
+/// Every version of `post`: the default language first, then its translations. A post without translations has only
+/// the one, and no language links.
+pub fn alternates(
+    config: &I18nConfig,
+    post: &Post,
+    translations: &[Translation],
+) -> Vec<Alternate> {
+    let default = Alternate {
+        lang: config.default_language.clone(),
+        name: config.language_name(&config.default_language).to_string(),
+        path: post.path(),
+    };
+    std::iter::once(default)
+        .chain(
+            translations
+                .iter()
+                .filter(|translation| translation.slug == post.slug)
+                .map(|translation| Alternate {
+                    lang: translation.lang.clone(),
+                    name: config.language_name(&translation.lang).to_string(),
+                    path: translation.path(),
+                }),
+        )
+        .collect()
+}
+
+/// The links from the version in `lang` to the others, or nothing when there are no others.
+pub fn links_html(alternates: &[Alternate], lang: &str) -> String {
+    let links: Vec<String> = alternates
+        .iter()
+        .filter(|alternate| alternate.lang != lang)
+        .map(|alternate| {
+            format!(
+                r#"<a href="{path}" hreflang="{lang}" lang="{lang}">{name}</a>"#,
+                path = alternate.path,
+                lang = html::escape(&alternate.lang),
+                name = html::escape(&alternate.name),
+            )
+        })
+        .collect();
+    if links.is_empty() {
+        return String::new();
+    }
+    format!(
+        r#"<p class="blog-date post-languages">Also in: {}</p>"#,
+        links.join(", ")
+    )
+}
+
+/// `<link rel="alternate" hreflang>` tags for every version, for the head of each, or nothing when there's one.
+pub fn head_links(config: &SiteConfig, alternates: &[Alternate]) -> String {
+    if alternates.len() < 2 {
+        return String::new();
+    }
+    let base_url = config.site.base_url.trim_end_matches('/');
+    alternates
+        .iter()
+        .map(|alternate| {
+            format!(
+                "    <link rel=\"alternate\" hreflang=\"{}\" href=\"{}{}\">\n",
+                html::escape(&alternate.lang),
+                base_url,
+                config.build.output_style.page_url(&alternate.path)
+            )
+        })
+        .collect()
+}
+
+/// What a post reads as in one language: its translation, or the post itself when there's none.
+pub enum Version<'a> {
+    Translated(&'a Translation),
+    Fallback(&'a Post),
+}
+
+impl Version<'_> {
+    pub fn title(&self) -> &str {
+        match self {
+            Version::Translated(translation) => &translation.title,
+            Version::Fallback(post) => post.title,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        match self {
+            Version::Translated(translation) => translation.path(),
+            Version::Fallback(post) => post.path(),
+        }
+    }
+}
+
+/// Every post of `posts` in `lang`, in order, falling back to the default language.
+pub fn versions<'a>(
+    posts: &'a [Post],
+    translations: &'a [Translation],
+    lang: &str,
+) -> Vec<(&'a Post, Version<'a>)> {
+    posts
+        .iter()
+        .map(|post| {
+            let version = match find(translations, post, lang) {
+                Some(translation) => Version::Translated(translation),
+                None => Version::Fallback(post),
+            };
+            (post, version)
+        })
+        .collect()
+}
+
+/// The RSS feed in `lang` of `posts`, which should only hold published posts: the translation of each, or the post
+/// itself where there's none.
+pub fn render_feed(
+    config: &SiteConfig,
+    lang: &str,
+    posts: &[Post],
+    translations: &[Translation],
+) -> String {
+    let style = config.build.output_style;
+    let versions = versions(posts, translations, lang);
+    let paths: Vec<String> = versions
+        .iter()
+        .map(|(_, version)| style.page_url(&version.path()))
+        .collect();
+    let summaries: Vec<String> = versions
+        .iter()
+        .map(|(post, version)| match version {
+            Version::Translated(translation) => translation.excerpt(),
+            Version::Fallback(_) => post.excerpt(),
+        })
+        .collect();
+    let bodies: Vec<String> = versions
+        .iter()
+        .map(|(post, version)| match version {
+            Version::Translated(translation) => translation.html.clone(),
+            Version::Fallback(_) => post.body_html(),
+        })
+        .collect();
+    let notices: Vec<String> = posts
+        .iter()
+        .map(|post| post.license_notice(&config.license))
+        .collect();
+
+    let items: Vec<FeedItem> = versions
+        .iter()
+        .zip(
+            paths
+                .iter()
+                .zip(summaries.iter().zip(bodies.iter().zip(&notices))),
+        )
+        .map(
+            |((post, version), (path, (summary, (body, notice))))| FeedItem {
+                title: version.title(),
+                path,
+                summary,
+                published: post.published,
+                updated: Some(post.last_modified()).filter(|&modified| modified > post.published),
+                content_html: body,
+                enclosure: None,
+                rights: notice,
+                license_url: post.license(&config.license).url(),
+            },
+        )
+        .collect();
+
+    let title = format!(
+        "{} ({})",
+        config.site.title,
+        config.i18n.language_name(lang)
+    );
+    let page = style.page_url(&index_path(lang));
+    feed::render_channel(
+        config,
+        &Channel {
+            title: &title,
+            description: &config.site.description,
+            path: &page,
+            feed_path: &feed_path(lang),
+        },
+        &items,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::posts::POSTS;
+
+    fn translation(slug: &str, lang: &str) -> Translation {
+        parse(
+            slug.to_string(),
+            lang.to_string(),
+            "---\ntitle: Pierwszy wpis\n---\nCześć.\n",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn a_translation_needs_a_title_and_a_body() {
+        let translation = translation("blog-post-1", "pl");
+        assert_eq!(translation.title, "Pierwszy wpis");
+        assert_eq!(translation.path(), "/pl/blog/blog-post-1/");
+        assert!(parse("a".into(), "pl".into(), "---\ntitle: A\n---\n").is_err());
+        assert!(parse("a".into(), "pl".into(), "Cześć.").is_err());
+    }
+
+    #[test]
+    fn posts_without_a_translation_fall_back_to_the_default_language() {
+        let posts = &POSTS[..2];
+        let translations = [translation(posts[0].slug, "pl")];
+        let versions = versions(posts, &translations, "pl");
+        assert!(matches!(versions[0].1, Version::Translated(_)));
+        assert!(matches!(versions[1].1, Version::Fallback(_)));
+        assert_eq!(versions[1].1.path(), posts[1].path());
+    }
+
+    #[test]
+    fn versions_link_to_each_other() {
+        let config = I18nConfig {
+            default_language: "en".to_string(),
+            languages: [("en", "English"), ("pl", "Polski")]
+                .map(|(code, name)| (code.to_string(), name.to_string()))
+                .into(),
+        };
+        let post = &POSTS[0];
+        let translations = [translation(post.slug, "pl")];
+        let alternates = alternates(&config, post, &translations);
+        assert_eq!(alternates.len(), 2);
+        assert!(links_html(&alternates, "en").contains(r#"hreflang="pl" lang="pl">Polski</a>"#));
+        assert!(links_html(&alternates, "pl").contains(">English</a>"));
+        assert_eq!(links_html(&alternates[..1], "en"), "");
+    }
+}
@@ -0,0 +1,111 @@
+//! Publishing content changes without anyone running a build: `site watch-remote` pulls the `[watch]` branch on an
+//! interval and builds and deploys only when the content is different from what it last published.
+//!
+//! What counts as the content is a [`fingerprint`]: the git objects of the `[watch] paths` at `HEAD`, and the posts
+//! published as of today, so a scheduled post going live needs a build even when no file changed. The fingerprint of
+//! the last successful deploy is kept in the `[watch] state` file.
+//!
+//! The posts are the ones compiled into the command. `cargo run` rebuilds it after a pull changed them, so running
+//! `site watch-remote --once` from cron picks up newly scheduled posts on the next run; a long-running loop needs a
+//! restart for that.
+
+use crate::posts;
+use crate::ssg::config::WatchConfig;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// What was last published.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct State {
+    pub fingerprint: String,
+    /// Commit the build was made from
+    pub commit: String,
+    pub published_at: DateTime<Utc>,
+}
+
+impl State {
+    /// The state in `path`, or `None` before the first deploy.
+    pub fn load(path: &Path) -> Result<Option<Self>, String> {
+        match fs::read_to_string(path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map(Some)
+                .map_err(|e| format!("{}: {}", path.display(), e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn store(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        fs::write(path, json).map_err(|e| format!("{}: {}", path.display(), e))
+    }
+}
+
+/// Bring the checkout up to date with `[watch] remote` and `branch`, fast-forward only, so local commits (such as a
+/// previous deploy's) are never lost. Does nothing without a remote.
+pub fn pull(config: &WatchConfig) -> Result<(), String> {
+    if config.remote.is_empty() {
+        return Ok(());
+    }
+    git(&["fetch", "--quiet", &config.remote, &config.branch])?;
+    git(&["merge", "--ff-only", "--quiet", "FETCH_HEAD"]).map(|_| ())
+}
+
+/// The commit checked out.
+pub fn head() -> Result<String, String> {
+    git(&["rev-parse", "--short", "HEAD"]).map(|commit| commit.trim().to_string())
+}
+
+/// Hash of the content at `HEAD` and of the posts published as of `today`.
+pub fn fingerprint(config: &WatchConfig, today: NaiveDate) -> Result<String, String> {
+    let mut args = vec!["ls-tree", "-r", "HEAD", "--"];
+    args.extend(config.paths.iter().map(String::as_str));
+    let tree = git(&args)?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(tree.as_bytes());
+    for post in posts::published(today) {
+        hasher.update(post.slug.as_bytes());
+        hasher.update(b"\n");
+    }
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+/// Run `command` with the shell, in the repository root, passing its output through.
+pub fn run(command: &str) -> Result<(), String> {
+    let status = Command::new("sh")
+        .args(["-c", command])
+        .status()
+        .map_err(|e| format!("couldn't run `{}`: {}", command, e))?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("`{}` failed ({})", command, status))
+    }
+}
+
+fn git(args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|e| format!("couldn't run git: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "git {}: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
@@ -0,0 +1,369 @@
+//! Contact submissions posted as JSON to the webhook in `[contact.webhook]`, so they can flow into Zapier, n8n or a
+//! Discord or Slack channel alongside the emails, or instead of them.
+//!
+//! The body is signed with HMAC-SHA256 using the secret in the environment variable named by `secret_env`, and the
+//! signature sent as `X-Signature-256: sha256=<hex>` so the receiver can tell the request came from the site. A
+//! delivery that fails (no connection or a status other than 2xx) is retried after a growing delay, up to
+//! `max_attempts` times. A submission that still isn't delivered is appended to the `dead_letters` file as a
+//! [`DeadLetter`], which `site webhooks list` shows and `site webhooks retry` sends again.
+//!
+//! Requests are made with `curl`, like the blogroll's feed fetches.
+
+use crate::api::ContactRequest;
+use crate::contact_reasons;
+use crate::ssg::config::{WebhookConfig, WebhookFormat};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// Header carrying the signature of the body.
+pub const SIGNATURE_HEADER: &str = "X-Signature-256";
+
+/// Event name in the JSON body.
+const EVENT: &str = "contact.submitted";
+
+/// Longest a single attempt may take.
+const TIMEOUT_SECS: u64 = 10;
+
+/// Delay before the first retry; it doubles for every further one.
+const BASE_DELAY_SECS: u64 = 1;
+
+/// Longest delay between attempts.
+const MAX_DELAY_SECS: u64 = 30;
+
+/// Discord rejects messages longer than this.
+const DISCORD_MAX_LENGTH: usize = 2000;
+
+/// Slack truncates messages longer than this.
+const SLACK_MAX_LENGTH: usize = 40000;
+
+/// A submission the webhook didn't take, as a line of the dead letters file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeadLetter {
+    pub received_at: DateTime<Utc>,
+    pub failed_at: DateTime<Utc>,
+    /// Attempts made before giving up, over every delivery of this submission
+    pub attempts: u32,
+    /// Why the last attempt failed
+    pub error: String,
+    pub request: ContactRequest,
+}
+
+/// How a delivery ended.
+#[derive(Debug, Clone)]
+pub enum Delivery {
+    Delivered { attempts: u32 },
+    DeadLettered(Box<DeadLetter>),
+}
+
+/// Post `request`, received at `received_at`, to the webhook, retrying with backoff. A submission that can't be
+/// delivered is appended to the dead letters; the error is only for a dead letter that couldn't be written.
+pub fn deliver(
+    config: &WebhookConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Result<Delivery, Box<dyn std::error::Error>> {
+    let delivery = attempt_delivery(config, request, received_at, 0);
+    if let Delivery::DeadLettered(letter) = &delivery {
+        append_dead_letter(Path::new(&config.dead_letters), letter)?;
+    }
+    Ok(delivery)
+}
+
+/// Post `request` to the webhook, retrying with backoff, without recording a failure anywhere. For callers that keep
+/// track of failed deliveries themselves, like the fan-out in [`notify`](crate::ssg::notify).
+pub fn send(
+    config: &WebhookConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Delivery {
+    attempt_delivery(config, request, received_at, 0)
+}
+
+/// Send every dead letter again. The ones that fail once more stay in the file, with the new attempts counted.
+pub fn retry_dead_letters(
+    config: &WebhookConfig,
+) -> Result<Vec<Delivery>, Box<dyn std::error::Error>> {
+    let path = Path::new(&config.dead_letters);
+    let letters = dead_letters(path)?;
+    if letters.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let deliveries: Vec<Delivery> = letters
+        .into_iter()
+        .map(|letter| {
+            attempt_delivery(config, &letter.request, letter.received_at, letter.attempts)
+        })
+        .collect();
+
+    let remaining: Vec<&DeadLetter> = deliveries
+        .iter()
+        .filter_map(|delivery| match delivery {
+            Delivery::DeadLettered(letter) => Some(letter.as_ref()),
+            Delivery::Delivered { .. } => None,
+        })
+        .collect();
+    write_dead_letters(path, &remaining)?;
+
+    Ok(deliveries)
+}
+
+/// The dead letters recorded so far, oldest first. A missing file has none.
+pub fn dead_letters(path: &Path) -> Result<Vec<DeadLetter>, Box<dyn std::error::Error>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .enumerate()
+        .map(|(index, line)| {
+            serde_json::from_str(line)
+                .map_err(|e| format!("{} line {}: {}", path.display(), index + 1, e).into())
+        })
+        .collect()
+}
+
+/// `attempts_before` counts the attempts of earlier deliveries of the same submission.
+fn attempt_delivery(
+    config: &WebhookConfig,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+    attempts_before: u32,
+) -> Delivery {
+    let dead_letter = |attempts: u32, error: String| {
+        Delivery::DeadLettered(Box::new(DeadLetter {
+            received_at,
+            failed_at: Utc::now(),
+            attempts: attempts_before + attempts,
+            error,
+            request: request.clone(),
+        }))
+    };
+
+    let secret = match secret(config) {
+        Ok(secret) => secret,
+        Err(error) => return dead_letter(0, error),
+    };
+    let body = match body(config.format, request, received_at) {
+        Ok(body) => body,
+        Err(error) => return dead_letter(0, error.to_string()),
+    };
+    let signature = secret.map(|secret| signature(secret.as_bytes(), body.as_bytes()));
+
+    let mut error = String::new();
+    for attempt in 1..=config.max_attempts {
+        match post(&config.url, &body, signature.as_deref()) {
+            Ok(()) => return Delivery::Delivered { attempts: attempt },
+            Err(e) => error = e,
+        }
+        if attempt < config.max_attempts {
+            std::thread::sleep(backoff(attempt));
+        }
+    }
+    dead_letter(config.max_attempts, error)
+}
+
+/// The signing secret, or `None` when signing is turned off with an empty `secret_env`.
+fn secret(config: &WebhookConfig) -> Result<Option<String>, String> {
+    if config.secret_env.is_empty() {
+        return Ok(None);
+    }
+    std::env::var(&config.secret_env)
+        .map(Some)
+        .map_err(|_| format!("the signing secret ${} isn't set", config.secret_env))
+}
+
+/// Delay after failed attempt `attempt` (from 1).
+fn backoff(attempt: u32) -> Duration {
+    let seconds = BASE_DELAY_SECS.saturating_mul(1 << attempt.saturating_sub(1).min(16));
+    Duration::from_secs(seconds.min(MAX_DELAY_SECS))
+}
+
+/// The JSON body for `request` in `format`.
+pub fn body(
+    format: WebhookFormat,
+    request: &ContactRequest,
+    received_at: DateTime<Utc>,
+) -> Result<String, serde_json::Error> {
+    let form = request
+        .schema()
+        .map_or(request.form.as_str(), |schema| schema.id.as_str());
+    let reason = contact_reasons::reason(&request.reason)
+        .map_or(request.reason.as_str(), |reason| reason.label);
+
+    let body = match format {
+        WebhookFormat::Json => serde_json::json!({
+            "event": EVENT,
+            "received_at": received_at.to_rfc3339(),
+            "form": form,
+            "reason": { "id": request.reason, "label": reason },
+            "phone_e164": request.normalized_phone(),
+            "submission": request,
+        }),
+        WebhookFormat::Discord => {
+            let content = format!(
+                "**{}** from {} <{}> ({}, {})\n\n{}",
+                request.subject.trim(),
+                request.name.trim(),
+                request.email.trim(),
+                form,
+                reason,
+                request.message.trim()
+            );
+            serde_json::json!({
+                "content": truncate(&content, DISCORD_MAX_LENGTH),
+                // Nothing in a submission should ping anyone in the channel
+                "allowed_mentions": { "parse": [] },
+            })
+        }
+        WebhookFormat::Slack => {
+            let email = slack_escape(request.email.trim());
+            let content = format!(
+                "*{}* from {} <mailto:{}|{}> ({}, {})\n\n{}",
+                slack_escape(request.subject.trim()),
+                slack_escape(request.name.trim()),
+                email,
+                email,
+                slack_escape(form),
+                slack_escape(reason),
+                slack_escape(request.message.trim())
+            );
+            serde_json::json!({ "text": truncate(&content, SLACK_MAX_LENGTH) })
+        }
+    };
+    serde_json::to_string(&body)
+}
+
+/// Slack reads `&`, `<` and `>` as markup, so `<!channel>` in a submission would ping everyone in the channel.
+fn slack_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+/// `sha256=<hex HMAC-SHA256 of body>`, the value of [`SIGNATURE_HEADER`].
+pub fn signature(secret: &[u8], body: &[u8]) -> String {
+    // HMAC takes keys of any length, so this can't fail
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(body);
+    let hex: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    format!("sha256={}", hex)
+}
+
+fn post(url: &str, body: &str, signature: Option<&str>) -> Result<(), String> {
+    let mut command = Command::new("curl");
+    command
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--output",
+            "/dev/null",
+        ])
+        .args(["--max-time", &TIMEOUT_SECS.to_string()])
+        .args(["--header", "Content-Type: application/json"])
+        .args(["--data-binary", "@-"]);
+    if let Some(signature) = signature {
+        command.args(["--header", &format!("{}: {}", SIGNATURE_HEADER, signature)]);
+    }
+
+    let mut child = command
+        .arg(url)
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("couldn't run curl: {}", e))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(body.as_bytes())
+            .map_err(|e| format!("couldn't send the body to curl: {}", e))?;
+    }
+    let output = child.wait_with_output().map_err(|e| e.to_string())?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+fn append_dead_letter(path: &Path, letter: &DeadLetter) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(letter)?)?;
+    Ok(())
+}
+
+fn write_dead_letters(
+    path: &Path,
+    letters: &[&DeadLetter],
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lines = String::new();
+    for letter in letters {
+        lines.push_str(&serde_json::to_string(letter)?);
+        lines.push('\n');
+    }
+    fs::write(path, lines)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test cases from RFC 4231, section 4.
+    #[test]
+    fn signature_matches_rfc_4231() {
+        // Test case 1
+        assert_eq!(
+            signature(&[0x0b; 20], b"Hi There"),
+            "sha256=b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        // Test case 2: a key shorter than the output
+        assert_eq!(
+            signature(b"Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        // Test case 6: a key longer than the block size, which is hashed first
+        assert_eq!(
+            signature(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            ),
+            "sha256=60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+        // Test case 7: a long key and a long message
+        assert_eq!(
+            signature(
+                &[0xaa; 131],
+                b"This is a test using a larger than block-size key and a larger than block-size data. The key needs to be hashed before being used by the HMAC algorithm."
+            ),
+            "sha256=9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2"
+        );
+    }
+}
@@ -0,0 +1,127 @@
+//! The scoped page stylesheets, compiled in by `build.rs` (see [`scoped_css`](crate::scoped_css)).
+//!
+//! A view renders [`ScopedStylesheet`] for its stylesheet and puts the [`scope`](ScopedStyle::scope) class on its root
+//! element, or on any element outside the view that uses the stylesheet's classes. The generator writes the same CSS
+//! over the copied files in `assets/styling/`, so the static pages link them as before.
+//!
+//! Components link their unscoped stylesheet with [`Stylesheet`] rather than `document::Link`. It adds each file to
+//! the head once, in the order the components first render, and leaves out the ones the page already links: an island
+//! mounted into a generated page finds its stylesheet in the static head, and a page rendered by
+//! [`spa_ssr`](crate::ssg::spa_ssr) lists them in its head from [`Stylesheets`].
+
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::collections::BTreeSet;
+use std::rc::Rc;
+
+/// A stylesheet from `assets/styling/` with its rules scoped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScopedStyle {
+    /// File name without the extension, e.g. `contact`
+    pub name: &'static str,
+    /// Class that the rules are limited to
+    pub scope: &'static str,
+    pub css: &'static str,
+}
+
+include!(concat!(env!("OUT_DIR"), "/styles.rs"));
+
+/// The names of the stylesheets already added to the document.
+#[derive(Clone, Default)]
+struct AddedStyles(Rc<RefCell<BTreeSet<&'static str>>>);
+
+/// Adds `style` to the document head, once however many views use it. Head elements stay when the view goes, so the
+/// rules are there for the next visit.
+#[component]
+pub fn ScopedStylesheet(style: &'static ScopedStyle) -> Element {
+    let added = use_root_context(AddedStyles::default);
+    let first = use_hook(|| added.0.borrow_mut().insert(style.name));
+
+    rsx! {
+        if first {
+            document::Style { {style.css} }
+        }
+    }
+}
+
+/// The stylesheets a document links through [`Stylesheet`], by [`sheet_name`], in the order they were first used.
+/// Kept in the root context; a renderer can provide it to read the list after rendering.
+#[derive(Clone, Default)]
+pub struct Stylesheets(Rc<RefCell<Vec<String>>>);
+
+impl Stylesheets {
+    pub fn names(&self) -> Vec<String> {
+        self.0.borrow().clone()
+    }
+
+    /// Whether `name` wasn't in the list yet.
+    fn add(&self, name: &str) -> bool {
+        let mut names = self.0.borrow_mut();
+        if names.iter().any(|added| added == name) {
+            return false;
+        }
+        names.push(name.to_string());
+        true
+    }
+}
+
+/// The file name of a stylesheet without its directory, extension and bundle hash, so the copies of a file agree:
+/// `hints` for `/assets/styling/hints.css` and `/assets/hints-dxh3f2a.css`.
+pub fn sheet_name(href: &str) -> &str {
+    let file = href
+        .split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .rsplit('/')
+        .next()
+        .unwrap_or_default();
+    let stem = file.strip_suffix(".css").unwrap_or(file);
+    stem.rsplit_once("-dxh").map_or(stem, |(name, _)| name)
+}
+
+/// Links the stylesheet at `href` from the document head, once however many components use it. Renders nothing in
+/// place, so a prerendered page and the client that hydrates it agree whether the head had it already.
+#[component]
+pub fn Stylesheet(href: Asset) -> Element {
+    let stylesheets = use_root_context(Stylesheets::default);
+    use_hook(|| {
+        let href = href.to_string();
+        if stylesheets.add(sheet_name(&href)) && !linked_by_page(&href) {
+            document::document().create_head_element(
+                "link",
+                &[("rel", "stylesheet".to_string()), ("href", href)],
+                None,
+            );
+        }
+    });
+
+    rsx! {}
+}
+
+/// Whether the page already links a copy of the stylesheet at `href`: the static head of a generated page, or an
+/// island mounted before this one.
+#[cfg(feature = "web")]
+fn linked_by_page(href: &str) -> bool {
+    use wasm_bindgen::JsCast;
+
+    let Some(links) = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| {
+            document
+                .query_selector_all(r#"link[rel~="stylesheet"]"#)
+                .ok()
+        })
+    else {
+        return false;
+    };
+    (0..links.length())
+        .filter_map(|index| links.item(index))
+        .filter_map(|node| node.dyn_into::<web_sys::Element>().ok())
+        .filter_map(|link| link.get_attribute("href"))
+        .any(|linked| sheet_name(&linked) == sheet_name(href))
+}
+
+#[cfg(not(feature = "web"))]
+fn linked_by_page(_href: &str) -> bool {
+    false
+}
@@ -0,0 +1,158 @@
+//! Tag normalization, so the projects filter doesn't fragment into `Rust`, `rust` and `rust-lang`.
+//!
+//! Tags compare by their [`fold`]ed form: lowercase letters and digits only. `build.rs` normalizes every tag in
+//! `assets/data/` as it compiles the data in: a tag listed in `[tags.aliases]` in `site.toml` becomes its canonical
+//! spelling, and any other tag takes the spelling it first appears with. Tags that still look alike afterwards (a
+//! typo, or `React` next to `ReactJS`) are listed by [`near_duplicates`], which the static generator prints as a
+//! build warning so they can be merged with an alias.
+//!
+//! This file is also compiled into `build.rs`, so it only uses what the build script depends on.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::OnceLock;
+
+const SITE_TOML: &str = include_str!("../site.toml");
+
+/// The `[tags]` section of `site.toml`.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TagsConfig {
+    /// Alternative spelling → canonical tag, e.g. `rustlang = "Rust"`. Keys match in any case and punctuation.
+    pub aliases: BTreeMap<String, String>,
+}
+
+impl TagsConfig {
+    /// The `[tags]` section of the compiled-in `site.toml`, or no aliases if it can't be read.
+    pub fn compiled() -> &'static TagsConfig {
+        #[derive(Default, Deserialize)]
+        #[serde(default)]
+        struct SiteToml {
+            tags: TagsConfig,
+        }
+
+        static TAGS: OnceLock<TagsConfig> = OnceLock::new();
+        TAGS.get_or_init(|| {
+            toml::from_str::<SiteToml>(SITE_TOML)
+                .map(|site| site.tags)
+                .unwrap_or_default()
+        })
+    }
+
+    /// Canonical spelling for `tag` if it's an alias.
+    pub fn alias(&self, tag: &str) -> Option<&str> {
+        let folded = fold(tag);
+        self.aliases
+            .iter()
+            .find(|(alias, _)| fold(alias) == folded)
+            .map(|(_, canonical)| canonical.as_str())
+    }
+
+    /// Problems with the aliases, for `SiteConfig::load`.
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (alias, canonical) in &self.aliases {
+            if canonical.trim().is_empty() {
+                problems.push(format!("[tags.aliases] `{}` has an empty tag", alias));
+            } else if self
+                .alias(canonical)
+                .is_some_and(|target| fold(target) != fold(canonical))
+            {
+                problems.push(format!(
+                    "[tags.aliases] `{}` points to `{}`, which is an alias itself",
+                    alias, canonical
+                ));
+            }
+        }
+        problems
+    }
+}
+
+/// The form tags are compared in: lowercase, with everything but letters and digits removed.
+pub fn fold(tag: &str) -> String {
+    tag.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Normalizes a stream of tags, remembering the spelling each one was first seen with.
+pub struct Normalizer<'a> {
+    config: &'a TagsConfig,
+    seen: BTreeMap<String, String>,
+}
+
+impl<'a> Normalizer<'a> {
+    pub fn new(config: &'a TagsConfig) -> Self {
+        Self {
+            config,
+            seen: BTreeMap::new(),
+        }
+    }
+
+    pub fn normalize(&mut self, tag: &str) -> String {
+        let tag = self.config.alias(tag).unwrap_or(tag).trim();
+        self.seen
+            .entry(fold(tag))
+            .or_insert_with(|| tag.to_string())
+            .clone()
+    }
+}
+
+/// Pairs of distinct tags that are probably the same one: a letter or two apart, or one a short extension of the other
+/// (`React` / `ReactJS`).
+pub fn near_duplicates(tags: &[&str]) -> Vec<(String, String)> {
+    let mut unique: Vec<&str> = Vec::new();
+    for tag in tags {
+        if !unique.iter().any(|known| fold(known) == fold(tag)) {
+            unique.push(tag);
+        }
+    }
+
+    let mut pairs = Vec::new();
+    for (i, a) in unique.iter().enumerate() {
+        for b in &unique[i + 1..] {
+            if looks_alike(&fold(a), &fold(b)) {
+                pairs.push((a.to_string(), b.to_string()));
+            }
+        }
+    }
+    pairs
+}
+
+fn looks_alike(a: &str, b: &str) -> bool {
+    let (short, long) = if a.chars().count() <= b.chars().count() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let short_len = short.chars().count();
+    let long_len = long.chars().count();
+
+    // Too little to go on: `C` and `Go` look like everything
+    if short_len < 3 {
+        return false;
+    }
+    if long.starts_with(short) && long_len - short_len <= 4 {
+        return true;
+    }
+    let allowed = if short_len < 6 { 1 } else { 2 };
+    edit_distance(short, long) <= allowed
+}
+
+/// Levenshtein distance in characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,61 @@
+//! Design tokens from `theme.toml`, emitted as CSS custom properties.
+//!
+//! The file is compiled in, so the SPA and the static generator always agree on the values.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+const THEME_TOML: &str = include_str!("../theme.toml");
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct Theme {
+    pub colors: BTreeMap<String, String>,
+    pub spacing: BTreeMap<String, String>,
+    pub radii: BTreeMap<String, String>,
+    #[serde(rename = "font-sizes")]
+    pub font_sizes: BTreeMap<String, String>,
+}
+
+impl Theme {
+    /// Parse the compiled-in `theme.toml`.
+    pub fn load() -> Result<Self, String> {
+        let theme: Theme = toml::from_str(THEME_TOML).map_err(|e| format!("theme.toml: {}", e))?;
+
+        // Values end up inside a <style> block, so anything that could close the declaration or the element is refused
+        for (name, value) in theme.tokens() {
+            if value.contains(['{', '}', ';', '<', '>']) {
+                return Err(format!(
+                    "theme.toml: invalid value for --{}: {}",
+                    name, value
+                ));
+            }
+        }
+
+        Ok(theme)
+    }
+
+    /// Every token as `(custom property name without the leading --, value)`.
+    fn tokens(&self) -> impl Iterator<Item = (String, &str)> {
+        let groups = [
+            ("color", &self.colors),
+            ("space", &self.spacing),
+            ("radius", &self.radii),
+            ("font-size", &self.font_sizes),
+        ];
+        groups.into_iter().flat_map(|(prefix, tokens)| {
+            tokens
+                .iter()
+                .map(move |(name, value)| (format!("{}-{}", prefix, name), value.as_str()))
+        })
+    }
+
+    /// A `:root { ... }` rule declaring every token.
+    pub fn css_variables(&self) -> String {
+        let declarations: String = self
+            .tokens()
+            .map(|(name, value)| format!("    --{}: {};\n", name, value))
+            .collect();
+        format!(":root {{\n{}}}\n", declarations)
+    }
+}
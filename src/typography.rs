@@ -0,0 +1,43 @@
+//! The typographic scale shared by the [`Heading`](crate::components::Heading) and
+//! [`Prose`](crate::components::Prose) components and the static generator's templates.
+//!
+//! Headings are styled by level alone (`.heading-1` to `.heading-4` in `main.css`, sized by the `[font-sizes]` tokens
+//! in `theme.toml`), so an `h2` looks the same on every page. Anchor ids are derived the same way on both sides, which
+//! keeps links like `/about#experience` working in the SPA and the generated pages.
+
+/// Levels on the scale. Deeper headings are rendered as the last one.
+pub const HEADING_LEVELS: u8 = 4;
+
+/// Class of long-form text: paragraphs, lists and any headings inside body HTML follow the scale.
+pub const PROSE_CLASS: &str = "prose";
+
+/// The level actually rendered for `level`, between 1 and [`HEADING_LEVELS`].
+pub fn heading_level(level: u8) -> u8 {
+    level.clamp(1, HEADING_LEVELS)
+}
+
+/// Classes of a heading at `level`, followed by `extra` for page-specific decoration.
+pub fn heading_class(level: u8, extra: Option<&str>) -> String {
+    let level = heading_level(level);
+    match extra {
+        Some(extra) if !extra.is_empty() => format!("heading heading-{} {}", level, extra),
+        _ => format!("heading heading-{}", level),
+    }
+}
+
+/// Anchor id for a heading: lowercase letters and digits, with `-` between words.
+pub fn anchor_id(text: &str) -> String {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Classes of a [`Prose`](crate::components::Prose) block, followed by `extra`.
+pub fn prose_class(extra: Option<&str>) -> String {
+    match extra {
+        Some(extra) if !extra.is_empty() => format!("{} {}", PROSE_CLASS, extra),
+        _ => PROSE_CLASS.to_string(),
+    }
+}
@@ -0,0 +1,122 @@
+//! A warning before leaving a form that has changes nobody submitted.
+//!
+//! [`use_unsaved_guard`] asks for confirmation while its `dirty` signal is true: the browser's own prompt on a reload,
+//! a closed tab or a link to another site (`beforeunload`), and [`MESSAGE`] in a `confirm` dialog on a link to another
+//! route of the SPA. Back and forward buttons within the SPA aren't guarded, since the router has already moved by
+//! the time it could ask.
+//!
+//! The forms built from `forms/` get it from [`use_contact_form`](crate::components::use_contact_form); another form
+//! only needs a signal saying whether it has changes.
+
+use dioxus::prelude::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// Question asked before an SPA navigation leaves unsubmitted changes behind. Browsers show their own text for
+/// `beforeunload`.
+pub const MESSAGE: &str = "You have changes that haven't been sent. Leave this page and lose them?";
+
+/// Listeners asking before the page is left while `dirty` is set. Dropping the guard removes them.
+pub struct UnsavedGuard {
+    #[cfg(feature = "web")]
+    before_unload: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::Event)>,
+    #[cfg(feature = "web")]
+    click: wasm_bindgen::closure::Closure<dyn FnMut(web_sys::MouseEvent)>,
+}
+
+impl UnsavedGuard {
+    /// Start asking whenever `dirty` holds. `None` outside the browser.
+    #[cfg(feature = "web")]
+    pub fn install(dirty: ReadSignal<bool>) -> Option<Self> {
+        use dioxus::core::Runtime;
+        use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+        let window = web_sys::window()?;
+
+        // The events arrive outside the Dioxus runtime. `dirty` is read as it is when they do, so a form that was
+        // just submitted and navigates away isn't held up by a render that hasn't happened yet
+        let runtime = Runtime::current();
+        let dirty = move || runtime.in_scope(ScopeId::ROOT, || *dirty.peek());
+
+        let before_unload = Closure::<dyn FnMut(web_sys::Event)>::new({
+            let dirty = dirty.clone();
+            move |event: web_sys::Event| {
+                if dirty() {
+                    event.prevent_default();
+                    // Older browsers only prompt when `returnValue` is set
+                    let _ = js_sys::Reflect::set(
+                        &event,
+                        &JsValue::from_str("returnValue"),
+                        &JsValue::TRUE,
+                    );
+                }
+            }
+        });
+        window
+            .add_event_listener_with_callback(
+                "beforeunload",
+                before_unload.as_ref().unchecked_ref(),
+            )
+            .ok()?;
+
+        // The capture phase on the window comes before the view transitions' listener on the document and the
+        // router's on the app's root element, so a cancelled navigation never reaches either
+        let click = Closure::<dyn FnMut(web_sys::MouseEvent)>::new({
+            let window = window.clone();
+            move |event: web_sys::MouseEvent| {
+                if !dirty() || crate::view_transitions::followed_route(&event).is_none() {
+                    return;
+                }
+                if !window.confirm_with_message(MESSAGE).unwrap_or(true) {
+                    event.prevent_default();
+                    event.stop_immediate_propagation();
+                }
+            }
+        });
+        window
+            .add_event_listener_with_callback_and_bool(
+                "click",
+                click.as_ref().unchecked_ref(),
+                true,
+            )
+            .ok()?;
+
+        Some(Self {
+            before_unload,
+            click,
+        })
+    }
+
+    #[cfg(not(feature = "web"))]
+    pub fn install(_dirty: ReadSignal<bool>) -> Option<Self> {
+        None
+    }
+}
+
+#[cfg(feature = "web")]
+impl Drop for UnsavedGuard {
+    fn drop(&mut self) {
+        use wasm_bindgen::JsCast;
+
+        if let Some(window) = web_sys::window() {
+            let _ = window.remove_event_listener_with_callback(
+                "beforeunload",
+                self.before_unload.as_ref().unchecked_ref(),
+            );
+            let _ = window.remove_event_listener_with_callback_and_bool(
+                "click",
+                self.click.as_ref().unchecked_ref(),
+                true,
+            );
+        }
+    }
+}
+
+/// Ask before leaving the page while `dirty` is true, for as long as the component is mounted.
+pub fn use_unsaved_guard(dirty: ReadSignal<bool>) {
+    let guard = use_hook(|| Rc::new(RefCell::new(UnsavedGuard::install(dirty))));
+
+    dioxus::core::use_drop(move || {
+        guard.borrow_mut().take();
+    });
+}
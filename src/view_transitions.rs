@@ -0,0 +1,263 @@
+//! View transitions between pages, in browsers with the View Transitions API.
+//!
+//! Generated pages opt in to cross-document transitions in `transitions.css`, and the SPA runs route changes started
+//! from a link in `document.startViewTransition` with [`use_view_transitions`]. Either way the navbar stays put while
+//! the rest of the page crossfades, and a post's title moves between its entry on a list and the heading of the post.
+//! Other browsers, and readers who prefer reduced motion, navigate as before.
+//!
+//! Transition names have to be unique on a page, so the titles in a list carry [`TRANSITION_ATTRIBUTE`] and only the
+//! one linking to the page on the other side of the navigation is given its name as the transition starts.
+//! A post's own heading always has the [`POST_TITLE`] class, which names it in the stylesheet.
+
+/// Attribute marking an element that takes part in a transition when its link is followed (or was followed to get
+/// here). Its value is the transition name.
+pub const TRANSITION_ATTRIBUTE: &str = "data-view-transition";
+
+/// Transition name of a post's title, and the class of the heading on the post's page.
+pub const POST_TITLE: &str = "post-title";
+
+/// Names the marked title on generated pages as a cross-document transition starts: the one linking to the next page
+/// when leaving (`pageswap`), and the one linking to the previous page on arrival (`pagereveal`). It has to run before
+/// the first render, so it goes in the head.
+pub const STATIC_SCRIPT: &str = r#"(function () {
+        if (!window.navigation || !('onpagereveal' in window)) return;
+        function mark(url) {
+            var path = new URL(url).pathname;
+            var marked = Array.prototype.find.call(document.querySelectorAll('[data-view-transition]'), function (element) {
+                var link = element.querySelector('a[href]');
+                return link && new URL(link.href).pathname === path;
+            });
+            if (marked) marked.style.viewTransitionName = marked.getAttribute('data-view-transition');
+            return marked;
+        }
+        function unmarkAfter(transition, marked) {
+            if (marked) transition.finished.finally(function () { marked.style.viewTransitionName = ''; });
+        }
+        window.addEventListener('pageswap', function (event) {
+            if (event.viewTransition && event.activation && event.activation.entry) {
+                unmarkAfter(event.viewTransition, mark(event.activation.entry.url));
+            }
+        });
+        window.addEventListener('pagereveal', function (event) {
+            var from = navigation.activation && navigation.activation.from;
+            if (event.viewTransition && from) {
+                unmarkAfter(event.viewTransition, mark(from.url));
+            }
+        });
+    })();"#;
+
+/// Run SPA navigations started from a link in a view transition. Call it once, in the layout every route renders in.
+///
+/// A click on a link to one of the SPA's routes is taken over before the router sees it: the title linking to the new
+/// page is named, and the router navigates inside the transition's update callback, which resolves once the new route
+/// has rendered. Back and forward navigations aren't animated.
+pub fn use_view_transitions() {
+    #[cfg(feature = "web")]
+    web::use_view_transitions();
+}
+
+#[cfg(feature = "web")]
+pub(crate) use web::followed_route;
+
+#[cfg(feature = "web")]
+mod web {
+    use super::TRANSITION_ATTRIBUTE;
+    use crate::Route;
+    use dioxus::core::Runtime;
+    use dioxus::prelude::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+    use std::str::FromStr;
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+
+    /// The transition in progress.
+    #[derive(Default)]
+    struct Pending {
+        /// Resolves the update callback's promise, which lets the browser capture the new page
+        rendered: Option<js_sys::Function>,
+        /// Path of the page the navigation started on, whose title is named on the new page
+        from: String,
+        /// The titles named for this transition, unnamed again when the next one starts
+        marked: Vec<web_sys::HtmlElement>,
+    }
+
+    pub fn use_view_transitions() {
+        let route = use_route::<Route>();
+        let pending = use_hook(|| {
+            let pending = Rc::new(RefCell::new(Pending::default()));
+            listen(pending.clone());
+            pending
+        });
+
+        // Effects run once the new route is in the DOM
+        use_effect(use_reactive!(|route| {
+            let _ = route;
+            let mut pending = pending.borrow_mut();
+            let Some(rendered) = pending.rendered.take() else {
+                return;
+            };
+            if let Some(title) = marked_title(&pending.from) {
+                pending.marked.push(title);
+            }
+            let _ = rendered.call0(&JsValue::NULL);
+        }));
+    }
+
+    /// Take over clicks on links to SPA routes. The listener lives as long as the page, like the layout calling it.
+    fn listen(pending: Rc<RefCell<Pending>>) {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let Some(document) = window.document() else {
+            return;
+        };
+        let start = js_sys::Reflect::get(&document, &JsValue::from_str("startViewTransition"))
+            .ok()
+            .and_then(|start| start.dyn_into::<js_sys::Function>().ok());
+        let Some(start) = start else {
+            return;
+        };
+        let reduced_motion = window
+            .match_media("(prefers-reduced-motion: reduce)")
+            .ok()
+            .flatten()
+            .is_some_and(|query| query.matches());
+        if reduced_motion {
+            return;
+        }
+
+        // The clicks arrive outside the Dioxus runtime, so navigation is run in this app's root scope
+        let runtime = Runtime::current();
+        let navigator = navigator();
+        let target = document.clone();
+        let listener =
+            Closure::<dyn FnMut(web_sys::MouseEvent)>::new(move |event: web_sys::MouseEvent| {
+                let Some((link, route)) = followed_route(&event) else {
+                    return;
+                };
+                // Handled here, so the router's own link handler never sees it
+                event.prevent_default();
+                event.stop_propagation();
+
+                {
+                    let mut pending = pending.borrow_mut();
+                    for title in pending.marked.drain(..) {
+                        let _ = title.style().remove_property("view-transition-name");
+                    }
+                    if let Some(title) = name_title(&link) {
+                        pending.marked.push(title);
+                    }
+                    pending.from = current_path();
+                }
+
+                let update = {
+                    let pending = pending.clone();
+                    let runtime = runtime.clone();
+                    let route = route.clone();
+                    Closure::once_into_js(move || -> JsValue {
+                        let rendered = js_sys::Promise::new(&mut |resolve, _reject| {
+                            pending.borrow_mut().rendered = Some(resolve);
+                        });
+                        runtime.in_scope(ScopeId::ROOT, || {
+                            navigator.push(route);
+                        });
+                        rendered.into()
+                    })
+                };
+                if start.call1(&target, &update).is_err() {
+                    pending.borrow_mut().rendered = None;
+                    runtime.in_scope(ScopeId::ROOT, || {
+                        navigator.push(route);
+                    });
+                }
+            });
+
+        // The capture phase on the document comes before the router's handler on the app's root element
+        let _ = document.add_event_listener_with_callback_and_bool(
+            "click",
+            listener.as_ref().unchecked_ref(),
+            true,
+        );
+        listener.forget();
+    }
+
+    /// The link a plain click followed and the SPA route it leads to. Clicks that open a new tab or window, links to
+    /// other origins, downloads and links within the current page are left to the browser and the router.
+    pub(crate) fn followed_route(
+        event: &web_sys::MouseEvent,
+    ) -> Option<(web_sys::HtmlAnchorElement, Route)> {
+        if event.default_prevented()
+            || event.button() != 0
+            || event.meta_key()
+            || event.ctrl_key()
+            || event.shift_key()
+            || event.alt_key()
+        {
+            return None;
+        }
+        let link = event
+            .target()?
+            .dyn_into::<web_sys::Element>()
+            .ok()?
+            .closest("a[href]")
+            .ok()??
+            .dyn_into::<web_sys::HtmlAnchorElement>()
+            .ok()?;
+        let location = web_sys::window()?.location();
+        if !link.target().is_empty()
+            || link.has_attribute("download")
+            || link.origin() != location.origin().ok()?
+            || link.pathname() == location.pathname().ok()?
+        {
+            return None;
+        }
+        let route = Route::from_str(&format!("{}{}", link.pathname(), link.search())).ok()?;
+        Some((link, route))
+    }
+
+    /// Name the marked title containing `link`, if there is one.
+    fn name_title(link: &web_sys::HtmlAnchorElement) -> Option<web_sys::HtmlElement> {
+        let selector = format!("[{}]", TRANSITION_ATTRIBUTE);
+        let title = link
+            .closest(&selector)
+            .ok()??
+            .dyn_into::<web_sys::HtmlElement>()
+            .ok()?;
+        name(&title)
+    }
+
+    /// Name the marked title on the new page that links to `path`, the page the navigation started on.
+    fn marked_title(path: &str) -> Option<web_sys::HtmlElement> {
+        let document = web_sys::window()?.document()?;
+        let titles = document
+            .query_selector_all(&format!("[{}]", TRANSITION_ATTRIBUTE))
+            .ok()?;
+        (0..titles.length())
+            .filter_map(|index| titles.item(index))
+            .filter_map(|node| node.dyn_into::<web_sys::HtmlElement>().ok())
+            .find(|title| {
+                title
+                    .query_selector("a[href]")
+                    .ok()
+                    .flatten()
+                    .and_then(|link| link.dyn_into::<web_sys::HtmlAnchorElement>().ok())
+                    .is_some_and(|link| link.pathname() == path)
+            })
+            .and_then(|title| name(&title))
+    }
+
+    fn name(title: &web_sys::HtmlElement) -> Option<web_sys::HtmlElement> {
+        let name = title.get_attribute(TRANSITION_ATTRIBUTE)?;
+        title
+            .style()
+            .set_property("view-transition-name", &name)
+            .ok()?;
+        Some(title.clone())
+    }
+
+    fn current_path() -> String {
+        web_sys::window()
+            .and_then(|window| window.location().pathname().ok())
+            .unwrap_or_default()
+    }
+}
@@ -1,77 +1,38 @@
+use crate::components::{Heading, Img, ImgLayout, Prose};
+use crate::data::ABOUT;
+use crate::styles::{self, ScopedStylesheet};
 use dioxus::prelude::*;
-use serde::{Deserialize, Serialize};
-
-const ABOUT_DATA: &str = include_str!("../../assets/data/about.json");
-const ABOUT_CSS: Asset = asset!("/assets/styling/about.css");
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-struct AboutData {
-    name: String,
-    title: String,
-    location: String,
-    bio: String,
-    skills: Vec<String>,
-    experience: Vec<Experience>,
-    interests: Vec<String>,
-    contact: Contact,
-    updated: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-struct Experience {
-    company: String,
-    position: String,
-    duration: String,
-    description: String,
-}
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-struct Contact {
-    email: String,
-    website: String,
-    github: String,
-}
 
 #[component]
 pub fn About() -> Element {
-    // Parse the JSON data at compile time
-    let about_data = use_memo(move || {
-        serde_json::from_str::<AboutData>(ABOUT_DATA).unwrap_or_else(|e| {
-            eprintln!("Failed to parse about.json: {}", e);
-            AboutData {
-                name: "Error Loading Data".to_string(),
-                title: "".to_string(),
-                location: "".to_string(),
-                bio: "Failed to load about information.".to_string(),
-                skills: vec![],
-                experience: vec![],
-                interests: vec![],
-                contact: Contact {
-                    email: "".to_string(),
-                    website: "".to_string(),
-                    github: "".to_string(),
-                },
-                updated: "".to_string(),
-            }
-        })
-    });
-
-    let data = about_data();
+    // Compiled in from about.json by build.rs
+    let data = &ABOUT;
 
     rsx! {
-        document::Link { rel: "stylesheet", href: ABOUT_CSS }
+        ScopedStylesheet { style: &styles::ABOUT }
 
         div {
-            class: "about-container",
+            class: "{styles::ABOUT.scope} about-container",
 
             // Header Section
             header {
                 class: "about-header",
-                h1 {
-                    class: "about-name",
-                    "{data.name}"
+                if let Some(photo) = data.photo {
+                    // Above the fold, so not deferred
+                    Img {
+                        src: photo.src,
+                        alt: photo.alt,
+                        width: photo.image().width,
+                        height: photo.image().height,
+                        placeholder: photo.image().placeholder.to_string(),
+                        srcset: photo.image().srcset(),
+                        class: "about-photo",
+                        layout: ImgLayout::Card,
+                        eager: true,
+                    }
                 }
-                h2 {
+                Heading { level: 1, class: "about-name", "{data.name}" }
+                p {
                     class: "about-title",
                     "{data.title}"
                 }
@@ -84,23 +45,19 @@ pub fn About() -> Element {
             // Bio Section
             section {
                 class: "about-bio-section",
-                h3 {
-                    class: "about-section-title",
-                    "About Me"
-                }
-                p {
-                    class: "about-bio-text",
-                    "{data.bio}"
+                Heading { level: 2, id: "About Me", class: "about-section-title", "About Me" }
+                Prose {
+                    p {
+                        class: "about-bio-text",
+                        "{data.bio}"
+                    }
                 }
             }
 
             // Skills Section
             section {
                 class: "about-section",
-                h3 {
-                    class: "about-section-title",
-                    "Skills"
-                }
+                Heading { level: 2, id: "Skills", class: "about-section-title", "Skills" }
                 div {
                     class: "skills-grid",
                     for skill in data.skills {
@@ -116,10 +73,7 @@ pub fn About() -> Element {
             // Experience Section
             section {
                 class: "about-section",
-                h3 {
-                    class: "about-section-title",
-                    "Experience"
-                }
+                Heading { level: 2, id: "Experience", class: "about-section-title", "Experience" }
                 for exp in data.experience {
                     div {
                         key: "{exp.company}-{exp.position}",
@@ -127,10 +81,7 @@ pub fn About() -> Element {
                         div {
                             class: "experience-header",
                             div {
-                                h4 {
-                                    class: "experience-position",
-                                    "{exp.position}"
-                                }
+                                Heading { level: 3, class: "experience-position", "{exp.position}" }
                                 p {
                                     class: "experience-company",
                                     "{exp.company}"
@@ -152,10 +103,7 @@ pub fn About() -> Element {
             // Interests Section
             section {
                 class: "about-section",
-                h3 {
-                    class: "about-section-title",
-                    "Interests"
-                }
+                Heading { level: 2, id: "Interests", class: "about-section-title", "Interests" }
                 div {
                     class: "interests-grid",
                     for interest in data.interests {
@@ -169,13 +117,39 @@ pub fn About() -> Element {
                 }
             }
 
+            // Gallery Section
+            if !data.gallery.is_empty() {
+                section {
+                    class: "about-section",
+                    Heading { level: 2, id: "Photos", class: "about-section-title", "Photos" }
+                    div {
+                        class: "about-gallery",
+                        for photo in data.gallery {
+                            figure {
+                                key: "{photo.src}",
+                                Img {
+                                    src: photo.src,
+                                    alt: photo.alt,
+                                    width: photo.image().width,
+                                    height: photo.image().height,
+                                    placeholder: photo.image().placeholder.to_string(),
+                                    srcset: photo.image().srcset(),
+                                    class: "about-gallery-image",
+                                    layout: ImgLayout::Card,
+                                }
+                                if let Some(caption) = photo.caption {
+                                    figcaption { "{caption}" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             // Contact Section
             section {
                 class: "contact-section",
-                h3 {
-                    class: "about-section-title",
-                    "Contact"
-                }
+                Heading { level: 2, id: "Contact", class: "about-section-title", "Contact" }
                 div {
                     class: "contact-grid",
 
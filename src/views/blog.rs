@@ -1,38 +1,232 @@
+use crate::api::reactions;
+use crate::components::{
+    Heading, Img, ImgLayout, NarrationPlayer, OnThisDay, PostBody, Reactions, ReadingProgress,
+    SaveButton,
+};
+use crate::license::LicenseConfig;
+use crate::posts::{self, Status};
+use crate::reading_progress::ReadingProgressConfig;
+use crate::source::SourceConfig;
+use crate::styles::{self, ScopedStylesheet};
+use crate::view_transitions;
 use crate::Route;
 use dioxus::prelude::*;
 
-const BLOG_CSS: Asset = asset!("/assets/styling/blog.css");
+/// The blog index rendered for `[Route::BlogIndex]`: every published post with its summary, or an excerpt when it has
+/// none, and teaser cards for upcoming posts, from the same [`posts::index`] as `/posts.json`.
+#[component]
+pub fn BlogIndex() -> Element {
+    let index = posts::index(posts::today());
+
+    rsx! {
+        ScopedStylesheet { style: &styles::BLOG }
+
+        div {
+            id: "blog",
+            class: styles::BLOG.scope,
+            Heading { level: 1, "Blog" }
+            OnThisDay {}
+            ul {
+                class: "blog-index",
+                for post in index {
+                    if !post.coming_soon {
+                        li {
+                            key: "{post.slug}",
+                            class: "blog-index-entry",
+                            if let Some(cover) = posts::find(&post.slug).and_then(|post| post.cover) {
+                                Img {
+                                    src: cover.src,
+                                    alt: cover.alt,
+                                    width: cover.image().width,
+                                    height: cover.image().height,
+                                    placeholder: cover.image().placeholder.to_string(),
+                                    srcset: cover.image().srcset(),
+                                    class: "blog-cover blog-cover-card",
+                                    layout: ImgLayout::Card,
+                                }
+                            }
+                            Heading {
+                                level: 2,
+                                view_transition: view_transitions::POST_TITLE,
+                                Link {
+                                    to: Route::Blog { slug: post.slug.clone() },
+                                    "{post.title}"
+                                }
+                            }
+                            p { class: "blog-date", "{post.date_label()} · {post.reading_minutes} min read" }
+                            p { "{post.summary}" }
+                        }
+                    } else {
+                        li {
+                            key: "{post.slug}",
+                            class: "blog-index-entry coming-soon",
+                            Heading { level: 2, "{post.title}" }
+                            p {
+                                class: "blog-date",
+                                span { class: "blog-badge", "Coming soon" }
+                                " {post.date_label()}"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
 
 /// The Blog page component that will be rendered when the current route is `[Route::Blog]`
 ///
-/// The component takes a `id` prop of type `i32` from the route enum. Whenever the id changes, the component function will be
-/// re-run and the rendered HTML will be updated.
+/// The component takes a `slug` prop from the route enum. Whenever the slug changes, the component function will be
+/// re-run and the rendered HTML will be updated. Old numeric URLs (`/blog/1`) are redirected to the post's slug by the
+/// router before they get here.
 #[component]
-pub fn Blog(id: i32) -> Element {
+pub fn Blog(slug: String) -> Element {
+    let today = posts::today();
+
+    let Some(post) = posts::find(&slug).filter(|post| post.status(today) == Status::Published)
+    else {
+        if let Some(post) =
+            posts::find(&slug).filter(|post| post.status(today) == Status::ComingSoon)
+        {
+            return rsx! {
+                ScopedStylesheet { style: &styles::BLOG }
+                div {
+                    id: "blog",
+                    class: styles::BLOG.scope,
+                    Heading { level: 1, "{post.title}" }
+                    p {
+                        class: "blog-date",
+                        span { class: "blog-badge", "Coming soon" }
+                        " This post will be published on {post.date_label()}."
+                    }
+                    Link { to: Route::BlogIndex {}, "← All posts" }
+                }
+            };
+        }
+
+        return rsx! {
+            ScopedStylesheet { style: &styles::BLOG }
+            div {
+                id: "blog",
+                class: styles::BLOG.scope,
+                Heading { level: 1, "Post not found" }
+                p { "There is no blog post at this address." }
+                Link { to: Route::Home {}, "← Back to Home" }
+            }
+        };
+    };
+    let published = posts::published(today);
+    let (previous, next) = posts::neighbours(&published, post);
+    let markdown_path = post.markdown_path(SourceConfig::compiled());
+    let edit_url = post.edit_url(SourceConfig::compiled());
+    let license = post.license(LicenseConfig::compiled());
+
     rsx! {
-        document::Link { rel: "stylesheet", href: BLOG_CSS }
+        ScopedStylesheet { style: &styles::BLOG }
+
+        if ReadingProgressConfig::compiled().shows_for(post) {
+            ReadingProgress {}
+        }
 
         div {
             id: "blog",
+            class: styles::BLOG.scope,
 
             // Content
-            h1 { "This is blog #{id}!" }
-            p { "In blog #{id}, we show how the Dioxus router works and how URL parameters can be passed as props to our route components." }
+            Heading { level: 1, class: view_transitions::POST_TITLE, "{post.title}" }
+            p { class: "blog-date", "{post.date_label()}" }
+            if let Some(cover) = post.cover {
+                // Above the fold, so not deferred
+                Img {
+                    src: cover.src,
+                    alt: cover.alt,
+                    width: cover.image().width,
+                    height: cover.image().height,
+                    placeholder: cover.image().placeholder.to_string(),
+                    srcset: cover.image().srcset(),
+                    class: "blog-cover",
+                    eager: true,
+                }
+            }
+            if let (Some(url), Some(site)) = (post.canonical_url, post.original_site()) {
+                p {
+                    class: "blog-original",
+                    "Originally published at "
+                    a { href: url, "{site}" }
+                    "."
+                }
+            }
+            if !post.updates.is_empty() {
+                aside {
+                    class: "blog-updates",
+                    aria_labelledby: "post-updates",
+                    h2 { id: "post-updates", class: "blog-updates-title", "Updates" }
+                    ul {
+                        for update in post.updates_newest_first() {
+                            li {
+                                key: "{update.date}",
+                                time { datetime: "{update.date}", "{update.date_label()}" }
+                                ": {update.note}"
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(audio) = post.audio {
+                NarrationPlayer { src: audio, media_type: post.audio_type().map(str::to_string) }
+            }
+            SaveButton { post: post.slug.to_string() }
+            PostBody { body: post.body }
+            if reactions::enabled() {
+                Reactions { post: post.slug.to_string() }
+            }
 
             // Navigation links
             // The `Link` component lets us link to other routes inside our app. It takes a `to` prop of type `Route` and
-            // any number of child nodes.
-            Link {
-                // The `to` prop is the route that the link should navigate to. We can use the `Route` enum to link to the
-                // blog page with the id of -1. Since we are using an enum instead of a string, all of the routes will be checked
-                // at compile time to make sure they are valid.
-                to: Route::Blog { id: id - 1 },
-                "Previous"
-            }
-            span { " <---> " }
-            Link {
-                to: Route::Blog { id: id + 1 },
-                "Next"
+            // any number of child nodes. Since we are using an enum instead of a string, all of the routes will be checked
+            // at compile time to make sure they are valid.
+            if let Some(previous) = previous {
+                Link {
+                    to: Route::Blog { slug: previous.slug.to_string() },
+                    "Previous"
+                }
+            }
+            if previous.is_some() && next.is_some() {
+                span { " <---> " }
+            }
+            if let Some(next) = next {
+                Link {
+                    to: Route::Blog { slug: next.slug.to_string() },
+                    "Next"
+                }
+            }
+
+            footer {
+                class: "blog-footer",
+                p {
+                    class: "blog-license",
+                    if let Some(url) = license.url() {
+                        a { class: "blog-license-badge", rel: "license", href: url, "{license.name()}" }
+                    } else {
+                        span { class: "blog-license-badge", "{license.name()}" }
+                    }
+                    " {post.license_notice(LicenseConfig::compiled())}"
+                }
+                if markdown_path.is_some() || edit_url.is_some() {
+                    p {
+                        class: "blog-links",
+                        // A plain link: the markdown is a file next to the generated page, not a route
+                        if let Some(markdown_path) = &markdown_path {
+                            a { href: "{markdown_path}", r#type: "text/markdown", "View source" }
+                        }
+                        if markdown_path.is_some() && edit_url.is_some() {
+                            " · "
+                        }
+                        if let Some(edit_url) = &edit_url {
+                            a { href: "{edit_url}", rel: "noopener", "Suggest an edit on GitHub" }
+                        }
+                    }
+                }
             }
         }
     }
@@ -0,0 +1,336 @@
+use crate::api::reactions::{Reaction, ReactionCounts};
+use crate::components::{
+    use_sample_form, Callout, ContactFields, Heading, Hero, Note, Reactions, SaveButton, Tip,
+    Warning,
+};
+use crate::contact_form::FormSchema;
+use crate::data::Project;
+use crate::styles::{self, ScopedStylesheet, Stylesheet};
+use crate::theme::Theme;
+use crate::views::ProjectCard;
+use crate::Route;
+use dioxus::prelude::*;
+
+const PREVIEWS_CSS: Asset = asset!("/assets/styling/previews.css");
+
+/// Component previews are part of development builds (`dx serve`) only, like the drafts; release builds show a notice
+/// instead.
+pub const PREVIEWS_ENABLED: bool = cfg!(debug_assertions);
+
+/// A component shown on the previews page, with each of its variants.
+pub struct Preview {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub variants: &'static [Variant],
+}
+
+/// One way of rendering a component: sample props, a state, a modifier.
+pub struct Variant {
+    pub name: &'static str,
+    /// Renders the variant. It's called from its own component, so it can use hooks.
+    pub render: fn() -> Element,
+}
+
+/// Backgrounds every variant is shown on: the dark page chrome and the light content cards.
+const THEME_MODES: [(&str, &str); 2] = [("Dark", "preview-dark"), ("Light", "preview-light")];
+
+/// Every previewed component, in page order. A new component gets an entry here, with a variant for each state worth
+/// looking at.
+pub static PREVIEWS: &[Preview] = &[
+    Preview {
+        name: "Heading",
+        description: "Page and section headings, with the heading scale from theme.toml.",
+        variants: &[
+            Variant {
+                name: "Level 1",
+                render: || rsx! { Heading { level: 1, "The quick brown fox" } },
+            },
+            Variant {
+                name: "Level 2",
+                render: || rsx! { Heading { level: 2, "The quick brown fox" } },
+            },
+            Variant {
+                name: "Level 3",
+                render: || rsx! { Heading { level: 3, "The quick brown fox" } },
+            },
+            Variant {
+                name: "Level 4",
+                render: || rsx! { Heading { level: 4, "The quick brown fox" } },
+            },
+        ],
+    },
+    Preview {
+        name: "Callout",
+        description: "Notes, tips and warnings in posts and views.",
+        variants: &[
+            Variant {
+                name: "Note",
+                render: || rsx! { Note { "Posts are compiled into the app." } },
+            },
+            Variant {
+                name: "Tip",
+                render: || rsx! { Tip { "Pick a technology to filter the projects." } },
+            },
+            Variant {
+                name: "Warning",
+                render: || rsx! { Warning { "This removes every saved post." } },
+            },
+            Variant {
+                name: "With a title",
+                render: || {
+                    rsx! {
+                        Callout {
+                            kind: crate::callouts::CalloutKind::Note,
+                            title: "Heads up",
+                            "A callout with its own title."
+                        }
+                    }
+                },
+            },
+        ],
+    },
+    Preview {
+        name: "Hero",
+        description: "The banner on the home page.",
+        variants: &[Variant {
+            name: "Default",
+            render: || rsx! { Hero {} },
+        }],
+    },
+    Preview {
+        name: "Project card",
+        description: "A project in the grid on /projects.",
+        variants: &[
+            Variant {
+                name: "With tags",
+                render: || project_card(&SAMPLE_PROJECT),
+            },
+            Variant {
+                name: "Without tags",
+                render: || project_card(&UNTAGGED_PROJECT),
+            },
+        ],
+    },
+    Preview {
+        name: "Contact form",
+        description: "The fields of forms/contact.toml, in each state of the form.",
+        variants: &[
+            Variant {
+                name: "Empty",
+                render: || contact_form(&[], false),
+            },
+            Variant {
+                name: "Filled in",
+                render: || contact_form(SAMPLE_MESSAGE, false),
+            },
+            Variant {
+                name: "With errors",
+                render: || contact_form(&[("name", "A"), ("email", "not-an-email")], true),
+            },
+        ],
+    },
+    Preview {
+        name: "Buttons",
+        description: "Form buttons from the contact stylesheet.",
+        variants: &[Variant {
+            name: "Primary, disabled and secondary",
+            render: || {
+                rsx! {
+                    ScopedStylesheet { style: &styles::CONTACT }
+                    div {
+                        class: "{styles::CONTACT.scope} form-actions",
+                        button { r#type: "button", class: "btn btn-primary", "Send Message ✨" }
+                        button { r#type: "button", class: "btn btn-primary disabled", disabled: true, "Send Message ✨" }
+                        button { r#type: "button", class: "btn btn-secondary", "Reset Form" }
+                    }
+                }
+            },
+        }],
+    },
+    Preview {
+        name: "Save button",
+        description: "Saves a post to read later. Clicking it here saves the post for real.",
+        variants: &[Variant {
+            name: "Default",
+            render: || rsx! { SaveButton { post: "component-preview" } },
+        }],
+    },
+    Preview {
+        name: "Reactions",
+        description:
+            "The buttons under a post. The counts are replaced by the server's once it answers.",
+        variants: &[
+            Variant {
+                name: "No reactions yet",
+                render: || rsx! { Reactions { post: "component-preview" } },
+            },
+            Variant {
+                name: "With counts",
+                render: || {
+                    let counts =
+                        ReactionCounts(Reaction::ALL.into_iter().zip([12, 5, 1]).collect());
+                    rsx! { Reactions { post: "component-preview", counts } }
+                },
+            },
+        ],
+    },
+];
+
+static SAMPLE_PROJECT: Project = Project {
+    name: "Sample Project",
+    description:
+        "A project card with a description long enough to wrap onto a second line in the grid.",
+    url: "#",
+    tags: &["Rust", "Dioxus", "WebAssembly"],
+};
+
+static UNTAGGED_PROJECT: Project = Project {
+    name: "Untagged Project",
+    description: "A project without any technologies listed.",
+    url: "#",
+    tags: &[],
+};
+
+const SAMPLE_MESSAGE: &[(&str, &str)] = &[
+    ("name", "Ada Lovelace"),
+    ("email", "ada@example.com"),
+    ("subject", "Hello"),
+    (
+        "message",
+        "I read your post about static pages with islands and have a question.",
+    ),
+];
+
+fn project_card(project: &'static Project) -> Element {
+    rsx! {
+        ScopedStylesheet { style: &styles::PROJECTS }
+        div {
+            class: styles::PROJECTS.scope,
+            ul { class: "project-grid", ProjectCard { project } }
+        }
+    }
+}
+
+fn contact_form(values: &'static [(&'static str, &'static str)], show_errors: bool) -> Element {
+    let form = use_sample_form(FormSchema::contact(), values);
+    let problems = if show_errors {
+        form.request().validate()
+    } else {
+        Vec::new()
+    };
+
+    rsx! {
+        ScopedStylesheet { style: &styles::CONTACT }
+        form {
+            class: "{styles::CONTACT.scope} contact-form",
+            onsubmit: move |e| e.prevent_default(),
+            if !problems.is_empty() {
+                div {
+                    class: "validation-errors",
+                    h4 { "Please fix the following errors:" }
+                    ul {
+                        for problem in problems {
+                            li { "{problem}" }
+                        }
+                    }
+                }
+            }
+            ContactFields { form, show_errors }
+        }
+    }
+}
+
+/// A living style guide for `[Route::Components]`: every component in [`PREVIEWS`] in each of its variants, each on
+/// the dark and the light background, followed by the colors of the theme. Under `dx serve` an edit to a component
+/// reloads it here, next to its siblings, so a visual regression in any variant shows at a glance.
+#[component]
+pub fn Components() -> Element {
+    if !PREVIEWS_ENABLED {
+        return rsx! {
+            ScopedStylesheet { style: &styles::BLOG }
+            div {
+                id: "blog",
+                class: styles::BLOG.scope,
+                Heading { level: 1, "Components" }
+                p { "Component previews are only available in development builds." }
+                Link { to: Route::Home {}, "← Home" }
+            }
+        };
+    }
+
+    let colors = use_hook(|| Theme::load().map(|theme| theme.colors).unwrap_or_default());
+
+    rsx! {
+        Stylesheet { href: PREVIEWS_CSS }
+
+        div {
+            class: "previews",
+            Heading { level: 1, "Components" }
+            nav {
+                class: "previews-index",
+                aria_label: "Components",
+                for preview in PREVIEWS {
+                    a { key: "{preview.name}", href: "#{anchor(preview.name)}", "{preview.name}" }
+                }
+                a { href: "#theme-colors", "Theme colors" }
+            }
+
+            for (preview_index, preview) in PREVIEWS.iter().enumerate() {
+                section {
+                    key: "{preview.name}",
+                    id: anchor(preview.name),
+                    class: "preview",
+                    Heading { level: 2, "{preview.name}" }
+                    p { class: "preview-description", "{preview.description}" }
+                    for (variant_index, variant) in preview.variants.iter().enumerate() {
+                        div {
+                            key: "{variant.name}",
+                            class: "preview-variant",
+                            Heading { level: 3, "{variant.name}" }
+                            div {
+                                class: "preview-modes",
+                                for (mode, class) in THEME_MODES {
+                                    figure {
+                                        key: "{mode}",
+                                        class: "preview-frame {class}",
+                                        figcaption { "{mode}" }
+                                        VariantFrame { preview: preview_index, variant: variant_index }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            section {
+                id: "theme-colors",
+                class: "preview",
+                Heading { level: 2, "Theme colors" }
+                p { class: "preview-description", "The [colors] of theme.toml, as --color-<name>." }
+                ul {
+                    class: "preview-swatches",
+                    for (name, value) in colors {
+                        li {
+                            key: "{name}",
+                            span { class: "preview-swatch", style: "background: var(--color-{name})" }
+                            code { "--color-{name}" }
+                            " {value}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Variant `variant` of `PREVIEWS[preview]` in its own scope, so its hooks stay apart from the other variants'.
+#[component]
+fn VariantFrame(preview: usize, variant: usize) -> Element {
+    (PREVIEWS[preview].variants[variant].render)()
+}
+
+/// Fragment id of a component's section.
+fn anchor(name: &str) -> String {
+    format!("preview-{}", name.to_lowercase().replace(' ', "-"))
+}
@@ -1,21 +1,33 @@
+use crate::api::{ContactRequest, ValidationProblem};
+use crate::components::{use_contact_form, ContactFields};
+use crate::contact_delivery::{self, use_contact_delivery, DeliveryStatus, MAX_ATTEMPTS};
+use crate::contact_form::FormSchema;
+use crate::contact_history::{self, Submission};
+use crate::contact_reasons;
+use crate::data::ABOUT;
+use crate::styles::{self, ScopedStylesheet};
 use dioxus::prelude::*;
-use serde::{Deserialize, Serialize};
 
-const CONTACT_CSS: Asset = asset!("/assets/styling/contact.css");
-
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-struct FormData {
-    name: String,
-    email: String,
-    subject: String,
-    message: String,
-    submitted_at: String,
+/// Where the WASM island sends a submission as JSON once it has validated it (see
+/// [`contact_delivery`](crate::contact_delivery)). The contact backend answers a plain form post with a redirect to
+/// [`CONTACT_THANKS_PATH`], but until it's deployed the forms don't post here without JavaScript: see
+/// [`fallback_form_action`].
+pub const CONTACT_ENDPOINT: &str = "/api/contact";
+
+/// Where the forms go without JavaScript: the visitor's mail app, with the fields as the body (`enctype="text/plain"`)
+/// of a message to the site's address. Static hosts reject a POST to [`CONTACT_ENDPOINT`], which would lose the
+/// message.
+pub fn fallback_form_action() -> String {
+    format!("mailto:{}", ABOUT.contact.email)
 }
 
+/// Page the server redirects to after accepting a submission.
+pub const CONTACT_THANKS_PATH: &str = "/contact/thanks/";
+
 #[derive(Debug, Clone, PartialEq)]
 enum FormState {
     Editing,
-    Submitted(FormData),
+    Submitted(Submission),
     Error(String),
 }
 
@@ -23,42 +35,23 @@ enum FormState {
 pub fn Contact() -> Element {
     // Form state management
     let mut form_state = use_signal(|| FormState::Editing);
-    let mut name = use_signal(|| String::new());
-    let mut email = use_signal(|| String::new());
-    let mut subject = use_signal(|| String::new());
-    let mut message = use_signal(|| String::new());
+    // Every field in forms/contact.toml, prefilled from the URL
+    let mut form = use_contact_form(FormSchema::contact());
 
     // Validation state
-    let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut validation_errors = use_signal(Vec::<ValidationProblem>::new);
 
-    // Calculate if form is valid
-    let is_valid = use_memo(move || {
-        !name().trim().is_empty()
-            && !email().trim().is_empty()
-            && !subject().trim().is_empty()
-            && !message().trim().is_empty()
-            && email().contains('@')
-    });
+    // Earlier submissions from this browser, read once mounted so hydration matches the generated page
+    let mut history = use_signal(Vec::new);
+    use_effect(move || history.set(contact_history::load()));
+
+    // The submission as the server will receive it, validated with the same rules
+    let request = use_memo(move || form.request());
+    let is_valid = use_memo(move || request().validate().is_empty());
 
     // Form submission handler
     let mut handle_submit = move |_| {
-        let mut errors = Vec::new();
-
-        // Validate form
-        if name().trim().is_empty() {
-            errors.push("Name is required".to_string());
-        }
-        if email().trim().is_empty() {
-            errors.push("Email is required".to_string());
-        } else if !email().contains('@') {
-            errors.push("Please enter a valid email address".to_string());
-        }
-        if subject().trim().is_empty() {
-            errors.push("Subject is required".to_string());
-        }
-        if message().trim().is_empty() {
-            errors.push("Message is required".to_string());
-        }
+        let errors = request().validate();
 
         if !errors.is_empty() {
             validation_errors.set(errors);
@@ -66,45 +59,26 @@ pub fn Contact() -> Element {
             return;
         }
 
-        // Simulate form processing
-        let form_data = FormData {
-            name: name().clone(),
-            email: email().clone(),
-            subject: subject().clone(),
-            message: message().clone(),
-            submitted_at: chrono::Utc::now()
-                .format("%Y-%m-%d %H:%M:%S UTC")
-                .to_string(),
-        };
-
-        // Save to localStorage (browser-only feature)
-        #[cfg(feature = "web")]
-        {
-            if let Ok(json) = serde_json::to_string(&form_data) {
-                let window = web_sys::window().unwrap();
-                let storage = window.local_storage().unwrap().unwrap();
-                let _ = storage.set_item("last_contact_submission", &json);
-            }
-        }
+        // Simulate form processing, keeping the submission in the browser's history
+        let submission = Submission::new(&request());
+        history.set(contact_history::record(submission.clone()));
+        form.mark_submitted(request());
 
         validation_errors.set(Vec::new());
-        form_state.set(FormState::Submitted(form_data));
+        form_state.set(FormState::Submitted(submission));
     };
 
     let reset_form = move |_| {
-        name.set(String::new());
-        email.set(String::new());
-        subject.set(String::new());
-        message.set(String::new());
+        form.clear();
         validation_errors.set(Vec::new());
         form_state.set(FormState::Editing);
     };
 
     rsx! {
-        document::Link { rel: "stylesheet", href: CONTACT_CSS }
+        ScopedStylesheet { style: &styles::CONTACT }
 
         div {
-            class: "contact-container",
+            class: "{styles::CONTACT.scope} contact-container",
 
             header {
                 class: "contact-header",
@@ -130,9 +104,9 @@ pub fn Contact() -> Element {
                             div {
                                 h3 { "Email" }
                                 a {
-                                    href: "mailto:marcin.sydor@sky.uk",
+                                    href: "mailto:{ABOUT.contact.email}",
                                     class: "contact-link",
-                                    "marcin.sydor@sky.uk"
+                                    "{ABOUT.contact.email}"
                                 }
                             }
                         }
@@ -157,6 +131,18 @@ pub fn Contact() -> Element {
                                 }
                             }
                         }
+                        div {
+                            class: "contact-method",
+                            span { class: "contact-icon", "🎤" }
+                            div {
+                                h3 { "Speaking" }
+                                Link {
+                                    to: crate::Route::Speak {},
+                                    class: "contact-link",
+                                    "Invite me to your event"
+                                }
+                            }
+                        }
                     }
                 }
 
@@ -226,6 +212,10 @@ pub fn Contact() -> Element {
                                         strong { "Email: " }
                                         span { "{data.email}" }
                                     }
+                                    div { class: "data-item",
+                                        strong { "Reason: " }
+                                        span { "{reason_label(&data.reason)}" }
+                                    }
                                     div { class: "data-item",
                                         strong { "Subject: " }
                                         span { "{data.subject}" }
@@ -234,6 +224,12 @@ pub fn Contact() -> Element {
                                         strong { "Message: " }
                                         span { "{data.message}" }
                                     }
+                                    if let Some(phone) = &data.phone {
+                                        div { class: "data-item",
+                                            strong { "Phone: " }
+                                            span { "{phone}" }
+                                        }
+                                    }
                                     div { class: "data-item",
                                         strong { "Submitted: " }
                                         span { "{data.submitted_at}" }
@@ -292,75 +288,7 @@ pub fn Contact() -> Element {
                                     }
                                 }
 
-                                div {
-                                    class: "form-row",
-                                    div {
-                                        class: "form-group",
-                                        label { "for": "name", "Name *" }
-                                        input {
-                                            "type": "text",
-                                            id: "name",
-                                            class: if name().trim().is_empty() && matches!(form_state(), FormState::Error(_)) {
-                                                "form-input error"
-                                            } else {
-                                                "form-input"
-                                            },
-                                            placeholder: "Your full name",
-                                            value: "{name}",
-                                            oninput: move |e| name.set(e.value())
-                                        }
-                                    }
-                                    div {
-                                        class: "form-group",
-                                        label { "for": "email", "Email *" }
-                                        input {
-                                            "type": "email",
-                                            id: "email",
-                                            class: if (email().trim().is_empty() || !email().contains('@')) && matches!(form_state(), FormState::Error(_)) {
-                                                "form-input error"
-                                            } else {
-                                                "form-input"
-                                            },
-                                            placeholder: "your.email@example.com",
-                                            value: "{email}",
-                                            oninput: move |e| email.set(e.value())
-                                        }
-                                    }
-                                }
-
-                                div {
-                                    class: "form-group",
-                                    label { "for": "subject", "Subject *" }
-                                    input {
-                                        "type": "text",
-                                        id: "subject",
-                                        class: if subject().trim().is_empty() && matches!(form_state(), FormState::Error(_)) {
-                                            "form-input error"
-                                        } else {
-                                            "form-input"
-                                        },
-                                        placeholder: "What's this about?",
-                                        value: "{subject}",
-                                        oninput: move |e| subject.set(e.value())
-                                    }
-                                }
-
-                                div {
-                                    class: "form-group",
-                                    label { "for": "message", "Message *" }
-                                    textarea {
-                                        id: "message",
-                                        class: if message().trim().is_empty() && matches!(form_state(), FormState::Error(_)) {
-                                            "form-textarea error"
-                                        } else {
-                                            "form-textarea"
-                                        },
-                                        placeholder: "Tell me what's on your mind...",
-                                        rows: "6",
-                                        value: "{message}",
-                                        oninput: move |e| message.set(e.value())
-                                    }
-                                }
+                                ContactFields { form, show_errors: matches!(form_state(), FormState::Error(_)) }
 
                                 div {
                                     class: "form-actions",
@@ -388,6 +316,8 @@ pub fn Contact() -> Element {
                             }
                         }
                     }
+
+                    PreviousMessages { history }
                 }
             }
 
@@ -445,42 +375,26 @@ pub fn Contact() -> Element {
 pub fn ContactFormOnly() -> Element {
     // Form state management
     let mut form_state = use_signal(|| FormState::Editing);
-    let mut name = use_signal(|| String::new());
-    let mut email = use_signal(|| String::new());
-    let mut subject = use_signal(|| String::new());
-    let mut message = use_signal(|| String::new());
+    // Every field in forms/contact.toml, prefilled from the URL
+    let mut form = use_contact_form(FormSchema::contact());
 
     // Validation state
-    let mut validation_errors = use_signal(|| Vec::<String>::new());
+    let mut validation_errors = use_signal(Vec::<ValidationProblem>::new);
 
-    // Calculate if form is valid
-    let is_valid = use_memo(move || {
-        !name().trim().is_empty()
-            && !email().trim().is_empty()
-            && !subject().trim().is_empty()
-            && !message().trim().is_empty()
-            && email().contains('@')
-    });
+    // Earlier submissions from this browser, read once mounted so hydration matches the generated page
+    let mut history = use_signal(Vec::new);
+    use_effect(move || history.set(contact_history::load()));
 
-    // Form submission handler
-    let mut handle_submit = move |_| {
-        let mut errors = Vec::new();
+    // The submission as the server will receive it, validated with the same rules
+    let request = use_memo(move || form.request());
+    let is_valid = use_memo(move || request().validate().is_empty());
 
-        // Validate form
-        if name().trim().is_empty() {
-            errors.push("Name is required".to_string());
-        }
-        if email().trim().is_empty() {
-            errors.push("Email is required".to_string());
-        } else if !email().contains('@') {
-            errors.push("Please enter a valid email address".to_string());
-        }
-        if subject().trim().is_empty() {
-            errors.push("Subject is required".to_string());
-        }
-        if message().trim().is_empty() {
-            errors.push("Message is required".to_string());
-        }
+    // Sends valid submissions to the server, backing off when it keeps failing
+    let mut delivery = use_contact_delivery();
+
+    // Form submission handler
+    let mut handle_submit = move || {
+        let errors = request().validate();
 
         if !errors.is_empty() {
             validation_errors.set(errors);
@@ -488,42 +402,27 @@ pub fn ContactFormOnly() -> Element {
             return;
         }
 
-        // Simulate form processing
-        let form_data = FormData {
-            name: name().clone(),
-            email: email().clone(),
-            subject: subject().clone(),
-            message: message().clone(),
-            submitted_at: chrono::Utc::now()
-                .format("%Y-%m-%d %H:%M:%S UTC")
-                .to_string(),
-        };
-
-        // Save to localStorage (browser-only feature)
-        #[cfg(feature = "web")]
-        {
-            if let Ok(json) = serde_json::to_string(&form_data) {
-                let window = web_sys::window().unwrap();
-                let storage = window.local_storage().unwrap().unwrap();
-                let _ = storage.set_item("last_contact_submission", &json);
-            }
-        }
+        // Kept in the browser's history once the server has taken it; the page moves on to the redirect right after
+        let submission = Submission::new(&request());
+        let sent = request();
+        delivery.send(sent.clone(), form.attachments(), move || {
+            history.set(contact_history::record(submission));
+            form.mark_submitted(sent);
+        });
 
         validation_errors.set(Vec::new());
-        form_state.set(FormState::Submitted(form_data));
+        form_state.set(FormState::Editing);
     };
 
     let reset_form = move |_| {
-        name.set(String::new());
-        email.set(String::new());
-        subject.set(String::new());
-        message.set(String::new());
+        form.clear();
         validation_errors.set(Vec::new());
         form_state.set(FormState::Editing);
+        delivery.reset();
     };
 
     rsx! {
-        document::Link { rel: "stylesheet", href: CONTACT_CSS }
+        ScopedStylesheet { style: &styles::CONTACT }
 
         div {
             class: "js-functionality-notice",
@@ -569,74 +468,17 @@ pub fn ContactFormOnly() -> Element {
             }
         }
 
-        // Show submission result
-        match form_state() {
-            FormState::Submitted(data) => rsx! {
-                div {
-                    class: "submission-result",
-                    h3 { "✅ Form Submitted Successfully!" }
-                    div {
-                        class: "submitted-data",
-                        h4 { "Submitted Data:" }
-                        div { class: "data-item",
-                            strong { "Name: " }
-                            span { "{data.name}" }
-                        }
-                        div { class: "data-item",
-                            strong { "Email: " }
-                            span { "{data.email}" }
-                        }
-                        div { class: "data-item",
-                            strong { "Subject: " }
-                            span { "{data.subject}" }
-                        }
-                        div { class: "data-item",
-                            strong { "Message: " }
-                            span { "{data.message}" }
-                        }
-                        div { class: "data-item",
-                            strong { "Submitted: " }
-                            span { "{data.submitted_at}" }
-                        }
-                    }
-
-                    div {
-                        class: "demo-features",
-                        h4 { "🎯 WASM Features Successfully Loaded:" }
-                        ul {
-                            li { "✅ Rust form validation compiled to WebAssembly (~800KB .wasm file)" }
-                            li { "✅ Real-time reactive state management in WASM" }
-                            li { "✅ Interactive form submission processed in WASM" }
-                            li { "✅ JSON serialization/deserialization in WASM" }
-                            li { "✅ Browser localStorage integration via WASM" }
-                            li { "✅ Conditional rendering powered by WASM" }
-                            li { "✅ Zero JavaScript - all logic runs in WebAssembly!" }
-                        }
-
-                        div {
-                            class: "wasm-info",
-                            p {
-                                "🦀 This page loaded a full Rust/WASM binary to handle the form, "
-                                "demonstrating how you can add interactive functionality to specific pages "
-                                "while keeping others as pure static HTML."
-                            }
-                        }
-                    }
-
-                    button {
-                        class: "btn btn-secondary",
-                        onclick: reset_form,
-                        "Send Another Message"
-                    }
-                }
-            },
-            _ => rsx! {
-                // Contact Form
+        // Contact Form
+        // The static form this island replaces goes to the mail app; here submissions are sent as JSON instead, so
+        // failures can be retried and the email fallback offered
                 form {
                     class: "contact-form",
+                    method: "post",
+                    enctype: "text/plain",
+                    action: fallback_form_action(),
                     onsubmit: move |e| {
                         e.prevent_default();
-                        handle_submit(());
+                        handle_submit();
                     },
 
                     // Show validation errors
@@ -652,67 +494,25 @@ pub fn ContactFormOnly() -> Element {
                         }
                     }
 
-                    div {
-                        class: "form-row",
-                        div {
-                            class: "form-group",
-                            label { "for": "name", "Name *" }
-                            input {
-                                r#type: "text",
-                                id: "name",
-                                class: "form-input",
-                                placeholder: "Your full name",
-                                value: "{name}",
-                                oninput: move |e| name.set(e.value()),
-                            }
-                        }
-                        div {
-                            class: "form-group",
-                            label { "for": "email", "Email *" }
-                            input {
-                                r#type: "email",
-                                id: "email",
-                                class: "form-input",
-                                placeholder: "your.email@example.com",
-                                value: "{email}",
-                                oninput: move |e| email.set(e.value()),
-                            }
-                        }
-                    }
-
-                    div {
-                        class: "form-group",
-                        label { "for": "subject", "Subject *" }
-                        input {
-                            r#type: "text",
-                            id: "subject",
-                            class: "form-input",
-                            placeholder: "What's this about?",
-                            value: "{subject}",
-                            oninput: move |e| subject.set(e.value()),
-                        }
-                    }
+                    ContactFields { form, show_errors: !validation_errors().is_empty() }
 
-                    div {
-                        class: "form-group",
-                        label { "for": "message", "Message *" }
-                        textarea {
-                            id: "message",
-                            class: "form-textarea",
-                            placeholder: "Tell me what's on your mind...",
-                            rows: "6",
-                            value: "{message}",
-                            oninput: move |e| message.set(e.value()),
-                        }
-                    }
+                    DeliveryNotice { status: delivery.status(), request: request() }
 
                     div {
                         class: "form-actions",
-                        button {
-                            r#type: "submit",
-                            class: "btn btn-primary",
-                            disabled: !is_valid(),
-                            "Send Message ✨"
+                        if delivery.status() == DeliveryStatus::Unavailable {
+                            a {
+                                class: "btn btn-primary",
+                                href: contact_delivery::mailto(ABOUT.contact.email, &request()),
+                                "Send by Email ✉️"
+                            }
+                        } else {
+                            button {
+                                r#type: "submit",
+                                class: "btn btn-primary",
+                                disabled: !is_valid() || delivery.is_busy(),
+                                "Send Message ✨"
+                            }
                         }
                         button {
                             r#type: "button",
@@ -722,7 +522,119 @@ pub fn ContactFormOnly() -> Element {
                         }
                     }
                 }
+
+        PreviousMessages { history }
+    }
+}
+
+/// Progress and problems of the submission being sent, and the email fallback once the form has stopped trying.
+#[component]
+pub(super) fn DeliveryNotice(status: DeliveryStatus, request: ContactRequest) -> Element {
+    match status {
+        DeliveryStatus::Idle => rsx! {},
+        DeliveryStatus::Sending { attempt } => rsx! {
+            p {
+                class: "delivery-notice",
+                role: "status",
+                if attempt == 1 {
+                    "Sending your message…"
+                } else {
+                    "Sending your message (attempt {attempt} of {MAX_ATTEMPTS})…"
+                }
+            }
+        },
+        DeliveryStatus::Retrying { seconds, .. } => rsx! {
+            p {
+                class: "delivery-notice",
+                role: "status",
+                "Couldn't reach the server. Trying again in {seconds}s…"
+            }
+        },
+        DeliveryStatus::Accepted { .. } => rsx! {
+            p { class: "delivery-notice delivery-success", role: "status", "✅ Message sent!" }
+        },
+        DeliveryStatus::Rejected(problems) => rsx! {
+            div {
+                class: "validation-errors",
+                role: "alert",
+                h4 { "The server couldn't accept your message:" }
+                ul {
+                    for problem in problems {
+                        li { "{problem}" }
+                    }
+                }
+            }
+        },
+        DeliveryStatus::Failed => rsx! {
+            p {
+                class: "delivery-notice delivery-error",
+                role: "alert",
+                "Your message couldn't be sent. Please try again in a moment."
+            }
+        },
+        DeliveryStatus::Unavailable => rsx! {
+            div {
+                class: "delivery-notice delivery-error",
+                role: "alert",
+                p { "The contact form isn't working right now, so it has stopped trying for a while." }
+                p {
+                    "Please send your message by email to "
+                    a {
+                        href: contact_delivery::mailto(ABOUT.contact.email, &request),
+                        "{ABOUT.contact.email}"
+                    }
+                    " instead; the button below opens it with your message filled in."
+                }
+            }
+        },
+    }
+}
+
+/// Collapsible list of the submissions made from this browser, newest first, with a button to clear them. Renders
+/// nothing while the history is empty.
+#[component]
+fn PreviousMessages(history: Signal<Vec<Submission>>) -> Element {
+    let count = history.read().len();
+    if count == 0 {
+        return rsx! {};
+    }
+
+    rsx! {
+        details {
+            class: "previous-messages",
+            summary { "Previous messages ({count})" }
+            ul {
+                for (index, entry) in history().into_iter().enumerate() {
+                    li {
+                        key: "{index}-{entry.submitted_at}",
+                        class: "previous-message",
+                        div {
+                            class: "previous-message-heading",
+                            strong { "{entry.subject}" }
+                            span { class: "previous-message-date", "{entry.submitted_at}" }
+                        }
+                        p {
+                            class: "previous-message-meta",
+                            "{reason_label(&entry.reason)} · {entry.name} ({entry.email})"
+                        }
+                        p { class: "previous-message-body", "{entry.message}" }
+                    }
+                }
+            }
+            button {
+                r#type: "button",
+                class: "btn btn-secondary",
+                onclick: move |_| {
+                    contact_history::clear();
+                    history.set(Vec::new());
+                },
+                "Clear history"
             }
         }
     }
 }
+
+/// Label of a submitted reason id, falling back to the id for reasons that have since been removed
+fn reason_label(id: &str) -> &str {
+    contact_reasons::reason(id).map_or(id, |reason| reason.label)
+}
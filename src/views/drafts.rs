@@ -0,0 +1,124 @@
+use crate::components::{Heading, PostBody};
+use crate::posts::{self, Status};
+use crate::styles::{self, ScopedStylesheet};
+use crate::Route;
+use dioxus::prelude::*;
+
+/// Draft previews are part of development builds (`dx serve`) only; release builds show a notice instead.
+pub const DRAFTS_ENABLED: bool = cfg!(debug_assertions);
+
+/// The drafts list rendered for `[Route::Drafts]`: every post not published yet, soonest first.
+///
+/// Posts are compiled into the app, so under `dx serve` an edit to `src/posts.rs` rebuilds and reloads this page,
+/// and a post can be previewed without running the static generator.
+#[component]
+pub fn Drafts() -> Element {
+    if !DRAFTS_ENABLED {
+        return rsx! { DraftsUnavailable {} };
+    }
+
+    let today = posts::today();
+    let drafts = posts::drafts(today);
+
+    rsx! {
+        ScopedStylesheet { style: &styles::BLOG }
+
+        div {
+            id: "blog",
+            class: styles::BLOG.scope,
+            Heading { level: 1, "Drafts" }
+            if drafts.is_empty() {
+                p { "No drafts. Posts dated after today show up here until they're published." }
+            }
+            ul {
+                class: "blog-index",
+                for post in drafts {
+                    li {
+                        key: "{post.slug}",
+                        class: "blog-index-entry",
+                        Heading {
+                            level: 2,
+                            Link {
+                                to: Route::DraftPreview { slug: post.slug.to_string() },
+                                "{post.title}"
+                            }
+                        }
+                        p {
+                            class: "blog-date",
+                            span { class: "blog-badge", "{draft_badge(post.status(today))}" }
+                            " Publishes {post.date_label()}"
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A draft rendered like the post page it will become, for `[Route::DraftPreview]`.
+#[component]
+pub fn DraftPreview(slug: String) -> Element {
+    if !DRAFTS_ENABLED {
+        return rsx! { DraftsUnavailable {} };
+    }
+
+    let today = posts::today();
+    let Some(post) = posts::find(&slug) else {
+        return rsx! {
+            ScopedStylesheet { style: &styles::BLOG }
+            div {
+                id: "blog",
+                class: styles::BLOG.scope,
+                Heading { level: 1, "Draft not found" }
+                p { "There is no post with the slug \"{slug}\"." }
+                Link { to: Route::Drafts {}, "← All drafts" }
+            }
+        };
+    };
+
+    rsx! {
+        ScopedStylesheet { style: &styles::BLOG }
+
+        div {
+            id: "blog",
+            class: styles::BLOG.scope,
+            p {
+                class: "blog-date",
+                if post.status(today) == Status::Published {
+                    span { class: "blog-badge", "Published" }
+                    " This post is already out; readers see it at "
+                    Link { to: Route::Blog { slug: post.slug.to_string() }, "{post.path()}" }
+                } else {
+                    span { class: "blog-badge", "{draft_badge(post.status(today))}" }
+                    " Preview of a post publishing {post.date_label()}"
+                }
+            }
+            Heading { level: 1, "{post.title}" }
+            p { class: "blog-date", "{post.date_label()}" }
+            PostBody { body: post.body }
+            Link { to: Route::Drafts {}, "← All drafts" }
+        }
+    }
+}
+
+#[component]
+fn DraftsUnavailable() -> Element {
+    rsx! {
+        ScopedStylesheet { style: &styles::BLOG }
+        div {
+            id: "blog",
+            class: styles::BLOG.scope,
+            Heading { level: 1, "Drafts" }
+            p { "Draft previews are only available in development builds." }
+            Link { to: Route::BlogIndex {}, "← All posts" }
+        }
+    }
+}
+
+/// How readers see an unpublished post until its date.
+fn draft_badge(status: Status) -> &'static str {
+    match status {
+        Status::ComingSoon => "Teaser",
+        _ => "Draft",
+    }
+}
@@ -1,4 +1,4 @@
-use crate::components::{Echo, Hero};
+use crate::components::{Echo, Hero, OnThisDay};
 use dioxus::prelude::*;
 
 /// The Home page component that will be rendered when the current route is `[Route::Home]`
@@ -6,6 +6,7 @@ use dioxus::prelude::*;
 pub fn Home() -> Element {
     rsx! {
         Hero {}
+        OnThisDay {}
         Echo {}
     }
 }
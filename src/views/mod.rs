@@ -12,7 +12,7 @@ mod home;
 pub use home::Home;
 
 mod blog;
-pub use blog::Blog;
+pub use blog::{Blog, BlogIndex};
 
 mod navbar;
 pub use navbar::Navbar;
@@ -21,4 +21,21 @@ mod about;
 pub use about::About;
 
 mod contact;
-pub use contact::{Contact, ContactFormOnly};
+pub use contact::{
+    fallback_form_action, Contact, ContactFormOnly, CONTACT_ENDPOINT, CONTACT_THANKS_PATH,
+};
+
+mod speak;
+pub use speak::{Speak, SpeakForm};
+
+mod saved;
+pub use saved::Saved;
+
+mod drafts;
+pub use drafts::{DraftPreview, Drafts, DRAFTS_ENABLED};
+
+mod components_preview;
+pub use components_preview::{Components, Preview, Variant, PREVIEWS, PREVIEWS_ENABLED};
+
+mod projects;
+pub use projects::{ProjectCard, Projects, FILTER_TIP};
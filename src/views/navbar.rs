@@ -1,5 +1,13 @@
+use crate::components::{
+    ConnectivityStatus, Hints, MobileNav, NavLink, NavSearch, HAS_MOBILE_NAV_CLASS,
+};
+use crate::links;
+use crate::styles::Stylesheet;
+use crate::view_transitions;
 use crate::Route;
 use dioxus::prelude::*;
+use std::cell::Cell;
+use std::rc::Rc;
 
 const NAVBAR_CSS: Asset = asset!("/assets/styling/navbar.css");
 
@@ -8,29 +16,51 @@ const NAVBAR_CSS: Asset = asset!("/assets/styling/navbar.css");
 ///
 /// This layout component wraps the UI of [Route::Home] and [Route::Blog] in a common navbar. The contents of the Home and Blog
 /// routes will be rendered under the outlet inside this component
+///
+/// It also keeps the document title in step with the route and, after each client-side navigation, puts the new page's
+/// name in a polite live region so screen readers announce it the way they would a full page load. On small screens the
+/// links move into a [`MobileNav`] drawer, and a [`ConnectivityStatus`] banner appears above them while offline. Wide
+/// screens get a [`NavSearch`] box after the links.
+/// First-visit [`Hints`] show in a corner, one at a time.
+/// Navigations from a link run as view transitions where the browser supports them.
 #[component]
 pub fn Navbar() -> Element {
+    let route = use_route::<Route>();
+    let meta = route.page_meta();
+    view_transitions::use_view_transitions();
+
+    let mut announcement = use_signal(String::new);
+    // The first page is announced by the browser itself; only later navigations need the live region
+    let initial_render = use_hook(|| Rc::new(Cell::new(true)));
+    use_effect(use_reactive!(|route| {
+        if initial_render.replace(false) {
+            return;
+        }
+        announcement.set(route.page_meta().name);
+    }));
+
     rsx! {
-        document::Link { rel: "stylesheet", href: NAVBAR_CSS }
+        document::Title { "{meta.title}" }
+        document::Meta { name: "description", content: "{meta.description}" }
+        Stylesheet { href: NAVBAR_CSS }
+
+        div {
+            class: "route-announcer",
+            role: "status",
+            aria_live: "polite",
+            aria_atomic: "true",
+            "{announcement}"
+        }
+
+        ConnectivityStatus {}
+        Hints {}
 
         div {
             id: "navbar",
-            Link {
-                to: Route::Home {},
-                "Home"
-            }
-            Link {
-                to: Route::About {},
-                "About"
-            }
-            Link {
-                to: Route::Contact {},
-                "Contact"
-            }
-            Link {
-                to: Route::Blog { id: 1 },
-                "Blog"
-            }
+            class: HAS_MOBILE_NAV_CLASS,
+            {nav_links()}
+            NavSearch {}
+            MobileNav { {nav_links()} }
         }
 
         // The `Outlet` component is used to render the next component inside the layout. In this case, it will render either
@@ -38,3 +68,20 @@ pub fn Navbar() -> Element {
         Outlet::<Route> {}
     }
 }
+
+/// The navigation links, shown in the navbar row on wide screens and in the [`MobileNav`] drawer on small ones.
+fn nav_links() -> Element {
+    rsx! {
+        for (route, label) in links::nav_routes() {
+            NavLink {
+                key: "{route}",
+                to: route,
+                "{label}"
+            }
+        }
+        NavLink {
+            to: Route::Saved {},
+            "Saved"
+        }
+    }
+}
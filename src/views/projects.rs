@@ -0,0 +1,213 @@
+use crate::components::{Heading, Tip};
+use crate::contact_prefill;
+use crate::data::{Project, PROJECTS};
+use crate::styles::{self, ScopedStylesheet};
+use crate::tags::{fold, TagsConfig};
+use dioxus::prelude::*;
+
+/// Query string parameter holding the selected technologies, comma separated: `/projects?tags=Rust,Dioxus`
+#[cfg(feature = "web")]
+const TAGS_PARAM: &str = "tags";
+
+/// Tip above the projects, also on the generated page the view is mounted over.
+pub const FILTER_TIP: &str =
+    "Pick one or more technologies to list only the projects that use all of them. The page \
+    address keeps the selection, so a filtered list can be shared.";
+
+/// The Projects page component that will be rendered when the current route is `[Route::Projects]`
+///
+/// Projects can be filtered by technology; only projects using every selected tag are shown. The selection is kept in
+/// the `tags` query parameter so filtered views can be shared. Like [`Saved`](super::Saved), this view is also mounted
+/// as an island over the statically generated grid, so it reads and writes the URL directly instead of going through
+/// the router.
+#[component]
+pub fn Projects() -> Element {
+    let all_tags = use_hook(|| {
+        let mut tags: Vec<String> = PROJECTS
+            .iter()
+            .flat_map(|p| p.tags.iter().map(|tag| tag.to_string()))
+            .collect();
+        tags.sort_by_key(|tag| tag.to_lowercase());
+        tags.dedup();
+        tags
+    });
+    let mut selected = use_signal(|| {
+        // Match the tags the way they were normalized, and drop those no project uses, so a stale or hand-written
+        // link doesn't hide everything
+        let aliases = TagsConfig::compiled();
+        selected_tags_from_url()
+            .into_iter()
+            .filter_map(|tag| {
+                let tag = fold(aliases.alias(&tag).unwrap_or(&tag));
+                all_tags.iter().find(|known| fold(known) == tag).cloned()
+            })
+            .collect::<Vec<_>>()
+    });
+
+    let visible: Vec<&Project> = PROJECTS
+        .iter()
+        .filter(|project| {
+            selected
+                .read()
+                .iter()
+                .all(|tag| project.tags.contains(&tag.as_str()))
+        })
+        .collect();
+
+    rsx! {
+        ScopedStylesheet { style: &styles::PROJECTS }
+
+        div {
+            id: "projects",
+            class: styles::PROJECTS.scope,
+            Heading { level: 1, "Projects" }
+            Tip { "{FILTER_TIP}" }
+
+            div {
+                class: "project-filter",
+                role: "group",
+                aria_label: "Filter projects by technology",
+                for tag in all_tags.iter().cloned() {
+                    button {
+                        key: "{tag}",
+                        r#type: "button",
+                        aria_pressed: "{selected.read().contains(&tag)}",
+                        onclick: move |_| {
+                            let mut tags = selected();
+                            if let Some(index) = tags.iter().position(|t| *t == tag) {
+                                tags.remove(index);
+                            } else {
+                                tags.push(tag.clone());
+                            }
+                            write_selected_tags_to_url(&tags);
+                            selected.set(tags);
+                        },
+                        "{tag}"
+                    }
+                }
+                if !selected.read().is_empty() {
+                    button {
+                        r#type: "button",
+                        class: "project-filter-clear",
+                        onclick: move |_| {
+                            write_selected_tags_to_url(&[]);
+                            selected.set(Vec::new());
+                        },
+                        "Clear filter"
+                    }
+                }
+            }
+
+            if visible.is_empty() {
+                p {
+                    class: "projects-empty",
+                    "No projects use all of the selected technologies."
+                }
+            }
+
+            ul {
+                class: "project-grid",
+                for project in visible {
+                    ProjectCard { key: "{project.name}", project }
+                }
+            }
+        }
+    }
+}
+
+/// One project in the grid: its name linking to it, the description, the technologies and a link asking about it.
+/// Goes in a `.project-grid` list inside the projects stylesheet's scope.
+#[component]
+pub fn ProjectCard(project: &'static Project) -> Element {
+    rsx! {
+        li {
+            class: "project-card",
+            Heading {
+                level: 2,
+                a { href: "{project.url}", "{project.name}" }
+            }
+            p { "{project.description}" }
+            ul {
+                class: "project-tags",
+                for tag in project.tags.iter() {
+                    li { "{tag}" }
+                }
+            }
+            a {
+                class: "project-ask",
+                href: contact_prefill::ask_about_project(project.name),
+                "Ask about this project"
+            }
+        }
+    }
+}
+
+#[cfg(feature = "web")]
+/// Parse the selected tags from a query string such as `?tags=Rust,Static%20Site%20Generation`.
+fn tags_from_query(search: &str) -> Vec<String> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .find_map(|pair| pair.strip_prefix(TAGS_PARAM)?.strip_prefix('='))
+        .map(|value| {
+            value
+                .split(',')
+                .filter_map(|tag| {
+                    let tag = js_sys::decode_uri_component(&tag.replace('+', " ")).ok()?;
+                    let tag = String::from(tag);
+                    (!tag.is_empty()).then_some(tag)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn selected_tags_from_url() -> Vec<String> {
+    #[cfg(feature = "web")]
+    {
+        if let Some(search) = web_sys::window().and_then(|window| window.location().search().ok()) {
+            return tags_from_query(&search);
+        }
+    }
+
+    Vec::new()
+}
+
+/// Replace the current history entry so the address bar reflects the filter without adding a back-button step per
+/// click. Other query parameters are kept.
+#[cfg_attr(not(feature = "web"), allow(unused_variables))]
+fn write_selected_tags_to_url(tags: &[String]) {
+    #[cfg(feature = "web")]
+    {
+        let Some(window) = web_sys::window() else {
+            return;
+        };
+        let location = window.location();
+        let (Ok(pathname), Ok(search), Ok(history)) =
+            (location.pathname(), location.search(), window.history())
+        else {
+            return;
+        };
+
+        let mut params: Vec<String> = search
+            .trim_start_matches('?')
+            .split('&')
+            .filter(|pair| !pair.is_empty() && pair.split('=').next() != Some(TAGS_PARAM))
+            .map(str::to_string)
+            .collect();
+        if !tags.is_empty() {
+            let encoded: Vec<String> = tags
+                .iter()
+                .map(|tag| String::from(js_sys::encode_uri_component(tag)))
+                .collect();
+            params.push(format!("{}={}", TAGS_PARAM, encoded.join(",")));
+        }
+
+        let url = if params.is_empty() {
+            pathname
+        } else {
+            format!("{}?{}", pathname, params.join("&"))
+        };
+        let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+    }
+}
@@ -0,0 +1,61 @@
+use crate::components::Heading;
+use crate::global_state::{use_global_state, StateChange};
+use crate::styles::{self, ScopedStylesheet};
+use crate::{links, posts, saved_posts, Route};
+use dioxus::prelude::*;
+
+/// The Saved page component that will be rendered when the current route is `[Route::Saved]`
+///
+/// Lists the posts saved with the [`SaveButton`](crate::components::SaveButton). Links are plain anchors rather than
+/// router `Link`s because this view is also mounted as an island on the static `/saved` page, where there is no router.
+/// The list follows the [`GlobalState`](crate::global_state::GlobalState), so it updates when a save button changes.
+#[component]
+pub fn Saved() -> Element {
+    let mut state = use_global_state();
+    let saved = state.saved_posts();
+
+    rsx! {
+        ScopedStylesheet { style: &styles::SAVED }
+
+        div {
+            id: "saved",
+            class: styles::SAVED.scope,
+            Heading { level: 1, "Saved Posts" }
+
+            if saved.read().is_empty() {
+                p {
+                    class: "saved-empty",
+                    "Nothing saved yet. Use the ☆ Save for later button on a blog post to add it here."
+                }
+            } else {
+                ul {
+                    class: "saved-list",
+                    {saved().into_iter().map(|post| {
+                        let remove_post = post.clone();
+                        // Posts saved before slugs were introduced are stored by number
+                        let found = posts::find(&post).or_else(|| posts::find_legacy(&post));
+                        let (slug, title) = match found {
+                            Some(found) => (found.slug.to_string(), found.title.to_string()),
+                            None => (post.clone(), post.clone()),
+                        };
+                        rsx! {
+                            li {
+                                key: "{post}",
+                                a { href: links::link_to(Route::Blog { slug }), "{title}" }
+                                button {
+                                    r#type: "button",
+                                    class: "saved-remove",
+                                    onclick: move |_| {
+                                        saved_posts::remove(&remove_post);
+                                        state.publish(StateChange::SavedPosts(saved_posts::load()));
+                                    },
+                                    "Remove"
+                                }
+                            }
+                        }
+                    })}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use super::contact::DeliveryNotice;
+use crate::api::ValidationProblem;
+use crate::components::{use_contact_form, ContactFields};
+use crate::contact_delivery::{self, use_contact_delivery, DeliveryStatus};
+use crate::contact_form::FormSchema;
+use crate::data::ABOUT;
+use crate::styles::{self, ScopedStylesheet};
+use crate::views::fallback_form_action;
+use dioxus::prelude::*;
+
+/// Id of the speaking inquiry form in `forms/speak.toml`.
+const SPEAK_FORM: &str = "speak";
+
+/// The Speak page: what to include in an invitation to speak, and the inquiry form.
+#[component]
+pub fn Speak() -> Element {
+    rsx! {
+        ScopedStylesheet { style: &styles::CONTACT }
+
+        div {
+            class: "{styles::CONTACT.scope} contact-container",
+
+            header {
+                class: "contact-header",
+                h1 { class: "contact-title", "Speaking & Events" }
+                p {
+                    class: "contact-subtitle",
+                    "I talk about Rust, WebAssembly and building sites with Dioxus. Tell me about your event!"
+                }
+            }
+
+            div {
+                class: "contact-content",
+
+                div {
+                    class: "contact-info",
+                    h2 { "What Helps" }
+                    ul {
+                        li { "The date and where the event takes place, or that it's online" }
+                        li { "Who comes, and roughly how many" }
+                        li { "Whether travel is covered or there's a speaker fee" }
+                        li { "The topics you have in mind" }
+                    }
+                    p {
+                        "For anything else, use the "
+                        Link { to: crate::Route::Contact {}, class: "contact-link", "contact form" }
+                        "."
+                    }
+                }
+
+                div {
+                    class: "contact-form-section",
+                    h2 { "Send an Inquiry" }
+                    SpeakForm {}
+                }
+            }
+        }
+    }
+}
+
+/// SpeakForm - the speaking inquiry form from `forms/speak.toml` without the surrounding layout. The Speak page
+/// renders it, and the hybrid build mounts it over the plain form in the static page.
+#[component]
+pub fn SpeakForm() -> Element {
+    let schema = FormSchema::named(SPEAK_FORM).expect("forms/speak.toml declares the speak form");
+    let mut form = use_contact_form(schema);
+    let mut validation_errors = use_signal(Vec::<ValidationProblem>::new);
+
+    // The submission as the server will receive it, validated with the same rules
+    let request = use_memo(move || form.request());
+    let is_valid = use_memo(move || request().validate().is_empty());
+
+    // Sent like the contact form; the server routes it by its form to [contact.forms.speak]
+    let mut delivery = use_contact_delivery();
+
+    let mut handle_submit = move || {
+        let errors = request().validate();
+        if !errors.is_empty() {
+            validation_errors.set(errors);
+            return;
+        }
+        let sent = request();
+        delivery.send(sent.clone(), form.attachments(), move || {
+            form.mark_submitted(sent)
+        });
+        validation_errors.set(Vec::new());
+    };
+
+    let reset_form = move |_| {
+        form.clear();
+        validation_errors.set(Vec::new());
+        delivery.reset();
+    };
+
+    rsx! {
+        ScopedStylesheet { style: &styles::CONTACT }
+
+        form {
+            class: "contact-form",
+            method: "post",
+            enctype: "text/plain",
+            action: fallback_form_action(),
+            onsubmit: move |e| {
+                e.prevent_default();
+                handle_submit();
+            },
+
+            if !validation_errors().is_empty() {
+                div {
+                    class: "validation-errors",
+                    h4 { "Please fix the following errors:" }
+                    ul {
+                        for error in validation_errors() {
+                            li { "{error}" }
+                        }
+                    }
+                }
+            }
+
+            ContactFields { form, show_errors: !validation_errors().is_empty() }
+
+            DeliveryNotice { status: delivery.status(), request: request() }
+
+            div {
+                class: "form-actions",
+                if delivery.status() == DeliveryStatus::Unavailable {
+                    a {
+                        class: "btn btn-primary",
+                        href: contact_delivery::mailto(ABOUT.contact.email, &request()),
+                        "Send by Email ✉️"
+                    }
+                } else {
+                    button {
+                        r#type: "submit",
+                        class: "btn btn-primary",
+                        disabled: !is_valid() || delivery.is_busy(),
+                        "Send Inquiry 🎤"
+                    }
+                }
+                button {
+                    r#type: "button",
+                    class: "btn btn-secondary",
+                    onclick: reset_form,
+                    "Reset Form"
+                }
+            }
+
+            div {
+                class: "form-note",
+                p { "* Required fields" }
+            }
+        }
+    }
+}